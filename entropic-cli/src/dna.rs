@@ -0,0 +1,277 @@
+//! `entropic dna ...` commands
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::Subcommand;
+use serde::Serialize;
+
+use entropic_dna_core::{
+    diff::{ChangeKind, DnaDiff, FieldChange},
+    validation::{checksum, ValidationEngine, ValidationResult},
+    version::MigrationManager,
+};
+
+use crate::error::CliError;
+use crate::files::{encode_dna, read_dna, write_bytes, DnaFormat};
+use crate::output::{print_json, OutputFormat};
+use crate::Result;
+
+#[derive(Subcommand, Debug)]
+pub enum DnaCommand {
+    /// Validate documents; exits with 1 when any is invalid
+    Validate {
+        /// Documents to validate (`-` for stdin)
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+
+    /// Convert a document between JSON and binary
+    Convert {
+        /// Document to convert (`-` for stdin)
+        input: PathBuf,
+        /// Encoding to write
+        #[arg(long, value_enum, default_value_t = DnaFormat::Pretty)]
+        to: DnaFormat,
+        /// Where to write the result
+        #[arg(long, short, default_value = "-")]
+        output: PathBuf,
+    },
+
+    /// Migrate a document to another schema version
+    ///
+    /// Without --output or --in-place only the report is printed.
+    Migrate {
+        /// Document to migrate
+        file: PathBuf,
+        /// Schema version to migrate to
+        #[arg(long)]
+        to: String,
+        /// Where to write the migrated document
+        #[arg(long, short, conflicts_with = "in_place")]
+        output: Option<PathBuf>,
+        /// Overwrite the input with the migrated document
+        #[arg(long)]
+        in_place: bool,
+    },
+
+    /// Show the changes between two documents; exits with 1 when they differ
+    Diff {
+        /// Original document
+        old: PathBuf,
+        /// Changed document
+        new: PathBuf,
+    },
+
+    /// Print document checksums
+    Checksum {
+        /// Documents to checksum (`-` for stdin)
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+}
+
+pub fn run(command: DnaCommand, format: OutputFormat) -> Result {
+    match command {
+        DnaCommand::Validate { files } => validate(&files, format),
+        DnaCommand::Convert { input, to, output } => {
+            let dna = read_dna(&input)?;
+            write_bytes(&output, &encode_dna(&dna, to)?)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        DnaCommand::Migrate {
+            file,
+            to,
+            output,
+            in_place,
+        } => {
+            let destination = if in_place { Some(file.clone()) } else { output };
+            migrate(&file, &to, destination.as_deref(), format)
+        }
+        DnaCommand::Diff { old, new } => diff(&old, &new, format),
+        DnaCommand::Checksum { files } => checksums(&files, format),
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct FileValidation {
+    file: String,
+    #[serde(flatten)]
+    result: ValidationResult,
+}
+
+fn validate(files: &[PathBuf], format: OutputFormat) -> Result {
+    let engine = ValidationEngine::new();
+    let mut reports = Vec::with_capacity(files.len());
+    for file in files {
+        let dna = read_dna(file)?;
+        reports.push(FileValidation {
+            file: file.display().to_string(),
+            result: engine.validate(&dna),
+        });
+    }
+
+    match format {
+        OutputFormat::Json => print_json(&reports)?,
+        OutputFormat::Text => {
+            for report in &reports {
+                print!("{}", validation_text(&report.file, &report.result));
+            }
+        }
+    }
+
+    let all_valid = reports.iter().all(|r| r.result.is_valid);
+    Ok(if all_valid { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+fn validation_text(file: &str, result: &ValidationResult) -> String {
+    let mut text = format!(
+        "{file}: {} ({} errors, {} warnings)\n",
+        if result.is_valid { "valid" } else { "invalid" },
+        result.errors.len(),
+        result.warnings.len()
+    );
+    for e in &result.errors {
+        text.push_str(&format!("  error [{}] {}: {}\n", e.code, e.field, e.message));
+    }
+    for w in &result.warnings {
+        text.push_str(&format!("  warning [{}] {}: {}\n", w.code, w.field, w.message));
+    }
+    text
+}
+
+#[derive(Debug, Serialize)]
+struct MigrationReport {
+    from_version: String,
+    to_version: String,
+    steps: Vec<String>,
+    changes: Vec<FieldChange>,
+    written_to: Option<String>,
+}
+
+fn migrate(
+    file: &Path,
+    target: &str,
+    destination: Option<&Path>,
+    format: OutputFormat,
+) -> Result {
+    let original = read_dna(file)?;
+    let manager = MigrationManager::with_builtin_migrations();
+    let from_version = original.version.to_string();
+    let steps = manager
+        .find_migration_path(&from_version, target)
+        .map(|path| {
+            path.iter()
+                .map(|m| format!("{} -> {}", m.from_version(), m.to_version()))
+                .collect()
+        })
+        .unwrap_or_default();
+    let migrated = manager
+        .migrate(original.clone(), target)
+        .map_err(CliError::failed)?;
+
+    if let Some(destination) = destination {
+        write_bytes(destination, &encode_dna(&migrated, DnaFormat::Pretty)?)?;
+    }
+
+    let report = MigrationReport {
+        from_version,
+        to_version: migrated.version.to_string(),
+        steps,
+        changes: DnaDiff::between(&original, &migrated).changes,
+        written_to: destination.map(|d| d.display().to_string()),
+    };
+    match format {
+        OutputFormat::Json => print_json(&report)?,
+        OutputFormat::Text => {
+            println!("{} -> {}", report.from_version, report.to_version);
+            for step in &report.steps {
+                println!("  step {step}");
+            }
+            print!("{}", diff_text(&report.changes));
+            match &report.written_to {
+                Some(path) => println!("wrote {path}"),
+                None => println!("dry run: nothing written"),
+            }
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn diff(old: &Path, new: &Path, format: OutputFormat) -> Result {
+    let diff = DnaDiff::between(&read_dna(old)?, &read_dna(new)?);
+    match format {
+        OutputFormat::Json => print_json(&diff)?,
+        OutputFormat::Text => print!("{}", diff_text(&diff.changes)),
+    }
+    Ok(if diff.is_empty() { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+/// One line per change: `+ path: after`, `- path: before` or `~ path: before -> after`
+fn diff_text(changes: &[FieldChange]) -> String {
+    let show = |value: &Option<serde_json::Value>| {
+        value.as_ref().map_or_else(String::new, |v| v.to_string())
+    };
+    changes
+        .iter()
+        .map(|c| match c.kind {
+            ChangeKind::Added => format!("+ {}: {}\n", c.path, show(&c.after)),
+            ChangeKind::Removed => format!("- {}: {}\n", c.path, show(&c.before)),
+            ChangeKind::Modified => {
+                format!("~ {}: {} -> {}\n", c.path, show(&c.before), show(&c.after))
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize)]
+struct FileChecksum {
+    file: String,
+    checksum: String,
+}
+
+fn checksums(files: &[PathBuf], format: OutputFormat) -> Result {
+    let mut sums = Vec::with_capacity(files.len());
+    for file in files {
+        sums.push(FileChecksum {
+            file: file.display().to_string(),
+            checksum: checksum::generate_checksum(&read_dna(file)?),
+        });
+    }
+
+    match format {
+        OutputFormat::Json => print_json(&sums)?,
+        OutputFormat::Text => {
+            // Same layout as sha256sum
+            for sum in &sums {
+                println!("{}  {}", sum.checksum, sum.file);
+            }
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_text() {
+        let changes = vec![
+            FieldChange {
+                path: "genre".to_string(),
+                kind: ChangeKind::Modified,
+                before: Some(json!("FPS")),
+                after: Some(json!("RPG")),
+            },
+            FieldChange {
+                path: "tags[0]".to_string(),
+                kind: ChangeKind::Added,
+                before: None,
+                after: Some(json!("co-op")),
+            },
+        ];
+        assert_eq!(diff_text(&changes), "~ genre: \"FPS\" -> \"RPG\"\n+ tags[0]: \"co-op\"\n");
+    }
+}
@@ -0,0 +1,118 @@
+//! Reading and writing Game DNA and world files
+//!
+//! A path of `-` means stdin or stdout. Game DNA documents are JSON, or binary MessagePack
+//! led by the `EDN1` tag used by the wasm package, so both can be read interchangeably.
+
+use std::io::{Read, Write};
+use std::path::Path;
+
+use clap::ValueEnum;
+
+use entropic_dna_core::{schema::GameDNA, serialization::to_json_string};
+use entropic_world_core::{
+    serialization::{deserialize_from_bytes, serialize_to_json},
+    World,
+};
+
+use crate::{error::CliError, Result};
+
+/// Tag leading every binary GameDNA payload
+pub const DNA_BINARY_TAG: &[u8; 4] = b"EDN1";
+
+/// Encodings a GameDNA document can be written in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum DnaFormat {
+    /// Normalized single-line JSON, as used for checksums
+    Json,
+    /// Indented JSON with sorted keys
+    Pretty,
+    /// Tagged MessagePack, as produced by the wasm package
+    Binary,
+}
+
+pub fn read_bytes(path: &Path) -> Result<Vec<u8>> {
+    if path == Path::new("-") {
+        let mut bytes = Vec::new();
+        std::io::stdin()
+            .read_to_end(&mut bytes)
+            .map_err(|e| CliError::io(path, e))?;
+        return Ok(bytes);
+    }
+    std::fs::read(path).map_err(|e| CliError::io(path, e))
+}
+
+pub fn write_bytes(path: &Path, bytes: &[u8]) -> Result<()> {
+    if path == Path::new("-") {
+        return std::io::stdout()
+            .write_all(bytes)
+            .map_err(|e| CliError::io(path, e));
+    }
+    std::fs::write(path, bytes).map_err(|e| CliError::io(path, e))
+}
+
+/// Read a GameDNA document of any schema version, JSON or binary
+pub fn read_dna(path: &Path) -> Result<GameDNA> {
+    let bytes = read_bytes(path)?;
+    decode_dna(&bytes).map_err(|e| CliError::parse(path, e))
+}
+
+pub fn decode_dna(bytes: &[u8]) -> std::result::Result<GameDNA, String> {
+    match bytes.strip_prefix(DNA_BINARY_TAG.as_slice()) {
+        Some(payload) => rmp_serde::from_slice(payload).map_err(|e| e.to_string()),
+        None => serde_json::from_slice(bytes).map_err(|e| e.to_string()),
+    }
+}
+
+pub fn encode_dna(dna: &GameDNA, format: DnaFormat) -> Result<Vec<u8>> {
+    match format {
+        DnaFormat::Json => {
+            let mut json = to_json_string(dna).map_err(CliError::failed)?;
+            json.push('\n');
+            Ok(json.into_bytes())
+        }
+        DnaFormat::Pretty => {
+            // Round trip through the normalized form so keys come out sorted
+            let normalized = to_json_string(dna).map_err(CliError::failed)?;
+            let value: serde_json::Value =
+                serde_json::from_str(&normalized).map_err(CliError::failed)?;
+            let mut json = serde_json::to_string_pretty(&value).map_err(CliError::failed)?;
+            json.push('\n');
+            Ok(json.into_bytes())
+        }
+        DnaFormat::Binary => {
+            let mut bytes = DNA_BINARY_TAG.to_vec();
+            rmp_serde::encode::write_named(&mut bytes, dna).map_err(CliError::failed)?;
+            Ok(bytes)
+        }
+    }
+}
+
+pub fn read_world(path: &Path) -> Result<World> {
+    let bytes = read_bytes(path)?;
+    deserialize_from_bytes(&bytes).map_err(|e| CliError::parse(path, e))
+}
+
+pub fn write_world(path: &Path, world: &World) -> Result<()> {
+    let mut json = serialize_to_json(world).map_err(CliError::failed)?;
+    json.push('\n');
+    write_bytes(path, json.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entropic_dna_core::schema::{Genre, TargetPlatform};
+
+    #[test]
+    fn test_every_format_decodes() {
+        let dna = GameDNA::minimal("Files".to_string(), Genre::RPG, vec![TargetPlatform::PC]);
+        let expected = to_json_string(&dna).unwrap();
+
+        for format in [DnaFormat::Json, DnaFormat::Pretty, DnaFormat::Binary] {
+            let bytes = encode_dna(&dna, format).unwrap();
+            let decoded = decode_dna(&bytes).unwrap();
+            assert_eq!(to_json_string(&decoded).unwrap(), expected, "{format:?}");
+        }
+        assert!(decode_dna(b"EDN1 not msgpack").is_err());
+    }
+}
@@ -0,0 +1,63 @@
+//! `entropic` command-line tool
+//!
+//! Validates, converts, migrates, diffs and checksums Game DNA documents, and creates,
+//! ticks and inspects world files, using the same library code as the engine.
+//!
+//! Every command prints human-readable text by default and a single JSON document with
+//! `--format json`. Exit codes: `0` success, `1` a negative answer (an invalid document,
+//! documents that differ), `2` an error.
+
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+
+mod dna;
+mod error;
+mod files;
+mod output;
+mod world;
+
+use error::CliError;
+use output::OutputFormat;
+
+/// Work with Game DNA and world files
+#[derive(Parser, Debug)]
+#[command(name = "entropic", version)]
+struct Cli {
+    /// Output format
+    #[arg(long, short = 'f', global = true, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Game DNA documents
+    #[command(subcommand)]
+    Dna(dna::DnaCommand),
+
+    /// World files
+    #[command(subcommand)]
+    World(world::WorldCommand),
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Dna(command) => dna::run(command, cli.format),
+        Command::World(command) => world::run(command, cli.format),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Result of a command: the exit code to use, or an error
+pub type Result<T = ExitCode> = std::result::Result<T, CliError>;
@@ -0,0 +1,22 @@
+//! Text and JSON output
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{error::CliError, Result};
+
+/// How results are printed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text
+    Text,
+    /// One JSON document on stdout
+    Json,
+}
+
+/// Print `value` as pretty JSON on stdout
+pub fn print_json<T: Serialize + ?Sized>(value: &T) -> Result<()> {
+    let json = serde_json::to_string_pretty(value).map_err(CliError::failed)?;
+    println!("{json}");
+    Ok(())
+}
@@ -0,0 +1,179 @@
+//! `entropic world ...` commands
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::Subcommand;
+use serde::Serialize;
+
+use entropic_world_core::World;
+
+use crate::error::CliError;
+use crate::files::{read_dna, read_world, write_world};
+use crate::output::{print_json, OutputFormat};
+use crate::Result;
+
+#[derive(Subcommand, Debug)]
+pub enum WorldCommand {
+    /// Create a world file with initialized chunks
+    Create {
+        /// GameDNA document the world is for
+        #[arg(long, required_unless_present = "dna_id", conflicts_with = "dna_id")]
+        dna: Option<PathBuf>,
+        /// ID of the GameDNA the world is for, instead of --dna
+        #[arg(long)]
+        dna_id: Option<String>,
+        /// World name; defaults to the game name
+        #[arg(long)]
+        name: Option<String>,
+        /// Width in chunks
+        #[arg(long)]
+        width: u32,
+        /// Height in chunks
+        #[arg(long)]
+        height: u32,
+        /// Random seed
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Where to write the world
+        #[arg(long, short)]
+        output: PathBuf,
+    },
+
+    /// Advance a world file, in place unless --output is given
+    Tick {
+        /// World file
+        file: PathBuf,
+        /// Number of ticks to simulate
+        #[arg(long, short, default_value_t = 1)]
+        ticks: u64,
+        /// Where to write the advanced world
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+
+    /// Print a world's counters
+    Stats {
+        /// World file (`-` for stdin)
+        file: PathBuf,
+    },
+}
+
+pub fn run(command: WorldCommand, format: OutputFormat) -> Result {
+    match command {
+        WorldCommand::Create {
+            dna,
+            dna_id,
+            name,
+            width,
+            height,
+            seed,
+            output,
+        } => {
+            if width == 0 || height == 0 {
+                return Err(CliError::failed("a world needs at least one chunk on each axis"));
+            }
+            let (dna_name, dna_id) = match (dna, dna_id) {
+                (Some(path), _) => {
+                    let dna = read_dna(&path)?;
+                    (dna.name, dna.id)
+                }
+                (None, Some(id)) => ("World".to_string(), id),
+                (None, None) => unreachable!("clap requires --dna or --dna-id"),
+            };
+
+            let mut world = World::new(name.unwrap_or(dna_name), dna_id, width, height);
+            if let Some(seed) = seed {
+                world.reseed(seed);
+            }
+            world.initialize_chunks();
+            write_world(&output, &world)?;
+            report(&world, &output, format)
+        }
+        WorldCommand::Tick {
+            file,
+            ticks,
+            output,
+        } => {
+            let mut world = read_world(&file)?;
+            for _ in 0..ticks {
+                world.advance_tick();
+            }
+            let output = output.unwrap_or(file);
+            write_world(&output, &world)?;
+            report(&world, &output, format)
+        }
+        WorldCommand::Stats { file } => {
+            let stats = WorldStats::of(&read_world(&file)?);
+            match format {
+                OutputFormat::Json => print_json(&stats)?,
+                OutputFormat::Text => print!("{}", stats.text()),
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+/// Print the stats of a world just written to `path`
+fn report(world: &World, path: &Path, format: OutputFormat) -> Result {
+    let stats = WorldStats::of(world);
+    match format {
+        OutputFormat::Json => print_json(&stats)?,
+        OutputFormat::Text => {
+            println!("wrote {}", path.display());
+            print!("{}", stats.text());
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[derive(Debug, Serialize)]
+struct WorldStats {
+    id: String,
+    name: String,
+    game_dna_id: String,
+    width_chunks: u32,
+    height_chunks: u32,
+    current_tick: u64,
+    chunks: usize,
+    entities: usize,
+    npcs: usize,
+}
+
+impl WorldStats {
+    fn of(world: &World) -> Self {
+        Self {
+            id: world.id.clone(),
+            name: world.name.clone(),
+            game_dna_id: world.game_dna_id.clone(),
+            width_chunks: world.width_chunks,
+            height_chunks: world.height_chunks,
+            current_tick: world.current_tick,
+            chunks: world.total_chunks(),
+            entities: world.total_entities(),
+            npcs: world.total_npcs(),
+        }
+    }
+
+    fn text(&self) -> String {
+        format!(
+            "id:           {}\n\
+             name:         {}\n\
+             game_dna_id:  {}\n\
+             size:         {}x{} chunks\n\
+             current_tick: {}\n\
+             chunks:       {}\n\
+             entities:     {}\n\
+             npcs:         {}\n",
+            self.id,
+            self.name,
+            self.game_dna_id,
+            self.width_chunks,
+            self.height_chunks,
+            self.current_tick,
+            self.chunks,
+            self.entities,
+            self.npcs
+        )
+    }
+}
@@ -0,0 +1,85 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+use entropic_dna_core::schema::{Genre, TargetPlatform};
+use entropic_dna_core::serialization::to_json_string;
+use entropic_dna_core::GameDNA;
+
+/// Scratch directory unique to one test
+fn scratch(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("entropic-cli-{}-{test}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn entropic(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_entropic"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn write_dna(dir: &std::path::Path, file: &str, dna: &GameDNA) -> String {
+    let path = dir.join(file);
+    std::fs::write(&path, to_json_string(dna).unwrap()).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn test_dna_commands() {
+    let dir = scratch("dna");
+    let dna = GameDNA::minimal("Pipeline".to_string(), Genre::RPG, vec![TargetPlatform::PC]);
+    let a = write_dna(&dir, "a.json", &dna);
+
+    let out = entropic(&["dna", "validate", &a]);
+    assert!(out.status.success());
+    assert!(String::from_utf8_lossy(&out.stdout).contains("valid"));
+
+    // Binary conversion keeps the checksum
+    let bin = dir.join("a.bin").to_str().unwrap().to_string();
+    assert!(entropic(&["dna", "convert", &a, "--to", "binary", "-o", &bin]).status.success());
+    let sums = entropic(&["--format", "json", "dna", "checksum", &a, &bin]);
+    let sums: serde_json::Value = serde_json::from_slice(&sums.stdout).unwrap();
+    assert_eq!(sums[0]["checksum"], sums[1]["checksum"]);
+
+    let mut changed = dna.clone();
+    changed.genre = Genre::FPS;
+    let b = write_dna(&dir, "b.json", &changed);
+    let out = entropic(&["dna", "diff", &a, &b]);
+    assert_eq!(out.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&out.stdout).starts_with("~ genre"));
+    assert_eq!(entropic(&["dna", "diff", &a, &bin]).status.code(), Some(0));
+
+    let out = entropic(&["--format", "json", "dna", "migrate", &a, "--to", "0.1.0"]);
+    assert!(out.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(report["written_to"], serde_json::Value::Null);
+
+    let missing = dir.join("missing.json").to_str().unwrap().to_string();
+    assert_eq!(entropic(&["dna", "validate", &missing]).status.code(), Some(2));
+}
+
+#[test]
+fn test_world_commands() {
+    let dir = scratch("world");
+    let world = dir.join("world.json").to_str().unwrap().to_string();
+
+    let out = entropic(&[
+        "world", "create", "--dna-id", "dna_1", "--width", "3", "--height", "2", "--seed", "9",
+        "-o", &world,
+    ]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+
+    assert!(entropic(&["world", "tick", &world, "--ticks", "5"]).status.success());
+
+    let out = entropic(&["-f", "json", "world", "stats", &world]);
+    let stats: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(stats["current_tick"], 5);
+    assert_eq!(stats["chunks"], 6);
+    assert_eq!(stats["game_dna_id"], "dna_1");
+
+    let out = entropic(&[
+        "world", "create", "--dna-id", "x", "--width", "0", "--height", "2", "-o", &world,
+    ]);
+    assert_eq!(out.status.code(), Some(2));
+}
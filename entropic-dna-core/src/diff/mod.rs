@@ -0,0 +1,191 @@
+//! Structural diffs between Game DNA documents
+//!
+//! Compares two GameDNA configurations field by field and reports every value that was
+//! added, removed or modified. Nested values are walked down to their leaves, so a change
+//! to one tag or custom property shows up as a single entry with a path such as
+//! `tags[2]` or `custom_properties.engine`.
+
+use crate::GameDNA;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Kind of change made to a single value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+pub enum ChangeKind {
+    /// Value only exists in the new document
+    Added,
+    /// Value only exists in the old document
+    Removed,
+    /// Value exists in both documents with different contents
+    Modified,
+}
+
+/// A single changed value between two documents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+pub struct FieldChange {
+    /// Dotted path to the value, with `[i]` for list elements
+    pub path: String,
+    /// Kind of change
+    pub kind: ChangeKind,
+    /// Value in the old document, if present
+    #[cfg_attr(feature = "wasm", tsify(type = "unknown"))]
+    pub before: Option<Value>,
+    /// Value in the new document, if present
+    #[cfg_attr(feature = "wasm", tsify(type = "unknown"))]
+    pub after: Option<Value>,
+}
+
+/// Ordered list of changes between two GameDNA documents
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(into_wasm_abi))]
+pub struct DnaDiff {
+    /// Changes sorted by path
+    pub changes: Vec<FieldChange>,
+}
+
+impl DnaDiff {
+    /// Computes the changes needed to turn `old` into `new`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_dna_core::{diff::DnaDiff, GameDNA};
+    /// use entropic_dna_core::schema::{Genre, TargetPlatform};
+    ///
+    /// let old = GameDNA::minimal("Old".to_string(), Genre::FPS, vec![TargetPlatform::PC]);
+    /// let mut new = old.clone();
+    /// new.name = "New".to_string();
+    ///
+    /// let diff = DnaDiff::between(&old, &new);
+    /// assert_eq!(diff.changes.len(), 1);
+    /// assert_eq!(diff.changes[0].path, "name");
+    /// ```
+    pub fn between(old: &GameDNA, new: &GameDNA) -> Self {
+        let old = serde_json::to_value(old).unwrap_or(Value::Null);
+        let new = serde_json::to_value(new).unwrap_or(Value::Null);
+        Self::between_values(&old, &new)
+    }
+
+    /// Computes the changes between two arbitrary JSON values, such as raw documents
+    /// written by older tool versions.
+    pub fn between_values(old: &Value, new: &Value) -> Self {
+        let mut changes = Vec::new();
+        diff_values(String::new(), old, new, &mut changes);
+        changes.sort_by(|a, b| a.path.cmp(&b.path));
+        Self { changes }
+    }
+
+    /// Whether the two documents are identical
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Drops changes to `id`, `created_at` and `last_modified`, which differ between
+    /// otherwise identical documents
+    pub fn without_metadata(mut self) -> Self {
+        self.changes
+            .retain(|c| !matches!(c.path.as_str(), "id" | "created_at" | "last_modified"));
+        self
+    }
+}
+
+fn diff_values(path: String, old: &Value, new: &Value, changes: &mut Vec<FieldChange>) {
+    match (old, new) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (key, before) in a {
+                let child = join(&path, key);
+                match b.get(key) {
+                    Some(after) => diff_values(child, before, after, changes),
+                    None => changes.push(removed(child, before)),
+                }
+            }
+            for (key, after) in b {
+                if !a.contains_key(key) {
+                    changes.push(added(join(&path, key), after));
+                }
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for i in 0..a.len().max(b.len()) {
+                let child = format!("{path}[{i}]");
+                match (a.get(i), b.get(i)) {
+                    (Some(before), Some(after)) => diff_values(child, before, after, changes),
+                    (Some(before), None) => changes.push(removed(child, before)),
+                    (None, Some(after)) => changes.push(added(child, after)),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ if old != new => changes.push(FieldChange {
+            path,
+            kind: ChangeKind::Modified,
+            before: Some(old.clone()),
+            after: Some(new.clone()),
+        }),
+        _ => {}
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+fn added(path: String, value: &Value) -> FieldChange {
+    FieldChange {
+        path,
+        kind: ChangeKind::Added,
+        before: None,
+        after: Some(value.clone()),
+    }
+}
+
+fn removed(path: String, value: &Value) -> FieldChange {
+    FieldChange {
+        path,
+        kind: ChangeKind::Removed,
+        before: Some(value.clone()),
+        after: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{Genre, TargetPlatform};
+
+    #[test]
+    fn test_diff_reports_leaf_changes() {
+        let old = GameDNA::minimal("Game".to_string(), Genre::FPS, vec![TargetPlatform::PC]);
+        let mut new = old.clone();
+        new.target_fps = 120;
+        new.target_platforms.push(TargetPlatform::Console);
+        new.custom_properties.insert("engine".to_string(), "v2".to_string());
+
+        let diff = DnaDiff::between(&old, &new);
+        let paths: Vec<_> = diff.changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["custom_properties.engine", "target_fps", "target_platforms[1]"]
+        );
+        assert_eq!(diff.changes[0].kind, ChangeKind::Added);
+        assert_eq!(diff.changes[1].kind, ChangeKind::Modified);
+        assert_eq!(diff.changes[1].before, Some(Value::from(60)));
+
+        assert!(DnaDiff::between(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn test_without_metadata() {
+        let a = GameDNA::minimal("Game".to_string(), Genre::FPS, vec![TargetPlatform::PC]);
+        let b = GameDNA::minimal("Game".to_string(), Genre::FPS, vec![TargetPlatform::PC]);
+        assert!(!DnaDiff::between(&a, &b).is_empty());
+        assert!(DnaDiff::between(&a, &b).without_metadata().is_empty());
+    }
+}
@@ -3,6 +3,8 @@
 //! Provides structured error handling for parsing, schema validation,
 //! serialization, and version management errors.
 
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use thiserror::Error;
 
 /// Error type for parsing GameDNA from serialization formats
@@ -54,6 +56,7 @@ pub enum ParseError {
     },
 }
 
+#[cfg(feature = "std")]
 impl From<serde_json::Error> for ParseError {
     fn from(err: serde_json::Error) -> Self {
         ParseError::InvalidJson {
@@ -182,6 +185,7 @@ pub enum SerializationError {
     },
 }
 
+#[cfg(feature = "std")]
 impl From<serde_json::Error> for SerializationError {
     fn from(err: serde_json::Error) -> Self {
         if err.is_data() {
@@ -10,6 +10,8 @@
 //! - **`serialization`** - Deterministic JSON serialization with round-trip guarantees
 //! - **`version`** - Schema versioning and migration framework
 //! - **`errors`** - Comprehensive error types for parsing, validation, and serialization
+//! - **`diff`** - Field-level diffs between Game DNA documents
+//! - **`test_support`** - proptest strategies and fixtures, behind the `test-support` feature
 //! 
 //! # Features
 //! 
@@ -20,6 +22,18 @@
 //! - Extensible design with custom properties and tags
 //! - Builder pattern for ergonomic GameDNA construction
 //! - No unsafe code - written in 100% safe Rust
+//! - `no_std` + `alloc` support for the schema types (disable the default `std` feature)
+//! 
+//! # `no_std`
+//! 
+//! With `default-features = false` the crate builds without `std`, keeping `schema` and
+//! `errors` so lightweight runtimes can deserialize Game DNA with any `serde` format that
+//! works on `alloc`, such as `serde_json` with its `alloc` feature. The builder then
+//! leaves the ID empty, which `GameDNA::validate` rejects, so one must be set with
+//! `GameDNABuilder::id` and `GameDNA::minimal` panics. Timestamps are
+//! `DateTime::default()`, the Unix epoch, and `custom_properties` is a `BTreeMap` rather
+//! than a `HashMap` (see `schema::PropertyMap`). `SemanticVersion` parsing and the
+//! serialization, validation, diff and version modules need `std`.
 //! 
 //! # Example
 //! 
@@ -61,26 +75,38 @@
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 #![warn(rust_2018_idioms)]
 #![deny(unsafe_code)]
 
+extern crate alloc;
+
 // Re-export commonly used types at the crate root
 pub use crate::schema::{GameDNA, GameDNABuilder, SemanticVersion};
 
+#[cfg(feature = "std")]
+pub mod diff;
 pub mod errors;
 pub mod schema;
+#[cfg(feature = "std")]
 pub mod serialization;
+#[cfg(feature = "test-support")]
+pub mod test_support;
+#[cfg(feature = "std")]
 pub mod validation;
+#[cfg(feature = "std")]
 pub mod version;
 
 #[cfg(test)]
 mod tests;
 
 /// Type alias for Results using entropic-dna-core's error types
+#[cfg(feature = "std")]
 pub type Result<T, E = Box<dyn std::error::Error + Send + Sync>> = std::result::Result<T, E>;
 
 /// Re-export of version constants for convenience
+#[cfg(feature = "std")]
 pub use version::{CURRENT_VERSION, MINIMUM_COMPATIBLE_VERSION};
 
 #[cfg(test)]
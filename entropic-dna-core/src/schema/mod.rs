@@ -2,12 +2,26 @@
 //! 
 //! This module defines the canonical structure for representing game configurations
 //! in the ENTROPIC game engine. All game types are type-safe and deterministic.
+//!
+//! Everything here only needs `alloc`, so the schema is available without the `std`
+//! feature.
 
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+
+/// Map holding `GameDNA::custom_properties`: a `HashMap` with the `std` feature, and a
+/// `BTreeMap` without it, as `alloc` has no hash map.
+#[cfg(feature = "std")]
+pub type PropertyMap = std::collections::HashMap<String, String>;
+/// Map holding `GameDNA::custom_properties`, a `BTreeMap` without the `std` feature.
+#[cfg(not(feature = "std"))]
+pub type PropertyMap = alloc::collections::BTreeMap<String, String>;
 
 /// Represents the genre classification of a game
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
 pub enum Genre {
     /// First Person Shooter
     FPS,
@@ -36,6 +50,7 @@ pub enum Genre {
 
 /// Represents the camera perspective/view mode
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
 pub enum CameraMode {
     /// 2D perspective
     Perspective2D,
@@ -55,6 +70,7 @@ pub enum CameraMode {
 
 /// Represents the visual and gameplay tone/style
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
 pub enum Tone {
     /// Realistic tone
     Realistic,
@@ -73,6 +89,7 @@ pub enum Tone {
 
 /// Represents the scale of the game world
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
 pub enum WorldScale {
     /// Small enclosed level
     TinyLevel,
@@ -95,6 +112,7 @@ pub enum WorldScale {
 
 /// Represents target platforms for the game
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
 pub enum TargetPlatform {
     /// Mobile devices (iOS/Android)
     Mobile,
@@ -112,6 +130,7 @@ pub enum TargetPlatform {
 
 /// Represents the game's monetization model
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
 pub enum MonetizationModel {
     /// Free to play with optional purchases
     #[serde(rename = "FreeToPlay")]
@@ -133,6 +152,7 @@ pub enum MonetizationModel {
 
 /// Represents physics simulation profile
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
 pub enum PhysicsProfile {
     /// Arcade-style physics (fast, forgiving)
     Arcade,
@@ -147,6 +167,7 @@ pub enum PhysicsProfile {
 
 /// Represents difficulty configuration
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
 pub enum DifficultyMode {
     /// Easy difficulty setting
     Easy,
@@ -163,6 +184,7 @@ pub enum DifficultyMode {
 
 /// Represents a semantic version (MAJOR.MINOR.PATCH)
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
 pub struct SemanticVersion {
     /// Major version (breaking changes)
     pub major: u32,
@@ -184,14 +206,28 @@ impl SemanticVersion {
     }
 }
 
-impl std::fmt::Display for SemanticVersion {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+#[cfg(feature = "std")]
+impl std::str::FromStr for SemanticVersion {
+    type Err = crate::errors::VersionError;
+
+    /// Parses a `MAJOR.MINOR.PATCH` version string
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        crate::version::VersionManager::validate_version_format(s)?;
+        let parts: Vec<u32> = s.split('.').map(|p| p.parse().unwrap_or(0)).collect();
+        Ok(Self::new(parts[0], parts[1], parts[2]))
+    }
+}
+
+impl core::fmt::Display for SemanticVersion {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
     }
 }
 
 /// The core Game DNA struct representing complete game configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(into_wasm_abi, from_wasm_abi))]
 pub struct GameDNA {
     /// Unique identifier (UUID)
     pub id: String,
@@ -200,8 +236,10 @@ pub struct GameDNA {
     /// Semantic version
     pub version: SemanticVersion,
     /// Creation timestamp
+    #[cfg_attr(feature = "wasm", tsify(type = "string"))]
     pub created_at: chrono::DateTime<chrono::Utc>,
     /// Last modification timestamp
+    #[cfg_attr(feature = "wasm", tsify(type = "string"))]
     pub last_modified: chrono::DateTime<chrono::Utc>,
     
     /// Core configuration
@@ -278,7 +316,8 @@ pub struct GameDNA {
     /// Descriptive tags
     pub tags: Vec<String>,
     /// Custom properties for engine-specific extensions
-    pub custom_properties: HashMap<String, String>,
+    #[cfg_attr(feature = "wasm", tsify(type = "Record<string, string>"))]
+    pub custom_properties: PropertyMap,
 }
 
 impl GameDNA {
@@ -288,6 +327,11 @@ impl GameDNA {
     }
     
     /// Creates a minimal valid GameDNA instance with required fields
+    ///
+    /// # Panics
+    ///
+    /// Without the `std` feature no ID is generated, and the empty ID fails validation, so
+    /// this always panics; use the builder with an explicit `id` instead.
     pub fn minimal(name: String, genre: Genre, platforms: Vec<TargetPlatform>) -> Self {
         Self::builder()
             .name(name)
@@ -339,6 +383,7 @@ impl GameDNA {
 }
 
 /// Builder for GameDNA with sensible defaults
+#[derive(Debug, Clone)]
 pub struct GameDNABuilder {
     name: String,
     id: Option<String>,
@@ -372,13 +417,12 @@ pub struct GameDNABuilder {
     has_side_quests: bool,
     dynamic_quests: bool,
     tags: Vec<String>,
-    custom_properties: HashMap<String, String>,
+    custom_properties: PropertyMap,
 }
 
 impl GameDNABuilder {
     /// Creates a new GameDNA builder with default values
     pub fn new() -> Self {
-        let now = chrono::Utc::now();
         Self {
             name: String::new(),
             id: None,
@@ -412,7 +456,7 @@ impl GameDNABuilder {
             has_side_quests: false,
             dynamic_quests: false,
             tags: Vec::new(),
-            custom_properties: HashMap::new(),
+            custom_properties: PropertyMap::new(),
         }
     }
     
@@ -423,6 +467,9 @@ impl GameDNABuilder {
     }
     
     /// Sets the game ID (UUID format)
+    ///
+    /// A random UUID is generated when no ID is set. Without the `std` feature the ID is
+    /// left empty, which `GameDNA::validate` rejects, so `build` fails unless an ID was given.
     pub fn id<S: Into<String>>(mut self, id: S) -> Self {
         self.id = Some(id.into());
         self
@@ -621,8 +668,17 @@ impl GameDNABuilder {
     }
     
     /// Builds the GameDNA instance
+    ///
+    /// Both timestamps are set to the current time, or to `DateTime::default()` (the Unix
+    /// epoch) without the `std` feature.
     pub fn build(self) -> Result<GameDNA, crate::errors::SchemaError> {
-        let id = self.id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        #[cfg(feature = "std")]
+        let (id, now) = (
+            self.id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+            chrono::Utc::now(),
+        );
+        #[cfg(not(feature = "std"))]
+        let (id, now) = (self.id.unwrap_or_default(), chrono::DateTime::default());
         
         let dna = GameDNA {
             id,
@@ -0,0 +1,236 @@
+//! Property-testing strategies and fixtures for Game DNA, enabled by the `test-support`
+//! feature.
+//!
+//! Every schema enum and `GameDNA` implement `proptest::arbitrary::Arbitrary`, so tests can
+//! draw them with `any::<T>()`. Generated documents always pass `GameDNA::validate`.
+//!
+//! ```rust
+//! use entropic_dna_core::GameDNA;
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(dna in any::<GameDNA>())| {
+//!     prop_assert!(dna.validate().is_ok());
+//! });
+//! ```
+
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+use crate::schema::{
+    CameraMode, DifficultyMode, GameDNA, Genre, MonetizationModel, PhysicsProfile,
+    TargetPlatform, Tone, WorldScale,
+};
+
+/// Short lowercase labels, used for names of custom variants and tags
+pub fn label() -> impl Strategy<Value = String> {
+    "[a-z][a-z0-9-]{0,11}"
+}
+
+/// A valid single-player Game DNA with a fixed ID, for tests that need a document but
+/// don't care about its contents
+pub fn sample_dna() -> GameDNA {
+    GameDNA::builder()
+        .id("test-dna")
+        .name("Test Game")
+        .genre(Genre::RPG)
+        .target_platforms(vec![TargetPlatform::PC])
+        .build()
+        .expect("Sample GameDNA should be valid")
+}
+
+impl Arbitrary for Genre {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    // `Self::Strategy` would be ambiguous with `Genre::Strategy`
+    fn arbitrary_with(_: ()) -> BoxedStrategy<Self> {
+        prop_oneof![
+            Just(Genre::FPS),
+            Just(Genre::RPG),
+            Just(Genre::TPS),
+            Just(Genre::Strategy),
+            Just(Genre::Casual),
+            Just(Genre::Horror),
+            Just(Genre::Racing),
+            Just(Genre::Simulation),
+            Just(Genre::Puzzle),
+            Just(Genre::Educational),
+            label().prop_map(Genre::CustomGenre),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for CameraMode {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(CameraMode::Perspective2D),
+            Just(CameraMode::Perspective2_5D),
+            Just(CameraMode::Perspective3D),
+            Just(CameraMode::Isometric),
+            Just(CameraMode::VR),
+            label().prop_map(CameraMode::CustomCamera),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for Tone {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(Tone::Realistic),
+            Just(Tone::Arcade),
+            Just(Tone::Cinematic),
+            Just(Tone::Stylized),
+            Just(Tone::Minimalist),
+            label().prop_map(Tone::CustomTone),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for WorldScale {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(WorldScale::TinyLevel),
+            Just(WorldScale::SmallLevel),
+            Just(WorldScale::MediumLevel),
+            Just(WorldScale::LargeLevel),
+            Just(WorldScale::OpenWorld),
+            Just(WorldScale::Planet),
+            Just(WorldScale::Galaxy),
+            label().prop_map(WorldScale::CustomScale),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for TargetPlatform {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(TargetPlatform::Mobile),
+            Just(TargetPlatform::PC),
+            Just(TargetPlatform::Console),
+            Just(TargetPlatform::XR),
+            Just(TargetPlatform::CloudStreamed),
+            Just(TargetPlatform::MultiPlatform),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for PhysicsProfile {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(PhysicsProfile::Arcade),
+            Just(PhysicsProfile::SemiRealistic),
+            Just(PhysicsProfile::Realistic),
+            label().prop_map(PhysicsProfile::CustomPhysics),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for DifficultyMode {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(DifficultyMode::Easy),
+            Just(DifficultyMode::Medium),
+            Just(DifficultyMode::Hard),
+            Just(DifficultyMode::Dynamic),
+            label().prop_map(DifficultyMode::CustomDifficulty),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for MonetizationModel {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(MonetizationModel::FreeToPlay),
+            Just(MonetizationModel::PremiumBuy),
+            Just(MonetizationModel::Subscription),
+            Just(MonetizationModel::OneTimePay),
+            Just(MonetizationModel::Hybrid),
+            label().prop_map(MonetizationModel::Custom),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for GameDNA {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        let presentation = (
+            any::<Genre>(),
+            any::<CameraMode>(),
+            any::<Tone>(),
+            any::<WorldScale>(),
+            any::<PhysicsProfile>(),
+        );
+        let players = (1u32..=64, any::<bool>(), any::<bool>(), any::<DifficultyMode>());
+        let world = (1u32..=240, 0.1f32..100.0, any::<[bool; 4]>(), 0u32..500);
+        (
+            "[A-Z][A-Za-z0-9 ]{0,23}",
+            presentation,
+            vec(any::<TargetPlatform>(), 1..4),
+            players,
+            any::<MonetizationModel>(),
+            world,
+            vec(label(), 0..4),
+        )
+            .prop_map(|(name, presentation, platforms, players, monetization, world, tags)| {
+                let (genre, camera, tone, world_scale, physics) = presentation;
+                let (max_players, competitive, coop, difficulty) = players;
+                let (fps, time_scale, [weather, seasons, day_night, persistent], npcs) = world;
+                let builder = GameDNA::builder()
+                    .name(name)
+                    .genre(genre)
+                    .camera(camera)
+                    .tone(tone)
+                    .world_scale(world_scale)
+                    .physics_profile(physics)
+                    .target_platforms(platforms)
+                    .max_players(max_players)
+                    .is_competitive(competitive)
+                    .supports_coop(coop)
+                    .difficulty(difficulty)
+                    .monetization(monetization)
+                    .target_fps(fps)
+                    .time_scale(time_scale)
+                    .weather_enabled(weather)
+                    .seasons_enabled(seasons)
+                    .day_night_cycle(day_night)
+                    .persistent_world(persistent)
+                    .npc_count(npcs);
+                tags.into_iter()
+                    .fold(builder, |builder, tag| builder.tag(tag))
+                    .build()
+                    .expect("Generated GameDNA should be valid")
+            })
+            .boxed()
+    }
+}
@@ -1310,4 +1310,21 @@ mod validation_tests {
         // This is a generous target
         assert!(duration.as_millis() < 100, "Validation took too long: {}ms for 100 validations", duration.as_millis());
     }
-}
\ No newline at end of file
+}
+#[cfg(all(test, feature = "test-support"))]
+mod property_tests {
+    use crate::diff::DnaDiff;
+    use crate::serialization::{from_json_str, to_json_string};
+    use crate::GameDNA;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_arbitrary_dna_round_trips(dna in any::<GameDNA>()) {
+            let json = to_json_string(&dna).unwrap();
+            let restored = from_json_str(&json).unwrap();
+            prop_assert!(restored.validate().is_ok());
+            prop_assert!(DnaDiff::between(&dna, &restored).is_empty());
+        }
+    }
+}
@@ -11,11 +11,14 @@ pub mod checksum;
 
 use crate::schema::GameDNA;
 use chrono::DateTime;
+use serde::{Deserialize, Serialize};
 use chrono::Utc;
 use std::collections::HashMap;
 
 /// Validation result containing errors, warnings, and suggestions
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(into_wasm_abi))]
 pub struct ValidationResult {
     /// Whether the configuration is valid
     pub is_valid: bool,
@@ -115,7 +118,8 @@ impl ValidationResult {
 }
 
 /// Validation error with detailed information
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
 pub struct ValidationError {
     /// Error code (e.g., "INCOMPATIBLE_CAMERA_FOR_GENRE")
     pub code: String,
@@ -159,7 +163,8 @@ impl ValidationError {
 }
 
 /// Validation warning with suggested fixes
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
 pub struct ValidationWarning {
     /// Warning code
     pub code: String,
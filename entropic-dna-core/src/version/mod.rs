@@ -3,7 +3,7 @@
 //! Provides schema versioning, compatibility checking, and migration framework
 //! for evolving the GameDNA schema over time.
 
-use crate::{errors::VersionError, GameDNA};
+use crate::{errors::VersionError, GameDNA, SemanticVersion};
 
 /// Current schema version
 pub const CURRENT_VERSION: &str = "0.1.0";
@@ -226,20 +226,89 @@ impl MigrationManager {
         }
     }
     
+    /// Creates a manager holding every migration shipped with this crate
+    ///
+    /// The schema has not changed since 0.1.0, so there are none yet; new schema versions
+    /// register their migrations here.
+    pub fn with_builtin_migrations() -> Self {
+        Self::new()
+    }
+    
     /// Adds a migration to the manager
     pub fn add_migration<M: Migration + 'static>(&mut self, migration: M) {
         self.migrations.push(Box::new(migration));
     }
     
     /// Finds a migration path from one version to another
+    ///
+    /// Prefers a direct migration; otherwise chains migrations starting at `from`, always
+    /// taking the step that goes furthest without passing `to`.
     pub fn find_migration_path(&self, from: &str, to: &str) -> Option<Vec<&dyn Migration>> {
-        // Simple implementation for direct migrations
-        self.migrations
-            .iter()
-            .find(|m| m.from_version() == from && m.to_version() == to)
-            .map(|m| vec![m.as_ref()])
+        let manager = VersionManager::new();
+        let mut path = Vec::new();
+        let mut current = from.to_string();
+
+        while current != to {
+            let step = self
+                .migrations
+                .iter()
+                .filter(|m| m.from_version() == current)
+                .filter(|m| {
+                    matches!(
+                        manager.compare_versions(m.to_version(), to),
+                        Ok(std::cmp::Ordering::Less | std::cmp::Ordering::Equal)
+                    )
+                })
+                .max_by(|a, b| {
+                    manager
+                        .compare_versions(a.to_version(), b.to_version())
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })?;
+
+            // Each step must move forward, so a bad registry can't loop forever
+            if !matches!(
+                manager.compare_versions(step.to_version(), &current),
+                Ok(std::cmp::Ordering::Greater)
+            ) {
+                return None;
+            }
+
+            current = step.to_version().to_string();
+            path.push(step.as_ref());
+        }
+
+        (!path.is_empty()).then_some(path)
     }
-    
+
+    /// Migrates a GameDNA from its current version to `to`, returning it unchanged when it
+    /// is already there.
+    ///
+    /// The version of the result is set to each step's target version as the step runs.
+    pub fn migrate(&self, dna: GameDNA, to: &str) -> Result<GameDNA, VersionError> {
+        let target: SemanticVersion = to.parse()?;
+        let from = dna.version.to_string();
+        if from == to {
+            return Ok(dna);
+        }
+
+        let path = self.find_migration_path(&from, to).ok_or_else(|| {
+            VersionError::MigrationNotAvailable {
+                from_version: from.clone(),
+                to_version: to.to_string(),
+                help: "Register a migration for every schema change between these versions."
+                    .to_string(),
+            }
+        })?;
+
+        let mut dna = dna;
+        for step in path {
+            dna = step.migrate(dna)?;
+            dna.version = step.to_version().parse()?;
+        }
+        debug_assert_eq!(dna.version, target);
+        Ok(dna)
+    }
+
     /// Checks if a direct migration exists between versions
     pub fn has_migration(&self, from: &str, to: &str) -> bool {
         self.migrations
@@ -346,4 +415,34 @@ mod tests {
         assert!(path.is_some());
         assert_eq!(path.unwrap().len(), 1);
     }
+
+    #[test]
+    fn test_chained_migration() {
+        struct Step(&'static str, &'static str);
+        impl Migration for Step {
+            fn from_version(&self) -> &str { self.0 }
+            fn to_version(&self) -> &str { self.1 }
+            fn migrate(&self, mut dna: GameDNA) -> Result<GameDNA, VersionError> {
+                dna.tags.push(format!("migrated-{}", self.1));
+                Ok(dna)
+            }
+        }
+
+        let mut manager = MigrationManager::new();
+        manager.add_migration(Step("0.1.0", "0.2.0"));
+        manager.add_migration(Step("0.2.0", "0.3.0"));
+        manager.add_migration(Step("0.2.0", "0.4.0"));
+
+        assert_eq!(manager.find_migration_path("0.1.0", "0.3.0").unwrap().len(), 2);
+        assert!(manager.find_migration_path("0.1.0", "0.5.0").is_none());
+
+        let dna = GameDNA::minimal(
+            "Old".to_string(),
+            crate::schema::Genre::FPS,
+            vec![crate::schema::TargetPlatform::PC],
+        );
+        let migrated = manager.migrate(dna, "0.3.0").unwrap();
+        assert_eq!(migrated.version, SemanticVersion::new(0, 3, 0));
+        assert_eq!(migrated.tags, vec!["migrated-0.2.0", "migrated-0.3.0"]);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,83 @@
+/*
+ * Stable C ABI for the ENTROPIC engine.
+ *
+ * Mirrors the exported functions of the `entropic-ffi` crate. Every fallible
+ * function returns an EntropicStatus; on failure entropic_last_error() describes
+ * the problem on the calling thread. Strings handed out by the library are
+ * owned by the caller and must be released with entropic_string_free().
+ */
+
+#ifndef ENTROPIC_H
+#define ENTROPIC_H
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define ENTROPIC_FFI_ABI_VERSION 1
+
+typedef enum EntropicStatus {
+    ENTROPIC_STATUS_OK = 0,
+    ENTROPIC_STATUS_NULL_POINTER = 1,
+    ENTROPIC_STATUS_INVALID_UTF8 = 2,
+    ENTROPIC_STATUS_PARSE_ERROR = 3,
+    ENTROPIC_STATUS_SERIALIZATION_ERROR = 4,
+    ENTROPIC_STATUS_INVALID_ARGUMENT = 5,
+    ENTROPIC_STATUS_PANIC = 6
+} EntropicStatus;
+
+/* Values accepted by entropic_world_add_entity */
+typedef enum EntropicEntityType {
+    ENTROPIC_ENTITY_NPC = 0,
+    ENTROPIC_ENTITY_ANIMAL = 1,
+    ENTROPIC_ENTITY_STRUCTURE = 2,
+    ENTROPIC_ENTITY_ITEM = 3,
+    ENTROPIC_ENTITY_EFFECT = 4
+} EntropicEntityType;
+
+typedef struct EntropicGameDna EntropicGameDna;
+typedef struct EntropicWorld EntropicWorld;
+
+typedef struct EntropicWorldStats {
+    uint64_t current_tick;
+    uint64_t chunks;
+    uint64_t entities;
+    uint64_t npcs;
+} EntropicWorldStats;
+
+/* Library */
+uint32_t entropic_abi_version(void);
+const char *entropic_version(void);
+const char *entropic_last_error(void);
+void entropic_string_free(char *s);
+
+/* Game DNA */
+EntropicStatus entropic_dna_parse(const char *json, EntropicGameDna **out_dna);
+void entropic_dna_free(EntropicGameDna *dna);
+EntropicStatus entropic_dna_validate(const EntropicGameDna *dna, bool *out_is_valid,
+                                     char **out_report);
+EntropicStatus entropic_dna_serialize(const EntropicGameDna *dna, char **out_json);
+EntropicStatus entropic_dna_checksum(const EntropicGameDna *dna, char **out_checksum);
+
+/* World */
+EntropicStatus entropic_world_create(const char *name, const char *game_dna_id,
+                                     uint32_t width_chunks, uint32_t height_chunks,
+                                     EntropicWorld **out_world);
+EntropicStatus entropic_world_from_json(const char *json, EntropicWorld **out_world);
+void entropic_world_free(EntropicWorld *world);
+EntropicStatus entropic_world_tick(EntropicWorld *world, uint32_t ticks);
+EntropicStatus entropic_world_stats(const EntropicWorld *world, EntropicWorldStats *out_stats);
+EntropicStatus entropic_world_add_entity(EntropicWorld *world, const char *id,
+                                         uint32_t entity_type, float x, float y, float z);
+EntropicStatus entropic_world_query_radius(const EntropicWorld *world, float x, float y,
+                                           float radius, char **out_json);
+EntropicStatus entropic_world_to_json(const EntropicWorld *world, char **out_json);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ENTROPIC_H */
@@ -0,0 +1,140 @@
+//! GameDNA parse, validate, serialize and checksum
+
+use std::ffi::c_char;
+
+use entropic_dna_core::{
+    serialization::{from_json_str, to_json_string},
+    validation::{checksum, ValidationEngine},
+    GameDNA,
+};
+
+use crate::error::{guard, read_str, write_string, EntropicStatus, FfiError};
+
+/// Opaque handle to a parsed GameDNA document
+#[derive(Debug)]
+pub struct EntropicGameDna {
+    pub(crate) dna: GameDNA,
+}
+
+/// Borrow the document behind a handle
+///
+/// # Safety
+///
+/// `dna` must be null or a live handle from [`entropic_dna_parse`].
+unsafe fn dna_ref<'a>(dna: *const EntropicGameDna) -> Result<&'a GameDNA, FfiError> {
+    // SAFETY: null or live per the caller's contract
+    unsafe { dna.as_ref() }
+        .map(|handle| &handle.dna)
+        .ok_or_else(|| FfiError::null("dna"))
+}
+
+/// Parse a GameDNA JSON document into a new handle written to `out_dna`.
+///
+/// # Safety
+///
+/// `json` must be a NUL-terminated string and `out_dna` valid for a pointer write.
+#[no_mangle]
+pub unsafe extern "C" fn entropic_dna_parse(
+    json: *const c_char,
+    out_dna: *mut *mut EntropicGameDna,
+) -> EntropicStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract
+        let json = unsafe { read_str(json, "json") }?;
+        if out_dna.is_null() {
+            return Err(FfiError::null("out_dna"));
+        }
+        let dna = from_json_str(json)
+            .map_err(|e| FfiError::new(EntropicStatus::ParseError, e.to_string()))?;
+        let handle = Box::into_raw(Box::new(EntropicGameDna { dna }));
+        // SAFETY: checked non-null above, writable per the caller's contract
+        unsafe { out_dna.write(handle) };
+        Ok(())
+    })
+}
+
+/// Release a GameDNA handle. Null is ignored.
+///
+/// # Safety
+///
+/// `dna` must be null or a handle from [`entropic_dna_parse`] that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn entropic_dna_free(dna: *mut EntropicGameDna) {
+    if !dna.is_null() {
+        // SAFETY: the handle came from `Box::into_raw` in `entropic_dna_parse`
+        drop(unsafe { Box::from_raw(dna) });
+    }
+}
+
+/// Run the validation engine on a document.
+///
+/// Writes whether the document is valid to `out_is_valid`, and the full validation result
+/// as JSON to `out_report` unless it is null.
+///
+/// # Safety
+///
+/// `dna` must be a live handle, `out_is_valid` valid for a write, and `out_report` null or
+/// valid for a pointer write.
+#[no_mangle]
+pub unsafe extern "C" fn entropic_dna_validate(
+    dna: *const EntropicGameDna,
+    out_is_valid: *mut bool,
+    out_report: *mut *mut c_char,
+) -> EntropicStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract
+        let dna = unsafe { dna_ref(dna) }?;
+        if out_is_valid.is_null() {
+            return Err(FfiError::null("out_is_valid"));
+        }
+
+        let result = ValidationEngine::new().validate(dna);
+        if !out_report.is_null() {
+            let report = serde_json::to_string(&result)
+                .map_err(|e| FfiError::new(EntropicStatus::SerializationError, e.to_string()))?;
+            // SAFETY: non-null and writable per the caller's contract
+            unsafe { write_string(out_report, report) }?;
+        }
+        // SAFETY: checked non-null above, writable per the caller's contract
+        unsafe { out_is_valid.write(result.is_valid) };
+        Ok(())
+    })
+}
+
+/// Write the document as normalized, deterministic JSON to `out_json`.
+///
+/// # Safety
+///
+/// `dna` must be a live handle and `out_json` valid for a pointer write.
+#[no_mangle]
+pub unsafe extern "C" fn entropic_dna_serialize(
+    dna: *const EntropicGameDna,
+    out_json: *mut *mut c_char,
+) -> EntropicStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract
+        let dna = unsafe { dna_ref(dna) }?;
+        let json = to_json_string(dna)
+            .map_err(|e| FfiError::new(EntropicStatus::SerializationError, e.to_string()))?;
+        // SAFETY: forwarded caller contract
+        unsafe { write_string(out_json, json) }
+    })
+}
+
+/// Write the document's checksum to `out_checksum`.
+///
+/// # Safety
+///
+/// `dna` must be a live handle and `out_checksum` valid for a pointer write.
+#[no_mangle]
+pub unsafe extern "C" fn entropic_dna_checksum(
+    dna: *const EntropicGameDna,
+    out_checksum: *mut *mut c_char,
+) -> EntropicStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract
+        let dna = unsafe { dna_ref(dna) }?;
+        // SAFETY: forwarded caller contract
+        unsafe { write_string(out_checksum, checksum::generate_checksum(dna)) }
+    })
+}
@@ -0,0 +1,123 @@
+//! Status codes, per-thread error messages and string ownership
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Result of a fallible FFI call
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntropicStatus {
+    /// The call succeeded
+    Ok = 0,
+    /// A required pointer argument was null
+    NullPointer = 1,
+    /// A string argument was not valid UTF-8
+    InvalidUtf8 = 2,
+    /// A document could not be parsed
+    ParseError = 3,
+    /// A value could not be serialized
+    SerializationError = 4,
+    /// An argument was out of range
+    InvalidArgument = 5,
+    /// The library panicked; the handle involved should be freed and not reused
+    Panic = 6,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Message describing the last failed call on this thread, or null when there is none.
+///
+/// The pointer stays valid until the next failing call on the same thread; do not free it.
+#[no_mangle]
+pub extern "C" fn entropic_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| {
+        e.borrow()
+            .as_ref()
+            .map_or(std::ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Release a string returned by this library. Null is ignored.
+///
+/// # Safety
+///
+/// `s` must be null or a string returned by this library that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn entropic_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        // SAFETY: the caller guarantees `s` came from `CString::into_raw` in `into_c_string`
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+/// An FFI failure: the status to return and the message to record
+#[derive(Debug)]
+pub(crate) struct FfiError {
+    pub status: EntropicStatus,
+    pub message: String,
+}
+
+impl FfiError {
+    pub fn new(status: EntropicStatus, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    pub fn null(argument: &str) -> Self {
+        Self::new(EntropicStatus::NullPointer, format!("`{argument}` must not be null"))
+    }
+}
+
+/// Run `f`, recording any error or panic for [`entropic_last_error`]
+///
+/// A handle touched by a panicking call may be left half-updated, which is why
+/// [`EntropicStatus::Panic`] tells callers to stop using it.
+pub(crate) fn guard(f: impl FnOnce() -> Result<(), FfiError>) -> EntropicStatus {
+    let error = match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => return EntropicStatus::Ok,
+        Ok(Err(error)) => error,
+        Err(_) => FfiError::new(EntropicStatus::Panic, "entropic-ffi panicked"),
+    };
+
+    // Interior NULs can't occur in our messages, but never fail while reporting a failure
+    let message = CString::new(error.message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(message));
+    error.status
+}
+
+/// Borrow a NUL-terminated UTF-8 argument
+///
+/// # Safety
+///
+/// `s` must be null or point to a NUL-terminated string that outlives the returned borrow.
+pub(crate) unsafe fn read_str<'a>(s: *const c_char, argument: &str) -> Result<&'a str, FfiError> {
+    if s.is_null() {
+        return Err(FfiError::null(argument));
+    }
+    // SAFETY: non-null and NUL-terminated per the caller's contract
+    unsafe { CStr::from_ptr(s) }.to_str().map_err(|_| {
+        FfiError::new(EntropicStatus::InvalidUtf8, format!("`{argument}` is not valid UTF-8"))
+    })
+}
+
+/// Hand an owned string to the caller through `out`
+///
+/// # Safety
+///
+/// `out` must be null or valid for a pointer write.
+pub(crate) unsafe fn write_string(out: *mut *mut c_char, s: String) -> Result<(), FfiError> {
+    if out.is_null() {
+        return Err(FfiError::null("out"));
+    }
+    let s = CString::new(s).map_err(|_| {
+        FfiError::new(EntropicStatus::SerializationError, "output contains a NUL byte")
+    })?;
+    // SAFETY: non-null and writable per the caller's contract
+    unsafe { out.write(s.into_raw()) };
+    Ok(())
+}
@@ -0,0 +1,40 @@
+//! Stable C ABI for the ENTROPIC engine
+//!
+//! Native engine plugins (Unreal, Unity) link against this crate instead of reimplementing
+//! the Game DNA schema and world model. The ABI follows a few fixed rules, mirrored in
+//! `include/entropic.h`:
+//!
+//! - Every fallible function returns an [`EntropicStatus`]; on failure the message is
+//!   available from [`entropic_last_error`] on the same thread.
+//! - Documents and worlds are opaque handles created by `*_parse` / `*_create` and released
+//!   with the matching `*_free`.
+//! - Strings passed in are NUL-terminated UTF-8; strings handed out are owned by the caller
+//!   and released with [`entropic_string_free`].
+//! - Panics never cross the boundary; they are reported as [`EntropicStatus::Panic`].
+
+#![deny(unsafe_op_in_unsafe_fn)]
+
+pub mod dna;
+pub mod error;
+pub mod world;
+
+pub use dna::EntropicGameDna;
+pub use error::{entropic_last_error, entropic_string_free, EntropicStatus};
+pub use world::EntropicWorld;
+
+use std::ffi::c_char;
+
+/// ABI version, bumped on every incompatible change to the exported functions
+pub const ENTROPIC_FFI_ABI_VERSION: u32 = 1;
+
+/// ABI version of the loaded library, for plugins to check against their header
+#[no_mangle]
+pub extern "C" fn entropic_abi_version() -> u32 {
+    ENTROPIC_FFI_ABI_VERSION
+}
+
+/// Crate version of the loaded library as a static NUL-terminated string; do not free it
+#[no_mangle]
+pub extern "C" fn entropic_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
@@ -0,0 +1,285 @@
+//! World creation, ticking and spatial queries
+
+use std::ffi::c_char;
+
+use entropic_world_core::{
+    population::{Entity, EntityType},
+    serialization::{deserialize_from_json, serialize_to_json_compact},
+    spatial::ChunkCoord,
+    World,
+};
+
+use crate::error::{guard, read_str, write_string, EntropicStatus, FfiError};
+
+/// Opaque handle to a simulated world
+#[derive(Debug)]
+pub struct EntropicWorld {
+    pub(crate) world: World,
+}
+
+/// Counters describing a world, filled by [`entropic_world_stats`]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EntropicWorldStats {
+    /// Ticks simulated so far
+    pub current_tick: u64,
+    /// Chunks stored in the world
+    pub chunks: u64,
+    /// Entities in the world
+    pub entities: u64,
+    /// NPCs in the world
+    pub npcs: u64,
+}
+
+/// Borrow the world behind a handle
+///
+/// # Safety
+///
+/// `world` must be null or a live handle.
+unsafe fn world_ref<'a>(world: *const EntropicWorld) -> Result<&'a World, FfiError> {
+    // SAFETY: null or live per the caller's contract
+    unsafe { world.as_ref() }
+        .map(|handle| &handle.world)
+        .ok_or_else(|| FfiError::null("world"))
+}
+
+/// Mutably borrow the world behind a handle
+///
+/// # Safety
+///
+/// `world` must be null or a live handle not borrowed elsewhere.
+unsafe fn world_mut<'a>(world: *mut EntropicWorld) -> Result<&'a mut World, FfiError> {
+    // SAFETY: null or live and unaliased per the caller's contract
+    unsafe { world.as_mut() }
+        .map(|handle| &mut handle.world)
+        .ok_or_else(|| FfiError::null("world"))
+}
+
+/// Hand a new world handle to the caller through `out_world`
+///
+/// # Safety
+///
+/// `out_world` must be null or valid for a pointer write.
+unsafe fn write_world(out_world: *mut *mut EntropicWorld, world: World) -> Result<(), FfiError> {
+    if out_world.is_null() {
+        return Err(FfiError::null("out_world"));
+    }
+    let handle = Box::into_raw(Box::new(EntropicWorld { world }));
+    // SAFETY: checked non-null above, writable per the caller's contract
+    unsafe { out_world.write(handle) };
+    Ok(())
+}
+
+/// Create a world of `width_chunks * height_chunks` initialized chunks.
+///
+/// # Safety
+///
+/// `name` and `game_dna_id` must be NUL-terminated strings and `out_world` valid for a
+/// pointer write.
+#[no_mangle]
+pub unsafe extern "C" fn entropic_world_create(
+    name: *const c_char,
+    game_dna_id: *const c_char,
+    width_chunks: u32,
+    height_chunks: u32,
+    out_world: *mut *mut EntropicWorld,
+) -> EntropicStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract
+        let name = unsafe { read_str(name, "name") }?;
+        // SAFETY: forwarded caller contract
+        let game_dna_id = unsafe { read_str(game_dna_id, "game_dna_id") }?;
+        if width_chunks == 0 || height_chunks == 0 {
+            return Err(FfiError::new(
+                EntropicStatus::InvalidArgument,
+                "a world needs at least one chunk on each axis",
+            ));
+        }
+
+        let mut world =
+            World::new(name.to_string(), game_dna_id.to_string(), width_chunks, height_chunks);
+        world.initialize_chunks();
+        // SAFETY: forwarded caller contract
+        unsafe { write_world(out_world, world) }
+    })
+}
+
+/// Restore a world from JSON written by [`entropic_world_to_json`].
+///
+/// # Safety
+///
+/// `json` must be a NUL-terminated string and `out_world` valid for a pointer write.
+#[no_mangle]
+pub unsafe extern "C" fn entropic_world_from_json(
+    json: *const c_char,
+    out_world: *mut *mut EntropicWorld,
+) -> EntropicStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract
+        let json = unsafe { read_str(json, "json") }?;
+        let world = deserialize_from_json(json)
+            .map_err(|e| FfiError::new(EntropicStatus::ParseError, e.to_string()))?;
+        // SAFETY: forwarded caller contract
+        unsafe { write_world(out_world, world) }
+    })
+}
+
+/// Release a world handle. Null is ignored.
+///
+/// # Safety
+///
+/// `world` must be null or a handle from this library that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn entropic_world_free(world: *mut EntropicWorld) {
+    if !world.is_null() {
+        // SAFETY: the handle came from `Box::into_raw` in `write_world`
+        drop(unsafe { Box::from_raw(world) });
+    }
+}
+
+/// Advance the simulation by `ticks` ticks.
+///
+/// # Safety
+///
+/// `world` must be a live handle not used by another thread during the call.
+#[no_mangle]
+pub unsafe extern "C" fn entropic_world_tick(
+    world: *mut EntropicWorld,
+    ticks: u32,
+) -> EntropicStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract
+        let world = unsafe { world_mut(world) }?;
+        for _ in 0..ticks {
+            world.advance_tick();
+        }
+        Ok(())
+    })
+}
+
+/// Fill `out_stats` with the world's counters.
+///
+/// # Safety
+///
+/// `world` must be a live handle and `out_stats` valid for a write.
+#[no_mangle]
+pub unsafe extern "C" fn entropic_world_stats(
+    world: *const EntropicWorld,
+    out_stats: *mut EntropicWorldStats,
+) -> EntropicStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract
+        let world = unsafe { world_ref(world) }?;
+        if out_stats.is_null() {
+            return Err(FfiError::null("out_stats"));
+        }
+        let stats = EntropicWorldStats {
+            current_tick: world.current_tick,
+            chunks: world.total_chunks() as u64,
+            entities: world.total_entities() as u64,
+            npcs: world.total_npcs() as u64,
+        };
+        // SAFETY: checked non-null above, writable per the caller's contract
+        unsafe { out_stats.write(stats) };
+        Ok(())
+    })
+}
+
+/// Add an entity at a world position.
+///
+/// `entity_type` is 0 = NPC, 1 = Animal, 2 = Structure, 3 = Item, 4 = Effect.
+///
+/// # Safety
+///
+/// `world` must be a live handle not used by another thread during the call, and `id` a
+/// NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn entropic_world_add_entity(
+    world: *mut EntropicWorld,
+    id: *const c_char,
+    entity_type: u32,
+    x: f32,
+    y: f32,
+    z: f32,
+) -> EntropicStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract
+        let world = unsafe { world_mut(world) }?;
+        // SAFETY: forwarded caller contract
+        let id = unsafe { read_str(id, "id") }?;
+        let entity_type = match entity_type {
+            0 => EntityType::NPC,
+            1 => EntityType::Animal,
+            2 => EntityType::Structure,
+            3 => EntityType::Item,
+            4 => EntityType::Effect,
+            other => {
+                return Err(FfiError::new(
+                    EntropicStatus::InvalidArgument,
+                    format!("unknown entity type {other}"),
+                ))
+            }
+        };
+
+        let chunk = ChunkCoord::from_world_position(x, y);
+        if !world.contains_chunk_coord(&chunk) {
+            return Err(FfiError::new(
+                EntropicStatus::InvalidArgument,
+                format!("position ({x}, {y}) is outside the world"),
+            ));
+        }
+        world.add_entity(Entity::new(id.to_string(), entity_type, x, y, z, chunk));
+        Ok(())
+    })
+}
+
+/// Write the IDs of entities within `radius` of `(x, y)` to `out_json` as a JSON array.
+///
+/// # Safety
+///
+/// `world` must be a live handle and `out_json` valid for a pointer write.
+#[no_mangle]
+pub unsafe extern "C" fn entropic_world_query_radius(
+    world: *const EntropicWorld,
+    x: f32,
+    y: f32,
+    radius: f32,
+    out_json: *mut *mut c_char,
+) -> EntropicStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract
+        let world = unsafe { world_ref(world) }?;
+        if radius.is_nan() || radius < 0.0 {
+            return Err(FfiError::new(
+                EntropicStatus::InvalidArgument,
+                "radius must be a non-negative number",
+            ));
+        }
+        let mut ids = world.spatial_index.query_radius(x, y, radius);
+        ids.sort();
+        let json = serde_json::to_string(&ids)
+            .map_err(|e| FfiError::new(EntropicStatus::SerializationError, e.to_string()))?;
+        // SAFETY: forwarded caller contract
+        unsafe { write_string(out_json, json) }
+    })
+}
+
+/// Write the full world state to `out_json` as compact JSON.
+///
+/// # Safety
+///
+/// `world` must be a live handle and `out_json` valid for a pointer write.
+#[no_mangle]
+pub unsafe extern "C" fn entropic_world_to_json(
+    world: *const EntropicWorld,
+    out_json: *mut *mut c_char,
+) -> EntropicStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract
+        let world = unsafe { world_ref(world) }?;
+        let json = serialize_to_json_compact(world)
+            .map_err(|e| FfiError::new(EntropicStatus::SerializationError, e.to_string()))?;
+        // SAFETY: forwarded caller contract
+        unsafe { write_string(out_json, json) }
+    })
+}
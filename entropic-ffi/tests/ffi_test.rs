@@ -0,0 +1,189 @@
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::ptr;
+
+use entropic_dna_core::schema::{
+    CameraMode, DifficultyMode, Genre, MonetizationModel, PhysicsProfile, TargetPlatform, Tone,
+    WorldScale,
+};
+use entropic_dna_core::serialization::to_json_string;
+use entropic_dna_core::GameDNA;
+use entropic_ffi::dna::*;
+use entropic_ffi::world::*;
+use entropic_ffi::*;
+
+fn sample_dna_json() -> CString {
+    let dna = GameDNA::builder()
+        .name("FFI Game".to_string())
+        .genre(Genre::RPG)
+        .camera(CameraMode::Perspective3D)
+        .tone(Tone::Stylized)
+        .world_scale(WorldScale::OpenWorld)
+        .target_platforms(vec![TargetPlatform::PC])
+        .physics_profile(PhysicsProfile::Arcade)
+        .monetization(MonetizationModel::PremiumBuy)
+        .difficulty(DifficultyMode::Medium)
+        .target_fps(60)
+        .build()
+        .unwrap();
+    CString::new(to_json_string(&dna).unwrap()).unwrap()
+}
+
+/// Take ownership of a string handed out by the library
+unsafe fn take_string(s: *mut c_char) -> String {
+    let owned = CStr::from_ptr(s).to_str().unwrap().to_string();
+    entropic_string_free(s);
+    owned
+}
+
+fn last_error() -> String {
+    unsafe { CStr::from_ptr(entropic_last_error()) }
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn test_abi_version() {
+    assert_eq!(entropic_abi_version(), ENTROPIC_FFI_ABI_VERSION);
+    let version = unsafe { CStr::from_ptr(entropic_version()) };
+    assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+fn test_dna_round_trip() {
+    let json = sample_dna_json();
+    unsafe {
+        let mut dna = ptr::null_mut();
+        assert_eq!(entropic_dna_parse(json.as_ptr(), &mut dna), EntropicStatus::Ok);
+
+        let mut is_valid = false;
+        let mut report = ptr::null_mut();
+        assert_eq!(entropic_dna_validate(dna, &mut is_valid, &mut report), EntropicStatus::Ok);
+        assert!(is_valid);
+        let report: serde_json::Value = serde_json::from_str(&take_string(report)).unwrap();
+        assert_eq!(report["is_valid"], true);
+
+        let mut serialized = ptr::null_mut();
+        assert_eq!(entropic_dna_serialize(dna, &mut serialized), EntropicStatus::Ok);
+        let serialized = CString::new(take_string(serialized)).unwrap();
+
+        let mut checksum = ptr::null_mut();
+        assert_eq!(entropic_dna_checksum(dna, &mut checksum), EntropicStatus::Ok);
+        let checksum = take_string(checksum);
+
+        // Reparsing the serialized document yields the same checksum
+        let mut reparsed = ptr::null_mut();
+        assert_eq!(entropic_dna_parse(serialized.as_ptr(), &mut reparsed), EntropicStatus::Ok);
+        let mut reparsed_checksum = ptr::null_mut();
+        assert_eq!(entropic_dna_checksum(reparsed, &mut reparsed_checksum), EntropicStatus::Ok);
+        assert_eq!(take_string(reparsed_checksum), checksum);
+
+        entropic_dna_free(reparsed);
+        entropic_dna_free(dna);
+    }
+}
+
+#[test]
+fn test_dna_errors_are_reported() {
+    let garbage = CString::new("{ not json").unwrap();
+    unsafe {
+        let mut dna = ptr::null_mut();
+        assert_eq!(entropic_dna_parse(garbage.as_ptr(), &mut dna), EntropicStatus::ParseError);
+        assert!(dna.is_null());
+        assert!(!last_error().is_empty());
+
+        assert_eq!(entropic_dna_parse(ptr::null(), &mut dna), EntropicStatus::NullPointer);
+        assert!(last_error().contains("json"));
+
+        let mut checksum = ptr::null_mut();
+        assert_eq!(entropic_dna_checksum(ptr::null(), &mut checksum), EntropicStatus::NullPointer);
+    }
+}
+
+#[test]
+fn test_world_lifecycle() {
+    let name = CString::new("Overworld").unwrap();
+    let dna_id = CString::new("game_dna_1").unwrap();
+    let tree = CString::new("tree_1").unwrap();
+    let rock = CString::new("rock_1").unwrap();
+    unsafe {
+        let mut world = ptr::null_mut();
+        assert_eq!(
+            entropic_world_create(name.as_ptr(), dna_id.as_ptr(), 4, 4, &mut world),
+            EntropicStatus::Ok
+        );
+
+        assert_eq!(
+            entropic_world_add_entity(world, tree.as_ptr(), 2, 100.0, 100.0, 0.0),
+            EntropicStatus::Ok
+        );
+        assert_eq!(
+            entropic_world_add_entity(world, rock.as_ptr(), 3, 600.0, 600.0, 0.0),
+            EntropicStatus::Ok
+        );
+        assert_eq!(entropic_world_tick(world, 5), EntropicStatus::Ok);
+
+        let mut stats = EntropicWorldStats::default();
+        assert_eq!(entropic_world_stats(world, &mut stats), EntropicStatus::Ok);
+        assert_eq!(stats.current_tick, 5);
+        assert_eq!(stats.chunks, 16);
+        assert_eq!(stats.entities, 2);
+
+        let mut ids = ptr::null_mut();
+        assert_eq!(
+            entropic_world_query_radius(world, 100.0, 100.0, 50.0, &mut ids),
+            EntropicStatus::Ok
+        );
+        let ids: Vec<String> = serde_json::from_str(&take_string(ids)).unwrap();
+        assert_eq!(ids, vec!["tree_1".to_string()]);
+
+        let mut json = ptr::null_mut();
+        assert_eq!(entropic_world_to_json(world, &mut json), EntropicStatus::Ok);
+        let json = CString::new(take_string(json)).unwrap();
+        let mut restored = ptr::null_mut();
+        assert_eq!(entropic_world_from_json(json.as_ptr(), &mut restored), EntropicStatus::Ok);
+        let mut restored_stats = EntropicWorldStats::default();
+        assert_eq!(entropic_world_stats(restored, &mut restored_stats), EntropicStatus::Ok);
+        assert_eq!(restored_stats, stats);
+
+        entropic_world_free(restored);
+        entropic_world_free(world);
+    }
+}
+
+#[test]
+fn test_world_rejects_invalid_arguments() {
+    let name = CString::new("Tiny").unwrap();
+    let dna_id = CString::new("game_dna_1").unwrap();
+    let id = CString::new("far_away").unwrap();
+    unsafe {
+        let mut world = ptr::null_mut();
+        assert_eq!(
+            entropic_world_create(name.as_ptr(), dna_id.as_ptr(), 0, 4, &mut world),
+            EntropicStatus::InvalidArgument
+        );
+
+        assert_eq!(
+            entropic_world_create(name.as_ptr(), dna_id.as_ptr(), 1, 1, &mut world),
+            EntropicStatus::Ok
+        );
+        assert_eq!(
+            entropic_world_add_entity(world, id.as_ptr(), 0, 10_000.0, 10_000.0, 0.0),
+            EntropicStatus::InvalidArgument
+        );
+        assert_eq!(
+            entropic_world_add_entity(world, id.as_ptr(), 99, 1.0, 1.0, 0.0),
+            EntropicStatus::InvalidArgument
+        );
+        assert!(last_error().contains("99"));
+
+        let mut ids = ptr::null_mut();
+        assert_eq!(
+            entropic_world_query_radius(world, 0.0, 0.0, f32::NAN, &mut ids),
+            EntropicStatus::InvalidArgument
+        );
+
+        entropic_world_free(world);
+    }
+}
@@ -0,0 +1,357 @@
+//! Transport-independent operations behind the RPC endpoints
+//!
+//! [`Engine`] owns the worlds hosted by the server and implements every endpoint as a
+//! plain method, so the gRPC layer only converts messages and errors.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+
+use serde::Serialize;
+use thiserror::Error;
+
+use entropic_dna_core::{
+    schema::GameDNA,
+    serialization::to_json_string,
+    validation::{checksum, LockedGameDNA, ValidationEngine, ValidationResult},
+    version::MigrationManager,
+};
+use entropic_world_core::{serialization::serialize_to_json_compact, World};
+
+/// Errors returned by [`Engine`] operations
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum EngineError {
+    #[error("Invalid Game DNA: {0}")]
+    InvalidDna(String),
+
+    #[error("Migration failed: {0}")]
+    MigrationFailed(String),
+
+    #[error("World not found: {0}")]
+    WorldNotFound(String),
+
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error("Serialization error: {0}")]
+    Serialization(String),
+}
+
+pub type Result<T> = std::result::Result<T, EngineError>;
+
+/// Resource limits protecting a shared server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EngineLimits {
+    /// Largest `width_chunks * height_chunks` a created world may have
+    pub max_world_chunks: u64,
+    /// Most ticks a single `advance_ticks` call may run
+    pub max_ticks_per_call: u32,
+    /// Most worlds hosted at once
+    pub max_worlds: usize,
+}
+
+impl Default for EngineLimits {
+    fn default() -> Self {
+        Self {
+            max_world_chunks: 64 * 64,
+            max_ticks_per_call: 10_000,
+            max_worlds: 256,
+        }
+    }
+}
+
+/// Result of locking a document
+#[derive(Debug, Clone)]
+pub struct LockOutcome {
+    /// Validation report for the document
+    pub validation: ValidationResult,
+    /// The locked document, present when validation passed
+    pub locked: Option<LockedGameDNA>,
+}
+
+/// Result of migrating a document
+#[derive(Debug, Clone)]
+pub struct MigrationOutcome {
+    /// The migrated document
+    pub dna: GameDNA,
+    /// Version the document was written with
+    pub from_version: String,
+    /// Migration steps that ran, as `"FROM -> TO"`
+    pub steps: Vec<String>,
+}
+
+/// Counters describing a hosted world
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WorldSummary {
+    pub world_id: String,
+    pub name: String,
+    pub game_dna_id: String,
+    pub width_chunks: u32,
+    pub height_chunks: u32,
+    pub current_tick: u64,
+    pub chunks: u64,
+    pub entities: u64,
+    pub npcs: u64,
+}
+
+impl WorldSummary {
+    fn of(world: &World) -> Self {
+        Self {
+            world_id: world.id.clone(),
+            name: world.name.clone(),
+            game_dna_id: world.game_dna_id.clone(),
+            width_chunks: world.width_chunks,
+            height_chunks: world.height_chunks,
+            current_tick: world.current_tick,
+            chunks: world.total_chunks() as u64,
+            entities: world.total_entities() as u64,
+            npcs: world.total_npcs() as u64,
+        }
+    }
+}
+
+/// Validation, migration and hosted world simulation
+#[derive(Debug, Default)]
+pub struct Engine {
+    limits: EngineLimits,
+    worlds: RwLock<HashMap<String, Arc<Mutex<World>>>>,
+}
+
+impl Engine {
+    /// Create an engine with the default limits
+    pub fn new() -> Self {
+        Self::with_limits(EngineLimits::default())
+    }
+
+    /// Create an engine with custom limits
+    pub fn with_limits(limits: EngineLimits) -> Self {
+        Self {
+            limits,
+            worlds: RwLock::default(),
+        }
+    }
+
+    /// Limits enforced by this engine
+    pub fn limits(&self) -> EngineLimits {
+        self.limits
+    }
+
+    /// Run the validation engine on a JSON document
+    pub fn validate_dna(&self, dna_json: &str) -> Result<ValidationResult> {
+        Ok(ValidationEngine::new().validate(&parse_dna(dna_json)?))
+    }
+
+    /// Rewrite a JSON document as normalized, deterministic JSON
+    pub fn serialize_dna(&self, dna_json: &str) -> Result<String> {
+        to_json_string(&parse_dna(dna_json)?).map_err(|e| EngineError::Serialization(e.to_string()))
+    }
+
+    /// Checksum of a JSON document
+    pub fn checksum_dna(&self, dna_json: &str) -> Result<String> {
+        Ok(checksum::generate_checksum(&parse_dna(dna_json)?))
+    }
+
+    /// Validate a JSON document and lock it when it passes
+    pub fn lock_dna(&self, dna_json: &str) -> Result<LockOutcome> {
+        let dna = parse_dna(dna_json)?;
+        let validation = ValidationEngine::new().validate(&dna);
+        let locked = validation.is_valid.then(|| LockedGameDNA::new(dna));
+        Ok(LockOutcome { validation, locked })
+    }
+
+    /// Migrate a JSON document to `target_version`
+    pub fn migrate_dna(&self, dna_json: &str, target_version: &str) -> Result<MigrationOutcome> {
+        let dna = parse_dna(dna_json)?;
+        let from_version = dna.version.to_string();
+        let migrations = MigrationManager::with_builtin_migrations();
+        let steps = migrations
+            .find_migration_path(&from_version, target_version)
+            .map(|path| {
+                path.iter()
+                    .map(|m| format!("{} -> {}", m.from_version(), m.to_version()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let dna = migrations
+            .migrate(dna, target_version)
+            .map_err(|e| EngineError::MigrationFailed(e.to_string()))?;
+        Ok(MigrationOutcome {
+            dna,
+            from_version,
+            steps,
+        })
+    }
+
+    /// Create and host a world with initialized chunks
+    pub fn create_world(
+        &self,
+        name: &str,
+        game_dna_id: &str,
+        width_chunks: u32,
+        height_chunks: u32,
+        seed: Option<u64>,
+    ) -> Result<WorldSummary> {
+        let chunks = u64::from(width_chunks) * u64::from(height_chunks);
+        if chunks == 0 || chunks > self.limits.max_world_chunks {
+            return Err(EngineError::InvalidArgument(format!(
+                "a world must have between 1 and {} chunks, got {width_chunks}x{height_chunks}",
+                self.limits.max_world_chunks
+            )));
+        }
+
+        let mut world =
+            World::new(name.to_string(), game_dna_id.to_string(), width_chunks, height_chunks);
+        if let Some(seed) = seed {
+            world.reseed(seed);
+        }
+        world.initialize_chunks();
+        let summary = WorldSummary::of(&world);
+
+        let mut worlds = self.worlds.write().unwrap_or_else(|e| e.into_inner());
+        if worlds.len() >= self.limits.max_worlds {
+            return Err(EngineError::InvalidArgument(format!(
+                "the server already hosts {} worlds",
+                self.limits.max_worlds
+            )));
+        }
+        worlds.insert(summary.world_id.clone(), Arc::new(Mutex::new(world)));
+        Ok(summary)
+    }
+
+    /// Advance a hosted world by `ticks` ticks.
+    ///
+    /// Only the world itself is locked while it runs, so other worlds stay available.
+    pub fn advance_ticks(&self, world_id: &str, ticks: u32) -> Result<WorldSummary> {
+        if ticks > self.limits.max_ticks_per_call {
+            return Err(EngineError::InvalidArgument(format!(
+                "at most {} ticks can be advanced per call",
+                self.limits.max_ticks_per_call
+            )));
+        }
+        let world = self.world(world_id)?;
+        let mut world = world.lock().unwrap_or_else(|e| e.into_inner());
+        for _ in 0..ticks {
+            world.advance_tick();
+        }
+        Ok(WorldSummary::of(&world))
+    }
+
+    /// Describe a hosted world, listing the sorted IDs of entities within `radius` of
+    /// `(x, y)` when `near` is given
+    pub fn query_world(
+        &self,
+        world_id: &str,
+        near: Option<(f32, f32, f32)>,
+    ) -> Result<(WorldSummary, Vec<String>)> {
+        let world = self.world(world_id)?;
+        let world = world.lock().unwrap_or_else(|e| e.into_inner());
+        let mut entity_ids = Vec::new();
+        if let Some((x, y, radius)) = near {
+            if radius.is_nan() || radius < 0.0 {
+                return Err(EngineError::InvalidArgument(
+                    "radius must be a non-negative number".to_string(),
+                ));
+            }
+            entity_ids = world.spatial_index.query_radius(x, y, radius);
+            entity_ids.sort();
+        }
+        Ok((WorldSummary::of(&world), entity_ids))
+    }
+
+    /// Full state of a hosted world as compact JSON
+    pub fn world_snapshot(&self, world_id: &str) -> Result<String> {
+        let world = self.world(world_id)?;
+        let world = world.lock().unwrap_or_else(|e| e.into_inner());
+        serialize_to_json_compact(&world).map_err(|e| EngineError::Serialization(e.to_string()))
+    }
+
+    fn world(&self, world_id: &str) -> Result<Arc<Mutex<World>>> {
+        self.worlds
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(world_id)
+            .cloned()
+            .ok_or_else(|| EngineError::WorldNotFound(world_id.to_string()))
+    }
+}
+
+/// Parse a document of any schema version, leaving version checks to the caller
+fn parse_dna(dna_json: &str) -> Result<GameDNA> {
+    serde_json::from_str(dna_json).map_err(|e| EngineError::InvalidDna(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entropic_dna_core::schema::{Genre, TargetPlatform};
+    use entropic_dna_core::serialization::to_json_string;
+
+    fn dna_json(name: &str) -> String {
+        let dna = GameDNA::minimal(name.to_string(), Genre::RPG, vec![TargetPlatform::PC]);
+        to_json_string(&dna).unwrap()
+    }
+
+    #[test]
+    fn test_validate_and_lock() {
+        let engine = Engine::new();
+        assert!(engine.validate_dna(&dna_json("Game")).unwrap().is_valid);
+        assert!(matches!(engine.validate_dna("{}"), Err(EngineError::InvalidDna(_))));
+
+        let outcome = engine.lock_dna(&dna_json("Game")).unwrap();
+        let locked = outcome.locked.unwrap();
+        assert!(locked.is_locked);
+        assert!(!locked.checksum.is_empty());
+    }
+
+    #[test]
+    fn test_serialize_and_checksum() {
+        let engine = Engine::new();
+        let json = dna_json("Game");
+        let normalized = engine.serialize_dna(&json).unwrap();
+        assert_eq!(engine.serialize_dna(&normalized).unwrap(), normalized);
+        assert_eq!(
+            engine.checksum_dna(&normalized).unwrap(),
+            engine.checksum_dna(&json).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_world_lifecycle() {
+        let engine = Engine::new();
+        let created = engine.create_world("Hosted", "dna_1", 4, 4, Some(7)).unwrap();
+        assert_eq!(created.chunks, 16);
+
+        let advanced = engine.advance_ticks(&created.world_id, 10).unwrap();
+        assert_eq!(advanced.current_tick, 10);
+
+        let (summary, ids) = engine
+            .query_world(&created.world_id, Some((0.0, 0.0, 100.0)))
+            .unwrap();
+        assert_eq!(summary, advanced);
+        assert!(ids.is_empty());
+
+        let snapshot: serde_json::Value =
+            serde_json::from_str(&engine.world_snapshot(&created.world_id).unwrap()).unwrap();
+        assert_eq!(snapshot["current_tick"], 10);
+
+        assert!(matches!(
+            engine.advance_ticks("missing", 1),
+            Err(EngineError::WorldNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_limits_are_enforced() {
+        let engine = Engine::with_limits(EngineLimits {
+            max_world_chunks: 4,
+            max_ticks_per_call: 5,
+            max_worlds: 1,
+        });
+        assert!(engine.create_world("Big", "dna_1", 3, 3, None).is_err());
+        assert!(engine.create_world("Flat", "dna_1", 0, 3, None).is_err());
+
+        let world = engine.create_world("Small", "dna_1", 2, 2, None).unwrap();
+        assert!(engine.create_world("Second", "dna_1", 1, 1, None).is_err());
+        assert!(engine.advance_ticks(&world.world_id, 6).is_err());
+        assert!(engine.query_world(&world.world_id, Some((0.0, 0.0, -1.0))).is_err());
+    }
+}
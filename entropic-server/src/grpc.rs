@@ -0,0 +1,269 @@
+//! gRPC endpoints defined in `proto/entropic/engine/v1/engine.proto`
+
+use std::sync::Arc;
+
+use tonic::{Request, Response, Status};
+
+use entropic_dna_core::{serialization::to_json_string, validation::ValidationResult};
+
+use crate::engine::{Engine, EngineError, WorldSummary};
+use crate::proto::{
+    self, engine_service_server::EngineServiceServer, AdvanceTicksRequest, CreateWorldRequest,
+    LockDnaRequest, LockDnaResponse, MigrateDnaRequest, MigrateDnaResponse, QueryWorldRequest,
+    QueryWorldResponse, ValidateDnaRequest, ValidateDnaResponse, ValidationIssue,
+};
+
+/// gRPC front end for an [`Engine`]
+#[derive(Debug, Clone)]
+pub struct EngineService {
+    engine: Arc<Engine>,
+}
+
+impl EngineService {
+    /// Serve `engine` over gRPC
+    pub fn new(engine: Arc<Engine>) -> Self {
+        Self { engine }
+    }
+
+    /// Wrap the service for `tonic::transport::Server::add_service`
+    pub fn into_server(self) -> EngineServiceServer<Self> {
+        EngineServiceServer::new(self)
+    }
+
+    /// Run a blocking engine call off the async runtime
+    async fn run<T, F>(&self, f: F) -> Result<T, Status>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Engine) -> Result<T, EngineError> + Send + 'static,
+    {
+        let engine = Arc::clone(&self.engine);
+        tokio::task::spawn_blocking(move || f(&engine))
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .map_err(status)
+    }
+}
+
+#[tonic::async_trait]
+impl proto::engine_service_server::EngineService for EngineService {
+    async fn validate_dna(
+        &self,
+        request: Request<ValidateDnaRequest>,
+    ) -> Result<Response<ValidateDnaResponse>, Status> {
+        let dna_json = request.into_inner().dna_json;
+        let result = self.run(move |engine| engine.validate_dna(&dna_json)).await?;
+        Ok(Response::new(validation_response(result)))
+    }
+
+    async fn lock_dna(
+        &self,
+        request: Request<LockDnaRequest>,
+    ) -> Result<Response<LockDnaResponse>, Status> {
+        let dna_json = request.into_inner().dna_json;
+        let outcome = self.run(move |engine| engine.lock_dna(&dna_json)).await?;
+
+        let mut response = LockDnaResponse {
+            validation: Some(validation_response(outcome.validation)),
+            ..Default::default()
+        };
+        if let Some(locked) = outcome.locked {
+            response.locked = true;
+            response.dna_json = dna_to_json(&locked.config)?;
+            response.checksum = locked.checksum;
+            response.locked_at = locked.lock_timestamp.to_rfc3339();
+        }
+        Ok(Response::new(response))
+    }
+
+    async fn migrate_dna(
+        &self,
+        request: Request<MigrateDnaRequest>,
+    ) -> Result<Response<MigrateDnaResponse>, Status> {
+        let MigrateDnaRequest {
+            dna_json,
+            target_version,
+        } = request.into_inner();
+        let outcome = self
+            .run(move |engine| engine.migrate_dna(&dna_json, &target_version))
+            .await?;
+
+        Ok(Response::new(MigrateDnaResponse {
+            dna_json: dna_to_json(&outcome.dna)?,
+            from_version: outcome.from_version,
+            to_version: outcome.dna.version.to_string(),
+            steps: outcome.steps,
+        }))
+    }
+
+    async fn create_world(
+        &self,
+        request: Request<CreateWorldRequest>,
+    ) -> Result<Response<proto::WorldSummary>, Status> {
+        let request = request.into_inner();
+        let summary = self
+            .run(move |engine| {
+                engine.create_world(
+                    &request.name,
+                    &request.game_dna_id,
+                    request.width_chunks,
+                    request.height_chunks,
+                    request.seed,
+                )
+            })
+            .await?;
+        Ok(Response::new(summary.into()))
+    }
+
+    async fn advance_ticks(
+        &self,
+        request: Request<AdvanceTicksRequest>,
+    ) -> Result<Response<proto::WorldSummary>, Status> {
+        let AdvanceTicksRequest { world_id, ticks } = request.into_inner();
+        let summary = self
+            .run(move |engine| engine.advance_ticks(&world_id, ticks))
+            .await?;
+        Ok(Response::new(summary.into()))
+    }
+
+    async fn query_world(
+        &self,
+        request: Request<QueryWorldRequest>,
+    ) -> Result<Response<QueryWorldResponse>, Status> {
+        let QueryWorldRequest { world_id, near } = request.into_inner();
+        let near = near.map(|q| (q.x, q.y, q.radius));
+        let (summary, entity_ids) = self
+            .run(move |engine| engine.query_world(&world_id, near))
+            .await?;
+        Ok(Response::new(QueryWorldResponse {
+            world: Some(summary.into()),
+            entity_ids,
+        }))
+    }
+}
+
+impl From<WorldSummary> for proto::WorldSummary {
+    fn from(summary: WorldSummary) -> Self {
+        Self {
+            world_id: summary.world_id,
+            name: summary.name,
+            game_dna_id: summary.game_dna_id,
+            width_chunks: summary.width_chunks,
+            height_chunks: summary.height_chunks,
+            current_tick: summary.current_tick,
+            chunks: summary.chunks,
+            entities: summary.entities,
+            npcs: summary.npcs,
+        }
+    }
+}
+
+fn validation_response(result: ValidationResult) -> ValidateDnaResponse {
+    ValidateDnaResponse {
+        is_valid: result.is_valid,
+        errors: result
+            .errors
+            .into_iter()
+            .map(|e| ValidationIssue {
+                code: e.code,
+                field: e.field,
+                message: e.message,
+                detail: e.details,
+            })
+            .collect(),
+        warnings: result
+            .warnings
+            .into_iter()
+            .map(|w| ValidationIssue {
+                code: w.code,
+                field: w.field,
+                message: w.message,
+                detail: w.suggestion,
+            })
+            .collect(),
+        suggestions: result.suggestions,
+    }
+}
+
+fn dna_to_json(dna: &entropic_dna_core::GameDNA) -> Result<String, Status> {
+    to_json_string(dna).map_err(|e| Status::internal(e.to_string()))
+}
+
+fn status(error: EngineError) -> Status {
+    match error {
+        EngineError::InvalidDna(_) | EngineError::InvalidArgument(_) => {
+            Status::invalid_argument(error.to_string())
+        }
+        EngineError::MigrationFailed(_) => Status::failed_precondition(error.to_string()),
+        EngineError::WorldNotFound(_) => Status::not_found(error.to_string()),
+        EngineError::Serialization(_) => Status::internal(error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proto::engine_service_server::EngineService as _;
+    use proto::RadiusQuery;
+
+    #[tokio::test]
+    async fn test_world_endpoints() {
+        let service = EngineService::new(Arc::new(Engine::new()));
+        let created = service
+            .create_world(Request::new(CreateWorldRequest {
+                name: "Hosted".to_string(),
+                game_dna_id: "dna_1".to_string(),
+                width_chunks: 2,
+                height_chunks: 3,
+                seed: Some(1),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(created.chunks, 6);
+
+        let advanced = service
+            .advance_ticks(Request::new(AdvanceTicksRequest {
+                world_id: created.world_id.clone(),
+                ticks: 3,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(advanced.current_tick, 3);
+
+        let queried = service
+            .query_world(Request::new(QueryWorldRequest {
+                world_id: created.world_id,
+                near: Some(RadiusQuery {
+                    x: 0.0,
+                    y: 0.0,
+                    radius: 10.0,
+                }),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(queried.world, Some(advanced));
+    }
+
+    #[tokio::test]
+    async fn test_errors_map_to_status_codes() {
+        let service = EngineService::new(Arc::new(Engine::new()));
+        let err = service
+            .validate_dna(Request::new(ValidateDnaRequest {
+                dna_json: "not json".to_string(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+        let err = service
+            .advance_ticks(Request::new(AdvanceTicksRequest {
+                world_id: "missing".to_string(),
+                ticks: 1,
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+    }
+}
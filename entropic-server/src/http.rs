@@ -0,0 +1,220 @@
+//! JSON endpoints for internal tooling that can't link Rust directly
+//!
+//! | Method | Path | Body / query | Response |
+//! |--------|------|--------------|----------|
+//! | `POST` | `/api/v1/dna/validate` | GameDNA JSON | validation result |
+//! | `POST` | `/api/v1/dna/serialize` | GameDNA JSON | normalized GameDNA JSON |
+//! | `POST` | `/api/v1/dna/checksum` | GameDNA JSON | `{"checksum": ...}` |
+//! | `GET` | `/api/v1/worlds/:world_id` | | world summary |
+//! | `GET` | `/api/v1/worlds/:world_id/snapshot` | | full world state |
+//! | `GET` | `/api/v1/worlds/:world_id/entities` | `x`, `y`, `radius` | summary and entity IDs |
+//!
+//! Worlds are the ones hosted by the shared [`Engine`], e.g. created over gRPC. Errors are
+//! returned as `{"error": message}` with a matching status code.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use entropic_dna_core::validation::ValidationResult;
+
+use crate::engine::{Engine, EngineError, WorldSummary};
+
+/// Build the REST router for `engine`
+pub fn router(engine: Arc<Engine>) -> Router {
+    Router::new()
+        .route("/api/v1/dna/validate", post(validate_dna))
+        .route("/api/v1/dna/serialize", post(serialize_dna))
+        .route("/api/v1/dna/checksum", post(checksum_dna))
+        .route("/api/v1/worlds/:world_id", get(world_summary))
+        .route("/api/v1/worlds/:world_id/snapshot", get(world_snapshot))
+        .route("/api/v1/worlds/:world_id/entities", get(world_entities))
+        .with_state(engine)
+}
+
+/// Serve the REST endpoints on `addr` until the server fails
+pub async fn serve(engine: Arc<Engine>, addr: SocketAddr) -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, router(engine)).await
+}
+
+/// A failed request, rendered as a JSON error response
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub message: String,
+}
+
+impl From<EngineError> for ApiError {
+    fn from(error: EngineError) -> Self {
+        let status = match error {
+            EngineError::InvalidDna(_) | EngineError::InvalidArgument(_) => {
+                StatusCode::BAD_REQUEST
+            }
+            EngineError::MigrationFailed(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            EngineError::WorldNotFound(_) => StatusCode::NOT_FOUND,
+            EngineError::Serialization(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        Self {
+            status,
+            message: error.to_string(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = ErrorBody {
+            error: self.message,
+        };
+        (self.status, Json(body)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ChecksumBody {
+    checksum: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RadiusParams {
+    x: f32,
+    y: f32,
+    radius: f32,
+}
+
+#[derive(Debug, Serialize)]
+struct EntitiesBody {
+    world: WorldSummary,
+    entity_ids: Vec<String>,
+}
+
+/// Run a blocking engine call off the async runtime
+async fn run<T, F>(engine: Arc<Engine>, f: F) -> Result<T, ApiError>
+where
+    T: Send + 'static,
+    F: FnOnce(&Engine) -> Result<T, EngineError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || f(&engine))
+        .await
+        .map_err(|e| ApiError {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            message: e.to_string(),
+        })?
+        .map_err(ApiError::from)
+}
+
+/// Pre-serialized JSON with the right content type
+fn raw_json(json: String) -> Response {
+    ([(header::CONTENT_TYPE, "application/json")], json).into_response()
+}
+
+async fn validate_dna(
+    State(engine): State<Arc<Engine>>,
+    body: String,
+) -> Result<Json<ValidationResult>, ApiError> {
+    run(engine, move |engine| engine.validate_dna(&body)).await.map(Json)
+}
+
+async fn serialize_dna(
+    State(engine): State<Arc<Engine>>,
+    body: String,
+) -> Result<Response, ApiError> {
+    run(engine, move |engine| engine.serialize_dna(&body)).await.map(raw_json)
+}
+
+async fn checksum_dna(
+    State(engine): State<Arc<Engine>>,
+    body: String,
+) -> Result<Json<ChecksumBody>, ApiError> {
+    let checksum = run(engine, move |engine| engine.checksum_dna(&body)).await?;
+    Ok(Json(ChecksumBody { checksum }))
+}
+
+async fn world_summary(
+    State(engine): State<Arc<Engine>>,
+    Path(world_id): Path<String>,
+) -> Result<Json<WorldSummary>, ApiError> {
+    let (summary, _) = run(engine, move |engine| engine.query_world(&world_id, None)).await?;
+    Ok(Json(summary))
+}
+
+async fn world_snapshot(
+    State(engine): State<Arc<Engine>>,
+    Path(world_id): Path<String>,
+) -> Result<Response, ApiError> {
+    run(engine, move |engine| engine.world_snapshot(&world_id)).await.map(raw_json)
+}
+
+async fn world_entities(
+    State(engine): State<Arc<Engine>>,
+    Path(world_id): Path<String>,
+    Query(params): Query<RadiusParams>,
+) -> Result<Json<EntitiesBody>, ApiError> {
+    let near = Some((params.x, params.y, params.radius));
+    let (world, entity_ids) = run(engine, move |engine| engine.query_world(&world_id, near)).await?;
+    Ok(Json(EntitiesBody { world, entity_ids }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entropic_dna_core::schema::{Genre, TargetPlatform};
+    use entropic_dna_core::serialization::to_json_string;
+    use entropic_dna_core::GameDNA;
+
+    #[tokio::test]
+    async fn test_dna_endpoints() {
+        let engine = Arc::new(Engine::new());
+        let dna = GameDNA::minimal("Tool".to_string(), Genre::RPG, vec![TargetPlatform::PC]);
+        let json = to_json_string(&dna).unwrap();
+
+        let Json(result) = validate_dna(State(engine.clone()), json.clone()).await.unwrap();
+        assert!(result.is_valid);
+
+        let Json(body) = checksum_dna(State(engine.clone()), json).await.unwrap();
+        assert!(!body.checksum.is_empty());
+
+        let err = serialize_dna(State(engine), "{}".to_string()).await.unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_world_endpoints() {
+        let engine = Arc::new(Engine::new());
+        let created = engine.create_world("Hosted", "dna_1", 2, 2, None).unwrap();
+
+        let Json(summary) = world_summary(State(engine.clone()), Path(created.world_id.clone()))
+            .await
+            .unwrap();
+        assert_eq!(summary, created);
+
+        let params = RadiusParams {
+            x: 0.0,
+            y: 0.0,
+            radius: 50.0,
+        };
+        let Json(body) =
+            world_entities(State(engine.clone()), Path(created.world_id), Query(params))
+                .await
+                .unwrap();
+        assert!(body.entity_ids.is_empty());
+
+        let err = world_snapshot(State(engine), Path("missing".to_string())).await.unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+        assert_eq!(err.into_response().status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,26 @@
+//! RPC server for the ENTROPIC engine
+//!
+//! Lets non-Rust services validate, lock and migrate Game DNA documents and host world
+//! simulations through a stable RPC contract backed by `entropic-dna-core` and
+//! `entropic-world-core`.
+//!
+//! - [`engine`]: transport-independent operations and the hosted worlds
+//! - [`grpc`]: the `entropic.engine.v1.EngineService` gRPC endpoints (feature `grpc`,
+//!   on by default)
+//! - [`http`]: JSON endpoints for internal tooling (feature `http-server`)
+
+pub mod engine;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "http-server")]
+pub mod http;
+
+/// Messages and service stubs generated from `proto/entropic/engine/v1/engine.proto`
+#[cfg(feature = "grpc")]
+pub mod proto {
+    tonic::include_proto!("entropic.engine.v1");
+}
+
+pub use engine::{Engine, EngineError, EngineLimits, WorldSummary};
+#[cfg(feature = "grpc")]
+pub use grpc::EngineService;
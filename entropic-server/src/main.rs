@@ -0,0 +1,41 @@
+//! Standalone ENTROPIC RPC server
+//!
+//! Listens for gRPC on `ENTROPIC_GRPC_ADDR` (default `0.0.0.0:50051`) and, with the
+//! `http-server` feature, for REST on `ENTROPIC_HTTP_ADDR` (default `0.0.0.0:8080`). Both
+//! share the same hosted worlds.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use entropic_server::{Engine, EngineService};
+
+fn addr_from_env(var: &str, default: &str) -> Result<SocketAddr, std::net::AddrParseError> {
+    std::env::var(var).unwrap_or_else(|_| default.to_string()).parse()
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let engine = Arc::new(Engine::new());
+
+    let grpc_addr = addr_from_env("ENTROPIC_GRPC_ADDR", "0.0.0.0:50051")?;
+    println!("entropic-server: gRPC listening on {grpc_addr}");
+    let grpc = tonic::transport::Server::builder()
+        .add_service(EngineService::new(engine.clone()).into_server())
+        .serve(grpc_addr);
+
+    #[cfg(feature = "http-server")]
+    {
+        let http_addr = addr_from_env("ENTROPIC_HTTP_ADDR", "0.0.0.0:8080")?;
+        println!("entropic-server: REST listening on {http_addr}");
+        let http = entropic_server::http::serve(engine, http_addr);
+        // Either server stopping ends the process
+        tokio::select! {
+            result = grpc => result?,
+            result = http => result?,
+        }
+    }
+    #[cfg(not(feature = "http-server"))]
+    grpc.await?;
+
+    Ok(())
+}
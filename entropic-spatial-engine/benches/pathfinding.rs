@@ -1,5 +1,5 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use entropic_spatial_engine::{CollisionDetector, Pathfinder};
+use entropic_spatial_engine::{CollisionDetector, JumpPointTable, PathAlgorithm, Pathfinder};
 use entropic_world_core::World;
 
 fn bench_pathfinding(c: &mut Criterion) {
@@ -98,5 +98,63 @@ fn bench_pathfinding_performance(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_pathfinding, bench_pathfinding_performance);
+fn bench_jump_point_search(c: &mut Criterion) {
+    let mut world = World::new("Test".to_string(), "game1".to_string(), 10, 10);
+    world.initialize_chunks();
+
+    // Open terrain, where JPS skips over most cells
+    for chunk in world.chunks.values_mut() {
+        chunk.water_level = -100.0;
+        for i in 0..256 {
+            for j in 0..256 {
+                chunk.set_elevation_at(i, j, 100.0);
+            }
+        }
+    }
+
+    let start = (0.0, 0.0);
+    let goal = (2000.0, 1200.0);
+    let table = JumpPointTable::build(&world, (0.0, 0.0), (2559.0, 2559.0));
+
+    let mut group = c.benchmark_group("jump_point_search");
+
+    group.bench_function("astar_open_terrain", |b| {
+        b.iter(|| {
+            black_box(Pathfinder::find_path_with_algorithm(
+                &world,
+                start,
+                goal,
+                100_000,
+                PathAlgorithm::AStar,
+            ));
+        });
+    });
+
+    group.bench_function("jps_open_terrain", |b| {
+        b.iter(|| {
+            black_box(Pathfinder::find_path_with_algorithm(
+                &world,
+                start,
+                goal,
+                100_000,
+                PathAlgorithm::JumpPoint,
+            ));
+        });
+    });
+
+    group.bench_function("jps_plus_open_terrain", |b| {
+        b.iter(|| {
+            black_box(table.find_path(start, goal, 100_000));
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_pathfinding,
+    bench_pathfinding_performance,
+    bench_jump_point_search
+);
 criterion_main!(benches);
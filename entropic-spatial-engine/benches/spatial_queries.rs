@@ -1,5 +1,5 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use entropic_spatial_engine::{CollisionDetector, SpatialQueries};
+use entropic_spatial_engine::{Aabb, CollisionDetector, Frustum, SpatialQueries, CHUNK_SIZE};
 use entropic_world_core::{Entity, ChunkCoord, World, EntityType};
 
 fn bench_spatial_queries(c: &mut Criterion) {
@@ -55,6 +55,63 @@ fn bench_spatial_queries(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_region_queries(c: &mut Criterion) {
+    let mut world = World::new("Test".to_string(), "game1".to_string(), 10, 10);
+    world.initialize_chunks();
+
+    // Entities registered in their chunks and in the world spatial index
+    for i in 0..10_000 {
+        let x = (i as f32 * 37.3) % 2560.0;
+        let y = (i as f32 * 91.7) % 2560.0;
+        let chunk = ChunkCoord::new((x / CHUNK_SIZE) as u32, (y / CHUNK_SIZE) as u32);
+        world.add_entity(Entity::new(format!("entity_{}", i), EntityType::NPC, x, y, 0.0, chunk));
+    }
+
+    let region = Aabb::new([1000.0, 1000.0, -10.0], [1300.0, 1200.0, 10.0]);
+    let frustum = Frustum::perspective([1280.0, 1000.0, 20.0], [0.0, 1.0, -0.1], [0.0, 0.0, 1.0], 1.0, 16.0 / 9.0, 1.0, 400.0);
+
+    let mut group = c.benchmark_group("region_queries");
+
+    group.bench_function("k_nearest", |b| {
+        b.iter(|| {
+            black_box(SpatialQueries::k_nearest(&world, 1280.0, 1280.0, 16, 500.0));
+        });
+    });
+
+    group.bench_function("spatial_index_radius", |b| {
+        b.iter(|| {
+            black_box(world.spatial_index.query_radius(1280.0, 1280.0, 100.0));
+        });
+    });
+
+    group.bench_function("query_region", |b| {
+        b.iter(|| {
+            black_box(SpatialQueries::query_region(&world, &region));
+        });
+    });
+
+    group.bench_function("spatial_index_rect", |b| {
+        b.iter(|| {
+            black_box(world.spatial_index.query_rect(1000.0, 1000.0, 1300.0, 1200.0));
+        });
+    });
+
+    group.bench_function("query_frustum", |b| {
+        b.iter(|| {
+            black_box(SpatialQueries::query_frustum(&world, &frustum));
+        });
+    });
+
+    group.bench_function("spatial_index_frustum_bounds", |b| {
+        let bounds = frustum.bounds();
+        b.iter(|| {
+            black_box(world.spatial_index.query_rect(bounds.min[0], bounds.min[1], bounds.max[0], bounds.max[1]));
+        });
+    });
+
+    group.finish();
+}
+
 fn bench_collision_detection(c: &mut Criterion) {
     let mut world = World::new("Test".to_string(), "game1".to_string(), 5, 5);
     world.initialize_chunks();
@@ -92,5 +149,80 @@ fn bench_collision_detection(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_spatial_queries, bench_collision_detection);
+#[cfg(feature = "octree")]
+fn bench_dynamic_octree(c: &mut Criterion) {
+    use entropic_spatial_engine::octree::{Bounds, Octree};
+    use entropic_world_core::spatial::SpatialIndex;
+
+    // 100k entities spread over 16x16 chunks, all moving every tick
+    let extent = 16.0 * CHUNK_SIZE;
+    let mut positions: Vec<(String, f32, f32, f32)> = (0..100_000u32)
+        .map(|i| {
+            let x = (i.wrapping_mul(7919) % 4096) as f32 / 4096.0 * extent;
+            let y = (i.wrapping_mul(104_729) % 4096) as f32 / 4096.0 * extent;
+            (format!("entity_{}", i), x, y, (i % 64) as f32)
+        })
+        .collect();
+
+    let mut octree = Octree::new(32).with_bounds(Bounds::new(0.0, 0.0, 0.0, extent, extent, 64.0));
+    let mut index = SpatialIndex::new();
+    for (id, x, y, z) in &positions {
+        octree.insert(*x, *y, *z, id.clone()).unwrap();
+        index.insert(id.clone(), *x, *y);
+    }
+
+    let mut group = c.benchmark_group("dynamic_octree");
+    group.sample_size(10);
+
+    let mut tick = 0usize;
+    group.bench_function("octree_move_100k", |b| {
+        b.iter(|| {
+            tick += 1;
+            for (i, (id, x, y, z)) in positions.iter_mut().enumerate() {
+                let angle = (i + tick) as f32 * 0.61;
+                *x = (*x + angle.cos() * 2.0).clamp(0.0, extent - 1.0);
+                *y = (*y + angle.sin() * 2.0).clamp(0.0, extent - 1.0);
+                octree.update(id, *x, *y, *z).unwrap();
+            }
+        });
+    });
+
+    group.bench_function("spatial_index_move_100k", |b| {
+        b.iter(|| {
+            tick += 1;
+            for (i, (id, x, y, _)) in positions.iter_mut().enumerate() {
+                let angle = (i + tick) as f32 * 0.61;
+                let (old_x, old_y) = (*x, *y);
+                *x = (*x + angle.cos() * 2.0).clamp(0.0, extent - 1.0);
+                *y = (*y + angle.sin() * 2.0).clamp(0.0, extent - 1.0);
+                index.update(id.clone(), old_x, old_y, *x, *y);
+            }
+        });
+    });
+
+    group.bench_function("octree_radius_100k", |b| {
+        b.iter(|| {
+            black_box(octree.query_radius(2048.0, 2048.0, 32.0, 100.0));
+        });
+    });
+
+    group.bench_function("spatial_index_radius_100k", |b| {
+        b.iter(|| {
+            black_box(index.query_radius(2048.0, 2048.0, 100.0));
+        });
+    });
+
+    group.finish();
+}
+
+#[cfg(feature = "octree")]
+criterion_group!(
+    benches,
+    bench_spatial_queries,
+    bench_region_queries,
+    bench_collision_detection,
+    bench_dynamic_octree
+);
+#[cfg(not(feature = "octree"))]
+criterion_group!(benches, bench_spatial_queries, bench_region_queries, bench_collision_detection);
 criterion_main!(benches);
@@ -0,0 +1,425 @@
+//! Cave and overhang generation for voxel chunks
+//!
+//! This module is only available when the "voxel" feature is enabled.
+//!
+//! Voxel chunks are 16 voxels of one meter per side, with `z` as height. Terrain is filled up
+//! to the surface height, which 3D noise pushes in and out to form overhangs. Below the
+//! surface, two noise fields carve tunnels where both are close to zero, and worms dug
+//! downward from the surface give the cave network its entrances. Every worm is derived from
+//! the seed and the region it starts in, so a chunk carves the same tunnels as its neighbors
+//! no matter which chunk is generated first.
+
+use crate::constants::*;
+use crate::erosion::SplitMix64;
+use crate::noise::PerlinNoise;
+use crate::voxel::{Voxel, VoxelChunk, VOXEL_CHUNK_SIZE};
+use crate::{ChunkCoord, World};
+use std::sync::Arc;
+
+/// Material of voxels near the surface
+pub const SOIL_MATERIAL: u8 = 1;
+
+/// Material of voxels deeper down
+pub const STONE_MATERIAL: u8 = 2;
+
+/// Depth of the soil layer (in meters)
+const SOIL_DEPTH: f32 = 3.0;
+
+/// Steps a worm digs steeply down before it starts to wander
+const WORM_ENTRANCE_STEPS: u32 = 12;
+
+/// Tunnel dug from a surface entrance
+#[derive(Clone, Debug, PartialEq)]
+pub struct CaveWorm {
+    /// Tunnel center points, starting at the surface entrance
+    pub points: Vec<(f32, f32, f32)>,
+    /// Tunnel radius at each point
+    pub radii: Vec<f32>,
+}
+
+impl CaveWorm {
+    /// Check if a position lies inside the tunnel
+    pub fn contains(&self, x: f32, y: f32, z: f32) -> bool {
+        self.points.iter().zip(&self.radii).any(|(&(px, py, pz), &radius)| {
+            let (dx, dy, dz) = (x - px, y - py, z - pz);
+            dx * dx + dy * dy + dz * dz <= radius * radius
+        })
+    }
+
+    /// Get the bounding box of the tunnel
+    fn bounds(&self) -> ((f32, f32, f32), (f32, f32, f32)) {
+        let mut min = (f32::MAX, f32::MAX, f32::MAX);
+        let mut max = (f32::MIN, f32::MIN, f32::MIN);
+        for (&(x, y, z), &radius) in self.points.iter().zip(&self.radii) {
+            min = (min.0.min(x - radius), min.1.min(y - radius), min.2.min(z - radius));
+            max = (max.0.max(x + radius), max.1.max(y + radius), max.2.max(z + radius));
+        }
+        (min, max)
+    }
+}
+
+/// Deterministic cave generator for voxel chunks
+#[derive(Clone)]
+pub struct CaveGenerator {
+    noise: Arc<PerlinNoise>,
+    seed: u32,
+    tunnel_frequency: f64,
+    tunnel_width: f64,
+    overhang_amplitude: f32,
+    min_tunnel_depth: f32,
+    worm_region: f32,
+    worms_per_region: u32,
+    worm_length: u32,
+    worm_radius: f32,
+}
+
+impl CaveGenerator {
+    /// Create a cave generator with default seed
+    pub fn new() -> Self {
+        Self::with_seed(12345)
+    }
+
+    /// Create a cave generator with a specific seed
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            noise: Arc::new(PerlinNoise::with_seed(seed.wrapping_add(0xCA7E))),
+            seed,
+            tunnel_frequency: CAVE_NOISE_FREQUENCY,
+            tunnel_width: 0.08,
+            overhang_amplitude: 4.0,
+            min_tunnel_depth: 8.0,
+            worm_region: CAVE_WORM_REGION,
+            worms_per_region: 2,
+            worm_length: 96,
+            worm_radius: 2.5,
+        }
+    }
+
+    /// Set how wide noise tunnels are, from 0 (none) to 1
+    pub fn with_tunnel_width(mut self, tunnel_width: f64) -> Self {
+        self.tunnel_width = tunnel_width.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set how far 3D noise pushes the surface in and out (in meters)
+    pub fn with_overhang_amplitude(mut self, overhang_amplitude: f32) -> Self {
+        self.overhang_amplitude = overhang_amplitude.max(0.0);
+        self
+    }
+
+    /// Set the depth below the surface where noise tunnels start
+    pub fn with_min_tunnel_depth(mut self, min_tunnel_depth: f32) -> Self {
+        self.min_tunnel_depth = min_tunnel_depth.max(0.0);
+        self
+    }
+
+    /// Set the maximum number of worms starting in each region
+    pub fn with_worms_per_region(mut self, worms_per_region: u32) -> Self {
+        self.worms_per_region = worms_per_region;
+        self
+    }
+
+    /// Set the number of one meter steps a worm digs
+    pub fn with_worm_length(mut self, worm_length: u32) -> Self {
+        self.worm_length = worm_length;
+        self
+    }
+
+    /// Set the base radius of worm tunnels
+    pub fn with_worm_radius(mut self, worm_radius: f32) -> Self {
+        self.worm_radius = worm_radius.max(0.5);
+        self
+    }
+
+    /// Get the seed used for generation
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    /// Generate a voxel chunk below the given surface
+    ///
+    /// `surface` returns the terrain height at a world position.
+    pub fn generate_chunk<S: Fn(f32, f32) -> f32>(&self, coord: (u32, u32, u32), surface: S) -> VoxelChunk {
+        let mut chunk = VoxelChunk::new(coord);
+        let size = VOXEL_CHUNK_SIZE as f32;
+        let origin = (coord.0 as f32 * size, coord.1 as f32 * size, coord.2 as f32 * size);
+        let worms = self.worms_near(
+            (origin.0, origin.1, origin.2),
+            (origin.0 + size, origin.1 + size, origin.2 + size),
+            &surface,
+        );
+
+        for x in 0..VOXEL_CHUNK_SIZE {
+            for y in 0..VOXEL_CHUNK_SIZE {
+                let wx = origin.0 + x as f32 + 0.5;
+                let wy = origin.1 + y as f32 + 0.5;
+                let height = surface(wx, wy);
+                for z in 0..VOXEL_CHUNK_SIZE {
+                    let wz = origin.2 + z as f32 + 0.5;
+                    chunk.set(x, y, z, self.voxel(wx, wy, wz, height, &worms));
+                }
+            }
+        }
+
+        chunk
+    }
+
+    /// Generate a voxel chunk below the terrain of a world
+    pub fn generate_chunk_in_world(&self, world: &World, coord: (u32, u32, u32)) -> VoxelChunk {
+        self.generate_chunk(coord, |x, y| world_height(world, x, y))
+    }
+
+    /// Get the voxel at a world position
+    ///
+    /// Gives the same result as the voxel of the generated chunk containing the position.
+    pub fn voxel_at<S: Fn(f32, f32) -> f32>(&self, x: f32, y: f32, z: f32, surface: S) -> Voxel {
+        let cell = (x.floor() + 0.5, y.floor() + 0.5, z.floor() + 0.5);
+        let worms = self.worms_near(cell, cell, &surface);
+        self.voxel(cell.0, cell.1, cell.2, surface(cell.0, cell.1), &worms)
+    }
+
+    /// Get the worms whose tunnels may reach into a box
+    pub fn worms_near<S: Fn(f32, f32) -> f32>(
+        &self,
+        min: (f32, f32, f32),
+        max: (f32, f32, f32),
+        surface: S,
+    ) -> Vec<CaveWorm> {
+        let reach = self.worm_length as f32 + self.worm_radius * 2.0;
+        let first = (
+            ((min.0 - reach) / self.worm_region).floor().max(0.0) as u32,
+            ((min.1 - reach) / self.worm_region).floor().max(0.0) as u32,
+        );
+        let last = (
+            ((max.0 + reach) / self.worm_region).floor().max(0.0) as u32,
+            ((max.1 + reach) / self.worm_region).floor().max(0.0) as u32,
+        );
+
+        let mut worms = Vec::new();
+        for rx in first.0..=last.0 {
+            for ry in first.1..=last.1 {
+                for worm in self.region_worms(rx, ry, &surface) {
+                    let (low, high) = worm.bounds();
+                    if high.0 >= min.0
+                        && high.1 >= min.1
+                        && high.2 >= min.2
+                        && low.0 <= max.0
+                        && low.1 <= max.1
+                        && low.2 <= max.2
+                    {
+                        worms.push(worm);
+                    }
+                }
+            }
+        }
+        worms
+    }
+
+    /// Dig the worms starting in a region
+    fn region_worms<S: Fn(f32, f32) -> f32>(&self, rx: u32, ry: u32, surface: &S) -> Vec<CaveWorm> {
+        let mut rng = SplitMix64::new(
+            (((self.seed as u64) << 32) ^ ((rx as u64) << 16) ^ ry as u64).wrapping_mul(0xD1B5_4A32_D192_ED03),
+        );
+        let count = (rng.next_u64() % (self.worms_per_region as u64 + 1)) as u32;
+
+        (0..count)
+            .map(|_| {
+                let x = (rx as f32 + rng.next_f32()) * self.worm_region;
+                let y = (ry as f32 + rng.next_f32()) * self.worm_region;
+                let mut position = (x, y, surface(x, y));
+                let mut yaw = rng.next_f32() * std::f32::consts::TAU;
+                let mut pitch = -0.6 - rng.next_f32() * 0.3;
+
+                let mut worm = CaveWorm {
+                    points: Vec::with_capacity(self.worm_length as usize),
+                    radii: Vec::with_capacity(self.worm_length as usize),
+                };
+                for step in 0..self.worm_length {
+                    let swell = (step as f32 * 0.15).sin() * 0.3 + 1.0;
+                    worm.points.push(position);
+                    worm.radii.push(self.worm_radius * swell);
+
+                    yaw += (rng.next_f32() - 0.5) * 0.6;
+                    // Dig steeply down from the entrance, then wander mostly level
+                    let wander = (rng.next_f32() - 0.5) * 0.3;
+                    if step >= WORM_ENTRANCE_STEPS {
+                        pitch = (pitch * 0.9 + wander).clamp(-0.9, 0.3);
+                    }
+                    position = (
+                        position.0 + yaw.cos() * pitch.cos(),
+                        position.1 + yaw.sin() * pitch.cos(),
+                        (position.2 + pitch.sin()).max(1.0),
+                    );
+                }
+                worm
+            })
+            .collect()
+    }
+
+    /// Decide the voxel at a voxel center
+    fn voxel(&self, x: f32, y: f32, z: f32, height: f32, worms: &[CaveWorm]) -> Voxel {
+        let f = self.tunnel_frequency;
+        let (nx, ny, nz) = (x as f64 * f, y as f64 * f, z as f64 * f);
+
+        let displacement = self.noise.get3(nx * 2.0, ny * 2.0, nz * 2.0) as f32 * self.overhang_amplitude;
+        let depth = height + displacement - z;
+        if depth <= 0.0 {
+            return Voxel::Empty;
+        }
+
+        if worms.iter().any(|worm| worm.contains(x, y, z)) {
+            return Voxel::Empty;
+        }
+
+        if height - z > self.min_tunnel_depth {
+            let first = self.noise.get3(nx, ny, nz);
+            let second = self.noise.get3(nx + 512.0, ny + 512.0, nz + 512.0);
+            if first.abs() < self.tunnel_width && second.abs() < self.tunnel_width {
+                return Voxel::Empty;
+            }
+        }
+
+        let material = if depth < SOIL_DEPTH {
+            SOIL_MATERIAL
+        } else {
+            STONE_MATERIAL
+        };
+        Voxel::Solid {
+            material,
+            density: 255,
+        }
+    }
+}
+
+impl Default for CaveGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sample the terrain height of a world at a position
+fn world_height(world: &World, x: f32, y: f32) -> f32 {
+    let coord = ChunkCoord::new(
+        (x.max(0.0) / CHUNK_SIZE) as u32,
+        (y.max(0.0) / CHUNK_SIZE) as u32,
+    );
+    let Some(chunk) = world.chunks.get(&coord) else {
+        return 0.0;
+    };
+    let scale = HEIGHTMAP_RESOLUTION as f32 / CHUNK_SIZE;
+    let sx = (((x - coord.x as f32 * CHUNK_SIZE) * scale).max(0.0) as usize).min(HEIGHTMAP_RESOLUTION - 1);
+    let sy = (((y - coord.y as f32 * CHUNK_SIZE) * scale).max(0.0) as usize).min(HEIGHTMAP_RESOLUTION - 1);
+    chunk.get_elevation_at(sx, sy).unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn surface(x: f32, y: f32) -> f32 {
+        40.0 + (x * 0.05).sin() * 4.0 + (y * 0.03).cos() * 4.0
+    }
+
+    #[test]
+    fn test_deterministic_generation() {
+        let chunk1 = CaveGenerator::with_seed(42).generate_chunk((3, 4, 1), surface);
+        let chunk2 = CaveGenerator::with_seed(42).generate_chunk((3, 4, 1), surface);
+        let other = CaveGenerator::with_seed(43).generate_chunk((3, 4, 1), surface);
+
+        assert_eq!(chunk1.voxels, chunk2.voxels);
+        assert_ne!(chunk1.voxels, other.voxels);
+    }
+
+    #[test]
+    fn test_chunks_line_up_across_borders() {
+        let generator = CaveGenerator::with_seed(7);
+
+        for coord in [(4, 4, 1), (5, 4, 1), (4, 5, 2)] {
+            let chunk = generator.generate_chunk(coord, surface);
+            let origin = (coord.0 as f32 * 16.0, coord.1 as f32 * 16.0, coord.2 as f32 * 16.0);
+            // Check the faces shared with neighboring chunks against point queries.
+            for a in 0..VOXEL_CHUNK_SIZE {
+                for b in 0..VOXEL_CHUNK_SIZE {
+                    for (x, y, z) in [(0, a, b), (15, a, b), (a, 0, b), (a, 15, b)] {
+                        let expected = generator.voxel_at(
+                            origin.0 + x as f32,
+                            origin.1 + y as f32,
+                            origin.2 + z as f32,
+                            surface,
+                        );
+                        assert_eq!(chunk.get(x, y, z), Some(&expected));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_worms_start_at_surface_entrances() {
+        let generator = CaveGenerator::with_seed(3).with_worms_per_region(3);
+        let worms = generator.worms_near((0.0, 0.0, 0.0), (256.0, 256.0, 64.0), surface);
+        assert!(!worms.is_empty());
+
+        for worm in &worms {
+            let (x, y, z) = worm.points[0];
+            assert_eq!(z, surface(x, y));
+            // The entrance just below the surface is open
+            assert_eq!(generator.voxel_at(x, y, z - 1.0, surface), Voxel::Empty);
+            // and the worm digs down into the ground
+            assert!(worm.points.iter().any(|p| p.2 < z - 5.0));
+        }
+    }
+
+    #[test]
+    fn test_solid_underground_and_empty_sky() {
+        let generator = CaveGenerator::new().with_tunnel_width(0.0).with_worms_per_region(0);
+
+        let sky = generator.generate_chunk((0, 0, 4), surface);
+        assert!(sky.voxels.iter().all(|v| *v == Voxel::Empty));
+
+        let ground = generator.generate_chunk((0, 0, 0), surface);
+        assert!(ground.voxels.iter().all(|v| matches!(v, Voxel::Solid { material: STONE_MATERIAL, .. })));
+    }
+
+    #[test]
+    fn test_generate_in_world() {
+        let mut world = World::new("Test".to_string(), "game1".to_string(), 1, 1);
+        world.initialize_chunks();
+        for chunk in world.chunks.values_mut() {
+            chunk.elevation.iter_mut().for_each(|h| *h = 100.0);
+        }
+        let generator = CaveGenerator::new().with_worms_per_region(0);
+
+        let sky = generator.generate_chunk_in_world(&world, (1, 1, 7));
+        assert!(sky.voxels.iter().all(|v| *v == Voxel::Empty));
+        let ground = generator.generate_chunk_in_world(&world, (1, 1, 5));
+        assert_eq!(ground.voxels, generator.generate_chunk((1, 1, 5), |_, _| 100.0).voxels);
+    }
+
+    #[test]
+    fn test_noise_tunnels_and_overhangs() {
+        let generator = CaveGenerator::with_seed(11).with_worms_per_region(0).with_tunnel_width(0.2);
+        let deep = generator.generate_chunk((2, 2, 1), |_, _| 200.0);
+        assert!(deep.voxels.contains(&Voxel::Empty));
+
+        // Overhang: an empty voxel below a solid one near the surface
+        let mut overhang = false;
+        for cx in 0..4 {
+            let chunk = generator
+                .clone()
+                .with_overhang_amplitude(8.0)
+                .with_tunnel_width(0.0)
+                .generate_chunk((cx, 0, 2), surface);
+            for x in 0..16 {
+                for y in 0..16 {
+                    for z in 1..16 {
+                        let below = chunk.get(x, y, z - 1) == Some(&Voxel::Empty);
+                        let above = matches!(chunk.get(x, y, z), Some(Voxel::Solid { .. }));
+                        overhang |= below && above;
+                    }
+                }
+            }
+        }
+        assert!(overhang);
+    }
+}
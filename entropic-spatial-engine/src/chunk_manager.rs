@@ -1,9 +1,19 @@
 use crate::constants::*;
 use crate::errors::SpatialError;
+use crate::priority_policy::{DistancePriority, PriorityContext, PriorityPolicy};
+use crate::streaming::{ChunkDiskCache, ChunkFetcher, ChunkSource, FetchPolicy};
 use crate::terrain_generator::TerrainGenerator;
+use crate::terrain_source::TerrainSource;
 use crate::{Chunk, ChunkCoord, World};
+use entropic_world_core::observability::{self, increment_counter, set_gauge};
+use entropic_world_core::spatial::Structure;
+use rayon::prelude::*;
+use rayon::ThreadPool;
 use std::collections::{HashMap, VecDeque};
+use std::mem::size_of;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
+use tokio::sync::{mpsc, oneshot, Semaphore};
 
 /// Priority levels for chunk loading
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -14,6 +24,250 @@ pub enum Priority {
     Low = 0,      // Distant
 }
 
+/// Counters of the chunks evicted to stay within the memory budget
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EvictionStats {
+    /// Chunks evicted so far
+    pub evicted_chunks: u64,
+    /// Memory freed by evictions (in bytes)
+    pub evicted_bytes: u64,
+    /// Unload passes that found the manager over budget
+    pub eviction_passes: u64,
+    /// Unload passes left over budget because all remaining chunks were referenced
+    pub over_budget_passes: u64,
+}
+
+/// Memory and last use of a loaded chunk
+#[derive(Clone, Copy, Debug)]
+struct ChunkUsage {
+    bytes: usize,
+    last_used: u64,
+}
+
+/// Estimate the memory held by a chunk (in bytes)
+pub fn chunk_memory(chunk: &Chunk) -> usize {
+    let structures: usize = chunk
+        .structures
+        .iter()
+        .map(|s| {
+            size_of::<Structure>()
+                + s.id.capacity()
+                + s.owner.as_ref().map_or(0, String::capacity)
+                + s.faction.as_ref().map_or(0, String::capacity)
+        })
+        .sum();
+    let entities: usize = chunk.entities.iter().map(|e| size_of::<String>() + e.capacity()).sum();
+
+    size_of::<Chunk>()
+        + chunk.elevation.capacity() * size_of::<f32>()
+        + chunk.vegetation.capacity()
+        + chunk.materials.capacity() * size_of::<[u8; 4]>()
+        + entities
+        + structures
+}
+
+/// Default budget: the chunk count for a view distance, at the size of a freshly painted chunk
+fn default_memory_budget(view_distance: u32) -> usize {
+    let max_loaded = ((view_distance as usize + 1).pow(2) * 2).min(MAX_LOADED_CHUNKS);
+    max_loaded * chunk_memory(&painted_chunk())
+}
+
+/// Fresh chunk with a full layer of materials, the size of a generated chunk without contents
+fn painted_chunk() -> Chunk {
+    let mut chunk = Chunk::new(ChunkCoord::new(0, 0));
+    chunk.materials = vec![[255, 0, 0, 0]; HEIGHTMAP_RESOLUTION * HEIGHTMAP_RESOLUTION];
+    chunk
+}
+
+/// Notification about a chunk taken from the load queue
+#[derive(Clone, Debug, PartialEq)]
+pub enum GenerationEvent {
+    /// The chunk is loaded, `generated` telling whether it came from the terrain or chunk source
+    Loaded { coord: ChunkCoord, generated: bool },
+    /// The chunk could not be loaded
+    Failed { coord: ChunkCoord, message: String },
+}
+
+/// Identifier of an observer registered with a chunk manager
+pub type ObserverId = u64;
+
+/// Point of view that needs chunks loaded, such as a player or a spectator camera
+#[derive(Clone, Debug, PartialEq)]
+pub struct Observer {
+    pub x: f32,
+    pub y: f32,
+    /// Velocity along x (in meters per second)
+    pub velocity_x: f32,
+    /// Velocity along y (in meters per second)
+    pub velocity_y: f32,
+    /// View distance (in chunks), the manager's own when `None`
+    pub view_distance: Option<u32>,
+    /// Direction the observer looks in, e.g. a camera, the velocity direction when `None`
+    pub facing: Option<(f32, f32)>,
+}
+
+impl Observer {
+    /// Create a stationary observer at a world position
+    pub fn new(x: f32, y: f32) -> Self {
+        Self {
+            x,
+            y,
+            velocity_x: 0.0,
+            velocity_y: 0.0,
+            view_distance: None,
+            facing: None,
+        }
+    }
+
+    /// Set the direction the observer looks in
+    pub fn with_facing(mut self, x: f32, y: f32) -> Self {
+        self.facing = Some((x, y));
+        self
+    }
+
+    /// Get the unit direction the observer faces or moves in, if any
+    pub fn heading(&self) -> Option<(f32, f32)> {
+        let (x, y) = self.facing.unwrap_or((self.velocity_x, self.velocity_y));
+        let length = (x * x + y * y).sqrt();
+        if length > f32::EPSILON {
+            Some((x / length, y / length))
+        } else {
+            None
+        }
+    }
+
+    /// Set the velocity (in meters per second) used to prefetch ahead of the observer
+    pub fn with_velocity(mut self, velocity_x: f32, velocity_y: f32) -> Self {
+        self.velocity_x = velocity_x;
+        self.velocity_y = velocity_y;
+        self
+    }
+
+    /// Set a view distance (in chunks) for this observer
+    pub fn with_view_distance(mut self, view_distance: u32) -> Self {
+        self.view_distance = Some(view_distance);
+        self
+    }
+}
+
+/// Chunks required with their priority, in the order they were first required
+struct ChunkDemand<'a> {
+    policy: &'a dyn PriorityPolicy,
+    chunks: Vec<(ChunkCoord, Priority)>,
+    index: HashMap<ChunkCoord, usize>,
+}
+
+impl<'a> ChunkDemand<'a> {
+    fn new(policy: &'a dyn PriorityPolicy) -> Self {
+        Self {
+            policy,
+            chunks: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// Require a chunk, raising its priority if already required
+    fn require(&mut self, coord: ChunkCoord, priority: Priority) {
+        match self.index.get(&coord) {
+            Some(&i) => self.chunks[i].1 = self.chunks[i].1.max(priority),
+            None => {
+                self.index.insert(coord, self.chunks.len());
+                self.chunks.push((coord, priority));
+            }
+        }
+    }
+
+    /// Require a chunk for an observer at the priority the policy gives it
+    fn require_for(&mut self, observer: &Observer, chunk: (i32, i32), prefetch: bool, default: Priority) {
+        // Skip negative coordinates for now
+        if chunk.0 < 0 || chunk.1 < 0 {
+            return;
+        }
+
+        let center_x = (observer.x / CHUNK_SIZE).floor() as i32;
+        let center_y = (observer.y / CHUNK_SIZE).floor() as i32;
+        let context = PriorityContext {
+            coord: ChunkCoord::new(chunk.0 as u32, chunk.1 as u32),
+            observer: (observer.x, observer.y),
+            heading: observer.heading(),
+            distance: (chunk.0 - center_x).abs().max((chunk.1 - center_y).abs()) as u32,
+            prefetch,
+            default,
+        };
+        self.require(context.coord, self.policy.priority(&context));
+    }
+
+    /// Require the chunks within a view distance of an observer, by distance
+    fn add_view(&mut self, observer: &Observer, view_distance: u32) {
+        let center_x = (observer.x / CHUNK_SIZE).floor() as i32;
+        let center_y = (observer.y / CHUNK_SIZE).floor() as i32;
+        let view = view_distance as i32;
+
+        for dx in -view..=view {
+            for dy in -view..=view {
+                // Calculate priority based on distance
+                let distance = (dx.abs().max(dy.abs())) as u32;
+                let priority = if distance == 0 {
+                    Priority::Critical
+                } else if distance <= 1 {
+                    Priority::High
+                } else if distance <= 2 {
+                    Priority::Normal
+                } else {
+                    Priority::Low
+                };
+
+                self.require_for(observer, (center_x + dx, center_y + dy), false, priority);
+            }
+        }
+    }
+
+    /// Require the chunks around the path an observer moves along within
+    /// [`PREFETCH_LOOKAHEAD_SECONDS`]
+    fn add_path(&mut self, observer: &Observer) {
+        let (velocity_x, velocity_y) = (observer.velocity_x, observer.velocity_y);
+        let speed = (velocity_x * velocity_x + velocity_y * velocity_y).sqrt();
+        if speed <= f32::EPSILON {
+            return;
+        }
+
+        // Sample the path every half chunk
+        let lookahead = speed * PREFETCH_LOOKAHEAD_SECONDS;
+        let steps = (lookahead / (CHUNK_SIZE * 0.5)).ceil() as u32;
+        let (dir_x, dir_y) = (velocity_x / speed, velocity_y / speed);
+
+        for step in 1..=steps {
+            let travelled = (step as f32 * CHUNK_SIZE * 0.5).min(lookahead);
+            let center_x = ((observer.x + dir_x * travelled) / CHUNK_SIZE).floor() as i32;
+            let center_y = ((observer.y + dir_y * travelled) / CHUNK_SIZE).floor() as i32;
+
+            // The first half of the path is needed soonest
+            let priority = if travelled <= lookahead * 0.5 {
+                Priority::High
+            } else {
+                Priority::Normal
+            };
+
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    self.require_for(observer, (center_x + dx, center_y + dy), true, priority);
+                }
+            }
+        }
+    }
+}
+
+/// Count a chunk loaded by a chunk manager
+fn record_load(generated: bool) {
+    let source = if generated { "generated" } else { "cached" };
+    increment_counter(observability::CHUNKS_LOADED_TOTAL, &[("source", source)], 1);
+}
+
+/// Default limit of chunks generated at once, from the global rayon pool size
+fn default_in_flight() -> usize {
+    rayon::current_num_threads() * CHUNKS_IN_FLIGHT_PER_THREAD
+}
+
 /// Manages chunk lifecycle: generation, loading, caching, unloading
 #[derive(Clone)]
 pub struct ChunkManager {
@@ -21,45 +275,226 @@ pub struct ChunkManager {
     loaded_chunks: Arc<RwLock<HashMap<ChunkCoord, Arc<Chunk>>>>,
     load_queue: Arc<RwLock<VecDeque<(ChunkCoord, Priority)>>>,
     unload_candidates: Arc<RwLock<Vec<ChunkCoord>>>,
-    generator: TerrainGenerator,
-    max_loaded_chunks: usize,
+    generator: Arc<dyn TerrainSource>,
     view_distance: u32,
+    memory_budget: usize,
+    usage: Arc<RwLock<HashMap<ChunkCoord, ChunkUsage>>>,
+    observers: Arc<RwLock<HashMap<ObserverId, Observer>>>,
+    next_observer: Arc<AtomicU64>,
+    refs: Arc<RwLock<HashMap<ChunkCoord, usize>>>,
+    clock: Arc<AtomicU64>,
+    eviction_stats: Arc<RwLock<EvictionStats>>,
+    disk_cache: Option<Arc<ChunkDiskCache>>,
+    fetcher: Option<Arc<ChunkFetcher>>,
+    pool: Option<Arc<ThreadPool>>,
+    generation_permits: Arc<Semaphore>,
+    max_in_flight: usize,
+    load_queue_capacity: usize,
+    subscribers: Arc<RwLock<Vec<mpsc::UnboundedSender<GenerationEvent>>>>,
+    priority_policy: Arc<dyn PriorityPolicy>,
 }
 
 impl ChunkManager {
     /// Create a new chunk manager
     pub fn new(world: Arc<RwLock<World>>, view_distance: u32) -> Self {
-        let max_loaded = ((view_distance as usize + 1).pow(2) * 2).min(MAX_LOADED_CHUNKS);
-
         Self {
             world,
             loaded_chunks: Arc::new(RwLock::new(HashMap::new())),
             load_queue: Arc::new(RwLock::new(VecDeque::new())),
             unload_candidates: Arc::new(RwLock::new(Vec::new())),
-            generator: TerrainGenerator::new(),
-            max_loaded_chunks: max_loaded,
+            generator: Arc::new(TerrainGenerator::new()),
             view_distance,
+            memory_budget: default_memory_budget(view_distance),
+            usage: Arc::new(RwLock::new(HashMap::new())),
+            observers: Arc::new(RwLock::new(HashMap::new())),
+            next_observer: Arc::new(AtomicU64::new(0)),
+            refs: Arc::new(RwLock::new(HashMap::new())),
+            clock: Arc::new(AtomicU64::new(0)),
+            eviction_stats: Arc::new(RwLock::new(EvictionStats::default())),
+            disk_cache: None,
+            fetcher: None,
+            pool: None,
+            generation_permits: Arc::new(Semaphore::new(default_in_flight())),
+            max_in_flight: default_in_flight(),
+            load_queue_capacity: LOAD_QUEUE_CAPACITY,
+            subscribers: Arc::new(RwLock::new(Vec::new())),
+            priority_policy: Arc::new(DistancePriority),
         }
     }
 
     /// Create a new chunk manager with custom terrain generator seed
     pub fn with_seed(world: Arc<RwLock<World>>, view_distance: u32, seed: u32) -> Self {
-        let max_loaded = ((view_distance as usize + 1).pow(2) * 2).min(MAX_LOADED_CHUNKS);
+        Self {
+            world,
+            loaded_chunks: Arc::new(RwLock::new(HashMap::new())),
+            load_queue: Arc::new(RwLock::new(VecDeque::new())),
+            unload_candidates: Arc::new(RwLock::new(Vec::new())),
+            generator: Arc::new(TerrainGenerator::with_seed(seed)),
+            view_distance,
+            memory_budget: default_memory_budget(view_distance),
+            usage: Arc::new(RwLock::new(HashMap::new())),
+            observers: Arc::new(RwLock::new(HashMap::new())),
+            next_observer: Arc::new(AtomicU64::new(0)),
+            refs: Arc::new(RwLock::new(HashMap::new())),
+            clock: Arc::new(AtomicU64::new(0)),
+            eviction_stats: Arc::new(RwLock::new(EvictionStats::default())),
+            disk_cache: None,
+            fetcher: None,
+            pool: None,
+            generation_permits: Arc::new(Semaphore::new(default_in_flight())),
+            max_in_flight: default_in_flight(),
+            load_queue_capacity: LOAD_QUEUE_CAPACITY,
+            subscribers: Arc::new(RwLock::new(Vec::new())),
+            priority_policy: Arc::new(DistancePriority),
+        }
+    }
 
+    /// Create a new chunk manager generating missing chunks from a custom terrain source
+    pub fn with_source(
+        world: Arc<RwLock<World>>,
+        view_distance: u32,
+        source: Arc<dyn TerrainSource>,
+    ) -> Self {
         Self {
             world,
             loaded_chunks: Arc::new(RwLock::new(HashMap::new())),
             load_queue: Arc::new(RwLock::new(VecDeque::new())),
             unload_candidates: Arc::new(RwLock::new(Vec::new())),
-            generator: TerrainGenerator::with_seed(seed),
-            max_loaded_chunks: max_loaded,
+            generator: source,
             view_distance,
+            memory_budget: default_memory_budget(view_distance),
+            usage: Arc::new(RwLock::new(HashMap::new())),
+            observers: Arc::new(RwLock::new(HashMap::new())),
+            next_observer: Arc::new(AtomicU64::new(0)),
+            refs: Arc::new(RwLock::new(HashMap::new())),
+            clock: Arc::new(AtomicU64::new(0)),
+            eviction_stats: Arc::new(RwLock::new(EvictionStats::default())),
+            disk_cache: None,
+            fetcher: None,
+            pool: None,
+            generation_permits: Arc::new(Semaphore::new(default_in_flight())),
+            max_in_flight: default_in_flight(),
+            load_queue_capacity: LOAD_QUEUE_CAPACITY,
+            subscribers: Arc::new(RwLock::new(Vec::new())),
+            priority_policy: Arc::new(DistancePriority),
         }
     }
 
-    /// Get the terrain generator
-    pub fn generator(&self) -> &TerrainGenerator {
-        &self.generator
+    /// Set the memory budget for loaded chunks (in bytes)
+    ///
+    /// The default budget fits the chunks around the view distance.
+    pub fn with_memory_budget(mut self, bytes: usize) -> Self {
+        self.memory_budget = bytes;
+        self
+    }
+
+    /// Reload chunks from a disk cache instead of regenerating them, and persist generated ones
+    pub fn with_disk_cache(mut self, cache: Arc<ChunkDiskCache>) -> Self {
+        self.disk_cache = Some(cache);
+        self
+    }
+
+    /// Fetch missing chunks from a chunk source, such as a network service, before generating them
+    ///
+    /// Fetched chunks must carry the recipe of the manager's terrain source and a valid checksum,
+    /// and are persisted to the disk cache like generated ones. Once every attempt at a chunk
+    /// failed, it is generated instead if the policy allows fallback, or fails to load.
+    pub fn with_chunk_source(mut self, source: Arc<dyn ChunkSource>, policy: FetchPolicy) -> Self {
+        let recipe = self.generator.recipe();
+        self.fetcher = Some(Arc::new(ChunkFetcher::new(source, policy, recipe)));
+        self
+    }
+
+    /// Generate chunks on a dedicated pool of `threads` threads instead of the global rayon pool
+    ///
+    /// At most [`CHUNKS_IN_FLIGHT_PER_THREAD`] chunks per thread are generated at once.
+    pub fn with_generation_threads(mut self, threads: usize) -> Result<Self, SpatialError> {
+        let threads = threads.max(1);
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|i| format!("chunk-gen-{}", i))
+            .build()
+            .map_err(|e| SpatialError::ChunkGenerationFailed {
+                coord: ChunkCoord::new(0, 0),
+                message: e.to_string(),
+            })?;
+
+        self.pool = Some(Arc::new(pool));
+        self.max_in_flight = threads * CHUNKS_IN_FLIGHT_PER_THREAD;
+        self.generation_permits = Arc::new(Semaphore::new(self.max_in_flight));
+        Ok(self)
+    }
+
+    /// Decide the priority of chunks required by observers with a custom policy
+    pub fn with_priority_policy(mut self, policy: Arc<dyn PriorityPolicy>) -> Self {
+        self.priority_policy = policy;
+        self
+    }
+
+    /// Get the priority policy
+    pub fn priority_policy(&self) -> &dyn PriorityPolicy {
+        self.priority_policy.as_ref()
+    }
+
+    /// Set how many chunks may wait in the load queue
+    pub fn with_load_queue_capacity(mut self, capacity: usize) -> Self {
+        self.load_queue_capacity = capacity.max(1);
+        self
+    }
+
+    /// Get the maximum number of chunks generated at once
+    pub fn max_in_flight(&self) -> usize {
+        self.max_in_flight
+    }
+
+    /// Get how many chunks may wait in the load queue
+    pub fn load_queue_capacity(&self) -> usize {
+        self.load_queue_capacity
+    }
+
+    /// Receive a notification for every chunk loaded or failed from the load queue
+    pub fn subscribe(&self) -> mpsc::UnboundedReceiver<GenerationEvent> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.subscribers.write().unwrap().push(tx);
+        rx
+    }
+
+    /// Send an event to all subscribers, forgetting the ones that went away
+    fn notify(&self, event: GenerationEvent) {
+        self.subscribers
+            .write()
+            .unwrap()
+            .retain(|tx| tx.send(event.clone()).is_ok());
+    }
+
+    /// Get the disk cache, if any
+    pub fn disk_cache(&self) -> Option<&ChunkDiskCache> {
+        self.disk_cache.as_deref()
+    }
+
+    /// Get the fetcher of the chunk source, if any
+    pub fn chunk_fetcher(&self) -> Option<&ChunkFetcher> {
+        self.fetcher.as_deref()
+    }
+
+    /// Get the memory budget for loaded chunks (in bytes)
+    pub fn memory_budget(&self) -> usize {
+        self.memory_budget
+    }
+
+    /// Get the estimated memory held by loaded chunks (in bytes)
+    pub fn memory_used(&self) -> usize {
+        self.usage.read().unwrap().values().map(|u| u.bytes).sum()
+    }
+
+    /// Get the eviction counters
+    pub fn eviction_stats(&self) -> EvictionStats {
+        *self.eviction_stats.read().unwrap()
+    }
+
+    /// Get the terrain source
+    pub fn generator(&self) -> &dyn TerrainSource {
+        self.generator.as_ref()
     }
 
     /// Get current view distance
@@ -77,77 +512,181 @@ impl ChunkManager {
         self.load_queue.read().unwrap().len()
     }
 
+    /// Get the queued chunks in load order
+    pub fn queued_chunks(&self) -> Vec<(ChunkCoord, Priority)> {
+        self.load_queue.read().unwrap().iter().copied().collect()
+    }
+
     /// Update visible chunks based on player position
+    ///
+    /// Replaces the chunks required by registered observers until the next
+    /// [`update_observers`](Self::update_observers).
     pub async fn update_visible_chunks(
         &self,
         player_x: f32,
         player_y: f32,
     ) -> Result<(), SpatialError> {
-        let player_chunk_x = (player_x / CHUNK_SIZE).floor() as i32;
-        let player_chunk_y = (player_y / CHUNK_SIZE).floor() as i32;
+        let mut demand = ChunkDemand::new(self.priority_policy.as_ref());
+        demand.add_view(&Observer::new(player_x, player_y), self.view_distance);
+        self.apply_demand(demand, None);
+        Ok(())
+    }
 
-        let mut load_queue = self.load_queue.write().unwrap();
-        let loaded = self.loaded_chunks.read().unwrap();
+    /// Update visible chunks for a moving player, prefetching along the predicted path
+    ///
+    /// Chunks around the positions the player reaches within
+    /// [`PREFETCH_LOOKAHEAD_SECONDS`] at the current velocity (in meters per second) are
+    /// queued ahead of the rest of the view, nearest first, and kept from eviction like
+    /// visible chunks.
+    pub async fn update_visible_chunks_with_velocity(
+        &self,
+        player_x: f32,
+        player_y: f32,
+        velocity_x: f32,
+        velocity_y: f32,
+    ) -> Result<(), SpatialError> {
+        let observer = Observer::new(player_x, player_y).with_velocity(velocity_x, velocity_y);
+        let mut demand = ChunkDemand::new(self.priority_policy.as_ref());
+        demand.add_view(&observer, self.view_distance);
+        demand.add_path(&observer);
+        self.apply_demand(demand, None);
+        Ok(())
+    }
 
-        // Queue chunks in view distance with priority
-        for dx in -(self.view_distance as i32)..=(self.view_distance as i32) {
-            for dy in -(self.view_distance as i32)..=(self.view_distance as i32) {
-                let chunk_x = player_chunk_x + dx;
-                let chunk_y = player_chunk_y + dy;
+    /// Register an observer, returning its id
+    pub fn add_observer(&self, observer: Observer) -> ObserverId {
+        let id = self.next_observer.fetch_add(1, Ordering::Relaxed);
+        self.observers.write().unwrap().insert(id, observer);
+        id
+    }
 
-                // Skip negative coordinates for now
-                if chunk_x < 0 || chunk_y < 0 {
-                    continue;
-                }
+    /// Move an observer, setting its velocity (in meters per second)
+    pub fn move_observer(
+        &self,
+        id: ObserverId,
+        x: f32,
+        y: f32,
+        velocity_x: f32,
+        velocity_y: f32,
+    ) -> Result<(), SpatialError> {
+        let mut observers = self.observers.write().unwrap();
+        let observer = observers.get_mut(&id).ok_or_else(|| SpatialError::QueryFailed {
+            message: format!("unknown observer {}", id),
+        })?;
+        observer.x = x;
+        observer.y = y;
+        observer.velocity_x = velocity_x;
+        observer.velocity_y = velocity_y;
+        Ok(())
+    }
 
-                let coord = ChunkCoord {
-                    x: chunk_x as u32,
-                    y: chunk_y as u32,
-                };
+    /// Unregister an observer
+    ///
+    /// Its chunks stay referenced until the next [`update_observers`](Self::update_observers).
+    pub fn remove_observer(&self, id: ObserverId) -> Option<Observer> {
+        self.observers.write().unwrap().remove(&id)
+    }
 
-                // Skip already loaded
-                if loaded.contains_key(&coord) {
-                    continue;
-                }
+    /// Get a registered observer
+    pub fn observer(&self, id: ObserverId) -> Option<Observer> {
+        self.observers.read().unwrap().get(&id).cloned()
+    }
 
-                // Skip if already in queue
-                if load_queue.iter().any(|(c, _)| c == &coord) {
-                    continue;
-                }
+    /// Get number of registered observers
+    pub fn observer_count(&self) -> usize {
+        self.observers.read().unwrap().len()
+    }
 
-                // Calculate priority based on distance
-                let distance = (dx.abs().max(dy.abs())) as u32;
-                let priority = if distance == 0 {
-                    Priority::Critical
-                } else if distance <= 1 {
-                    Priority::High
-                } else if distance <= 2 {
-                    Priority::Normal
-                } else {
-                    Priority::Low
-                };
+    /// Queue the union of the chunks required by all observers
+    ///
+    /// Each observer requires the chunks within its own view distance and along its predicted
+    /// path. A chunk required by several observers gets the highest of their priorities, and
+    /// its reference count is the number of observers requiring it.
+    pub async fn update_observers(&self) -> Result<(), SpatialError> {
+        let observers: Vec<Observer> = {
+            let observers = self.observers.read().unwrap();
+            let mut ids: Vec<_> = observers.keys().copied().collect();
+            ids.sort_unstable();
+            ids.into_iter().map(|id| observers[&id].clone()).collect()
+        };
+
+        let mut demand = ChunkDemand::new(self.priority_policy.as_ref());
+        let mut refs = HashMap::new();
+        for observer in observers {
+            let mut own = ChunkDemand::new(self.priority_policy.as_ref());
+            own.add_view(&observer, observer.view_distance.unwrap_or(self.view_distance));
+            own.add_path(&observer);
+
+            for (coord, priority) in own.chunks {
+                *refs.entry(coord).or_insert(0) += 1;
+                demand.require(coord, priority);
+            }
+        }
 
-                load_queue.push_back((coord, priority));
+        self.apply_demand(demand, Some(refs));
+        Ok(())
+    }
+
+    /// Get the number of observers requiring a chunk
+    pub fn chunk_ref_count(&self, coord: ChunkCoord) -> usize {
+        self.refs.read().unwrap().get(&coord).copied().unwrap_or(0)
+    }
+
+    /// Make the demanded chunks the referenced ones and queue those not loaded yet
+    ///
+    /// Without explicit reference counts, every demanded chunk counts once.
+    fn apply_demand(&self, demand: ChunkDemand, refs: Option<HashMap<ChunkCoord, usize>>) {
+        *self.refs.write().unwrap() = refs.unwrap_or_else(|| {
+            demand.chunks.iter().map(|(coord, _)| (*coord, 1)).collect()
+        });
+
+        let (loaded, missing): (Vec<_>, Vec<_>) = {
+            let loaded_chunks = self.loaded_chunks.read().unwrap();
+            demand
+                .chunks
+                .into_iter()
+                .partition(|(coord, _)| loaded_chunks.contains_key(coord))
+        };
+        for (coord, _) in loaded {
+            self.touch(coord);
+        }
+
+        let mut load_queue = self.load_queue.write().unwrap();
+        for (coord, priority) in missing {
+            match load_queue.iter_mut().find(|(c, _)| c == &coord) {
+                Some(entry) => entry.1 = entry.1.max(priority),
+                None => load_queue.push_back((coord, priority)),
             }
         }
 
-        // Sort by priority
+        // Sort by priority, dropping the least important chunks past the capacity
         let mut queue: Vec<_> = load_queue.drain(..).collect();
         queue.sort_by_key(|e| std::cmp::Reverse(e.1));
+        queue.truncate(self.load_queue_capacity);
         queue.into_iter().for_each(|e| load_queue.push_back(e));
-
-        Ok(())
     }
 
     /// Load next chunk from queue
+    #[cfg_attr(feature = "observability", tracing::instrument(level = "debug", skip_all))]
     pub async fn process_load_queue(&self) -> Result<Option<ChunkCoord>, SpatialError> {
         let mut queue = self.load_queue.write().unwrap();
 
         if let Some((coord, _)) = queue.pop_front() {
             drop(queue); // Release lock before loading
 
-            let chunk = self.load_or_generate_chunk(coord).await?;
-            self.loaded_chunks.write().unwrap().insert(coord, Arc::new(chunk));
+            let (chunk, generated) = match self.load_or_generate_chunk(coord).await {
+                Ok(loaded) => loaded,
+                Err(e) => {
+                    self.notify(GenerationEvent::Failed {
+                        coord,
+                        message: e.to_string(),
+                    });
+                    return Err(e);
+                }
+            };
+            self.insert_chunk(coord, chunk, generated)?;
+            record_load(generated);
+            self.notify(GenerationEvent::Loaded { coord, generated });
 
             return Ok(Some(coord));
         }
@@ -170,51 +709,210 @@ impl ChunkManager {
         Ok(loaded)
     }
 
-    /// Load chunk from disk or generate if missing
-    async fn load_or_generate_chunk(&self, coord: ChunkCoord) -> Result<Chunk, SpatialError> {
+    /// Load up to `count` chunks from the queue in parallel on the generation pool
+    ///
+    /// No more than [`max_in_flight`](Self::max_in_flight) chunks are taken per call, and calls
+    /// wait while other calls hold the pool, so queueing many chunks never generates them all
+    /// at once. Chunks that fail are reported to subscribers and left out of the result.
+    #[cfg_attr(
+        feature = "observability",
+        tracing::instrument(level = "debug", skip(self), fields(in_flight = self.max_in_flight))
+    )]
+    pub async fn process_load_queue_parallel(&self, count: usize) -> Result<Vec<ChunkCoord>, SpatialError> {
+        let count = count.min(self.max_in_flight);
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+        let _permits = self
+            .generation_permits
+            .acquire_many(count as u32)
+            .await
+            .map_err(|_| SpatialError::LoadQueueFull)?;
+
+        let batch: Vec<ChunkCoord> = {
+            let mut queue = self.load_queue.write().unwrap();
+            let taken = count.min(queue.len());
+            queue.drain(..taken).map(|(coord, _)| coord).collect()
+        };
+        if batch.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (tx, rx) = oneshot::channel();
+        let manager = self.clone();
+        let job = move || {
+            let results: Vec<_> = batch
+                .into_par_iter()
+                .map(|coord| (coord, manager.fetch_chunk(coord)))
+                .collect();
+            let _ = tx.send(results);
+        };
+        match &self.pool {
+            Some(pool) => pool.spawn(job),
+            None => rayon::spawn(job),
+        }
+        let results = rx.await.map_err(|_| SpatialError::ChunkGenerationFailed {
+            coord: ChunkCoord::new(0, 0),
+            message: "generation pool stopped".to_string(),
+        })?;
+
+        let mut loaded = Vec::new();
+        for (coord, result) in results {
+            match result.and_then(|(chunk, generated)| {
+                self.insert_chunk(coord, chunk, generated).map(|_| generated)
+            }) {
+                Ok(generated) => {
+                    record_load(generated);
+                    self.notify(GenerationEvent::Loaded { coord, generated });
+                    loaded.push(coord);
+                }
+                Err(e) => self.notify(GenerationEvent::Failed {
+                    coord,
+                    message: e.to_string(),
+                }),
+            }
+        }
+
+        Ok(loaded)
+    }
+
+    /// Load chunk from disk or generate if missing, telling whether it was generated
+    async fn load_or_generate_chunk(&self, coord: ChunkCoord) -> Result<(Chunk, bool), SpatialError> {
+        self.fetch_chunk(coord)
+    }
+
+    /// Take a chunk from the world, the disk cache, the chunk source or the terrain source, in that order
+    fn fetch_chunk(&self, coord: ChunkCoord) -> Result<(Chunk, bool), SpatialError> {
         let world = self.world.read().unwrap();
 
         // Try to load from world
         if let Some(chunk) = world.chunks.get(&coord) {
-            return Ok(chunk.clone());
+            return Ok((chunk.clone(), false));
         }
 
         drop(world);
 
+        self.cached_or_generated(coord)
+    }
+
+    /// Load a chunk from the disk cache, or fetch or generate it if not cached
+    fn cached_or_generated(&self, coord: ChunkCoord) -> Result<(Chunk, bool), SpatialError> {
+        if let Some(chunk) = self.disk_cache.as_ref().and_then(|cache| cache.load(coord)) {
+            return Ok((chunk, false));
+        }
+
+        if let Some(fetcher) = &self.fetcher {
+            match fetcher.fetch(coord) {
+                Ok(Some(mut chunk)) => {
+                    chunk.load();
+                    return Ok((chunk, true));
+                }
+                Ok(None) => {}
+                Err(_) if fetcher.policy().fallback => {}
+                Err(e) => return Err(e),
+            }
+        }
+
         // Generate if not found
         let chunk = self.generator.generate_chunk(coord)?;
-        Ok(chunk)
+        Ok((chunk, true))
+    }
+
+    /// Store a loaded chunk and start tracking its memory and use
+    ///
+    /// With `persist`, the chunk is also queued for the disk cache.
+    fn insert_chunk(&self, coord: ChunkCoord, chunk: Chunk, persist: bool) -> Result<(), SpatialError> {
+        let usage = ChunkUsage {
+            bytes: chunk_memory(&chunk),
+            last_used: self.clock.fetch_add(1, Ordering::Relaxed),
+        };
+        let chunk = Arc::new(chunk);
+        let resident = {
+            let mut loaded = self.loaded_chunks.write().unwrap();
+            loaded.insert(coord, chunk.clone());
+            loaded.len()
+        };
+        set_gauge(observability::CHUNKS_RESIDENT, resident as f64);
+        self.usage.write().unwrap().insert(coord, usage);
+
+        match &self.disk_cache {
+            Some(cache) if persist => cache.store(chunk),
+            _ => Ok(()),
+        }
+    }
+
+    /// Replace a chunk with a modified version, persisting it to the disk cache
+    pub fn replace_chunk(&self, chunk: Chunk) -> Result<(), SpatialError> {
+        self.insert_chunk(chunk.coord, chunk, true)
+    }
+
+    /// Mark a chunk as just used
+    fn touch(&self, coord: ChunkCoord) {
+        if let Some(usage) = self.usage.write().unwrap().get_mut(&coord) {
+            usage.last_used = self.clock.fetch_add(1, Ordering::Relaxed);
+        }
     }
 
-    /// Unload chunks exceeding max loaded chunks
+    /// Evict least recently used chunks while over the memory budget
+    ///
+    /// Chunks referenced at the last visibility update are never evicted, so the manager may
+    /// stay over budget when the views alone exceed it.
+    #[cfg_attr(feature = "observability", tracing::instrument(level = "debug", skip_all))]
     pub fn process_unload_queue(&self) -> Result<Vec<ChunkCoord>, SpatialError> {
-        let loaded = self.loaded_chunks.read().unwrap();
+        let mut usage = self.usage.write().unwrap();
+        let mut used: usize = usage.values().map(|u| u.bytes).sum();
+        if used <= self.memory_budget {
+            return Ok(Vec::new());
+        }
 
-        // Mark for unload if count exceeds max
-        let mut to_unload = Vec::new();
-        if loaded.len() > self.max_loaded_chunks {
-            let excess = loaded.len() - self.max_loaded_chunks;
+        let refs = self.refs.read().unwrap();
+        let mut candidates: Vec<(u64, ChunkCoord)> = usage
+            .iter()
+            .filter(|(coord, _)| !refs.contains_key(coord))
+            .map(|(coord, u)| (u.last_used, *coord))
+            .collect();
+        candidates.sort_unstable_by_key(|(last_used, _)| *last_used);
+        drop(refs);
 
-            // Unload least recently used chunks (simplified: oldest entries)
-            for (coord, _) in loaded.iter().take(excess) {
-                to_unload.push(*coord);
+        let mut to_unload = Vec::new();
+        let mut freed = 0;
+        for (_, coord) in candidates {
+            if used <= self.memory_budget {
+                break;
+            }
+            if let Some(u) = usage.remove(&coord) {
+                used -= u.bytes;
+                freed += u.bytes;
+                to_unload.push(coord);
             }
         }
-
-        drop(loaded);
+        drop(usage);
 
         // Actually unload
         let mut loaded = self.loaded_chunks.write().unwrap();
         for coord in &to_unload {
             loaded.remove(coord);
         }
+        set_gauge(observability::CHUNKS_RESIDENT, loaded.len() as f64);
+
+        let mut stats = self.eviction_stats.write().unwrap();
+        stats.eviction_passes += 1;
+        stats.evicted_chunks += to_unload.len() as u64;
+        stats.evicted_bytes += freed as u64;
+        if used > self.memory_budget {
+            stats.over_budget_passes += 1;
+        }
 
         Ok(to_unload)
     }
 
     /// Get loaded chunk by coordinate
     pub fn get_chunk(&self, coord: ChunkCoord) -> Option<Arc<Chunk>> {
-        self.loaded_chunks.read().unwrap().get(&coord).cloned()
+        let chunk = self.loaded_chunks.read().unwrap().get(&coord).cloned();
+        if chunk.is_some() {
+            self.touch(coord);
+        }
+        chunk
     }
 
     /// Get all loaded chunks
@@ -230,15 +928,19 @@ impl ChunkManager {
                 continue;
             }
 
-            let chunk = self.generator.generate_chunk(coord)?;
-            self.loaded_chunks.write().unwrap().insert(coord, Arc::new(chunk));
+            let (chunk, generated) = self.cached_or_generated(coord)?;
+            self.insert_chunk(coord, chunk, generated)?;
         }
         Ok(())
     }
 
     /// Unload a specific chunk
     pub fn unload_chunk(&self, coord: ChunkCoord) -> Result<(), SpatialError> {
-        self.loaded_chunks.write().unwrap().remove(&coord);
+        let mut loaded = self.loaded_chunks.write().unwrap();
+        loaded.remove(&coord);
+        set_gauge(observability::CHUNKS_RESIDENT, loaded.len() as f64);
+        drop(loaded);
+        self.usage.write().unwrap().remove(&coord);
         Ok(())
     }
 
@@ -250,6 +952,8 @@ impl ChunkManager {
     /// Force unload all chunks (for cleanup)
     pub fn unload_all(&self) {
         self.loaded_chunks.write().unwrap().clear();
+        set_gauge(observability::CHUNKS_RESIDENT, 0.0);
+        self.usage.write().unwrap().clear();
         self.load_queue.write().unwrap().clear();
     }
 
@@ -261,6 +965,9 @@ impl ChunkManager {
         }
 
         let mut queue = self.load_queue.write().unwrap();
+        if queue.len() >= self.load_queue_capacity {
+            return Err(SpatialError::LoadQueueFull);
+        }
         queue.push_back((coord, priority));
         Ok(())
     }
@@ -269,6 +976,7 @@ impl ChunkManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::serialization::ChunkPacket;
 
     fn create_test_world() -> Arc<RwLock<World>> {
         Arc::new(RwLock::new(World::new(
@@ -297,6 +1005,76 @@ mod tests {
         assert_eq!(manager.generator().seed(), 42);
     }
 
+    #[tokio::test]
+    async fn test_chunk_manager_with_source() {
+        struct Flat;
+
+        impl TerrainSource for Flat {
+            fn height(&self, _x: f32, _y: f32) -> f32 {
+                42.0
+            }
+
+            fn biome(&self, _coord: ChunkCoord, _heightmap: &[f32]) -> crate::Biome {
+                crate::Biome::Desert
+            }
+        }
+
+        let world = create_test_world();
+        let manager = ChunkManager::with_source(world, 1, Arc::new(Flat));
+        manager.queue_chunk(ChunkCoord::new(3, 3), Priority::High).unwrap();
+        manager.process_load_queue().await.unwrap();
+
+        let chunk = manager.get_chunk(ChunkCoord::new(3, 3)).unwrap();
+        assert_eq!(chunk.biome, crate::Biome::Desert);
+        assert!(chunk.elevation.iter().all(|&h| h == 42.0));
+        assert_eq!(manager.generator().seed(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_chunk_source_with_fallback() {
+        /// Serves chunks in the first column, and is down everywhere else
+        struct Column;
+
+        impl ChunkSource for Column {
+            fn fetch(&self, coord: ChunkCoord, _timeout: std::time::Duration) -> Result<Option<ChunkPacket>, SpatialError> {
+                if coord.x > 0 {
+                    return Err(SpatialError::IoError {
+                        message: "service unavailable".to_string(),
+                    });
+                }
+                let mut chunk = Chunk::new(coord);
+                chunk.biome = crate::Biome::Tundra;
+                ChunkPacket::new(&chunk, TerrainGenerator::new().recipe()).map(Some)
+            }
+        }
+
+        let policy = FetchPolicy::default().with_retries(1, std::time::Duration::ZERO);
+        let manager = ChunkManager::new(create_test_world(), 1).with_chunk_source(Arc::new(Column), policy);
+        let mut events = manager.subscribe();
+        manager.queue_chunk(ChunkCoord::new(0, 4), Priority::High).unwrap();
+        manager.queue_chunk(ChunkCoord::new(2, 4), Priority::High).unwrap();
+        manager.process_load_queue_batch(2).await.unwrap();
+
+        let fetched = manager.get_chunk(ChunkCoord::new(0, 4)).unwrap();
+        assert!(fetched.loaded);
+        assert_eq!(fetched.biome, crate::Biome::Tundra);
+        let generated = TerrainGenerator::new().generate_chunk(ChunkCoord::new(2, 4)).unwrap();
+        assert_eq!(manager.get_chunk(ChunkCoord::new(2, 4)).unwrap().elevation, generated.elevation);
+        assert_eq!(events.recv().await, Some(GenerationEvent::Loaded { coord: ChunkCoord::new(0, 4), generated: true }));
+        let stats = manager.chunk_fetcher().unwrap().stats();
+        assert_eq!((stats.fetched, stats.retries, stats.failures), (1, 1, 1));
+
+        // Without fallback the load fails
+        let manager = ChunkManager::new(create_test_world(), 1)
+            .with_chunk_source(Arc::new(Column), policy.with_fallback(false));
+        manager.queue_chunk(ChunkCoord::new(2, 4), Priority::High).unwrap();
+        assert!(matches!(
+            manager.process_load_queue().await,
+            Err(SpatialError::ChunkFetchFailed { attempts: 2, .. })
+        ));
+        assert!(!manager.is_chunk_loaded(ChunkCoord::new(2, 4)));
+    }
+
     #[tokio::test]
     async fn test_update_visible_chunks() {
         let world = create_test_world();
@@ -383,9 +1161,10 @@ mod tests {
     #[tokio::test]
     async fn test_process_unload_queue() {
         let world = create_test_world();
-        let manager = ChunkManager::new(world, 1); // Small view distance
+        let chunk_bytes = chunk_memory(&painted_chunk());
+        let manager = ChunkManager::new(world, 1).with_memory_budget(2 * chunk_bytes);
 
-        // Preload more than max
+        // Preload more than the budget holds
         let coords = vec![
             ChunkCoord::new(0, 0),
             ChunkCoord::new(1, 0),
@@ -396,7 +1175,185 @@ mod tests {
         assert_eq!(manager.loaded_chunk_count(), 3);
 
         let unloaded = manager.process_unload_queue().unwrap();
-        assert!(unloaded.len() > 0);
-        assert!(manager.loaded_chunk_count() <= manager.max_loaded_chunks);
+        assert_eq!(unloaded, vec![ChunkCoord::new(0, 0)]);
+        assert_eq!(manager.loaded_chunk_count(), 2);
+        assert!(manager.memory_used() <= manager.memory_budget());
+
+        // Within the default budget nothing is evicted
+        let manager = ChunkManager::new(create_test_world(), 1);
+        manager.preload_chunks(vec![ChunkCoord::new(0, 0), ChunkCoord::new(1, 0)]).unwrap();
+        assert!(manager.process_unload_queue().unwrap().is_empty());
+        assert_eq!(manager.loaded_chunk_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_memory_budget_evicts_lru() {
+        let world = create_test_world();
+        let chunk_bytes = chunk_memory(&painted_chunk());
+        let manager = ChunkManager::new(world, 1).with_memory_budget(3 * chunk_bytes);
+
+        let coords: Vec<ChunkCoord> = (5..10).map(|x| ChunkCoord::new(x, 5)).collect();
+        manager.preload_chunks(coords.clone()).unwrap();
+        assert_eq!(manager.memory_used(), 5 * chunk_bytes);
+
+        // Using the oldest chunk keeps it loaded
+        manager.get_chunk(coords[0]).unwrap();
+        let unloaded = manager.process_unload_queue().unwrap();
+
+        assert_eq!(unloaded, vec![coords[1], coords[2]]);
+        assert_eq!(manager.loaded_chunk_count(), 3);
+        assert!(manager.memory_used() <= manager.memory_budget());
+        let stats = manager.eviction_stats();
+        assert_eq!(stats.evicted_chunks, 2);
+        assert_eq!(stats.evicted_bytes, 2 * chunk_bytes as u64);
+        assert_eq!(stats.eviction_passes, 1);
+        assert!(manager.process_unload_queue().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_visible_chunks_are_not_evicted() {
+        let world = create_test_world();
+        let chunk_bytes = chunk_memory(&painted_chunk());
+        let manager = ChunkManager::new(world, 1).with_memory_budget(chunk_bytes);
+
+        manager.update_visible_chunks(100.0, 100.0).await.unwrap();
+        manager.process_load_queue_batch(4).await.unwrap();
+        manager.preload_chunks(vec![ChunkCoord::new(8, 8)]).unwrap();
+
+        let unloaded = manager.process_unload_queue().unwrap();
+        assert_eq!(unloaded, vec![ChunkCoord::new(8, 8)]);
+        assert_eq!(manager.loaded_chunk_count(), 4);
+        assert_eq!(manager.eviction_stats().over_budget_passes, 1);
+    }
+
+    #[tokio::test]
+    async fn test_velocity_prefetch() {
+        let world = create_test_world();
+        let manager = ChunkManager::new(world, 3);
+
+        // Running along +x at 200 m/s reaches 800 meters ahead
+        manager
+            .update_visible_chunks_with_velocity(900.0, 900.0, 200.0, 0.0)
+            .await
+            .unwrap();
+
+        let queue: Vec<_> = manager.load_queue.read().unwrap().iter().cloned().collect();
+        let priority = |x, y| {
+            queue
+                .iter()
+                .find(|(c, _)| *c == ChunkCoord::new(x, y))
+                .map(|(_, p)| *p)
+        };
+
+        assert_eq!(priority(3, 3), Some(Priority::Critical));
+        // Edge of the view ahead is raised, the edge behind is not
+        assert_eq!(priority(6, 3), Some(Priority::High));
+        assert_eq!(priority(0, 3), Some(Priority::Low));
+        // Beyond the view along the path
+        assert_eq!(priority(7, 3), Some(Priority::Normal));
+        assert_eq!(priority(8, 3), None);
+        assert!(queue.windows(2).all(|w| w[0].1 >= w[1].1));
+        assert_eq!(manager.chunk_ref_count(ChunkCoord::new(7, 4)), 1);
+
+        // Standing still only queues the view
+        let manager = ChunkManager::new(create_test_world(), 3);
+        manager
+            .update_visible_chunks_with_velocity(900.0, 900.0, 0.0, 0.0)
+            .await
+            .unwrap();
+        assert_eq!(manager.load_queue_size(), 49);
+    }
+
+    #[tokio::test]
+    async fn test_multiple_observers() {
+        let world = create_test_world();
+        let chunk_bytes = chunk_memory(&painted_chunk());
+        let manager = ChunkManager::new(world, 1).with_memory_budget(chunk_bytes);
+
+        let first = manager.add_observer(Observer::new(300.0, 300.0));
+        let second = manager.add_observer(Observer::new(800.0, 300.0).with_view_distance(0));
+        assert_eq!(manager.observer_count(), 2);
+        manager.update_observers().await.unwrap();
+
+        // 3x3 around (1, 1) and the single chunk (3, 1)
+        assert_eq!(manager.load_queue_size(), 10);
+        assert_eq!(manager.chunk_ref_count(ChunkCoord::new(1, 1)), 1);
+        assert_eq!(manager.chunk_ref_count(ChunkCoord::new(3, 1)), 1);
+        assert_eq!(manager.chunk_ref_count(ChunkCoord::new(5, 5)), 0);
+
+        // Both observers now see (2, 1)
+        manager.move_observer(second, 600.0, 300.0, 0.0, 0.0).unwrap();
+        manager.update_observers().await.unwrap();
+        assert_eq!(manager.chunk_ref_count(ChunkCoord::new(2, 1)), 2);
+        assert_eq!(manager.chunk_ref_count(ChunkCoord::new(3, 1)), 0);
+        let queue: Vec<_> = manager.load_queue.read().unwrap().iter().cloned().collect();
+        assert!(queue.contains(&(ChunkCoord::new(2, 1), Priority::Critical)));
+
+        // Referenced chunks survive eviction, the rest go once no observer needs them
+        manager.process_load_queue_batch(20).await.unwrap();
+        manager.process_unload_queue().unwrap();
+        assert!(manager.is_chunk_loaded(ChunkCoord::new(2, 1)));
+        assert!(!manager.is_chunk_loaded(ChunkCoord::new(3, 1)));
+
+        manager.remove_observer(first).unwrap();
+        manager.update_observers().await.unwrap();
+        assert_eq!(manager.chunk_ref_count(ChunkCoord::new(2, 1)), 1);
+        manager.process_unload_queue().unwrap();
+        assert_eq!(manager.loaded_chunk_count(), 1);
+        assert!(manager.move_observer(first, 0.0, 0.0, 0.0, 0.0).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_parallel_generation() {
+        let world = create_test_world();
+        let manager = ChunkManager::new(world, 2).with_generation_threads(3).unwrap();
+        assert_eq!(manager.max_in_flight(), 6);
+        let mut events = manager.subscribe();
+
+        for x in 0..4 {
+            for y in 0..4 {
+                manager.queue_chunk(ChunkCoord::new(x, y), Priority::Normal).unwrap();
+            }
+        }
+
+        // Each call takes no more than the in-flight limit
+        let first = manager.process_load_queue_parallel(100).await.unwrap();
+        assert_eq!(first.len(), 6);
+        assert_eq!(manager.load_queue_size(), 10);
+        while !manager.process_load_queue_parallel(100).await.unwrap().is_empty() {}
+        assert_eq!(manager.loaded_chunk_count(), 16);
+
+        let mut loaded = 0;
+        while let Ok(event) = events.try_recv() {
+            assert!(matches!(event, GenerationEvent::Loaded { generated: true, .. }));
+            loaded += 1;
+        }
+        assert_eq!(loaded, 16);
+
+        // Same terrain as sequential generation
+        let sequential = TerrainGenerator::new().generate_chunk(ChunkCoord::new(3, 2)).unwrap();
+        assert_eq!(manager.get_chunk(ChunkCoord::new(3, 2)).unwrap().elevation, sequential.elevation);
+    }
+
+    #[tokio::test]
+    async fn test_load_queue_backpressure() {
+        let world = create_test_world();
+        let manager = ChunkManager::new(world, 5).with_load_queue_capacity(4);
+
+        for x in 0..4 {
+            manager.queue_chunk(ChunkCoord::new(x, 0), Priority::Low).unwrap();
+        }
+        assert!(matches!(
+            manager.queue_chunk(ChunkCoord::new(9, 9), Priority::Low),
+            Err(SpatialError::LoadQueueFull)
+        ));
+
+        // Visibility updates keep the most important chunks
+        manager.unload_all();
+        manager.update_visible_chunks(600.0, 600.0).await.unwrap();
+        let queue: Vec<_> = manager.load_queue.read().unwrap().iter().cloned().collect();
+        assert_eq!(queue.len(), 4);
+        assert_eq!(queue[0], (ChunkCoord::new(2, 2), Priority::Critical));
+        assert!(queue[1..].iter().all(|(_, p)| *p == Priority::High));
     }
 }
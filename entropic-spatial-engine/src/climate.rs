@@ -0,0 +1,299 @@
+//! Climate model for biome assignment
+//!
+//! Temperature falls off with latitude and elevation, moisture comes from noise with wetter
+//! lowlands, and biomes are picked from the two on a Whittaker-style diagram. Latitude
+//! repeats every two spans, so the world alternates between equator and pole bands.
+
+use crate::constants::*;
+use crate::noise::PerlinNoise;
+use crate::{Biome, ChunkCoord};
+use std::sync::Arc;
+
+/// Temperature and moisture at a point
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Climate {
+    /// Mean temperature (in degrees Celsius)
+    pub temperature: f32,
+    /// Relative moisture from 0 (arid) to 1 (saturated)
+    pub moisture: f32,
+}
+
+/// Temperature and moisture sampled over a chunk
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClimateMap {
+    /// Samples per side
+    pub resolution: usize,
+    /// Temperatures indexed `[y * resolution + x]`
+    pub temperature: Vec<f32>,
+    /// Moistures indexed `[y * resolution + x]`
+    pub moisture: Vec<f32>,
+}
+
+impl ClimateMap {
+    /// Get the mean climate over the map
+    pub fn mean(&self) -> Climate {
+        let count = self.temperature.len().max(1) as f32;
+        Climate {
+            temperature: self.temperature.iter().sum::<f32>() / count,
+            moisture: self.moisture.iter().sum::<f32>() / count,
+        }
+    }
+}
+
+/// Whittaker-style climate model
+#[derive(Clone)]
+pub struct ClimateModel {
+    noise: Arc<PerlinNoise>,
+    equator: f32,
+    latitude_span: f32,
+    equator_temperature: f32,
+    pole_temperature: f32,
+    lapse_rate: f32,
+    sea_level: f32,
+    mountain_level: f32,
+}
+
+impl ClimateModel {
+    /// Create a climate model with default settings
+    pub fn new() -> Self {
+        Self::with_seed(12345)
+    }
+
+    /// Create a climate model with a specific seed
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            // Offset the seed so climate noise doesn't mirror the terrain noise
+            noise: Arc::new(PerlinNoise::with_seed(seed.wrapping_add(0x5EED))),
+            equator: 0.0,
+            latitude_span: CLIMATE_LATITUDE_SPAN,
+            equator_temperature: 30.0,
+            pole_temperature: -20.0,
+            lapse_rate: CLIMATE_LAPSE_RATE,
+            sea_level: CLIMATE_SEA_LEVEL,
+            mountain_level: CLIMATE_MOUNTAIN_LEVEL,
+        }
+    }
+
+    /// Set the y coordinate of the equator
+    pub fn with_equator(mut self, equator: f32) -> Self {
+        self.equator = equator;
+        self
+    }
+
+    /// Set the distance from the equator to a pole (in meters)
+    pub fn with_latitude_span(mut self, latitude_span: f32) -> Self {
+        self.latitude_span = latitude_span.max(1.0);
+        self
+    }
+
+    /// Set the sea level temperatures at the equator and at the poles
+    pub fn with_temperatures(mut self, equator: f32, pole: f32) -> Self {
+        self.equator_temperature = equator;
+        self.pole_temperature = pole;
+        self
+    }
+
+    /// Set the temperature drop per meter above sea level
+    pub fn with_lapse_rate(mut self, lapse_rate: f32) -> Self {
+        self.lapse_rate = lapse_rate.max(0.0);
+        self
+    }
+
+    /// Set the heights below which chunks are ocean and above which they are mountains
+    pub fn with_levels(mut self, sea_level: f32, mountain_level: f32) -> Self {
+        self.sea_level = sea_level;
+        self.mountain_level = mountain_level.max(sea_level);
+        self
+    }
+
+    /// Get the latitude of a y coordinate, from 0 at the equator to 1 at a pole
+    pub fn latitude(&self, y: f32) -> f32 {
+        let t = ((y - self.equator) / self.latitude_span).abs() % 2.0;
+        if t > 1.0 {
+            2.0 - t
+        } else {
+            t
+        }
+    }
+
+    /// Get the climate at a world position with the given terrain height
+    pub fn climate_at(&self, x: f32, y: f32, height: f32) -> Climate {
+        let nx = (x / CHUNK_SIZE) as f64 * CLIMATE_NOISE_FREQUENCY;
+        let ny = (y / CHUNK_SIZE) as f64 * CLIMATE_NOISE_FREQUENCY;
+
+        let latitude = self.latitude(y);
+        let base = self.equator_temperature
+            + (self.pole_temperature - self.equator_temperature) * latitude;
+        let altitude = (height - self.sea_level).max(0.0);
+        let variation = self.noise.fbm(nx, ny, 3, 0.5, 2.0) as f32 * 5.0;
+        let temperature = base - altitude * self.lapse_rate + variation;
+
+        // Lowlands collect water, highlands dry out
+        let noise = (self.noise.fbm(nx + 1000.0, ny + 1000.0, 3, 0.5, 2.0) as f32 + 1.0) / 2.0;
+        let relief = (height - self.sea_level) / (self.mountain_level - self.sea_level).max(1.0);
+        let moisture = (noise - (relief - 0.5) * 0.3).clamp(0.0, 1.0);
+
+        Climate {
+            temperature,
+            moisture,
+        }
+    }
+
+    /// Sample the climate over a chunk
+    ///
+    /// `heights` is the chunk heightmap indexed `[x * HEIGHTMAP_RESOLUTION + y]`.
+    pub fn climate_map(&self, coord: ChunkCoord, heights: &[f32], resolution: usize) -> ClimateMap {
+        let resolution = resolution.max(1);
+        let step = CHUNK_SIZE / resolution as f32;
+        let scale = HEIGHTMAP_RESOLUTION as f32 / CHUNK_SIZE;
+        let mut map = ClimateMap {
+            resolution,
+            temperature: Vec::with_capacity(resolution * resolution),
+            moisture: Vec::with_capacity(resolution * resolution),
+        };
+
+        for j in 0..resolution {
+            for i in 0..resolution {
+                let local = ((i as f32 + 0.5) * step, (j as f32 + 0.5) * step);
+                let sx = ((local.0 * scale) as usize).min(HEIGHTMAP_RESOLUTION - 1);
+                let sy = ((local.1 * scale) as usize).min(HEIGHTMAP_RESOLUTION - 1);
+                let height = heights
+                    .get(sx * HEIGHTMAP_RESOLUTION + sy)
+                    .copied()
+                    .unwrap_or(self.sea_level);
+
+                let climate = self.climate_at(
+                    coord.x as f32 * CHUNK_SIZE + local.0,
+                    coord.y as f32 * CHUNK_SIZE + local.1,
+                    height,
+                );
+                map.temperature.push(climate.temperature);
+                map.moisture.push(climate.moisture);
+            }
+        }
+
+        map
+    }
+
+    /// Pick the biome of a chunk from its heightmap
+    pub fn chunk_biome(&self, coord: ChunkCoord, heights: &[f32]) -> Biome {
+        let mean_height = heights.iter().sum::<f32>() / heights.len().max(1) as f32;
+        let climate = self.climate_map(coord, heights, CLIMATE_MAP_RESOLUTION).mean();
+        self.biome(climate, mean_height)
+    }
+
+    /// Pick a biome from a climate and terrain height
+    pub fn biome(&self, climate: Climate, height: f32) -> Biome {
+        if height < self.sea_level {
+            return Biome::Ocean;
+        }
+        if height > self.mountain_level {
+            return Biome::Mountains;
+        }
+
+        match (climate.temperature, climate.moisture) {
+            (t, _) if t < 0.0 => Biome::Tundra,
+            // Boreal
+            (t, m) if t < 10.0 && m < 0.3 => Biome::Plains,
+            (t, _) if t < 10.0 => Biome::Forest,
+            // Temperate
+            (t, m) if t < 20.0 && m < 0.25 => Biome::Grassland,
+            (t, m) if t < 20.0 && m < 0.5 => Biome::Plains,
+            (t, m) if t < 20.0 && m < 0.8 => Biome::Forest,
+            (t, _) if t < 20.0 => Biome::Swamp,
+            // Tropical
+            (_, m) if m < 0.3 => Biome::Desert,
+            (_, m) if m < 0.55 => Biome::Grassland,
+            (_, m) if m < 0.85 => Biome::Forest,
+            _ => Biome::Swamp,
+        }
+    }
+}
+
+impl Default for ClimateModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flat_heights(height: f32) -> Vec<f32> {
+        vec![height; HEIGHTMAP_RESOLUTION * HEIGHTMAP_RESOLUTION]
+    }
+
+    #[test]
+    fn test_latitude_bands() {
+        let model = ClimateModel::new().with_latitude_span(1000.0);
+
+        assert_eq!(model.latitude(0.0), 0.0);
+        assert_eq!(model.latitude(500.0), 0.5);
+        assert_eq!(model.latitude(1000.0), 1.0);
+        assert_eq!(model.latitude(1500.0), 0.5);
+        assert_eq!(model.latitude(2000.0), 0.0);
+    }
+
+    #[test]
+    fn test_temperature_falls_with_latitude_and_elevation() {
+        let model = ClimateModel::new();
+        let span = CLIMATE_LATITUDE_SPAN;
+
+        let equator = model.climate_at(0.0, 0.0, CLIMATE_SEA_LEVEL);
+        let pole = model.climate_at(0.0, span, CLIMATE_SEA_LEVEL);
+        let peak = model.climate_at(0.0, 0.0, CLIMATE_MOUNTAIN_LEVEL);
+
+        assert!(equator.temperature > pole.temperature + 30.0);
+        assert!(peak.temperature < equator.temperature);
+        assert!((0.0..=1.0).contains(&equator.moisture));
+    }
+
+    #[test]
+    fn test_whittaker_biomes() {
+        let model = ClimateModel::new();
+        let climate = |temperature, moisture| Climate {
+            temperature,
+            moisture,
+        };
+
+        assert_eq!(model.biome(climate(25.0, 0.1), 150.0), Biome::Desert);
+        assert_eq!(model.biome(climate(25.0, 0.4), 150.0), Biome::Grassland);
+        assert_eq!(model.biome(climate(25.0, 0.7), 150.0), Biome::Forest);
+        assert_eq!(model.biome(climate(25.0, 0.9), 150.0), Biome::Swamp);
+        assert_eq!(model.biome(climate(15.0, 0.4), 150.0), Biome::Plains);
+        assert_eq!(model.biome(climate(5.0, 0.6), 150.0), Biome::Forest);
+        assert_eq!(model.biome(climate(-10.0, 0.6), 150.0), Biome::Tundra);
+        assert_eq!(model.biome(climate(25.0, 0.6), CLIMATE_SEA_LEVEL - 1.0), Biome::Ocean);
+        assert_eq!(model.biome(climate(25.0, 0.6), CLIMATE_MOUNTAIN_LEVEL + 1.0), Biome::Mountains);
+    }
+
+    #[test]
+    fn test_climate_bands_are_coherent() {
+        let model = ClimateModel::with_seed(7).with_latitude_span(64.0 * CHUNK_SIZE);
+        let heights = flat_heights(150.0);
+
+        // Polar chunks are cold, equatorial chunks are not
+        for x in 0..8 {
+            assert_eq!(model.chunk_biome(ChunkCoord::new(x, 64), &heights), Biome::Tundra);
+            assert_ne!(model.chunk_biome(ChunkCoord::new(x, 0), &heights), Biome::Tundra);
+        }
+
+        // Neighboring chunks mostly share a biome
+        let biomes: Vec<Biome> = (0..32).map(|x| model.chunk_biome(ChunkCoord::new(x, 10), &heights)).collect();
+        let changes = biomes.windows(2).filter(|pair| pair[0] != pair[1]).count();
+        assert!(changes < 8);
+    }
+
+    #[test]
+    fn test_climate_map() {
+        let model = ClimateModel::new();
+        let map = model.climate_map(ChunkCoord::new(2, 3), &flat_heights(120.0), 8);
+
+        assert_eq!(map.resolution, 8);
+        assert_eq!(map.temperature.len(), 64);
+        assert_eq!(map.moisture.len(), 64);
+        let mean = map.mean();
+        assert!(map.temperature.iter().copied().fold(f32::MAX, f32::min) <= mean.temperature);
+    }
+}
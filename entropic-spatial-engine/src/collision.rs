@@ -1,8 +1,15 @@
 use crate::constants::*;
 use crate::errors::SpatialError;
+use crate::shapes::{Capsule, CollisionShape, SweepHit, Vec3};
 use crate::{ChunkCoord, World};
 use entropic_world_core::constants::HEIGHTMAP_RESOLUTION;
 
+/// Shortest step of a terrain cast (in meters)
+const MIN_TERRAIN_CAST_STEP: f32 = 0.05;
+
+/// Bisection steps refining the time of impact of a terrain cast
+const TERRAIN_CAST_REFINEMENTS: usize = 24;
+
 /// Collision detection system
 pub struct CollisionDetector;
 
@@ -99,6 +106,163 @@ impl CollisionDetector {
         })
     }
 
+    /// Get terrain height at position, interpolated between the surrounding height samples
+    ///
+    /// Samples missing past the edge of the loaded terrain repeat the last one available.
+    pub fn interpolated_terrain_height(world: &World, x: f32, y: f32) -> Option<f32> {
+        if x < 0.0 || y < 0.0 {
+            return None;
+        }
+
+        let step = CHUNK_SIZE / HEIGHTMAP_RESOLUTION as f32;
+        let (gx, gy) = (x / step, y / step);
+        let (ix, iy) = (gx.floor() as u32, gy.floor() as u32);
+        let (fx, fy) = (gx - ix as f32, gy - iy as f32);
+
+        let [h00, h10, h01, h11] = Self::cell_heights(world, ix, iy)?;
+        let near = h00 + (h10 - h00) * fx;
+        let far = h01 + (h11 - h01) * fx;
+        Some(near + (far - near) * fy)
+    }
+
+    /// Get the heights at the corners of a heightmap cell, as `[h00, h10, h01, h11]`
+    pub(crate) fn cell_heights(world: &World, ix: u32, iy: u32) -> Option<[f32; 4]> {
+        let h00 = Self::height_sample(world, ix, iy)?;
+        let h10 = Self::height_sample(world, ix + 1, iy).unwrap_or(h00);
+        let h01 = Self::height_sample(world, ix, iy + 1).unwrap_or(h00);
+        let h11 = Self::height_sample(world, ix + 1, iy + 1).unwrap_or(h10);
+        Some([h00, h10, h01, h11])
+    }
+
+    /// Get the height sample at a global heightmap index
+    fn height_sample(world: &World, sx: u32, sy: u32) -> Option<f32> {
+        let resolution = HEIGHTMAP_RESOLUTION as u32;
+        let coord = ChunkCoord::new(sx / resolution, sy / resolution);
+        let (local_x, local_y) = ((sx % resolution) as usize, (sy % resolution) as usize);
+
+        world
+            .chunks
+            .get(&coord)
+            .map(|chunk| chunk.elevation[local_x * HEIGHTMAP_RESOLUTION + local_y])
+    }
+
+    /// Check collision between two shapes
+    pub fn shape_collision(a: &CollisionShape, b: &CollisionShape) -> bool {
+        a.intersects(b)
+    }
+
+    /// Get how deep a shape reaches below the terrain surface
+    ///
+    /// The terrain is sampled at heightmap resolution under the shape's footprint, plus its
+    /// edges and center, and compared with the bottom of the shape above each sample. Returns
+    /// `None` when the shape stays above the terrain or over unloaded chunks.
+    pub fn terrain_penetration(world: &World, shape: &CollisionShape) -> Option<f32> {
+        Self::deepest_terrain_contact(world, shape).map(|(depth, _)| depth)
+    }
+
+    /// Get the deepest penetration of a shape into the terrain and where it is
+    fn deepest_terrain_contact(world: &World, shape: &CollisionShape) -> Option<(f32, (f32, f32))> {
+        let bounds = shape.bounds();
+        let step = CHUNK_SIZE / HEIGHTMAP_RESOLUTION as f32;
+        let samples = |axis: usize| {
+            let (min, max) = (bounds.min[axis], bounds.max[axis]);
+            let mut values = vec![min, max, (min + max) * 0.5];
+            let mut value = (min / step).ceil() * step;
+            while value < max {
+                values.push(value);
+                value += step;
+            }
+            values
+        };
+
+        let ys = samples(1);
+        let mut deepest: Option<(f32, (f32, f32))> = None;
+        for x in samples(0) {
+            for &y in &ys {
+                let (Some(bottom), Some(height)) = (
+                    shape.lowest_point_at(x, y),
+                    Self::interpolated_terrain_height(world, x, y),
+                ) else {
+                    continue;
+                };
+                if bottom < height && deepest.filter(|&(depth, _)| depth >= height - bottom).is_none() {
+                    deepest = Some((height - bottom, (x, y)));
+                }
+            }
+        }
+
+        deepest
+    }
+
+    /// Check if a shape reaches below the terrain surface
+    pub fn shape_terrain_collision(world: &World, shape: &CollisionShape) -> bool {
+        Self::terrain_penetration(world, shape).is_some()
+    }
+
+    /// Get the terrain surface normal at position, from the interpolated heights around it
+    pub fn terrain_normal(world: &World, x: f32, y: f32) -> Option<Vec3> {
+        let step = CHUNK_SIZE / HEIGHTMAP_RESOLUTION as f32;
+        let height = |dx: f32, dy: f32| {
+            Self::interpolated_terrain_height(world, x + dx, y + dy)
+                .or_else(|| Self::interpolated_terrain_height(world, x, y))
+        };
+
+        let slope_x = (height(step, 0.0)? - height(-step, 0.0)?) / (2.0 * step);
+        let slope_y = (height(0.0, step)? - height(0.0, -step)?) / (2.0 * step);
+        let length = (slope_x * slope_x + slope_y * slope_y + 1.0).sqrt();
+        Some([-slope_x / length, -slope_y / length, 1.0 / length])
+    }
+
+    /// Cast a capsule along a motion and find where it first touches the terrain
+    ///
+    /// The motion is walked in steps of at most half the radius or half a heightmap cell, so
+    /// fast entities cannot skip over thin ridges, and the first step reaching below the surface
+    /// is refined by bisection. The hit time is the last moment the capsule was still clear.
+    pub fn cast_against_terrain(world: &World, capsule: &Capsule, motion: Vec3) -> Option<SweepHit> {
+        let contact_at = |time: f32| {
+            let moved = capsule.translated(motion.map(|m| m * time));
+            Self::deepest_terrain_contact(world, &moved.into()).map(|(_, position)| position)
+        };
+        let hit = |time: f32, (x, y): (f32, f32)| {
+            Some(SweepHit {
+                time,
+                point: [x, y, Self::interpolated_terrain_height(world, x, y)?],
+                normal: Self::terrain_normal(world, x, y)?,
+            })
+        };
+
+        if let Some(position) = contact_at(0.0) {
+            return hit(0.0, position);
+        }
+
+        let cell = CHUNK_SIZE / HEIGHTMAP_RESOLUTION as f32;
+        let step = (capsule.radius.min(cell) * 0.5).max(MIN_TERRAIN_CAST_STEP);
+        let distance = motion.iter().map(|m| m * m).sum::<f32>().sqrt();
+        let steps = (distance / step).ceil().max(1.0) as usize;
+
+        for i in 1..=steps {
+            let time = i as f32 / steps as f32;
+            let Some(mut position) = contact_at(time) else {
+                continue;
+            };
+
+            let (mut clear, mut touching) = ((i - 1) as f32 / steps as f32, time);
+            for _ in 0..TERRAIN_CAST_REFINEMENTS {
+                let middle = (clear + touching) * 0.5;
+                match contact_at(middle) {
+                    Some(found) => {
+                        touching = middle;
+                        position = found;
+                    }
+                    None => clear = middle,
+                }
+            }
+            return hit(clear, position);
+        }
+
+        None
+    }
+
     /// Check if point is underwater
     pub fn is_underwater(world: &World, x: f32, y: f32) -> bool {
         let chunk_x = (x / CHUNK_SIZE).floor() as u32;
@@ -237,4 +401,94 @@ mod tests {
         assert!(height.is_some());
         assert_eq!(height.unwrap(), 150.0);
     }
+
+    fn create_flat_world(height: f32) -> World {
+        let mut world = create_test_world();
+        for chunk in world.chunks.values_mut() {
+            chunk.elevation.iter_mut().for_each(|h| *h = height);
+        }
+        world
+    }
+
+    #[test]
+    fn test_interpolated_terrain_height() {
+        let mut world = create_flat_world(100.0);
+        // Ramp rising 1m per meter along x, across the chunk border
+        for chunk in world.chunks.values_mut() {
+            for x in 0..HEIGHTMAP_RESOLUTION {
+                for y in 0..HEIGHTMAP_RESOLUTION {
+                    chunk.elevation[x * HEIGHTMAP_RESOLUTION + y] = (chunk.coord.x as usize * HEIGHTMAP_RESOLUTION + x) as f32;
+                }
+            }
+        }
+
+        let height = CollisionDetector::interpolated_terrain_height(&world, 10.25, 30.5).unwrap();
+        assert!((height - 10.25).abs() < 1e-4);
+        let height = CollisionDetector::interpolated_terrain_height(&world, 255.5, 3.0).unwrap();
+        assert!((height - 255.5).abs() < 1e-4);
+        assert_eq!(CollisionDetector::interpolated_terrain_height(&world, -1.0, 3.0), None);
+        assert_eq!(CollisionDetector::interpolated_terrain_height(&world, 600.0, 3.0), None);
+    }
+
+    #[test]
+    fn test_shape_terrain_collision() {
+        use crate::shapes::{Aabb, Capsule, Obb};
+
+        let world = create_flat_world(100.0);
+
+        let sphere = CollisionShape::sphere([50.3, 60.7, 100.5], 1.0);
+        let depth = CollisionDetector::terrain_penetration(&world, &sphere).unwrap();
+        assert!((depth - 0.5).abs() < 1e-3);
+        assert!(!CollisionDetector::shape_terrain_collision(&world, &sphere.translated([0.0, 0.0, 1.0])));
+
+        let body = CollisionShape::from(Capsule::upright([20.0, 20.0, 99.8], 2.0, 0.4));
+        assert!(CollisionDetector::shape_terrain_collision(&world, &body));
+        assert!(!CollisionDetector::shape_terrain_collision(&world, &body.translated([0.0, 0.0, 0.3])));
+
+        // A crate turned on its corner dips 0.2m below the ground
+        let tilted = Obb::from_axes([80.0, 80.0, 100.0 + 2f32.sqrt() - 0.2], [1.0; 3], [1.0, 0.0, 1.0], [0.0, 1.0, 0.0]);
+        let depth = CollisionDetector::terrain_penetration(&world, &tilted.into()).unwrap();
+        assert!((depth - 0.2).abs() < 1e-3);
+
+        let hovering: CollisionShape = Aabb::new([10.0, 10.0, 100.1], [30.0, 20.2, 104.0]).into();
+        assert!(!CollisionDetector::shape_terrain_collision(&world, &hovering));
+        assert!(CollisionDetector::shape_collision(&hovering, &body));
+
+        // Nothing collides over terrain that is not loaded
+        let outside = CollisionShape::sphere([2000.0, 2000.0, 0.0], 5.0);
+        assert_eq!(CollisionDetector::terrain_penetration(&world, &outside), None);
+    }
+
+    #[test]
+    fn test_cast_against_terrain() {
+        let mut world = create_flat_world(100.0);
+
+        // Landing on flat ground
+        let ball = Capsule::sphere([40.0, 40.0, 110.0], 1.0);
+        let hit = CollisionDetector::cast_against_terrain(&world, &ball, [0.0, 0.0, -20.0]).unwrap();
+        assert!((hit.time - 0.45).abs() < 1e-3);
+        assert!((hit.point[2] - 100.0).abs() < 1e-4);
+        assert_eq!(hit.normal, [0.0, 0.0, 1.0]);
+        assert_eq!(CollisionDetector::cast_against_terrain(&world, &ball, [0.0, 0.0, 5.0]), None);
+
+        // A one-sample-wide wall across the path, missed when only checking where the motion ends
+        if let Some(chunk) = world.chunks.get_mut(&ChunkCoord::new(0, 0)) {
+            for y in 0..HEIGHTMAP_RESOLUTION {
+                chunk.elevation[100 * HEIGHTMAP_RESOLUTION + y] = 150.0;
+            }
+        }
+        let runner = Capsule::upright([50.0, 40.0, 120.0], 2.0, 0.5);
+        let motion = [100.0, 0.0, 0.0];
+        let end = CollisionShape::from(runner.translated(motion));
+        assert!(!CollisionDetector::shape_terrain_collision(&world, &end));
+
+        let hit = CollisionDetector::cast_against_terrain(&world, &runner, motion).unwrap();
+        assert!(hit.time > 0.48 && hit.time < 0.5, "hit at {}", hit.time);
+        assert!(hit.point[0] > 99.0 && hit.point[0] < 100.0);
+        assert!(hit.normal[0] < 0.0 && hit.normal[2] > 0.0);
+        assert!(!CollisionDetector::shape_terrain_collision(&world, &runner.translated(motion.map(|m| m * hit.time)).into()));
+
+        let over = runner.translated([0.0, 0.0, 40.0]);
+        assert_eq!(CollisionDetector::cast_against_terrain(&world, &over, motion), None);
+    }
 }
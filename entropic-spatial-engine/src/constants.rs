@@ -12,15 +12,60 @@ pub const ENTITY_RADIUS: f32 = 1.0;
 /// Maximum number of chunks to keep loaded in memory
 pub const MAX_LOADED_CHUNKS: usize = 10_000;
 
+/// Maximum number of chunks waiting in the load queue
+pub const LOAD_QUEUE_CAPACITY: usize = 16_384;
+
+/// Chunks generated at once per generation thread
+pub const CHUNKS_IN_FLIGHT_PER_THREAD: usize = 2;
+
+/// Time allowed for each attempt at fetching a chunk from a chunk source (in milliseconds)
+pub const CHUNK_FETCH_TIMEOUT_MS: u64 = 2_000;
+
+/// Attempts at fetching a chunk made after the first one fails
+pub const CHUNK_FETCH_RETRIES: u32 = 2;
+
+/// Wait before retrying a chunk fetch, doubling for every further retry (in milliseconds)
+pub const CHUNK_FETCH_RETRY_DELAY_MS: u64 = 100;
+
 /// Default view distance (in chunks)
 pub const DEFAULT_VIEW_DISTANCE: u32 = 5;
 
+/// How far ahead moving observers get chunks prefetched (in seconds of travel)
+pub const PREFETCH_LOOKAHEAD_SECONDS: f32 = 4.0;
+
 /// Pathfinding grid cell size (in meters)
 pub const PATHFINDING_GRID_SIZE: f32 = 16.0;
 
 /// Maximum pathfinding iterations
 pub const MAX_PATHFINDING_ITERATIONS: u32 = 10_000;
 
+/// Steepest slope a smoothed path may cross between grid cells (in degrees)
+pub const PATH_SMOOTHING_MAX_SLOPE_DEGREES: f32 = 45.0;
+
+/// Navmesh sampling cell size (in meters)
+pub const NAVMESH_CELL_SIZE: f32 = 4.0;
+
+/// Steepest slope walkable on the navmesh (in degrees)
+pub const NAVMESH_MAX_SLOPE_DEGREES: f32 = 45.0;
+
+/// Largest height difference walkable between navmesh cells (in meters)
+pub const NAVMESH_MAX_STEP: f32 = 1.0;
+
+/// Radius around structures excluded from the navmesh (in meters)
+pub const NAVMESH_STRUCTURE_RADIUS: f32 = 4.0;
+
+/// Radius searched for neighbors by local steering (in meters)
+pub const STEERING_NEIGHBOR_RADIUS: f32 = 12.0;
+
+/// Look-ahead time for velocity obstacle avoidance (in seconds)
+pub const STEERING_TIME_HORIZON: f32 = 2.0;
+
+/// Default maximum speed of steered entities (in meters per second)
+pub const STEERING_MAX_SPEED: f32 = 2.0;
+
+/// Heights probed per side when fingerprinting a terrain recipe
+pub const TERRAIN_RECIPE_PROBES: usize = 8;
+
 /// Noise octaves for terrain generation
 pub const TERRAIN_NOISE_OCTAVES: u32 = 6;
 
@@ -30,6 +75,86 @@ pub const TERRAIN_NOISE_FREQUENCY: f64 = 0.001;
 /// Noise frequency for biome generation
 pub const BIOME_NOISE_FREQUENCY: f64 = 0.01;
 
+/// Number of hydraulic erosion droplets simulated per chunk
+pub const EROSION_DROPLETS_PER_CHUNK: u32 = 20_000;
+
+/// Steepest stable slope for thermal erosion (in degrees)
+pub const EROSION_TALUS_ANGLE_DEGREES: f32 = 40.0;
+
+/// Grid cell size for river and lake generation (in meters)
+pub const HYDROLOGY_CELL_SIZE: f32 = 8.0;
+
+/// Drainage area above which a cell carries a river (in square meters)
+pub const HYDROLOGY_RIVER_THRESHOLD: f32 = 20_000.0;
+
+/// Channel depth carved for the smallest rivers (in meters)
+pub const HYDROLOGY_CHANNEL_DEPTH: f32 = 1.0;
+
+/// Distance from the equator to a pole for climate bands (in meters)
+pub const CLIMATE_LATITUDE_SPAN: f32 = 16_384.0;
+
+/// Temperature drop per meter above sea level (in degrees Celsius)
+///
+/// Steeper than on Earth, since terrain heights only span 0-255 meters.
+pub const CLIMATE_LAPSE_RATE: f32 = 0.08;
+
+/// Mean chunk height below which a chunk is ocean
+pub const CLIMATE_SEA_LEVEL: f32 = 100.0;
+
+/// Mean chunk height above which a chunk is mountains
+pub const CLIMATE_MOUNTAIN_LEVEL: f32 = 170.0;
+
+/// Climate noise frequency (per chunk)
+pub const CLIMATE_NOISE_FREQUENCY: f64 = 0.05;
+
+/// Climate samples per chunk side when assigning biomes
+pub const CLIMATE_MAP_RESOLUTION: usize = 16;
+
+/// Noise frequency for cave tunnels (per meter)
+pub const CAVE_NOISE_FREQUENCY: f64 = 0.05;
+
+/// Side of the regions cave worms are seeded in (in meters)
+pub const CAVE_WORM_REGION: f32 = 64.0;
+
+/// Margin over which imported heightmaps fade into procedural terrain (in meters)
+pub const HEIGHTMAP_BLEND_WIDTH: f32 = 32.0;
+
+/// Terrain slope (rise over run) where rock starts to show in splat maps
+pub const SPLAT_ROCK_SLOPE_START: f32 = 0.6;
+
+/// Terrain slope where splat maps are all rock
+pub const SPLAT_ROCK_SLOPE_FULL: f32 = 1.2;
+
+/// Height where snow is halfway blended into splat maps (in meters)
+pub const SPLAT_SNOW_LINE: f32 = 210.0;
+
+/// Height range over which snow blends in around the snow line (in meters)
+pub const SPLAT_SNOW_BLEND: f32 = 20.0;
+
+/// Height above the water level that sandy beaches reach (in meters)
+pub const SPLAT_SAND_BAND: f32 = 4.0;
+
+/// Candidate points drawn per grid cell when scattering
+pub const SCATTER_CANDIDATES_PER_CELL: u32 = 4;
+
+/// Fog-of-war cells along each side of a chunk
+pub const FOG_CELLS_PER_CHUNK: usize = 16;
+
+/// Sight range of entities revealing the fog of war (in meters)
+pub const DEFAULT_SIGHT_RANGE: f32 = 64.0;
+
+/// Influence map cells along each side of a chunk
+pub const INFLUENCE_CELLS_PER_CHUNK: usize = 8;
+
+/// Fraction of presence and threat influence fading away every update
+pub const INFLUENCE_DECAY_RATE: f32 = 0.1;
+
+/// Radius of the influence an NPC projects around itself (in meters)
+pub const INFLUENCE_PRESENCE_RADIUS: f32 = 48.0;
+
+/// Deepest level octree leaves split to
+pub const OCTREE_MAX_DEPTH: usize = 10;
+
 /// Target chunk generation time (in milliseconds)
 pub const TARGET_CHUNK_GENERATION_MS: u64 = 50;
 
@@ -38,3 +163,6 @@ pub const TARGET_SPATIAL_QUERY_MS: u64 = 10;
 
 /// Target pathfinding time (in milliseconds)
 pub const TARGET_PATHFINDING_MS: u64 = 5;
+
+/// Distance past an LOD band boundary before a chunk switches level (in meters)
+pub const LOD_HYSTERESIS: f32 = 32.0;
@@ -0,0 +1,390 @@
+//! Cross-platform deterministic terrain
+//!
+//! Terrain has to come out bit-identical on x86 servers, ARM clients and WASM. Rust never
+//! fuses or reorders float operations, and IEEE 754 pins down the results of addition,
+//! multiplication, division and square roots, so the terrain pipeline sticks to those and
+//! avoids platform math libraries for anything transcendental. The fixed-point mode goes
+//! further and computes heights with integer-only noise.
+//!
+//! Conformance vectors record terrain heights for known inputs. Checking them on every
+//! target, or sending them from a server to its clients, catches platforms that diverge.
+
+use crate::constants::*;
+use crate::erosion::SplitMix64;
+use crate::errors::SpatialError;
+use crate::terrain_source::TerrainSource;
+use crate::TerrainGenerator;
+
+/// One in Q16.16 fixed point
+pub const FIXED_ONE: i64 = 1 << 16;
+
+/// How terrain heights are computed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Determinism {
+    /// Floating-point Perlin noise, identical wherever IEEE 754 is followed
+    #[default]
+    Native,
+    /// Integer noise in Q16.16 fixed point, identical on every platform
+    FixedPoint,
+}
+
+/// Gradient noise computed entirely in Q16.16 fixed point
+#[derive(Clone, Debug)]
+pub struct FixedNoise {
+    permutation: [u8; 256],
+}
+
+impl FixedNoise {
+    /// Create fixed-point noise with a specific seed
+    pub fn with_seed(seed: u32) -> Self {
+        let mut permutation = [0u8; 256];
+        for (i, p) in permutation.iter_mut().enumerate() {
+            *p = i as u8;
+        }
+
+        let mut rng = SplitMix64::new(seed as u64);
+        for i in (1..256).rev() {
+            let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+            permutation.swap(i, j);
+        }
+
+        Self { permutation }
+    }
+
+    /// Get noise in [-1, 1] at a position, both in Q16.16
+    pub fn get(&self, x: i64, y: i64) -> i64 {
+        let (xi, yi) = (x >> 16, y >> 16);
+        let (xf, yf) = (x & (FIXED_ONE - 1), y & (FIXED_ONE - 1));
+
+        let corner = |dx: i64, dy: i64| {
+            let hash = self.hash(xi + dx, yi + dy);
+            gradient(hash, xf - dx * FIXED_ONE, yf - dy * FIXED_ONE)
+        };
+        let (u, v) = (fade(xf), fade(yf));
+
+        let bottom = lerp(corner(0, 0), corner(1, 0), u);
+        let top = lerp(corner(0, 1), corner(1, 1), u);
+        lerp(bottom, top, v).clamp(-FIXED_ONE, FIXED_ONE)
+    }
+
+    /// Fractal noise in [-1, 1], halving the amplitude and doubling the frequency per octave
+    pub fn fbm(&self, x: i64, y: i64, octaves: u32) -> i64 {
+        let (mut total, mut max_value) = (0i64, 0i64);
+
+        for octave in 0..octaves.clamp(1, 16) {
+            // Shift octaves apart so their lattices don't line up at the origin
+            let shift = octave as i64 * 19 * FIXED_ONE;
+            let amplitude = FIXED_ONE >> octave;
+            total += self.get((x << octave) + shift, (y << octave) + shift) * amplitude;
+            max_value += amplitude;
+        }
+
+        total / max_value
+    }
+
+    /// Get fractal noise at a floating-point position
+    pub fn sample(&self, x: f64, y: f64, octaves: u32) -> f64 {
+        self.fbm(to_fixed(x), to_fixed(y), octaves) as f64 / FIXED_ONE as f64
+    }
+
+    fn hash(&self, x: i64, y: i64) -> u8 {
+        let p = &self.permutation;
+        p[(p[(x & 255) as usize] as i64 + y).rem_euclid(256) as usize]
+    }
+}
+
+/// Convert to Q16.16, rounding down
+pub fn to_fixed(value: f64) -> i64 {
+    (value * FIXED_ONE as f64).floor() as i64
+}
+
+/// Quintic fade curve `6t^5 - 15t^4 + 10t^3` in Q16.16
+fn fade(t: i64) -> i64 {
+    let t3 = (((t * t) >> 16) * t) >> 16;
+    let inner = ((t * (t * 6 - 15 * FIXED_ONE)) >> 16) + 10 * FIXED_ONE;
+    (t3 * inner) >> 16
+}
+
+fn lerp(a: i64, b: i64, t: i64) -> i64 {
+    a + (((b - a) * t) >> 16)
+}
+
+/// Dot product with one of eight gradient directions
+fn gradient(hash: u8, x: i64, y: i64) -> i64 {
+    match hash & 7 {
+        0 => x + y,
+        1 => -x + y,
+        2 => x - y,
+        3 => -x - y,
+        4 => x,
+        5 => -x,
+        6 => y,
+        _ => -y,
+    }
+}
+
+/// Tangent using only basic arithmetic, for angles in [-pi/2, pi/2]
+///
+/// Platform math libraries are free to round `tan` differently.
+pub fn tan(radians: f32) -> f32 {
+    let x = radians as f64;
+    let (mut sin, mut cos) = (0.0f64, 0.0f64);
+    let (mut sin_term, mut cos_term) = (x, 1.0f64);
+
+    for n in 0..12 {
+        sin += sin_term;
+        cos += cos_term;
+        let k = 2.0 * n as f64;
+        sin_term *= -x * x / ((k + 2.0) * (k + 3.0));
+        cos_term *= -x * x / ((k + 1.0) * (k + 2.0));
+    }
+
+    (sin / cos) as f32
+}
+
+/// Checksum of a heightmap over the exact bits of every height
+pub fn heightmap_checksum(heights: &[f32]) -> u64 {
+    heights
+        .iter()
+        .fold(FNV_OFFSET, |hash, h| fnv1a(hash, &h.to_bits().to_le_bytes()))
+}
+
+/// Initial state of an FNV-1a hash
+pub(crate) const FNV_OFFSET: u64 = 0xCBF2_9CE4_8422_2325;
+
+/// Continue an FNV-1a hash with more bytes
+pub(crate) fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(hash, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01B3))
+}
+
+/// Terrain heights expected for a seed and position
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConformanceVector {
+    pub seed: u32,
+    pub x: f32,
+    pub y: f32,
+    /// Bits of the height in [`Determinism::Native`] mode
+    pub native: u32,
+    /// Bits of the height in [`Determinism::FixedPoint`] mode
+    pub fixed: u32,
+}
+
+/// Reference vectors every platform has to reproduce
+///
+/// Recorded with [`conformance_vectors`] on x86_64. A change to these values is a change to
+/// the terrain of every existing world.
+pub const CONFORMANCE_VECTORS: &[ConformanceVector] = &[
+    ConformanceVector {
+        seed: 0,
+        x: 899174.5,
+        y: 984741.5,
+        native: 0x42CF_11AF,
+        fixed: 0x4304_A6D4,
+    },
+    ConformanceVector {
+        seed: 0,
+        x: 531578.0,
+        y: 963676.0,
+        native: 0x4308_CFC3,
+        fixed: 0x42CE_7CB4,
+    },
+    ConformanceVector {
+        seed: 0,
+        x: 656068.0,
+        y: 620075.0,
+        native: 0x42FE_8058,
+        fixed: 0x4302_1FDD,
+    },
+    ConformanceVector {
+        seed: 12345,
+        x: 147573.0,
+        y: 369237.5,
+        native: 0x42C6_2ED7,
+        fixed: 0x4307_F483,
+    },
+    ConformanceVector {
+        seed: 12345,
+        x: 442094.5,
+        y: 279658.0,
+        native: 0x429E_60E5,
+        fixed: 0x42DA_27FC,
+    },
+    ConformanceVector {
+        seed: 12345,
+        x: 253786.5,
+        y: 557984.5,
+        native: 0x4302_6476,
+        fixed: 0x4313_6DFE,
+    },
+    ConformanceVector {
+        seed: 12345,
+        x: 157347.0,
+        y: 184405.5,
+        native: 0x4322_E8F9,
+        fixed: 0x42E4_64B6,
+    },
+    ConformanceVector {
+        seed: 2024,
+        x: 817681.0,
+        y: 885016.5,
+        native: 0x432C_5BBE,
+        fixed: 0x42D4_F930,
+    },
+    ConformanceVector {
+        seed: 2024,
+        x: 962609.5,
+        y: 544188.5,
+        native: 0x4309_13AF,
+        fixed: 0x4313_E784,
+    },
+    ConformanceVector {
+        seed: 2024,
+        x: 234688.5,
+        y: 573447.5,
+        native: 0x42E0_A043,
+        fixed: 0x4306_2455,
+    },
+    ConformanceVector {
+        seed: 2024,
+        x: 776785.5,
+        y: 770117.0,
+        native: 0x4323_0849,
+        fixed: 0x42CB_91A2,
+    },
+];
+
+/// Record conformance vectors on this platform
+///
+/// Positions are spread over the world from the seed, so a server can send the vectors of
+/// its seed to clients and have them checked with [`check_conformance`].
+pub fn conformance_vectors(seed: u32, count: usize) -> Vec<ConformanceVector> {
+    let native = TerrainGenerator::with_seed(seed);
+    let fixed = TerrainGenerator::with_seed(seed).with_determinism(Determinism::FixedPoint);
+    let mut rng = SplitMix64::new(seed as u64 ^ 0xC0FF_EE00);
+
+    (0..count)
+        .map(|_| {
+            // Whole and half meters keep the positions exact in every representation
+            let x = (rng.next_u64() % 2_000_000) as f32 / 2.0;
+            let y = (rng.next_u64() % 2_000_000) as f32 / 2.0;
+            ConformanceVector {
+                seed,
+                x,
+                y,
+                native: native.height(x, y).to_bits(),
+                fixed: fixed.height(x, y).to_bits(),
+            }
+        })
+        .collect()
+}
+
+/// Check that this platform reproduces conformance vectors
+pub fn check_conformance(vectors: &[ConformanceVector]) -> Result<(), SpatialError> {
+    for vector in vectors {
+        let native = TerrainGenerator::with_seed(vector.seed);
+        let fixed = TerrainGenerator::with_seed(vector.seed).with_determinism(Determinism::FixedPoint);
+        let actual = (
+            native.height(vector.x, vector.y).to_bits(),
+            fixed.height(vector.x, vector.y).to_bits(),
+        );
+
+        if actual != (vector.native, vector.fixed) {
+            return Err(SpatialError::TerrainError {
+                message: format!(
+                    "conformance mismatch for seed {} at ({}, {}): expected {:#010x}/{:#010x}, got {:#010x}/{:#010x}",
+                    vector.seed, vector.x, vector.y, vector.native, vector.fixed, actual.0, actual.1
+                ),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Map noise in [-1, 1] from Q16.16 to a terrain height of 0-255
+///
+/// The intermediate stays below 2^24, so the conversion to f32 is exact.
+pub(crate) fn fixed_height(noise: i64) -> f32 {
+    let scaled = (noise.clamp(-FIXED_ONE, FIXED_ONE) + FIXED_ONE) * 255 / 2;
+    scaled as f32 / FIXED_ONE as f32
+}
+
+/// Sample the fixed-point terrain noise at a world position
+pub(crate) fn fixed_terrain_noise(noise: &FixedNoise, world_x: f64, world_y: f64) -> i64 {
+    noise.fbm(
+        to_fixed(world_x * TERRAIN_NOISE_FREQUENCY),
+        to_fixed(world_y * TERRAIN_NOISE_FREQUENCY),
+        TERRAIN_NOISE_OCTAVES,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChunkCoord;
+
+    #[test]
+    fn test_fixed_noise_range_and_continuity() {
+        let noise = FixedNoise::with_seed(1);
+        let mut previous = noise.get(0, 5 * FIXED_ONE / 3);
+
+        for i in 1..4000 {
+            let value = noise.get(i * FIXED_ONE / 200, 5 * FIXED_ONE / 3);
+            assert!((-FIXED_ONE..=FIXED_ONE).contains(&value));
+            assert!((value - previous).abs() < FIXED_ONE / 20);
+            previous = value;
+        }
+
+        // Zero at lattice points, like Perlin noise
+        assert_eq!(noise.get(3 * FIXED_ONE, -7 * FIXED_ONE), 0);
+    }
+
+    #[test]
+    fn test_fixed_noise_is_seeded() {
+        let a = FixedNoise::with_seed(5);
+        let b = FixedNoise::with_seed(6);
+        let points: Vec<(f64, f64)> = (0..100).map(|i| (i as f64 * 0.37, i as f64 * -0.61)).collect();
+
+        assert!(points.iter().all(|&(x, y)| a.sample(x, y, 4) == FixedNoise::with_seed(5).sample(x, y, 4)));
+        assert!(points.iter().any(|&(x, y)| a.sample(x, y, 4) != b.sample(x, y, 4)));
+        assert!(points.iter().all(|&(x, y)| (-1.0..=1.0).contains(&a.sample(x, y, 6))));
+    }
+
+    #[test]
+    fn test_tan() {
+        for degrees in [0.0f32, 10.0, 33.5, 40.0, 60.0, 80.0] {
+            let radians = degrees.to_radians();
+            assert!((tan(radians) - radians.tan()).abs() <= radians.tan() * 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_fixed_point_generation() {
+        let generator = TerrainGenerator::with_seed(8).with_determinism(Determinism::FixedPoint);
+        let chunk = generator.generate_chunk(ChunkCoord::new(4, 9)).unwrap();
+        let again = generator.generate_chunk(ChunkCoord::new(4, 9)).unwrap();
+
+        assert!(chunk.elevation.iter().all(|h| (0.0..=255.0).contains(h)));
+        assert_eq!(heightmap_checksum(&chunk.elevation), heightmap_checksum(&again.elevation));
+        // Heights land on the Q16.16 grid
+        assert!(chunk.elevation.iter().all(|h| (h * FIXED_ONE as f32).fract() == 0.0));
+    }
+
+    #[test]
+    fn test_reference_vectors() {
+        assert!(!CONFORMANCE_VECTORS.is_empty());
+        check_conformance(CONFORMANCE_VECTORS).unwrap();
+    }
+
+    #[test]
+    fn test_recorded_vectors_roundtrip() {
+        let vectors = conformance_vectors(77, 16);
+        check_conformance(&vectors).unwrap();
+
+        let mut tampered = vectors[3];
+        tampered.fixed ^= 1;
+        assert!(check_conformance(&[tampered]).is_err());
+    }
+}
@@ -0,0 +1,384 @@
+//! Erosion passes for generated heightmaps
+//!
+//! Hydraulic erosion simulates rain droplets that pick up sediment on steep descents and drop
+//! it where they slow down, carving valleys and leaving ridgelines. Thermal erosion then lets
+//! material slide down slopes steeper than the talus angle. Both passes work on a single
+//! chunk heightmap indexed `[x * resolution + y]` with one sample per meter, and fade out
+//! toward the chunk edges so neighboring chunks still line up.
+
+use crate::constants::*;
+use crate::determinism;
+
+/// Tuning of the hydraulic and thermal erosion passes
+#[derive(Clone, Debug, PartialEq)]
+pub struct ErosionConfig {
+    /// Number of droplets simulated per chunk
+    pub droplets: u32,
+    /// Maximum number of steps a droplet lives
+    pub droplet_lifetime: u32,
+    /// How much a droplet keeps its direction instead of following the slope (0..1)
+    pub inertia: f32,
+    /// Sediment a droplet can carry per unit of speed, water and descent
+    pub sediment_capacity: f32,
+    /// Minimum carrying capacity, so droplets on flat ground still erode a little
+    pub min_sediment_capacity: f32,
+    /// Fraction of missing capacity picked up per step (0..1)
+    pub erode_speed: f32,
+    /// Fraction of excess sediment dropped per step (0..1)
+    pub deposit_speed: f32,
+    /// Fraction of water evaporated per step (0..1)
+    pub evaporate_speed: f32,
+    /// Acceleration of droplets going downhill
+    pub gravity: f32,
+    /// Number of thermal erosion iterations, 0 to disable the pass
+    pub thermal_iterations: u32,
+    /// Steepest stable slope for thermal erosion (in degrees)
+    pub talus_angle: f32,
+    /// Fraction of the excess slope moved per thermal iteration (0..0.5)
+    pub thermal_rate: f32,
+    /// Width of the border band where erosion fades out (in samples)
+    pub edge_margin: usize,
+}
+
+impl Default for ErosionConfig {
+    fn default() -> Self {
+        Self {
+            droplets: EROSION_DROPLETS_PER_CHUNK,
+            droplet_lifetime: 48,
+            inertia: 0.05,
+            sediment_capacity: 4.0,
+            min_sediment_capacity: 0.01,
+            erode_speed: 0.3,
+            deposit_speed: 0.3,
+            evaporate_speed: 0.01,
+            gravity: 4.0,
+            thermal_iterations: 8,
+            talus_angle: EROSION_TALUS_ANGLE_DEGREES,
+            thermal_rate: 0.25,
+            edge_margin: 16,
+        }
+    }
+}
+
+impl ErosionConfig {
+    /// Set the number of droplets simulated per chunk
+    pub fn with_droplets(mut self, droplets: u32) -> Self {
+        self.droplets = droplets;
+        self
+    }
+
+    /// Set the number of thermal erosion iterations
+    pub fn with_thermal_iterations(mut self, thermal_iterations: u32) -> Self {
+        self.thermal_iterations = thermal_iterations;
+        self
+    }
+
+    /// Set the steepest stable slope for thermal erosion (in degrees)
+    pub fn with_talus_angle(mut self, talus_angle: f32) -> Self {
+        self.talus_angle = talus_angle.clamp(0.0, 89.0);
+        self
+    }
+
+    /// Set the width of the border band where erosion fades out
+    pub fn with_edge_margin(mut self, edge_margin: usize) -> Self {
+        self.edge_margin = edge_margin;
+        self
+    }
+}
+
+/// Run hydraulic then thermal erosion on a heightmap
+///
+/// The result only depends on the heightmap, the seed and the config.
+pub fn erode(heights: &mut [f32], resolution: usize, seed: u64, config: &ErosionConfig) {
+    hydraulic_erosion(heights, resolution, seed, config);
+    thermal_erosion(heights, resolution, config);
+}
+
+/// Simulate rain droplets flowing over the heightmap
+pub fn hydraulic_erosion(heights: &mut [f32], resolution: usize, seed: u64, config: &ErosionConfig) {
+    if resolution < 2 || heights.len() < resolution * resolution {
+        return;
+    }
+
+    let mut rng = SplitMix64::new(seed);
+    let limit = (resolution - 1) as f32;
+
+    for _ in 0..config.droplets {
+        let mut position = (rng.next_f32() * limit, rng.next_f32() * limit);
+        let mut direction = (0.0f32, 0.0f32);
+        let mut speed = 1.0f32;
+        let mut water = 1.0f32;
+        let mut sediment = 0.0f32;
+
+        for _ in 0..config.droplet_lifetime {
+            let (height, gradient) = height_and_gradient(heights, resolution, position);
+
+            direction = (
+                direction.0 * config.inertia - gradient.0 * (1.0 - config.inertia),
+                direction.1 * config.inertia - gradient.1 * (1.0 - config.inertia),
+            );
+            let length = (direction.0 * direction.0 + direction.1 * direction.1).sqrt();
+            if length <= f32::EPSILON {
+                break;
+            }
+            direction = (direction.0 / length, direction.1 / length);
+
+            let previous = position;
+            position = (position.0 + direction.0, position.1 + direction.1);
+            if position.0 < 0.0 || position.1 < 0.0 || position.0 >= limit || position.1 >= limit {
+                break;
+            }
+
+            let delta = height_and_gradient(heights, resolution, position).0 - height;
+            let capacity = (-delta * speed * water * config.sediment_capacity)
+                .max(config.min_sediment_capacity);
+
+            if delta > 0.0 || sediment > capacity {
+                // Fill the pit when going uphill, otherwise drop what can't be carried.
+                let amount = if delta > 0.0 {
+                    delta.min(sediment)
+                } else {
+                    (sediment - capacity) * config.deposit_speed
+                };
+                sediment -= amount;
+                splat(heights, resolution, previous, amount, config.edge_margin);
+            } else {
+                let amount = ((capacity - sediment) * config.erode_speed).min(-delta);
+                sediment += amount;
+                splat(heights, resolution, previous, -amount, config.edge_margin);
+            }
+
+            speed = (speed * speed - delta * config.gravity).max(0.0).sqrt();
+            water *= 1.0 - config.evaporate_speed;
+        }
+    }
+}
+
+/// Let material slide down slopes steeper than the talus angle
+pub fn thermal_erosion(heights: &mut [f32], resolution: usize, config: &ErosionConfig) {
+    if resolution < 2 || heights.len() < resolution * resolution {
+        return;
+    }
+
+    let talus = determinism::tan(config.talus_angle.to_radians());
+    let mut changes = vec![0.0f32; resolution * resolution];
+
+    for _ in 0..config.thermal_iterations {
+        changes.iter_mut().for_each(|change| *change = 0.0);
+
+        for x in 0..resolution {
+            for y in 0..resolution {
+                let index = x * resolution + y;
+                // Only compare with the neighbors to the right and below, so each pair is
+                // handled once and the result doesn't depend on iteration order.
+                for (nx, ny) in [(x + 1, y), (x, y + 1)] {
+                    if nx >= resolution || ny >= resolution {
+                        continue;
+                    }
+                    let neighbor = nx * resolution + ny;
+                    let difference = heights[index] - heights[neighbor];
+                    if difference.abs() <= talus {
+                        continue;
+                    }
+
+                    let moved = (difference.abs() - talus) * config.thermal_rate;
+                    let (high, low) = if difference > 0.0 {
+                        (index, neighbor)
+                    } else {
+                        (neighbor, index)
+                    };
+                    let weight = edge_weight(resolution, high, config.edge_margin)
+                        .min(edge_weight(resolution, low, config.edge_margin));
+                    changes[high] -= moved * weight;
+                    changes[low] += moved * weight;
+                }
+            }
+        }
+
+        for (height, change) in heights.iter_mut().zip(&changes) {
+            *height += change;
+        }
+    }
+}
+
+/// Sample the heightmap bilinearly, returning the height and its gradient
+fn height_and_gradient(heights: &[f32], resolution: usize, position: (f32, f32)) -> (f32, (f32, f32)) {
+    let x = (position.0 as usize).min(resolution - 2);
+    let y = (position.1 as usize).min(resolution - 2);
+    let u = position.0 - x as f32;
+    let v = position.1 - y as f32;
+
+    let h00 = heights[x * resolution + y];
+    let h10 = heights[(x + 1) * resolution + y];
+    let h01 = heights[x * resolution + y + 1];
+    let h11 = heights[(x + 1) * resolution + y + 1];
+
+    let gradient = (
+        (h10 - h00) * (1.0 - v) + (h11 - h01) * v,
+        (h01 - h00) * (1.0 - u) + (h11 - h10) * u,
+    );
+    let height = h00 * (1.0 - u) * (1.0 - v) + h10 * u * (1.0 - v) + h01 * (1.0 - u) * v + h11 * u * v;
+
+    (height, gradient)
+}
+
+/// Add an amount to the four samples around a position, weighted bilinearly
+fn splat(heights: &mut [f32], resolution: usize, position: (f32, f32), amount: f32, margin: usize) {
+    let x = (position.0 as usize).min(resolution - 2);
+    let y = (position.1 as usize).min(resolution - 2);
+    let u = position.0 - x as f32;
+    let v = position.1 - y as f32;
+
+    for (index, weight) in [
+        (x * resolution + y, (1.0 - u) * (1.0 - v)),
+        ((x + 1) * resolution + y, u * (1.0 - v)),
+        (x * resolution + y + 1, (1.0 - u) * v),
+        ((x + 1) * resolution + y + 1, u * v),
+    ] {
+        heights[index] += amount * weight * edge_weight(resolution, index, margin);
+    }
+}
+
+/// Fade factor that is 0 on the heightmap border and 1 past the margin
+fn edge_weight(resolution: usize, index: usize, margin: usize) -> f32 {
+    if margin == 0 {
+        return 1.0;
+    }
+    let x = index / resolution;
+    let y = index % resolution;
+    let distance = x.min(y).min(resolution - 1 - x).min(resolution - 1 - y);
+    (distance as f32 / margin as f32).min(1.0)
+}
+
+/// Small deterministic random number generator for procedural placement
+pub(crate) struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in [0, 1)
+    pub(crate) fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESOLUTION: usize = 64;
+
+    /// Bumpy slope descending along x
+    fn create_test_heightmap() -> Vec<f32> {
+        let mut heights = vec![0.0; RESOLUTION * RESOLUTION];
+        for x in 0..RESOLUTION {
+            for y in 0..RESOLUTION {
+                let bumps = ((x as f32 * 0.7).sin() + (y as f32 * 0.9).cos()) * 2.0;
+                heights[x * RESOLUTION + y] = 200.0 - x as f32 * 2.0 + bumps;
+            }
+        }
+        heights
+    }
+
+    fn max_step(heights: &[f32]) -> f32 {
+        let mut steepest = 0.0f32;
+        for x in 0..RESOLUTION - 1 {
+            for y in 0..RESOLUTION - 1 {
+                let h = heights[x * RESOLUTION + y];
+                steepest = steepest
+                    .max((h - heights[(x + 1) * RESOLUTION + y]).abs())
+                    .max((h - heights[x * RESOLUTION + y + 1]).abs());
+            }
+        }
+        steepest
+    }
+
+    #[test]
+    fn test_hydraulic_erosion_is_deterministic() {
+        let config = ErosionConfig::default().with_droplets(2000);
+        let mut first = create_test_heightmap();
+        let mut second = create_test_heightmap();
+        let mut other_seed = create_test_heightmap();
+
+        hydraulic_erosion(&mut first, RESOLUTION, 7, &config);
+        hydraulic_erosion(&mut second, RESOLUTION, 7, &config);
+        hydraulic_erosion(&mut other_seed, RESOLUTION, 8, &config);
+
+        assert_eq!(first, second);
+        assert_ne!(first, other_seed);
+    }
+
+    #[test]
+    fn test_hydraulic_erosion_carves_and_deposits() {
+        let original = create_test_heightmap();
+        let mut heights = original.clone();
+        hydraulic_erosion(&mut heights, RESOLUTION, 1, &ErosionConfig::default().with_droplets(2000));
+
+        let lowered = heights.iter().zip(&original).filter(|(h, o)| *h < *o).count();
+        let raised = heights.iter().zip(&original).filter(|(h, o)| *h > *o).count();
+        assert!(lowered > 0);
+        assert!(raised > 0);
+        assert!(heights.iter().all(|h| h.is_finite()));
+    }
+
+    #[test]
+    fn test_erosion_keeps_edges() {
+        let original = create_test_heightmap();
+        let mut heights = original.clone();
+        erode(&mut heights, RESOLUTION, 3, &ErosionConfig::default().with_droplets(2000));
+
+        for i in 0..RESOLUTION {
+            for index in [
+                i,
+                (RESOLUTION - 1) * RESOLUTION + i,
+                i * RESOLUTION,
+                i * RESOLUTION + RESOLUTION - 1,
+            ] {
+                assert_eq!(heights[index], original[index]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_thermal_erosion_flattens_steep_slopes() {
+        let mut heights = vec![0.0; RESOLUTION * RESOLUTION];
+        for x in RESOLUTION / 2..RESOLUTION {
+            for y in 0..RESOLUTION {
+                heights[x * RESOLUTION + y] = 20.0;
+            }
+        }
+        let volume: f32 = heights.iter().sum();
+
+        let config = ErosionConfig::default()
+            .with_thermal_iterations(200)
+            .with_talus_angle(45.0)
+            .with_edge_margin(0);
+        thermal_erosion(&mut heights, RESOLUTION, &config);
+
+        assert!(max_step(&heights) < 2.0);
+        let eroded: f32 = heights.iter().sum();
+        assert!((volume - eroded).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_thermal_erosion_leaves_gentle_slopes() {
+        let original = create_test_heightmap();
+        let mut heights = original.clone();
+        let config = ErosionConfig::default().with_talus_angle(80.0);
+
+        thermal_erosion(&mut heights, RESOLUTION, &config);
+        assert_eq!(heights, original);
+    }
+}
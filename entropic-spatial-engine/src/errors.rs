@@ -12,12 +12,27 @@ pub enum SpatialError {
     #[error("Chunk at {coord:?} is already loaded")]
     ChunkAlreadyLoaded { coord: ChunkCoord },
 
+    #[error("Fetching chunk at {coord:?} failed after {attempts} attempts: {message}")]
+    ChunkFetchFailed { coord: ChunkCoord, attempts: u32, message: String },
+
+    #[error("Chunk at {coord:?} is corrupted: checksum {actual:016x} does not match {expected:016x}")]
+    ChunkChecksumMismatch { coord: ChunkCoord, expected: u64, actual: u64 },
+
+    #[error("Chunk at {coord:?} is stale: made for terrain recipe {found:016x} instead of {expected:016x}")]
+    StaleChunk { coord: ChunkCoord, expected: u64, found: u64 },
+
+    #[error("Expected chunk {expected:?}, got {found:?}")]
+    UnexpectedChunk { expected: ChunkCoord, found: ChunkCoord },
+
     #[error("Load queue is full")]
     LoadQueueFull,
 
     #[error("Unload queue is full")]
     UnloadQueueFull,
 
+    #[error("Path request queue is full")]
+    PathQueueFull,
+
     #[error("Spatial query failed: {message}")]
     QueryFailed { message: String },
 
@@ -39,6 +54,9 @@ pub enum SpatialError {
     #[error("Invalid coordinates: {message}")]
     InvalidCoordinates { message: String },
 
+    #[error("Mesh has {vertices} vertices, more than the {max} its index format can address")]
+    TooManyVertices { vertices: usize, max: usize },
+
     #[error("Terrain generation error: {message}")]
     TerrainError { message: String },
 
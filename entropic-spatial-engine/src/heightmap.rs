@@ -0,0 +1,453 @@
+//! Heightmap import and export
+//!
+//! Heights are stored as 16-bit grayscale PNG or headerless little-endian 16-bit raw data,
+//! with an elevation range mapped onto 0-65535. Imported heightmaps replace the procedural
+//! terrain inside a region and fade into it over a margin along the region edges, so the
+//! border of the region matches the procedural surroundings exactly.
+
+use crate::constants::*;
+use crate::errors::SpatialError;
+use crate::{Chunk, ChunkCoord, World};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Grid of heights (in meters) indexed `[y * width + x]`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Heightmap {
+    width: usize,
+    height: usize,
+    heights: Vec<f32>,
+}
+
+impl Heightmap {
+    /// Create a heightmap from row-major heights
+    pub fn new(width: usize, height: usize, heights: Vec<f32>) -> Result<Self, SpatialError> {
+        if width == 0 || height == 0 || heights.len() != width * height {
+            return Err(SpatialError::TerrainError {
+                message: format!(
+                    "heightmap of {}x{} needs {} heights, got {}",
+                    width,
+                    height,
+                    width * height,
+                    heights.len()
+                ),
+            });
+        }
+
+        Ok(Self {
+            width,
+            height,
+            heights,
+        })
+    }
+
+    /// Copy the heightmap of a chunk
+    pub fn from_chunk(chunk: &Chunk) -> Self {
+        let heights = (0..HEIGHTMAP_RESOLUTION)
+            .flat_map(|y| (0..HEIGHTMAP_RESOLUTION).map(move |x| (x, y)))
+            .map(|(x, y)| chunk.get_elevation_at(x, y).unwrap_or(0.0))
+            .collect();
+
+        Self {
+            width: HEIGHTMAP_RESOLUTION,
+            height: HEIGHTMAP_RESOLUTION,
+            heights,
+        }
+    }
+
+    /// Stitch the heightmaps of the chunks from `min` to `max` (inclusive) into one
+    pub fn from_world(world: &World, min: ChunkCoord, max: ChunkCoord) -> Result<Self, SpatialError> {
+        if max.x < min.x || max.y < min.y {
+            return Err(SpatialError::InvalidCoordinates {
+                message: format!("empty chunk range {:?} to {:?}", min, max),
+            });
+        }
+
+        let width = (max.x - min.x + 1) as usize * HEIGHTMAP_RESOLUTION;
+        let height = (max.y - min.y + 1) as usize * HEIGHTMAP_RESOLUTION;
+        let mut heights = vec![0.0; width * height];
+
+        for cy in min.y..=max.y {
+            for cx in min.x..=max.x {
+                let coord = ChunkCoord::new(cx, cy);
+                let chunk = world
+                    .chunks
+                    .get(&coord)
+                    .ok_or(SpatialError::ChunkNotFound { coord })?;
+                let origin = (
+                    (cx - min.x) as usize * HEIGHTMAP_RESOLUTION,
+                    (cy - min.y) as usize * HEIGHTMAP_RESOLUTION,
+                );
+                for sy in 0..HEIGHTMAP_RESOLUTION {
+                    for sx in 0..HEIGHTMAP_RESOLUTION {
+                        heights[(origin.1 + sy) * width + origin.0 + sx] =
+                            chunk.get_elevation_at(sx, sy).unwrap_or(0.0);
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            width,
+            height,
+            heights,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get the heights indexed `[y * width + x]`
+    pub fn heights(&self) -> &[f32] {
+        &self.heights
+    }
+
+    /// Get the height of a pixel
+    pub fn get(&self, x: usize, y: usize) -> Option<f32> {
+        if x < self.width && y < self.height {
+            Some(self.heights[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Bilinearly sample the height at fractional pixel coordinates, clamped to the edges
+    pub fn sample(&self, x: f32, y: f32) -> f32 {
+        let x = x.clamp(0.0, (self.width - 1) as f32);
+        let y = y.clamp(0.0, (self.height - 1) as f32);
+        let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+        let (x1, y1) = ((x0 + 1).min(self.width - 1), (y0 + 1).min(self.height - 1));
+        let (fx, fy) = (x - x0 as f32, y - y0 as f32);
+
+        let at = |x: usize, y: usize| self.heights[y * self.width + x];
+        let top = at(x0, y0) * (1.0 - fx) + at(x1, y0) * fx;
+        let bottom = at(x0, y1) * (1.0 - fx) + at(x1, y1) * fx;
+        top * (1.0 - fy) + bottom * fy
+    }
+
+    /// Encode as a 16-bit grayscale PNG, mapping `range` onto 0-65535
+    pub fn to_png(&self, range: (f32, f32)) -> Result<Vec<u8>, SpatialError> {
+        let mut data = Vec::new();
+        let mut encoder = png::Encoder::new(&mut data, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Sixteen);
+
+        let samples: Vec<u8> = self
+            .heights
+            .iter()
+            .flat_map(|&h| quantize(h, range).to_be_bytes())
+            .collect();
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&samples))
+            .map_err(|e| SpatialError::SerializationError {
+                message: e.to_string(),
+            })?;
+
+        Ok(data)
+    }
+
+    /// Decode an 8- or 16-bit grayscale PNG, mapping the full sample range onto `range`
+    pub fn from_png(data: &[u8], range: (f32, f32)) -> Result<Self, SpatialError> {
+        let error = |message: String| SpatialError::DeserializationError { message };
+
+        let mut decoder = png::Decoder::new(data);
+        decoder.set_transformations(png::Transformations::IDENTITY);
+        let mut reader = decoder.read_info().map_err(|e| error(e.to_string()))?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer).map_err(|e| error(e.to_string()))?;
+        let samples = &buffer[..info.buffer_size()];
+
+        let heights: Vec<f32> = match (info.color_type, info.bit_depth) {
+            (png::ColorType::Grayscale, png::BitDepth::Sixteen) => samples
+                .chunks_exact(2)
+                .map(|pair| dequantize(u16::from_be_bytes([pair[0], pair[1]]), range))
+                .collect(),
+            (png::ColorType::Grayscale, png::BitDepth::Eight) => samples
+                .iter()
+                .map(|&v| dequantize(u16::from(v) * 257, range))
+                .collect(),
+            (color, depth) => {
+                return Err(error(format!(
+                    "unsupported heightmap format {:?} at {:?} bits",
+                    color, depth
+                )))
+            }
+        };
+
+        Self::new(info.width as usize, info.height as usize, heights)
+    }
+
+    /// Encode as little-endian 16-bit raw data, mapping `range` onto 0-65535
+    pub fn to_raw(&self, range: (f32, f32)) -> Vec<u8> {
+        self.heights
+            .iter()
+            .flat_map(|&h| quantize(h, range).to_le_bytes())
+            .collect()
+    }
+
+    /// Decode little-endian 16-bit raw data, mapping 0-65535 onto `range`
+    pub fn from_raw(data: &[u8], width: usize, height: usize, range: (f32, f32)) -> Result<Self, SpatialError> {
+        if data.len() != width * height * 2 {
+            return Err(SpatialError::DeserializationError {
+                message: format!(
+                    "raw heightmap of {}x{} needs {} bytes, got {}",
+                    width,
+                    height,
+                    width * height * 2,
+                    data.len()
+                ),
+            });
+        }
+
+        let heights = data
+            .chunks_exact(2)
+            .map(|pair| dequantize(u16::from_le_bytes([pair[0], pair[1]]), range))
+            .collect();
+        Self::new(width, height, heights)
+    }
+
+    /// Save as a 16-bit grayscale PNG file
+    pub fn save_png<P: AsRef<Path>>(&self, path: P, range: (f32, f32)) -> Result<(), SpatialError> {
+        fs::write(path, self.to_png(range)?).map_err(|e| SpatialError::IoError {
+            message: e.to_string(),
+        })
+    }
+
+    /// Load a grayscale PNG file
+    pub fn load_png<P: AsRef<Path>>(path: P, range: (f32, f32)) -> Result<Self, SpatialError> {
+        let data = fs::read(path).map_err(|e| SpatialError::IoError {
+            message: e.to_string(),
+        })?;
+        Self::from_png(&data, range)
+    }
+
+    /// Save as a little-endian 16-bit raw file
+    pub fn save_raw<P: AsRef<Path>>(&self, path: P, range: (f32, f32)) -> Result<(), SpatialError> {
+        fs::write(path, self.to_raw(range)).map_err(|e| SpatialError::IoError {
+            message: e.to_string(),
+        })
+    }
+
+    /// Load a little-endian 16-bit raw file
+    pub fn load_raw<P: AsRef<Path>>(
+        path: P,
+        width: usize,
+        height: usize,
+        range: (f32, f32),
+    ) -> Result<Self, SpatialError> {
+        let data = fs::read(path).map_err(|e| SpatialError::IoError {
+            message: e.to_string(),
+        })?;
+        Self::from_raw(&data, width, height, range)
+    }
+}
+
+/// External heightmap used as the terrain of a region
+#[derive(Clone, Debug)]
+pub struct ImportedTerrain {
+    heightmap: Arc<Heightmap>,
+    origin: (f32, f32),
+    size: (f32, f32),
+    blend_width: f32,
+}
+
+impl ImportedTerrain {
+    /// Place a heightmap with its first pixel at `origin`, one meter per pixel
+    pub fn new(heightmap: Heightmap, origin: (f32, f32)) -> Self {
+        let size = ((heightmap.width - 1) as f32, (heightmap.height - 1) as f32);
+        Self {
+            heightmap: Arc::new(heightmap),
+            origin,
+            size,
+            blend_width: HEIGHTMAP_BLEND_WIDTH,
+        }
+    }
+
+    /// Stretch the heightmap over a region of the given size (in meters)
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.size = (width.max(1.0), height.max(1.0));
+        self
+    }
+
+    /// Set the width of the margin blending into the procedural terrain (in meters)
+    pub fn with_blend_width(mut self, blend_width: f32) -> Self {
+        self.blend_width = blend_width.max(0.0);
+        self
+    }
+
+    pub fn heightmap(&self) -> &Heightmap {
+        &self.heightmap
+    }
+
+    /// Get the width of the margin blending into the procedural terrain (in meters)
+    pub fn blend_width(&self) -> f32 {
+        self.blend_width
+    }
+
+    /// Get the region covered as (min, max) world positions
+    pub fn bounds(&self) -> ((f32, f32), (f32, f32)) {
+        (
+            self.origin,
+            (self.origin.0 + self.size.0, self.origin.1 + self.size.1),
+        )
+    }
+
+    /// Check whether the region reaches into a chunk
+    pub fn overlaps(&self, coord: ChunkCoord) -> bool {
+        let (min, max) = self.bounds();
+        let chunk_min = (coord.x as f32 * CHUNK_SIZE, coord.y as f32 * CHUNK_SIZE);
+        let chunk_max = (chunk_min.0 + CHUNK_SIZE, chunk_min.1 + CHUNK_SIZE);
+        min.0 < chunk_max.0 && max.0 > chunk_min.0 && min.1 < chunk_max.1 && max.1 > chunk_min.1
+    }
+
+    /// Get the imported height at a world position, if inside the region
+    pub fn height_at(&self, x: f32, y: f32) -> Option<f32> {
+        let (min, max) = self.bounds();
+        if x < min.0 || y < min.1 || x > max.0 || y > max.1 {
+            return None;
+        }
+        let px = (x - min.0) / self.size.0 * (self.heightmap.width - 1) as f32;
+        let py = (y - min.1) / self.size.1 * (self.heightmap.height - 1) as f32;
+        Some(self.heightmap.sample(px, py))
+    }
+
+    /// Get how much the imported height counts at a world position
+    ///
+    /// Zero outside the region and on its edges, one further than the blend width inside,
+    /// with a smoothstep in between.
+    pub fn weight(&self, x: f32, y: f32) -> f32 {
+        let (min, max) = self.bounds();
+        let edge = (x - min.0).min(max.0 - x).min(y - min.1).min(max.1 - y);
+        if edge <= 0.0 {
+            return 0.0;
+        }
+        if edge >= self.blend_width {
+            return 1.0;
+        }
+        let t = edge / self.blend_width;
+        t * t * (3.0 - 2.0 * t)
+    }
+
+    /// Blend the imported height into a procedural height at a world position
+    pub fn blend(&self, x: f32, y: f32, procedural: f32) -> f32 {
+        match self.height_at(x, y) {
+            Some(imported) => {
+                let weight = self.weight(x, y);
+                procedural + (imported - procedural) * weight
+            }
+            None => procedural,
+        }
+    }
+}
+
+fn quantize(height: f32, range: (f32, f32)) -> u16 {
+    let span = (range.1 - range.0).max(f32::EPSILON);
+    (((height - range.0) / span).clamp(0.0, 1.0) * u16::MAX as f32).round() as u16
+}
+
+fn dequantize(value: u16, range: (f32, f32)) -> f32 {
+    range.0 + value as f32 / u16::MAX as f32 * (range.1 - range.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TerrainGenerator;
+
+    const RANGE: (f32, f32) = (0.0, 255.0);
+
+    fn ramp(width: usize, height: usize) -> Heightmap {
+        let heights = (0..width * height)
+            .map(|i| ((i % width) + (i / width)) as f32 * 255.0 / (width + height) as f32)
+            .collect();
+        Heightmap::new(width, height, heights).unwrap()
+    }
+
+    fn assert_close(a: &Heightmap, b: &Heightmap, tolerance: f32) {
+        assert_eq!((a.width(), a.height()), (b.width(), b.height()));
+        for (x, y) in a.heights().iter().zip(b.heights()) {
+            assert!((x - y).abs() <= tolerance, "{} vs {}", x, y);
+        }
+    }
+
+    #[test]
+    fn test_png_roundtrip() {
+        let heightmap = ramp(37, 21);
+        let png = heightmap.to_png(RANGE).unwrap();
+        let decoded = Heightmap::from_png(&png, RANGE).unwrap();
+
+        assert_close(&heightmap, &decoded, 255.0 / 65535.0);
+    }
+
+    #[test]
+    fn test_raw_roundtrip() {
+        let heightmap = ramp(16, 8);
+        let raw = heightmap.to_raw(RANGE);
+        assert_eq!(raw.len(), 16 * 8 * 2);
+
+        let decoded = Heightmap::from_raw(&raw, 16, 8, RANGE).unwrap();
+        assert_close(&heightmap, &decoded, 255.0 / 65535.0);
+        assert!(Heightmap::from_raw(&raw, 16, 9, RANGE).is_err());
+    }
+
+    #[test]
+    fn test_export_world_region() {
+        let mut world = World::new("Test".to_string(), "game1".to_string(), 2, 2);
+        world.initialize_chunks();
+        for chunk in world.chunks.values_mut() {
+            let height = (chunk.coord.x * 10 + chunk.coord.y) as f32;
+            chunk.elevation.iter_mut().for_each(|h| *h = height);
+        }
+
+        let heightmap = Heightmap::from_world(&world, ChunkCoord::new(0, 0), ChunkCoord::new(1, 1)).unwrap();
+        let res = HEIGHTMAP_RESOLUTION;
+        assert_eq!(heightmap.width(), 2 * res);
+        assert_eq!(heightmap.get(0, 0), Some(0.0));
+        assert_eq!(heightmap.get(res, 0), Some(10.0));
+        assert_eq!(heightmap.get(0, res), Some(1.0));
+        assert_eq!(heightmap.get(2 * res - 1, 2 * res - 1), Some(11.0));
+        assert!(Heightmap::from_world(&world, ChunkCoord::new(0, 0), ChunkCoord::new(2, 0)).is_err());
+    }
+
+    #[test]
+    fn test_blend_weights() {
+        let heightmap = Heightmap::new(101, 101, vec![200.0; 101 * 101]).unwrap();
+        let terrain = ImportedTerrain::new(heightmap, (100.0, 100.0)).with_blend_width(20.0);
+
+        assert_eq!(terrain.blend(50.0, 150.0, 80.0), 80.0);
+        assert_eq!(terrain.blend(100.0, 150.0, 80.0), 80.0);
+        assert_eq!(terrain.blend(150.0, 150.0, 80.0), 200.0);
+        let mid = terrain.blend(110.0, 150.0, 80.0);
+        assert!(mid > 80.0 && mid < 200.0);
+        assert!(terrain.overlaps(ChunkCoord::new(0, 0)));
+        assert!(!terrain.overlaps(ChunkCoord::new(1, 0)));
+    }
+
+    #[test]
+    fn test_generator_uses_imported_terrain() {
+        let heightmap = Heightmap::new(65, 65, vec![240.0; 65 * 65]).unwrap();
+        let terrain = ImportedTerrain::new(heightmap, (96.0, 96.0)).with_blend_width(16.0);
+        let procedural = TerrainGenerator::with_seed(4).generate_chunk(ChunkCoord::new(0, 0)).unwrap();
+        let imported = TerrainGenerator::with_seed(4)
+            .with_import(terrain)
+            .generate_chunk(ChunkCoord::new(0, 0))
+            .unwrap();
+
+        let at = |chunk: &Chunk, x: usize, y: usize| chunk.elevation[x * HEIGHTMAP_RESOLUTION + y];
+        // Imported in the middle of the region, procedural outside and on its edge
+        assert_eq!(at(&imported, 128, 128), 240.0);
+        assert_eq!(at(&imported, 40, 128), at(&procedural, 40, 128));
+        assert_eq!(at(&imported, 96, 128), at(&procedural, 96, 128));
+        // No jumps across the blend margin
+        for x in 90..120 {
+            assert!((at(&imported, x + 1, 128) - at(&imported, x, 128)).abs() < 30.0);
+        }
+    }
+}
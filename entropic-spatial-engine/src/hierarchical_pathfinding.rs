@@ -0,0 +1,512 @@
+use crate::collision::CollisionDetector;
+use crate::constants::*;
+use crate::{ChunkCoord, World};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Grid cell used by the hierarchical pathfinder, in units of `PATHFINDING_GRID_SIZE`
+type Cell = (i32, i32);
+
+/// Number of pathfinding cells along one side of a chunk
+const CELLS_PER_CHUNK: i32 = (CHUNK_SIZE / PATHFINDING_GRID_SIZE) as i32;
+
+const STRAIGHT_COST: u32 = 1000;
+const DIAGONAL_COST: u32 = 1414;
+
+#[derive(Clone, Eq, PartialEq)]
+struct SearchState {
+    cost: u32,
+    cell: Cell,
+}
+
+impl Ord for SearchState {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.cmp(&self.cost).then_with(|| other.cell.cmp(&self.cell))
+    }
+}
+
+impl PartialOrd for SearchState {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Walkable cell on a chunk border, paired with the walkable cell across the border
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Entrance {
+    pub cell: (i32, i32),
+    pub partner: (i32, i32),
+}
+
+/// Abstract graph of one chunk: its entrances and the costs between them
+#[derive(Clone, Debug, Default)]
+struct Cluster {
+    entrances: Vec<Entrance>,
+    edges: HashMap<Cell, Vec<(Cell, u32)>>,
+}
+
+/// Hierarchical A* (HPA*) pathfinder across chunks
+///
+/// Each chunk is a cluster. Its border is scanned for entrances, stretches of walkable
+/// cells facing walkable cells in the neighboring chunk, and the costs between entrances
+/// of the same chunk are precomputed. Long paths are first searched over this abstract
+/// graph and then refined with local A* inside each chunk they cross.
+///
+/// Cluster graphs are built lazily and cached; call [`invalidate_chunk`] when a chunk's
+/// terrain changes.
+///
+/// [`invalidate_chunk`]: HierarchicalPathfinder::invalidate_chunk
+#[derive(Debug, Default)]
+pub struct HierarchicalPathfinder {
+    clusters: HashMap<ChunkCoord, Cluster>,
+}
+
+impl HierarchicalPathfinder {
+    /// Create a pathfinder with no cached clusters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the abstract graph of every chunk of the world ahead of time
+    pub fn precompute(&mut self, world: &World) {
+        let mut coords: Vec<ChunkCoord> = world.chunks.keys().copied().collect();
+        coords.sort_by_key(|coord| (coord.x, coord.y));
+        for coord in coords {
+            self.cluster(world, coord);
+        }
+    }
+
+    /// Drop the cached abstraction of a chunk whose terrain changed
+    ///
+    /// The entrances of the neighboring chunks depend on the chunk's border, so their
+    /// abstractions are dropped too.
+    pub fn invalidate_chunk(&mut self, coord: ChunkCoord) {
+        self.clusters.remove(&coord);
+        for neighbor in coord.neighbors() {
+            self.clusters.remove(&neighbor);
+        }
+    }
+
+    /// Drop every cached abstraction
+    pub fn invalidate_all(&mut self) {
+        self.clusters.clear();
+    }
+
+    /// Check if the abstraction of a chunk is cached
+    pub fn is_cached(&self, coord: &ChunkCoord) -> bool {
+        self.clusters.contains_key(coord)
+    }
+
+    /// Get the number of cached chunk abstractions
+    pub fn cached_chunks(&self) -> usize {
+        self.clusters.len()
+    }
+
+    /// Get the entrances of a chunk, building its abstraction if needed
+    pub fn entrances(&mut self, world: &World, coord: ChunkCoord) -> &[Entrance] {
+        &self.cluster(world, coord).entrances
+    }
+
+    /// Find path from start to goal
+    ///
+    /// Returns a vector of (x, y) positions from start to goal, inclusive, like
+    /// [`Pathfinder::find_path`](crate::Pathfinder::find_path).
+    /// Returns None if either point is not walkable or no path exists.
+    pub fn find_path(
+        &mut self,
+        world: &World,
+        start: (f32, f32),
+        goal: (f32, f32),
+    ) -> Option<Vec<(f32, f32)>> {
+        let start_cell = to_cell(start);
+        let goal_cell = to_cell(goal);
+
+        if !is_walkable(world, start_cell) || !is_walkable(world, goal_cell) {
+            return None;
+        }
+
+        let start_chunk = chunk_of(start_cell);
+        let goal_chunk = chunk_of(goal_cell);
+
+        if start_chunk == goal_chunk {
+            if let Some(path) = local_path(world, start_chunk, start_cell, goal_cell) {
+                return Some(to_world(&path));
+            }
+        }
+
+        let abstract_path = self.abstract_path(world, start_cell, goal_cell)?;
+        let mut cells = vec![start_cell];
+        for pair in abstract_path.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            let chunk = chunk_of(from);
+            if chunk == chunk_of(to) {
+                let segment = local_path(world, chunk, from, to)?;
+                cells.extend(segment.into_iter().skip(1));
+            } else {
+                cells.push(to);
+            }
+        }
+
+        Some(to_world(&cells))
+    }
+
+    /// Search the abstract graph, with start and goal temporarily linked to the entrances
+    /// of their chunks
+    fn abstract_path(&mut self, world: &World, start: Cell, goal: Cell) -> Option<Vec<Cell>> {
+        let start_chunk = chunk_of(start);
+        let goal_chunk = chunk_of(goal);
+
+        let start_edges = entrance_costs(world, &self.cluster(world, start_chunk).entrances, start_chunk, start);
+        let goal_edges: HashMap<Cell, u32> =
+            entrance_costs(world, &self.cluster(world, goal_chunk).entrances, goal_chunk, goal)
+                .into_iter()
+                .collect();
+
+        let mut open_set = BinaryHeap::new();
+        let mut came_from: HashMap<Cell, Cell> = HashMap::new();
+        let mut g_score: HashMap<Cell, u32> = HashMap::new();
+        let mut closed: HashSet<Cell> = HashSet::new();
+
+        g_score.insert(start, 0);
+        open_set.push(SearchState {
+            cost: heuristic(start, goal),
+            cell: start,
+        });
+
+        while let Some(SearchState { cell: current, .. }) = open_set.pop() {
+            if current == goal {
+                let mut path = vec![current];
+                let mut node = current;
+                while let Some(&prev) = came_from.get(&node) {
+                    path.push(prev);
+                    node = prev;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            if !closed.insert(current) {
+                continue;
+            }
+
+            let cluster = self.cluster(world, chunk_of(current));
+            let mut neighbors: Vec<(Cell, u32)> = if current == start {
+                start_edges.clone()
+            } else {
+                cluster.edges.get(&current).cloned().unwrap_or_default()
+            };
+            if let Some(entrance) = cluster.entrances.iter().find(|e| e.cell == current) {
+                neighbors.push((entrance.partner, STRAIGHT_COST));
+            }
+            if let Some(&cost) = goal_edges.get(&current) {
+                neighbors.push((goal, cost));
+            }
+
+            let current_g = g_score[&current];
+            for (neighbor, cost) in neighbors {
+                let tentative_g = current_g + cost;
+                if tentative_g < g_score.get(&neighbor).copied().unwrap_or(u32::MAX) {
+                    came_from.insert(neighbor, current);
+                    g_score.insert(neighbor, tentative_g);
+                    open_set.push(SearchState {
+                        cost: tentative_g + heuristic(neighbor, goal),
+                        cell: neighbor,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Get the abstraction of a chunk, building and caching it if needed
+    fn cluster(&mut self, world: &World, coord: ChunkCoord) -> &Cluster {
+        self.clusters
+            .entry(coord)
+            .or_insert_with(|| build_cluster(world, coord))
+    }
+}
+
+/// Scan the borders of a chunk for entrances and connect them with local searches
+fn build_cluster(world: &World, coord: ChunkCoord) -> Cluster {
+    let entrances = find_entrances(world, coord);
+    let mut edges: HashMap<Cell, Vec<(Cell, u32)>> = HashMap::new();
+
+    for (i, from) in entrances.iter().enumerate() {
+        for to in &entrances[i + 1..] {
+            if let Some(cost) = local_search(world, coord, from.cell, to.cell).map(|(_, cost)| cost) {
+                edges.entry(from.cell).or_default().push((to.cell, cost));
+                edges.entry(to.cell).or_default().push((from.cell, cost));
+            }
+        }
+    }
+
+    Cluster { entrances, edges }
+}
+
+/// Find one entrance per run of walkable cells along each side of a chunk
+fn find_entrances(world: &World, coord: ChunkCoord) -> Vec<Entrance> {
+    let (min_x, min_y) = (
+        coord.x as i32 * CELLS_PER_CHUNK,
+        coord.y as i32 * CELLS_PER_CHUNK,
+    );
+    let max_x = min_x + CELLS_PER_CHUNK - 1;
+    let max_y = min_y + CELLS_PER_CHUNK - 1;
+
+    // Each side as (cells along the side, offset to the cell across the border).
+    let sides: [(Vec<Cell>, Cell); 4] = [
+        ((min_y..=max_y).map(|y| (min_x, y)).collect(), (-1, 0)),
+        ((min_y..=max_y).map(|y| (max_x, y)).collect(), (1, 0)),
+        ((min_x..=max_x).map(|x| (x, min_y)).collect(), (0, -1)),
+        ((min_x..=max_x).map(|x| (x, max_y)).collect(), (0, 1)),
+    ];
+
+    let mut entrances = Vec::new();
+    for (cells, (dx, dy)) in sides {
+        let mut run: Vec<Cell> = Vec::new();
+        for cell in cells.into_iter().map(Some).chain(std::iter::once(None)) {
+            let open = cell.filter(|&(x, y)| is_walkable(world, (x, y)) && is_walkable(world, (x + dx, y + dy)));
+            match open {
+                Some(cell) => run.push(cell),
+                None if !run.is_empty() => {
+                    let (x, y) = run[run.len() / 2];
+                    entrances.push(Entrance {
+                        cell: (x, y),
+                        partner: (x + dx, y + dy),
+                    });
+                    run.clear();
+                }
+                None => {}
+            }
+        }
+    }
+
+    entrances
+}
+
+/// Costs from a cell to each entrance reachable within its chunk
+fn entrance_costs(world: &World, entrances: &[Entrance], coord: ChunkCoord, cell: Cell) -> Vec<(Cell, u32)> {
+    entrances
+        .iter()
+        .filter_map(|entrance| {
+            let (_, cost) = local_search(world, coord, cell, entrance.cell)?;
+            Some((entrance.cell, cost))
+        })
+        .collect()
+}
+
+fn local_path(world: &World, coord: ChunkCoord, start: Cell, goal: Cell) -> Option<Vec<Cell>> {
+    local_search(world, coord, start, goal).map(|(path, _)| path)
+}
+
+/// A* restricted to the cells of one chunk, returning the cell path and its cost
+fn local_search(world: &World, coord: ChunkCoord, start: Cell, goal: Cell) -> Option<(Vec<Cell>, u32)> {
+    let in_chunk = |cell: Cell| chunk_of(cell) == coord;
+
+    let mut open_set = BinaryHeap::new();
+    let mut came_from: HashMap<Cell, Cell> = HashMap::new();
+    let mut g_score: HashMap<Cell, u32> = HashMap::new();
+
+    g_score.insert(start, 0);
+    open_set.push(SearchState {
+        cost: heuristic(start, goal),
+        cell: start,
+    });
+
+    while let Some(SearchState { cost, cell: current }) = open_set.pop() {
+        let current_g = g_score[&current];
+        if current == goal {
+            let mut path = vec![current];
+            let mut node = current;
+            while let Some(&prev) = came_from.get(&node) {
+                path.push(prev);
+                node = prev;
+            }
+            path.reverse();
+            return Some((path, current_g));
+        }
+        if cost > current_g + heuristic(current, goal) {
+            continue;
+        }
+
+        for (dx, dy) in [(0, 1), (1, 0), (0, -1), (-1, 0), (1, 1), (-1, -1), (1, -1), (-1, 1)] {
+            let neighbor = (current.0 + dx, current.1 + dy);
+            if !in_chunk(neighbor) || !is_walkable(world, neighbor) {
+                continue;
+            }
+
+            let move_cost = if dx != 0 && dy != 0 { DIAGONAL_COST } else { STRAIGHT_COST };
+            let tentative_g = current_g + move_cost;
+            if tentative_g < g_score.get(&neighbor).copied().unwrap_or(u32::MAX) {
+                came_from.insert(neighbor, current);
+                g_score.insert(neighbor, tentative_g);
+                open_set.push(SearchState {
+                    cost: tentative_g + heuristic(neighbor, goal),
+                    cell: neighbor,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Octile distance heuristic
+fn heuristic(a: Cell, b: Cell) -> u32 {
+    let dx = (a.0 - b.0).unsigned_abs();
+    let dy = (a.1 - b.1).unsigned_abs();
+    STRAIGHT_COST * dx.max(dy) + (DIAGONAL_COST - STRAIGHT_COST) * dx.min(dy)
+}
+
+fn is_walkable(world: &World, cell: Cell) -> bool {
+    cell.0 >= 0
+        && cell.1 >= 0
+        && CollisionDetector::is_walkable(
+            world,
+            cell.0 as f32 * PATHFINDING_GRID_SIZE,
+            cell.1 as f32 * PATHFINDING_GRID_SIZE,
+        )
+}
+
+fn chunk_of(cell: Cell) -> ChunkCoord {
+    ChunkCoord::new(
+        cell.0.div_euclid(CELLS_PER_CHUNK).max(0) as u32,
+        cell.1.div_euclid(CELLS_PER_CHUNK).max(0) as u32,
+    )
+}
+
+fn to_cell(position: (f32, f32)) -> Cell {
+    (
+        (position.0 / PATHFINDING_GRID_SIZE).floor() as i32,
+        (position.1 / PATHFINDING_GRID_SIZE).floor() as i32,
+    )
+}
+
+/// Convert cells to their centers in world coordinates
+fn to_world(cells: &[Cell]) -> Vec<(f32, f32)> {
+    cells
+        .iter()
+        .map(|&(x, y)| {
+            (
+                x as f32 * PATHFINDING_GRID_SIZE + PATHFINDING_GRID_SIZE / 2.0,
+                y as f32 * PATHFINDING_GRID_SIZE + PATHFINDING_GRID_SIZE / 2.0,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Pathfinder;
+
+    fn create_test_world(width: u32, height: u32) -> World {
+        let mut world = World::new("Test".to_string(), "game1".to_string(), width, height);
+        world.initialize_chunks();
+
+        for chunk in world.chunks.values_mut() {
+            chunk.water_level = -100.0;
+            chunk.elevation.iter_mut().for_each(|h| *h = 100.0);
+        }
+
+        world
+    }
+
+    /// Flood a column of cells at local x = `x` across the whole chunk, except for the cell
+    /// row `gap`
+    fn flood_column(world: &mut World, chunk: ChunkCoord, x: usize, gap: Option<usize>) {
+        let chunk = world.chunks.get_mut(&chunk).unwrap();
+        for y in 0..HEIGHTMAP_RESOLUTION {
+            if gap == Some(y / PATHFINDING_GRID_SIZE as usize) {
+                continue;
+            }
+            for dx in 0..PATHFINDING_GRID_SIZE as usize {
+                chunk.elevation[(x + dx) * HEIGHTMAP_RESOLUTION + y] = -200.0;
+            }
+        }
+    }
+
+    #[test]
+    fn test_entrances_pair_across_borders() {
+        let world = create_test_world(2, 1);
+        let mut pathfinder = HierarchicalPathfinder::new();
+
+        let east = pathfinder.entrances(&world, ChunkCoord::new(0, 0)).to_vec();
+        let west = pathfinder.entrances(&world, ChunkCoord::new(1, 0)).to_vec();
+
+        assert_eq!(east.len(), 1);
+        assert_eq!(west.len(), 1);
+        assert_eq!(east[0].partner, west[0].cell);
+        assert_eq!(west[0].partner, east[0].cell);
+    }
+
+    #[test]
+    fn test_find_path_across_chunks() {
+        let world = create_test_world(4, 2);
+        let mut pathfinder = HierarchicalPathfinder::new();
+
+        let path = pathfinder.find_path(&world, (10.0, 10.0), (900.0, 300.0)).unwrap();
+        assert_eq!(path.first(), Some(&(8.0, 8.0)));
+        assert_eq!(path.last(), Some(&(904.0, 296.0)));
+
+        // Every step moves to an adjacent cell.
+        for step in path.windows(2) {
+            let dx = (step[1].0 - step[0].0).abs();
+            let dy = (step[1].1 - step[0].1).abs();
+            assert!(dx <= PATHFINDING_GRID_SIZE && dy <= PATHFINDING_GRID_SIZE);
+        }
+        assert!(pathfinder.cached_chunks() > 0);
+    }
+
+    #[test]
+    fn test_path_within_one_chunk() {
+        let world = create_test_world(1, 1);
+        let mut pathfinder = HierarchicalPathfinder::new();
+
+        let path = pathfinder.find_path(&world, (10.0, 10.0), (100.0, 10.0)).unwrap();
+        let grid = Pathfinder::find_path(&world, (10.0, 10.0), (100.0, 10.0), 1000).unwrap();
+        assert_eq!(path.len(), grid.len());
+    }
+
+    #[test]
+    fn test_unreachable_goal() {
+        let mut world = create_test_world(3, 1);
+        flood_column(&mut world, ChunkCoord::new(1, 0), 128, None);
+
+        let mut pathfinder = HierarchicalPathfinder::new();
+        pathfinder.precompute(&world);
+        assert_eq!(pathfinder.cached_chunks(), 3);
+        assert!(pathfinder.find_path(&world, (10.0, 10.0), (700.0, 10.0)).is_none());
+    }
+
+    #[test]
+    fn test_invalidate_chunk_picks_up_changes() {
+        let mut world = create_test_world(3, 1);
+        flood_column(&mut world, ChunkCoord::new(1, 0), 128, None);
+
+        let mut pathfinder = HierarchicalPathfinder::new();
+        assert!(pathfinder.find_path(&world, (10.0, 10.0), (700.0, 10.0)).is_none());
+
+        // Open a gap in the flooded column; the stale abstraction still blocks the way
+        // until the chunk is invalidated.
+        let coord = ChunkCoord::new(1, 0);
+        let chunk = world.chunks.get_mut(&coord).unwrap();
+        chunk.elevation.iter_mut().for_each(|h| *h = 100.0);
+        flood_column(&mut world, coord, 128, Some(10));
+
+        pathfinder.invalidate_chunk(coord);
+        assert!(!pathfinder.is_cached(&coord));
+        assert!(!pathfinder.is_cached(&ChunkCoord::new(0, 0)));
+
+        let path = pathfinder.find_path(&world, (10.0, 10.0), (700.0, 10.0)).unwrap();
+        assert!(path.iter().any(|&(_, y)| y >= 10.0 * PATHFINDING_GRID_SIZE));
+    }
+
+    #[test]
+    fn test_unwalkable_endpoints() {
+        let mut world = create_test_world(1, 1);
+        world.chunks.get_mut(&ChunkCoord::new(0, 0)).unwrap().water_level = 1000.0;
+
+        let mut pathfinder = HierarchicalPathfinder::new();
+        assert!(pathfinder.find_path(&world, (10.0, 10.0), (100.0, 10.0)).is_none());
+    }
+}
@@ -0,0 +1,739 @@
+//! Rivers and lakes derived from terrain
+//!
+//! Terrain is sampled into a coarse grid and depressions are filled with a priority flood
+//! starting from the sea and the edge of the world. The fill gives every cell a downstream
+//! neighbor, which is used to accumulate drainage area; cells draining enough area become
+//! rivers and filled depressions become lakes.
+
+use crate::constants::*;
+use crate::{Chunk, ChunkCoord, World};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Index of a river within a [`Hydrology`]
+pub type RiverId = usize;
+
+/// Index of a lake within a [`Hydrology`]
+pub type LakeId = usize;
+
+/// Height added per cell while filling, so filled flats still drain
+const FILL_EPSILON: f32 = 1e-3;
+
+/// Marker for cells without a downstream neighbor
+const NO_RECEIVER: usize = usize::MAX;
+
+/// Where a river ends
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RiverOutlet {
+    /// Flows into the sea
+    Sea,
+    /// Flows into a lake
+    Lake(LakeId),
+    /// Joins a larger river
+    Tributary(RiverId),
+    /// Leaves the analyzed area
+    Edge,
+}
+
+/// River channel from its source to its outlet
+#[derive(Clone, Debug, PartialEq)]
+pub struct River {
+    pub id: RiverId,
+    /// Cell centers from source to mouth, in world coordinates
+    pub points: Vec<(f32, f32)>,
+    /// Drainage area at the mouth (in square meters)
+    pub flow: f32,
+    pub outlet: RiverOutlet,
+}
+
+/// Lake filling a terrain depression
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lake {
+    pub id: LakeId,
+    /// Height of the lake surface
+    pub level: f32,
+    /// Surface area (in square meters)
+    pub area: f32,
+    /// Chunks the lake covers, sorted
+    pub chunks: Vec<ChunkCoord>,
+}
+
+/// Classification of a hydrology grid cell
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Water {
+    /// No chunk covers the cell
+    Void,
+    Land,
+    Sea,
+    River,
+    Lake(LakeId),
+}
+
+/// Builds rivers and lakes from the terrain of a world
+#[derive(Clone, Debug)]
+pub struct HydrologyBuilder {
+    cell_size: f32,
+    river_threshold: f32,
+    channel_depth: f32,
+    min_lake_depth: f32,
+}
+
+impl Default for HydrologyBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HydrologyBuilder {
+    /// Create a builder with the default settings
+    pub fn new() -> Self {
+        Self {
+            cell_size: HYDROLOGY_CELL_SIZE,
+            river_threshold: HYDROLOGY_RIVER_THRESHOLD,
+            channel_depth: HYDROLOGY_CHANNEL_DEPTH,
+            min_lake_depth: 0.5,
+        }
+    }
+
+    /// Set the grid cell size in meters
+    ///
+    /// The size is rounded so a whole number of cells fits in a chunk.
+    pub fn with_cell_size(mut self, cell_size: f32) -> Self {
+        let cells = (CHUNK_SIZE / cell_size.max(1.0)).round().max(1.0);
+        self.cell_size = CHUNK_SIZE / cells;
+        self
+    }
+
+    /// Set the drainage area in square meters above which a cell carries a river
+    pub fn with_river_threshold(mut self, river_threshold: f32) -> Self {
+        self.river_threshold = river_threshold.max(0.0);
+        self
+    }
+
+    /// Set the depth carved for the smallest rivers
+    pub fn with_channel_depth(mut self, channel_depth: f32) -> Self {
+        self.channel_depth = channel_depth.max(0.0);
+        self
+    }
+
+    /// Set the depth a depression needs before it holds a lake
+    pub fn with_min_lake_depth(mut self, min_lake_depth: f32) -> Self {
+        self.min_lake_depth = min_lake_depth.max(0.0);
+        self
+    }
+
+    /// Get the grid cell size in meters
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Compute rivers and lakes over all chunks of a world
+    ///
+    /// Cells at or below their chunk's water level are sea. Water leaving the area covered by
+    /// chunks drains off the edge.
+    pub fn build(&self, world: &World) -> Hydrology {
+        let cells_per_chunk = (CHUNK_SIZE / self.cell_size).round() as usize;
+        if world.chunks.is_empty() {
+            return Hydrology::empty(self);
+        }
+        let min_x = world.chunks.keys().map(|c| c.x).min().unwrap_or(0);
+        let min_y = world.chunks.keys().map(|c| c.y).min().unwrap_or(0);
+        let max_x = world.chunks.keys().map(|c| c.x).max().unwrap_or(0);
+        let max_y = world.chunks.keys().map(|c| c.y).max().unwrap_or(0);
+
+        let width = (max_x - min_x + 1) as usize * cells_per_chunk;
+        let height = (max_y - min_y + 1) as usize * cells_per_chunk;
+        let origin = (min_x as f32 * CHUNK_SIZE, min_y as f32 * CHUNK_SIZE);
+
+        let mut grid = Hydrology {
+            cell_size: self.cell_size,
+            river_threshold: self.river_threshold,
+            channel_depth: self.channel_depth,
+            origin,
+            width,
+            height,
+            heights: vec![0.0; width * height],
+            filled: vec![0.0; width * height],
+            receivers: vec![NO_RECEIVER; width * height],
+            accumulation: vec![0.0; width * height],
+            water: vec![Water::Void; width * height],
+            rivers: Vec::new(),
+            lakes: Vec::new(),
+            chunk_water_levels: HashMap::new(),
+        };
+
+        for chunk in world.chunks.values() {
+            let first = (
+                (chunk.coord.x - min_x) as usize * cells_per_chunk,
+                (chunk.coord.y - min_y) as usize * cells_per_chunk,
+            );
+            for cx in 0..cells_per_chunk {
+                for cy in 0..cells_per_chunk {
+                    let index = (first.1 + cy) * width + first.0 + cx;
+                    let center = grid.cell_center(index);
+                    let h = height_at(chunk, center.0, center.1);
+                    grid.heights[index] = h;
+                    grid.water[index] = if h <= chunk.water_level {
+                        Water::Sea
+                    } else {
+                        Water::Land
+                    };
+                }
+            }
+        }
+
+        let (levels, order) = grid.flood();
+        grid.accumulate(&order);
+        grid.mark_lakes(&levels, self.min_lake_depth);
+        grid.mark_rivers(self.river_threshold);
+        grid.trace_rivers();
+        grid
+    }
+}
+
+/// Rivers, lakes and drainage computed for a world
+#[derive(Clone, Debug)]
+pub struct Hydrology {
+    cell_size: f32,
+    river_threshold: f32,
+    channel_depth: f32,
+    origin: (f32, f32),
+    width: usize,
+    height: usize,
+    heights: Vec<f32>,
+    filled: Vec<f32>,
+    receivers: Vec<usize>,
+    accumulation: Vec<f32>,
+    water: Vec<Water>,
+    rivers: Vec<River>,
+    lakes: Vec<Lake>,
+    chunk_water_levels: HashMap<ChunkCoord, f32>,
+}
+
+/// Cell waiting in the priority flood, lowest fill height first
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct FloodCell {
+    level: f32,
+    index: usize,
+}
+
+impl Eq for FloodCell {}
+
+impl Ord for FloodCell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .level
+            .total_cmp(&self.level)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl PartialOrd for FloodCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Hydrology {
+    fn empty(builder: &HydrologyBuilder) -> Self {
+        Self {
+            cell_size: builder.cell_size,
+            river_threshold: builder.river_threshold,
+            channel_depth: builder.channel_depth,
+            origin: (0.0, 0.0),
+            width: 0,
+            height: 0,
+            heights: Vec::new(),
+            filled: Vec::new(),
+            receivers: Vec::new(),
+            accumulation: Vec::new(),
+            water: Vec::new(),
+            rivers: Vec::new(),
+            lakes: Vec::new(),
+            chunk_water_levels: HashMap::new(),
+        }
+    }
+
+    /// Get all rivers, main stems before their tributaries
+    pub fn rivers(&self) -> &[River] {
+        &self.rivers
+    }
+
+    /// Get all lakes
+    pub fn lakes(&self) -> &[Lake] {
+        &self.lakes
+    }
+
+    /// Get the grid cell size in meters
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Get the drainage area (in square meters) flowing through a point
+    pub fn flow_at(&self, x: f32, y: f32) -> f32 {
+        self.cell_index(x, y).map_or(0.0, |index| self.accumulation[index])
+    }
+
+    /// Check if a point lies on a river
+    pub fn is_river(&self, x: f32, y: f32) -> bool {
+        self.cell_index(x, y)
+            .is_some_and(|index| self.water[index] == Water::River)
+    }
+
+    /// Check if a point lies in the sea
+    pub fn is_sea(&self, x: f32, y: f32) -> bool {
+        self.cell_index(x, y)
+            .is_some_and(|index| self.water[index] == Water::Sea)
+    }
+
+    /// Get the lake covering a point
+    pub fn lake_at(&self, x: f32, y: f32) -> Option<&Lake> {
+        match self.water[self.cell_index(x, y)?] {
+            Water::Lake(id) => self.lakes.get(id),
+            _ => None,
+        }
+    }
+
+    /// Get the highest lake surface within a chunk
+    pub fn water_level(&self, coord: ChunkCoord) -> Option<f32> {
+        self.chunk_water_levels.get(&coord).copied()
+    }
+
+    /// Carve river channels into the terrain and raise water levels of chunks holding lakes
+    ///
+    /// Channels get deeper as more area drains through them, and the bed always descends
+    /// toward the mouth. A chunk holds a single water level, so the whole chunk takes the
+    /// level of its highest lake.
+    pub fn apply(&self, world: &mut World) {
+        for index in 0..self.water.len() {
+            if self.water[index] != Water::River {
+                continue;
+            }
+            let size = self.accumulation[index] / self.river_threshold.max(1.0);
+            let depth = self.channel_depth * (1.0 + size.ln().max(0.0));
+            let bed = self.filled[index] - depth;
+            let center = self.cell_center(index);
+            let half = self.cell_size / 2.0;
+
+            let coord = ChunkCoord::new(
+                (center.0 / CHUNK_SIZE).floor() as u32,
+                (center.1 / CHUNK_SIZE).floor() as u32,
+            );
+            let Some(chunk) = world.chunks.get_mut(&coord) else {
+                continue;
+            };
+            let chunk_origin = (coord.x as f32 * CHUNK_SIZE, coord.y as f32 * CHUNK_SIZE);
+            let scale = HEIGHTMAP_RESOLUTION as f32 / CHUNK_SIZE;
+            let first_x = ((center.0 - half - chunk_origin.0) * scale).max(0.0) as usize;
+            let first_y = ((center.1 - half - chunk_origin.1) * scale).max(0.0) as usize;
+            let last_x = (((center.0 + half - chunk_origin.0) * scale) as usize).min(HEIGHTMAP_RESOLUTION);
+            let last_y = (((center.1 + half - chunk_origin.1) * scale) as usize).min(HEIGHTMAP_RESOLUTION);
+
+            for sy in first_y..last_y {
+                for sx in first_x..last_x {
+                    let sample = &mut chunk.elevation[sy * HEIGHTMAP_RESOLUTION + sx];
+                    *sample = sample.min(bed);
+                }
+            }
+        }
+
+        for (coord, level) in &self.chunk_water_levels {
+            if let Some(chunk) = world.chunks.get_mut(coord) {
+                chunk.water_level = chunk.water_level.max(*level);
+            }
+        }
+    }
+
+    /// World position of a cell center
+    fn cell_center(&self, index: usize) -> (f32, f32) {
+        (
+            self.origin.0 + ((index % self.width) as f32 + 0.5) * self.cell_size,
+            self.origin.1 + ((index / self.width) as f32 + 0.5) * self.cell_size,
+        )
+    }
+
+    /// Cell containing a world position
+    fn cell_index(&self, x: f32, y: f32) -> Option<usize> {
+        let cx = ((x - self.origin.0) / self.cell_size).floor();
+        let cy = ((y - self.origin.1) / self.cell_size).floor();
+        if cx < 0.0 || cy < 0.0 || cx as usize >= self.width || cy as usize >= self.height {
+            return None;
+        }
+        Some(cy as usize * self.width + cx as usize)
+    }
+
+    /// Neighboring cells in all 8 directions
+    fn neighbors(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        let x = (index % self.width) as i64;
+        let y = (index / self.width) as i64;
+        (-1..=1)
+            .flat_map(move |dy| (-1..=1).map(move |dx| (x + dx, y + dy)))
+            .filter(move |&(nx, ny)| {
+                (nx, ny) != (x, y)
+                    && nx >= 0
+                    && ny >= 0
+                    && (nx as usize) < self.width
+                    && (ny as usize) < self.height
+            })
+            .map(move |(nx, ny)| ny as usize * self.width + nx as usize)
+    }
+
+    /// Fill depressions with a priority flood from the sea and the edges
+    ///
+    /// Sets the drainage surface and downstream receivers, and returns the water levels of
+    /// the plain fill together with the order cells were reached in, outlets first.
+    fn flood(&mut self) -> (Vec<f32>, Vec<usize>) {
+        let mut levels = self.heights.clone();
+        let mut visited = vec![false; self.water.len()];
+        let mut order = Vec::with_capacity(self.water.len());
+        let mut open = BinaryHeap::new();
+
+        let outlets: Vec<usize> = (0..self.water.len())
+            .filter(|&index| match self.water[index] {
+                Water::Void => false,
+                Water::Sea => true,
+                _ => self.is_border(index),
+            })
+            .collect();
+        for index in outlets {
+            visited[index] = true;
+            self.filled[index] = self.heights[index];
+            open.push(FloodCell {
+                level: self.heights[index],
+                index,
+            });
+        }
+
+        while let Some(FloodCell { index, .. }) = open.pop() {
+            order.push(index);
+            let neighbors: Vec<usize> = self.neighbors(index).collect();
+            for neighbor in neighbors {
+                if visited[neighbor] || self.water[neighbor] == Water::Void {
+                    continue;
+                }
+                visited[neighbor] = true;
+                self.filled[neighbor] = self.heights[neighbor].max(self.filled[index] + FILL_EPSILON);
+                levels[neighbor] = self.heights[neighbor].max(levels[index]);
+                self.receivers[neighbor] = index;
+                open.push(FloodCell {
+                    level: self.filled[neighbor],
+                    index: neighbor,
+                });
+            }
+        }
+
+        (levels, order)
+    }
+
+    /// Check if a cell touches the edge of the grid or an area without chunks
+    fn is_border(&self, index: usize) -> bool {
+        let x = index % self.width;
+        let y = index / self.width;
+        x == 0
+            || y == 0
+            || x + 1 == self.width
+            || y + 1 == self.height
+            || self.neighbors(index).any(|n| self.water[n] == Water::Void)
+    }
+
+    /// Accumulate drainage area downstream, sources first
+    fn accumulate(&mut self, order: &[usize]) {
+        let area = self.cell_size * self.cell_size;
+        for &index in order {
+            self.accumulation[index] = area;
+        }
+        for &index in order.iter().rev() {
+            let receiver = self.receivers[index];
+            if receiver != NO_RECEIVER {
+                self.accumulation[receiver] += self.accumulation[index];
+            }
+        }
+    }
+
+    /// Group filled depressions into lakes
+    fn mark_lakes(&mut self, levels: &[f32], min_depth: f32) {
+        let submerged = |index: usize| levels[index] - self.heights[index] > min_depth;
+        let mut assigned = vec![false; self.water.len()];
+
+        for start in 0..self.water.len() {
+            if assigned[start] || self.water[start] != Water::Land || !submerged(start) {
+                continue;
+            }
+
+            let id = self.lakes.len();
+            let mut stack = vec![start];
+            let mut members = Vec::new();
+            assigned[start] = true;
+            while let Some(index) = stack.pop() {
+                members.push(index);
+                for neighbor in self.neighbors(index) {
+                    if !assigned[neighbor] && self.water[neighbor] == Water::Land && submerged(neighbor) {
+                        assigned[neighbor] = true;
+                        stack.push(neighbor);
+                    }
+                }
+            }
+
+            let level = members.iter().map(|&i| levels[i]).fold(f32::MIN, f32::max);
+            let mut chunks: Vec<ChunkCoord> = Vec::new();
+            for &index in &members {
+                self.water[index] = Water::Lake(id);
+                let center = self.cell_center(index);
+                let coord = ChunkCoord::new(
+                    (center.0 / CHUNK_SIZE).floor() as u32,
+                    (center.1 / CHUNK_SIZE).floor() as u32,
+                );
+                if !chunks.contains(&coord) {
+                    chunks.push(coord);
+                }
+                let chunk_level = self.chunk_water_levels.entry(coord).or_insert(level);
+                *chunk_level = chunk_level.max(level);
+            }
+            chunks.sort_by_key(|coord| (coord.x, coord.y));
+
+            self.lakes.push(Lake {
+                id,
+                level,
+                area: members.len() as f32 * self.cell_size * self.cell_size,
+                chunks,
+            });
+        }
+    }
+
+    /// Mark land cells draining enough area as rivers
+    fn mark_rivers(&mut self, threshold: f32) {
+        for index in 0..self.water.len() {
+            if self.water[index] == Water::Land && self.accumulation[index] >= threshold {
+                self.water[index] = Water::River;
+            }
+        }
+    }
+
+    /// Trace river polylines upstream from their mouths
+    ///
+    /// At each confluence the branch draining the most area continues the river and the
+    /// others become tributaries.
+    fn trace_rivers(&mut self) {
+        let mut upstream: Vec<Vec<usize>> = vec![Vec::new(); self.water.len()];
+        for index in 0..self.water.len() {
+            let receiver = self.receivers[index];
+            if self.water[index] == Water::River && receiver != NO_RECEIVER {
+                upstream[receiver].push(index);
+            }
+        }
+
+        let mut mouths: Vec<(usize, RiverOutlet)> = Vec::new();
+        for index in 0..self.water.len() {
+            if self.water[index] != Water::River {
+                continue;
+            }
+            let receiver = self.receivers[index];
+            let outlet = if receiver == NO_RECEIVER {
+                RiverOutlet::Edge
+            } else {
+                match self.water[receiver] {
+                    Water::River => continue,
+                    Water::Sea => RiverOutlet::Sea,
+                    Water::Lake(id) => RiverOutlet::Lake(id),
+                    Water::Land | Water::Void => RiverOutlet::Edge,
+                }
+            };
+            mouths.push((index, outlet));
+        }
+        mouths.sort_by(|a, b| {
+            self.accumulation[b.0]
+                .total_cmp(&self.accumulation[a.0])
+                .then_with(|| a.0.cmp(&b.0))
+        });
+
+        let mut pending: Vec<(usize, RiverOutlet)> = mouths.into_iter().rev().collect();
+        while let Some((mouth, outlet)) = pending.pop() {
+            let id = self.rivers.len();
+            let mut cells = vec![mouth];
+            let mut branches = Vec::new();
+            let mut current = mouth;
+
+            loop {
+                let mut sources = upstream[current].clone();
+                sources.sort_by(|&a, &b| {
+                    self.accumulation[b]
+                        .total_cmp(&self.accumulation[a])
+                        .then_with(|| a.cmp(&b))
+                });
+                let Some((&main, rest)) = sources.split_first() else {
+                    break;
+                };
+                branches.extend(rest.iter().map(|&cell| (cell, RiverOutlet::Tributary(id))));
+                cells.push(main);
+                current = main;
+            }
+
+            let mut points: Vec<(f32, f32)> = cells.iter().rev().map(|&i| self.cell_center(i)).collect();
+            let receiver = self.receivers[mouth];
+            if receiver != NO_RECEIVER {
+                points.push(self.cell_center(receiver));
+            }
+
+            self.rivers.push(River {
+                id,
+                points,
+                flow: self.accumulation[mouth],
+                outlet,
+            });
+
+            // Larger tributaries get lower ids
+            branches.sort_by(|a, b| {
+                self.accumulation[a.0]
+                    .total_cmp(&self.accumulation[b.0])
+                    .then_with(|| b.0.cmp(&a.0))
+            });
+            pending.extend(branches);
+        }
+    }
+}
+
+/// Sample a chunk heightmap at a world position
+fn height_at(chunk: &Chunk, x: f32, y: f32) -> f32 {
+    let scale = HEIGHTMAP_RESOLUTION as f32 / CHUNK_SIZE;
+    let local_x = (x - chunk.coord.x as f32 * CHUNK_SIZE) * scale;
+    let local_y = (y - chunk.coord.y as f32 * CHUNK_SIZE) * scale;
+    let sx = (local_x.max(0.0) as usize).min(HEIGHTMAP_RESOLUTION - 1);
+    let sy = (local_y.max(0.0) as usize).min(HEIGHTMAP_RESOLUTION - 1);
+    chunk.get_elevation_at(sx, sy).unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_world(terrain: impl Fn(f32, f32) -> f32) -> World {
+        let mut world = World::new("Test".to_string(), "game1".to_string(), 2, 2);
+        world.initialize_chunks();
+
+        for chunk in world.chunks.values_mut() {
+            chunk.water_level = -100.0;
+            let origin = (chunk.coord.x as f32 * CHUNK_SIZE, chunk.coord.y as f32 * CHUNK_SIZE);
+            for sy in 0..HEIGHTMAP_RESOLUTION {
+                for sx in 0..HEIGHTMAP_RESOLUTION {
+                    chunk.elevation[sy * HEIGHTMAP_RESOLUTION + sx] =
+                        terrain(origin.0 + sx as f32, origin.1 + sy as f32);
+                }
+            }
+        }
+
+        world
+    }
+
+    /// Valley running along y = 256, descending toward x = 0
+    fn valley(x: f32, y: f32) -> f32 {
+        100.0 + x * 0.05 + (y - 256.0).abs() * 0.1
+    }
+
+    /// Valley with a bowl sunk into it around (384, 256)
+    fn valley_with_bowl(x: f32, y: f32) -> f32 {
+        let distance = ((x - 384.0).powi(2) + (y - 256.0).powi(2)).sqrt();
+        valley(x, y) - (20.0 - distance / 4.0).max(0.0)
+    }
+
+    #[test]
+    fn test_river_follows_valley() {
+        let world = create_test_world(valley);
+        let hydrology = HydrologyBuilder::new().build(&world);
+
+        let river = &hydrology.rivers()[0];
+        assert_eq!(river.outlet, RiverOutlet::Edge);
+        let mouth = *river.points.last().unwrap();
+        assert!(mouth.0 < 16.0);
+        assert!((mouth.1 - 256.0).abs() < 16.0);
+        assert!(river.points.windows(2).all(|pair| pair[1].0 <= pair[0].0));
+        assert!(hydrology.lakes().is_empty());
+
+        assert!(hydrology.is_river(100.0, 252.0));
+        assert!(!hydrology.is_river(100.0, 60.0));
+        assert!(hydrology.flow_at(100.0, 252.0) > hydrology.flow_at(400.0, 252.0));
+    }
+
+    #[test]
+    fn test_tributaries_join_larger_rivers() {
+        let world = create_test_world(valley);
+        let hydrology = HydrologyBuilder::new().with_river_threshold(500.0).build(&world);
+
+        let tributaries: Vec<&River> = hydrology
+            .rivers()
+            .iter()
+            .filter(|river| matches!(river.outlet, RiverOutlet::Tributary(_)))
+            .collect();
+        assert!(!tributaries.is_empty());
+
+        for river in tributaries {
+            let RiverOutlet::Tributary(parent) = river.outlet else {
+                unreachable!();
+            };
+            assert!(parent < river.id);
+            assert!(hydrology.rivers()[parent].flow >= river.flow);
+            let confluence = *river.points.last().unwrap();
+            assert!(hydrology.rivers()[parent].points.contains(&confluence));
+        }
+    }
+
+    #[test]
+    fn test_river_reaches_sea() {
+        let mut world = create_test_world(valley);
+        for chunk in world.chunks.values_mut().filter(|chunk| chunk.coord.x == 0) {
+            chunk.water_level = 110.0;
+        }
+        let hydrology = HydrologyBuilder::new().build(&world);
+
+        assert!(hydrology.is_sea(50.0, 256.0));
+        assert_eq!(hydrology.rivers()[0].outlet, RiverOutlet::Sea);
+    }
+
+    #[test]
+    fn test_depression_holds_lake() {
+        let world = create_test_world(valley_with_bowl);
+        let hydrology = HydrologyBuilder::new().build(&world);
+
+        let lake = hydrology.lake_at(384.0, 256.0).expect("bowl should hold a lake");
+        assert!(lake.level > valley_with_bowl(384.0, 256.0));
+        assert!(lake.area > 0.0);
+        assert!(lake.chunks.contains(&ChunkCoord::new(1, 0)));
+        assert_eq!(hydrology.water_level(ChunkCoord::new(1, 0)), Some(lake.level));
+        assert_eq!(hydrology.water_level(ChunkCoord::new(0, 0)), None);
+
+        // Rivers run into the lake and out of it again toward the edge
+        let rivers = hydrology.rivers();
+        assert!(rivers.iter().any(|river| river.outlet == RiverOutlet::Lake(lake.id)));
+        assert!(rivers.iter().any(|river| river.outlet == RiverOutlet::Edge
+            && river.points[0].0 > 256.0
+            && river.points[0].0 < 384.0));
+    }
+
+    #[test]
+    fn test_apply_carves_channels_and_sets_water_levels() {
+        let mut world = create_test_world(valley_with_bowl);
+        let hydrology = HydrologyBuilder::new().with_channel_depth(2.0).build(&world);
+        hydrology.apply(&mut world);
+
+        let chunk = &world.chunks[&ChunkCoord::new(0, 0)];
+        let carved = chunk.get_elevation_at(100, 252).unwrap();
+        assert!(carved <= valley(100.0, 252.0) - 2.0);
+        let untouched = chunk.get_elevation_at(100, 60).unwrap();
+        assert_eq!(untouched, valley(100.0, 60.0));
+
+        let lake_level = hydrology.water_level(ChunkCoord::new(1, 0)).unwrap();
+        assert_eq!(world.chunks[&ChunkCoord::new(1, 0)].water_level, lake_level);
+        assert_eq!(world.chunks[&ChunkCoord::new(0, 0)].water_level, -100.0);
+    }
+
+    #[test]
+    fn test_empty_world() {
+        let mut world = World::new("Test".to_string(), "game1".to_string(), 0, 0);
+        world.chunks.clear();
+        let hydrology = HydrologyBuilder::new().build(&world);
+
+        assert!(hydrology.rivers().is_empty());
+        assert!(hydrology.lakes().is_empty());
+        assert!(!hydrology.is_river(0.0, 0.0));
+        assert!(hydrology.lake_at(0.0, 0.0).is_none());
+    }
+}
@@ -0,0 +1,510 @@
+//! Influence maps
+//!
+//! Grids over the world that AI systems read when choosing where to go: how strongly each
+//! faction is present, how threatening a place is to a faction, and how desirable it is for
+//! its resources. Sources are stamped into a grid with a linear falloff; decay passes let old
+//! influence fade and blur passes spread it into the surrounding cells.
+
+use crate::constants::*;
+use crate::World;
+use entropic_world_core::ecosystem::FloraProfile;
+use entropic_world_core::population::FactionId;
+use std::collections::HashMap;
+
+/// Grid of influence values over the world
+#[derive(Clone, Debug, PartialEq)]
+pub struct InfluenceMap {
+    columns: usize,
+    rows: usize,
+    cells_per_chunk: usize,
+    values: Vec<f32>,
+}
+
+impl InfluenceMap {
+    /// Create an empty map covering a world
+    pub fn new(world: &World) -> Self {
+        Self::with_size(world.width_chunks, world.height_chunks, INFLUENCE_CELLS_PER_CHUNK)
+    }
+
+    /// Set how many cells the map has along each side of a chunk, clearing it
+    pub fn with_cells_per_chunk(self, cells_per_chunk: usize) -> Self {
+        let cells_per_chunk = cells_per_chunk.max(1);
+        let (width, height) = (self.columns / self.cells_per_chunk, self.rows / self.cells_per_chunk);
+        Self::with_size(width as u32, height as u32, cells_per_chunk)
+    }
+
+    fn with_size(width_chunks: u32, height_chunks: u32, cells_per_chunk: usize) -> Self {
+        let columns = width_chunks as usize * cells_per_chunk;
+        let rows = height_chunks as usize * cells_per_chunk;
+        Self {
+            columns,
+            rows,
+            cells_per_chunk,
+            values: vec![0.0; columns * rows],
+        }
+    }
+
+    /// Get the size of a cell (in meters)
+    pub fn cell_size(&self) -> f32 {
+        CHUNK_SIZE / self.cells_per_chunk as f32
+    }
+
+    /// Get the number of columns and rows of cells
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.columns, self.rows)
+    }
+
+    /// Get the value of a cell, `None` outside the map
+    pub fn cell(&self, cx: usize, cy: usize) -> Option<f32> {
+        (cx < self.columns && cy < self.rows).then(|| self.values[cy * self.columns + cx])
+    }
+
+    /// Get the value at a world position, zero outside the map
+    pub fn value_at(&self, x: f32, y: f32) -> f32 {
+        let cell_size = self.cell_size();
+        let (cx, cy) = ((x / cell_size).floor(), (y / cell_size).floor());
+        if cx < 0.0 || cy < 0.0 {
+            return 0.0;
+        }
+        self.cell(cx as usize, cy as usize).unwrap_or(0.0)
+    }
+
+    /// Get the highest value anywhere on the map, zero for an empty map
+    pub fn max_value(&self) -> f32 {
+        self.values.iter().copied().reduce(f32::max).unwrap_or(0.0)
+    }
+
+    /// Reset every cell to zero
+    pub fn clear(&mut self) {
+        self.values.iter_mut().for_each(|value| *value = 0.0);
+    }
+
+    /// Add influence around a position, falling off linearly to zero at the radius
+    ///
+    /// A radius under a cell only touches the cell under the position.
+    pub fn stamp(&mut self, x: f32, y: f32, amount: f32, radius: f32) {
+        let cell_size = self.cell_size();
+        if radius < cell_size {
+            let (cx, cy) = ((x / cell_size).floor(), (y / cell_size).floor());
+            if cx >= 0.0 && cy >= 0.0 && (cx as usize) < self.columns && (cy as usize) < self.rows {
+                self.values[cy as usize * self.columns + cx as usize] += amount;
+            }
+            return;
+        }
+
+        let cell_range = |center: f32, cells: usize| {
+            let first = ((center - radius) / cell_size).floor().max(0.0) as usize;
+            let last = ((center + radius) / cell_size).floor().max(0.0) as usize;
+            first..=last.min(cells.saturating_sub(1))
+        };
+        if self.columns == 0 || self.rows == 0 || x + radius < 0.0 || y + radius < 0.0 {
+            return;
+        }
+
+        for cy in cell_range(y, self.rows) {
+            for cx in cell_range(x, self.columns) {
+                let dx = (cx as f32 + 0.5) * cell_size - x;
+                let dy = (cy as f32 + 0.5) * cell_size - y;
+                let falloff = 1.0 - (dx * dx + dy * dy).sqrt() / radius;
+                if falloff > 0.0 {
+                    self.values[cy * self.columns + cx] += amount * falloff;
+                }
+            }
+        }
+    }
+
+    /// Add another map covering the same grid, scaled by a weight
+    ///
+    /// Maps with different dimensions are left alone.
+    pub fn add_scaled(&mut self, other: &InfluenceMap, weight: f32) {
+        if self.dimensions() != other.dimensions() {
+            return;
+        }
+        for (value, added) in self.values.iter_mut().zip(&other.values) {
+            *value += added * weight;
+        }
+    }
+
+    /// Fade every cell towards zero by a fraction of its value
+    pub fn decay(&mut self, rate: f32) {
+        let keep = 1.0 - rate.clamp(0.0, 1.0);
+        self.values.iter_mut().for_each(|value| *value *= keep);
+    }
+
+    /// Spread influence into neighboring cells
+    ///
+    /// Each pass moves every cell towards a [1, 2, 1] weighted average of its neighborhood by
+    /// `strength`, from 0 (unchanged) to 1 (fully blurred). Cells past the edges repeat the
+    /// border cells.
+    pub fn blur(&mut self, strength: f32, passes: usize) {
+        let strength = strength.clamp(0.0, 1.0);
+        if strength == 0.0 || self.values.is_empty() {
+            return;
+        }
+
+        let (columns, rows) = (self.columns, self.rows);
+        let mut horizontal = vec![0.0; self.values.len()];
+        for _ in 0..passes {
+            for cy in 0..rows {
+                let row = &self.values[cy * columns..(cy + 1) * columns];
+                for (cx, out) in horizontal[cy * columns..(cy + 1) * columns].iter_mut().enumerate() {
+                    let left = row[cx.saturating_sub(1)];
+                    let right = row[(cx + 1).min(columns - 1)];
+                    *out = (left + 2.0 * row[cx] + right) * 0.25;
+                }
+            }
+            for cy in 0..rows {
+                let (up, down) = (cy.saturating_sub(1), (cy + 1).min(rows - 1));
+                for cx in 0..columns {
+                    let blurred = (horizontal[up * columns + cx]
+                        + 2.0 * horizontal[cy * columns + cx]
+                        + horizontal[down * columns + cx])
+                        * 0.25;
+                    let value = &mut self.values[cy * columns + cx];
+                    *value += (blurred - *value) * strength;
+                }
+            }
+        }
+    }
+
+    /// Find the cell with the highest value within a radius, as its center and value
+    pub fn highest_in_radius(&self, x: f32, y: f32, radius: f32) -> Option<(f32, f32, f32)> {
+        self.cells_in_radius(x, y, radius)
+            .fold(None, |best, cell| match best {
+                Some((_, _, value)) if value >= cell.2 => best,
+                _ => Some(cell),
+            })
+    }
+
+    /// Find the cell with the lowest value within a radius, as its center and value
+    pub fn lowest_in_radius(&self, x: f32, y: f32, radius: f32) -> Option<(f32, f32, f32)> {
+        self.cells_in_radius(x, y, radius)
+            .fold(None, |best, cell| match best {
+                Some((_, _, value)) if value <= cell.2 => best,
+                _ => Some(cell),
+            })
+    }
+
+    fn cells_in_radius(&self, x: f32, y: f32, radius: f32) -> impl Iterator<Item = (f32, f32, f32)> + '_ {
+        let cell_size = self.cell_size();
+        let radius_sq = radius * radius;
+        let cell_range = move |center: f32, cells: usize| {
+            let first = ((center - radius) / cell_size).floor().max(0.0) as usize;
+            let last = ((center + radius) / cell_size).floor().max(0.0) as usize;
+            first..(last + 1).min(cells)
+        };
+        let (xs, ys) = (cell_range(x, self.columns), cell_range(y, self.rows));
+
+        ys.flat_map(move |cy| xs.clone().map(move |cx| (cx, cy)))
+            .filter_map(move |(cx, cy)| {
+                let (px, py) = ((cx as f32 + 0.5) * cell_size, (cy as f32 + 0.5) * cell_size);
+                let (dx, dy) = (px - x, py - y);
+                (dx * dx + dy * dy <= radius_sq).then(|| (px, py, self.values[cy * self.columns + cx]))
+            })
+    }
+}
+
+/// Kind of influence kept by [`InfluenceMaps`]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum InfluenceLayer {
+    /// Where a faction's NPCs are, and recently were
+    Presence(FactionId),
+    /// Where the enemies of a faction are, and recently were
+    Threat(FactionId),
+    /// How much forage, crops and timber the land offers
+    Resources,
+}
+
+/// Influence layers of a world, updated together every tick
+#[derive(Clone, Debug)]
+pub struct InfluenceMaps {
+    template: InfluenceMap,
+    layers: HashMap<InfluenceLayer, InfluenceMap>,
+    decay_rate: f32,
+    blur_strength: f32,
+    blur_passes: usize,
+    presence_radius: f32,
+}
+
+impl InfluenceMaps {
+    /// Create empty layers covering a world
+    pub fn new(world: &World) -> Self {
+        Self {
+            template: InfluenceMap::new(world),
+            layers: HashMap::new(),
+            decay_rate: INFLUENCE_DECAY_RATE,
+            blur_strength: 0.5,
+            blur_passes: 1,
+            presence_radius: INFLUENCE_PRESENCE_RADIUS,
+        }
+    }
+
+    /// Set how many cells the layers have along each side of a chunk, clearing them
+    pub fn with_cells_per_chunk(mut self, cells_per_chunk: usize) -> Self {
+        self.template = self.template.with_cells_per_chunk(cells_per_chunk);
+        self.layers.clear();
+        self
+    }
+
+    /// Set the fraction of presence and threat fading away every update
+    pub fn with_decay(mut self, rate: f32) -> Self {
+        self.decay_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set how much every update spreads influence, see [`InfluenceMap::blur`]
+    pub fn with_blur(mut self, strength: f32, passes: usize) -> Self {
+        self.blur_strength = strength.clamp(0.0, 1.0);
+        self.blur_passes = passes;
+        self
+    }
+
+    /// Set the radius of the presence stamped around every NPC (in meters)
+    pub fn with_presence_radius(mut self, radius: f32) -> Self {
+        self.presence_radius = radius.max(0.0);
+        self
+    }
+
+    /// Get a layer, `None` if nothing was stamped into it yet
+    pub fn layer(&self, layer: &InfluenceLayer) -> Option<&InfluenceMap> {
+        self.layers.get(layer)
+    }
+
+    /// Get a layer to stamp into, creating it empty if needed
+    pub fn layer_mut(&mut self, layer: InfluenceLayer) -> &mut InfluenceMap {
+        let template = &self.template;
+        self.layers.entry(layer).or_insert_with(|| {
+            let mut map = template.clone();
+            map.clear();
+            map
+        })
+    }
+
+    /// Get the value of a layer at a world position
+    pub fn value_at(&self, layer: &InfluenceLayer, x: f32, y: f32) -> f32 {
+        self.layer(layer).map_or(0.0, |map| map.value_at(x, y))
+    }
+
+    /// Add influence to a layer around a position, see [`InfluenceMap::stamp`]
+    pub fn stamp(&mut self, layer: InfluenceLayer, x: f32, y: f32, amount: f32, radius: f32) {
+        self.layer_mut(layer).stamp(x, y, amount, radius);
+    }
+
+    /// Advance the layers by a tick
+    ///
+    /// Presence and threat decay and blur, so they linger where NPCs have been and anything
+    /// stamped between updates fades out too. Then every living NPC with a faction stamps its
+    /// health as presence of its faction, and as threat to every faction at war with it either
+    /// way. Resources are rebuilt from the flora of every chunk, relative to what its biome can
+    /// hold, and blurred across chunk borders.
+    pub fn update(&mut self, world: &World) {
+        for map in self.layers.values_mut() {
+            map.decay(self.decay_rate);
+            map.blur(self.blur_strength, self.blur_passes);
+        }
+
+        let mut enemies: HashMap<&FactionId, Vec<&FactionId>> = HashMap::new();
+        for faction in world.factions.values() {
+            for enemy in &faction.enemy_factions {
+                enemies.entry(&faction.id).or_default().push(enemy);
+                enemies.entry(enemy).or_default().push(&faction.id);
+            }
+        }
+
+        for npc in world.npcs.values() {
+            let Some(faction) = npc.faction.as_ref() else {
+                continue;
+            };
+            let Some(entity) = world.entities.get(&npc.entity_id).filter(|entity| entity.is_alive) else {
+                continue;
+            };
+            let radius = self.presence_radius;
+            self.stamp(InfluenceLayer::Presence(faction.clone()), entity.x, entity.y, entity.health, radius);
+
+            let mut threatened = enemies.get(faction).cloned().unwrap_or_default();
+            threatened.sort();
+            threatened.dedup();
+            for enemy in threatened {
+                self.stamp(InfluenceLayer::Threat(enemy.clone()), entity.x, entity.y, entity.health, radius);
+            }
+        }
+
+        self.rebuild_resources(world);
+    }
+
+    fn rebuild_resources(&mut self, world: &World) {
+        let (blur_strength, blur_passes) = (self.blur_strength, self.blur_passes);
+        let resources = self.layer_mut(InfluenceLayer::Resources);
+        let n = resources.cells_per_chunk;
+        let columns = resources.columns;
+        resources.clear();
+
+        for (coord, chunk) in &world.chunks {
+            let (x0, y0) = (coord.x as usize * n, coord.y as usize * n);
+            if x0 >= resources.columns || y0 >= resources.rows {
+                continue;
+            }
+            let profile = FloraProfile::for_biome(chunk.biome);
+            let forage = if profile.forage_capacity > 0 {
+                chunk.flora.forage / profile.forage_capacity as f32
+            } else {
+                0.0
+            };
+            let value = chunk.flora.forest_density + chunk.flora.crops + forage.clamp(0.0, 1.0);
+
+            for cy in y0..y0 + n {
+                resources.values[cy * columns + x0..cy * columns + x0 + n].fill(value);
+            }
+        }
+
+        resources.blur(blur_strength, blur_passes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ChunkCoord, Entity};
+    use entropic_world_core::population::{EntityType, Faction, NPC};
+
+    fn create_test_world() -> World {
+        let mut world = World::new("Test".to_string(), "game1".to_string(), 4, 4);
+        world.initialize_chunks();
+        world
+    }
+
+    fn add_soldier(world: &mut World, id: &str, faction: &str, x: f32, y: f32) {
+        let coord = ChunkCoord::new((x / CHUNK_SIZE) as u32, (y / CHUNK_SIZE) as u32);
+        world.add_entity(Entity::new(id.to_string(), EntityType::NPC, x, y, 0.0, coord));
+        let mut npc = NPC::new(format!("npc-{}", id), id.to_string(), id.to_string());
+        npc.faction = Some(faction.to_string());
+        world.add_npc(npc);
+    }
+
+    #[test]
+    fn test_stamp_falloff() {
+        let world = create_test_world();
+        let mut map = InfluenceMap::new(&world);
+        assert_eq!(map.dimensions(), (32, 32));
+        assert_eq!(map.cell_size(), 32.0);
+
+        map.stamp(304.0, 304.0, 10.0, 160.0);
+        let center = map.value_at(304.0, 304.0);
+        let near = map.value_at(304.0, 370.0);
+        let far = map.value_at(304.0, 430.0);
+        assert!(center > near && near > far && far > 0.0);
+        assert!(center <= 10.0);
+        assert_eq!(map.value_at(304.0, 500.0), 0.0);
+
+        // Stamps hanging over the edges and small radii
+        map.stamp(-10.0, 5.0, 4.0, 80.0);
+        assert!(map.value_at(0.0, 0.0) > 0.0);
+        map.stamp(1000.0, 1000.0, 3.0, 1.0);
+        assert_eq!(map.value_at(1000.0, 1000.0), 3.0);
+        map.stamp(5000.0, 5000.0, 3.0, 1.0);
+        assert_eq!(map.value_at(5000.0, 5000.0), 0.0);
+        assert_eq!(map.value_at(-1.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_decay_and_blur() {
+        let world = create_test_world();
+        let mut map = InfluenceMap::new(&world);
+        map.stamp(500.0, 500.0, 8.0, 0.0);
+
+        map.decay(0.25);
+        assert_eq!(map.value_at(500.0, 500.0), 6.0);
+
+        let total: f32 = map.values.iter().sum();
+        map.blur(1.0, 2);
+        let blurred: f32 = map.values.iter().sum();
+        assert!(map.value_at(500.0, 500.0) < 6.0);
+        assert!(map.value_at(500.0 + 2.0 * map.cell_size(), 500.0) > 0.0);
+        assert_eq!(map.value_at(500.0 + 3.0 * map.cell_size(), 500.0), 0.0);
+        assert!((total - blurred).abs() < 1e-3);
+
+        // Clamped edges keep a corner from leaking out of the map
+        let mut corner = InfluenceMap::new(&world);
+        corner.stamp(0.0, 0.0, 1.0, 0.0);
+        corner.blur(0.5, 3);
+        assert!(corner.max_value() > 0.0 && corner.max_value() < 1.0);
+
+        map.clear();
+        assert_eq!(map.max_value(), 0.0);
+    }
+
+    #[test]
+    fn test_best_cells_in_radius() {
+        let world = create_test_world();
+        let mut map = InfluenceMap::new(&world).with_cells_per_chunk(4);
+        assert_eq!(map.dimensions(), (16, 16));
+        map.stamp(200.0, 200.0, 5.0, 0.0);
+        map.stamp(400.0, 200.0, -2.0, 0.0);
+
+        let (x, y, value) = map.highest_in_radius(300.0, 200.0, 150.0).unwrap();
+        assert_eq!((x, y, value), (224.0, 224.0, 5.0));
+        let (x, y, value) = map.lowest_in_radius(300.0, 200.0, 150.0).unwrap();
+        assert_eq!((x, y, value), (416.0, 224.0, -2.0));
+
+        assert!(map.highest_in_radius(-500.0, -500.0, 10.0).is_none());
+        assert_eq!(map.highest_in_radius(928.0, 928.0, 10.0).map(|cell| cell.2), Some(0.0));
+    }
+
+    #[test]
+    fn test_update_presence_and_threat() {
+        let mut world = create_test_world();
+        let mut red = Faction::new("red".to_string(), "Red".to_string(), "a".to_string());
+        red.add_enemy("blue".to_string());
+        world.factions.insert("red".to_string(), red);
+        add_soldier(&mut world, "a", "red", 100.0, 100.0);
+        add_soldier(&mut world, "b", "blue", 800.0, 800.0);
+        add_soldier(&mut world, "c", "green", 500.0, 100.0);
+
+        let mut maps = InfluenceMaps::new(&world).with_decay(0.5).with_blur(0.0, 0);
+        maps.update(&world);
+
+        let red_presence = InfluenceLayer::Presence("red".to_string());
+        let red_threat = InfluenceLayer::Threat("red".to_string());
+        let blue_threat = InfluenceLayer::Threat("blue".to_string());
+        assert!(maps.value_at(&red_presence, 100.0, 100.0) > 0.0);
+        assert_eq!(maps.value_at(&red_presence, 800.0, 800.0), 0.0);
+        assert!(maps.value_at(&red_threat, 800.0, 800.0) > 0.0);
+        assert!(maps.value_at(&blue_threat, 100.0, 100.0) > 0.0);
+        assert_eq!(maps.value_at(&red_threat, 500.0, 100.0), 0.0);
+        assert!(maps.layer(&InfluenceLayer::Threat("green".to_string())).is_none());
+
+        // The blue soldier moves away, leaving threat behind that fades out
+        let before = maps.value_at(&red_threat, 800.0, 800.0);
+        world.entities.get_mut("b").unwrap().is_alive = false;
+        maps.update(&world);
+        assert_eq!(maps.value_at(&red_threat, 800.0, 800.0), before * 0.5);
+
+        // A safe spot for red lies away from the threat
+        let (x, y, _) = maps.layer(&red_threat).unwrap().lowest_in_radius(700.0, 700.0, 150.0).unwrap();
+        assert!(x < 700.0 && y < 700.0);
+    }
+
+    #[test]
+    fn test_resources_from_flora() {
+        let mut world = create_test_world();
+        for chunk in world.chunks.values_mut() {
+            chunk.flora.forest_density = 0.0;
+            chunk.flora.forage = 0.0;
+            chunk.flora.crops = 0.0;
+        }
+        world.chunks.get_mut(&ChunkCoord::new(2, 1)).unwrap().flora.crops = 1.0;
+
+        let mut maps = InfluenceMaps::new(&world).with_blur(0.0, 0);
+        maps.update(&world);
+        let resources = InfluenceLayer::Resources;
+        assert_eq!(maps.value_at(&resources, 600.0, 300.0), 1.0);
+        assert_eq!(maps.value_at(&resources, 100.0, 100.0), 0.0);
+
+        let (x, y, _) = maps.layer(&resources).unwrap().highest_in_radius(300.0, 300.0, 400.0).unwrap();
+        assert_eq!(ChunkCoord::new((x / CHUNK_SIZE) as u32, (y / CHUNK_SIZE) as u32), ChunkCoord::new(2, 1));
+
+        // Resources are rebuilt rather than decayed
+        maps.update(&world);
+        assert_eq!(maps.value_at(&resources, 600.0, 300.0), 1.0);
+    }
+}
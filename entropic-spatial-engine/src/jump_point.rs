@@ -0,0 +1,597 @@
+use crate::collision::CollisionDetector;
+use crate::constants::*;
+use crate::World;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Pathfinding grid cell, in units of `PATHFINDING_GRID_SIZE`
+type Cell = (i32, i32);
+
+/// Straight directions first, then diagonals
+const DIRECTIONS: [Cell; 8] = [
+    (1, 0),
+    (0, 1),
+    (-1, 0),
+    (0, -1),
+    (1, 1),
+    (-1, 1),
+    (-1, -1),
+    (1, -1),
+];
+
+const STRAIGHT_COST: u32 = 1000;
+const DIAGONAL_COST: u32 = 1414;
+
+#[derive(Clone, Eq, PartialEq)]
+struct JumpState {
+    cost: u32,
+    cell: Cell,
+}
+
+impl Ord for JumpState {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.cmp(&self.cost).then_with(|| other.cell.cmp(&self.cell))
+    }
+}
+
+impl PartialOrd for JumpState {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Jump point search (JPS) on the pathfinding grid
+///
+/// Diagonal moves are only taken when both adjacent straight moves are open, so paths
+/// never cut the corner of an obstacle. The returned path lists every cell like A* does.
+pub(crate) fn find_path(
+    world: &World,
+    start: (f32, f32),
+    goal: (f32, f32),
+    max_iterations: u32,
+) -> Option<Vec<(f32, f32)>> {
+    let walkable = |cell: Cell| {
+        cell.0 >= 0
+            && cell.1 >= 0
+            && CollisionDetector::is_walkable(
+                world,
+                cell.0 as f32 * PATHFINDING_GRID_SIZE,
+                cell.1 as f32 * PATHFINDING_GRID_SIZE,
+            )
+    };
+
+    let start = to_cell(start);
+    let goal = to_cell(goal);
+    if !walkable(goal) {
+        return None;
+    }
+
+    search(start, goal, max_iterations, |cell, parent| {
+        pruned_directions(&walkable, cell, parent)
+            .into_iter()
+            .filter_map(|direction| jump(&walkable, cell, direction, goal))
+            .collect()
+    })
+}
+
+/// Jump distances precomputed over a rectangle of the pathfinding grid for JPS+
+///
+/// For every cell and each of the eight directions the table stores how far the next jump
+/// point is (positive) or how far the next obstacle is (zero or negative), so searches no
+/// longer scan the grid. Cells outside the rectangle are treated as blocked. Rebuild the
+/// table when the terrain inside it changes.
+#[derive(Clone, Debug)]
+pub struct JumpPointTable {
+    origin: Cell,
+    width: i32,
+    height: i32,
+    walkable: Vec<bool>,
+    distances: Vec<[i32; 8]>,
+}
+
+impl JumpPointTable {
+    /// Precompute jump distances for the grid cells between two world positions
+    pub fn build(world: &World, min: (f32, f32), max: (f32, f32)) -> Self {
+        let origin = to_cell((min.0.min(max.0), min.1.min(max.1)));
+        let end = to_cell((min.0.max(max.0), min.1.max(max.1)));
+        let width = end.0 - origin.0 + 1;
+        let height = end.1 - origin.1 + 1;
+
+        let mut walkable_cells = Vec::with_capacity((width.max(0) * height.max(0)) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                let cell = (origin.0 + x, origin.1 + y);
+                walkable_cells.push(
+                    cell.0 >= 0
+                        && cell.1 >= 0
+                        && CollisionDetector::is_walkable(
+                            world,
+                            cell.0 as f32 * PATHFINDING_GRID_SIZE,
+                            cell.1 as f32 * PATHFINDING_GRID_SIZE,
+                        ),
+                );
+            }
+        }
+
+        let mut table = Self {
+            origin,
+            width,
+            height,
+            distances: vec![[0; 8]; walkable_cells.len()],
+            walkable: walkable_cells,
+        };
+        let walkable = |cell: Cell| table.index(cell).is_some_and(|i| table.walkable[i]);
+
+        let mut straight = vec![[0; 8]; table.walkable.len()];
+        for (index, distances) in straight.iter_mut().enumerate() {
+            let cell = table.cell(index);
+            if !walkable(cell) {
+                continue;
+            }
+            for (d, &direction) in DIRECTIONS[..4].iter().enumerate() {
+                let mut current = cell;
+                let mut steps = 0;
+                distances[d] = loop {
+                    let next = (current.0 + direction.0, current.1 + direction.1);
+                    if !walkable(next) {
+                        break -steps;
+                    }
+                    current = next;
+                    steps += 1;
+                    if is_forced(&walkable, current, direction) {
+                        break steps;
+                    }
+                };
+            }
+        }
+
+        let mut distances = straight.clone();
+        for (index, cell_distances) in distances.iter_mut().enumerate() {
+            let cell = table.cell(index);
+            if !walkable(cell) {
+                continue;
+            }
+            for (d, &(dx, dy)) in DIRECTIONS.iter().enumerate().skip(4) {
+                let straight_x = DIRECTIONS.iter().position(|&dir| dir == (dx, 0)).unwrap_or(0);
+                let straight_y = DIRECTIONS.iter().position(|&dir| dir == (0, dy)).unwrap_or(0);
+                let mut current = cell;
+                let mut steps = 0;
+                cell_distances[d] = loop {
+                    if !diagonal_open(&walkable, current, (dx, dy)) {
+                        break -steps;
+                    }
+                    current = (current.0 + dx, current.1 + dy);
+                    steps += 1;
+                    let Some(i) = table.index(current) else {
+                        break -steps;
+                    };
+                    if straight[i][straight_x] > 0 || straight[i][straight_y] > 0 {
+                        break steps;
+                    }
+                };
+            }
+        }
+
+        table.distances = distances;
+        table
+    }
+
+    /// Check if a world position is covered by the table
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        self.index(to_cell((x, y))).is_some()
+    }
+
+    /// Find path from start to goal with JPS+ using the precomputed jump distances
+    ///
+    /// Returns the same kind of path as [`Pathfinder::find_path`](crate::Pathfinder::find_path),
+    /// or None if either point lies outside the table or no path is found within
+    /// `max_iterations`.
+    pub fn find_path(
+        &self,
+        start: (f32, f32),
+        goal: (f32, f32),
+        max_iterations: u32,
+    ) -> Option<Vec<(f32, f32)>> {
+        let start = to_cell(start);
+        let goal = to_cell(goal);
+        self.index(start)?;
+        if !self.walkable[self.index(goal)?] {
+            return None;
+        }
+
+        search(start, goal, max_iterations, |cell, parent| {
+            let Some(index) = self.index(cell) else {
+                return Vec::new();
+            };
+            let (gx, gy) = (goal.0 - cell.0, goal.1 - cell.1);
+            let mut successors = Vec::new();
+
+            for direction in travel_directions(cell, parent) {
+                let d = DIRECTIONS.iter().position(|&dir| dir == direction).unwrap_or(0);
+                let distance = self.distances[index][d];
+                let (dx, dy) = direction;
+
+                if dx == 0 || dy == 0 {
+                    // Goal straight ahead and before the next obstacle.
+                    let ahead = if dx == 0 { gy * dy } else { gx * dx };
+                    let aligned = if dx == 0 { gx == 0 } else { gy == 0 };
+                    if aligned && ahead > 0 && ahead <= distance.abs() {
+                        successors.push(goal);
+                        continue;
+                    }
+                } else if gx.signum() == dx && gy.signum() == dy {
+                    // Goal in this quadrant: stop where its row or column is reached.
+                    let steps = gx.abs().min(gy.abs());
+                    if steps <= distance.abs() {
+                        successors.push((cell.0 + dx * steps, cell.1 + dy * steps));
+                        continue;
+                    }
+                }
+
+                if distance > 0 {
+                    successors.push((cell.0 + dx * distance, cell.1 + dy * distance));
+                }
+            }
+
+            successors
+        })
+    }
+
+    fn index(&self, cell: Cell) -> Option<usize> {
+        let x = cell.0 - self.origin.0;
+        let y = cell.1 - self.origin.1;
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return None;
+        }
+        Some((y * self.width + x) as usize)
+    }
+
+    fn cell(&self, index: usize) -> Cell {
+        let index = index as i32;
+        (self.origin.0 + index % self.width, self.origin.1 + index / self.width)
+    }
+}
+
+/// A* over jump points; `successors` lists the jump points reachable from a cell given the
+/// cell it was reached from
+fn search(
+    start: Cell,
+    goal: Cell,
+    max_iterations: u32,
+    mut successors: impl FnMut(Cell, Option<Cell>) -> Vec<Cell>,
+) -> Option<Vec<(f32, f32)>> {
+    let mut open_set = BinaryHeap::new();
+    let mut came_from: HashMap<Cell, Cell> = HashMap::new();
+    let mut g_score: HashMap<Cell, u32> = HashMap::new();
+    let mut closed: HashSet<Cell> = HashSet::new();
+
+    g_score.insert(start, 0);
+    open_set.push(JumpState {
+        cost: octile(start, goal),
+        cell: start,
+    });
+
+    let mut iterations = 0;
+    while let Some(JumpState { cell: current, .. }) = open_set.pop() {
+        if current == goal {
+            return Some(expand(&came_from, current));
+        }
+        if !closed.insert(current) {
+            continue;
+        }
+        iterations += 1;
+        if iterations > max_iterations {
+            break;
+        }
+
+        let current_g = g_score[&current];
+        for next in successors(current, came_from.get(&current).copied()) {
+            let tentative_g = current_g + octile(current, next);
+            if tentative_g < g_score.get(&next).copied().unwrap_or(u32::MAX) {
+                came_from.insert(next, current);
+                g_score.insert(next, tentative_g);
+                open_set.push(JumpState {
+                    cost: tentative_g + octile(next, goal),
+                    cell: next,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Directions worth searching from a cell, pruned by the direction it was entered from
+fn pruned_directions(walkable: &impl Fn(Cell) -> bool, cell: Cell, parent: Option<Cell>) -> Vec<Cell> {
+    let (x, y) = cell;
+    let Some(parent) = parent else {
+        return DIRECTIONS
+            .iter()
+            .copied()
+            .filter(|&(dx, dy)| {
+                if dx != 0 && dy != 0 {
+                    diagonal_open(walkable, cell, (dx, dy))
+                } else {
+                    walkable((x + dx, y + dy))
+                }
+            })
+            .collect();
+    };
+
+    let (dx, dy) = ((x - parent.0).signum(), (y - parent.1).signum());
+    let mut directions = Vec::new();
+
+    if dx != 0 && dy != 0 {
+        let open_x = walkable((x + dx, y));
+        let open_y = walkable((x, y + dy));
+        if open_y {
+            directions.push((0, dy));
+        }
+        if open_x {
+            directions.push((dx, 0));
+        }
+        if open_x && open_y {
+            directions.push((dx, dy));
+        }
+    } else if dx != 0 {
+        let ahead = walkable((x + dx, y));
+        for side in [1, -1] {
+            if walkable((x, y + side)) {
+                if ahead {
+                    directions.push((dx, side));
+                }
+                directions.push((0, side));
+            }
+        }
+        if ahead {
+            directions.push((dx, 0));
+        }
+    } else {
+        let ahead = walkable((x, y + dy));
+        for side in [1, -1] {
+            if walkable((x + side, y)) {
+                if ahead {
+                    directions.push((side, dy));
+                }
+                directions.push((side, 0));
+            }
+        }
+        if ahead {
+            directions.push((0, dy));
+        }
+    }
+
+    directions
+}
+
+/// Directions searched by JPS+ from a cell; blocked ones are filtered by the jump distances
+fn travel_directions(cell: Cell, parent: Option<Cell>) -> Vec<Cell> {
+    let Some(parent) = parent else {
+        return DIRECTIONS.to_vec();
+    };
+
+    let (dx, dy) = ((cell.0 - parent.0).signum(), (cell.1 - parent.1).signum());
+    if dx != 0 && dy != 0 {
+        vec![(dx, 0), (0, dy), (dx, dy)]
+    } else if dx != 0 {
+        vec![(dx, 0), (0, 1), (0, -1), (dx, 1), (dx, -1)]
+    } else {
+        vec![(0, dy), (1, 0), (-1, 0), (1, dy), (-1, dy)]
+    }
+}
+
+/// Walk from `cell` in `direction` until a jump point, the goal or an obstacle is reached
+fn jump(walkable: &impl Fn(Cell) -> bool, cell: Cell, direction: Cell, goal: Cell) -> Option<Cell> {
+    let (dx, dy) = direction;
+    let mut current = cell;
+
+    loop {
+        if dx != 0 && dy != 0 && !diagonal_open(walkable, current, direction) {
+            return None;
+        }
+        current = (current.0 + dx, current.1 + dy);
+        if !walkable(current) {
+            return None;
+        }
+        if current == goal {
+            return Some(current);
+        }
+
+        if dx != 0 && dy != 0 {
+            // Crossing the goal's row or column: the goal may be straight ahead from here.
+            if current.0 == goal.0 || current.1 == goal.1 {
+                return Some(current);
+            }
+            if jump(walkable, current, (dx, 0), goal).is_some()
+                || jump(walkable, current, (0, dy), goal).is_some()
+            {
+                return Some(current);
+            }
+        } else if is_forced(walkable, current, direction) {
+            return Some(current);
+        }
+    }
+}
+
+/// Check if a cell reached by a straight move has a neighbor only reachable through it
+fn is_forced(walkable: &impl Fn(Cell) -> bool, cell: Cell, direction: Cell) -> bool {
+    let (x, y) = cell;
+    let (dx, dy) = direction;
+    if dx != 0 {
+        (walkable((x, y - 1)) && !walkable((x - dx, y - 1)))
+            || (walkable((x, y + 1)) && !walkable((x - dx, y + 1)))
+    } else {
+        (walkable((x - 1, y)) && !walkable((x - 1, y - dy)))
+            || (walkable((x + 1, y)) && !walkable((x + 1, y - dy)))
+    }
+}
+
+/// Check if a diagonal step can be taken without cutting a corner
+fn diagonal_open(walkable: &impl Fn(Cell) -> bool, cell: Cell, direction: Cell) -> bool {
+    let (x, y) = cell;
+    let (dx, dy) = direction;
+    walkable((x + dx, y)) && walkable((x, y + dy)) && walkable((x + dx, y + dy))
+}
+
+/// Octile distance between two cells, exact for cells on a straight or diagonal line
+fn octile(a: Cell, b: Cell) -> u32 {
+    let dx = (a.0 - b.0).unsigned_abs();
+    let dy = (a.1 - b.1).unsigned_abs();
+    STRAIGHT_COST * dx.max(dy) + (DIAGONAL_COST - STRAIGHT_COST) * dx.min(dy)
+}
+
+/// Rebuild the path of jump points ending at `goal` and fill in the cells between them
+fn expand(came_from: &HashMap<Cell, Cell>, goal: Cell) -> Vec<(f32, f32)> {
+    let mut jump_points = vec![goal];
+    let mut node = goal;
+    while let Some(&prev) = came_from.get(&node) {
+        jump_points.push(prev);
+        node = prev;
+    }
+    jump_points.reverse();
+
+    let mut cells = vec![jump_points[0]];
+    for pair in jump_points.windows(2) {
+        let (dx, dy) = ((pair[1].0 - pair[0].0).signum(), (pair[1].1 - pair[0].1).signum());
+        let mut current = pair[0];
+        while current != pair[1] {
+            current = (current.0 + dx, current.1 + dy);
+            cells.push(current);
+        }
+    }
+
+    cells
+        .into_iter()
+        .map(|(x, y)| {
+            (
+                x as f32 * PATHFINDING_GRID_SIZE + PATHFINDING_GRID_SIZE / 2.0,
+                y as f32 * PATHFINDING_GRID_SIZE + PATHFINDING_GRID_SIZE / 2.0,
+            )
+        })
+        .collect()
+}
+
+fn to_cell(position: (f32, f32)) -> Cell {
+    (
+        (position.0 / PATHFINDING_GRID_SIZE).floor() as i32,
+        (position.1 / PATHFINDING_GRID_SIZE).floor() as i32,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pathfinding::{PathAlgorithm, Pathfinder};
+    use crate::ChunkCoord;
+
+    fn create_test_world(width: u32, height: u32) -> World {
+        let mut world = World::new("Test".to_string(), "game1".to_string(), width, height);
+        world.initialize_chunks();
+
+        for chunk in world.chunks.values_mut() {
+            chunk.water_level = -100.0;
+            chunk.elevation.iter_mut().for_each(|h| *h = 100.0);
+        }
+
+        world
+    }
+
+    /// Make a pathfinding cell of the first chunk unwalkable
+    fn block(world: &mut World, cell: Cell) {
+        let chunk = world.chunks.get_mut(&ChunkCoord::new(0, 0)).unwrap();
+        let x = cell.0 as usize * PATHFINDING_GRID_SIZE as usize;
+        let y = cell.1 as usize * PATHFINDING_GRID_SIZE as usize;
+        chunk.elevation[x * HEIGHTMAP_RESOLUTION + y] = 250.0;
+    }
+
+    fn assert_connected(path: &[(f32, f32)]) {
+        for step in path.windows(2) {
+            let dx = (step[1].0 - step[0].0).abs();
+            let dy = (step[1].1 - step[0].1).abs();
+            assert!(dx <= PATHFINDING_GRID_SIZE && dy <= PATHFINDING_GRID_SIZE);
+            assert!(dx + dy > 0.0);
+        }
+    }
+
+    /// A wall at cell column 8 with an opening at row 12
+    fn create_walled_world() -> World {
+        let mut world = create_test_world(1, 1);
+        for y in 0..16 {
+            if y != 12 {
+                block(&mut world, (8, y));
+            }
+        }
+        world
+    }
+
+    #[test]
+    fn test_jps_matches_astar_on_open_terrain() {
+        let world = create_test_world(2, 2);
+        let start = (8.0, 8.0);
+        let goal = (300.0, 180.0);
+
+        let astar = Pathfinder::find_path(&world, start, goal, 10_000).unwrap();
+        let jps = Pathfinder::find_path_with_algorithm(&world, start, goal, 10_000, PathAlgorithm::JumpPoint)
+            .unwrap();
+
+        assert_eq!(jps.first(), astar.first());
+        assert_eq!(jps.last(), astar.last());
+        assert_connected(&jps);
+        let (jps_length, astar_length) = (Pathfinder::path_length(&jps), Pathfinder::path_length(&astar));
+        assert!(jps_length <= astar_length + 0.01);
+    }
+
+    #[test]
+    fn test_jps_same_position() {
+        let world = create_test_world(1, 1);
+        let path = Pathfinder::find_path_with_algorithm(&world, (50.0, 50.0), (50.0, 50.0), 10, PathAlgorithm::JumpPoint)
+            .unwrap();
+        assert_eq!(path.len(), 1);
+    }
+
+    #[test]
+    fn test_jps_goes_through_opening() {
+        let world = create_walled_world();
+        let path = Pathfinder::find_path_with_algorithm(&world, (8.0, 8.0), (200.0, 8.0), 10_000, PathAlgorithm::JumpPoint)
+            .unwrap();
+
+        assert_connected(&path);
+        assert!(path.contains(&(8.0 * 16.0 + 8.0, 12.0 * 16.0 + 8.0)));
+    }
+
+    #[test]
+    fn test_jps_unreachable() {
+        let mut world = create_walled_world();
+        block(&mut world, (8, 12));
+        assert!(Pathfinder::find_path_with_algorithm(&world, (8.0, 8.0), (200.0, 8.0), 10_000, PathAlgorithm::JumpPoint)
+            .is_none());
+    }
+
+    #[test]
+    fn test_jps_plus_matches_jps() {
+        let world = create_walled_world();
+        let table = JumpPointTable::build(&world, (0.0, 0.0), (255.0, 255.0));
+
+        for goal in [(200.0, 8.0), (250.0, 250.0), (100.0, 40.0), (8.0, 200.0)] {
+            let jps = find_path(&world, (8.0, 8.0), goal, 10_000).unwrap();
+            let jps_plus = table.find_path((8.0, 8.0), goal, 10_000).unwrap();
+
+            assert_eq!(jps_plus.first(), jps.first());
+            assert_eq!(jps_plus.last(), jps.last());
+            assert_connected(&jps_plus);
+            let difference = Pathfinder::path_length(&jps_plus) - Pathfinder::path_length(&jps);
+            assert!(difference.abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_jps_plus_outside_table() {
+        let world = create_test_world(2, 1);
+        let table = JumpPointTable::build(&world, (0.0, 0.0), (255.0, 255.0));
+
+        assert!(table.contains(100.0, 100.0));
+        assert!(!table.contains(300.0, 100.0));
+        assert!(table.find_path((8.0, 8.0), (300.0, 8.0), 10_000).is_none());
+        assert!(table.find_path((8.0, 8.0), (200.0, 200.0), 10_000).is_some());
+    }
+}
@@ -5,12 +5,41 @@
 //!
 //! ## Features
 //!
-//! - **Chunk Manager**: Asynchronous chunk loading/unloading with priority queues
-//! - **Terrain Generator**: Deterministic procedural generation using Perlin noise
-//! - **Spatial Queries**: Fast radius, nearest entity, and raycast queries
-//! - **Pathfinding**: A* algorithm with terrain awareness
-//! - **Collision Detection**: Circle and terrain-based collision
-//! - **LOD System**: Progressive detail levels for distant terrain
+//! - **Chunk Manager**: Asynchronous chunk loading/unloading with priority queues for multiple observers,
+//!   parallel generation with backpressure, velocity-based prefetching and memory-budgeted LRU eviction
+//! - **Disk Cache**: Background persistence of generated and modified chunks, invalidated when the terrain
+//!   recipe changes, with checksummed files so corrupted or stale chunks are rejected
+//! - **Chunk Sources**: Chunks streamed from a network service with timeouts, retries, checksum and recipe
+//!   verification and procedural fallback
+//! - **Load Priorities**: Pluggable priority policies to boost chunks by heading or gameplay importance
+//! - **Terrain Generator**: Deterministic procedural generation using Perlin noise, with optional erosion and
+//!   temperature/moisture driven biomes
+//! - **Splat Maps**: Grass/rock/sand/snow weights per heightmap sample from slope, altitude and biome
+//! - **Noise**: Perlin, Simplex, Worley, ridged multifractal, billow and domain-warped noise, composable
+//!   into serializable noise graphs
+//! - **Determinism**: Fixed-point terrain noise and conformance vectors for bit-identical terrain across platforms
+//! - **Terrain Sources**: Pluggable `TerrainSource` trait for custom or hybrid authored/procedural terrain
+//! - **Heightmap Import/Export**: 16-bit PNG and raw heightmaps, with imported regions blended into
+//!   procedural terrain
+//! - **Structure Placement**: Villages, ruins and dungeons placed by biome, slope, water and spacing
+//! - **Scattering**: Seeded Poisson-disk sampling with per-biome density for trees, rocks and resources
+//! - **Hydrology**: Rivers and lakes from flow accumulation over the terrain
+//! - **Spatial Queries**: Fast radius, k-nearest, region, frustum and raycast queries, with exact terrain
+//!   raycasts and hit normals
+//! - **Pathfinding**: A* algorithm with terrain awareness, plus JPS/JPS+ and path smoothing
+//! - **Path Cost Profiles**: Per-entity-type terrain costs by biome, material, roads and water
+//! - **Path Jobs**: Async path request queue served by a bounded worker pool
+//! - **Hierarchical Pathfinding**: HPA* over cached chunk-border entrances for long paths
+//! - **Steering**: Velocity obstacle avoidance and separation between path waypoints
+//! - **Navigation Mesh**: Polygonal navmesh built from heightmaps and structures
+//! - **Collision Detection**: Circle, box, oriented box and capsule shapes against each other and the terrain,
+//!   with swept casts for fast-moving entities
+//! - **Fog of War**: Per-faction explored and visible grids updated from NPC sight ranges, persisted in saves
+//! - **Influence Maps**: Faction presence, threat and resource layers with decay and blur, for AI positioning
+//! - **LOD System**: Progressive detail levels for distant terrain over configurable distance bands with hysteresis,
+//!   with crack-free stitched meshes and geomorphing
+//! - **GPU Buffers**: Interleaved vertex and index buffers of a chunk at a LOD level, ready to upload
+//! - **Observability**: Optional `tracing` spans and chunk, validation and path queue metrics
 //!
 //! ## Example
 //!
@@ -36,31 +65,81 @@
 //! ```
 
 pub mod chunk_manager;
+pub mod climate;
 pub mod collision;
 pub mod constants;
+pub mod determinism;
+pub mod erosion;
 pub mod errors;
+pub mod heightmap;
+pub mod influence;
+pub mod hierarchical_pathfinding;
+pub mod hydrology;
+pub mod jump_point;
 pub mod lod;
+pub mod navmesh;
 pub mod noise;
+pub mod noise_graph;
+pub mod path_costs;
+pub mod path_jobs;
 pub mod pathfinding;
+pub mod placement;
+pub mod priority_policy;
+pub mod scatter;
 pub mod serialization;
+pub mod shapes;
 pub mod spatial_queries;
+pub mod splat;
+pub mod steering;
 pub mod streaming;
+pub mod terrain_buffers;
 pub mod terrain_generator;
+pub mod terrain_source;
+pub mod visibility;
 
 // Optional 3D features
 #[cfg(feature = "voxel")]
 pub mod voxel;
 
+#[cfg(feature = "voxel")]
+pub mod caves;
+
+#[cfg(feature = "voxel")]
+pub mod voxel_edit;
+
 #[cfg(feature = "octree")]
 pub mod octree;
 
-pub use chunk_manager::{ChunkManager, Priority};
+pub use chunk_manager::{
+    chunk_memory, ChunkManager, EvictionStats, GenerationEvent, Observer, ObserverId, Priority,
+};
+pub use climate::{Climate, ClimateMap, ClimateModel};
+pub use determinism::{ConformanceVector, Determinism, FixedNoise};
 pub use collision::CollisionDetector;
+pub use erosion::ErosionConfig;
 pub use errors::SpatialError;
-pub use lod::{LODLevel, LODManager};
-pub use pathfinding::Pathfinder;
-pub use spatial_queries::SpatialQueries;
+pub use heightmap::{Heightmap, ImportedTerrain};
+pub use hierarchical_pathfinding::HierarchicalPathfinder;
+pub use influence::{InfluenceLayer, InfluenceMap, InfluenceMaps};
+pub use hydrology::{Hydrology, HydrologyBuilder, Lake, River, RiverOutlet};
+pub use jump_point::JumpPointTable;
+pub use lod::{LODLevel, LODManager, LODMesh, LODNeighbors, LODTracker};
+pub use navmesh::{NavMesh, NavMeshBuilder, NavPolygon};
+pub use noise_graph::{CompiledNoise, NoiseGraph, NoiseNode};
+pub use path_costs::{CostProfile, CostProfiles};
+pub use path_jobs::{PathJob, PathJobQueue, PathOutcome, PathRequest};
+pub use pathfinding::{PathAlgorithm, Pathfinder};
+pub use placement::{PlacedPoi, PlacementRule, PoiKind, StructurePlacer};
+pub use priority_policy::{BoostedPriority, DistancePriority, PriorityContext, PriorityPolicy};
+pub use scatter::{PoissonSampler, ScatterPoint};
+pub use shapes::{Aabb, Capsule, CollisionShape, Obb, SweepHit};
+pub use spatial_queries::{Frustum, Plane, SpatialQueries, TerrainHit};
+pub use splat::SplatRules;
+pub use steering::{PathFollower, Steering};
+pub use terrain_buffers::{IndexBuffer, IndexFormat, TerrainBuffers};
 pub use terrain_generator::TerrainGenerator;
+pub use terrain_source::{HybridSource, TerrainSource};
+pub use visibility::{FogOfWar, Sighting, Visibility, VisibilityGrid};
 
 pub use entropic_world_core::{
     Chunk, ChunkCoord, Entity, Biome, World,
@@ -74,3 +153,4 @@ pub use constants::{
 
 // Re-export noise functions
 pub use noise::{NoiseFn, Perlin, Simplex};
+pub use crate::noise::{Billow, DomainWarp, Fbm, RidgedMultifractal, WorleyNoise, WorleyReturn};
@@ -1,3 +1,9 @@
+use crate::constants::*;
+use crate::errors::SpatialError;
+use crate::terrain_buffers::{IndexFormat, TerrainBuffers};
+use crate::{ChunkCoord, World};
+use std::collections::HashMap;
+
 /// Level-of-detail (LOD) system for progressive terrain detail
 #[derive(Clone)]
 pub struct LODLevel {
@@ -26,9 +32,59 @@ impl LODLevel {
     }
 }
 
+/// Mesh resolutions of the four chunks bordering a chunk
+///
+/// Resolutions are cells per side, as in [`LODLevel::heightmap_resolution`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LODNeighbors {
+    /// Neighbor towards -x
+    pub neg_x: usize,
+    /// Neighbor towards +x
+    pub pos_x: usize,
+    /// Neighbor towards -y
+    pub neg_y: usize,
+    /// Neighbor towards +y
+    pub pos_y: usize,
+}
+
+impl LODNeighbors {
+    /// All neighbors at the same resolution
+    pub fn uniform(resolution: usize) -> Self {
+        Self {
+            neg_x: resolution,
+            pos_x: resolution,
+            neg_y: resolution,
+            pos_y: resolution,
+        }
+    }
+}
+
+/// Terrain mesh of a chunk at one LOD level
+///
+/// Edges bordering a coarser chunk only use the vertices the coarser chunk has, so shared
+/// edges match exactly as long as resolutions are powers of two.
+#[derive(Clone, Debug)]
+pub struct LODMesh {
+    /// Cells per side
+    pub resolution: usize,
+    /// World positions as (x, y, height), grid vertices first, indexed `[j * (resolution + 1) + i]`
+    pub vertices: Vec<[f32; 3]>,
+    /// Counter-clockwise triangles of the surface
+    pub indices: Vec<u32>,
+    /// Triangles of the skirts hanging down from the edges, empty without skirts
+    pub skirt_indices: Vec<u32>,
+    /// Height of every grid vertex at the next coarser level, empty without geomorphing
+    pub morph_heights: Vec<f32>,
+    /// How far to blend grid vertices towards `morph_heights`, from 0 to 1
+    pub morph_weight: f32,
+}
+
 /// LOD manager for progressive terrain detail
 pub struct LODManager {
     levels: Vec<LODLevel>,
+    hysteresis: f32,
+    skirt_depth: Option<f32>,
+    morph_range: Option<f32>,
 }
 
 impl LODManager {
@@ -41,6 +97,9 @@ impl LODManager {
                 LODLevel::new(1024.0, 0.25),
                 LODLevel::new(2048.0, 0.125),
             ],
+            hysteresis: LOD_HYSTERESIS,
+            skirt_depth: None,
+            morph_range: None,
         }
     }
 
@@ -51,7 +110,51 @@ impl LODManager {
 
         Self {
             levels: sorted_levels,
+            hysteresis: LOD_HYSTERESIS,
+            skirt_depth: None,
+            morph_range: None,
+        }
+    }
+
+    /// Move the band boundaries to new distances (in meters), keeping the detail of each level
+    ///
+    /// Distances are assigned to the levels from finest to coarsest. Extra distances are
+    /// ignored and levels without a distance keep their own.
+    pub fn with_distances(mut self, distances: &[f32]) -> Self {
+        for (level, &distance) in self.levels.iter_mut().zip(distances) {
+            level.distance = distance.max(0.0);
+        }
+        self.levels.sort_by_key(|l| l.distance as i32);
+        self
+    }
+
+    /// Scale all band boundaries, e.g. to follow the view distance of an application
+    pub fn with_distance_scale(mut self, scale: f32) -> Self {
+        for level in &mut self.levels {
+            level.distance *= scale.max(0.0);
         }
+        self
+    }
+
+    /// Set how far past a band boundary (in meters) a chunk has to move before changing level
+    pub fn with_hysteresis(mut self, hysteresis: f32) -> Self {
+        self.hysteresis = hysteresis.max(0.0);
+        self
+    }
+
+    /// Hang skirts of the given depth (in meters) from chunk edges to hide remaining cracks
+    pub fn with_skirts(mut self, depth: f32) -> Self {
+        self.skirt_depth = Some(depth.max(0.0));
+        self
+    }
+
+    /// Geomorph towards the next level over the last part of each band
+    ///
+    /// `range` is the fraction of the band, so 0.25 starts morphing three quarters of the way
+    /// to the next level.
+    pub fn with_geomorphing(mut self, range: f32) -> Self {
+        self.morph_range = Some(range.clamp(0.0, 1.0));
+        self
     }
 
     /// Get appropriate LOD level for distance
@@ -63,6 +166,41 @@ impl LODManager {
             .unwrap_or(&self.levels[self.levels.len() - 1])
     }
 
+    /// Get the index of the LOD level for a distance, ignoring hysteresis
+    pub fn level_index(&self, distance: f32) -> usize {
+        self.levels
+            .iter()
+            .rposition(|l| l.distance <= distance)
+            .unwrap_or(self.levels.len().saturating_sub(1))
+    }
+
+    /// Get the index of the LOD level for a distance, given the level currently shown
+    ///
+    /// The current level is kept until the distance is more than the hysteresis past one of
+    /// its band boundaries, so small movements around a boundary do not flip levels.
+    pub fn update_level(&self, current: usize, distance: f32) -> usize {
+        let target = self.level_index(distance);
+        if current >= self.levels.len() {
+            return target;
+        }
+
+        let min = self.levels[current].distance - self.hysteresis;
+        let max = self
+            .levels
+            .get(current + 1)
+            .map_or(f32::INFINITY, |next| next.distance + self.hysteresis);
+        if (current == 0 || distance >= min) && distance < max {
+            current
+        } else {
+            target
+        }
+    }
+
+    /// Get the distance past a band boundary needed to change level
+    pub fn hysteresis(&self) -> f32 {
+        self.hysteresis
+    }
+
     /// Get all LOD levels
     pub fn levels(&self) -> &[LODLevel] {
         &self.levels
@@ -86,12 +224,257 @@ impl LODManager {
         self.get_lod_level(distance).detail_scale
     }
 
+    /// Get the mesh resolution for a distance
+    pub fn resolution_for(&self, distance: f32) -> usize {
+        self.get_lod_level(distance).heightmap_resolution.max(2)
+    }
+
+    /// Get how far vertices at a distance are morphed towards the next coarser level
+    pub fn morph_weight(&self, distance: f32) -> f32 {
+        let Some(range) = self.morph_range else {
+            return 0.0;
+        };
+        let Some(index) = self.levels.iter().rposition(|l| l.distance <= distance) else {
+            return 0.0;
+        };
+        let Some(next) = self.levels.get(index + 1) else {
+            return 0.0;
+        };
+
+        let band = next.distance - self.levels[index].distance;
+        let start = next.distance - band * range;
+        if next.distance <= start {
+            return 0.0;
+        }
+        ((distance - start) / (next.distance - start)).clamp(0.0, 1.0)
+    }
+
+    /// Build the terrain mesh of a chunk seen from a distance
+    ///
+    /// Edges are stitched to the resolutions of the neighbors, and skirts and morph targets are
+    /// added when enabled. Heights are read from the world, so chunks on both sides of an
+    /// edge see the same heights.
+    pub fn build_mesh(&self, world: &World, coord: ChunkCoord, distance: f32, neighbors: LODNeighbors) -> LODMesh {
+        let cells = self.resolution_for(distance);
+        let row = cells + 1;
+        let origin = (coord.x as f32 * CHUNK_SIZE, coord.y as f32 * CHUNK_SIZE);
+        let spacing = CHUNK_SIZE / cells as f32;
+
+        let mut vertices = Vec::with_capacity(row * row);
+        for j in 0..row {
+            for i in 0..row {
+                let (x, y) = (origin.0 + i as f32 * spacing, origin.1 + j as f32 * spacing);
+                vertices.push([x, y, sample_height(world, coord, x, y)]);
+            }
+        }
+        let index = |i: usize, j: usize| (j * row + i) as u32;
+
+        // Interior cells, split along the (i, j) - (i + 1, j + 1) diagonal
+        let mut indices = Vec::with_capacity(cells * cells * 6);
+        for j in 1..cells.saturating_sub(1) {
+            for i in 1..cells - 1 {
+                let (a, b, c, d) = (index(i, j), index(i + 1, j), index(i + 1, j + 1), index(i, j + 1));
+                indices.extend_from_slice(&[a, b, c, a, c, d]);
+            }
+        }
+
+        // Border ring, one strip per edge zipping the edge vertices to the first inner row
+        let edges = [
+            (neighbors.neg_y, Edge::NegY),
+            (neighbors.pos_x, Edge::PosX),
+            (neighbors.pos_y, Edge::PosY),
+            (neighbors.neg_x, Edge::NegX),
+        ];
+        let mut skirt_indices = Vec::new();
+
+        for (neighbor, side) in edges {
+            let step = if neighbor > 0 && neighbor < cells { cells / neighbor } else { 1 };
+            let mut edge: Vec<usize> = (0..=cells).step_by(step.max(1)).collect();
+            if edge.last() != Some(&cells) {
+                edge.push(cells);
+            }
+            let inner: Vec<usize> = (1..cells).collect();
+            let at = |t: usize, o: usize| {
+                let (i, j) = side.to_grid(cells, t, o);
+                index(i, j)
+            };
+
+            let (mut e, mut n) = (0, 0);
+            while e + 1 < edge.len() || n + 1 < inner.len() {
+                let advance_edge = n + 1 >= inner.len() || (e + 1 < edge.len() && edge[e + 1] <= inner[n + 1]);
+                if advance_edge {
+                    indices.extend_from_slice(&[at(edge[e], 0), at(edge[e + 1], 0), at(inner[n], 1)]);
+                    e += 1;
+                } else {
+                    indices.extend_from_slice(&[at(edge[e], 0), at(inner[n + 1], 1), at(inner[n], 1)]);
+                    n += 1;
+                }
+            }
+
+            if let Some(depth) = self.skirt_depth {
+                let first = vertices.len() as u32;
+                for &t in &edge {
+                    let [x, y, z] = vertices[at(t, 0) as usize];
+                    vertices.push([x, y, z - depth]);
+                }
+                for k in 0..edge.len() as u32 - 1 {
+                    let (top, next) = (at(edge[k as usize], 0), at(edge[k as usize + 1], 0));
+                    let (bottom, next_bottom) = (first + k, first + k + 1);
+                    skirt_indices.extend_from_slice(&[top, bottom, next, next, bottom, next_bottom]);
+                }
+            }
+        }
+
+        let morph_heights = if self.morph_range.is_some() {
+            (0..row * row)
+                .map(|v| {
+                    let (i, j) = (v % row, v / row);
+                    let height = |i: usize, j: usize| vertices[j * row + i][2];
+                    match (i % 2, j % 2) {
+                        (0, 0) => height(i, j),
+                        (1, 0) => (height(i - 1, j) + height(i + 1, j)) / 2.0,
+                        (0, _) => (height(i, j - 1) + height(i, j + 1)) / 2.0,
+                        // On the diagonal of the coarser cell
+                        _ => (height(i - 1, j - 1) + height(i + 1, j + 1)) / 2.0,
+                    }
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        LODMesh {
+            resolution: cells,
+            vertices,
+            indices,
+            skirt_indices,
+            morph_heights,
+            morph_weight: self.morph_weight(distance),
+        }
+    }
+
+    /// Build GPU-ready buffers of a loaded chunk at a LOD level
+    ///
+    /// Levels past the coarsest use the coarsest. See [`TerrainBuffers`] for the layout.
+    pub fn build_buffers(
+        &self,
+        world: &World,
+        coord: ChunkCoord,
+        level: usize,
+        neighbors: LODNeighbors,
+        format: IndexFormat,
+    ) -> Result<TerrainBuffers, SpatialError> {
+        if !world.chunks.contains_key(&coord) {
+            return Err(SpatialError::ChunkNotFound { coord });
+        }
+        let distance = self.levels.get(level).or(self.levels.last()).map_or(0.0, |l| l.distance);
+        let mesh = self.build_mesh(world, coord, distance, neighbors);
+        TerrainBuffers::from_mesh(world, coord, &mesh, format)
+    }
+
     /// Check if LOD should change for new distance
+    ///
+    /// The level shown at the old distance is only left once the new distance is past the
+    /// hysteresis.
     pub fn should_change_lod(&self, old_distance: f32, new_distance: f32) -> bool {
-        let old_lod = self.get_detail_scale(old_distance);
-        let new_lod = self.get_detail_scale(new_distance);
-        old_lod != new_lod
+        let old_level = self.level_index(old_distance);
+        old_level != self.update_level(old_level, new_distance)
+    }
+}
+
+/// LOD levels currently shown for chunks, switched with hysteresis
+#[derive(Clone, Debug, Default)]
+pub struct LODTracker {
+    levels: HashMap<ChunkCoord, usize>,
+}
+
+impl LODTracker {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self::default()
     }
+
+    /// Update the level of a chunk for its current distance
+    ///
+    /// Returns the new level index when it changed, or when the chunk was not tracked yet.
+    pub fn update(&mut self, manager: &LODManager, coord: ChunkCoord, distance: f32) -> Option<usize> {
+        match self.levels.get_mut(&coord) {
+            Some(current) => {
+                let level = manager.update_level(*current, distance);
+                if level == *current {
+                    return None;
+                }
+                *current = level;
+                Some(level)
+            }
+            None => {
+                let level = manager.level_index(distance);
+                self.levels.insert(coord, level);
+                Some(level)
+            }
+        }
+    }
+
+    /// Get the level index shown for a chunk
+    pub fn level(&self, coord: ChunkCoord) -> Option<usize> {
+        self.levels.get(&coord).copied()
+    }
+
+    /// Stop tracking a chunk, e.g. when it is unloaded
+    pub fn remove(&mut self, coord: ChunkCoord) {
+        self.levels.remove(&coord);
+    }
+
+    /// Get the number of tracked chunks
+    pub fn len(&self) -> usize {
+        self.levels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+}
+
+/// Chunk edge, walked counter-clockwise around the chunk
+#[derive(Clone, Copy)]
+enum Edge {
+    NegY,
+    PosX,
+    PosY,
+    NegX,
+}
+
+impl Edge {
+    /// Map a position `t` along the edge and an offset `o` inwards to grid coordinates
+    ///
+    /// Each edge is a rotation of the -y edge, so triangles keep their winding.
+    fn to_grid(self, cells: usize, t: usize, o: usize) -> (usize, usize) {
+        match self {
+            Edge::NegY => (t, o),
+            Edge::PosX => (cells - o, t),
+            Edge::PosY => (cells - t, cells - o),
+            Edge::NegX => (o, cells - t),
+        }
+    }
+}
+
+/// Height at a world position, read from the chunk containing it
+///
+/// Positions on the far edge of the world fall back to the last samples of `coord`.
+pub(crate) fn sample_height(world: &World, coord: ChunkCoord, x: f32, y: f32) -> f32 {
+    let containing = ChunkCoord::new((x / CHUNK_SIZE) as u32, (y / CHUNK_SIZE) as u32);
+    let (chunk_coord, chunk) = match world.chunks.get(&containing) {
+        Some(chunk) => (containing, chunk),
+        None => match world.chunks.get(&coord) {
+            Some(chunk) => (coord, chunk),
+            None => return 0.0,
+        },
+    };
+
+    let scale = HEIGHTMAP_RESOLUTION as f32 / CHUNK_SIZE;
+    let sx = (((x - chunk_coord.x as f32 * CHUNK_SIZE) * scale) as usize).min(HEIGHTMAP_RESOLUTION - 1);
+    let sy = (((y - chunk_coord.y as f32 * CHUNK_SIZE) * scale) as usize).min(HEIGHTMAP_RESOLUTION - 1);
+    chunk.get_elevation_at(sx, sy).unwrap_or(0.0)
 }
 
 impl Default for LODManager {
@@ -170,4 +553,163 @@ mod tests {
         let manager = LODManager::with_levels(levels.clone());
         assert_eq!(manager.levels().len(), 2);
     }
+
+    #[test]
+    fn test_configured_distances() {
+        let manager = LODManager::new().with_distances(&[0.0, 200.0, 400.0, 800.0]);
+        assert_eq!(manager.get_detail_scale(300.0), 0.5);
+        assert_eq!(manager.get_detail_scale(900.0), 0.125);
+
+        let manager = LODManager::new().with_distance_scale(2.0);
+        assert_eq!(manager.get_lod_level(600.0).distance, 0.0);
+        assert_eq!(manager.get_lod_level(1100.0).distance, 1024.0);
+    }
+
+    #[test]
+    fn test_hysteresis() {
+        let manager = LODManager::new().with_hysteresis(20.0);
+
+        // Crossing the 512 meter boundary only counts 20 meters past it
+        assert_eq!(manager.update_level(0, 520.0), 0);
+        assert_eq!(manager.update_level(0, 540.0), 1);
+        assert_eq!(manager.update_level(1, 500.0), 1);
+        assert_eq!(manager.update_level(1, 480.0), 0);
+        // Big jumps go straight to the right level
+        assert_eq!(manager.update_level(0, 3000.0), 3);
+        assert_eq!(manager.update_level(3, 10.0), 0);
+
+        assert!(!manager.should_change_lod(500.0, 525.0));
+        assert!(manager.should_change_lod(500.0, 540.0));
+        assert!(LODManager::new().with_hysteresis(0.0).should_change_lod(500.0, 525.0));
+    }
+
+    #[test]
+    fn test_tracker_does_not_flap() {
+        let manager = LODManager::new();
+        let mut tracker = LODTracker::new();
+        let coord = ChunkCoord::new(3, 4);
+
+        assert_eq!(tracker.update(&manager, coord, 100.0), Some(0));
+        let mut changes = 0;
+        for frame in 0..100 {
+            let distance = 512.0 + if frame % 2 == 0 { -10.0 } else { 10.0 };
+            changes += tracker.update(&manager, coord, distance).iter().count();
+        }
+        assert_eq!(changes, 0);
+        assert_eq!(tracker.update(&manager, coord, 600.0), Some(1));
+        assert_eq!(tracker.level(coord), Some(1));
+
+        tracker.remove(coord);
+        assert!(tracker.is_empty());
+    }
+
+    fn create_test_world() -> World {
+        let mut world = World::new("Test".to_string(), "game1".to_string(), 2, 2);
+        world.initialize_chunks();
+        for chunk in world.chunks.values_mut() {
+            let origin = (chunk.coord.x as f32 * CHUNK_SIZE, chunk.coord.y as f32 * CHUNK_SIZE);
+            for sy in 0..HEIGHTMAP_RESOLUTION {
+                for sx in 0..HEIGHTMAP_RESOLUTION {
+                    let (x, y) = (origin.0 + sx as f32, origin.1 + sy as f32);
+                    chunk.elevation[sy * HEIGHTMAP_RESOLUTION + sx] = 100.0 + (x * 0.05).sin() * 20.0 + y * 0.1;
+                }
+            }
+        }
+        world
+    }
+
+    /// Vertices used by triangles along the x = CHUNK_SIZE * (coord.x + 1) edge
+    fn edge_vertices(mesh: &LODMesh, x: f32) -> Vec<[u32; 3]> {
+        let mut used: Vec<[u32; 3]> = mesh
+            .indices
+            .iter()
+            .map(|&i| mesh.vertices[i as usize])
+            .filter(|v| v[0] == x)
+            .map(|v| v.map(f32::to_bits))
+            .collect();
+        used.sort_unstable();
+        used.dedup();
+        used
+    }
+
+    #[test]
+    fn test_mesh_covers_chunk() {
+        let world = create_test_world();
+        let manager = LODManager::new();
+        let mesh = manager.build_mesh(&world, ChunkCoord::new(0, 0), 2500.0, LODNeighbors::uniform(32));
+
+        assert_eq!(mesh.resolution, 32);
+        assert_eq!(mesh.vertices.len(), 33 * 33);
+        assert_eq!(mesh.indices.len(), 32 * 32 * 6);
+        assert!(mesh.skirt_indices.is_empty() && mesh.morph_heights.is_empty());
+
+        // Counter-clockwise seen from above
+        for triangle in mesh.indices.chunks(3) {
+            let [a, b, c] = [0, 1, 2].map(|k| mesh.vertices[triangle[k] as usize]);
+            let cross = (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+            assert!(cross > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_stitched_edges_match() {
+        let world = create_test_world();
+        let manager = LODManager::new();
+
+        // Fine chunk at 64 cells next to a coarse chunk at 32 cells
+        let fine = manager.build_mesh(
+            &world,
+            ChunkCoord::new(0, 0),
+            1500.0,
+            LODNeighbors {
+                pos_x: 32,
+                ..LODNeighbors::uniform(64)
+            },
+        );
+        let coarse = manager.build_mesh(
+            &world,
+            ChunkCoord::new(1, 0),
+            2500.0,
+            LODNeighbors {
+                neg_x: 64,
+                ..LODNeighbors::uniform(32)
+            },
+        );
+
+        let shared = edge_vertices(&fine, CHUNK_SIZE);
+        assert_eq!(shared.len(), 33);
+        assert_eq!(shared, edge_vertices(&coarse, CHUNK_SIZE));
+        assert_eq!(fine.indices.len() % 3, 0);
+    }
+
+    #[test]
+    fn test_skirts() {
+        let world = create_test_world();
+        let manager = LODManager::new().with_skirts(10.0);
+        let mesh = manager.build_mesh(&world, ChunkCoord::new(0, 0), 2500.0, LODNeighbors::uniform(32));
+
+        assert_eq!(mesh.vertices.len(), 33 * 33 + 4 * 33);
+        assert_eq!(mesh.skirt_indices.len(), 4 * 32 * 6);
+        let skirt = mesh.vertices[33 * 33];
+        assert_eq!(skirt[2], mesh.vertices[0][2] - 10.0);
+    }
+
+    #[test]
+    fn test_geomorphing() {
+        let world = create_test_world();
+        let manager = LODManager::new().with_geomorphing(0.25);
+
+        assert_eq!(manager.morph_weight(100.0), 0.0);
+        assert_eq!(manager.morph_weight(384.0), 0.0);
+        assert!((manager.morph_weight(448.0) - 0.5).abs() < 1e-6);
+        assert!(manager.morph_weight(511.0) < 1.0);
+        assert_eq!(manager.morph_weight(5000.0), 0.0);
+
+        let mesh = manager.build_mesh(&world, ChunkCoord::new(0, 0), 448.0, LODNeighbors::uniform(256));
+        let row = mesh.resolution + 1;
+        assert_eq!(mesh.morph_heights.len(), row * row);
+        assert_eq!(mesh.morph_heights[2 * row + 2], mesh.vertices[2 * row + 2][2]);
+        let between = (mesh.vertices[2 * row][2] + mesh.vertices[2 * row + 2][2]) / 2.0;
+        assert_eq!(mesh.morph_heights[2 * row + 1], between);
+    }
 }
@@ -0,0 +1,703 @@
+use crate::constants::*;
+use crate::{Chunk, ChunkCoord, World};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Index of a polygon within a [`NavMesh`]
+pub type PolygonId = usize;
+
+/// Edge between two navmesh polygons, as its two endpoints
+pub type Portal = ((f32, f32), (f32, f32));
+
+/// Connection from one navmesh polygon to a neighboring one
+#[derive(Clone, Debug, PartialEq)]
+pub struct NavLink {
+    /// Polygon on the other side of the portal
+    pub polygon: PolygonId,
+
+    /// Endpoints of the shared edge that can be crossed between the two polygons
+    pub portal: Portal,
+}
+
+impl NavLink {
+    /// Midpoint of the portal edge
+    pub fn midpoint(&self) -> (f32, f32) {
+        let (a, b) = self.portal;
+        ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0)
+    }
+}
+
+/// Convex walkable area of a navmesh, an axis-aligned rectangle of merged cells
+#[derive(Clone, Debug)]
+pub struct NavPolygon {
+    pub id: PolygonId,
+
+    /// Chunk the polygon was built from
+    pub chunk: ChunkCoord,
+
+    /// Minimum (x, y) corner in world coordinates
+    pub min: (f32, f32),
+
+    /// Maximum (x, y) corner in world coordinates
+    pub max: (f32, f32),
+
+    /// Average terrain height across the polygon
+    pub height: f32,
+
+    /// Polygons reachable by crossing one of this polygon's edges
+    pub links: Vec<NavLink>,
+}
+
+impl NavPolygon {
+    /// Center of the polygon in world coordinates
+    pub fn center(&self) -> (f32, f32) {
+        (
+            (self.min.0 + self.max.0) / 2.0,
+            (self.min.1 + self.max.1) / 2.0,
+        )
+    }
+
+    /// Corners of the polygon in counter-clockwise order
+    pub fn vertices(&self) -> [(f32, f32); 4] {
+        [
+            self.min,
+            (self.max.0, self.min.1),
+            self.max,
+            (self.min.0, self.max.1),
+        ]
+    }
+
+    /// Check if a point lies inside the polygon
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        x >= self.min.0 && x <= self.max.0 && y >= self.min.1 && y <= self.max.1
+    }
+}
+
+/// Per-cell walkability data gathered while building a navmesh
+#[derive(Clone, Copy)]
+struct Cell {
+    height: f32,
+    walkable: bool,
+}
+
+/// Builds a [`NavMesh`] from chunk heightmaps and structures
+///
+/// The terrain is sampled on a grid of `cell_size` meter cells. A cell is walkable when it
+/// is above the chunk's water level, its slope does not exceed `max_slope` degrees and no
+/// structure stands within `structure_radius` of it. Walkable cells whose heights differ by
+/// no more than `max_step` are merged into rectangular polygons within each chunk, and
+/// neighboring polygons are linked wherever such a step can be taken across their shared edge.
+#[derive(Clone, Debug)]
+pub struct NavMeshBuilder {
+    cell_size: f32,
+    max_slope: f32,
+    max_step: f32,
+    structure_radius: f32,
+}
+
+impl NavMeshBuilder {
+    /// Create a navmesh builder with default thresholds
+    pub fn new() -> Self {
+        Self {
+            cell_size: NAVMESH_CELL_SIZE,
+            max_slope: NAVMESH_MAX_SLOPE_DEGREES,
+            max_step: NAVMESH_MAX_STEP,
+            structure_radius: NAVMESH_STRUCTURE_RADIUS,
+        }
+    }
+
+    /// Set the size of the sampling cells in meters
+    ///
+    /// Values that don't evenly divide the chunk size are rounded so that they do.
+    pub fn with_cell_size(mut self, cell_size: f32) -> Self {
+        let cells = (CHUNK_SIZE / cell_size.max(1.0)).round().max(1.0);
+        self.cell_size = CHUNK_SIZE / cells;
+        self
+    }
+
+    /// Set the steepest walkable slope in degrees
+    pub fn with_max_slope(mut self, degrees: f32) -> Self {
+        self.max_slope = degrees.clamp(0.0, 90.0);
+        self
+    }
+
+    /// Set the largest height difference that can be stepped between neighboring cells
+    pub fn with_max_step(mut self, max_step: f32) -> Self {
+        self.max_step = max_step.max(0.0);
+        self
+    }
+
+    /// Set the radius around each structure that is excluded from the navmesh
+    pub fn with_structure_radius(mut self, radius: f32) -> Self {
+        self.structure_radius = radius.max(0.0);
+        self
+    }
+
+    /// Get the cell size in meters
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Build a navmesh covering every chunk of the world
+    pub fn build(&self, world: &World) -> NavMesh {
+        let mut coords: Vec<ChunkCoord> = world.chunks.keys().copied().collect();
+        coords.sort_by_key(|coord| (coord.x, coord.y));
+        self.build_chunks(coords.iter().filter_map(|coord| world.chunks.get(coord)))
+    }
+
+    /// Build a navmesh covering the given chunks
+    pub fn build_chunks<'a>(&self, chunks: impl IntoIterator<Item = &'a Chunk>) -> NavMesh {
+        let mut mesh = NavMesh {
+            cell_size: self.cell_size,
+            polygons: Vec::new(),
+            cells: HashMap::new(),
+        };
+        let mut heights: HashMap<(i32, i32), f32> = HashMap::new();
+
+        for chunk in chunks {
+            self.build_chunk(chunk, &mut mesh, &mut heights);
+        }
+
+        self.link_polygons(&mut mesh, &heights);
+        mesh
+    }
+
+    /// Sample a chunk into cells and merge its walkable cells into polygons
+    fn build_chunk(&self, chunk: &Chunk, mesh: &mut NavMesh, heights: &mut HashMap<(i32, i32), f32>) {
+        let cells_per_side = (CHUNK_SIZE / self.cell_size).round() as usize;
+        let origin = (
+            chunk.coord.x as f32 * CHUNK_SIZE,
+            chunk.coord.y as f32 * CHUNK_SIZE,
+        );
+
+        let mut cells = Vec::with_capacity(cells_per_side * cells_per_side);
+        for cy in 0..cells_per_side {
+            for cx in 0..cells_per_side {
+                let min = (
+                    origin.0 + cx as f32 * self.cell_size,
+                    origin.1 + cy as f32 * self.cell_size,
+                );
+                cells.push(self.sample_cell(chunk, min));
+            }
+        }
+
+        let first_cell = (
+            chunk.coord.x as i32 * cells_per_side as i32,
+            chunk.coord.y as i32 * cells_per_side as i32,
+        );
+        let mut assigned = vec![false; cells.len()];
+
+        for cy in 0..cells_per_side {
+            for cx in 0..cells_per_side {
+                let index = cy * cells_per_side + cx;
+                if assigned[index] || !cells[index].walkable {
+                    continue;
+                }
+
+                let base = cells[index].height;
+                let mergeable = |x: usize, y: usize, assigned: &[bool]| {
+                    let i = y * cells_per_side + x;
+                    !assigned[i] && cells[i].walkable && (cells[i].height - base).abs() <= self.max_step
+                };
+
+                // Grow a row to the right, then grow the rectangle downwards.
+                let mut width = 1;
+                while cx + width < cells_per_side && mergeable(cx + width, cy, &assigned) {
+                    width += 1;
+                }
+                let mut depth = 1;
+                while cy + depth < cells_per_side
+                    && (cx..cx + width).all(|x| mergeable(x, cy + depth, &assigned))
+                {
+                    depth += 1;
+                }
+
+                let id = mesh.polygons.len();
+                let mut total_height = 0.0;
+                for y in cy..cy + depth {
+                    for x in cx..cx + width {
+                        let i = y * cells_per_side + x;
+                        assigned[i] = true;
+                        total_height += cells[i].height;
+                        let key = (first_cell.0 + x as i32, first_cell.1 + y as i32);
+                        mesh.cells.insert(key, id);
+                        heights.insert(key, cells[i].height);
+                    }
+                }
+
+                mesh.polygons.push(NavPolygon {
+                    id,
+                    chunk: chunk.coord,
+                    min: (
+                        origin.0 + cx as f32 * self.cell_size,
+                        origin.1 + cy as f32 * self.cell_size,
+                    ),
+                    max: (
+                        origin.0 + (cx + width) as f32 * self.cell_size,
+                        origin.1 + (cy + depth) as f32 * self.cell_size,
+                    ),
+                    height: total_height / (width * depth) as f32,
+                    links: Vec::new(),
+                });
+            }
+        }
+    }
+
+    /// Measure the height and walkability of the cell with the given minimum corner
+    fn sample_cell(&self, chunk: &Chunk, min: (f32, f32)) -> Cell {
+        let corners = [
+            (min.0, min.1),
+            (min.0 + self.cell_size, min.1),
+            (min.0, min.1 + self.cell_size),
+            (min.0 + self.cell_size, min.1 + self.cell_size),
+        ];
+        let samples = corners.map(|(x, y)| Self::height_at(chunk, x, y));
+
+        let lowest = samples.iter().copied().fold(f32::MAX, f32::min);
+        let highest = samples.iter().copied().fold(f32::MIN, f32::max);
+        let height = samples.iter().sum::<f32>() / samples.len() as f32;
+
+        let slope = ((highest - lowest) / self.cell_size).atan().to_degrees();
+        let center = (min.0 + self.cell_size / 2.0, min.1 + self.cell_size / 2.0);
+        let blocked = chunk.structures.iter().any(|structure| {
+            let dx = structure.x - center.0;
+            let dy = structure.y - center.1;
+            (dx * dx + dy * dy).sqrt() < self.structure_radius + self.cell_size / 2.0
+        });
+
+        Cell {
+            height,
+            walkable: lowest > chunk.water_level && slope <= self.max_slope && !blocked,
+        }
+    }
+
+    /// Heightmap sample nearest to a world position, clamped to the chunk
+    fn height_at(chunk: &Chunk, x: f32, y: f32) -> f32 {
+        let scale = HEIGHTMAP_RESOLUTION as f32 / CHUNK_SIZE;
+        let local_x = (x - chunk.coord.x as f32 * CHUNK_SIZE) * scale;
+        let local_y = (y - chunk.coord.y as f32 * CHUNK_SIZE) * scale;
+        let sx = (local_x.max(0.0) as usize).min(HEIGHTMAP_RESOLUTION - 1);
+        let sy = (local_y.max(0.0) as usize).min(HEIGHTMAP_RESOLUTION - 1);
+        chunk.get_elevation_at(sx, sy).unwrap_or(0.0)
+    }
+
+    /// Link polygons whose cells touch across an edge with a walkable step
+    fn link_polygons(&self, mesh: &mut NavMesh, heights: &HashMap<(i32, i32), f32>) {
+        let mut portals: HashMap<(PolygonId, PolygonId), Portal> = HashMap::new();
+        let mut keys: Vec<&(i32, i32)> = mesh.cells.keys().collect();
+        keys.sort();
+
+        for &(x, y) in keys {
+            let id = mesh.cells[&(x, y)];
+            for (dx, dy) in [(1, 0), (0, 1)] {
+                let neighbor = (x + dx, y + dy);
+                let Some(&other) = mesh.cells.get(&neighbor) else {
+                    continue;
+                };
+                if other == id || (heights[&(x, y)] - heights[&neighbor]).abs() > self.max_step {
+                    continue;
+                }
+
+                // The edge shared by the two cells.
+                let start = (
+                    (x + dx) as f32 * self.cell_size,
+                    (y + dy) as f32 * self.cell_size,
+                );
+                let end = (
+                    start.0 + dy as f32 * self.cell_size,
+                    start.1 + dx as f32 * self.cell_size,
+                );
+
+                for key in [(id, other), (other, id)] {
+                    portals
+                        .entry(key)
+                        .and_modify(|(a, b)| {
+                            *a = (a.0.min(start.0), a.1.min(start.1));
+                            *b = (b.0.max(end.0), b.1.max(end.1));
+                        })
+                        .or_insert((start, end));
+                }
+            }
+        }
+
+        let mut portals: Vec<_> = portals.into_iter().collect();
+        portals.sort_by_key(|((from, to), _)| (*from, *to));
+        for ((from, to), portal) in portals {
+            mesh.polygons[from].links.push(NavLink { polygon: to, portal });
+        }
+    }
+}
+
+impl Default for NavMeshBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+struct NavState {
+    cost: f32,
+    polygon: PolygonId,
+}
+
+impl Eq for NavState {}
+
+impl Ord for NavState {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then_with(|| other.polygon.cmp(&self.polygon))
+    }
+}
+
+impl PartialOrd for NavState {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Polygonal navigation mesh over walkable terrain
+///
+/// Paths are searched over polygons instead of grid cells, which keeps searches over large
+/// open areas short compared to grid A*.
+#[derive(Clone, Debug)]
+pub struct NavMesh {
+    cell_size: f32,
+    polygons: Vec<NavPolygon>,
+    cells: HashMap<(i32, i32), PolygonId>,
+}
+
+impl NavMesh {
+    /// Get all polygons of the mesh
+    pub fn polygons(&self) -> &[NavPolygon] {
+        &self.polygons
+    }
+
+    /// Get a polygon by id
+    pub fn polygon(&self, id: PolygonId) -> Option<&NavPolygon> {
+        self.polygons.get(id)
+    }
+
+    /// Get the number of polygons
+    pub fn len(&self) -> usize {
+        self.polygons.len()
+    }
+
+    /// Check if the mesh has no walkable polygons
+    pub fn is_empty(&self) -> bool {
+        self.polygons.is_empty()
+    }
+
+    /// Find the polygon containing a world position
+    pub fn polygon_at(&self, x: f32, y: f32) -> Option<&NavPolygon> {
+        let cell = (
+            (x / self.cell_size).floor() as i32,
+            (y / self.cell_size).floor() as i32,
+        );
+        self.cells.get(&cell).map(|&id| &self.polygons[id])
+    }
+
+    /// Check if a world position is on the navmesh
+    pub fn is_walkable(&self, x: f32, y: f32) -> bool {
+        self.polygon_at(x, y).is_some()
+    }
+
+    /// Find path from start to goal across the navmesh
+    ///
+    /// Returns the start, the midpoint of each portal crossed and the goal.
+    /// Returns None if either point is off the mesh or the goal can't be reached.
+    pub fn find_path(&self, start: (f32, f32), goal: (f32, f32)) -> Option<Vec<(f32, f32)>> {
+        let links = self.find_links(start, goal)?;
+
+        let mut path = vec![start];
+        path.extend(links.iter().map(|(_, link)| link.midpoint()));
+        path.push(goal);
+        Some(path)
+    }
+
+    /// Find the shortest path from start to goal through the portals of the navmesh path
+    ///
+    /// The portal midpoints of [`find_path`](Self::find_path) are pulled taut with the
+    /// funnel algorithm, so the path only turns at portal corners.
+    pub fn find_smoothed_path(&self, start: (f32, f32), goal: (f32, f32)) -> Option<Vec<(f32, f32)>> {
+        let links = self.find_links(start, goal)?;
+
+        // Portal endpoints as (left, right) seen when walking from one polygon to the next.
+        let mut portals: Vec<Portal> = vec![(start, start)];
+        for (from, link) in &links {
+            let from = self.polygons[*from].center();
+            let to = self.polygons[link.polygon].center();
+            let (a, b) = link.portal;
+            if triangle_area(from, to, a) >= triangle_area(from, to, b) {
+                portals.push((a, b));
+            } else {
+                portals.push((b, a));
+            }
+        }
+        portals.push((goal, goal));
+
+        Some(string_pull(&portals))
+    }
+
+    /// A* over polygon adjacency, returning each link crossed with the polygon it leaves
+    fn find_links(&self, start: (f32, f32), goal: (f32, f32)) -> Option<Vec<(PolygonId, &NavLink)>> {
+        let start_polygon = self.polygon_at(start.0, start.1)?.id;
+        let goal_polygon = self.polygon_at(goal.0, goal.1)?.id;
+
+        // Each polygon is entered at a portal midpoint; the start polygon at the start itself.
+        let mut entry: HashMap<PolygonId, (f32, f32)> = HashMap::new();
+        let mut came_from: HashMap<PolygonId, (PolygonId, &NavLink)> = HashMap::new();
+        let mut g_score: HashMap<PolygonId, f32> = HashMap::new();
+        let mut open_set = BinaryHeap::new();
+
+        entry.insert(start_polygon, start);
+        g_score.insert(start_polygon, 0.0);
+        open_set.push(NavState {
+            cost: distance(start, goal),
+            polygon: start_polygon,
+        });
+
+        while let Some(NavState { polygon: current, .. }) = open_set.pop() {
+            if current == goal_polygon {
+                let mut links = Vec::new();
+                let mut node = current;
+                while let Some(&(prev, link)) = came_from.get(&node) {
+                    links.push((prev, link));
+                    node = prev;
+                }
+                links.reverse();
+                return Some(links);
+            }
+
+            let from = entry[&current];
+            let current_g = g_score[&current];
+
+            for link in &self.polygons[current].links {
+                let point = link.midpoint();
+                let tentative_g = current_g + distance(from, point);
+
+                if tentative_g < g_score.get(&link.polygon).copied().unwrap_or(f32::MAX) {
+                    came_from.insert(link.polygon, (current, link));
+                    entry.insert(link.polygon, point);
+                    g_score.insert(link.polygon, tentative_g);
+                    open_set.push(NavState {
+                        cost: tentative_g + distance(point, goal),
+                        polygon: link.polygon,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Twice the signed area of the triangle abc, positive when c lies left of the line ab
+fn triangle_area(a: (f32, f32), b: (f32, f32), c: (f32, f32)) -> f32 {
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
+
+/// Simple stupid funnel algorithm over (left, right) portals; the first and last portals
+/// are the start and goal points
+fn string_pull(portals: &[Portal]) -> Vec<(f32, f32)> {
+    let mut path = vec![portals[0].0];
+    let (mut apex, mut left, mut right) = (portals[0].0, portals[0].0, portals[0].1);
+    let (mut left_index, mut right_index) = (0, 0);
+
+    let mut i = 1;
+    while i < portals.len() {
+        let (portal_left, portal_right) = portals[i];
+
+        // Tighten the right side of the funnel.
+        if triangle_area(apex, right, portal_right) >= 0.0 {
+            if apex == right || triangle_area(apex, left, portal_right) < 0.0 {
+                right = portal_right;
+                right_index = i;
+            } else {
+                // The right side crossed the left one: the left corner is a waypoint.
+                path.push(left);
+                apex = left;
+                right = apex;
+                right_index = left_index;
+                i = left_index + 1;
+                continue;
+            }
+        }
+
+        // Tighten the left side of the funnel.
+        if triangle_area(apex, left, portal_left) <= 0.0 {
+            if apex == left || triangle_area(apex, right, portal_left) > 0.0 {
+                left = portal_left;
+                left_index = i;
+            } else {
+                path.push(right);
+                apex = right;
+                left = apex;
+                left_index = right_index;
+                i = right_index + 1;
+                continue;
+            }
+        }
+
+        i += 1;
+    }
+
+    let goal = portals[portals.len() - 1].0;
+    if path.last() != Some(&goal) {
+        path.push(goal);
+    }
+    path
+}
+
+fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+    let dx = b.0 - a.0;
+    let dy = b.1 - a.1;
+    (dx * dx + dy * dy).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entropic_world_core::spatial::{Structure, StructureType};
+
+    fn create_test_world(width: u32, height: u32) -> World {
+        let mut world = World::new("Test".to_string(), "game1".to_string(), width, height);
+        world.initialize_chunks();
+
+        for chunk in world.chunks.values_mut() {
+            chunk.water_level = -100.0;
+        }
+
+        world
+    }
+
+    /// Raise a ridge along world x = `x` from y = 0 up to `length` meters
+    fn raise_wall(world: &mut World, x: usize, length: usize, height: f32) {
+        let chunk = world.chunks.get_mut(&ChunkCoord::new(0, 0)).unwrap();
+        for y in 0..length.min(HEIGHTMAP_RESOLUTION) {
+            for dx in 0..4 {
+                chunk.set_elevation_at(x + dx, y, height);
+            }
+        }
+    }
+
+    #[test]
+    fn test_flat_chunk_merges_into_one_polygon() {
+        let world = create_test_world(1, 1);
+        let mesh = NavMeshBuilder::new().build(&world);
+
+        assert_eq!(mesh.len(), 1);
+        let polygon = &mesh.polygons()[0];
+        assert_eq!(polygon.min, (0.0, 0.0));
+        assert_eq!(polygon.max, (CHUNK_SIZE, CHUNK_SIZE));
+        assert!(polygon.contains(10.0, 200.0));
+    }
+
+    #[test]
+    fn test_chunks_are_linked() {
+        let world = create_test_world(2, 1);
+        let mesh = NavMeshBuilder::new().build(&world);
+
+        assert_eq!(mesh.len(), 2);
+        let link = &mesh.polygons()[0].links[0];
+        assert_eq!(link.polygon, 1);
+        assert_eq!(link.portal, ((CHUNK_SIZE, 0.0), (CHUNK_SIZE, CHUNK_SIZE)));
+
+        let path = mesh.find_path((10.0, 10.0), (400.0, 10.0)).unwrap();
+        assert_eq!(path.first(), Some(&(10.0, 10.0)));
+        assert_eq!(path.last(), Some(&(400.0, 10.0)));
+        assert_eq!(path.len(), 3);
+    }
+
+    #[test]
+    fn test_water_is_excluded() {
+        let mut world = create_test_world(1, 1);
+        world.chunks.get_mut(&ChunkCoord::new(0, 0)).unwrap().water_level = 10.0;
+
+        let mesh = NavMeshBuilder::new().build(&world);
+        assert!(mesh.is_empty());
+        assert!(mesh.find_path((1.0, 1.0), (2.0, 2.0)).is_none());
+    }
+
+    #[test]
+    fn test_structures_are_excluded() {
+        let mut world = create_test_world(1, 1);
+        let house = Structure::new("house".to_string(), StructureType::House, 100.0, 100.0, 0.0);
+        world.chunks.get_mut(&ChunkCoord::new(0, 0)).unwrap().add_structure(house);
+
+        let mesh = NavMeshBuilder::new().with_structure_radius(4.0).build(&world);
+        assert!(!mesh.is_walkable(100.0, 100.0));
+        assert!(mesh.is_walkable(20.0, 20.0));
+        assert!(mesh.find_path((20.0, 20.0), (200.0, 200.0)).is_some());
+    }
+
+    #[test]
+    fn test_steep_wall_blocks_path() {
+        let mut world = create_test_world(1, 1);
+        raise_wall(&mut world, 128, HEIGHTMAP_RESOLUTION, 100.0);
+
+        let mesh = NavMeshBuilder::new().with_cell_size(8.0).build(&world);
+        assert!(mesh.find_path((20.0, 20.0), (220.0, 20.0)).is_none());
+    }
+
+    #[test]
+    fn test_path_goes_around_wall() {
+        let mut world = create_test_world(1, 1);
+        raise_wall(&mut world, 128, 200, 100.0);
+
+        let mesh = NavMeshBuilder::new().with_cell_size(8.0).build(&world);
+        let path = mesh.find_path((20.0, 20.0), (220.0, 20.0)).unwrap();
+
+        // The path has to pass below the end of the wall.
+        assert!(path.iter().any(|&(_, y)| y >= 200.0));
+    }
+
+    #[test]
+    fn test_smoothed_path_pulls_taut() {
+        let world = create_test_world(2, 1);
+        let mesh = NavMeshBuilder::new().build(&world);
+
+        let path = mesh.find_smoothed_path((10.0, 10.0), (400.0, 30.0)).unwrap();
+        assert_eq!(path, vec![(10.0, 10.0), (400.0, 30.0)]);
+    }
+
+    #[test]
+    fn test_smoothed_path_turns_at_wall_end() {
+        let mut world = create_test_world(1, 1);
+        raise_wall(&mut world, 128, 200, 100.0);
+
+        let mesh = NavMeshBuilder::new().with_cell_size(8.0).build(&world);
+        let raw = mesh.find_path((20.0, 20.0), (220.0, 20.0)).unwrap();
+        let smoothed = mesh.find_smoothed_path((20.0, 20.0), (220.0, 20.0)).unwrap();
+
+        assert_eq!(smoothed.first(), Some(&(20.0, 20.0)));
+        assert_eq!(smoothed.last(), Some(&(220.0, 20.0)));
+        assert!(smoothed.len() <= raw.len());
+        assert!(crate::Pathfinder::path_length(&smoothed) < crate::Pathfinder::path_length(&raw));
+        // The path turns at the corners around the end of the wall.
+        assert!(smoothed[1..smoothed.len() - 1].iter().all(|&(_, y)| y >= 200.0));
+    }
+
+    #[test]
+    fn test_thresholds_control_walkability() {
+        let mut world = create_test_world(1, 1);
+        raise_wall(&mut world, 128, HEIGHTMAP_RESOLUTION, 1.0);
+
+        let strict = NavMeshBuilder::new()
+            .with_cell_size(8.0)
+            .with_max_step(0.1)
+            .with_max_slope(1.0)
+            .build(&world);
+        assert!(strict.find_path((20.0, 20.0), (220.0, 20.0)).is_none());
+
+        let lenient = NavMeshBuilder::new().with_cell_size(8.0).build(&world);
+        assert!(lenient.find_path((20.0, 20.0), (220.0, 20.0)).is_some());
+    }
+
+    #[test]
+    fn test_cell_size_divides_chunk() {
+        let builder = NavMeshBuilder::new().with_cell_size(7.0);
+        assert!((builder.cell_size() - CHUNK_SIZE / 37.0).abs() < 1e-4);
+    }
+}
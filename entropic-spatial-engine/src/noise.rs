@@ -1,9 +1,11 @@
 //! Noise functions for procedural generation
 //!
 //! This module provides convenient wrappers around the `noise` crate
-//! for terrain and biome generation.
+//! for terrain and biome generation, plus Worley, ridged multifractal, billow and
+//! domain-warping noise implementing the crate's `NoiseFn` and `Seedable` traits.
 
-use noise::{NoiseFn, Perlin, Simplex};
+use noise::{NoiseFn, Perlin, Seedable, Simplex};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 /// Noise generator using Perlin noise
@@ -113,3 +115,552 @@ impl Default for SimplexNoise {
         Self::new()
     }
 }
+
+/// Value returned by Worley noise
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WorleyReturn {
+    /// Distance to the nearest feature point
+    Distance,
+    /// Difference between the distances to the second nearest and nearest feature points,
+    /// which outlines the cells
+    Edge,
+    /// Random value of the nearest cell
+    CellValue,
+}
+
+/// Worley (cellular) noise with one feature point per cell
+///
+/// Outputs are scaled to [-1, 1].
+#[derive(Clone, Debug)]
+pub struct WorleyNoise {
+    seed: u32,
+    frequency: f64,
+    return_type: WorleyReturn,
+}
+
+impl WorleyNoise {
+    /// Create a Worley noise generator with default seed
+    pub fn new() -> Self {
+        Self::with_seed(0)
+    }
+
+    /// Create a Worley noise generator with a specific seed
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            seed,
+            frequency: 1.0,
+            return_type: WorleyReturn::Distance,
+        }
+    }
+
+    /// Set the number of cells per unit
+    pub fn with_frequency(mut self, frequency: f64) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    /// Set the value returned for each point
+    pub fn with_return_type(mut self, return_type: WorleyReturn) -> Self {
+        self.return_type = return_type;
+        self
+    }
+
+    /// Find the two nearest feature points around a point in cell space
+    ///
+    /// Returns the distances to both and the hash of the nearest cell.
+    fn nearest<const DIM: usize>(&self, point: [f64; DIM]) -> (f64, f64, u64) {
+        let cell = point.map(|v| v.floor() as i64);
+        let (mut f1, mut f2, mut nearest) = (f64::INFINITY, f64::INFINITY, 0);
+
+        // Neighbors within one cell along every axis, 3^DIM in total
+        for n in 0..3usize.pow(DIM as u32) {
+            let mut neighbor = cell;
+            let mut rest = n;
+            for axis in neighbor.iter_mut() {
+                *axis += (rest % 3) as i64 - 1;
+                rest /= 3;
+            }
+
+            let hash = cell_hash(self.seed, &neighbor);
+            let mut distance = 0.0;
+            for axis in 0..DIM {
+                let offset = mix(hash.wrapping_add(axis as u64)) as f64 / u64::MAX as f64;
+                distance += (neighbor[axis] as f64 + offset - point[axis]).powi(2);
+            }
+            let distance = distance.sqrt();
+
+            if distance < f1 {
+                f2 = f1;
+                f1 = distance;
+                nearest = hash;
+            } else if distance < f2 {
+                f2 = distance;
+            }
+        }
+
+        (f1, f2, nearest)
+    }
+
+    fn value<const DIM: usize>(&self, point: [f64; DIM]) -> f64 {
+        let (f1, f2, nearest) = self.nearest(point.map(|v| v * self.frequency));
+        let value = match self.return_type {
+            WorleyReturn::Distance => f1 * 2.0 - 1.0,
+            WorleyReturn::Edge => (f2 - f1) * 2.0 - 1.0,
+            WorleyReturn::CellValue => mix(nearest) as f64 / u64::MAX as f64 * 2.0 - 1.0,
+        };
+        value.clamp(-1.0, 1.0)
+    }
+}
+
+impl Default for WorleyNoise {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Seedable for WorleyNoise {
+    fn set_seed(self, seed: u32) -> Self {
+        Self { seed, ..self }
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed
+    }
+}
+
+impl NoiseFn<f64, 2> for WorleyNoise {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.value(point)
+    }
+}
+
+impl NoiseFn<f64, 3> for WorleyNoise {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.value(point)
+    }
+}
+
+/// Shared octave settings of the fractal noises
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Octaves {
+    count: u32,
+    persistence: f64,
+    lacunarity: f64,
+}
+
+impl Octaves {
+    const DEFAULT: Self = Self {
+        count: 6,
+        persistence: 0.5,
+        lacunarity: 2.0,
+    };
+
+    /// Sample a source at every octave, offsetting each one so their features don't line up
+    fn sum<S, const DIM: usize>(&self, source: &S, point: [f64; DIM], mut octave: impl FnMut(f64, f64) -> f64) -> f64
+    where
+        S: NoiseFn<f64, DIM>,
+    {
+        let (mut total, mut max_value) = (0.0, 0.0);
+        let (mut frequency, mut amplitude) = (1.0, 1.0);
+
+        for i in 0..self.count.max(1) {
+            let shift = i as f64 * 19.19;
+            let value = source.get(point.map(|v| v * frequency + shift));
+            total += octave(value, amplitude);
+            max_value += amplitude;
+            amplitude *= self.persistence;
+            frequency *= self.lacunarity;
+        }
+
+        total / max_value
+    }
+}
+
+/// Fractal Brownian motion over any source noise
+#[derive(Clone, Debug)]
+pub struct Fbm<S = Perlin> {
+    source: S,
+    octaves: Octaves,
+}
+
+impl<S> Fbm<S> {
+    /// Create fractal noise from a source noise
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            octaves: Octaves::DEFAULT,
+        }
+    }
+
+    /// Set the number of octaves
+    pub fn with_octaves(mut self, octaves: u32) -> Self {
+        self.octaves.count = octaves.max(1);
+        self
+    }
+
+    /// Set the amplitude falloff and frequency growth per octave
+    pub fn with_persistence(mut self, persistence: f64, lacunarity: f64) -> Self {
+        self.octaves.persistence = persistence;
+        self.octaves.lacunarity = lacunarity;
+        self
+    }
+}
+
+impl<S: Seedable> Seedable for Fbm<S> {
+    fn set_seed(self, seed: u32) -> Self {
+        Self {
+            source: self.source.set_seed(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.source.seed()
+    }
+}
+
+impl<S: NoiseFn<f64, 2>> NoiseFn<f64, 2> for Fbm<S> {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.octaves.sum(&self.source, point, |value, amplitude| value * amplitude)
+    }
+}
+
+impl<S: NoiseFn<f64, 3>> NoiseFn<f64, 3> for Fbm<S> {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.octaves.sum(&self.source, point, |value, amplitude| value * amplitude)
+    }
+}
+
+/// Ridged multifractal noise, sharp crests for mountain ranges
+///
+/// Each octave is weighted by the previous one, so detail gathers along the ridges.
+#[derive(Clone, Debug)]
+pub struct RidgedMultifractal<S = Perlin> {
+    source: S,
+    octaves: Octaves,
+    gain: f64,
+}
+
+impl<S> RidgedMultifractal<S> {
+    /// Create ridged noise from a source noise
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            octaves: Octaves::DEFAULT,
+            gain: 2.0,
+        }
+    }
+
+    /// Set the number of octaves
+    pub fn with_octaves(mut self, octaves: u32) -> Self {
+        self.octaves.count = octaves.max(1);
+        self
+    }
+
+    /// Set the amplitude falloff and frequency growth per octave
+    pub fn with_persistence(mut self, persistence: f64, lacunarity: f64) -> Self {
+        self.octaves.persistence = persistence;
+        self.octaves.lacunarity = lacunarity;
+        self
+    }
+
+    /// Set how strongly each octave is weighted by the previous one
+    pub fn with_gain(mut self, gain: f64) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    fn value<const DIM: usize>(&self, point: [f64; DIM]) -> f64
+    where
+        S: NoiseFn<f64, DIM>,
+    {
+        let mut weight = 1.0;
+        let ridged = self.octaves.sum(&self.source, point, |value, amplitude| {
+            let signal = (1.0 - value.abs()).powi(2) * weight;
+            weight = (signal * self.gain).clamp(0.0, 1.0);
+            signal * amplitude
+        });
+        ridged * 2.0 - 1.0
+    }
+}
+
+impl RidgedMultifractal<Perlin> {
+    /// Create ridged Perlin noise with a specific seed
+    pub fn with_seed(seed: u32) -> Self {
+        Self::new(Perlin::new(seed))
+    }
+}
+
+impl<S: Seedable> Seedable for RidgedMultifractal<S> {
+    fn set_seed(self, seed: u32) -> Self {
+        Self {
+            source: self.source.set_seed(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.source.seed()
+    }
+}
+
+impl<S: NoiseFn<f64, 2>> NoiseFn<f64, 2> for RidgedMultifractal<S> {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.value(point)
+    }
+}
+
+impl<S: NoiseFn<f64, 3>> NoiseFn<f64, 3> for RidgedMultifractal<S> {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.value(point)
+    }
+}
+
+/// Billow noise, rounded lumps for hills and clouds
+#[derive(Clone, Debug)]
+pub struct Billow<S = Perlin> {
+    source: S,
+    octaves: Octaves,
+}
+
+impl<S> Billow<S> {
+    /// Create billow noise from a source noise
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            octaves: Octaves::DEFAULT,
+        }
+    }
+
+    /// Set the number of octaves
+    pub fn with_octaves(mut self, octaves: u32) -> Self {
+        self.octaves.count = octaves.max(1);
+        self
+    }
+
+    /// Set the amplitude falloff and frequency growth per octave
+    pub fn with_persistence(mut self, persistence: f64, lacunarity: f64) -> Self {
+        self.octaves.persistence = persistence;
+        self.octaves.lacunarity = lacunarity;
+        self
+    }
+
+    fn value<const DIM: usize>(&self, point: [f64; DIM]) -> f64
+    where
+        S: NoiseFn<f64, DIM>,
+    {
+        self.octaves
+            .sum(&self.source, point, |value, amplitude| (value.abs() * 2.0 - 1.0) * amplitude)
+    }
+}
+
+impl Billow<Perlin> {
+    /// Create billow Perlin noise with a specific seed
+    pub fn with_seed(seed: u32) -> Self {
+        Self::new(Perlin::new(seed))
+    }
+}
+
+impl<S: Seedable> Seedable for Billow<S> {
+    fn set_seed(self, seed: u32) -> Self {
+        Self {
+            source: self.source.set_seed(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.source.seed()
+    }
+}
+
+impl<S: NoiseFn<f64, 2>> NoiseFn<f64, 2> for Billow<S> {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.value(point)
+    }
+}
+
+impl<S: NoiseFn<f64, 3>> NoiseFn<f64, 3> for Billow<S> {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.value(point)
+    }
+}
+
+/// Domain warping, offsetting the input of a noise by another noise
+///
+/// Each axis is displaced by the warp noise sampled at a shifted position, which bends
+/// straight features into swirls and folds.
+#[derive(Clone, Debug)]
+pub struct DomainWarp<S, W = Perlin> {
+    source: S,
+    warp: W,
+    strength: f64,
+    frequency: f64,
+}
+
+impl<S, W> DomainWarp<S, W> {
+    /// Warp a source noise by a warp noise
+    pub fn new(source: S, warp: W) -> Self {
+        Self {
+            source,
+            warp,
+            strength: 1.0,
+            frequency: 1.0,
+        }
+    }
+
+    /// Set the largest displacement along each axis
+    pub fn with_strength(mut self, strength: f64) -> Self {
+        self.strength = strength;
+        self
+    }
+
+    /// Set the frequency the warp noise is sampled at
+    pub fn with_frequency(mut self, frequency: f64) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    fn warped<const DIM: usize>(&self, point: [f64; DIM]) -> [f64; DIM]
+    where
+        W: NoiseFn<f64, DIM>,
+    {
+        const SHIFTS: [f64; 3] = [0.0, 5.2, 13.7];
+
+        let mut warped = point;
+        for (axis, value) in warped.iter_mut().enumerate() {
+            let sample = point.map(|v| v * self.frequency + SHIFTS[axis % SHIFTS.len()]);
+            *value += self.warp.get(sample) * self.strength;
+        }
+        warped
+    }
+}
+
+impl<S> DomainWarp<S, Perlin> {
+    /// Warp a source noise by Perlin noise with a specific seed
+    pub fn with_seed(source: S, seed: u32) -> Self {
+        Self::new(source, Perlin::new(seed))
+    }
+}
+
+impl<S: Seedable, W: Seedable> Seedable for DomainWarp<S, W> {
+    /// Seed the source, and the warp with the next seed so the two don't correlate
+    fn set_seed(self, seed: u32) -> Self {
+        Self {
+            source: self.source.set_seed(seed),
+            warp: self.warp.set_seed(seed.wrapping_add(1)),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.source.seed()
+    }
+}
+
+impl<S: NoiseFn<f64, 2>, W: NoiseFn<f64, 2>> NoiseFn<f64, 2> for DomainWarp<S, W> {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.source.get(self.warped(point))
+    }
+}
+
+impl<S: NoiseFn<f64, 3>, W: NoiseFn<f64, 3>> NoiseFn<f64, 3> for DomainWarp<S, W> {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.source.get(self.warped(point))
+    }
+}
+
+/// Hash the coordinates of a cell with a seed
+fn cell_hash(seed: u32, cell: &[i64]) -> u64 {
+    cell.iter()
+        .fold(mix(seed as u64), |hash, &v| mix(hash ^ (v as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)))
+}
+
+/// SplitMix64 finalizer
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> impl Iterator<Item = [f64; 2]> {
+        (0..40).flat_map(|i| (0..40).map(move |j| [i as f64 * 0.137, j as f64 * 0.219]))
+    }
+
+    fn assert_in_range<N: NoiseFn<f64, 2>>(noise: &N) {
+        let values: Vec<f64> = grid().map(|p| noise.get(p)).collect();
+        assert!(values.iter().all(|v| (-1.0..=1.0).contains(v)));
+        // Not constant
+        let min = values.iter().copied().fold(f64::MAX, f64::min);
+        let max = values.iter().copied().fold(f64::MIN, f64::max);
+        assert!(max - min > 0.3, "range {} to {}", min, max);
+    }
+
+    #[test]
+    fn test_noise_ranges() {
+        assert_in_range(&WorleyNoise::with_seed(1));
+        assert_in_range(&WorleyNoise::with_seed(1).with_return_type(WorleyReturn::Edge));
+        assert_in_range(&WorleyNoise::with_seed(1).with_return_type(WorleyReturn::CellValue));
+        assert_in_range(&RidgedMultifractal::with_seed(2));
+        assert_in_range(&Billow::with_seed(3));
+        assert_in_range(&DomainWarp::with_seed(Perlin::new(4), 5).with_strength(0.5));
+    }
+
+    #[test]
+    fn test_seeded() {
+        let a = WorleyNoise::with_seed(7);
+        let b = WorleyNoise::new().set_seed(7);
+        let c = WorleyNoise::with_seed(8);
+
+        assert_eq!(b.seed(), 7);
+        assert!(grid().all(|p| a.get(p) == b.get(p)));
+        assert!(grid().any(|p| a.get(p) != c.get(p)));
+
+        let ridged = RidgedMultifractal::with_seed(1).set_seed(9);
+        assert_eq!(ridged.seed(), 9);
+        assert!(grid().all(|p| ridged.get(p) == RidgedMultifractal::with_seed(9).get(p)));
+    }
+
+    #[test]
+    fn test_worley_cells() {
+        let noise = WorleyNoise::with_seed(3).with_return_type(WorleyReturn::CellValue);
+        let point = [10.3, 4.7];
+        // Nearby points mostly share their cell value
+        let same = (0..10)
+            .filter(|i| noise.get([point[0] + *i as f64 * 0.001, point[1]]) == noise.get(point))
+            .count();
+        assert!(same >= 9);
+
+        let noise3 = WorleyNoise::with_seed(3);
+        let value: f64 = noise3.get([1.5, 2.5, 3.5]);
+        assert!((-1.0..=1.0).contains(&value));
+    }
+
+    #[test]
+    fn test_domain_warp() {
+        let source = Perlin::new(11);
+        let unwarped = DomainWarp::with_seed(source, 12).with_strength(0.0);
+        let warped = DomainWarp::with_seed(source, 12).with_strength(2.0);
+
+        assert!(grid().all(|p| unwarped.get(p) == source.get(p)));
+        assert!(grid().any(|p| (warped.get(p) - source.get(p)).abs() > 0.1));
+
+        let seeded = warped.set_seed(20);
+        assert_eq!(seeded.seed(), 20);
+    }
+
+    #[test]
+    fn test_ridged_and_billow_differ() {
+        let ridged = RidgedMultifractal::with_seed(5).with_octaves(4);
+        let billow = Billow::with_seed(5).with_octaves(4);
+
+        assert!(grid().any(|p| (ridged.get(p) - billow.get(p)).abs() > 0.1));
+        // Composes with other sources
+        let ridged_worley = RidgedMultifractal::new(WorleyNoise::with_seed(5)).with_octaves(3);
+        assert_in_range(&ridged_worley);
+    }
+}
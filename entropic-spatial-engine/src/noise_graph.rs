@@ -0,0 +1,476 @@
+//! Declarative noise composition
+//!
+//! A [`NoiseGraph`] describes noise as a tree of nodes: sources, fractal layers, operators and
+//! warps. Graphs are plain data, so a terrain recipe can be stored as JSON next to the world
+//! seed and rebuilt into the same noise later. Every source takes its seed from the graph seed
+//! plus its own offset, so one recipe gives a different world for every seed.
+
+use crate::errors::SpatialError;
+use crate::noise::{Billow, DomainWarp, Fbm, RidgedMultifractal, WorleyNoise, WorleyReturn};
+use noise::{NoiseFn, Perlin, Simplex};
+use serde::{Deserialize, Serialize};
+
+/// Node of a noise graph
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum NoiseNode {
+    /// Perlin noise
+    Perlin {
+        #[serde(default)]
+        seed_offset: u32,
+    },
+    /// Simplex noise
+    Simplex {
+        #[serde(default)]
+        seed_offset: u32,
+    },
+    /// Worley (cellular) noise
+    Worley {
+        #[serde(default)]
+        seed_offset: u32,
+        return_type: WorleyReturn,
+    },
+    /// Same value everywhere
+    Constant { value: f64 },
+    /// Fractal Brownian motion of a source
+    Fbm {
+        source: Box<NoiseNode>,
+        octaves: u32,
+        persistence: f64,
+        lacunarity: f64,
+    },
+    /// Ridged multifractal of a source
+    Ridged {
+        source: Box<NoiseNode>,
+        octaves: u32,
+        persistence: f64,
+        lacunarity: f64,
+        gain: f64,
+    },
+    /// Billow of a source
+    Billow {
+        source: Box<NoiseNode>,
+        octaves: u32,
+        persistence: f64,
+        lacunarity: f64,
+    },
+    /// Sample a source at a scaled position
+    Frequency { source: Box<NoiseNode>, frequency: f64 },
+    /// Multiply the output of a source and add a bias
+    ScaleBias {
+        source: Box<NoiseNode>,
+        scale: f64,
+        bias: f64,
+    },
+    /// Clamp the output of a source
+    Clamp { source: Box<NoiseNode>, min: f64, max: f64 },
+    /// Sum of the sources
+    Add { sources: Vec<NoiseNode> },
+    /// Product of the sources
+    Multiply { sources: Vec<NoiseNode> },
+    /// Smallest output of the sources
+    Min { sources: Vec<NoiseNode> },
+    /// Largest output of the sources
+    Max { sources: Vec<NoiseNode> },
+    /// Offset the input of a source by a warp noise
+    Warp {
+        source: Box<NoiseNode>,
+        warp: Box<NoiseNode>,
+        strength: f64,
+        frequency: f64,
+    },
+}
+
+impl NoiseNode {
+    /// Perlin noise seeded with the graph seed plus an offset
+    pub fn perlin(seed_offset: u32) -> Self {
+        NoiseNode::Perlin { seed_offset }
+    }
+
+    /// Simplex noise seeded with the graph seed plus an offset
+    pub fn simplex(seed_offset: u32) -> Self {
+        NoiseNode::Simplex { seed_offset }
+    }
+
+    /// Worley noise seeded with the graph seed plus an offset
+    pub fn worley(seed_offset: u32, return_type: WorleyReturn) -> Self {
+        NoiseNode::Worley {
+            seed_offset,
+            return_type,
+        }
+    }
+
+    pub fn constant(value: f64) -> Self {
+        NoiseNode::Constant { value }
+    }
+
+    pub fn add(sources: Vec<NoiseNode>) -> Self {
+        NoiseNode::Add { sources }
+    }
+
+    pub fn multiply(sources: Vec<NoiseNode>) -> Self {
+        NoiseNode::Multiply { sources }
+    }
+
+    pub fn min(sources: Vec<NoiseNode>) -> Self {
+        NoiseNode::Min { sources }
+    }
+
+    pub fn max(sources: Vec<NoiseNode>) -> Self {
+        NoiseNode::Max { sources }
+    }
+
+    /// Layer octaves of this node as fractal Brownian motion
+    pub fn fbm(self, octaves: u32, persistence: f64, lacunarity: f64) -> Self {
+        NoiseNode::Fbm {
+            source: Box::new(self),
+            octaves,
+            persistence,
+            lacunarity,
+        }
+    }
+
+    /// Layer octaves of this node as a ridged multifractal
+    pub fn ridged(self, octaves: u32, persistence: f64, lacunarity: f64, gain: f64) -> Self {
+        NoiseNode::Ridged {
+            source: Box::new(self),
+            octaves,
+            persistence,
+            lacunarity,
+            gain,
+        }
+    }
+
+    /// Layer octaves of this node as billows
+    pub fn billow(self, octaves: u32, persistence: f64, lacunarity: f64) -> Self {
+        NoiseNode::Billow {
+            source: Box::new(self),
+            octaves,
+            persistence,
+            lacunarity,
+        }
+    }
+
+    /// Sample this node at a scaled position
+    pub fn frequency(self, frequency: f64) -> Self {
+        NoiseNode::Frequency {
+            source: Box::new(self),
+            frequency,
+        }
+    }
+
+    /// Multiply the output of this node and add a bias
+    pub fn scale_bias(self, scale: f64, bias: f64) -> Self {
+        NoiseNode::ScaleBias {
+            source: Box::new(self),
+            scale,
+            bias,
+        }
+    }
+
+    /// Clamp the output of this node
+    pub fn clamp(self, min: f64, max: f64) -> Self {
+        NoiseNode::Clamp {
+            source: Box::new(self),
+            min,
+            max,
+        }
+    }
+
+    /// Offset the input of this node by a warp noise sampled at a frequency
+    pub fn warp(self, warp: NoiseNode, strength: f64, frequency: f64) -> Self {
+        NoiseNode::Warp {
+            source: Box::new(self),
+            warp: Box::new(warp),
+            strength,
+            frequency,
+        }
+    }
+
+    /// Build the node into evaluable noise
+    fn compile(&self, seed: u32) -> Compiled {
+        let boxed = |node: &NoiseNode| Box::new(node.compile(seed));
+        let all = |nodes: &[NoiseNode]| nodes.iter().map(|node| node.compile(seed)).collect();
+
+        match self {
+            NoiseNode::Perlin { seed_offset } => Compiled::Perlin(Perlin::new(seed.wrapping_add(*seed_offset))),
+            NoiseNode::Simplex { seed_offset } => Compiled::Simplex(Simplex::new(seed.wrapping_add(*seed_offset))),
+            NoiseNode::Worley {
+                seed_offset,
+                return_type,
+            } => Compiled::Worley(WorleyNoise::with_seed(seed.wrapping_add(*seed_offset)).with_return_type(*return_type)),
+            NoiseNode::Constant { value } => Compiled::Constant(*value),
+            NoiseNode::Fbm {
+                source,
+                octaves,
+                persistence,
+                lacunarity,
+            } => Compiled::Fbm(
+                Fbm::new(boxed(source))
+                    .with_octaves(*octaves)
+                    .with_persistence(*persistence, *lacunarity),
+            ),
+            NoiseNode::Ridged {
+                source,
+                octaves,
+                persistence,
+                lacunarity,
+                gain,
+            } => Compiled::Ridged(
+                RidgedMultifractal::new(boxed(source))
+                    .with_octaves(*octaves)
+                    .with_persistence(*persistence, *lacunarity)
+                    .with_gain(*gain),
+            ),
+            NoiseNode::Billow {
+                source,
+                octaves,
+                persistence,
+                lacunarity,
+            } => Compiled::Billow(
+                Billow::new(boxed(source))
+                    .with_octaves(*octaves)
+                    .with_persistence(*persistence, *lacunarity),
+            ),
+            NoiseNode::Frequency { source, frequency } => Compiled::Frequency(boxed(source), *frequency),
+            NoiseNode::ScaleBias { source, scale, bias } => Compiled::ScaleBias(boxed(source), *scale, *bias),
+            NoiseNode::Clamp { source, min, max } => Compiled::Clamp(boxed(source), *min, max.max(*min)),
+            NoiseNode::Add { sources } => Compiled::Add(all(sources)),
+            NoiseNode::Multiply { sources } => Compiled::Multiply(all(sources)),
+            NoiseNode::Min { sources } => Compiled::Min(all(sources)),
+            NoiseNode::Max { sources } => Compiled::Max(all(sources)),
+            NoiseNode::Warp {
+                source,
+                warp,
+                strength,
+                frequency,
+            } => Compiled::Warp(
+                DomainWarp::new(boxed(source), boxed(warp))
+                    .with_strength(*strength)
+                    .with_frequency(*frequency),
+            ),
+        }
+    }
+}
+
+/// Seeded noise recipe
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NoiseGraph {
+    seed: u32,
+    root: NoiseNode,
+}
+
+impl NoiseGraph {
+    /// Create a graph from a seed and its root node
+    pub fn new(seed: u32, root: NoiseNode) -> Self {
+        Self { seed, root }
+    }
+
+    /// Recipe of the default terrain: Perlin fBm over world meters, from -1 to 1
+    pub fn terrain(seed: u32) -> Self {
+        use crate::constants::{TERRAIN_NOISE_FREQUENCY, TERRAIN_NOISE_OCTAVES};
+
+        Self::new(
+            seed,
+            NoiseNode::perlin(0)
+                .fbm(TERRAIN_NOISE_OCTAVES, 0.5, 2.0)
+                .frequency(TERRAIN_NOISE_FREQUENCY),
+        )
+    }
+
+    /// Use the same recipe with another seed
+    pub fn with_seed(mut self, seed: u32) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    pub fn root(&self) -> &NoiseNode {
+        &self.root
+    }
+
+    /// Build the graph into evaluable noise
+    pub fn build(&self) -> CompiledNoise {
+        CompiledNoise {
+            root: self.root.compile(self.seed),
+        }
+    }
+
+    /// Serialize the graph to JSON
+    pub fn to_json(&self) -> Result<String, SpatialError> {
+        serde_json::to_string_pretty(self).map_err(|e| SpatialError::SerializationError {
+            message: e.to_string(),
+        })
+    }
+
+    /// Deserialize a graph from JSON
+    pub fn from_json(json: &str) -> Result<Self, SpatialError> {
+        serde_json::from_str(json).map_err(|e| SpatialError::DeserializationError {
+            message: e.to_string(),
+        })
+    }
+}
+
+/// Noise built from a [`NoiseGraph`]
+#[derive(Clone, Debug)]
+pub struct CompiledNoise {
+    root: Compiled,
+}
+
+impl NoiseFn<f64, 2> for CompiledNoise {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.root.get(point)
+    }
+}
+
+impl NoiseFn<f64, 3> for CompiledNoise {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.root.get(point)
+    }
+}
+
+/// Evaluable form of a node
+#[derive(Clone, Debug)]
+enum Compiled {
+    Perlin(Perlin),
+    Simplex(Simplex),
+    Worley(WorleyNoise),
+    Constant(f64),
+    Fbm(Fbm<Box<Compiled>>),
+    Ridged(RidgedMultifractal<Box<Compiled>>),
+    Billow(Billow<Box<Compiled>>),
+    Frequency(Box<Compiled>, f64),
+    ScaleBias(Box<Compiled>, f64, f64),
+    Clamp(Box<Compiled>, f64, f64),
+    Add(Vec<Compiled>),
+    Multiply(Vec<Compiled>),
+    Min(Vec<Compiled>),
+    Max(Vec<Compiled>),
+    Warp(DomainWarp<Box<Compiled>, Box<Compiled>>),
+}
+
+impl Compiled {
+    fn value<const DIM: usize>(&self, point: [f64; DIM]) -> f64
+    where
+        Compiled: NoiseFn<f64, DIM>,
+        Perlin: NoiseFn<f64, DIM>,
+        Simplex: NoiseFn<f64, DIM>,
+        WorleyNoise: NoiseFn<f64, DIM>,
+        Fbm<Box<Compiled>>: NoiseFn<f64, DIM>,
+        RidgedMultifractal<Box<Compiled>>: NoiseFn<f64, DIM>,
+        Billow<Box<Compiled>>: NoiseFn<f64, DIM>,
+        DomainWarp<Box<Compiled>, Box<Compiled>>: NoiseFn<f64, DIM>,
+    {
+        let fold = |nodes: &[Compiled], f: fn(f64, f64) -> f64| {
+            nodes.iter().map(|node| node.get(point)).reduce(f).unwrap_or(0.0)
+        };
+
+        match self {
+            Compiled::Perlin(noise) => noise.get(point),
+            Compiled::Simplex(noise) => noise.get(point),
+            Compiled::Worley(noise) => noise.get(point),
+            Compiled::Constant(value) => *value,
+            Compiled::Fbm(noise) => noise.get(point),
+            Compiled::Ridged(noise) => noise.get(point),
+            Compiled::Billow(noise) => noise.get(point),
+            Compiled::Frequency(source, frequency) => source.get(point.map(|v| v * frequency)),
+            Compiled::ScaleBias(source, scale, bias) => source.get(point) * scale + bias,
+            Compiled::Clamp(source, min, max) => source.get(point).clamp(*min, *max),
+            Compiled::Add(sources) => fold(sources, |a, b| a + b),
+            Compiled::Multiply(sources) => fold(sources, |a, b| a * b),
+            Compiled::Min(sources) => fold(sources, f64::min),
+            Compiled::Max(sources) => fold(sources, f64::max),
+            Compiled::Warp(noise) => noise.get(point),
+        }
+    }
+}
+
+impl NoiseFn<f64, 2> for Compiled {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.value(point)
+    }
+}
+
+impl NoiseFn<f64, 3> for Compiled {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.value(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mountains() -> NoiseNode {
+        NoiseNode::add(vec![
+            NoiseNode::perlin(0).fbm(4, 0.5, 2.0).scale_bias(0.6, 0.0),
+            NoiseNode::perlin(1).ridged(4, 0.5, 2.0, 2.0).scale_bias(0.4, 0.0),
+        ])
+        .warp(NoiseNode::simplex(2), 0.3, 0.5)
+        .frequency(0.01)
+        .clamp(-1.0, 1.0)
+    }
+
+    #[test]
+    fn test_operators() {
+        let graph = NoiseGraph::new(
+            1,
+            NoiseNode::max(vec![
+                NoiseNode::constant(0.25),
+                NoiseNode::multiply(vec![NoiseNode::constant(2.0), NoiseNode::constant(-3.0)]),
+            ]),
+        );
+        assert_eq!(graph.build().get([3.0, 4.0]), 0.25);
+
+        let graph = NoiseGraph::new(1, NoiseNode::min(vec![NoiseNode::constant(0.25), NoiseNode::constant(-0.5)]));
+        assert_eq!(graph.build().get([0.0, 0.0, 0.0]), -0.5);
+
+        let graph = NoiseGraph::new(1, NoiseNode::constant(3.0).scale_bias(2.0, 1.0).clamp(0.0, 5.0));
+        assert_eq!(graph.build().get([0.0, 0.0]), 5.0);
+    }
+
+    #[test]
+    fn test_generator_uses_graph() {
+        use crate::{ChunkCoord, TerrainGenerator, HEIGHTMAP_RESOLUTION};
+
+        let flat = TerrainGenerator::with_seed(3).with_noise_graph(NoiseGraph::new(3, NoiseNode::constant(0.5)));
+        let chunk = flat.generate_chunk(ChunkCoord::new(1, 1)).unwrap();
+        assert!(chunk.elevation.iter().all(|&h| h == 191.25));
+
+        let recipe = TerrainGenerator::with_seed(3).with_noise_graph(NoiseGraph::terrain(3));
+        let chunk = recipe.generate_chunk(ChunkCoord::new(1, 1)).unwrap();
+        assert_eq!(chunk.elevation.len(), HEIGHTMAP_RESOLUTION * HEIGHTMAP_RESOLUTION);
+        assert!(chunk.elevation.iter().all(|&h| (0.0..=255.0).contains(&h)));
+        assert_eq!(recipe.noise_graph(), Some(&NoiseGraph::terrain(3)));
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let graph = NoiseGraph::new(7, mountains());
+        let json = graph.to_json().unwrap();
+        assert!(json.contains("\"op\": \"ridged\""));
+
+        let restored = NoiseGraph::from_json(&json).unwrap();
+        assert_eq!(restored, graph);
+        let (a, b) = (graph.build(), restored.build());
+        for i in 0..50 {
+            let point = [i as f64 * 13.7, i as f64 * -7.3];
+            assert_eq!(a.get(point), b.get(point));
+        }
+
+        assert!(NoiseGraph::from_json("{\"seed\": 1, \"root\": {\"op\": \"spline\"}}").is_err());
+    }
+
+    #[test]
+    fn test_seed_changes_output() {
+        let a = NoiseGraph::new(7, mountains()).build();
+        let b = NoiseGraph::new(7, mountains()).with_seed(8).build();
+
+        let points: Vec<[f64; 2]> = (0..50).map(|i| [i as f64 * 31.0, i as f64 * 17.0]).collect();
+        assert!(points.iter().any(|&p| a.get(p) != b.get(p)));
+        assert!(points.iter().all(|&p| (-1.0..=1.0).contains(&a.get(p))));
+    }
+}
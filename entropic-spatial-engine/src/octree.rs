@@ -1,8 +1,17 @@
 //! Octree spatial index for 3D spatial queries
 //!
 //! This module is only available when the "octree" feature is enabled.
+//!
+//! The tree is dynamic, so moving entities can live in it: objects are moved and removed by
+//! id as well as inserted. Leaves split once they hold more than `max_objects`, branches merge
+//! back into a leaf once they hold no more than the merge threshold, and the root grows
+//! outwards to take in objects outside of it. Moves staying within their leaf only update the
+//! stored position.
 
+use crate::constants::OCTREE_MAX_DEPTH;
 use crate::errors::SpatialError;
+use crate::World;
+use std::collections::HashMap;
 
 /// Octree node
 #[derive(Debug)]
@@ -11,11 +20,63 @@ pub enum OctreeNode {
     Internal {
         bounds: Bounds,
         children: [Box<OctreeNode>; 8],
+        /// Number of objects held by the children
+        len: usize,
     },
 }
 
+impl OctreeNode {
+    fn empty(bounds: Bounds) -> Self {
+        OctreeNode::Leaf {
+            bounds,
+            data: Vec::new(),
+        }
+    }
+
+    /// Get the bounds of the node
+    pub fn bounds(&self) -> Bounds {
+        match self {
+            OctreeNode::Leaf { bounds, .. } | OctreeNode::Internal { bounds, .. } => *bounds,
+        }
+    }
+
+    /// Get the number of objects under the node
+    pub fn len(&self) -> usize {
+        match self {
+            OctreeNode::Leaf { data, .. } => data.len(),
+            OctreeNode::Internal { len, .. } => *len,
+        }
+    }
+
+    /// Check if no objects are under the node
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the depth of the deepest leaf under the node, zero for a leaf
+    pub fn depth(&self) -> usize {
+        match self {
+            OctreeNode::Leaf { .. } => 0,
+            OctreeNode::Internal { children, .. } => {
+                1 + children.iter().map(|child| child.depth()).max().unwrap_or(0)
+            }
+        }
+    }
+
+    fn drain_into(self, out: &mut Vec<(f32, f32, f32, String)>) {
+        match self {
+            OctreeNode::Leaf { data, .. } => out.extend(data),
+            OctreeNode::Internal { children, .. } => {
+                for child in children {
+                    (*child).drain_into(out);
+                }
+            }
+        }
+    }
+}
+
 /// Bounding box
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Bounds {
     pub min_x: f32,
     pub min_y: f32,
@@ -45,6 +106,13 @@ impl Bounds {
         z >= self.min_z && z <= self.max_z
     }
 
+    /// Check if bounds overlap other bounds, touching included
+    pub fn intersects(&self, other: &Bounds) -> bool {
+        self.min_x <= other.max_x && other.min_x <= self.max_x &&
+        self.min_y <= other.max_y && other.min_y <= self.max_y &&
+        self.min_z <= other.max_z && other.min_z <= self.max_z
+    }
+
     /// Check if bounds intersects sphere
     pub fn intersects_sphere(&self, cx: f32, cy: f32, cz: f32, radius: f32) -> bool {
         // Find closest point on box to sphere center
@@ -58,6 +126,21 @@ impl Bounds {
 
         (dx * dx + dy * dy + dz * dz) <= radius * radius
     }
+
+    /// Check if a point is inside, excluding the max faces shared with the next node over
+    fn holds(&self, x: f32, y: f32, z: f32) -> bool {
+        x >= self.min_x && x < self.max_x &&
+        y >= self.min_y && y < self.max_y &&
+        z >= self.min_z && z < self.max_z
+    }
+
+    fn center(&self) -> (f32, f32, f32) {
+        (
+            (self.min_x + self.max_x) / 2.0,
+            (self.min_y + self.max_y) / 2.0,
+            (self.min_z + self.max_z) / 2.0,
+        )
+    }
 }
 
 /// Octree spatial index
@@ -65,29 +148,143 @@ impl Bounds {
 pub struct Octree {
     root: Option<OctreeNode>,
     max_objects: usize,
+    merge_threshold: usize,
+    max_depth: usize,
+    bounds: Bounds,
+    positions: HashMap<String, (f32, f32, f32)>,
 }
 
 impl Octree {
     /// Create new octree
+    ///
+    /// Leaves split past `max_objects` objects, and branches merge back at half of that.
     pub fn new(max_objects: usize) -> Self {
+        let max_objects = max_objects.max(1);
         Self {
             root: None,
             max_objects,
+            merge_threshold: max_objects / 2,
+            max_depth: OCTREE_MAX_DEPTH,
+            bounds: Bounds::new(-1000.0, -1000.0, -1000.0, 1000.0, 1000.0, 1000.0),
+            positions: HashMap::new(),
         }
     }
 
+    /// Set how many objects a branch may hold before it merges back into a leaf
+    ///
+    /// Capped at `max_objects`, so merged leaves never need splitting right away.
+    pub fn with_merge_threshold(mut self, threshold: usize) -> Self {
+        self.merge_threshold = threshold.min(self.max_objects);
+        self
+    }
+
+    /// Set how deep leaves may split
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self.rebuild();
+        self
+    }
+
+    /// Set the bounds the root starts from, before growing to fit objects outside of them
+    pub fn with_bounds(mut self, bounds: Bounds) -> Self {
+        self.bounds = bounds;
+        self.rebuild();
+        self
+    }
+
+    /// Get the number of objects in the tree
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Check if the tree holds no objects
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Check if an object is in the tree
+    pub fn contains(&self, id: &str) -> bool {
+        self.positions.contains_key(id)
+    }
+
+    /// Get the position of an object
+    pub fn position(&self, id: &str) -> Option<(f32, f32, f32)> {
+        self.positions.get(id).copied()
+    }
+
+    /// Get the depth of the deepest leaf, zero for an empty tree or a single leaf
+    pub fn depth(&self) -> usize {
+        self.root.as_ref().map_or(0, OctreeNode::depth)
+    }
+
+    /// Get the root node
+    pub fn root(&self) -> Option<&OctreeNode> {
+        self.root.as_ref()
+    }
+
     /// Insert object into octree
+    ///
+    /// Inserting an id already in the tree moves it instead.
     pub fn insert(&mut self, x: f32, y: f32, z: f32, id: String) -> Result<(), SpatialError> {
-        if self.root.is_none() {
-            self.root = Some(OctreeNode::Leaf {
-                bounds: Bounds::new(-1000.0, -1000.0, -1000.0, 1000.0, 1000.0, 1000.0),
-                data: vec![(x, y, z, id)],
-            });
-            return Ok(());
+        check_position(x, y, z)?;
+        if self.positions.contains_key(&id) {
+            return self.update(&id, x, y, z);
+        }
+
+        let root = self.root.take().unwrap_or_else(|| OctreeNode::empty(self.bounds));
+        let mut root = self.grow(root, x, y, z);
+        Self::insert_recursive(&mut root, (x, y, z, id.clone()), self.max_objects, 0, self.max_depth);
+        self.root = Some(root);
+        self.positions.insert(id, (x, y, z));
+
+        Ok(())
+    }
+
+    /// Move an object, inserting it if it is not in the tree yet
+    pub fn update(&mut self, id: &str, x: f32, y: f32, z: f32) -> Result<(), SpatialError> {
+        check_position(x, y, z)?;
+        let Some(&from) = self.positions.get(id) else {
+            return self.insert(x, y, z, id.to_string());
+        };
+
+        let moved = self.root.as_mut().is_some_and(|root| {
+            root.bounds().holds(x, y, z) && Self::relocate(root, from, (x, y, z), id)
+        });
+        if moved {
+            self.positions.insert(id.to_string(), (x, y, z));
+            Ok(())
+        } else {
+            self.remove(id);
+            self.insert(x, y, z, id.to_string())
         }
+    }
 
+    /// Remove an object, returning where it was
+    pub fn remove(&mut self, id: &str) -> Option<(f32, f32, f32)> {
+        let position = self.positions.remove(id)?;
         if let Some(root) = &mut self.root {
-            Self::insert_recursive(root, x, y, z, id, self.max_objects, 0);
+            Self::remove_recursive(root, position, id, self.merge_threshold);
+        }
+        Some(position)
+    }
+
+    /// Track the living entities of a world
+    ///
+    /// Entities are inserted or moved to their current position, and objects without a
+    /// living entity are removed.
+    pub fn sync(&mut self, world: &World) -> Result<(), SpatialError> {
+        for entity in world.entities.values().filter(|entity| entity.is_alive) {
+            self.update(&entity.id, entity.x, entity.y, entity.z)?;
+        }
+
+        let stale: Vec<String> = self
+            .positions
+            .keys()
+            .filter(|id| !world.entities.get(*id).is_some_and(|entity| entity.is_alive))
+            .cloned()
+            .collect();
+        for id in stale {
+            self.remove(&id);
         }
 
         Ok(())
@@ -95,88 +292,150 @@ impl Octree {
 
     fn insert_recursive(
         node: &mut OctreeNode,
-        x: f32,
-        y: f32,
-        z: f32,
-        id: String,
+        entry: (f32, f32, f32, String),
         max_objects: usize,
         depth: usize,
+        max_depth: usize,
     ) {
         match node {
             OctreeNode::Leaf { bounds, data } => {
-                if data.len() < max_objects || depth > 10 {
-                    data.push((x, y, z, id));
-                } else {
+                data.push(entry);
+                if data.len() > max_objects && depth < max_depth {
                     // Subdivide
-                    let children = Self::subdivide(*bounds);
-                    let old_data = std::mem::replace(data, vec![]);
-
-                    for (ox, oy, oz, oid) in old_data {
-                        let child_index = Self::get_child_index(*bounds, ox, oy, oz);
-                        Self::insert_recursive(&mut children[child_index], ox, oy, oz, oid, max_objects, depth + 1);
+                    let bounds = *bounds;
+                    let old_data = std::mem::take(data);
+                    let len = old_data.len();
+                    let mut children = Self::subdivide(bounds);
+
+                    for entry in old_data {
+                        let child_index = Self::get_child_index(bounds, entry.0, entry.1, entry.2);
+                        Self::insert_recursive(&mut children[child_index], entry, max_objects, depth + 1, max_depth);
                     }
 
-                    let child_index = Self::get_child_index(*bounds, x, y, z);
-                    Self::insert_recursive(&mut children[child_index], x, y, z, id, max_objects, depth + 1);
+                    *node = OctreeNode::Internal { bounds, children, len };
+                }
+            }
+            OctreeNode::Internal { bounds, children, len } => {
+                *len += 1;
+                let child_index = Self::get_child_index(*bounds, entry.0, entry.1, entry.2);
+                Self::insert_recursive(&mut children[child_index], entry, max_objects, depth + 1, max_depth);
+            }
+        }
+    }
+
+    /// Move an object within its leaf, failing if the move crosses into another node
+    fn relocate(node: &mut OctreeNode, from: (f32, f32, f32), to: (f32, f32, f32), id: &str) -> bool {
+        match node {
+            OctreeNode::Leaf { data, .. } => match data.iter_mut().find(|entry| entry.3 == id) {
+                Some(entry) => {
+                    (entry.0, entry.1, entry.2) = to;
+                    true
+                }
+                None => false,
+            },
+            OctreeNode::Internal { bounds, children, .. } => {
+                let child_index = Self::get_child_index(*bounds, from.0, from.1, from.2);
+                child_index == Self::get_child_index(*bounds, to.0, to.1, to.2)
+                    && Self::relocate(&mut children[child_index], from, to, id)
+            }
+        }
+    }
+
+    fn remove_recursive(node: &mut OctreeNode, at: (f32, f32, f32), id: &str, merge_threshold: usize) -> bool {
+        match node {
+            OctreeNode::Leaf { data, .. } => match data.iter().position(|entry| entry.3 == id) {
+                Some(index) => {
+                    data.swap_remove(index);
+                    true
+                }
+                None => false,
+            },
+            OctreeNode::Internal { bounds, children, len } => {
+                let child_index = Self::get_child_index(*bounds, at.0, at.1, at.2);
+                if !Self::remove_recursive(&mut children[child_index], at, id, merge_threshold) {
+                    return false;
+                }
 
-                    *node = OctreeNode::Internal {
-                        bounds: *bounds,
-                        children,
-                    };
+                *len -= 1;
+                if *len <= merge_threshold {
+                    // Merge the children back into a single leaf
+                    let bounds = *bounds;
+                    let mut data = Vec::with_capacity(*len);
+                    std::mem::replace(node, OctreeNode::empty(bounds)).drain_into(&mut data);
+                    *node = OctreeNode::Leaf { bounds, data };
                 }
+                true
+            }
+        }
+    }
+
+    /// Double the root towards a point until the point is inside
+    fn grow(&self, mut root: OctreeNode, x: f32, y: f32, z: f32) -> OctreeNode {
+        // Bounds of zero size could never double up to the point
+        let old = root.bounds();
+        if old.max_x <= old.min_x || old.max_y <= old.min_y || old.max_z <= old.min_z {
+            let mut data = Vec::new();
+            root.drain_into(&mut data);
+            let extent = (x.abs().max(y.abs()).max(z.abs()) + 1.0).max(1000.0);
+            root = OctreeNode::empty(Bounds::new(-extent, -extent, -extent, extent, extent, extent));
+            for entry in data {
+                Self::insert_recursive(&mut root, entry, self.max_objects, 0, self.max_depth);
             }
-            OctreeNode::Internal { bounds, children } => {
-                let child_index = Self::get_child_index(*bounds, x, y, z);
-                Self::insert_recursive(&mut children[child_index], x, y, z, id, max_objects, depth + 1);
+        }
+
+        while !root.bounds().holds(x, y, z) {
+            let old = root.bounds();
+            let (min_x, max_x, ix) = extend(old.min_x, old.max_x, x);
+            let (min_y, max_y, iy) = extend(old.min_y, old.max_y, y);
+            let (min_z, max_z, iz) = extend(old.min_z, old.max_z, z);
+            let bounds = Bounds::new(min_x, min_y, min_z, max_x, max_y, max_z);
+            let child_index = ix | (iy << 1) | (iz << 2);
+            let mut children = Self::subdivide(bounds);
+
+            if children[child_index].bounds() == old {
+                let len = root.len();
+                *children[child_index] = root;
+                root = OctreeNode::Internal { bounds, children, len };
+            } else {
+                // Rounding moved the split off the old faces, so route every object again
+                let mut data = Vec::new();
+                root.drain_into(&mut data);
+                root = OctreeNode::empty(bounds);
+                for entry in data {
+                    Self::insert_recursive(&mut root, entry, self.max_objects, 0, self.max_depth);
+                }
             }
         }
+
+        root
+    }
+
+    fn rebuild(&mut self) {
+        let Some(root) = self.root.take() else {
+            return;
+        };
+        let mut data = Vec::with_capacity(root.len());
+        root.drain_into(&mut data);
+        self.positions.clear();
+        for (x, y, z, id) in data {
+            // Positions were checked when first inserted
+            let _ = self.insert(x, y, z, id);
+        }
     }
 
     fn subdivide(bounds: Bounds) -> [Box<OctreeNode>; 8] {
-        let cx = (bounds.min_x + bounds.max_x) / 2.0;
-        let cy = (bounds.min_y + bounds.max_y) / 2.0;
-        let cz = (bounds.min_z + bounds.max_z) / 2.0;
-
-        [
-            Box::new(OctreeNode::Leaf {
-                bounds: Bounds::new(bounds.min_x, bounds.min_y, bounds.min_z, cx, cy, cz),
-                data: vec![],
-            }),
-            Box::new(OctreeNode::Leaf {
-                bounds: Bounds::new(cx, bounds.min_y, bounds.min_z, bounds.max_x, cy, cz),
-                data: vec![],
-            }),
-            Box::new(OctreeNode::Leaf {
-                bounds: Bounds::new(bounds.min_x, cy, bounds.min_z, cx, bounds.max_y, cz),
-                data: vec![],
-            }),
-            Box::new(OctreeNode::Leaf {
-                bounds: Bounds::new(cx, cy, bounds.min_z, bounds.max_x, bounds.max_y, cz),
-                data: vec![],
-            }),
-            Box::new(OctreeNode::Leaf {
-                bounds: Bounds::new(bounds.min_x, bounds.min_y, cz, cx, cy, bounds.max_z),
-                data: vec![],
-            }),
-            Box::new(OctreeNode::Leaf {
-                bounds: Bounds::new(cx, bounds.min_y, cz, bounds.max_x, cy, bounds.max_z),
-                data: vec![],
-            }),
-            Box::new(OctreeNode::Leaf {
-                bounds: Bounds::new(bounds.min_x, cy, cz, cx, bounds.max_y, bounds.max_z),
-                data: vec![],
-            }),
-            Box::new(OctreeNode::Leaf {
-                bounds: Bounds::new(cx, cy, cz, bounds.max_x, bounds.max_y, bounds.max_z),
-                data: vec![],
-            }),
-        ]
+        let (cx, cy, cz) = bounds.center();
+
+        std::array::from_fn(|index| {
+            let (min_x, max_x) = if index & 1 == 0 { (bounds.min_x, cx) } else { (cx, bounds.max_x) };
+            let (min_y, max_y) = if index & 2 == 0 { (bounds.min_y, cy) } else { (cy, bounds.max_y) };
+            let (min_z, max_z) = if index & 4 == 0 { (bounds.min_z, cz) } else { (cz, bounds.max_z) };
+            Box::new(OctreeNode::empty(Bounds::new(min_x, min_y, min_z, max_x, max_y, max_z)))
+        })
     }
 
     fn get_child_index(bounds: Bounds, x: f32, y: f32, z: f32) -> usize {
-        let cx = (bounds.min_x + bounds.max_x) / 2.0;
-        let cy = (bounds.min_y + bounds.max_y) / 2.0;
-        let cz = (bounds.min_z + bounds.max_z) / 2.0;
+        let (cx, cy, cz) = bounds.center();
 
         let mut index = 0;
         if x >= cx { index |= 1; }
@@ -187,10 +446,11 @@ impl Octree {
 
     /// Query objects within radius
     pub fn query_radius(&self, cx: f32, cy: f32, cz: f32, radius: f32) -> Vec<String> {
-        match &self.root {
-            Some(root) => Self::query_recursive(root, cx, cy, cz, radius),
-            None => vec![],
+        let mut results = vec![];
+        if let Some(root) = &self.root {
+            Self::query_recursive(root, cx, cy, cz, radius, &mut results);
         }
+        results
     }
 
     fn query_recursive(
@@ -199,47 +459,105 @@ impl Octree {
         cy: f32,
         cz: f32,
         radius: f32,
-    ) -> Vec<String> {
+        results: &mut Vec<String>,
+    ) {
+        if !node.bounds().intersects_sphere(cx, cy, cz, radius) {
+            return;
+        }
+
         match node {
-            OctreeNode::Leaf { bounds, data } => {
-                if bounds.intersects_sphere(cx, cy, cz, radius) {
+            OctreeNode::Leaf { data, .. } => {
+                results.extend(
                     data.iter()
-                        .filter(|t| {
-                            let (x, y, z, _) = t;
+                        .filter(|(x, y, z, _)| {
                             let dx = x - cx;
                             let dy = y - cy;
                             let dz = z - cz;
                             (dx * dx + dy * dy + dz * dz).sqrt() <= radius
                         })
-                        .map(|t| t.3.clone())
-                        .collect()
-                } else {
-                    vec![]
-                }
+                        .map(|t| t.3.clone()),
+                );
             }
-            OctreeNode::Internal { bounds, children } => {
-                if bounds.intersects_sphere(cx, cy, cz, radius) {
-                    let mut results = vec![];
-                    for child in children {
-                        results.extend(Self::query_recursive(child, cx, cy, cz, radius));
-                    }
-                    results
-                } else {
-                    vec![]
+            OctreeNode::Internal { children, .. } => {
+                for child in children {
+                    Self::query_recursive(child, cx, cy, cz, radius, results);
                 }
             }
         }
     }
 
+    /// Query objects inside bounds, faces included
+    pub fn query_bounds(&self, bounds: &Bounds) -> Vec<String> {
+        let mut results = vec![];
+        let mut stack: Vec<&OctreeNode> = self.root.iter().collect();
+
+        while let Some(node) = stack.pop() {
+            if !node.bounds().intersects(bounds) {
+                continue;
+            }
+            match node {
+                OctreeNode::Leaf { data, .. } => results.extend(
+                    data.iter()
+                        .filter(|(x, y, z, _)| bounds.contains(*x, *y, *z))
+                        .map(|t| t.3.clone()),
+                ),
+                OctreeNode::Internal { children, .. } => stack.extend(children.iter().map(|child| &**child)),
+            }
+        }
+
+        results
+    }
+
     /// Remove all objects
     pub fn clear(&mut self) {
         self.root = None;
+        self.positions.clear();
+    }
+}
+
+fn check_position(x: f32, y: f32, z: f32) -> Result<(), SpatialError> {
+    if x.is_finite() && y.is_finite() && z.is_finite() {
+        Ok(())
+    } else {
+        Err(SpatialError::InvalidCoordinates {
+            message: format!("octree position ({}, {}, {}) is not finite", x, y, z),
+        })
+    }
+}
+
+/// Double a span towards a value, with the side the old span ends up on
+fn extend(min: f32, max: f32, value: f32) -> (f32, f32, usize) {
+    let size = max - min;
+    if value < min {
+        (min - size, max, 1)
+    } else {
+        (min, max + size, 0)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{ChunkCoord, Entity};
+    use entropic_world_core::population::EntityType;
+
+    fn brute_force_radius(objects: &[(f32, f32, f32, String)], cx: f32, cy: f32, cz: f32, radius: f32) -> Vec<String> {
+        let mut ids: Vec<String> = objects
+            .iter()
+            .filter(|(x, y, z, _)| {
+                let (dx, dy, dz) = (x - cx, y - cy, z - cz);
+                (dx * dx + dy * dy + dz * dz).sqrt() <= radius
+            })
+            .map(|t| t.3.clone())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    fn sorted(mut ids: Vec<String>) -> Vec<String> {
+        ids.sort();
+        ids
+    }
 
     #[test]
     fn test_octree_creation() {
@@ -281,4 +599,131 @@ mod tests {
 
         assert!(octree.root.is_none());
     }
+
+    #[test]
+    fn test_split_and_merge() {
+        let mut octree = Octree::new(4);
+        for i in 0..16 {
+            let id = format!("object{}", i);
+            octree.insert(i as f32 * 10.0, (i % 4) as f32 * 10.0, 0.0, id).unwrap();
+        }
+        assert_eq!(octree.len(), 16);
+        assert!(octree.depth() > 0);
+        assert_eq!(octree.root().unwrap().len(), 16);
+
+        for i in 0..14 {
+            assert_eq!(octree.remove(&format!("object{}", i)), Some((i as f32 * 10.0, (i % 4) as f32 * 10.0, 0.0)));
+        }
+        assert_eq!(octree.remove("object0"), None);
+        assert_eq!(octree.len(), 2);
+        assert_eq!(octree.depth(), 0);
+        assert!(matches!(octree.root(), Some(OctreeNode::Leaf { data, .. }) if data.len() == 2));
+        assert_eq!(sorted(octree.query_radius(0.0, 0.0, 0.0, 1000.0)), vec!["object14", "object15"]);
+    }
+
+    #[test]
+    fn test_update_moves_objects() {
+        let mut octree = Octree::new(2).with_merge_threshold(0);
+        octree.insert(-500.0, -500.0, 0.0, "a".to_string()).unwrap();
+        octree.insert(500.0, 500.0, 0.0, "b".to_string()).unwrap();
+        octree.insert(510.0, 500.0, 0.0, "c".to_string()).unwrap();
+        assert!(octree.depth() > 0);
+
+        // Within its leaf
+        octree.update("a", -490.0, -500.0, 0.0).unwrap();
+        assert_eq!(octree.position("a"), Some((-490.0, -500.0, 0.0)));
+        assert_eq!(octree.query_radius(-490.0, -500.0, 0.0, 1.0), vec!["a"]);
+
+        // Across the tree, and inserting by update
+        octree.update("a", 505.0, 505.0, 0.0).unwrap();
+        octree.update("d", 0.0, 0.0, 0.0).unwrap();
+        assert_eq!(octree.len(), 4);
+        assert!(octree.query_radius(-490.0, -500.0, 0.0, 50.0).is_empty());
+        assert_eq!(sorted(octree.query_radius(505.0, 500.0, 0.0, 20.0)), vec!["a", "b", "c"]);
+
+        // Inserting an id again moves it
+        octree.insert(0.0, 0.0, 100.0, "d".to_string()).unwrap();
+        assert_eq!(octree.len(), 4);
+        assert_eq!(octree.query_radius(0.0, 0.0, 100.0, 1.0), vec!["d"]);
+
+        assert!(octree.update("a", f32::NAN, 0.0, 0.0).is_err());
+        assert_eq!(octree.position("a"), Some((505.0, 505.0, 0.0)));
+    }
+
+    #[test]
+    fn test_root_grows_to_fit() {
+        let mut octree = Octree::new(2).with_bounds(Bounds::new(0.0, 0.0, 0.0, 64.0, 64.0, 64.0));
+        octree.insert(10.0, 10.0, 10.0, "inside".to_string()).unwrap();
+        octree.insert(64.0, 64.0, 64.0, "face".to_string()).unwrap();
+        octree.insert(-300.0, 5000.0, 2.0, "far".to_string()).unwrap();
+        octree.insert(20.0, 20.0, 20.0, "near".to_string()).unwrap();
+
+        let bounds = octree.root().unwrap().bounds();
+        assert!(bounds.contains(-300.0, 5000.0, 2.0) && bounds.contains(10.0, 10.0, 10.0));
+        assert_eq!(octree.query_radius(-300.0, 5000.0, 2.0, 1.0), vec!["far"]);
+        assert_eq!(octree.query_radius(64.0, 64.0, 64.0, 0.5), vec!["face"]);
+        assert_eq!(octree.remove("face"), Some((64.0, 64.0, 64.0)));
+        assert_eq!(octree.remove("far"), Some((-300.0, 5000.0, 2.0)));
+        assert_eq!(sorted(octree.query_radius(0.0, 0.0, 0.0, 100.0)), vec!["inside", "near"]);
+
+        let query = Bounds::new(0.0, 0.0, 0.0, 15.0, 15.0, 15.0);
+        assert_eq!(octree.query_bounds(&query), vec!["inside"]);
+    }
+
+    #[test]
+    fn test_moving_objects_match_brute_force() {
+        let mut octree = Octree::new(8).with_bounds(Bounds::new(0.0, 0.0, 0.0, 1024.0, 1024.0, 64.0));
+        let mut objects: Vec<(f32, f32, f32, String)> = (0..500)
+            .map(|i| {
+                let x = ((i * 7919) % 1024) as f32;
+                let y = ((i * 104_729) % 1024) as f32;
+                (x, y, (i % 64) as f32, format!("object{}", i))
+            })
+            .collect();
+        for (x, y, z, id) in &objects {
+            octree.insert(*x, *y, *z, id.clone()).unwrap();
+        }
+
+        for step in 0..20 {
+            for (i, object) in objects.iter_mut().enumerate() {
+                let angle = (i + step) as f32 * 0.37;
+                object.0 = (object.0 + angle.cos() * 40.0).rem_euclid(1100.0) - 40.0;
+                object.1 = (object.1 + angle.sin() * 40.0).rem_euclid(1100.0) - 40.0;
+                octree.update(&object.3, object.0, object.1, object.2).unwrap();
+            }
+
+            let (cx, cy) = (step as f32 * 50.0, 1000.0 - step as f32 * 45.0);
+            assert_eq!(sorted(octree.query_radius(cx, cy, 32.0, 150.0)), brute_force_radius(&objects, cx, cy, 32.0, 150.0));
+        }
+
+        assert_eq!(octree.len(), 500);
+        assert_eq!(octree.root().unwrap().len(), 500);
+        for object in objects.drain(..250) {
+            octree.remove(&object.3).unwrap();
+        }
+        assert_eq!(sorted(octree.query_radius(500.0, 500.0, 0.0, 2000.0)), brute_force_radius(&objects, 500.0, 500.0, 0.0, 2000.0));
+    }
+
+    #[test]
+    fn test_sync_with_world() {
+        let mut world = World::new("Test".to_string(), "game1".to_string(), 4, 4);
+        world.initialize_chunks();
+        for i in 0..3 {
+            let x = 100.0 + i as f32 * 10.0;
+            world.add_entity(Entity::new(format!("e{}", i), EntityType::NPC, x, 100.0, 5.0, ChunkCoord::new(0, 0)));
+        }
+
+        let mut octree = Octree::new(4);
+        octree.sync(&world).unwrap();
+        assert_eq!(octree.len(), 3);
+
+        world.entities.get_mut("e0").unwrap().x = 700.0;
+        world.entities.get_mut("e1").unwrap().is_alive = false;
+        world.entities.remove("e2");
+        octree.sync(&world).unwrap();
+
+        assert_eq!(octree.len(), 1);
+        assert_eq!(octree.position("e0"), Some((700.0, 100.0, 5.0)));
+        assert!(!octree.contains("e1") && !octree.contains("e2"));
+    }
 }
@@ -0,0 +1,253 @@
+//! Terrain cost profiles for pathfinding
+//!
+//! A [`CostProfile`] weighs every grid cell by what an entity walks on: a multiplier per
+//! biome and per surface material, overridden near structures such as roads and bridges.
+//! Water is impassable unless the profile is amphibious. [`CostProfiles`] keeps one profile per
+//! entity type, so carts keep to roads while animals wade through swamps.
+
+use crate::collision::CollisionDetector;
+use crate::constants::*;
+use crate::{Biome, ChunkCoord, World};
+use entropic_world_core::population::EntityType;
+use entropic_world_core::spatial::{Material, StructureType};
+use std::collections::HashMap;
+
+/// Structure type used for road segments
+pub const ROAD: StructureType = StructureType::Custom(2);
+
+/// Traversal cost multipliers of one kind of entity
+///
+/// Multipliers scale the distance walked through a cell, so 1 is normal speed, 2 half speed
+/// and infinity impassable. The default profile costs 1 everywhere except water, which is
+/// impassable, and finds the same paths as [`Pathfinder::find_path`](crate::Pathfinder::find_path).
+#[derive(Clone, Debug, PartialEq)]
+pub struct CostProfile {
+    biomes: Vec<(Biome, f32)>,
+    materials: [f32; 4],
+    structures: Vec<(StructureType, f32)>,
+    structure_radius: f32,
+    water: Option<f32>,
+}
+
+impl Default for CostProfile {
+    fn default() -> Self {
+        Self {
+            biomes: Vec::new(),
+            materials: [1.0; 4],
+            structures: Vec::new(),
+            structure_radius: PATHFINDING_GRID_SIZE,
+            water: None,
+        }
+    }
+}
+
+impl CostProfile {
+    /// Create a profile costing 1 everywhere on land
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Profile of people on foot: slow in swamps, snow and mountains, fast on roads
+    pub fn walker() -> Self {
+        Self::new()
+            .with_biome_cost(Biome::Swamp, 3.0)
+            .with_biome_cost(Biome::Forest, 1.5)
+            .with_biome_cost(Biome::Mountains, 2.0)
+            .with_biome_cost(Biome::Tundra, 1.5)
+            .with_material_cost(Material::Rock, 1.5)
+            .with_material_cost(Material::Sand, 1.25)
+            .with_material_cost(Material::Snow, 2.0)
+            .with_structure_cost(ROAD, 0.5)
+            .with_structure_cost(StructureType::Bridge, 0.5)
+    }
+
+    /// Set the multiplier of a biome, infinity making it impassable
+    pub fn with_biome_cost(mut self, biome: Biome, cost: f32) -> Self {
+        self.biomes.retain(|(b, _)| *b != biome);
+        self.biomes.push((biome, cost.max(0.0)));
+        self
+    }
+
+    /// Set the multiplier of a surface material, blended by the material weights of a cell
+    pub fn with_material_cost(mut self, material: Material, cost: f32) -> Self {
+        self.materials[material as usize] = cost.max(0.0);
+        self
+    }
+
+    /// Set the multiplier near structures of a type, replacing the terrain cost there
+    pub fn with_structure_cost(mut self, structure_type: StructureType, cost: f32) -> Self {
+        self.structures.retain(|(t, _)| *t != structure_type);
+        self.structures.push((structure_type, cost.max(0.0)));
+        self
+    }
+
+    /// Set how close to a structure (in meters) a cell takes the structure's cost
+    pub fn with_structure_radius(mut self, radius: f32) -> Self {
+        self.structure_radius = radius.max(0.0);
+        self
+    }
+
+    /// Let the entity swim, crossing water at a multiplier
+    pub fn with_water_cost(mut self, cost: f32) -> Self {
+        self.water = Some(cost.max(0.0));
+        self
+    }
+
+    /// Check whether water is passable
+    pub fn is_amphibious(&self) -> bool {
+        self.water.is_some()
+    }
+
+    /// Get the multiplier of a biome
+    pub fn biome_cost(&self, biome: Biome) -> f32 {
+        self.biomes.iter().find(|(b, _)| *b == biome).map_or(1.0, |&(_, cost)| cost)
+    }
+
+    /// Get the multiplier of a surface material
+    pub fn material_cost(&self, material: Material) -> f32 {
+        self.materials[material as usize]
+    }
+
+    /// Get the multiplier of the cell at a world position, `None` where it is impassable
+    ///
+    /// Cells are sampled like [`CollisionDetector::is_walkable`], which also rules out cliffs.
+    pub fn cost_at(&self, world: &World, x: f32, y: f32) -> Option<f32> {
+        if x < 0.0 || y < 0.0 {
+            return None;
+        }
+        let coord = ChunkCoord::new((x / CHUNK_SIZE) as u32, (y / CHUNK_SIZE) as u32);
+        let chunk = world.chunks.get(&coord)?;
+
+        let cost = if CollisionDetector::is_walkable(world, x, y) {
+            let near = |sx: f32, sy: f32| (sx - x).powi(2) + (sy - y).powi(2) <= self.structure_radius.powi(2);
+            let structure = chunk
+                .structures
+                .iter()
+                .filter(|s| near(s.x, s.y))
+                .filter_map(|s| self.structures.iter().find(|(t, _)| *t == s.structure_type))
+                .map(|&(_, cost)| cost)
+                .reduce(f32::min);
+
+            match structure {
+                Some(cost) => cost,
+                None => {
+                    let scale = HEIGHTMAP_RESOLUTION as f32 / CHUNK_SIZE;
+                    let sx = ((x - coord.x as f32 * CHUNK_SIZE) * scale) as usize;
+                    let sy = ((y - coord.y as f32 * CHUNK_SIZE) * scale) as usize;
+                    let material = chunk.get_materials_at(sx, sy).map_or(1.0, |weights| {
+                        weights
+                            .iter()
+                            .zip(self.materials)
+                            .filter(|(&w, _)| w > 0)
+                            .map(|(&w, cost)| w as f32 * cost)
+                            .sum::<f32>()
+                            / 255.0
+                    });
+                    self.biome_cost(chunk.biome) * material
+                }
+            }
+        } else {
+            let height = CollisionDetector::get_terrain_height(world, x, y)?;
+            match self.water {
+                Some(cost) if height <= chunk.water_level => cost,
+                _ => return None,
+            }
+        };
+
+        cost.is_finite().then_some(cost)
+    }
+
+    /// Get the lowest multiplier any cell can have, keeping the A* heuristic admissible
+    pub fn min_cost(&self) -> f32 {
+        let lowest = |costs: &mut dyn Iterator<Item = f32>| costs.filter(|c| c.is_finite()).fold(1.0, f32::min);
+        let terrain = lowest(&mut self.biomes.iter().map(|&(_, c)| c)) * lowest(&mut self.materials.into_iter());
+        let other = self.structures.iter().map(|&(_, c)| c).chain(self.water);
+        lowest(&mut other.chain(std::iter::once(terrain)))
+    }
+}
+
+/// Cost profiles per entity type, with a fallback for types without one
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CostProfiles {
+    fallback: CostProfile,
+    profiles: HashMap<EntityType, CostProfile>,
+}
+
+impl CostProfiles {
+    /// Create profiles using `fallback` for every entity type
+    pub fn new(fallback: CostProfile) -> Self {
+        Self {
+            fallback,
+            profiles: HashMap::new(),
+        }
+    }
+
+    /// Set the profile of an entity type
+    pub fn with_profile(mut self, entity_type: EntityType, profile: CostProfile) -> Self {
+        self.profiles.insert(entity_type, profile);
+        self
+    }
+
+    /// Get the profile of an entity type
+    pub fn get(&self, entity_type: EntityType) -> &CostProfile {
+        self.profiles.get(&entity_type).unwrap_or(&self.fallback)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entropic_world_core::spatial::Structure;
+
+    fn create_test_world() -> World {
+        let mut world = World::new("Test".to_string(), "game1".to_string(), 2, 2);
+        world.initialize_chunks();
+        for chunk in world.chunks.values_mut() {
+            chunk.water_level = 50.0;
+            chunk.elevation.iter_mut().for_each(|h| *h = 100.0);
+        }
+        world
+    }
+
+    #[test]
+    fn test_costs_by_biome_material_and_structure() {
+        let mut world = create_test_world();
+        let profile = CostProfile::walker();
+        assert_eq!(profile.cost_at(&world, 20.0, 20.0), Some(1.0));
+
+        let chunk = world.chunks.get_mut(&ChunkCoord::new(0, 0)).unwrap();
+        chunk.biome = Biome::Swamp;
+        assert_eq!(profile.cost_at(&world, 20.0, 20.0), Some(3.0));
+
+        // Half snow, half grass
+        let chunk = world.chunks.get_mut(&ChunkCoord::new(0, 0)).unwrap();
+        chunk.set_materials_at(20, 20, [128, 0, 0, 127]);
+        let blended = 3.0 * (128.0 + 127.0 * 2.0) / 255.0;
+        assert!((profile.cost_at(&world, 20.5, 20.5).unwrap() - blended).abs() < 1e-5);
+
+        // Roads replace the swamp cost
+        let chunk = world.chunks.get_mut(&ChunkCoord::new(0, 0)).unwrap();
+        chunk.add_structure(Structure::new("road-1".to_string(), ROAD, 24.0, 20.0, 100.0));
+        assert_eq!(profile.cost_at(&world, 20.5, 20.5), Some(0.5));
+        assert_eq!(profile.min_cost(), 0.5);
+
+        let impassable = profile.with_biome_cost(Biome::Plains, f32::INFINITY);
+        assert_eq!(impassable.cost_at(&world, CHUNK_SIZE + 20.0, 20.0), None);
+    }
+
+    #[test]
+    fn test_water_needs_amphibious_profile() {
+        let mut world = create_test_world();
+        world.chunks.get_mut(&ChunkCoord::new(1, 1)).unwrap().water_level = 150.0;
+        let (x, y) = (CHUNK_SIZE + 10.0, CHUNK_SIZE + 10.0);
+
+        assert_eq!(CostProfile::walker().cost_at(&world, x, y), None);
+        let swimmer = CostProfile::walker().with_water_cost(4.0);
+        assert!(swimmer.is_amphibious());
+        assert_eq!(swimmer.cost_at(&world, x, y), Some(4.0));
+
+        let profiles = CostProfiles::default().with_profile(EntityType::Animal, swimmer.clone());
+        assert_eq!(profiles.get(EntityType::Animal), &swimmer);
+        assert_eq!(profiles.get(EntityType::NPC), &CostProfile::default());
+    }
+}
@@ -0,0 +1,376 @@
+use crate::constants::*;
+use crate::errors::SpatialError;
+use crate::path_costs::CostProfile;
+use crate::pathfinding::{PathAlgorithm, Pathfinder};
+use crate::World;
+use entropic_world_core::observability::{self, set_gauge};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use tokio::sync::{mpsc, oneshot, Mutex};
+
+/// Identifier of a submitted path job
+pub type PathJobId = u64;
+
+/// Path search to run on the job queue
+#[derive(Clone, Debug, PartialEq)]
+pub struct PathRequest {
+    pub start: (f32, f32),
+    pub goal: (f32, f32),
+    pub max_iterations: u32,
+    pub algorithm: PathAlgorithm,
+    pub smooth: bool,
+    pub cost_profile: Option<Arc<CostProfile>>,
+}
+
+impl PathRequest {
+    /// Create a request for an unsmoothed A* path
+    pub fn new(start: (f32, f32), goal: (f32, f32)) -> Self {
+        Self {
+            start,
+            goal,
+            max_iterations: MAX_PATHFINDING_ITERATIONS,
+            algorithm: PathAlgorithm::AStar,
+            smooth: false,
+            cost_profile: None,
+        }
+    }
+
+    /// Set the iteration limit of the search
+    pub fn with_max_iterations(mut self, max_iterations: u32) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Set the search algorithm
+    pub fn with_algorithm(mut self, algorithm: PathAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Smooth the found path into waypoints
+    pub fn with_smoothing(mut self, smooth: bool) -> Self {
+        self.smooth = smooth;
+        self
+    }
+
+    /// Weigh terrain by a cost profile
+    ///
+    /// Searches with a profile always run A*, as jump point search assumes uniform costs.
+    pub fn with_cost_profile(mut self, profile: Arc<CostProfile>) -> Self {
+        self.cost_profile = Some(profile);
+        self
+    }
+}
+
+/// Outcome of a path job
+#[derive(Clone, Debug, PartialEq)]
+pub enum PathOutcome {
+    Found(Vec<(f32, f32)>),
+    NotFound,
+    Cancelled,
+}
+
+/// Job waiting in the queue
+struct QueuedJob {
+    request: PathRequest,
+    cancelled: Arc<AtomicBool>,
+    reply: oneshot::Sender<PathOutcome>,
+}
+
+/// Handle to a submitted path job
+#[derive(Debug)]
+pub struct PathJob {
+    id: PathJobId,
+    cancelled: Arc<AtomicBool>,
+    result: oneshot::Receiver<PathOutcome>,
+}
+
+impl PathJob {
+    /// Get the job id
+    pub fn id(&self) -> PathJobId {
+        self.id
+    }
+
+    /// Check for the outcome without waiting
+    ///
+    /// Returns None while the job is queued or running, and after the outcome was taken.
+    pub fn poll(&mut self) -> Option<PathOutcome> {
+        self.result.try_recv().ok()
+    }
+
+    /// Wait for the outcome
+    pub async fn wait(self) -> PathOutcome {
+        self.result.await.unwrap_or(PathOutcome::Cancelled)
+    }
+
+    /// Cancel the job
+    ///
+    /// A job that hasn't started yet is skipped; a running search finishes, but its outcome
+    /// is reported as cancelled.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Check if the job was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// Async path request queue served by a bounded pool of workers
+///
+/// Searches run on tokio's blocking thread pool, so submitting requests never blocks the
+/// simulation tick. At most `workers` searches run at once and at most `capacity` requests
+/// wait in the queue; further submissions are rejected until the queue drains.
+pub struct PathJobQueue {
+    sender: mpsc::Sender<QueuedJob>,
+    next_id: AtomicU64,
+    pending: Arc<AtomicUsize>,
+    workers: usize,
+}
+
+impl PathJobQueue {
+    /// Create a job queue and spawn its workers on the current tokio runtime
+    pub fn new(world: Arc<RwLock<World>>, workers: usize, capacity: usize) -> Self {
+        let workers = workers.max(1);
+        let (sender, receiver) = mpsc::channel(capacity.max(1));
+        let receiver = Arc::new(Mutex::new(receiver));
+        let pending = Arc::new(AtomicUsize::new(0));
+
+        for _ in 0..workers {
+            tokio::spawn(worker_task(world.clone(), receiver.clone(), pending.clone()));
+        }
+
+        Self {
+            sender,
+            next_id: AtomicU64::new(1),
+            pending,
+            workers,
+        }
+    }
+
+    /// Submit a path request
+    ///
+    /// Fails with `PathQueueFull` when the queue is at capacity.
+    pub fn submit(&self, request: PathRequest) -> Result<PathJob, SpatialError> {
+        let (reply, result) = oneshot::channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let job = QueuedJob {
+            request,
+            cancelled: cancelled.clone(),
+            reply,
+        };
+
+        // Counted before sending, so a worker finishing the job can't decrement first
+        self.pending.fetch_add(1, Ordering::SeqCst);
+        if self.sender.try_send(job).is_err() {
+            self.pending.fetch_sub(1, Ordering::SeqCst);
+            return Err(SpatialError::PathQueueFull);
+        }
+        report_depth(&self.pending);
+
+        Ok(PathJob {
+            id: self.next_id.fetch_add(1, Ordering::SeqCst),
+            cancelled,
+            result,
+        })
+    }
+
+    /// Submit a path request and wait for its outcome
+    pub async fn find_path(&self, request: PathRequest) -> Result<PathOutcome, SpatialError> {
+        Ok(self.submit(request)?.wait().await)
+    }
+
+    /// Get the number of jobs queued or running
+    pub fn pending(&self) -> usize {
+        self.pending.load(Ordering::SeqCst)
+    }
+
+    /// Get the number of workers
+    pub fn workers(&self) -> usize {
+        self.workers
+    }
+}
+
+/// Worker that takes jobs off the shared queue until it is closed
+async fn worker_task(
+    world: Arc<RwLock<World>>,
+    receiver: Arc<Mutex<mpsc::Receiver<QueuedJob>>>,
+    pending: Arc<AtomicUsize>,
+) {
+    loop {
+        let job = receiver.lock().await.recv().await;
+        let Some(job) = job else {
+            break;
+        };
+
+        let outcome = if job.cancelled.load(Ordering::SeqCst) {
+            PathOutcome::Cancelled
+        } else {
+            let world = world.clone();
+            let request = job.request;
+            let search = tokio::task::spawn_blocking(move || run_request(&world, &request));
+            match search.await {
+                Ok(_) if job.cancelled.load(Ordering::SeqCst) => PathOutcome::Cancelled,
+                Ok(outcome) => outcome,
+                Err(_) => PathOutcome::NotFound,
+            }
+        };
+
+        pending.fetch_sub(1, Ordering::SeqCst);
+        report_depth(&pending);
+        let _ = job.reply.send(outcome);
+    }
+}
+
+/// Report the number of jobs queued or running
+fn report_depth(pending: &AtomicUsize) {
+    set_gauge(observability::PATH_QUEUE_DEPTH, pending.load(Ordering::SeqCst) as f64);
+}
+
+/// Run a path request against the world
+#[cfg_attr(
+    feature = "observability",
+    tracing::instrument(level = "debug", skip_all, fields(algorithm = ?request.algorithm))
+)]
+fn run_request(world: &RwLock<World>, request: &PathRequest) -> PathOutcome {
+    let Ok(world) = world.read() else {
+        return PathOutcome::NotFound;
+    };
+
+    let path = if let Some(profile) = &request.cost_profile {
+        if request.smooth {
+            Pathfinder::find_smoothed_path_with_profile(&world, request.start, request.goal, request.max_iterations, profile)
+        } else {
+            Pathfinder::find_path_with_profile(&world, request.start, request.goal, request.max_iterations, profile)
+        }
+    } else if request.smooth {
+        Pathfinder::find_smoothed_path_with_algorithm(
+            &world,
+            request.start,
+            request.goal,
+            request.max_iterations,
+            request.algorithm,
+        )
+    } else {
+        Pathfinder::find_path_with_algorithm(
+            &world,
+            request.start,
+            request.goal,
+            request.max_iterations,
+            request.algorithm,
+        )
+    };
+
+    match path {
+        Some(path) => PathOutcome::Found(path),
+        None => PathOutcome::NotFound,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_world() -> Arc<RwLock<World>> {
+        let mut world = World::new("Test".to_string(), "game1".to_string(), 2, 2);
+        world.initialize_chunks();
+
+        for chunk in world.chunks.values_mut() {
+            chunk.water_level = -100.0;
+            chunk.elevation.iter_mut().for_each(|h| *h = 100.0);
+        }
+
+        Arc::new(RwLock::new(world))
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_submit_and_wait() {
+        let queue = PathJobQueue::new(create_test_world(), 2, 16);
+        let job = queue.submit(PathRequest::new((8.0, 8.0), (300.0, 300.0))).unwrap();
+
+        match job.wait().await {
+            PathOutcome::Found(path) => assert_eq!(path.last(), Some(&(296.0, 296.0))),
+            outcome => panic!("unexpected outcome {:?}", outcome),
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_smoothed_request() {
+        let queue = PathJobQueue::new(create_test_world(), 1, 4);
+        let request = PathRequest::new((8.0, 8.0), (300.0, 100.0))
+            .with_algorithm(PathAlgorithm::JumpPoint)
+            .with_smoothing(true);
+
+        let outcome = queue.find_path(request).await.unwrap();
+        assert_eq!(outcome, PathOutcome::Found(vec![(8.0, 8.0), (300.0, 100.0)]));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_request_with_cost_profile() {
+        let world = create_test_world();
+        world.write().unwrap().chunks.get_mut(&crate::ChunkCoord::new(1, 0)).unwrap().water_level = 150.0;
+        let queue = PathJobQueue::new(world, 1, 4);
+        let request = PathRequest::new((8.0, 8.0), (300.0, 8.0)).with_algorithm(PathAlgorithm::JumpPoint);
+
+        let walker = request.clone().with_cost_profile(Arc::new(CostProfile::walker()));
+        assert_eq!(queue.find_path(walker).await.unwrap(), PathOutcome::NotFound);
+        let swimmer = request.with_cost_profile(Arc::new(CostProfile::walker().with_water_cost(2.0)));
+        assert!(matches!(queue.find_path(swimmer).await.unwrap(), PathOutcome::Found(_)));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_unreachable_goal() {
+        let queue = PathJobQueue::new(create_test_world(), 1, 4);
+        let outcome = queue.find_path(PathRequest::new((8.0, 8.0), (5000.0, 5000.0))).await.unwrap();
+        assert_eq!(outcome, PathOutcome::NotFound);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_poll_and_many_requests() {
+        let queue = PathJobQueue::new(create_test_world(), 4, 256);
+        let mut jobs: Vec<PathJob> = (0..200)
+            .map(|i| {
+                let goal = (8.0 + (i % 30) as f32 * 16.0, 400.0);
+                queue.submit(PathRequest::new((8.0, 8.0), goal)).unwrap()
+            })
+            .collect();
+
+        let ids: std::collections::HashSet<PathJobId> = jobs.iter().map(|job| job.id()).collect();
+        assert_eq!(ids.len(), 200);
+
+        let mut done = 0;
+        while done < 200 {
+            for job in &mut jobs {
+                if let Some(outcome) = job.poll() {
+                    assert!(matches!(outcome, PathOutcome::Found(_)));
+                    done += 1;
+                }
+            }
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(queue.pending(), 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_cancel_and_capacity() {
+        let queue = PathJobQueue::new(create_test_world(), 1, 1);
+
+        // Fill the worker and the single queue slot, then overflow.
+        let mut jobs = Vec::new();
+        let mut rejected = false;
+        for _ in 0..64 {
+            match queue.submit(PathRequest::new((8.0, 8.0), (500.0, 500.0))) {
+                Ok(job) => jobs.push(job),
+                Err(SpatialError::PathQueueFull) => rejected = true,
+                Err(error) => panic!("unexpected error {}", error),
+            }
+        }
+        assert!(rejected);
+
+        let last = jobs.pop().unwrap();
+        last.cancel();
+        assert!(last.is_cancelled());
+        assert_eq!(last.wait().await, PathOutcome::Cancelled);
+    }
+}
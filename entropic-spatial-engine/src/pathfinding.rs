@@ -1,6 +1,7 @@
 use crate::constants::*;
 use crate::collision::CollisionDetector;
 use crate::errors::SpatialError;
+use crate::path_costs::CostProfile;
 use crate::World;
 use std::collections::{BinaryHeap, HashMap};
 use std::cmp::Ordering;
@@ -23,6 +24,22 @@ impl PartialOrd for PathfindingState {
     }
 }
 
+/// Search algorithm used by [`Pathfinder::find_path_with_algorithm`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PathAlgorithm {
+    /// A* over every grid cell
+    #[default]
+    AStar,
+
+    /// Jump point search over uniform-cost terrain, which only expands cells where the
+    /// path may turn and returns shortest paths that never cut obstacle corners diagonally.
+    ///
+    /// Its scans run until the next obstacle or the world edge, so on wide open terrain the
+    /// speedup over A* comes from precomputing a
+    /// [`JumpPointTable`](crate::jump_point::JumpPointTable) and running JPS+ on it.
+    JumpPoint,
+}
+
 /// A* pathfinding on world terrain
 pub struct Pathfinder;
 
@@ -37,7 +54,21 @@ impl Pathfinder {
         goal: (f32, f32),
         max_iterations: u32,
     ) -> Option<Vec<(f32, f32)>> {
-        Self::find_path_internal(world, start, goal, max_iterations, None)
+        Self::find_path_internal(world, start, goal, max_iterations, None, None)
+    }
+
+    /// Find path from start to goal with the given search algorithm
+    pub fn find_path_with_algorithm(
+        world: &World,
+        start: (f32, f32),
+        goal: (f32, f32),
+        max_iterations: u32,
+        algorithm: PathAlgorithm,
+    ) -> Option<Vec<(f32, f32)>> {
+        match algorithm {
+            PathAlgorithm::AStar => Self::find_path(world, start, goal, max_iterations),
+            PathAlgorithm::JumpPoint => crate::jump_point::find_path(world, start, goal, max_iterations),
+        }
     }
 
     /// Find path from start to goal with custom heuristic weight
@@ -51,7 +82,21 @@ impl Pathfinder {
         max_iterations: u32,
         heuristic_weight: f32,
     ) -> Option<Vec<(f32, f32)>> {
-        Self::find_path_internal(world, start, goal, max_iterations, Some(heuristic_weight))
+        Self::find_path_internal(world, start, goal, max_iterations, Some(heuristic_weight), None)
+    }
+
+    /// Find path from start to goal, weighing cells by a terrain cost profile
+    ///
+    /// Moves cost their length times the multiplier of the cell entered, and cells the
+    /// profile rules out are never entered, so the path trades distance for easier terrain.
+    pub fn find_path_with_profile(
+        world: &World,
+        start: (f32, f32),
+        goal: (f32, f32),
+        max_iterations: u32,
+        profile: &CostProfile,
+    ) -> Option<Vec<(f32, f32)>> {
+        Self::find_path_internal(world, start, goal, max_iterations, None, Some(profile))
     }
 
     fn find_path_internal(
@@ -60,6 +105,7 @@ impl Pathfinder {
         goal: (f32, f32),
         max_iterations: u32,
         heuristic_weight: Option<f32>,
+        profile: Option<&CostProfile>,
     ) -> Option<Vec<(f32, f32)>> {
         let start_grid = (
             (start.0 / PATHFINDING_GRID_SIZE).floor() as i32,
@@ -70,10 +116,16 @@ impl Pathfinder {
             (goal.1 / PATHFINDING_GRID_SIZE).floor() as i32,
         );
 
+        // Multiplier of entering a cell, None where it cannot be entered
+        let cell_cost = |x: f32, y: f32| match profile {
+            Some(profile) => profile.cost_at(world, x, y),
+            None => CollisionDetector::is_walkable(world, x, y).then_some(1.0),
+        };
+        // Scaled down by the cheapest multiplier so the heuristic never overestimates
+        let heuristic_weight = heuristic_weight.unwrap_or(1.0) * profile.map_or(1.0, CostProfile::min_cost);
+
         // Check if goal is walkable
-        if !CollisionDetector::is_walkable(world, goal.0, goal.1) {
-            return None;
-        }
+        cell_cost(goal.0, goal.1)?;
 
         let mut open_set = BinaryHeap::new();
         let mut came_from: HashMap<(i32, i32), (i32, i32)> = HashMap::new();
@@ -88,7 +140,7 @@ impl Pathfinder {
         g_score.insert(start_grid, 0);
         f_score.insert(
             start_grid,
-            (Self::heuristic(start_grid, goal_grid) as f32 * heuristic_weight.unwrap_or(1.0)) as u32,
+            (Self::heuristic(start_grid, goal_grid) * heuristic_weight) as u32,
         );
 
         let mut iterations = 0;
@@ -123,9 +175,9 @@ impl Pathfinder {
                 let world_y = neighbor.1 as f32 * PATHFINDING_GRID_SIZE;
 
                 // Check if walkable
-                if !CollisionDetector::is_walkable(world, world_x, world_y) {
+                let Some(multiplier) = cell_cost(world_x, world_y) else {
                     continue;
-                }
+                };
 
                 // Calculate cost (diagonal movement costs sqrt(2))
                 let move_cost = if dx.abs() + dy.abs() == 2 {
@@ -133,14 +185,15 @@ impl Pathfinder {
                 } else {
                     1000 // 1.0 * 1000
                 };
+                let move_cost = (move_cost as f32 * multiplier).min(u32::MAX as f32) as u32;
 
-                let tentative_g = g_score.get(&current).copied().unwrap_or(u32::MAX) + move_cost;
+                let tentative_g = g_score.get(&current).copied().unwrap_or(u32::MAX).saturating_add(move_cost);
 
                 if tentative_g < g_score.get(&neighbor).copied().unwrap_or(u32::MAX) {
                     came_from.insert(neighbor, current);
                     g_score.insert(neighbor, tentative_g);
 
-                    let h = Self::heuristic(neighbor, goal_grid) as f32 * heuristic_weight.unwrap_or(1.0);
+                    let h = Self::heuristic(neighbor, goal_grid) * heuristic_weight;
                     let f = tentative_g + (h * 1000.0) as u32;
                     f_score.insert(neighbor, f);
 
@@ -216,6 +269,209 @@ impl Pathfinder {
         total
     }
 
+    /// Find path from start to goal and smooth it into waypoints
+    ///
+    /// The grid path is pulled taut with [`smooth_path`](Self::smooth_path) and begins and
+    /// ends at the exact start and goal positions instead of their cell centers.
+    pub fn find_smoothed_path(
+        world: &World,
+        start: (f32, f32),
+        goal: (f32, f32),
+        max_iterations: u32,
+    ) -> Option<Vec<(f32, f32)>> {
+        Self::find_smoothed_path_with_algorithm(world, start, goal, max_iterations, PathAlgorithm::AStar)
+    }
+
+    /// Find path from start to goal with the given search algorithm and smooth it into
+    /// waypoints
+    pub fn find_smoothed_path_with_algorithm(
+        world: &World,
+        start: (f32, f32),
+        goal: (f32, f32),
+        max_iterations: u32,
+        algorithm: PathAlgorithm,
+    ) -> Option<Vec<(f32, f32)>> {
+        let mut path = Self::find_path_with_algorithm(world, start, goal, max_iterations, algorithm)?;
+        if path.len() == 1 {
+            return Some(vec![start, goal]);
+        }
+
+        path[0] = start;
+        let last = path.len() - 1;
+        path[last] = goal;
+        Some(Self::smooth_path(world, &path))
+    }
+
+    /// Find path from start to goal with a terrain cost profile and smooth it into waypoints
+    ///
+    /// Shortcuts are taken as in [`smooth_path_with_profile`](Self::smooth_path_with_profile).
+    pub fn find_smoothed_path_with_profile(
+        world: &World,
+        start: (f32, f32),
+        goal: (f32, f32),
+        max_iterations: u32,
+        profile: &CostProfile,
+    ) -> Option<Vec<(f32, f32)>> {
+        let mut path = Self::find_path_with_profile(world, start, goal, max_iterations, profile)?;
+        if path.len() == 1 {
+            return Some(vec![start, goal]);
+        }
+
+        path[0] = start;
+        let last = path.len() - 1;
+        path[last] = goal;
+        Some(Self::smooth_path_with_profile(world, &path, profile))
+    }
+
+    /// Smooth a grid path by string pulling
+    ///
+    /// From each waypoint the path jumps straight to the farthest later waypoint in
+    /// [`walkable_line`](Self::walkable_line) of it, so the zig-zag along grid cells turns
+    /// into straight segments that only bend around obstacles and steep terrain.
+    pub fn smooth_path(world: &World, path: &[(f32, f32)]) -> Vec<(f32, f32)> {
+        Self::pull_taut(path, |from, to| Self::walkable_line(world, path[from], path[to]))
+    }
+
+    /// Smooth a grid path found with a terrain cost profile
+    ///
+    /// Like [`smooth_path`](Self::smooth_path), except that a shortcut may only cross cells
+    /// the profile allows and that cost no more than the costliest cell of the stretch of path
+    /// it replaces, so paths keeping to roads or out of swamps still do.
+    pub fn smooth_path_with_profile(world: &World, path: &[(f32, f32)], profile: &CostProfile) -> Vec<(f32, f32)> {
+        let corner = |cell: (i32, i32)| (cell.0 as f32 * PATHFINDING_GRID_SIZE, cell.1 as f32 * PATHFINDING_GRID_SIZE);
+        let cost = |cell: (i32, i32)| {
+            let (x, y) = corner(cell);
+            profile.cost_at(world, x, y)
+        };
+
+        Self::pull_taut(path, |from, to| {
+            let limit = path[from..=to]
+                .iter()
+                .filter_map(|&p| cost(Self::cell_of(p)))
+                .fold(0.0, f32::max);
+            Self::line_passable(world, path[from], path[to], |cell| {
+                cell.0 >= 0 && cell.1 >= 0 && cost(cell).is_some_and(|c| c <= limit)
+            })
+        })
+    }
+
+    /// String pull a path, jumping from each waypoint to the farthest later one `shortcut`
+    /// allows reaching by index
+    fn pull_taut(path: &[(f32, f32)], shortcut: impl Fn(usize, usize) -> bool) -> Vec<(f32, f32)> {
+        if path.len() <= 2 {
+            return path.to_vec();
+        }
+
+        let mut smoothed = vec![path[0]];
+        let mut anchor = 0;
+
+        while anchor < path.len() - 1 {
+            let next = (anchor + 2..path.len())
+                .rev()
+                .find(|&i| shortcut(anchor, i))
+                .unwrap_or(anchor + 1);
+
+            smoothed.push(path[next]);
+            anchor = next;
+        }
+
+        smoothed
+    }
+
+    /// Check if a straight line between two points can be walked
+    ///
+    /// Every grid cell the line passes through must be walkable, and the terrain between
+    /// consecutive cells must not be steeper than `PATH_SMOOTHING_MAX_SLOPE_DEGREES`.
+    pub fn walkable_line(world: &World, from: (f32, f32), to: (f32, f32)) -> bool {
+        Self::line_passable(world, from, to, |cell| {
+            cell.0 >= 0
+                && cell.1 >= 0
+                && CollisionDetector::is_walkable(
+                    world,
+                    cell.0 as f32 * PATHFINDING_GRID_SIZE,
+                    cell.1 as f32 * PATHFINDING_GRID_SIZE,
+                )
+        })
+    }
+
+    /// Grid cell containing a world position
+    fn cell_of(p: (f32, f32)) -> (i32, i32) {
+        (
+            (p.0 / PATHFINDING_GRID_SIZE).floor() as i32,
+            (p.1 / PATHFINDING_GRID_SIZE).floor() as i32,
+        )
+    }
+
+    /// Check a straight line against the slope limit, with `walkable` deciding which cells
+    /// it may cross
+    fn line_passable(world: &World, from: (f32, f32), to: (f32, f32), walkable: impl Fn((i32, i32)) -> bool) -> bool {
+        let max_rise = PATH_SMOOTHING_MAX_SLOPE_DEGREES.to_radians().tan();
+
+        let mut cell = Self::cell_of(from);
+        let goal = Self::cell_of(to);
+        if !walkable(cell) {
+            return false;
+        }
+
+        // Walk the cells crossed by the line (Amanatides & Woo traversal).
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let step = (dx.signum() as i32, dy.signum() as i32);
+        let boundary = |position: f32, cell: i32, direction: i32| {
+            (cell + (direction > 0) as i32) as f32 * PATHFINDING_GRID_SIZE - position
+        };
+        let mut t_max = (
+            if dx != 0.0 { boundary(from.0, cell.0, step.0) / dx } else { f32::INFINITY },
+            if dy != 0.0 { boundary(from.1, cell.1, step.1) / dy } else { f32::INFINITY },
+        );
+        let t_delta = (
+            if dx != 0.0 { PATHFINDING_GRID_SIZE / dx.abs() } else { f32::INFINITY },
+            if dy != 0.0 { PATHFINDING_GRID_SIZE / dy.abs() } else { f32::INFINITY },
+        );
+
+        // Guards against rounding carrying the traversal past the goal cell.
+        let mut remaining = (goal.0 - cell.0).abs() + (goal.1 - cell.1).abs();
+        let mut previous_height = Self::cell_height(world, cell);
+        while cell != goal {
+            if (t_max.0 - t_max.1).abs() < f32::EPSILON {
+                // Passing exactly through a corner: both side cells must be open.
+                if !walkable((cell.0 + step.0, cell.1)) || !walkable((cell.0, cell.1 + step.1)) {
+                    return false;
+                }
+                cell = (cell.0 + step.0, cell.1 + step.1);
+                t_max = (t_max.0 + t_delta.0, t_max.1 + t_delta.1);
+            } else if t_max.0 < t_max.1 {
+                cell.0 += step.0;
+                t_max.0 += t_delta.0;
+            } else {
+                cell.1 += step.1;
+                t_max.1 += t_delta.1;
+            }
+
+            remaining -= 1;
+            if !walkable(cell) || remaining < 0 {
+                return false;
+            }
+
+            let height = Self::cell_height(world, cell);
+            if (height - previous_height).abs() > max_rise * PATHFINDING_GRID_SIZE {
+                return false;
+            }
+            previous_height = height;
+        }
+
+        true
+    }
+
+    /// Terrain height at the point where a grid cell's walkability is sampled
+    fn cell_height(world: &World, cell: (i32, i32)) -> f32 {
+        CollisionDetector::get_terrain_height(
+            world,
+            cell.0 as f32 * PATHFINDING_GRID_SIZE,
+            cell.1 as f32 * PATHFINDING_GRID_SIZE,
+        )
+        .unwrap_or(0.0)
+    }
+
     /// Simplify path by removing unnecessary waypoints
     pub fn simplify_path(path: &[(f32, f32)], tolerance: f32) -> Vec<(f32, f32)> {
         if path.len() <= 2 {
@@ -259,6 +515,7 @@ impl Pathfinder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ChunkCoord;
 
     fn create_test_world() -> World {
         let mut world = World::new("Test".to_string(), "game1".to_string(), 5, 5);
@@ -319,4 +576,101 @@ mod tests {
         let world = create_test_world();
         assert!(Pathfinder::path_exists(&world, (0.0, 0.0), (100.0, 100.0)));
     }
+
+    #[test]
+    fn test_smoothed_path_is_straight_on_open_terrain() {
+        let world = create_test_world();
+        let path = Pathfinder::find_smoothed_path(&world, (10.0, 10.0), (300.0, 130.0), 10_000).unwrap();
+
+        assert_eq!(path, vec![(10.0, 10.0), (300.0, 130.0)]);
+    }
+
+    #[test]
+    fn test_smoothed_path_bends_around_obstacle() {
+        let mut world = create_test_world();
+        // Wall of cells at column 8, rows 0..12
+        let chunk = world.chunks.get_mut(&ChunkCoord::new(0, 0)).unwrap();
+        for y in 0..12 {
+            chunk.elevation[(8 * 16) * HEIGHTMAP_RESOLUTION + y * 16] = 250.0;
+        }
+
+        let raw = Pathfinder::find_path(&world, (8.0, 8.0), (200.0, 8.0), 10_000).unwrap();
+        let smoothed = Pathfinder::find_smoothed_path(&world, (8.0, 8.0), (200.0, 8.0), 10_000).unwrap();
+
+        assert!(smoothed.len() > 2);
+        assert!(smoothed.len() < raw.len());
+        // Every shortcut is walkable; other segments are steps of the raw path, which may
+        // cut the wall's corner diagonally.
+        for segment in smoothed.windows(2) {
+            let is_raw_step = raw.windows(2).any(|step| step == segment);
+            assert!(is_raw_step || Pathfinder::walkable_line(&world, segment[0], segment[1]));
+        }
+    }
+
+    #[test]
+    fn test_walkable_line() {
+        let mut world = create_test_world();
+        assert!(Pathfinder::walkable_line(&world, (8.0, 8.0), (200.0, 72.0)));
+
+        // A steep rise between neighboring cells blocks the line without blocking the cells.
+        let chunk = world.chunks.get_mut(&ChunkCoord::new(0, 0)).unwrap();
+        chunk.elevation[(5 * 16) * HEIGHTMAP_RESOLUTION] = 190.0;
+        assert!(Pathfinder::walkable_line(&world, (8.0, 8.0), (60.0, 8.0)));
+        assert!(!Pathfinder::walkable_line(&world, (8.0, 8.0), (120.0, 8.0)));
+    }
+
+    /// Test world with swamps in the chunks (1, 0) and (1, 1), blocking the way east at the top
+    fn create_swamp_world() -> World {
+        let mut world = create_test_world();
+        for y in 0..2 {
+            world.chunks.get_mut(&ChunkCoord::new(1, y)).unwrap().biome = crate::Biome::Swamp;
+        }
+        world
+    }
+
+    fn in_swamp(p: (f32, f32)) -> bool {
+        (CHUNK_SIZE..2.0 * CHUNK_SIZE).contains(&p.0) && p.1 < 2.0 * CHUNK_SIZE
+    }
+
+    #[test]
+    fn test_profile_detours_around_costly_biomes() {
+        let world = create_swamp_world();
+        let (start, goal) = ((200.0, 8.0), (600.0, 8.0));
+
+        let direct = Pathfinder::find_path_with_profile(&world, start, goal, 10_000, &CostProfile::new()).unwrap();
+        assert_eq!(direct, Pathfinder::find_path(&world, start, goal, 10_000).unwrap());
+        assert!(direct.iter().any(|&p| in_swamp(p)));
+
+        let profile = CostProfile::new().with_biome_cost(crate::Biome::Swamp, 10.0);
+        let detour = Pathfinder::find_path_with_profile(&world, start, goal, 10_000, &profile).unwrap();
+        assert!(!detour.iter().any(|&p| in_swamp(p)));
+        assert!(Pathfinder::path_length(&detour) > Pathfinder::path_length(&direct));
+
+        // Smoothing does not cut back through the swamp
+        let smoothed = Pathfinder::find_smoothed_path_with_profile(&world, start, goal, 10_000, &profile).unwrap();
+        assert!(smoothed.len() < detour.len());
+        for segment in smoothed.windows(2) {
+            let (a, b) = (segment[0], segment[1]);
+            let steps = (Pathfinder::path_length(segment) / 4.0).ceil() as usize;
+            for k in 0..=steps {
+                let t = k as f32 / steps.max(1) as f32;
+                assert!(!in_swamp((a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_water_needs_amphibious_profile() {
+        let mut world = create_test_world();
+        // A river of chunks across the whole world
+        for y in 0..5 {
+            world.chunks.get_mut(&ChunkCoord::new(1, y)).unwrap().water_level = 150.0;
+        }
+        let (start, goal) = ((100.0, 100.0), (700.0, 100.0));
+
+        assert!(Pathfinder::find_path_with_profile(&world, start, goal, 10_000, &CostProfile::walker()).is_none());
+        let swimmer = CostProfile::walker().with_water_cost(2.0);
+        let path = Pathfinder::find_path_with_profile(&world, start, goal, 10_000, &swimmer).unwrap();
+        assert!(path.iter().any(|p| CollisionDetector::is_underwater(&world, p.0, p.1)));
+    }
 }
@@ -0,0 +1,491 @@
+//! Procedural placement of structures and points of interest
+//!
+//! Candidate sites are drawn per chunk from the seed, then checked against each rule's biome,
+//! slope and water constraints and against the spacing to everything placed so far. Accepted
+//! sites are built out of world-core structures and registered into their chunks.
+
+use crate::constants::*;
+use crate::erosion::SplitMix64;
+use crate::{Biome, ChunkCoord, World};
+use entropic_world_core::spatial::{Structure, StructureId, StructureType};
+use serde::{Deserialize, Serialize};
+
+/// Structure type used for dungeon entrances
+pub const DUNGEON_ENTRANCE: StructureType = StructureType::Custom(1);
+
+/// Distance between samples when searching for water (in meters)
+const WATER_SEARCH_STEP: f32 = 8.0;
+
+/// Kind of point of interest
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PoiKind {
+    /// Cluster of houses around a market
+    Village,
+    /// Decayed towers and temples
+    Ruins,
+    /// Entrance leading underground
+    Dungeon,
+}
+
+impl PoiKind {
+    /// Get the lowercase name of the kind
+    pub fn name(&self) -> &'static str {
+        match self {
+            PoiKind::Village => "village",
+            PoiKind::Ruins => "ruins",
+            PoiKind::Dungeon => "dungeon",
+        }
+    }
+}
+
+/// Constraints for placing one kind of point of interest
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlacementRule {
+    pub kind: PoiKind,
+    /// Allowed biomes, any biome when empty
+    pub biomes: Vec<Biome>,
+    /// Steepest slope at the site (in degrees)
+    pub max_slope: f32,
+    /// Required distance to the nearest water (in meters)
+    pub water_distance: (f32, f32),
+    /// Minimum distance to any other point of interest (in meters)
+    pub min_spacing: f32,
+    /// Candidate sites tried per chunk
+    pub attempts_per_chunk: u32,
+}
+
+impl PlacementRule {
+    /// Create a rule with the default constraints of a kind
+    pub fn new(kind: PoiKind) -> Self {
+        match kind {
+            PoiKind::Village => Self {
+                kind,
+                biomes: vec![Biome::Plains, Biome::Grassland, Biome::Forest],
+                max_slope: 10.0,
+                water_distance: (8.0, 64.0),
+                min_spacing: 512.0,
+                attempts_per_chunk: 2,
+            },
+            PoiKind::Ruins => Self {
+                kind,
+                biomes: Vec::new(),
+                max_slope: 20.0,
+                water_distance: (8.0, f32::INFINITY),
+                min_spacing: 256.0,
+                attempts_per_chunk: 1,
+            },
+            PoiKind::Dungeon => Self {
+                kind,
+                biomes: vec![Biome::Mountains, Biome::Forest, Biome::Desert, Biome::Tundra],
+                max_slope: 35.0,
+                water_distance: (32.0, f32::INFINITY),
+                min_spacing: 384.0,
+                attempts_per_chunk: 1,
+            },
+        }
+    }
+
+    /// Restrict the rule to some biomes, any biome when empty
+    pub fn with_biomes(mut self, biomes: Vec<Biome>) -> Self {
+        self.biomes = biomes;
+        self
+    }
+
+    /// Set the steepest slope at the site in degrees
+    pub fn with_max_slope(mut self, max_slope: f32) -> Self {
+        self.max_slope = max_slope.clamp(0.0, 90.0);
+        self
+    }
+
+    /// Set the required distance range to the nearest water
+    pub fn with_water_distance(mut self, min: f32, max: f32) -> Self {
+        self.water_distance = (min.max(0.0), max.max(min));
+        self
+    }
+
+    /// Set the minimum distance to any other point of interest
+    pub fn with_min_spacing(mut self, min_spacing: f32) -> Self {
+        self.min_spacing = min_spacing.max(0.0);
+        self
+    }
+
+    /// Set the number of candidate sites tried per chunk
+    pub fn with_attempts_per_chunk(mut self, attempts_per_chunk: u32) -> Self {
+        self.attempts_per_chunk = attempts_per_chunk;
+        self
+    }
+}
+
+/// Point of interest placed into the world
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlacedPoi {
+    pub kind: PoiKind,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub chunk: ChunkCoord,
+    pub biome: Biome,
+    /// Distance to the nearest water found within the search radius
+    pub water_distance: Option<f32>,
+    /// Structures registered for this point of interest
+    pub structures: Vec<StructureId>,
+}
+
+/// Scatters villages, ruins and dungeons over the world
+pub struct StructurePlacer {
+    seed: u32,
+    rules: Vec<PlacementRule>,
+}
+
+impl StructurePlacer {
+    /// Create a placer with the default rule for every kind
+    pub fn new(seed: u32) -> Self {
+        Self {
+            seed,
+            rules: vec![
+                PlacementRule::new(PoiKind::Village),
+                PlacementRule::new(PoiKind::Dungeon),
+                PlacementRule::new(PoiKind::Ruins),
+            ],
+        }
+    }
+
+    /// Create a placer without rules
+    pub fn empty(seed: u32) -> Self {
+        Self {
+            seed,
+            rules: Vec::new(),
+        }
+    }
+
+    /// Add a rule, placed after the existing rules
+    pub fn with_rule(mut self, rule: PlacementRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Get the placement rules in the order they are applied
+    pub fn rules(&self) -> &[PlacementRule] {
+        &self.rules
+    }
+
+    /// Place points of interest over all chunks of a world
+    ///
+    /// Rules are applied in order, each over all chunks sorted by coordinate, so earlier
+    /// rules get the first pick of sites. The result only depends on the seed, the rules and
+    /// the world.
+    pub fn place(&self, world: &mut World) -> Vec<PlacedPoi> {
+        let mut coords: Vec<ChunkCoord> = world.chunks.keys().copied().collect();
+        coords.sort_by_key(|coord| (coord.x, coord.y));
+        let mut placed: Vec<PlacedPoi> = Vec::new();
+
+        for (rule_index, rule) in self.rules.iter().enumerate() {
+            for &coord in &coords {
+                let mut rng = SplitMix64::new(
+                    (((self.seed as u64) << 32) ^ ((coord.x as u64) << 16) ^ coord.y as u64)
+                        .wrapping_add((rule_index as u64 + 1).wrapping_mul(0xA076_1D64_78BD_642F)),
+                );
+
+                for _ in 0..rule.attempts_per_chunk {
+                    let x = (coord.x as f32 + rng.next_f32()) * CHUNK_SIZE;
+                    let y = (coord.y as f32 + rng.next_f32()) * CHUNK_SIZE;
+                    let Some(site) = self.evaluate(world, rule, &placed, x, y) else {
+                        continue;
+                    };
+
+                    let poi_index = placed.len();
+                    let structures = build_structures(rule.kind, x, y, &mut rng)
+                        .into_iter()
+                        .enumerate()
+                        .filter_map(|(n, (structure_type, sx, sy, condition))| {
+                            let id = format!("{}-{}-{}-{}", rule.kind.name(), self.seed, poi_index, n);
+                            let z = sample(world, sx, sy)?.0;
+                            let mut structure = Structure::new(id.clone(), structure_type, sx, sy, z);
+                            structure.condition = condition;
+                            world.chunks.get_mut(&chunk_of(sx, sy))?.add_structure(structure);
+                            Some(id)
+                        })
+                        .collect();
+
+                    placed.push(PlacedPoi {
+                        kind: rule.kind,
+                        x,
+                        y,
+                        z: site.z,
+                        chunk: coord,
+                        biome: site.biome,
+                        water_distance: site.water_distance,
+                        structures,
+                    });
+                }
+            }
+        }
+
+        placed
+    }
+
+    /// Check a candidate site against a rule and the points of interest placed so far
+    fn evaluate(&self, world: &World, rule: &PlacementRule, placed: &[PlacedPoi], x: f32, y: f32) -> Option<Site> {
+        let (z, water_level, biome) = sample(world, x, y)?;
+        if z <= water_level {
+            return None;
+        }
+        if !rule.biomes.is_empty() && !rule.biomes.contains(&biome) {
+            return None;
+        }
+
+        let spacing = rule.min_spacing * rule.min_spacing;
+        if placed.iter().any(|poi| (poi.x - x).powi(2) + (poi.y - y).powi(2) < spacing) {
+            return None;
+        }
+
+        if slope_at(world, x, y)? > rule.max_slope {
+            return None;
+        }
+
+        let (min_water, max_water) = rule.water_distance;
+        let search = if max_water.is_finite() { max_water } else { min_water };
+        let water_distance = nearest_water(world, x, y, search);
+        let distance = water_distance.unwrap_or(f32::INFINITY);
+        if distance < min_water || distance > max_water {
+            return None;
+        }
+
+        Some(Site {
+            z,
+            biome,
+            water_distance,
+        })
+    }
+}
+
+/// Accepted site of a point of interest
+struct Site {
+    z: f32,
+    biome: Biome,
+    water_distance: Option<f32>,
+}
+
+/// Lay out the structures of a point of interest as (type, x, y, condition)
+fn build_structures(kind: PoiKind, x: f32, y: f32, rng: &mut SplitMix64) -> Vec<(StructureType, f32, f32, f32)> {
+    match kind {
+        PoiKind::Village => {
+            let mut structures = vec![(StructureType::Market, x, y, 1.0)];
+            let houses = 4 + (rng.next_u64() % 4) as usize;
+            let start = rng.next_f32() * std::f32::consts::TAU;
+            for i in 0..houses {
+                let angle = start + i as f32 * std::f32::consts::TAU / houses as f32;
+                let radius = 14.0 + rng.next_f32() * 10.0;
+                let structure_type = match i {
+                    0 => StructureType::Inn,
+                    1 => StructureType::Farm,
+                    _ => StructureType::House,
+                };
+                structures.push((structure_type, x + angle.cos() * radius, y + angle.sin() * radius, 1.0));
+            }
+            structures
+        }
+        PoiKind::Ruins => {
+            let count = 1 + (rng.next_u64() % 3) as usize;
+            (0..count)
+                .map(|i| {
+                    let structure_type = if i == 0 {
+                        StructureType::Temple
+                    } else {
+                        StructureType::Tower
+                    };
+                    let offset = (i as f32 * 9.0, (rng.next_f32() - 0.5) * 12.0);
+                    let condition = 0.1 + rng.next_f32() * 0.3;
+                    (structure_type, x + offset.0, y + offset.1, condition)
+                })
+                .collect()
+        }
+        PoiKind::Dungeon => vec![(DUNGEON_ENTRANCE, x, y, 1.0)],
+    }
+}
+
+/// Chunk containing a world position
+fn chunk_of(x: f32, y: f32) -> ChunkCoord {
+    ChunkCoord::new(
+        (x.max(0.0) / CHUNK_SIZE) as u32,
+        (y.max(0.0) / CHUNK_SIZE) as u32,
+    )
+}
+
+/// Sample terrain height, water level and biome at a world position
+fn sample(world: &World, x: f32, y: f32) -> Option<(f32, f32, Biome)> {
+    if x < 0.0 || y < 0.0 {
+        return None;
+    }
+    let coord = chunk_of(x, y);
+    let chunk = world.chunks.get(&coord)?;
+    let scale = HEIGHTMAP_RESOLUTION as f32 / CHUNK_SIZE;
+    let sx = (((x - coord.x as f32 * CHUNK_SIZE) * scale) as usize).min(HEIGHTMAP_RESOLUTION - 1);
+    let sy = (((y - coord.y as f32 * CHUNK_SIZE) * scale) as usize).min(HEIGHTMAP_RESOLUTION - 1);
+    Some((chunk.get_elevation_at(sx, sy)?, chunk.water_level, chunk.biome))
+}
+
+/// Steepest slope around a position in degrees
+fn slope_at(world: &World, x: f32, y: f32) -> Option<f32> {
+    const STEP: f32 = 4.0;
+    let center = sample(world, x, y)?.0;
+    let mut steepest = 0.0f32;
+    for (dx, dy) in [(STEP, 0.0), (-STEP, 0.0), (0.0, STEP), (0.0, -STEP)] {
+        if let Some((height, _, _)) = sample(world, x + dx, y + dy) {
+            steepest = steepest.max((height - center).abs());
+        }
+    }
+    Some((steepest / STEP).atan().to_degrees())
+}
+
+/// Distance to the nearest submerged sample within a radius
+fn nearest_water(world: &World, x: f32, y: f32, radius: f32) -> Option<f32> {
+    let rings = (radius / WATER_SEARCH_STEP).ceil() as i32;
+    let mut nearest: Option<f32> = None;
+    for ring in 0..=rings {
+        let distance = ring as f32 * WATER_SEARCH_STEP;
+        if nearest.is_some_and(|found| found <= distance) {
+            break;
+        }
+        for dx in -ring..=ring {
+            for dy in -ring..=ring {
+                if dx.abs() != ring && dy.abs() != ring {
+                    continue;
+                }
+                let px = x + dx as f32 * WATER_SEARCH_STEP;
+                let py = y + dy as f32 * WATER_SEARCH_STEP;
+                let Some((height, water_level, _)) = sample(world, px, py) else {
+                    continue;
+                };
+                let d = ((px - x).powi(2) + (py - y).powi(2)).sqrt();
+                if height <= water_level && d <= radius {
+                    nearest = Some(nearest.map_or(d, |n| n.min(d)));
+                }
+            }
+        }
+    }
+    nearest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_world(terrain: impl Fn(f32, f32) -> f32) -> World {
+        let mut world = World::new("Test".to_string(), "game1".to_string(), 4, 4);
+        world.initialize_chunks();
+
+        for chunk in world.chunks.values_mut() {
+            chunk.water_level = 50.0;
+            chunk.biome = Biome::Plains;
+            let origin = (chunk.coord.x as f32 * CHUNK_SIZE, chunk.coord.y as f32 * CHUNK_SIZE);
+            for sy in 0..HEIGHTMAP_RESOLUTION {
+                for sx in 0..HEIGHTMAP_RESOLUTION {
+                    chunk.elevation[sy * HEIGHTMAP_RESOLUTION + sx] =
+                        terrain(origin.0 + sx as f32, origin.1 + sy as f32);
+                }
+            }
+        }
+
+        world
+    }
+
+    /// Flat land crossed by rivers every 128 meters
+    fn riverlands(x: f32, _y: f32) -> f32 {
+        if x % 128.0 < 8.0 {
+            40.0
+        } else {
+            100.0
+        }
+    }
+
+    #[test]
+    fn test_villages_near_water_with_spacing() {
+        let mut world = create_test_world(riverlands);
+        let rule = PlacementRule::new(PoiKind::Village)
+            .with_min_spacing(300.0)
+            .with_attempts_per_chunk(4);
+        let placed = StructurePlacer::empty(1).with_rule(rule).place(&mut world);
+
+        assert!(!placed.is_empty());
+        for (i, poi) in placed.iter().enumerate() {
+            assert_eq!(poi.kind, PoiKind::Village);
+            let water = poi.water_distance.unwrap();
+            assert!((8.0..=64.0).contains(&water));
+            for other in &placed[i + 1..] {
+                assert!(((poi.x - other.x).powi(2) + (poi.y - other.y).powi(2)).sqrt() >= 300.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_structures_registered_in_chunks() {
+        let mut world = create_test_world(|_, _| 100.0);
+        let placed = StructurePlacer::empty(5)
+            .with_rule(PlacementRule::new(PoiKind::Ruins).with_min_spacing(300.0))
+            .with_rule(
+                PlacementRule::new(PoiKind::Dungeon)
+                    .with_biomes(Vec::new())
+                    .with_min_spacing(150.0)
+                    .with_attempts_per_chunk(4),
+            )
+            .place(&mut world);
+
+        assert!(placed.iter().any(|poi| poi.kind == PoiKind::Ruins));
+        assert!(placed.iter().any(|poi| poi.kind == PoiKind::Dungeon));
+
+        for chunk in world.chunks.values() {
+            for structure in &chunk.structures {
+                assert_eq!(chunk_of(structure.x, structure.y), chunk.coord);
+            }
+        }
+
+        let registered: Vec<&Structure> = world.chunks.values().flat_map(|chunk| &chunk.structures).collect();
+        for poi in &placed {
+            assert!(!poi.structures.is_empty());
+            for id in &poi.structures {
+                let structure = registered.iter().find(|s| &s.id == id).unwrap();
+                match poi.kind {
+                    PoiKind::Ruins => assert!(structure.condition < 0.5),
+                    PoiKind::Dungeon => assert_eq!(structure.structure_type, DUNGEON_ENTRANCE),
+                    PoiKind::Village => {}
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_biome_and_slope_constraints() {
+        // Steep ridges everywhere except on the flat west half
+        let mut world = create_test_world(|x, y| if x < 512.0 { 100.0 } else { 100.0 + (y % 16.0) * 4.0 });
+        for chunk in world.chunks.values_mut().filter(|chunk| chunk.coord.y >= 2) {
+            chunk.biome = Biome::Desert;
+        }
+
+        let rule = PlacementRule::new(PoiKind::Ruins)
+            .with_biomes(vec![Biome::Plains])
+            .with_max_slope(10.0)
+            .with_min_spacing(64.0)
+            .with_attempts_per_chunk(8);
+        let placed = StructurePlacer::empty(9).with_rule(rule).place(&mut world);
+
+        assert!(!placed.is_empty());
+        for poi in &placed {
+            assert_eq!(poi.biome, Biome::Plains);
+            assert!(poi.x < 512.0 + 4.0);
+        }
+    }
+
+    #[test]
+    fn test_deterministic_placement() {
+        let mut world1 = create_test_world(riverlands);
+        let mut world2 = create_test_world(riverlands);
+
+        let placed1 = StructurePlacer::new(3).place(&mut world1);
+        let placed2 = StructurePlacer::new(3).place(&mut world2);
+        let placed3 = StructurePlacer::new(4).place(&mut create_test_world(riverlands));
+
+        assert_eq!(placed1, placed2);
+        assert_ne!(placed1, placed3);
+    }
+}
@@ -0,0 +1,195 @@
+//! Customizable chunk load priorities
+//!
+//! The chunk manager works out a default priority for every chunk an observer needs, from its
+//! distance and whether it lies on the predicted path, and then asks a [`PriorityPolicy`] for
+//! the priority actually queued. Policies can raise chunks holding quest objectives or
+//! settlements, favor chunks in front of the camera, or replace the heuristic entirely.
+
+use crate::chunk_manager::Priority;
+use crate::constants::*;
+use crate::ChunkCoord;
+use std::collections::HashMap;
+
+/// What is known about a chunk when prioritizing it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PriorityContext {
+    pub coord: ChunkCoord,
+    /// Position of the observer requiring the chunk (in meters)
+    pub observer: (f32, f32),
+    /// Unit direction the observer faces or moves in, if known
+    pub heading: Option<(f32, f32)>,
+    /// Distance from the observer's chunk (in chunks, along the longer axis)
+    pub distance: u32,
+    /// Whether the chunk was required by prefetching along the predicted path
+    pub prefetch: bool,
+    /// Priority from the built-in distance heuristic
+    pub default: Priority,
+}
+
+impl PriorityContext {
+    /// Check whether the chunk center lies within a cone around the heading
+    ///
+    /// Without a heading every chunk counts as in view. The observer's own chunk always does.
+    pub fn in_view_cone(&self, half_angle_degrees: f32) -> bool {
+        let Some((hx, hy)) = self.heading else {
+            return true;
+        };
+        let cx = (self.coord.x as f32 + 0.5) * CHUNK_SIZE - self.observer.0;
+        let cy = (self.coord.y as f32 + 0.5) * CHUNK_SIZE - self.observer.1;
+        let length = (cx * cx + cy * cy).sqrt();
+        if self.distance == 0 || length <= f32::EPSILON {
+            return true;
+        }
+        (cx * hx + cy * hy) / length >= half_angle_degrees.to_radians().cos()
+    }
+}
+
+/// Decides the load priority of chunks required by observers
+pub trait PriorityPolicy: Send + Sync {
+    fn priority(&self, context: &PriorityContext) -> Priority;
+}
+
+impl<F> PriorityPolicy for F
+where
+    F: Fn(&PriorityContext) -> Priority + Send + Sync,
+{
+    fn priority(&self, context: &PriorityContext) -> Priority {
+        self(context)
+    }
+}
+
+/// Built-in policy using the distance heuristic as it is
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DistancePriority;
+
+impl PriorityPolicy for DistancePriority {
+    fn priority(&self, context: &PriorityContext) -> Priority {
+        context.default
+    }
+}
+
+/// Distance heuristic with chunks of gameplay importance raised to a minimum priority
+#[derive(Clone, Debug, Default)]
+pub struct BoostedPriority {
+    boosts: HashMap<ChunkCoord, Priority>,
+    cone: Option<f32>,
+}
+
+impl BoostedPriority {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep a chunk at `priority` or above, e.g. for a quest objective or a settlement
+    pub fn with_boost(mut self, coord: ChunkCoord, priority: Priority) -> Self {
+        self.boost(coord, priority);
+        self
+    }
+
+    /// Raise chunks within a cone (half angle in degrees) around the observer heading one step
+    pub fn with_view_cone(mut self, half_angle_degrees: f32) -> Self {
+        self.cone = Some(half_angle_degrees.clamp(0.0, 180.0));
+        self
+    }
+
+    /// Keep a chunk at `priority` or above
+    pub fn boost(&mut self, coord: ChunkCoord, priority: Priority) {
+        self.boosts.insert(coord, priority);
+    }
+
+    /// Stop boosting a chunk
+    pub fn clear_boost(&mut self, coord: ChunkCoord) {
+        self.boosts.remove(&coord);
+    }
+}
+
+impl PriorityPolicy for BoostedPriority {
+    fn priority(&self, context: &PriorityContext) -> Priority {
+        let mut priority = context.default;
+        if self.cone.is_some_and(|cone| context.in_view_cone(cone)) {
+            priority = raise(priority);
+        }
+        match self.boosts.get(&context.coord) {
+            Some(&boost) => priority.max(boost),
+            None => priority,
+        }
+    }
+}
+
+/// Next priority up, staying at critical
+fn raise(priority: Priority) -> Priority {
+    match priority {
+        Priority::Low => Priority::Normal,
+        Priority::Normal => Priority::High,
+        Priority::High | Priority::Critical => Priority::Critical,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk_manager::{ChunkManager, Observer};
+    use crate::World;
+    use std::sync::{Arc, RwLock};
+
+    fn context(coord: ChunkCoord, heading: Option<(f32, f32)>) -> PriorityContext {
+        PriorityContext {
+            coord,
+            observer: (128.0, 128.0),
+            heading,
+            distance: coord.x.max(coord.y),
+            prefetch: false,
+            default: Priority::Low,
+        }
+    }
+
+    fn queued(manager: &ChunkManager, coord: ChunkCoord) -> Option<Priority> {
+        manager
+            .queued_chunks()
+            .into_iter()
+            .find(|(c, _)| *c == coord)
+            .map(|(_, p)| p)
+    }
+
+    #[test]
+    fn test_view_cone() {
+        let east = Some((1.0, 0.0));
+        assert!(context(ChunkCoord::new(3, 0), east).in_view_cone(30.0));
+        assert!(!context(ChunkCoord::new(0, 3), east).in_view_cone(30.0));
+        assert!(context(ChunkCoord::new(0, 3), None).in_view_cone(30.0));
+        assert!(context(ChunkCoord::new(0, 0), east).in_view_cone(0.0));
+    }
+
+    #[test]
+    fn test_boosted_priority() {
+        let policy = BoostedPriority::new()
+            .with_boost(ChunkCoord::new(4, 4), Priority::High)
+            .with_view_cone(30.0);
+        let east = Some((1.0, 0.0));
+
+        assert_eq!(policy.priority(&context(ChunkCoord::new(4, 4), None)), Priority::High);
+        assert_eq!(policy.priority(&context(ChunkCoord::new(3, 0), east)), Priority::Normal);
+        assert_eq!(policy.priority(&context(ChunkCoord::new(0, 3), east)), Priority::Low);
+        assert_eq!(DistancePriority.priority(&context(ChunkCoord::new(4, 4), None)), Priority::Low);
+    }
+
+    #[tokio::test]
+    async fn test_manager_uses_policy() {
+        let world = Arc::new(RwLock::new(World::new("Test".to_string(), "game1".to_string(), 10, 10)));
+        let settlement = ChunkCoord::new(4, 2);
+        let policy = BoostedPriority::new().with_boost(settlement, Priority::Critical);
+        let manager = ChunkManager::new(world.clone(), 3).with_priority_policy(Arc::new(policy));
+
+        manager.update_visible_chunks(300.0, 600.0).await.unwrap();
+        assert_eq!(queued(&manager, settlement), Some(Priority::Critical));
+        assert_eq!(manager.queued_chunks()[0].1, Priority::Critical);
+
+        // Closures work as policies too
+        let prefetch_last = |c: &PriorityContext| if c.prefetch { Priority::Low } else { c.default };
+        let manager = ChunkManager::new(world, 2).with_priority_policy(Arc::new(prefetch_last));
+        manager.add_observer(Observer::new(300.0, 300.0).with_velocity(200.0, 0.0));
+        manager.update_observers().await.unwrap();
+        assert_eq!(queued(&manager, ChunkCoord::new(3, 1)), Some(Priority::Normal));
+        assert_eq!(queued(&manager, ChunkCoord::new(4, 1)), Some(Priority::Low));
+    }
+}
@@ -0,0 +1,399 @@
+//! Deterministic Poisson-disk scattering
+//!
+//! Space is split into a grid of cells `radius / sqrt(2)` wide, and every cell draws its
+//! candidate points and their priorities from the seed and its own coordinates. Candidates are
+//! accepted greedily from the highest priority down, so a candidate is kept unless a kept
+//! candidate with a higher priority lies within the radius. Each decision only depends on
+//! nearby cells, so sampling a chunk on its own gives the same points along its borders as
+//! sampling it together with its neighbors. Density then thins the accepted points with a
+//! seeded roll per point.
+
+use crate::constants::*;
+use crate::erosion::SplitMix64;
+use crate::{Biome, ChunkCoord, World};
+use std::collections::HashMap;
+
+/// Candidate identified by its cell and its index within the cell
+type CandidateKey = (i64, i64, usize);
+
+/// Point scattered over the terrain of a world
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScatterPoint {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub chunk: ChunkCoord,
+    pub biome: Biome,
+}
+
+/// Seeded Poisson-disk sampler for trees, rocks and resource nodes
+#[derive(Clone, Debug)]
+pub struct PoissonSampler {
+    seed: u32,
+    radius: f32,
+    candidates_per_cell: u32,
+    default_density: f32,
+    densities: Vec<(Biome, f32)>,
+}
+
+impl PoissonSampler {
+    /// Create a sampler keeping points at least `radius` meters apart
+    pub fn new(seed: u32, radius: f32) -> Self {
+        Self {
+            seed,
+            radius: radius.max(0.1),
+            candidates_per_cell: SCATTER_CANDIDATES_PER_CELL,
+            default_density: 1.0,
+            densities: Vec::new(),
+        }
+    }
+
+    /// Set the number of candidates drawn per grid cell
+    ///
+    /// More candidates fill the space more tightly at a higher cost.
+    pub fn with_candidates_per_cell(mut self, candidates_per_cell: u32) -> Self {
+        self.candidates_per_cell = candidates_per_cell.max(1);
+        self
+    }
+
+    /// Set the density of biomes without their own density
+    pub fn with_default_density(mut self, density: f32) -> Self {
+        self.default_density = density.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the density of a biome, from 0 (empty) to 1 (as dense as the radius allows)
+    pub fn with_density(mut self, biome: Biome, density: f32) -> Self {
+        let density = density.clamp(0.0, 1.0);
+        match self.densities.iter_mut().find(|(b, _)| *b == biome) {
+            Some(entry) => entry.1 = density,
+            None => self.densities.push((biome, density)),
+        }
+        self
+    }
+
+    /// Get the minimum distance between points (in meters)
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    /// Get the density of a biome
+    pub fn density(&self, biome: Biome) -> f32 {
+        self.densities
+            .iter()
+            .find(|(b, _)| *b == biome)
+            .map_or(self.default_density, |(_, density)| *density)
+    }
+
+    /// Sample points in the rectangle `[min, max)`
+    ///
+    /// `density` gives the fraction in [0, 1] of points kept around a position. The result is
+    /// stable across region borders as long as it only depends on the position.
+    pub fn sample_region(
+        &self,
+        min: (f32, f32),
+        max: (f32, f32),
+        density: impl Fn(f32, f32) -> f32,
+    ) -> Vec<(f32, f32)> {
+        let mut sampling = Sampling {
+            sampler: self,
+            cells: HashMap::new(),
+            accepted: HashMap::new(),
+        };
+        let cell_size = self.cell_size();
+        let (x0, y0) = ((min.0 / cell_size).floor() as i64, (min.1 / cell_size).floor() as i64);
+        let (x1, y1) = ((max.0 / cell_size).ceil() as i64, (max.1 / cell_size).ceil() as i64);
+
+        let mut points = Vec::new();
+        for cy in y0..y1 {
+            for cx in x0..x1 {
+                for i in 0..sampling.cell(cx, cy).len() {
+                    let candidate = sampling.cell(cx, cy)[i];
+                    let inside = candidate.x >= min.0
+                        && candidate.y >= min.1
+                        && candidate.x < max.0
+                        && candidate.y < max.1;
+                    if inside
+                        && candidate.roll < density(candidate.x, candidate.y)
+                        && sampling.is_accepted((cx, cy, i))
+                    {
+                        points.push((candidate.x, candidate.y));
+                    }
+                }
+            }
+        }
+
+        points
+    }
+
+    /// Sample the points of one chunk
+    pub fn sample_chunk(&self, coord: ChunkCoord, density: impl Fn(f32, f32) -> f32) -> Vec<(f32, f32)> {
+        let min = (coord.x as f32 * CHUNK_SIZE, coord.y as f32 * CHUNK_SIZE);
+        self.sample_region(min, (min.0 + CHUNK_SIZE, min.1 + CHUNK_SIZE), density)
+    }
+
+    /// Scatter points over one chunk of a world, using the biome densities
+    ///
+    /// Points at or below the chunk water level are dropped.
+    pub fn scatter_chunk(&self, world: &World, coord: ChunkCoord) -> Vec<ScatterPoint> {
+        self.sample_chunk(coord, |x, y| self.world_density(world, x, y))
+            .into_iter()
+            .filter_map(|(x, y)| surface_point(world, x, y))
+            .collect()
+    }
+
+    /// Scatter points over all chunks of a world, using the biome densities
+    ///
+    /// Points at or below the chunk water level are dropped.
+    pub fn scatter(&self, world: &World) -> Vec<ScatterPoint> {
+        let Some(max) = world.chunks.keys().fold(None, |max: Option<(u32, u32)>, coord| {
+            Some(max.map_or((coord.x, coord.y), |(x, y)| (x.max(coord.x), y.max(coord.y))))
+        }) else {
+            return Vec::new();
+        };
+
+        let max = ((max.0 + 1) as f32 * CHUNK_SIZE, (max.1 + 1) as f32 * CHUNK_SIZE);
+        self.sample_region((0.0, 0.0), max, |x, y| self.world_density(world, x, y))
+            .into_iter()
+            .filter_map(|(x, y)| surface_point(world, x, y))
+            .collect()
+    }
+
+    /// Density at a world position from the biome of its chunk, zero outside the world
+    fn world_density(&self, world: &World, x: f32, y: f32) -> f32 {
+        if x < 0.0 || y < 0.0 {
+            return 0.0;
+        }
+        world
+            .chunks
+            .get(&chunk_of(x, y))
+            .map_or(0.0, |chunk| self.density(chunk.biome))
+    }
+
+    fn cell_size(&self) -> f32 {
+        self.radius / std::f32::consts::SQRT_2
+    }
+
+    /// Draw the candidates of a cell
+    fn candidates(&self, cx: i64, cy: i64) -> Vec<Candidate> {
+        let mut rng = SplitMix64::new(
+            ((self.seed as u64) << 32)
+                ^ (cx as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+                ^ (cy as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F),
+        );
+        let cell_size = self.cell_size();
+
+        (0..self.candidates_per_cell)
+            .map(|_| Candidate {
+                x: (cx as f32 + rng.next_f32()) * cell_size,
+                y: (cy as f32 + rng.next_f32()) * cell_size,
+                priority: rng.next_u64(),
+                roll: rng.next_f32(),
+            })
+            .collect()
+    }
+}
+
+/// Candidate point drawn for a cell
+#[derive(Clone, Copy)]
+struct Candidate {
+    x: f32,
+    y: f32,
+    priority: u64,
+    /// Thinning roll compared against the density
+    roll: f32,
+}
+
+/// Cached state of one sampling pass
+struct Sampling<'a> {
+    sampler: &'a PoissonSampler,
+    cells: HashMap<(i64, i64), Vec<Candidate>>,
+    accepted: HashMap<CandidateKey, bool>,
+}
+
+impl Sampling<'_> {
+    fn cell(&mut self, cx: i64, cy: i64) -> &[Candidate] {
+        let sampler = self.sampler;
+        self.cells
+            .entry((cx, cy))
+            .or_insert_with(|| sampler.candidates(cx, cy))
+    }
+
+    /// Check whether a candidate survives greedy acceptance by priority
+    ///
+    /// Ties on priority are broken by the key, so the order is total and the recursion only
+    /// ever climbs to strictly higher candidates.
+    fn is_accepted(&mut self, key: CandidateKey) -> bool {
+        if let Some(&accepted) = self.accepted.get(&key) {
+            return accepted;
+        }
+
+        let candidate = self.cell(key.0, key.1)[key.2];
+        let radius = self.sampler.radius;
+        let reach = (radius / self.sampler.cell_size()).ceil() as i64;
+
+        let mut rivals = Vec::new();
+        for cy in key.1 - reach..=key.1 + reach {
+            for cx in key.0 - reach..=key.0 + reach {
+                for (i, other) in self.cell(cx, cy).iter().enumerate() {
+                    let distance = (other.x - candidate.x).powi(2) + (other.y - candidate.y).powi(2);
+                    if distance < radius * radius
+                        && (other.priority, (cx, cy, i)) > (candidate.priority, key)
+                    {
+                        rivals.push((other.priority, (cx, cy, i)));
+                    }
+                }
+            }
+        }
+
+        // The strongest rivals are the most likely to have been kept
+        rivals.sort_unstable_by(|a, b| b.cmp(a));
+        let accepted = !rivals.into_iter().any(|(_, rival)| self.is_accepted(rival));
+        self.accepted.insert(key, accepted);
+        accepted
+    }
+}
+
+/// Chunk containing a world position
+fn chunk_of(x: f32, y: f32) -> ChunkCoord {
+    ChunkCoord::new(
+        (x.max(0.0) / CHUNK_SIZE) as u32,
+        (y.max(0.0) / CHUNK_SIZE) as u32,
+    )
+}
+
+/// Place a point on the terrain, unless it is missing or under water
+fn surface_point(world: &World, x: f32, y: f32) -> Option<ScatterPoint> {
+    let coord = chunk_of(x, y);
+    let chunk = world.chunks.get(&coord)?;
+    let scale = HEIGHTMAP_RESOLUTION as f32 / CHUNK_SIZE;
+    let sx = (((x - coord.x as f32 * CHUNK_SIZE) * scale) as usize).min(HEIGHTMAP_RESOLUTION - 1);
+    let sy = (((y - coord.y as f32 * CHUNK_SIZE) * scale) as usize).min(HEIGHTMAP_RESOLUTION - 1);
+    let z = chunk.get_elevation_at(sx, sy)?;
+    if z <= chunk.water_level {
+        return None;
+    }
+
+    Some(ScatterPoint {
+        x,
+        y,
+        z,
+        chunk: coord,
+        biome: chunk.biome,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_world() -> World {
+        let mut world = World::new("Test".to_string(), "game1".to_string(), 2, 2);
+        world.initialize_chunks();
+
+        for chunk in world.chunks.values_mut() {
+            chunk.water_level = 50.0;
+            chunk.elevation.iter_mut().for_each(|h| *h = 100.0);
+            chunk.biome = if chunk.coord.x == 0 {
+                Biome::Forest
+            } else {
+                Biome::Plains
+            };
+        }
+
+        world
+    }
+
+    fn min_distance(points: &[(f32, f32)]) -> f32 {
+        let mut min = f32::INFINITY;
+        for (i, a) in points.iter().enumerate() {
+            for b in &points[i + 1..] {
+                min = min.min(((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt());
+            }
+        }
+        min
+    }
+
+    #[test]
+    fn test_points_keep_their_distance() {
+        let sampler = PoissonSampler::new(1, 8.0);
+        let points = sampler.sample_region((0.0, 0.0), (256.0, 256.0), |_, _| 1.0);
+
+        assert!(min_distance(&points) >= 8.0);
+        // Dense packing of 8 meter disks fits about 1180 points, random packing over half that
+        assert!(points.len() > 550, "only {} points", points.len());
+        assert!(points.iter().all(|&(x, y)| (0.0..256.0).contains(&x) && (0.0..256.0).contains(&y)));
+    }
+
+    #[test]
+    fn test_chunks_agree_across_borders() {
+        let sampler = PoissonSampler::new(3, 6.0);
+        let mut whole = sampler.sample_region((0.0, 0.0), (2.0 * CHUNK_SIZE, 2.0 * CHUNK_SIZE), |_, _| 1.0);
+
+        let mut pieces = Vec::new();
+        for y in 0..2 {
+            for x in 0..2 {
+                pieces.extend(sampler.sample_chunk(ChunkCoord::new(x, y), |_, _| 1.0));
+            }
+        }
+
+        let key = |p: &(f32, f32)| (p.0.to_bits(), p.1.to_bits());
+        whole.sort_by_key(key);
+        pieces.sort_by_key(key);
+        assert_eq!(whole, pieces);
+        assert!(min_distance(&pieces) >= 6.0);
+    }
+
+    #[test]
+    fn test_seeded_sampling() {
+        let a = PoissonSampler::new(7, 5.0).sample_region((0.0, 0.0), (64.0, 64.0), |_, _| 1.0);
+        let b = PoissonSampler::new(7, 5.0).sample_region((0.0, 0.0), (64.0, 64.0), |_, _| 1.0);
+        let c = PoissonSampler::new(8, 5.0).sample_region((0.0, 0.0), (64.0, 64.0), |_, _| 1.0);
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_biome_density() {
+        let world = create_test_world();
+        let sampler = PoissonSampler::new(11, 10.0)
+            .with_density(Biome::Forest, 1.0)
+            .with_density(Biome::Plains, 0.1);
+
+        let points = sampler.scatter(&world);
+        let forest = points.iter().filter(|p| p.biome == Biome::Forest).count();
+        let plains = points.iter().filter(|p| p.biome == Biome::Plains).count();
+
+        assert!(forest > 3 * plains, "{} forest, {} plains", forest, plains);
+        assert!(plains > 0);
+        assert!(points.iter().all(|p| p.z == 100.0 && p.chunk == chunk_of(p.x, p.y)));
+
+        // Chunks scattered alone match the whole world
+        let mut alone: Vec<ScatterPoint> = world
+            .chunks
+            .keys()
+            .flat_map(|&coord| sampler.scatter_chunk(&world, coord))
+            .collect();
+        alone.sort_by(|a, b| (a.x, a.y).partial_cmp(&(b.x, b.y)).unwrap());
+        let mut points = points;
+        points.sort_by(|a, b| (a.x, a.y).partial_cmp(&(b.x, b.y)).unwrap());
+        assert_eq!(alone, points);
+    }
+
+    #[test]
+    fn test_empty_biomes_and_water() {
+        let mut world = create_test_world();
+        for chunk in world.chunks.values_mut() {
+            if chunk.coord.y == 1 {
+                chunk.water_level = 150.0;
+            }
+        }
+
+        let sampler = PoissonSampler::new(2, 10.0).with_density(Biome::Plains, 0.0);
+        let points = sampler.scatter(&world);
+
+        assert!(!points.is_empty());
+        assert!(points.iter().all(|p| p.chunk == ChunkCoord::new(0, 0)));
+    }
+}
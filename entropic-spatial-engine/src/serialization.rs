@@ -1,5 +1,7 @@
+use crate::determinism::{fnv1a, FNV_OFFSET};
 use crate::errors::SpatialError;
 use crate::{Chunk, ChunkCoord};
+use entropic_world_core::observability::{self, increment_counter, Timer};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
@@ -26,6 +28,20 @@ pub struct SerializedStructure {
     pub z: f32,
 }
 
+/// Binary chunk with a checksum and the terrain recipe it belongs to, as cached on disk or streamed
+///
+/// The checksum covers the coordinate and the encoded chunk, so corrupted or mislabeled data is
+/// caught before the chunk reaches the world. The recipe is the
+/// [`TerrainSource::recipe`](crate::TerrainSource::recipe) fingerprint of the terrain the chunk
+/// was made for, so chunks of an older or different terrain are rejected as stale.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkPacket {
+    pub coord: ChunkCoord,
+    pub recipe: u64,
+    pub checksum: u64,
+    pub payload: Vec<u8>,
+}
+
 /// Chunk persistence manager
 pub struct ChunkSerializer;
 
@@ -132,6 +148,89 @@ impl ChunkSerializer {
     }
 }
 
+impl ChunkPacket {
+    /// Encode a chunk made for a terrain recipe
+    pub fn new(chunk: &Chunk, recipe: u64) -> Result<Self, SpatialError> {
+        let payload = ChunkSerializer::serialize_chunk(chunk)?;
+        Ok(Self {
+            coord: chunk.coord,
+            recipe,
+            checksum: packet_checksum(chunk.coord, &payload),
+            payload,
+        })
+    }
+
+    /// Serialize the packet to bytes
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SpatialError> {
+        bincode::serialize(self)
+            .map_err(|e| SpatialError::SerializationError {
+                message: e.to_string(),
+            })
+    }
+
+    /// Deserialize a packet from bytes, without verifying it
+    pub fn from_bytes(data: &[u8]) -> Result<Self, SpatialError> {
+        bincode::deserialize(data)
+            .map_err(|e| SpatialError::DeserializationError {
+                message: e.to_string(),
+            })
+    }
+
+    /// Check that the packet holds an intact chunk at a coordinate, made for a terrain recipe
+    pub fn verify(&self, coord: ChunkCoord, recipe: u64) -> Result<(), SpatialError> {
+        if self.coord != coord {
+            return Err(SpatialError::UnexpectedChunk {
+                expected: coord,
+                found: self.coord,
+            });
+        }
+
+        let actual = packet_checksum(self.coord, &self.payload);
+        if actual != self.checksum {
+            return Err(SpatialError::ChunkChecksumMismatch {
+                coord,
+                expected: self.checksum,
+                actual,
+            });
+        }
+
+        if self.recipe != recipe {
+            return Err(SpatialError::StaleChunk {
+                coord,
+                expected: recipe,
+                found: self.recipe,
+            });
+        }
+        Ok(())
+    }
+
+    /// Verify the packet and decode its chunk
+    pub fn open(&self, coord: ChunkCoord, recipe: u64) -> Result<Chunk, SpatialError> {
+        let _timer = Timer::start(observability::CHUNK_VALIDATION_DURATION_SECONDS, &[]);
+        let opened = self.verify(coord, recipe).and_then(|()| {
+            let chunk = ChunkSerializer::deserialize_chunk(&self.payload)?;
+            if chunk.coord != coord {
+                return Err(SpatialError::UnexpectedChunk {
+                    expected: coord,
+                    found: chunk.coord,
+                });
+            }
+            Ok(chunk)
+        });
+        if opened.is_err() {
+            increment_counter(observability::CHUNK_VALIDATION_FAILURES_TOTAL, &[], 1);
+        }
+        opened
+    }
+}
+
+/// FNV-1a checksum of a chunk coordinate and its encoded chunk
+fn packet_checksum(coord: ChunkCoord, payload: &[u8]) -> u64 {
+    let hash = fnv1a(FNV_OFFSET, &coord.x.to_le_bytes());
+    let hash = fnv1a(hash, &coord.y.to_le_bytes());
+    fnv1a(hash, payload)
+}
+
 impl SerializedChunk {
     /// Convert from Chunk
     fn from_chunk(chunk: &Chunk) -> Self {
@@ -224,4 +323,27 @@ mod tests {
 
         assert_eq!(deserialized.len(), 2);
     }
+
+    #[test]
+    fn test_chunk_packet_verification() {
+        let chunk = create_test_chunk();
+        let coord = chunk.coord;
+        let packet = ChunkPacket::from_bytes(&ChunkPacket::new(&chunk, 7).unwrap().to_bytes().unwrap()).unwrap();
+        assert_eq!(packet.open(coord, 7).unwrap().elevation, chunk.elevation);
+
+        let mut corrupted = packet.clone();
+        corrupted.payload[40] ^= 0x10;
+        assert!(matches!(
+            corrupted.open(coord, 7),
+            Err(SpatialError::ChunkChecksumMismatch { expected, .. }) if expected == packet.checksum
+        ));
+        assert!(matches!(
+            packet.open(coord, 8),
+            Err(SpatialError::StaleChunk { expected: 8, found: 7, .. })
+        ));
+        assert!(matches!(
+            packet.open(ChunkCoord::new(0, 0), 7),
+            Err(SpatialError::UnexpectedChunk { .. })
+        ));
+    }
 }
@@ -0,0 +1,724 @@
+//! Collision shapes
+//!
+//! Axis-aligned boxes, oriented boxes, capsules and spheres in world meters, with `z` pointing
+//! up like entity elevation. Shapes are described around an entity's origin and moved to the
+//! entity with [`CollisionShape::at`] before testing them against other shapes or, through
+//! [`CollisionDetector`](crate::CollisionDetector), against the terrain.
+
+use crate::Entity;
+use serde::{Deserialize, Serialize};
+
+/// Point or direction in world meters
+pub type Vec3 = [f32; 3];
+
+/// Lengths below this are treated as zero
+const EPSILON: f32 = 1e-6;
+
+/// Ternary search steps when minimizing a convex function along a segment
+const SEGMENT_SEARCH_STEPS: usize = 48;
+
+/// Gap at which a cast shape counts as touching its target (in meters)
+const CONTACT_TOLERANCE: f32 = 1e-3;
+
+/// Conservative advancement steps before a cast gives up on a grazing target
+const MAX_CAST_ITERATIONS: usize = 64;
+
+/// Axis-aligned box
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    /// Create a box from two opposite corners
+    pub fn new(a: Vec3, b: Vec3) -> Self {
+        Self {
+            min: [a[0].min(b[0]), a[1].min(b[1]), a[2].min(b[2])],
+            max: [a[0].max(b[0]), a[1].max(b[1]), a[2].max(b[2])],
+        }
+    }
+
+    /// Create a box from its center and half of its size along each axis
+    pub fn from_center(center: Vec3, half_extents: Vec3) -> Self {
+        let half_extents = half_extents.map(f32::abs);
+        Self {
+            min: sub(center, half_extents),
+            max: add(center, half_extents),
+        }
+    }
+
+    pub fn center(&self) -> Vec3 {
+        scale(add(self.min, self.max), 0.5)
+    }
+
+    pub fn half_extents(&self) -> Vec3 {
+        scale(sub(self.max, self.min), 0.5)
+    }
+
+    /// Check if a point is inside the box or on its surface
+    pub fn contains(&self, point: Vec3) -> bool {
+        (0..3).all(|i| point[i] >= self.min[i] && point[i] <= self.max[i])
+    }
+
+    /// Check if two boxes overlap
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        (0..3).all(|i| self.min[i] < other.max[i] && self.max[i] > other.min[i])
+    }
+
+    /// Smallest box holding both boxes
+    pub fn union(&self, other: &Aabb) -> Self {
+        Self {
+            min: [0, 1, 2].map(|i| self.min[i].min(other.min[i])),
+            max: [0, 1, 2].map(|i| self.max[i].max(other.max[i])),
+        }
+    }
+}
+
+/// Box turned to its own axes
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Obb {
+    pub center: Vec3,
+    /// Half of the size along each of the box axes
+    pub half_extents: Vec3,
+    /// Orthonormal box axes in world space
+    pub axes: [Vec3; 3],
+}
+
+impl Obb {
+    /// Create a box turned by `yaw` radians around the vertical axis
+    pub fn new(center: Vec3, half_extents: Vec3, yaw: f32) -> Self {
+        let (sin, cos) = yaw.sin_cos();
+        Self {
+            center,
+            half_extents: half_extents.map(f32::abs),
+            axes: [[cos, sin, 0.0], [-sin, cos, 0.0], [0.0, 0.0, 1.0]],
+        }
+    }
+
+    /// Create a box from its first two axes, which are orthonormalized
+    ///
+    /// The third axis is their cross product. Degenerate axes fall back to the world axes.
+    pub fn from_axes(center: Vec3, half_extents: Vec3, x_axis: Vec3, y_axis: Vec3) -> Self {
+        let x_axis = normalize(x_axis).unwrap_or([1.0, 0.0, 0.0]);
+        let y_axis = normalize(sub(y_axis, scale(x_axis, dot(y_axis, x_axis))))
+            .or_else(|| normalize(cross([0.0, 0.0, 1.0], x_axis)))
+            .unwrap_or([0.0, 1.0, 0.0]);
+
+        Self {
+            center,
+            half_extents: half_extents.map(f32::abs),
+            axes: [x_axis, y_axis, cross(x_axis, y_axis)],
+        }
+    }
+
+    /// Create an oriented box matching an axis-aligned one
+    pub fn from_aabb(aabb: &Aabb) -> Self {
+        Self::new(aabb.center(), aabb.half_extents(), 0.0)
+    }
+
+    /// Express a world point in box coordinates, relative to the center
+    pub fn to_local(&self, point: Vec3) -> Vec3 {
+        let offset = sub(point, self.center);
+        self.axes.map(|axis| dot(offset, axis))
+    }
+
+    /// Express a point in box coordinates in world space
+    pub fn to_world(&self, local: Vec3) -> Vec3 {
+        (0..3).fold(self.center, |point, i| add(point, scale(self.axes[i], local[i])))
+    }
+
+    /// Get the point of the box closest to a world point
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        let local = self.to_local(point);
+        self.to_world([0, 1, 2].map(|i| local[i].clamp(-self.half_extents[i], self.half_extents[i])))
+    }
+
+    /// Check if a point is inside the box or on its surface
+    pub fn contains(&self, point: Vec3) -> bool {
+        let local = self.to_local(point);
+        (0..3).all(|i| local[i].abs() <= self.half_extents[i])
+    }
+
+    /// Axis-aligned box around the oriented one
+    pub fn bounds(&self) -> Aabb {
+        let reach = [0, 1, 2].map(|i| (0..3).map(|j| self.axes[j][i].abs() * self.half_extents[j]).sum());
+        Aabb::from_center(self.center, reach)
+    }
+
+    /// Distance from a world point to the box, zero inside
+    fn distance_to(&self, point: Vec3) -> f32 {
+        let local = self.to_local(point);
+        length([0, 1, 2].map(|i| (local[i].abs() - self.half_extents[i]).max(0.0)))
+    }
+}
+
+/// Segment swept by a sphere
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Capsule {
+    pub start: Vec3,
+    pub end: Vec3,
+    pub radius: f32,
+}
+
+impl Capsule {
+    pub fn new(start: Vec3, end: Vec3, radius: f32) -> Self {
+        Self {
+            start,
+            end,
+            radius: radius.abs(),
+        }
+    }
+
+    /// Create a capsule standing on a base point, `height` tall including both caps
+    pub fn upright(base: Vec3, height: f32, radius: f32) -> Self {
+        let radius = radius.abs();
+        let top = (height - radius).max(radius);
+        Self::new(add(base, [0.0, 0.0, radius]), add(base, [0.0, 0.0, top]), radius)
+    }
+
+    /// Create a capsule with both ends at the center, which is a sphere
+    pub fn sphere(center: Vec3, radius: f32) -> Self {
+        Self::new(center, center, radius)
+    }
+
+    /// Move the capsule by an offset
+    pub fn translated(&self, offset: Vec3) -> Self {
+        Self {
+            start: add(self.start, offset),
+            end: add(self.end, offset),
+            ..*self
+        }
+    }
+
+    /// Cast the capsule along a motion and find where it first touches a shape
+    ///
+    /// Uses conservative advancement: the capsule moves ahead by its gap to the target, which
+    /// can never carry it through a surface however thin, until the gap closes. Returns a hit
+    /// at time zero when the shapes already overlap.
+    pub fn cast(&self, motion: Vec3, target: &CollisionShape) -> Option<SweepHit> {
+        let target = target.primitive();
+        let speed = length(motion);
+        let mut time = 0.0;
+
+        for _ in 0..MAX_CAST_ITERATIONS {
+            let (gap, point, normal) = self.translated(scale(motion, time)).contact(&target);
+            if gap <= CONTACT_TOLERANCE {
+                return Some(SweepHit { time, point, normal });
+            }
+            if speed <= EPSILON {
+                return None;
+            }
+            time += gap / speed;
+            if time > 1.0 {
+                return None;
+            }
+        }
+
+        None
+    }
+
+    /// Gap to a primitive, with the closest point on it and the normal toward the capsule
+    fn contact(&self, target: &Primitive) -> (f32, Vec3, Vec3) {
+        match target {
+            Primitive::Box(obb) => {
+                let (axis_point, distance) = segment_box_closest(self.start, self.end, obb);
+                let point = obb.closest_point(axis_point);
+                let normal = normalize(sub(axis_point, point))
+                    .or_else(|| normalize(sub(axis_point, obb.center)))
+                    .unwrap_or([0.0, 0.0, 1.0]);
+                (distance - self.radius, point, normal)
+            }
+            Primitive::Capsule(other) => {
+                let (own, closest) = closest_segment_points(self.start, self.end, other.start, other.end);
+                let normal = normalize(sub(own, closest)).unwrap_or([0.0, 0.0, 1.0]);
+                let gap = length(sub(own, closest)) - self.radius - other.radius;
+                (gap, add(closest, scale(normal, other.radius)), normal)
+            }
+        }
+    }
+
+    /// Get the point of the capsule axis closest to a world point
+    pub fn closest_axis_point(&self, point: Vec3) -> Vec3 {
+        let axis = sub(self.end, self.start);
+        let len_sq = dot(axis, axis);
+        if len_sq <= EPSILON {
+            return self.start;
+        }
+        let t = (dot(sub(point, self.start), axis) / len_sq).clamp(0.0, 1.0);
+        add(self.start, scale(axis, t))
+    }
+
+    /// Check if a point is inside the capsule or on its surface
+    pub fn contains(&self, point: Vec3) -> bool {
+        length(sub(point, self.closest_axis_point(point))) <= self.radius
+    }
+
+    /// Axis-aligned box around the capsule
+    pub fn bounds(&self) -> Aabb {
+        let axis = Aabb::new(self.start, self.end);
+        let r = [self.radius; 3];
+        Aabb {
+            min: sub(axis.min, r),
+            max: add(axis.max, r),
+        }
+    }
+}
+
+/// First contact of a shape cast along a motion
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SweepHit {
+    /// Fraction of the motion travelled before the contact, from 0 to 1
+    pub time: f32,
+    /// Contact point on the surface that was hit
+    pub point: Vec3,
+    /// Unit normal of the surface that was hit, facing the moving shape
+    pub normal: Vec3,
+}
+
+/// Collision shape of an entity or object
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CollisionShape {
+    Sphere { center: Vec3, radius: f32 },
+    Aabb(Aabb),
+    Obb(Obb),
+    Capsule(Capsule),
+}
+
+/// Shapes reduced to the two kinds the intersection tests work on
+enum Primitive {
+    Box(Obb),
+    Capsule(Capsule),
+}
+
+impl CollisionShape {
+    pub fn sphere(center: Vec3, radius: f32) -> Self {
+        CollisionShape::Sphere {
+            center,
+            radius: radius.abs(),
+        }
+    }
+
+    /// Move the shape by an offset
+    pub fn translated(&self, offset: Vec3) -> Self {
+        match *self {
+            CollisionShape::Sphere { center, radius } => CollisionShape::Sphere {
+                center: add(center, offset),
+                radius,
+            },
+            CollisionShape::Aabb(aabb) => CollisionShape::Aabb(Aabb {
+                min: add(aabb.min, offset),
+                max: add(aabb.max, offset),
+            }),
+            CollisionShape::Obb(obb) => CollisionShape::Obb(Obb {
+                center: add(obb.center, offset),
+                ..obb
+            }),
+            CollisionShape::Capsule(capsule) => CollisionShape::Capsule(capsule.translated(offset)),
+        }
+    }
+
+    /// Place a shape described around the origin at an entity's position
+    pub fn at(&self, entity: &Entity) -> Self {
+        self.translated([entity.x, entity.y, entity.z])
+    }
+
+    /// Axis-aligned box around the shape
+    pub fn bounds(&self) -> Aabb {
+        match self {
+            CollisionShape::Sphere { center, radius } => Aabb::from_center(*center, [*radius; 3]),
+            CollisionShape::Aabb(aabb) => *aabb,
+            CollisionShape::Obb(obb) => obb.bounds(),
+            CollisionShape::Capsule(capsule) => capsule.bounds(),
+        }
+    }
+
+    /// Check if a point is inside the shape or on its surface
+    pub fn contains(&self, point: Vec3) -> bool {
+        match self.primitive() {
+            Primitive::Box(obb) => obb.contains(point),
+            Primitive::Capsule(capsule) => capsule.contains(point),
+        }
+    }
+
+    /// Check if two shapes overlap
+    ///
+    /// Boxes are tested with the separating axis theorem, capsules and spheres by the distance
+    /// between their axes. Shapes that only touch do not collide, like
+    /// [`CollisionDetector::circle_collision`](crate::CollisionDetector::circle_collision).
+    pub fn intersects(&self, other: &CollisionShape) -> bool {
+        match (self.primitive(), other.primitive()) {
+            (Primitive::Box(a), Primitive::Box(b)) => boxes_overlap(&a, &b),
+            (Primitive::Box(obb), Primitive::Capsule(capsule)) | (Primitive::Capsule(capsule), Primitive::Box(obb)) => {
+                segment_box_closest(capsule.start, capsule.end, &obb).1 < capsule.radius
+            }
+            (Primitive::Capsule(a), Primitive::Capsule(b)) => {
+                let (p, q) = closest_segment_points(a.start, a.end, b.start, b.end);
+                length(sub(p, q)) < a.radius + b.radius
+            }
+        }
+    }
+
+    /// Get the lowest point of the shape on the vertical line through `(x, y)`
+    pub fn lowest_point_at(&self, x: f32, y: f32) -> Option<f32> {
+        match self.primitive() {
+            Primitive::Box(obb) => box_lowest_point(&obb, x, y),
+            Primitive::Capsule(capsule) => capsule_lowest_point(&capsule, x, y),
+        }
+    }
+
+    fn primitive(&self) -> Primitive {
+        match *self {
+            CollisionShape::Sphere { center, radius } => Primitive::Capsule(Capsule::new(center, center, radius)),
+            CollisionShape::Aabb(aabb) => Primitive::Box(Obb::from_aabb(&aabb)),
+            CollisionShape::Obb(obb) => Primitive::Box(obb),
+            CollisionShape::Capsule(capsule) => Primitive::Capsule(capsule),
+        }
+    }
+}
+
+impl From<Aabb> for CollisionShape {
+    fn from(aabb: Aabb) -> Self {
+        CollisionShape::Aabb(aabb)
+    }
+}
+
+impl From<Obb> for CollisionShape {
+    fn from(obb: Obb) -> Self {
+        CollisionShape::Obb(obb)
+    }
+}
+
+impl From<Capsule> for CollisionShape {
+    fn from(capsule: Capsule) -> Self {
+        CollisionShape::Capsule(capsule)
+    }
+}
+
+/// Separating axis test between two oriented boxes
+fn boxes_overlap(a: &Obb, b: &Obb) -> bool {
+    let rotation = a.axes.map(|axis_a| b.axes.map(|axis_b| dot(axis_a, axis_b)));
+    // Padding keeps the cross-product axes of nearly parallel edges from separating anything
+    let abs_rotation = rotation.map(|row| row.map(|v| v.abs() + EPSILON));
+    let offset = a.axes.map(|axis| dot(sub(b.center, a.center), axis));
+    let (ea, eb) = (a.half_extents, b.half_extents);
+
+    for i in 0..3 {
+        let rb: f32 = (0..3).map(|j| eb[j] * abs_rotation[i][j]).sum();
+        if offset[i].abs() >= ea[i] + rb {
+            return false;
+        }
+    }
+
+    for j in 0..3 {
+        let ra: f32 = (0..3).map(|i| ea[i] * abs_rotation[i][j]).sum();
+        let projected: f32 = (0..3).map(|i| offset[i] * rotation[i][j]).sum();
+        if projected.abs() >= ra + eb[j] {
+            return false;
+        }
+    }
+
+    for i in 0..3 {
+        let (i0, i1) = ((i + 1) % 3, (i + 2) % 3);
+        for j in 0..3 {
+            let (j0, j1) = ((j + 1) % 3, (j + 2) % 3);
+            let ra = ea[i0] * abs_rotation[i1][j] + ea[i1] * abs_rotation[i0][j];
+            let rb = eb[j0] * abs_rotation[i][j1] + eb[j1] * abs_rotation[i][j0];
+            let projected = offset[i1] * rotation[i0][j] - offset[i0] * rotation[i1][j];
+            if projected.abs() >= ra + rb {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// Closest points between two segments, on the first and on the second
+fn closest_segment_points(p1: Vec3, q1: Vec3, p2: Vec3, q2: Vec3) -> (Vec3, Vec3) {
+    let (d1, d2, r) = (sub(q1, p1), sub(q2, p2), sub(p1, p2));
+    let (a, e, f) = (dot(d1, d1), dot(d2, d2), dot(d2, r));
+
+    let (s, t) = if a <= EPSILON && e <= EPSILON {
+        (0.0, 0.0)
+    } else if a <= EPSILON {
+        (0.0, (f / e).clamp(0.0, 1.0))
+    } else {
+        let c = dot(d1, r);
+        if e <= EPSILON {
+            ((-c / a).clamp(0.0, 1.0), 0.0)
+        } else {
+            let b = dot(d1, d2);
+            let denom = a * e - b * b;
+            let s = if denom > EPSILON {
+                ((b * f - c * e) / denom).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let t = (b * s + f) / e;
+            if t < 0.0 {
+                ((-c / a).clamp(0.0, 1.0), 0.0)
+            } else if t > 1.0 {
+                (((b - c) / a).clamp(0.0, 1.0), 1.0)
+            } else {
+                (s, t)
+            }
+        }
+    };
+
+    (add(p1, scale(d1, s)), add(p2, scale(d2, t)))
+}
+
+/// Point of a segment closest to an oriented box, with its distance to the box
+fn segment_box_closest(start: Vec3, end: Vec3, obb: &Obb) -> (Vec3, f32) {
+    let axis = sub(end, start);
+    // Distance to a convex set is convex along the segment
+    let (t, distance) = minimize_convex(0.0, 1.0, |t| obb.distance_to(add(start, scale(axis, t))));
+    (add(start, scale(axis, t)), distance)
+}
+
+/// Lowest point of a box on a vertical line, from the slabs between its faces
+fn box_lowest_point(obb: &Obb, x: f32, y: f32) -> Option<f32> {
+    let origin = obb.to_local([x, y, 0.0]);
+    let (mut low, mut high) = (f32::NEG_INFINITY, f32::INFINITY);
+
+    for ((offset, extent), axis) in origin.into_iter().zip(obb.half_extents).zip(obb.axes) {
+        let rate = axis[2];
+        if rate.abs() <= EPSILON {
+            if offset.abs() > extent {
+                return None;
+            }
+            continue;
+        }
+        let a = (-extent - offset) / rate;
+        let b = (extent - offset) / rate;
+        low = low.max(a.min(b));
+        high = high.min(a.max(b));
+    }
+
+    (low <= high).then_some(low)
+}
+
+/// Lowest point of a capsule on a vertical line
+///
+/// Only the part of the axis within the radius of the line reaches it, and along that part
+/// the bottom of the swept sphere is a convex function of the position on the axis.
+fn capsule_lowest_point(capsule: &Capsule, x: f32, y: f32) -> Option<f32> {
+    let axis = sub(capsule.end, capsule.start);
+    let (ox, oy) = (capsule.start[0] - x, capsule.start[1] - y);
+    let r_sq = capsule.radius * capsule.radius;
+
+    // Squared horizontal distance to the line along the axis: a t² + b t + c
+    let a = axis[0] * axis[0] + axis[1] * axis[1];
+    let b = 2.0 * (ox * axis[0] + oy * axis[1]);
+    let c = ox * ox + oy * oy;
+
+    let (low, high) = if a <= EPSILON {
+        if c > r_sq {
+            return None;
+        }
+        (0.0, 1.0)
+    } else {
+        let discriminant = b * b - 4.0 * a * (c - r_sq);
+        if discriminant < 0.0 {
+            return None;
+        }
+        let root = discriminant.sqrt();
+        let low = ((-b - root) / (2.0 * a)).max(0.0);
+        let high = ((-b + root) / (2.0 * a)).min(1.0);
+        if low > high {
+            return None;
+        }
+        (low, high)
+    };
+
+    let bottom = |t: f32| capsule.start[2] + axis[2] * t - (r_sq - (a * t * t + b * t + c)).max(0.0).sqrt();
+    Some(minimize_convex(low, high, bottom).1)
+}
+
+/// Minimum of a convex function over an interval and where it is, by ternary search
+fn minimize_convex(mut low: f32, mut high: f32, f: impl Fn(f32) -> f32) -> (f32, f32) {
+    let ends = [(low, f(low)), (high, f(high))];
+    for _ in 0..SEGMENT_SEARCH_STEPS {
+        let a = low + (high - low) / 3.0;
+        let b = high - (high - low) / 3.0;
+        if f(a) <= f(b) {
+            high = b;
+        } else {
+            low = a;
+        }
+    }
+    let middle = (low + high) * 0.5;
+    ends.into_iter()
+        .fold((middle, f(middle)), |best, end| if end.1 < best.1 { end } else { best })
+}
+
+fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(v: Vec3, s: f32) -> Vec3 {
+    [v[0] * s, v[1] * s, v[2] * s]
+}
+
+fn dot(a: Vec3, b: Vec3) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+fn length(v: Vec3) -> f32 {
+    dot(v, v).sqrt()
+}
+
+fn normalize(v: Vec3) -> Option<Vec3> {
+    let len = length(v);
+    (len > EPSILON).then(|| scale(v, 1.0 / len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChunkCoord;
+    use entropic_world_core::population::EntityType;
+    use std::f32::consts::FRAC_PI_4;
+
+    fn close(a: Vec3, b: Vec3) -> bool {
+        length(sub(a, b)) < 1e-4
+    }
+
+    fn unit_box(center: Vec3) -> CollisionShape {
+        Aabb::from_center(center, [1.0; 3]).into()
+    }
+
+    #[test]
+    fn test_box_collisions() {
+        let aabb = unit_box([0.0; 3]);
+        assert!(aabb.intersects(&unit_box([1.5, 1.5, 0.0])));
+        assert!(!aabb.intersects(&unit_box([0.0, 0.0, 2.5])));
+
+        // Turned 45 degrees, a unit box reaches sqrt(2) from its center
+        let turned = |x: f32| CollisionShape::from(Obb::new([x, 0.0, 0.0], [1.0; 3], FRAC_PI_4));
+        assert!(aabb.intersects(&turned(2.3)));
+        assert!(!aabb.intersects(&turned(2.5)));
+        assert!(!aabb.intersects(&CollisionShape::from(Obb::new([2.3, 0.0, 0.0], [1.0; 3], 0.0))));
+
+        assert!(turned(0.0).intersects(&turned(2.7)));
+        assert!(!turned(0.0).intersects(&turned(2.9)));
+
+        // Tilted about the y axis, the lower corner dips into a box below
+        let tilted = Obb::from_axes([0.0, 0.0, 2.35], [1.0; 3], [1.0, 0.0, 1.0], [0.0, 1.0, 0.0]);
+        assert!(aabb.intersects(&tilted.into()));
+        assert!(!unit_box([0.0, 0.0, 0.0]).intersects(&Obb::new([0.0, 0.0, 2.35], [1.0; 3], 0.3).into()));
+    }
+
+    #[test]
+    fn test_capsule_collisions() {
+        let standing = |x: f32| CollisionShape::from(Capsule::upright([x, 0.0, 0.0], 2.0, 0.5));
+        assert!(standing(0.0).intersects(&standing(0.9)));
+        assert!(!standing(0.0).intersects(&standing(1.1)));
+
+        let crossing = |z: f32| CollisionShape::from(Capsule::new([-5.0, 1.0, z], [5.0, 1.0, z], 0.5));
+        let block: CollisionShape = Aabb::new([0.0; 3], [2.0; 3]).into();
+        assert!(block.intersects(&crossing(2.4)));
+        assert!(!block.intersects(&crossing(2.6)));
+        assert!(crossing(2.4).intersects(&block));
+
+        // Skew capsules pass 1.0 apart
+        let x_axis = CollisionShape::from(Capsule::new([-3.0, 0.0, 0.0], [3.0, 0.0, 0.0], 0.6));
+        let y_axis = |radius: f32| CollisionShape::from(Capsule::new([0.0, -3.0, 1.0], [0.0, 3.0, 1.0], radius));
+        assert!(x_axis.intersects(&y_axis(0.5)));
+        assert!(!x_axis.intersects(&y_axis(0.3)));
+
+        let sphere = |x: f32| CollisionShape::sphere([x, 0.0, 0.0], 1.0);
+        assert!(sphere(0.0).intersects(&sphere(1.9)));
+        assert!(!sphere(0.0).intersects(&sphere(2.0)));
+        assert!(sphere(1.5).intersects(&Obb::new([0.0; 3], [1.0; 3], FRAC_PI_4).into()));
+        assert!(!sphere(2.5).intersects(&Obb::new([0.0; 3], [1.0; 3], FRAC_PI_4).into()));
+        assert!(sphere(0.0).contains([0.5, 0.5, 0.5]));
+    }
+
+    #[test]
+    fn test_lowest_point() {
+        let sphere = CollisionShape::sphere([0.0, 0.0, 10.0], 2.0);
+        assert_eq!(sphere.lowest_point_at(0.0, 0.0), Some(8.0));
+        assert!((sphere.lowest_point_at(2.0, 0.0).unwrap() - 10.0).abs() < 1e-4);
+        assert_eq!(sphere.lowest_point_at(3.0, 0.0), None);
+
+        let turned = CollisionShape::from(Obb::new([0.0, 0.0, 5.0], [1.0, 1.0, 2.0], FRAC_PI_4));
+        assert_eq!(turned.lowest_point_at(1.3, 0.0), Some(3.0));
+        assert_eq!(turned.lowest_point_at(1.3, 1.3), None);
+
+        // Tilted 45 degrees around the y axis, the bottom edge is 2 below the center and rises with x
+        let tilted = CollisionShape::from(Obb::from_axes([0.0; 3], [2f32.sqrt(); 3], [1.0, 0.0, 1.0], [0.0, 1.0, 0.0]));
+        assert!((tilted.lowest_point_at(0.0, 0.0).unwrap() + 2.0).abs() < 1e-4);
+        assert!((tilted.lowest_point_at(1.0, 0.0).unwrap() + 1.0).abs() < 1e-4);
+
+        let slanted = CollisionShape::from(Capsule::new([0.0, 0.0, 5.0], [10.0, 0.0, 15.0], 1.0));
+        assert!((slanted.lowest_point_at(0.0, 0.0).unwrap() - 4.0).abs() < 1e-3);
+        assert!((slanted.lowest_point_at(-0.5, 0.0).unwrap() - (5.0 - 0.75f32.sqrt())).abs() < 1e-3);
+        assert!((slanted.lowest_point_at(5.0, 0.0).unwrap() - (10.0 - 2f32.sqrt())).abs() < 1e-3);
+        assert_eq!(slanted.lowest_point_at(5.0, 2.0), None);
+    }
+
+    #[test]
+    fn test_cast_through_thin_wall() {
+        let wall: CollisionShape = Obb::new([5.0, 0.0, 0.0], [0.05, 2.0, 2.0], 0.0).into();
+        let ball = Capsule::sphere([0.0; 3], 0.5);
+
+        // Sampled only where the motion starts and ends, the ball would pass through the wall
+        assert!(!CollisionShape::from(ball).intersects(&wall));
+        assert!(!CollisionShape::from(ball.translated([10.0, 0.0, 0.0])).intersects(&wall));
+
+        let hit = ball.cast([10.0, 0.0, 0.0], &wall).unwrap();
+        assert!((hit.time - 0.445).abs() < 1e-3);
+        assert!((hit.point[0] - 4.95).abs() < 1e-4);
+        assert!(close(hit.normal, [-1.0, 0.0, 0.0]));
+
+        assert_eq!(ball.cast([-10.0, 0.0, 0.0], &wall), None);
+        assert_eq!(ball.cast([10.0, 0.0, 0.0], &wall.translated([0.0, 3.0, 0.0])), None);
+        assert_eq!(ball.translated([5.0, 0.0, 0.0]).cast([10.0, 0.0, 0.0], &wall).unwrap().time, 0.0);
+    }
+
+    #[test]
+    fn test_cast_capsules() {
+        let runner = Capsule::upright([0.0; 3], 2.0, 0.5);
+        let post: CollisionShape = Capsule::upright([8.0, 0.5, 0.0], 3.0, 0.25).into();
+
+        let hit = runner.cast([16.0, 0.0, 0.0], &post).unwrap();
+        // Touching when the axes are 0.75 apart: 0.5 across, so sqrt(0.75² - 0.5²) short of the post
+        let expected = (8.0 - (0.75f32 * 0.75 - 0.25).sqrt()) / 16.0;
+        assert!((hit.time - expected).abs() < 1e-3);
+        assert!(hit.normal[0] < 0.0 && hit.normal[1] < 0.0 && hit.normal[2].abs() < 1e-4);
+        assert!((length(hit.normal) - 1.0).abs() < 1e-4);
+        assert!((length(sub(hit.point, [8.0, 0.5, hit.point[2]])) - 0.25).abs() < 1e-4);
+
+        // Falling on a crate lands on its top face
+        let crate_box: CollisionShape = Aabb::new([-1.0, -1.0, 0.0], [1.0, 1.0, 1.0]).into();
+        let hit = Capsule::sphere([0.2, 0.0, 6.0], 1.0).cast([0.0, 0.0, -10.0], &crate_box).unwrap();
+        assert!((hit.time - 0.4).abs() < 1e-3);
+        assert!(close(hit.normal, [0.0, 0.0, 1.0]));
+        assert!((hit.point[2] - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_entity_shapes() {
+        let entity = Entity::new("e".to_string(), EntityType::NPC, 10.0, 20.0, 3.0, ChunkCoord::new(0, 0));
+        let body = CollisionShape::from(Capsule::upright([0.0; 3], 2.0, 0.5)).at(&entity);
+
+        assert_eq!(body, CollisionShape::Capsule(Capsule::new([10.0, 20.0, 3.5], [10.0, 20.0, 4.5], 0.5)));
+        assert_eq!(body.bounds(), Aabb::new([9.5, 19.5, 3.0], [10.5, 20.5, 5.0]));
+        assert!(body.contains([10.0, 20.0, 4.0]));
+        assert!(!body.contains([10.0, 20.0, 5.2]));
+
+        let crate_box = unit_box([0.0; 3]).at(&entity);
+        assert_eq!(crate_box.bounds(), Aabb::new([9.0, 19.0, 2.0], [11.0, 21.0, 4.0]));
+        assert!(crate_box.intersects(&body));
+    }
+}
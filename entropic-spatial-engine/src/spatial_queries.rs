@@ -1,8 +1,187 @@
+use crate::collision::CollisionDetector;
 use crate::constants::*;
 use crate::errors::SpatialError;
+use crate::shapes::{Aabb, Vec3};
 use crate::{Chunk, ChunkCoord, Entity, World};
+use entropic_world_core::spatial::Material;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
 use std::sync::Arc;
 
+/// Where a ray meets the terrain surface
+#[derive(Clone, Debug, PartialEq)]
+pub struct TerrainHit {
+    /// Hit point on the interpolated heightmap surface
+    pub position: Vec3,
+    /// Unit surface normal at the hit point
+    pub normal: Vec3,
+    /// Distance from the ray origin to the hit point
+    pub distance: f32,
+    /// Chunks the ray crossed up to the hit point, in order
+    pub chunks: Vec<ChunkCoord>,
+}
+
+/// Plane `normal · p + distance = 0`, with the inside where that is positive
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Plane {
+    pub normal: Vec3,
+    pub distance: f32,
+}
+
+impl Plane {
+    /// Create a plane, normalizing its normal
+    pub fn new(normal: Vec3, distance: f32) -> Self {
+        let length = dot(normal, normal).sqrt().max(f32::EPSILON);
+        Self {
+            normal: normal.map(|n| n / length),
+            distance: distance / length,
+        }
+    }
+
+    /// Create a plane through a point
+    pub fn through(point: Vec3, normal: Vec3) -> Self {
+        let plane = Self::new(normal, 0.0);
+        Self {
+            distance: -dot(plane.normal, point),
+            ..plane
+        }
+    }
+
+    /// Get how far a point is on the inside of the plane, negative outside
+    pub fn signed_distance(&self, point: Vec3) -> f32 {
+        dot(self.normal, point) + self.distance
+    }
+}
+
+/// Convex view volume bounded by six inward-facing planes
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frustum {
+    planes: [Plane; 6],
+    corners: [Vec3; 8],
+}
+
+impl Frustum {
+    /// Create a frustum from its left, right, bottom, top, near and far planes
+    pub fn from_planes(planes: [Plane; 6]) -> Self {
+        let [left, right, bottom, top, near, far] = planes;
+        let corners = [
+            (near, left, bottom),
+            (near, right, bottom),
+            (near, left, top),
+            (near, right, top),
+            (far, left, bottom),
+            (far, right, bottom),
+            (far, left, top),
+            (far, right, top),
+        ]
+        .map(|(a, b, c)| plane_intersection(&a, &b, &c).unwrap_or([0.0; 3]));
+
+        Self { planes, corners }
+    }
+
+    /// Create the frustum of a perspective camera
+    ///
+    /// `fov_y` is the vertical field of view in radians and `aspect` the width over the height
+    /// of the view. `up` only needs to lie in the vertical plane of the view.
+    pub fn perspective(eye: Vec3, forward: Vec3, up: Vec3, fov_y: f32, aspect: f32, near: f32, far: f32) -> Self {
+        let forward = normalize(forward);
+        let right = normalize(cross(forward, up));
+        let up = cross(right, forward);
+        let half_height = (fov_y * 0.5).tan();
+        let half_width = half_height * aspect;
+        let along = |a: Vec3, b: Vec3, scale: f32| [0, 1, 2].map(|i| a[i] + b[i] * scale);
+
+        let left = along(forward, right, -half_width);
+        let right_edge = along(forward, right, half_width);
+        let top = along(forward, up, half_height);
+        let bottom = along(forward, up, -half_height);
+
+        Self::from_planes([
+            Plane::through(eye, cross(left, up)),
+            Plane::through(eye, cross(up, right_edge)),
+            Plane::through(eye, cross(right, bottom)),
+            Plane::through(eye, cross(top, right)),
+            Plane::through(along(eye, forward, near), forward),
+            Plane::through(along(eye, forward, far), forward.map(|f| -f)),
+        ])
+    }
+
+    pub fn planes(&self) -> &[Plane; 6] {
+        &self.planes
+    }
+
+    /// Corners on the near plane, then on the far plane
+    pub fn corners(&self) -> &[Vec3; 8] {
+        &self.corners
+    }
+
+    /// Axis-aligned box around the frustum
+    pub fn bounds(&self) -> Aabb {
+        self.corners
+            .iter()
+            .fold(Aabb::new(self.corners[0], self.corners[0]), |bounds, &corner| {
+                bounds.union(&Aabb::new(corner, corner))
+            })
+    }
+
+    /// Check if a point is inside the frustum or on its surface
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        self.planes.iter().all(|plane| plane.signed_distance(point) >= 0.0)
+    }
+
+    /// Check if a sphere overlaps the frustum
+    ///
+    /// Conservative: spheres just outside a corner of the frustum may count as overlapping.
+    pub fn intersects_sphere(&self, center: Vec3, radius: f32) -> bool {
+        self.planes.iter().all(|plane| plane.signed_distance(center) >= -radius)
+    }
+
+    /// Check if a box overlaps the frustum
+    ///
+    /// Tests the box against every plane, then the frustum corners against every box face,
+    /// which only lets through boxes close to an edge of the frustum.
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        let outside_plane = self.planes.iter().any(|plane| {
+            // Corner of the box furthest along the plane normal
+            let corner = [0, 1, 2].map(|i| if plane.normal[i] >= 0.0 { aabb.max[i] } else { aabb.min[i] });
+            plane.signed_distance(corner) < 0.0
+        });
+        let outside_box = (0..3).any(|i| {
+            self.corners.iter().all(|c| c[i] < aabb.min[i]) || self.corners.iter().all(|c| c[i] > aabb.max[i])
+        });
+
+        !outside_plane && !outside_box
+    }
+}
+
+/// Entity kept while collecting the nearest ones, ordered by distance
+struct Candidate<'a> {
+    distance_sq: f32,
+    entity: &'a Entity,
+}
+
+impl PartialEq for Candidate<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate<'_> {}
+
+impl PartialOrd for Candidate<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance_sq
+            .total_cmp(&other.distance_sq)
+            .then_with(|| self.entity.id.cmp(&other.entity.id))
+    }
+}
+
 /// High-performance spatial query engine
 pub struct SpatialQueries;
 
@@ -52,6 +231,68 @@ impl SpatialQueries {
             .min_by_key(|e| ((e.x - x).powi(2) + (e.y - y).powi(2)) as i32)
     }
 
+    /// Find up to `k` entities closest to a point within a distance, nearest first
+    ///
+    /// Chunks are searched in rings around the point, stopping once no chunk further out can
+    /// hold anything closer than the `k` entities found so far.
+    pub fn k_nearest(world: &World, x: f32, y: f32, k: usize, max_distance: f32) -> Vec<Arc<Entity>> {
+        if k == 0 || max_distance < 0.0 {
+            return Vec::new();
+        }
+
+        let center = ((x / CHUNK_SIZE).floor() as i64, (y / CHUNK_SIZE).floor() as i64);
+        let (width, height) = (world.width_chunks as i64, world.height_chunks as i64);
+        // Rings past every edge of the world hold nothing
+        let world_ring = [center.0, width - 1 - center.0, center.1, height - 1 - center.1]
+            .into_iter()
+            .map(i64::abs)
+            .max()
+            .unwrap_or(0);
+        let max_ring = ((max_distance / CHUNK_SIZE).ceil() as i64 + 1).min(world_ring);
+        let max_distance_sq = max_distance * max_distance;
+        let mut nearest: BinaryHeap<Candidate> = BinaryHeap::with_capacity(k + 1);
+
+        for ring in 0..=max_ring {
+            // Anything in this ring is at least a chunk less than its ring number away
+            let closest = (ring - 1).max(0) as f32 * CHUNK_SIZE;
+            if nearest.len() == k && nearest.peek().is_some_and(|worst| closest * closest > worst.distance_sq) {
+                break;
+            }
+
+            // Chunks on the border of the ring, once each
+            let sides = (-ring..=ring).flat_map(|d| [(d, -ring), (d, ring)]);
+            let ends = (1 - ring..ring).flat_map(|d| [(-ring, d), (ring, d)]);
+            let mut cells: Vec<(i64, i64)> = sides.chain(ends).collect();
+            cells.dedup();
+
+            for (dx, dy) in cells {
+                let (chunk_x, chunk_y) = (center.0 + dx, center.1 + dy);
+                if chunk_x < 0 || chunk_y < 0 {
+                    continue;
+                }
+                let Some(chunk) = world.chunks.get(&ChunkCoord::new(chunk_x as u32, chunk_y as u32)) else {
+                    continue;
+                };
+
+                for entity in chunk.entities.iter().filter_map(|id| world.entities.get(id)) {
+                    let distance_sq = (entity.x - x).powi(2) + (entity.y - y).powi(2);
+                    if distance_sq <= max_distance_sq {
+                        nearest.push(Candidate { distance_sq, entity });
+                        if nearest.len() > k {
+                            nearest.pop();
+                        }
+                    }
+                }
+            }
+        }
+
+        nearest
+            .into_sorted_vec()
+            .into_iter()
+            .map(|candidate| Arc::new(candidate.entity.clone()))
+            .collect()
+    }
+
     /// Find all entities within radius, sorted by distance
     pub fn query_radius_sorted(
         world: &World,
@@ -79,6 +320,26 @@ impl SpatialQueries {
             .unwrap_or_default()
     }
 
+    /// Get the surface material weights at a world position, as `[grass, rock, sand, snow]`
+    ///
+    /// `None` outside the world's chunks and in chunks without painted materials.
+    pub fn material_weights_at(world: &World, x: f32, y: f32) -> Option<[u8; 4]> {
+        if x < 0.0 || y < 0.0 {
+            return None;
+        }
+        let coord = ChunkCoord::new((x / CHUNK_SIZE) as u32, (y / CHUNK_SIZE) as u32);
+        let chunk = world.chunks.get(&coord)?;
+        let scale = HEIGHTMAP_RESOLUTION as f32 / CHUNK_SIZE;
+        let sx = ((x - coord.x as f32 * CHUNK_SIZE) * scale) as usize;
+        let sy = ((y - coord.y as f32 * CHUNK_SIZE) * scale) as usize;
+        chunk.get_materials_at(sx, sy)
+    }
+
+    /// Get the dominant surface material at a world position, e.g. to pick footstep sounds
+    pub fn material_at(world: &World, x: f32, y: f32) -> Option<Material> {
+        Self::material_weights_at(world, x, y).map(Material::dominant)
+    }
+
     /// Raycast: find first entity hit by ray
     pub fn raycast(
         world: &World,
@@ -113,6 +374,96 @@ impl SpatialQueries {
             .map(|h| h.1)
     }
 
+    /// Raycast against the terrain surface
+    ///
+    /// Walks the heightmap cells under the ray and intersects each with the bilinear surface
+    /// between its four height samples, so hits land on the terrain between samples too.
+    /// Cells of unloaded chunks are passed through.
+    pub fn raycast_terrain(world: &World, origin: Vec3, direction: Vec3, max_distance: f32) -> Option<TerrainHit> {
+        let length = direction.iter().map(|d| d * d).sum::<f32>().sqrt();
+        if length <= f32::EPSILON || max_distance <= 0.0 {
+            return None;
+        }
+        let dir = direction.map(|d| d / length);
+        let point_at = |t: f32| [0, 1, 2].map(|i| origin[i] + dir[i] * t);
+
+        // Clip the ray to the world
+        let extent = [
+            world.width_chunks as f32 * CHUNK_SIZE,
+            world.height_chunks as f32 * CHUNK_SIZE,
+        ];
+        let (mut enter, mut leave) = (0.0f32, max_distance);
+        for (axis, size) in extent.into_iter().enumerate() {
+            if dir[axis].abs() <= f32::EPSILON {
+                if origin[axis] < 0.0 || origin[axis] >= size {
+                    return None;
+                }
+                continue;
+            }
+            let a = -origin[axis] / dir[axis];
+            let b = (size - origin[axis]) / dir[axis];
+            enter = enter.max(a.min(b));
+            leave = leave.min(a.max(b));
+        }
+        if enter > leave {
+            return None;
+        }
+
+        let step = CHUNK_SIZE / HEIGHTMAP_RESOLUTION as f32;
+        let resolution = HEIGHTMAP_RESOLUTION as i64;
+        let last_cell = extent.map(|size| (size / step) as i64 - 1);
+        let start = point_at(enter);
+        let mut cell = [0, 1].map(|i| ((start[i] / step).floor() as i64).clamp(0, last_cell[i]));
+
+        // Distance along the ray to the next cell border on each axis, and between borders
+        let advance = [0, 1].map(|i| dir[i].signum() as i64);
+        let between = [0, 1].map(|i| step / dir[i].abs());
+        let mut border = [0, 1].map(|i| {
+            if dir[i] > f32::EPSILON {
+                ((cell[i] + 1) as f32 * step - origin[i]) / dir[i]
+            } else if dir[i] < -f32::EPSILON {
+                (cell[i] as f32 * step - origin[i]) / dir[i]
+            } else {
+                f32::INFINITY
+            }
+        });
+
+        let mut chunks = Vec::new();
+        let mut t = enter;
+        loop {
+            let chunk = ChunkCoord::new((cell[0] / resolution) as u32, (cell[1] / resolution) as u32);
+            if chunks.last() != Some(&chunk) {
+                chunks.push(chunk);
+            }
+
+            let exit = border[0].min(border[1]).min(leave);
+            if let Some(heights) = CollisionDetector::cell_heights(world, cell[0] as u32, cell[1] as u32) {
+                let corner = [cell[0] as f32 * step, cell[1] as f32 * step];
+                if let Some((distance, normal)) = intersect_cell(heights, corner, step, point_at(t), dir) {
+                    if distance <= exit - t {
+                        return Some(TerrainHit {
+                            position: point_at(t + distance),
+                            normal,
+                            distance: t + distance,
+                            chunks,
+                        });
+                    }
+                }
+            }
+
+            if exit >= leave {
+                return None;
+            }
+            let axis = if border[0] < border[1] { 0 } else { 1 };
+            cell[axis] += advance[axis];
+            border[axis] += between[axis];
+            if cell[axis] < 0 || cell[axis] > last_cell[axis] {
+                return None;
+            }
+            t = exit;
+        }
+    }
+
     /// Raycast and find all entities hit by ray (sorted by distance)
     pub fn raycast_all(
         world: &World,
@@ -192,6 +543,73 @@ impl SpatialQueries {
         results
     }
 
+    /// Find entities inside a box, including their elevation
+    pub fn query_region(world: &World, region: &Aabb) -> Vec<Arc<Entity>> {
+        Self::entities_in_chunks_over(world, region, |entity| region.contains([entity.x, entity.y, entity.z]))
+    }
+
+    /// Find entities inside a view frustum
+    ///
+    /// Chunks under the frustum are culled as columns spanning its height before their entities
+    /// are tested, so this serves camera-driven culling as well as server interest management.
+    pub fn query_frustum(world: &World, frustum: &Frustum) -> Vec<Arc<Entity>> {
+        let bounds = frustum.bounds();
+        let mut results = Vec::new();
+
+        for coord in Self::chunks_over(world, &bounds) {
+            let column = Aabb::new(
+                [coord.x as f32 * CHUNK_SIZE, coord.y as f32 * CHUNK_SIZE, bounds.min[2]],
+                [(coord.x + 1) as f32 * CHUNK_SIZE, (coord.y + 1) as f32 * CHUNK_SIZE, bounds.max[2]],
+            );
+            if !frustum.intersects_aabb(&column) {
+                continue;
+            }
+            if let Some(chunk) = world.chunks.get(&coord) {
+                results.extend(
+                    chunk
+                        .entities
+                        .iter()
+                        .filter_map(|id| world.entities.get(id))
+                        .filter(|entity| frustum.contains_point([entity.x, entity.y, entity.z]))
+                        .map(|entity| Arc::new(entity.clone())),
+                );
+            }
+        }
+
+        results
+    }
+
+    /// Get the coordinates of the world chunks under the footprint of a box
+    fn chunks_over(world: &World, bounds: &Aabb) -> Vec<ChunkCoord> {
+        let range = |min: f32, max: f32, chunks: u32| {
+            if max < 0.0 || chunks == 0 {
+                return None;
+            }
+            let first = (min / CHUNK_SIZE).floor().max(0.0) as u32;
+            let last = ((max / CHUNK_SIZE).floor() as u32).min(chunks - 1);
+            (first <= last).then_some(first..=last)
+        };
+
+        let (Some(xs), Some(ys)) = (
+            range(bounds.min[0], bounds.max[0], world.width_chunks),
+            range(bounds.min[1], bounds.max[1], world.height_chunks),
+        ) else {
+            return Vec::new();
+        };
+        xs.flat_map(|x| ys.clone().map(move |y| ChunkCoord::new(x, y))).collect()
+    }
+
+    /// Collect the entities passing a filter in the chunks under a box
+    fn entities_in_chunks_over(world: &World, bounds: &Aabb, filter: impl Fn(&Entity) -> bool) -> Vec<Arc<Entity>> {
+        Self::chunks_over(world, bounds)
+            .into_iter()
+            .filter_map(|coord| world.chunks.get(&coord))
+            .flat_map(|chunk| chunk.entities.iter().filter_map(|id| world.entities.get(id)))
+            .filter(|entity| filter(entity))
+            .map(|entity| Arc::new(entity.clone()))
+            .collect()
+    }
+
     /// Find entities by type within radius
     pub fn query_by_type(
         world: &World,
@@ -221,42 +639,106 @@ impl SpatialQueries {
     }
 }
 
+/// Point shared by three planes, if they meet in one
+fn plane_intersection(a: &Plane, b: &Plane, c: &Plane) -> Option<Vec3> {
+    let (bc, ca, ab) = (cross(b.normal, c.normal), cross(c.normal, a.normal), cross(a.normal, b.normal));
+    let det = dot(a.normal, bc);
+    if det.abs() <= f32::EPSILON {
+        return None;
+    }
+    Some([0, 1, 2].map(|i| -(a.distance * bc[i] + b.distance * ca[i] + c.distance * ab[i]) / det))
+}
+
+fn dot(a: Vec3, b: Vec3) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+fn normalize(v: Vec3) -> Vec3 {
+    let length = dot(v, v).sqrt().max(f32::EPSILON);
+    v.map(|c| c / length)
+}
+
+/// Intersect a ray with the bilinear surface of a heightmap cell
+///
+/// Heights are `[h00, h10, h01, h11]` at the cell corners, `corner` is the world position of
+/// the first one and the ray starts inside the cell. Along the ray the surface is quadratic,
+/// so the hit is the first root past the start. Returns the distance to the hit and the
+/// surface normal there.
+fn intersect_cell(heights: [f32; 4], corner: [f32; 2], step: f32, start: Vec3, dir: Vec3) -> Option<(f32, Vec3)> {
+    let [h00, h10, h01, h11] = heights;
+    let (b, c, d) = (h10 - h00, h01 - h00, h00 - h10 - h01 + h11);
+
+    let (u0, v0) = ((start[0] - corner[0]) / step, (start[1] - corner[1]) / step);
+    let (du, dv) = (dir[0] / step, dir[1] / step);
+
+    // Height above the surface after s meters: qa s² + qb s + qc
+    let qc = start[2] - (h00 + b * u0 + c * v0 + d * u0 * v0);
+    let qb = dir[2] - b * du - c * dv - d * (u0 * dv + v0 * du);
+    let qa = -d * du * dv;
+
+    let s = if qc <= 0.0 { 0.0 } else { first_root(qa, qb, qc)? };
+    let (u, v) = ((u0 + du * s).clamp(0.0, 1.0), (v0 + dv * s).clamp(0.0, 1.0));
+    let (slope_x, slope_y) = ((b + d * v) / step, (c + d * u) / step);
+    let length = (slope_x * slope_x + slope_y * slope_y + 1.0).sqrt();
+
+    Some((s, [-slope_x / length, -slope_y / length, 1.0 / length]))
+}
+
+/// Smallest non-negative root of a s² + b s + c
+fn first_root(a: f32, b: f32, c: f32) -> Option<f32> {
+    let roots = if a.abs() <= f32::EPSILON {
+        [(b.abs() > f32::EPSILON).then(|| -c / b), None]
+    } else {
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        // Stable form, without cancellation between b and the root
+        let q = -0.5 * (b + b.signum() * discriminant.sqrt());
+        [Some(q / a), (q != 0.0).then(|| c / q)]
+    };
+
+    roots.into_iter().flatten().filter(|&s| s >= 0.0).reduce(f32::min)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use entropic_world_core::EntityId;
+    use entropic_world_core::population::EntityType;
 
     fn create_test_world() -> World {
         let mut world = World::new("Test".to_string(), "game1".to_string(), 2, 2);
         world.initialize_chunks();
 
         // Add test entities
-        let entity1 = Entity {
-            id: "entity1".to_string(),
-            x: 100.0,
-            y: 100.0,
-            entity_type: "player".to_string(),
-            faction: Some("faction1".to_string()),
-            ..Default::default()
-        };
-
-        let entity2 = Entity {
-            id: "entity2".to_string(),
-            x: 150.0,
-            y: 100.0,
-            entity_type: "npc".to_string(),
-            faction: Some("faction1".to_string()),
-            ..Default::default()
-        };
-
-        let entity3 = Entity {
-            id: "entity3".to_string(),
-            x: 500.0,
-            y: 500.0,
-            entity_type: "player".to_string(),
-            faction: Some("faction2".to_string()),
-            ..Default::default()
-        };
+        let entity1 = Entity::new(
+            "entity1".to_string(),
+            EntityType::NPC,
+            100.0,
+            100.0,
+            0.0,
+            ChunkCoord::new(0, 0),
+        );
+        let entity2 = Entity::new(
+            "entity2".to_string(),
+            EntityType::Animal,
+            150.0,
+            100.0,
+            0.0,
+            ChunkCoord::new(0, 0),
+        );
+        let entity3 = Entity::new(
+            "entity3".to_string(),
+            EntityType::NPC,
+            500.0,
+            500.0,
+            0.0,
+            ChunkCoord::new(1, 1),
+        );
 
         world.entities.insert("entity1".to_string(), entity1);
         world.entities.insert("entity2".to_string(), entity2);
@@ -300,6 +782,21 @@ mod tests {
         assert_eq!(entities.len(), 2);
     }
 
+    #[test]
+    fn test_material_at() {
+        let mut world = World::new("Test".to_string(), "game1".to_string(), 2, 2);
+        world.initialize_chunks();
+        assert_eq!(SpatialQueries::material_at(&world, 10.0, 10.0), None);
+
+        let chunk = world.chunks.get_mut(&ChunkCoord::new(1, 0)).unwrap();
+        chunk.set_materials_at(4, 8, [0, 20, 235, 0]);
+        let (x, y) = (CHUNK_SIZE + 4.5, 8.5);
+        assert_eq!(SpatialQueries::material_weights_at(&world, x, y), Some([0, 20, 235, 0]));
+        assert_eq!(SpatialQueries::material_at(&world, x, y), Some(Material::Sand));
+        assert_eq!(SpatialQueries::material_at(&world, CHUNK_SIZE + 1.0, 1.0), Some(Material::Grass));
+        assert_eq!(SpatialQueries::material_at(&world, -1.0, 1.0), None);
+    }
+
     #[test]
     fn test_raycast() {
         let world = create_test_world();
@@ -345,6 +842,189 @@ mod tests {
         assert_eq!(faction1[0].faction.as_deref(), Some("faction1"));
     }
 
+    /// World with a height for every global heightmap sample, indexed like collision heightmaps
+    fn create_terrain_world(height: impl Fn(usize, usize) -> f32) -> World {
+        let mut world = World::new("Test".to_string(), "game1".to_string(), 2, 2);
+        world.initialize_chunks();
+        for chunk in world.chunks.values_mut() {
+            let origin = (
+                chunk.coord.x as usize * HEIGHTMAP_RESOLUTION,
+                chunk.coord.y as usize * HEIGHTMAP_RESOLUTION,
+            );
+            for x in 0..HEIGHTMAP_RESOLUTION {
+                for y in 0..HEIGHTMAP_RESOLUTION {
+                    chunk.elevation[x * HEIGHTMAP_RESOLUTION + y] = height(origin.0 + x, origin.1 + y);
+                }
+            }
+        }
+        world
+    }
+
+    fn close(a: Vec3, b: Vec3) -> bool {
+        (0..3).all(|i| (a[i] - b[i]).abs() < 1e-3)
+    }
+
+    #[test]
+    fn test_raycast_terrain() {
+        let flat = create_terrain_world(|_, _| 100.0);
+        let hit = SpatialQueries::raycast_terrain(&flat, [10.5, 20.5, 150.0], [0.0, 0.0, -1.0], 100.0).unwrap();
+        assert!(close(hit.position, [10.5, 20.5, 100.0]));
+        assert!(close(hit.normal, [0.0, 0.0, 1.0]));
+        assert!((hit.distance - 50.0).abs() < 1e-3);
+        assert_eq!(hit.chunks, vec![ChunkCoord::new(0, 0)]);
+
+        assert_eq!(SpatialQueries::raycast_terrain(&flat, [10.5, 20.5, 150.0], [0.0, 0.0, -1.0], 40.0), None);
+        assert_eq!(SpatialQueries::raycast_terrain(&flat, [10.5, 20.5, 150.0], [1.0, 1.0, 0.5], 1000.0), None);
+        assert_eq!(SpatialQueries::raycast_terrain(&flat, [-10.0, 20.0, 150.0], [-1.0, 0.0, -1.0], 1000.0), None);
+
+        // Starting below the surface hits right away
+        let hit = SpatialQueries::raycast_terrain(&flat, [10.0, 10.0, 90.0], [1.0, 0.0, 0.0], 100.0).unwrap();
+        assert_eq!(hit.distance, 0.0);
+
+        // Coming in from outside the world
+        let hit = SpatialQueries::raycast_terrain(&flat, [-50.0, 20.0, 150.0], [1.0, 0.0, -1.0], 1000.0).unwrap();
+        assert!(close(hit.position, [0.0, 20.0, 100.0]));
+    }
+
+    #[test]
+    fn test_raycast_terrain_slope_across_chunks() {
+        // Plane rising half a meter per meter along x
+        let slope = create_terrain_world(|x, _| 100.0 + 0.5 * x as f32);
+        let hit = SpatialQueries::raycast_terrain(&slope, [200.0, 10.5, 300.0], [1.0, 0.0, -1.0], 1000.0).unwrap();
+
+        let travelled = 200.0 / 3.0;
+        assert!(close(hit.position, [200.0 + travelled, 10.5, 300.0 - travelled]), "{:?}", hit.position);
+        assert!((hit.distance - travelled * 2f32.sqrt()).abs() < 1e-2);
+        let n = 1.25f32.sqrt();
+        assert!(close(hit.normal, [-0.5 / n, 0.0, 1.0 / n]));
+        assert_eq!(hit.chunks, vec![ChunkCoord::new(0, 0), ChunkCoord::new(1, 0)]);
+    }
+
+    #[test]
+    fn test_raycast_terrain_between_samples() {
+        // A single raised sample, which a ray passing at half its height meets halfway up the slope
+        let peak = create_terrain_world(|x, y| if (x, y) == (50, 50) { 110.0 } else { 100.0 });
+        assert_eq!(CollisionDetector::get_terrain_height(&peak, 49.5, 50.0), Some(100.0));
+
+        let hit = SpatialQueries::raycast_terrain(&peak, [40.0, 50.0, 105.0], [1.0, 0.0, 0.0], 100.0).unwrap();
+        assert!(close(hit.position, [49.5, 50.0, 105.0]), "{:?}", hit.position);
+        assert!((hit.distance - 9.5).abs() < 1e-3);
+        assert!(hit.normal[0] < -0.8 && hit.normal[2] > 0.0);
+
+        let hit = SpatialQueries::raycast_terrain(&peak, [40.0, 50.6, 105.0], [1.0, 0.0, 0.0], 100.0);
+        assert_eq!(hit, None);
+    }
+
+    /// World with an entity every 20 meters on a grid, at an elevation rising with x
+    fn create_crowd_world() -> World {
+        let mut world = World::new("Test".to_string(), "game1".to_string(), 2, 2);
+        world.initialize_chunks();
+        for i in 0..25 {
+            for j in 0..25 {
+                let (x, y) = (i as f32 * 20.0 + 5.0, j as f32 * 20.0 + 5.0);
+                let coord = ChunkCoord::new((x / CHUNK_SIZE) as u32, (y / CHUNK_SIZE) as u32);
+                world.add_entity(Entity::new(format!("e{}-{}", i, j), EntityType::NPC, x, y, i as f32, coord));
+            }
+        }
+        world
+    }
+
+    #[test]
+    fn test_k_nearest() {
+        let world = create_crowd_world();
+
+        // Close to the corner shared by four chunks
+        let (x, y) = (258.0, 251.0);
+        let nearest = SpatialQueries::k_nearest(&world, x, y, 6, 1000.0);
+        let mut expected: Vec<&Entity> = world.entities.values().collect();
+        expected.sort_by(|a, b| {
+            let da = (a.x - x).powi(2) + (a.y - y).powi(2);
+            let db = (b.x - x).powi(2) + (b.y - y).powi(2);
+            da.total_cmp(&db).then_with(|| a.id.cmp(&b.id))
+        });
+
+        let ids: Vec<&str> = nearest.iter().map(|e| e.id.as_str()).collect();
+        let expected: Vec<&str> = expected.iter().take(6).map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, expected);
+        assert_eq!(ids[0], "e13-12");
+
+        assert_eq!(SpatialQueries::k_nearest(&world, x, y, 6, 10.0).len(), 1);
+        assert!(SpatialQueries::k_nearest(&world, x, y, 0, 100.0).is_empty());
+        assert_eq!(SpatialQueries::k_nearest(&world, 5.0, 5.0, 1000, 1e6).len(), 625);
+
+        let world = create_test_world();
+        let ids: Vec<String> = SpatialQueries::k_nearest(&world, 140.0, 100.0, 3, 1000.0)
+            .iter()
+            .map(|e| e.id.clone())
+            .collect();
+        assert_eq!(ids, vec!["entity2", "entity1", "entity3"]);
+    }
+
+    #[test]
+    fn test_query_region() {
+        let world = create_crowd_world();
+
+        let region = Aabb::new([240.0, 0.0, 0.0], [290.0, 30.0, 13.0]);
+        let mut ids: Vec<String> = SpatialQueries::query_region(&world, &region)
+            .iter()
+            .map(|e| e.id.clone())
+            .collect();
+        ids.sort();
+        // Columns 12 to 14 stand in the box, but column 14 is above it
+        assert_eq!(ids, vec!["e12-0", "e12-1", "e13-0", "e13-1"]);
+
+        let outside = Aabb::new([-100.0, -100.0, 0.0], [-1.0, -1.0, 100.0]);
+        assert!(SpatialQueries::query_region(&world, &outside).is_empty());
+    }
+
+    #[test]
+    fn test_frustum() {
+        use std::f32::consts::FRAC_PI_2;
+
+        // Looking along x from the origin with a square 90 degree view
+        let frustum = Frustum::perspective([0.0, 0.0, 10.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0], FRAC_PI_2, 1.0, 1.0, 100.0);
+        assert!(frustum.contains_point([50.0, 0.0, 10.0]));
+        assert!(frustum.contains_point([50.0, 49.0, -39.0]));
+        assert!(!frustum.contains_point([50.0, 51.0, 10.0]));
+        assert!(!frustum.contains_point([50.0, 0.0, 61.0]));
+        assert!(!frustum.contains_point([-5.0, 0.0, 10.0]));
+        assert!(!frustum.contains_point([101.0, 0.0, 10.0]));
+
+        let bounds = frustum.bounds();
+        assert!((bounds.min[0] - 1.0).abs() < 1e-3 && (bounds.max[0] - 100.0).abs() < 1e-3);
+        assert!((bounds.min[1] + 100.0).abs() < 1e-3 && (bounds.max[2] - 110.0).abs() < 1e-3);
+
+        assert!(frustum.intersects_aabb(&Aabb::new([40.0, -5.0, 5.0], [60.0, 5.0, 15.0])));
+        assert!(!frustum.intersects_aabb(&Aabb::new([-20.0, -5.0, 5.0], [-10.0, 5.0, 15.0])));
+        assert!(!frustum.intersects_aabb(&Aabb::new([10.0, 30.0, 5.0], [20.0, 40.0, 15.0])));
+        assert!(frustum.intersects_sphere([-0.5, 0.0, 10.0], 2.0));
+        assert!(!frustum.intersects_sphere([-5.0, 0.0, 10.0], 2.0));
+    }
+
+    #[test]
+    fn test_query_frustum() {
+        let world = create_crowd_world();
+
+        // Narrow view along y from below the grid, over the column of entities at x = 305
+        let frustum = Frustum::perspective([305.0, -10.0, 15.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0], 0.2, 1.0, 1.0, 300.0);
+        let mut ids: Vec<String> = SpatialQueries::query_frustum(&world, &frustum)
+            .iter()
+            .map(|e| e.id.clone())
+            .collect();
+        ids.sort();
+
+        let mut expected: Vec<String> = world
+            .entities
+            .values()
+            .filter(|e| frustum.contains_point([e.x, e.y, e.z]))
+            .map(|e| e.id.clone())
+            .collect();
+        expected.sort();
+        assert_eq!(ids, expected);
+        assert!(ids.iter().all(|id| ["e14-", "e15-", "e16-"].iter().any(|column| id.starts_with(column))));
+        assert!(ids.len() > 5);
+    }
+
     #[test]
     fn test_count_entities_in_radius() {
         let world = create_test_world();
@@ -0,0 +1,192 @@
+//! Terrain material splat maps
+//!
+//! Every heightmap sample of a generated chunk gets weights for grass, rock, sand and snow,
+//! stored in [`Chunk::materials`]. The ground material follows the biome and altitude: sand on
+//! beaches and in deserts, snow above the snow line and in tundra, grass elsewhere. Steep
+//! slopes turn to rock whatever the ground. Renderers blend textures by the weights, and
+//! gameplay reads the dominant material, e.g. for footstep sounds.
+
+use crate::constants::*;
+use crate::{Biome, Chunk};
+
+/// Rules turning slope, altitude and biome into material weights
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SplatRules {
+    /// Slope (rise over run) where rock starts to show
+    pub rock_slope_start: f32,
+    /// Slope where the ground is all rock
+    pub rock_slope_full: f32,
+    /// Height where snow is halfway blended in (in meters)
+    pub snow_line: f32,
+    /// Height range over which snow blends in, centered on the snow line (in meters)
+    pub snow_blend: f32,
+    /// Height above the water level that beaches reach up to (in meters)
+    pub sand_band: f32,
+}
+
+impl Default for SplatRules {
+    fn default() -> Self {
+        Self {
+            rock_slope_start: SPLAT_ROCK_SLOPE_START,
+            rock_slope_full: SPLAT_ROCK_SLOPE_FULL,
+            snow_line: SPLAT_SNOW_LINE,
+            snow_blend: SPLAT_SNOW_BLEND,
+            sand_band: SPLAT_SAND_BAND,
+        }
+    }
+}
+
+impl SplatRules {
+    /// Set the slopes over which ground turns to rock
+    pub fn with_rock_slope(mut self, start: f32, full: f32) -> Self {
+        self.rock_slope_start = start.max(0.0);
+        self.rock_slope_full = full.max(self.rock_slope_start);
+        self
+    }
+
+    /// Set the snow line and the height range it blends over
+    pub fn with_snow_line(mut self, height: f32, blend: f32) -> Self {
+        self.snow_line = height;
+        self.snow_blend = blend.max(0.0);
+        self
+    }
+
+    /// Set how far above the water level beaches reach
+    pub fn with_sand_band(mut self, band: f32) -> Self {
+        self.sand_band = band.max(0.0);
+        self
+    }
+
+    /// Compute the material weights of a chunk from its heightmap, biome and water level
+    ///
+    /// Weights are indexed like [`Chunk::elevation`], in the order of
+    /// [`Material::ALL`](entropic_world_core::spatial::Material::ALL), and
+    /// every sample sums to 255.
+    pub fn weights(&self, chunk: &Chunk) -> Vec<[u8; 4]> {
+        let heights = &chunk.elevation;
+        let res = HEIGHTMAP_RESOLUTION;
+        if heights.len() != res * res {
+            return Vec::new();
+        }
+
+        // Mountains expose rock on gentler slopes
+        let slope_scale = if chunk.biome == Biome::Mountains { 2.0 } else { 1.0 };
+        let spacing = CHUNK_SIZE / res as f32;
+        let height = |x: usize, y: usize| heights[y * res + x];
+
+        (0..res * res)
+            .map(|i| {
+                let (x, y) = (i % res, i / res);
+                let h = height(x, y);
+                let (left, right) = (x.saturating_sub(1), (x + 1).min(res - 1));
+                let (down, up) = (y.saturating_sub(1), (y + 1).min(res - 1));
+                let dx = (height(right, y) - height(left, y)) / ((right - left) as f32 * spacing);
+                let dy = (height(x, up) - height(x, down)) / ((up - down) as f32 * spacing);
+                let slope = (dx * dx + dy * dy).sqrt() * slope_scale;
+
+                let rock = smoothstep(self.rock_slope_start, self.rock_slope_full, slope);
+                let (grass, sand, snow) = self.ground(chunk.biome, chunk.water_level, h);
+                let ground = 1.0 - rock;
+                quantize([grass * ground, rock, sand * ground, snow * ground])
+            })
+            .collect()
+    }
+
+    /// Paint the material weights of a chunk
+    pub fn paint(&self, chunk: &mut Chunk) {
+        chunk.materials = self.weights(chunk);
+    }
+
+    /// Split flat ground at a height into grass, sand and snow
+    fn ground(&self, biome: Biome, water_level: f32, height: f32) -> (f32, f32, f32) {
+        let half = self.snow_blend / 2.0;
+        let snow = match biome {
+            Biome::Tundra => 1.0,
+            _ => smoothstep(self.snow_line - half, self.snow_line + half, height),
+        };
+        let sand = match biome {
+            Biome::Desert | Biome::Ocean => 1.0,
+            _ => 1.0 - smoothstep(water_level, water_level + self.sand_band, height),
+        } * (1.0 - snow);
+        (1.0 - snow - sand, sand, snow)
+    }
+}
+
+/// Hermite step from 0 at `edge0` to 1 at `edge1`
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    if edge1 <= edge0 {
+        return if x >= edge1 { 1.0 } else { 0.0 };
+    }
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Round weights summing to 1 into bytes summing to 255
+fn quantize(weights: [f32; 4]) -> [u8; 4] {
+    let mut bytes = weights.map(|w| (w.clamp(0.0, 1.0) * 255.0).round() as i32);
+    let error = 255 - bytes.iter().sum::<i32>();
+    let largest = (0..4).max_by(|&a, &b| weights[a].total_cmp(&weights[b])).unwrap_or(0);
+    bytes[largest] = (bytes[largest] + error).clamp(0, 255);
+    bytes.map(|b| b as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ChunkCoord, TerrainGenerator, TerrainSource};
+    use entropic_world_core::spatial::Material;
+
+    fn flat_chunk(biome: Biome, height: f32) -> Chunk {
+        let mut chunk = Chunk::new(ChunkCoord::new(0, 0));
+        chunk.biome = biome;
+        chunk.water_level = 50.0;
+        chunk.elevation.iter_mut().for_each(|h| *h = height);
+        chunk
+    }
+
+    #[test]
+    fn test_ground_follows_biome_and_altitude() {
+        let rules = SplatRules::default();
+        let at = |biome, height| {
+            let mut chunk = flat_chunk(biome, height);
+            rules.paint(&mut chunk);
+            chunk.get_material_at(10, 10).unwrap()
+        };
+
+        assert_eq!(at(Biome::Plains, 120.0), Material::Grass);
+        assert_eq!(at(Biome::Plains, 51.0), Material::Sand);
+        assert_eq!(at(Biome::Desert, 120.0), Material::Sand);
+        assert_eq!(at(Biome::Tundra, 120.0), Material::Snow);
+        assert_eq!(at(Biome::Plains, SPLAT_SNOW_LINE + SPLAT_SNOW_BLEND), Material::Snow);
+    }
+
+    #[test]
+    fn test_steep_slopes_are_rock() {
+        let mut chunk = flat_chunk(Biome::Plains, 100.0);
+        for (i, h) in chunk.elevation.iter_mut().enumerate() {
+            let x = (i % HEIGHTMAP_RESOLUTION) as f32;
+            // Steep cliff on the right half, gentle on the left
+            *h = if x < 128.0 { 100.0 + x * 0.1 } else { 112.8 + (x - 128.0) * 2.0 };
+        }
+        let weights = SplatRules::default().weights(&chunk);
+
+        assert!(weights.iter().all(|w| w.iter().map(|&b| b as u32).sum::<u32>() == 255));
+        assert_eq!(Material::dominant(weights[64 * HEIGHTMAP_RESOLUTION + 64]), Material::Grass);
+        assert_eq!(weights[64 * HEIGHTMAP_RESOLUTION + 200], [0, 255, 0, 0]);
+
+        // Mountains show rock on gentler slopes
+        let rules = SplatRules::default().with_rock_slope(0.12, 0.18);
+        let gentle = 64 * HEIGHTMAP_RESOLUTION + 64;
+        assert_eq!(rules.weights(&chunk)[gentle][1], 0);
+        chunk.biome = Biome::Mountains;
+        assert_eq!(rules.weights(&chunk)[gentle], [0, 255, 0, 0]);
+    }
+
+    #[test]
+    fn test_generated_chunks_are_painted() {
+        let generator = TerrainGenerator::with_seed(7);
+        let chunk = TerrainSource::generate_chunk(&generator, ChunkCoord::new(2, 3)).unwrap();
+        assert_eq!(chunk.materials.len(), HEIGHTMAP_RESOLUTION * HEIGHTMAP_RESOLUTION);
+        assert_eq!(chunk.materials, SplatRules::default().weights(&chunk));
+    }
+}
@@ -0,0 +1,465 @@
+use crate::constants::*;
+use crate::spatial_queries::SpatialQueries;
+use crate::{Entity, World};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Distance below which a waypoint counts as reached (in meters)
+const DEFAULT_ARRIVAL_RADIUS: f32 = 1.0;
+
+/// Waypoint cursor for an entity walking along a path
+#[derive(Clone, Debug, PartialEq)]
+pub struct PathFollower {
+    waypoints: Vec<(f32, f32)>,
+    index: usize,
+    arrival_radius: f32,
+}
+
+impl PathFollower {
+    /// Create a follower for a path
+    pub fn new(waypoints: Vec<(f32, f32)>) -> Self {
+        Self {
+            waypoints,
+            index: 0,
+            arrival_radius: DEFAULT_ARRIVAL_RADIUS,
+        }
+    }
+
+    /// Set the distance at which a waypoint counts as reached
+    pub fn with_arrival_radius(mut self, arrival_radius: f32) -> Self {
+        self.arrival_radius = arrival_radius.max(0.0);
+        self
+    }
+
+    /// Get the waypoint to head for, skipping the ones already reached
+    pub fn current_waypoint(&mut self, position: (f32, f32)) -> Option<(f32, f32)> {
+        while let Some(&waypoint) = self.waypoints.get(self.index) {
+            if length(sub(waypoint, position)) > self.arrival_radius {
+                return Some(waypoint);
+            }
+            self.index += 1;
+        }
+        None
+    }
+
+    /// Check if the last waypoint is the one being headed for
+    pub fn is_last_waypoint(&self) -> bool {
+        self.index + 1 >= self.waypoints.len()
+    }
+
+    /// Check if every waypoint was reached
+    pub fn is_finished(&self) -> bool {
+        self.index >= self.waypoints.len()
+    }
+}
+
+/// Local steering between path waypoints
+///
+/// Combines reciprocal velocity obstacles with boids-style separation: candidate velocities
+/// are sampled around the preferred one and scored by how soon they would collide with a
+/// nearby entity, then a separation push keeps crowds from bunching up. Moving neighbors are
+/// assumed to steer as well and take half of the avoidance; still neighbors are treated as
+/// static obstacles.
+#[derive(Clone, Debug)]
+pub struct Steering {
+    radius: f32,
+    max_speed: f32,
+    neighbor_radius: f32,
+    time_horizon: f32,
+    avoidance_weight: f32,
+    separation_weight: f32,
+    samples: usize,
+}
+
+impl Default for Steering {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Steering {
+    /// Create steering with the default tuning
+    pub fn new() -> Self {
+        Self {
+            radius: ENTITY_RADIUS,
+            max_speed: STEERING_MAX_SPEED,
+            neighbor_radius: STEERING_NEIGHBOR_RADIUS,
+            time_horizon: STEERING_TIME_HORIZON,
+            avoidance_weight: 1.0,
+            separation_weight: 0.5,
+            samples: 32,
+        }
+    }
+
+    /// Set the collision radius of steered entities
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius.max(0.0);
+        self
+    }
+
+    /// Set the maximum speed of steered entities
+    pub fn with_max_speed(mut self, max_speed: f32) -> Self {
+        self.max_speed = max_speed.max(0.0);
+        self
+    }
+
+    /// Set the radius searched for neighbors
+    pub fn with_neighbor_radius(mut self, neighbor_radius: f32) -> Self {
+        self.neighbor_radius = neighbor_radius.max(0.0);
+        self
+    }
+
+    /// Set how far ahead collisions are avoided (in seconds)
+    pub fn with_time_horizon(mut self, time_horizon: f32) -> Self {
+        self.time_horizon = time_horizon.max(f32::EPSILON);
+        self
+    }
+
+    /// Set how strongly imminent collisions are penalized
+    pub fn with_avoidance_weight(mut self, avoidance_weight: f32) -> Self {
+        self.avoidance_weight = avoidance_weight.max(0.0);
+        self
+    }
+
+    /// Set how strongly entities push away from close neighbors
+    pub fn with_separation_weight(mut self, separation_weight: f32) -> Self {
+        self.separation_weight = separation_weight.max(0.0);
+        self
+    }
+
+    /// Set the number of sampled directions per speed
+    pub fn with_samples(mut self, samples: usize) -> Self {
+        self.samples = samples.max(4);
+        self
+    }
+
+    /// Get the velocity heading straight for a waypoint
+    ///
+    /// Slows down within one second of the waypoint so the entity doesn't overshoot it.
+    pub fn preferred_velocity(&self, position: (f32, f32), waypoint: (f32, f32)) -> (f32, f32) {
+        let offset = sub(waypoint, position);
+        let distance = length(offset);
+        if distance <= f32::EPSILON {
+            return (0.0, 0.0);
+        }
+        scale(offset, self.max_speed.min(distance) / distance)
+    }
+
+    /// Steer an entity of the world toward a waypoint around its neighbors
+    pub fn steer(&self, world: &World, entity: &Entity, waypoint: (f32, f32)) -> (f32, f32) {
+        let neighbors = self.neighbors(world, entity);
+        let preferred = self.preferred_velocity((entity.x, entity.y), waypoint);
+        self.compute_velocity((entity.x, entity.y), entity.velocity, preferred, &neighbors)
+    }
+
+    /// Steer an entity of the world along a path
+    ///
+    /// Returns a zero velocity, still pushed away from neighbors, once the path is finished.
+    pub fn follow(&self, world: &World, entity: &Entity, follower: &mut PathFollower) -> (f32, f32) {
+        let position = (entity.x, entity.y);
+        let preferred = match follower.current_waypoint(position) {
+            Some(waypoint) if follower.is_last_waypoint() => {
+                self.preferred_velocity(position, waypoint)
+            }
+            Some(waypoint) => {
+                let offset = sub(waypoint, position);
+                scale(offset, self.max_speed / length(offset))
+            }
+            None => (0.0, 0.0),
+        };
+
+        let neighbors = self.neighbors(world, entity);
+        self.compute_velocity(position, entity.velocity, preferred, &neighbors)
+    }
+
+    /// Get the living entities around an entity that steering reacts to
+    pub fn neighbors(&self, world: &World, entity: &Entity) -> Vec<Arc<Entity>> {
+        // Near the world edge the radius query can visit the same chunk twice.
+        let mut seen = HashSet::new();
+        SpatialQueries::query_radius(world, entity.x, entity.y, self.neighbor_radius)
+            .into_iter()
+            .filter(|neighbor| neighbor.id != entity.id && neighbor.is_alive)
+            .filter(|neighbor| seen.insert(neighbor.id.clone()))
+            .collect()
+    }
+
+    /// Pick a velocity close to the preferred one that avoids the given neighbors
+    pub fn compute_velocity(
+        &self,
+        position: (f32, f32),
+        velocity: (f32, f32),
+        preferred: (f32, f32),
+        neighbors: &[Arc<Entity>],
+    ) -> (f32, f32) {
+        let preferred = clamp_length(preferred, self.max_speed);
+        if neighbors.is_empty() {
+            return preferred;
+        }
+
+        let mut best = preferred;
+        let mut best_penalty = self.penalty(position, velocity, preferred, preferred, neighbors);
+
+        for candidate in self.candidates() {
+            if best_penalty <= 0.0 {
+                break;
+            }
+            let penalty = self.penalty(position, velocity, candidate, preferred, neighbors);
+            if penalty < best_penalty {
+                best = candidate;
+                best_penalty = penalty;
+            }
+        }
+
+        let separation = self.separation(position, neighbors);
+        clamp_length(add(best, scale(separation, self.separation_weight)), self.max_speed)
+    }
+
+    /// Get the boids-style push away from neighbors closer than the neighbor radius
+    ///
+    /// Each neighbor contributes up to `max_speed`, growing linearly as it gets closer.
+    pub fn separation(&self, position: (f32, f32), neighbors: &[Arc<Entity>]) -> (f32, f32) {
+        let mut push = (0.0, 0.0);
+        for neighbor in neighbors {
+            let away = sub(position, (neighbor.x, neighbor.y));
+            let distance = length(away);
+            if distance <= f32::EPSILON || distance >= self.neighbor_radius {
+                continue;
+            }
+            let strength = self.max_speed * (1.0 - distance / self.neighbor_radius);
+            push = add(push, scale(away, strength / distance));
+        }
+        push
+    }
+
+    /// Sampled candidate velocities at full and half speed, plus standing still
+    fn candidates(&self) -> impl Iterator<Item = (f32, f32)> + '_ {
+        let step = std::f32::consts::TAU / self.samples as f32;
+        [1.0, 0.5]
+            .into_iter()
+            .flat_map(move |fraction| {
+                (0..self.samples).map(move |i| {
+                    let angle = i as f32 * step;
+                    (
+                        angle.cos() * self.max_speed * fraction,
+                        angle.sin() * self.max_speed * fraction,
+                    )
+                })
+            })
+            .chain(std::iter::once((0.0, 0.0)))
+    }
+
+    /// Score a candidate velocity: deviation from the preferred velocity plus collision urgency
+    fn penalty(
+        &self,
+        position: (f32, f32),
+        velocity: (f32, f32),
+        candidate: (f32, f32),
+        preferred: (f32, f32),
+        neighbors: &[Arc<Entity>],
+    ) -> f32 {
+        let mut earliest = f32::INFINITY;
+        for neighbor in neighbors {
+            let relative = if length(neighbor.velocity) > f32::EPSILON {
+                // Reciprocal: both sides take half of the avoidance.
+                sub(sub(scale(candidate, 2.0), velocity), neighbor.velocity)
+            } else {
+                candidate
+            };
+            let offset = sub((neighbor.x, neighbor.y), position);
+            if let Some(time) = time_to_collision(offset, relative, self.radius + ENTITY_RADIUS) {
+                earliest = earliest.min(time);
+            }
+        }
+
+        let deviation = length(sub(candidate, preferred));
+        if earliest >= self.time_horizon {
+            deviation
+        } else {
+            deviation + self.avoidance_weight * self.max_speed / earliest.max(1e-3)
+        }
+    }
+}
+
+/// Time until a disc moving with `velocity` touches another disc at `offset`
+///
+/// Overlapping discs collide immediately unless they are already moving apart.
+fn time_to_collision(offset: (f32, f32), velocity: (f32, f32), combined_radius: f32) -> Option<f32> {
+    let approach = dot(offset, velocity);
+    let gap = dot(offset, offset) - combined_radius * combined_radius;
+    if gap <= 0.0 {
+        return if approach > 0.0 { Some(0.0) } else { None };
+    }
+
+    let speed_squared = dot(velocity, velocity);
+    if speed_squared <= f32::EPSILON {
+        return None;
+    }
+    let discriminant = approach * approach - speed_squared * gap;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let time = (approach - discriminant.sqrt()) / speed_squared;
+    if time >= 0.0 {
+        Some(time)
+    } else {
+        None
+    }
+}
+
+fn add(a: (f32, f32), b: (f32, f32)) -> (f32, f32) {
+    (a.0 + b.0, a.1 + b.1)
+}
+
+fn sub(a: (f32, f32), b: (f32, f32)) -> (f32, f32) {
+    (a.0 - b.0, a.1 - b.1)
+}
+
+fn scale(a: (f32, f32), factor: f32) -> (f32, f32) {
+    (a.0 * factor, a.1 * factor)
+}
+
+fn dot(a: (f32, f32), b: (f32, f32)) -> f32 {
+    a.0 * b.0 + a.1 * b.1
+}
+
+fn length(a: (f32, f32)) -> f32 {
+    dot(a, a).sqrt()
+}
+
+fn clamp_length(a: (f32, f32), max: f32) -> (f32, f32) {
+    let len = length(a);
+    if len > max && len > 0.0 {
+        scale(a, max / len)
+    } else {
+        a
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChunkCoord;
+    use entropic_world_core::population::EntityType;
+
+    fn create_test_world() -> World {
+        let mut world = World::new("Test".to_string(), "game1".to_string(), 2, 2);
+        world.initialize_chunks();
+        world
+    }
+
+    fn add_entity(world: &mut World, id: &str, x: f32, y: f32, velocity: (f32, f32)) {
+        let mut entity = Entity::new(id.to_string(), EntityType::NPC, x, y, 0.0, ChunkCoord::new(0, 0));
+        entity.velocity = velocity;
+        world.add_entity(entity);
+    }
+
+    fn distance(world: &World, a: &str, b: &str) -> f32 {
+        let a = &world.entities[a];
+        let b = &world.entities[b];
+        length(sub((a.x, a.y), (b.x, b.y)))
+    }
+
+    /// Step every follower once with a fixed time step
+    fn simulate(world: &mut World, steering: &Steering, followers: &mut [(&str, PathFollower)], dt: f32) {
+        let velocities: Vec<(f32, f32)> = followers
+            .iter_mut()
+            .map(|(id, follower)| steering.follow(world, &world.entities[*id], follower))
+            .collect();
+
+        for ((id, _), velocity) in followers.iter().zip(velocities) {
+            let entity = world.entities.get_mut(*id).unwrap();
+            entity.velocity = velocity;
+            entity.x += velocity.0 * dt;
+            entity.y += velocity.1 * dt;
+        }
+    }
+
+    #[test]
+    fn test_path_follower_advances() {
+        let mut follower = PathFollower::new(vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)]);
+
+        assert_eq!(follower.current_waypoint((0.0, 0.0)), Some((10.0, 0.0)));
+        assert!(!follower.is_last_waypoint());
+        assert_eq!(follower.current_waypoint((9.5, 0.0)), Some((10.0, 10.0)));
+        assert!(follower.is_last_waypoint());
+        assert_eq!(follower.current_waypoint((10.0, 10.0)), None);
+        assert!(follower.is_finished());
+    }
+
+    #[test]
+    fn test_unobstructed_heads_for_waypoint() {
+        let mut world = create_test_world();
+        add_entity(&mut world, "walker", 50.0, 50.0, (0.0, 0.0));
+        let steering = Steering::new().with_max_speed(2.0);
+
+        let velocity = steering.steer(&world, &world.entities["walker"], (100.0, 50.0));
+        assert!((velocity.0 - 2.0).abs() < 1e-5);
+        assert!(velocity.1.abs() < 1e-5);
+
+        // Slows down on arrival
+        let velocity = steering.steer(&world, &world.entities["walker"], (50.5, 50.0));
+        assert!((velocity.0 - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_avoids_static_neighbor() {
+        let mut world = create_test_world();
+        add_entity(&mut world, "walker", 50.0, 50.0, (2.0, 0.0));
+        add_entity(&mut world, "rock", 54.0, 50.0, (0.0, 0.0));
+        let steering = Steering::new();
+
+        let velocity = steering.steer(&world, &world.entities["walker"], (100.0, 50.0));
+        let offset = (4.0, 0.0);
+        if let Some(time) = time_to_collision(offset, velocity, 2.0) {
+            assert!(time >= STEERING_TIME_HORIZON);
+        }
+        assert!(velocity.0 > 0.0);
+    }
+
+    #[test]
+    fn test_ignores_self_and_dead_neighbors() {
+        let mut world = create_test_world();
+        add_entity(&mut world, "walker", 50.0, 50.0, (0.0, 0.0));
+        add_entity(&mut world, "corpse", 52.0, 50.0, (0.0, 0.0));
+        world.entities.get_mut("corpse").unwrap().is_alive = false;
+        let steering = Steering::new();
+
+        assert!(steering.neighbors(&world, &world.entities["walker"]).is_empty());
+    }
+
+    #[test]
+    fn test_separation_pushes_apart() {
+        let mut world = create_test_world();
+        add_entity(&mut world, "a", 50.0, 50.0, (0.0, 0.0));
+        add_entity(&mut world, "b", 51.0, 50.0, (0.0, 0.0));
+        let steering = Steering::new();
+
+        let neighbors = steering.neighbors(&world, &world.entities["a"]);
+        assert_eq!(neighbors.len(), 1);
+        let push = steering.separation((50.0, 50.0), &neighbors);
+        assert!(push.0 < 0.0);
+        assert!(push.1.abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_head_on_agents_pass_each_other() {
+        let mut world = create_test_world();
+        add_entity(&mut world, "east", 20.0, 100.0, (0.0, 0.0));
+        add_entity(&mut world, "west", 80.0, 100.0, (0.0, 0.0));
+        let steering = Steering::new();
+        let mut followers = [
+            ("east", PathFollower::new(vec![(80.0, 100.0)])),
+            ("west", PathFollower::new(vec![(20.0, 100.0)])),
+        ];
+
+        let mut closest = f32::INFINITY;
+        for _ in 0..600 {
+            simulate(&mut world, &steering, &mut followers, 0.1);
+            closest = closest.min(distance(&world, "east", "west"));
+        }
+
+        assert!(closest >= 2.0 * ENTITY_RADIUS * 0.9, "agents came within {}", closest);
+        assert!(followers.iter().all(|(_, follower)| follower.is_finished()));
+    }
+}
@@ -1,9 +1,16 @@
 use crate::chunk_manager::{ChunkManager, Priority};
 use crate::constants::*;
 use crate::errors::SpatialError;
-use crate::{ChunkCoord, World};
+use crate::serialization::ChunkPacket;
+use crate::terrain_source::TerrainSource;
+use crate::{Chunk, ChunkCoord, World};
 use std::collections::VecDeque;
-use std::sync::Arc;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc as std_mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
 use tokio::sync::mpsc;
 
 /// Chunk streaming protocol for async loading/unloading
@@ -149,11 +156,13 @@ async fn streaming_task(
                 }
             }
 
-            // Process load queue on tick
+            // Process a batch of the load queue on tick
             _ = tick_interval.tick() => {
                 if !paused {
-                    if let Ok(Some(coord)) = manager.process_load_queue().await {
-                        let _ = event_tx.send(StreamingEvent::ChunkLoaded { coord });
+                    if let Ok(loaded) = manager.process_load_queue_parallel(manager.max_in_flight()).await {
+                        for coord in loaded {
+                            let _ = event_tx.send(StreamingEvent::ChunkLoaded { coord });
+                        }
                     }
 
                     // Process unloading
@@ -168,9 +177,427 @@ async fn streaming_task(
     }
 }
 
+/// Source of complete chunks from outside the engine, such as a network service
+///
+/// Implementations wrap whatever protocol serves the chunks, be it HTTP, gRPC or a custom
+/// one. Fetches run on the generation threads, so they may block, but should give up once the
+/// timeout has passed; [`ChunkFetcher`] stops waiting for them then either way.
+///
+/// Chunks arrive as [`ChunkPacket`]s, whose checksum and terrain recipe are verified before
+/// the chunk is used.
+pub trait ChunkSource: Send + Sync {
+    /// Fetch a chunk, `Ok(None)` if the source does not have it
+    fn fetch(&self, coord: ChunkCoord, timeout: Duration) -> Result<Option<ChunkPacket>, SpatialError>;
+}
+
+/// Timeout and retry handling for a [`ChunkSource`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FetchPolicy {
+    /// Time allowed for each attempt
+    pub timeout: Duration,
+    /// Attempts made after the first one fails
+    pub retries: u32,
+    /// Wait before the first retry, doubling for every further one
+    pub retry_delay: Duration,
+    /// Generate chunks procedurally once every attempt failed, instead of failing the load
+    pub fallback: bool,
+}
+
+impl Default for FetchPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_millis(CHUNK_FETCH_TIMEOUT_MS),
+            retries: CHUNK_FETCH_RETRIES,
+            retry_delay: Duration::from_millis(CHUNK_FETCH_RETRY_DELAY_MS),
+            fallback: true,
+        }
+    }
+}
+
+impl FetchPolicy {
+    /// Set the time allowed for each attempt
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set how many times failed fetches are retried, and the wait before the first retry
+    pub fn with_retries(mut self, retries: u32, retry_delay: Duration) -> Self {
+        self.retries = retries;
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Set whether chunks that cannot be fetched are generated instead
+    pub fn with_fallback(mut self, fallback: bool) -> Self {
+        self.fallback = fallback;
+        self
+    }
+}
+
+/// Counters of a chunk fetcher
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FetchStats {
+    /// Chunks received from the source
+    pub fetched: u64,
+    /// Chunks the source does not have
+    pub missing: u64,
+    /// Attempts made after a failed one
+    pub retries: u64,
+    /// Attempts that ran out of time
+    pub timeouts: u64,
+    /// Chunks rejected as corrupted, stale or for another coordinate
+    pub rejected: u64,
+    /// Chunks given up on after every attempt failed
+    pub failures: u64,
+}
+
+#[derive(Default)]
+struct FetchCounters {
+    fetched: AtomicU64,
+    missing: AtomicU64,
+    retries: AtomicU64,
+    timeouts: AtomicU64,
+    rejected: AtomicU64,
+    failures: AtomicU64,
+}
+
+/// Chunk source with timeouts, retries and verification
+///
+/// Every attempt runs on a thread of its own, so a source ignoring its timeout only holds
+/// up that thread.
+pub struct ChunkFetcher {
+    source: Arc<dyn ChunkSource>,
+    policy: FetchPolicy,
+    recipe: u64,
+    counters: FetchCounters,
+}
+
+impl ChunkFetcher {
+    /// Create a fetcher for a source serving chunks of the terrain with a recipe
+    pub fn new(source: Arc<dyn ChunkSource>, policy: FetchPolicy, recipe: u64) -> Self {
+        Self {
+            source,
+            policy,
+            recipe,
+            counters: FetchCounters::default(),
+        }
+    }
+
+    /// Get the terrain recipe fetched chunks must have been made for
+    pub fn recipe(&self) -> u64 {
+        self.recipe
+    }
+
+    /// Get the timeout and retry policy
+    pub fn policy(&self) -> FetchPolicy {
+        self.policy
+    }
+
+    /// Get the fetch counters
+    pub fn stats(&self) -> FetchStats {
+        FetchStats {
+            fetched: self.counters.fetched.load(Ordering::Relaxed),
+            missing: self.counters.missing.load(Ordering::Relaxed),
+            retries: self.counters.retries.load(Ordering::Relaxed),
+            timeouts: self.counters.timeouts.load(Ordering::Relaxed),
+            rejected: self.counters.rejected.load(Ordering::Relaxed),
+            failures: self.counters.failures.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Fetch a chunk, retrying attempts that fail or time out
+    ///
+    /// Corrupted chunks and chunks for another coordinate count as failed attempts, and are
+    /// reported as such when the last attempt returned one. Stale chunks fail the fetch right
+    /// away, as the source would keep serving them, while an answer that the source does not
+    /// have the chunk is final.
+    pub fn fetch(&self, coord: ChunkCoord) -> Result<Option<Chunk>, SpatialError> {
+        let attempts = self.policy.retries.saturating_add(1);
+        let mut delay = self.policy.retry_delay;
+        let mut message = String::new();
+        let mut rejection = None;
+
+        for attempt in 0..attempts {
+            if attempt > 0 {
+                self.counters.retries.fetch_add(1, Ordering::Relaxed);
+                thread::sleep(delay);
+                delay = delay.saturating_mul(2);
+            }
+
+            match self.attempt(coord) {
+                Ok(Some(packet)) => match packet.open(coord, self.recipe) {
+                    Ok(chunk) => {
+                        self.counters.fetched.fetch_add(1, Ordering::Relaxed);
+                        return Ok(Some(chunk));
+                    }
+                    Err(e) => {
+                        self.counters.rejected.fetch_add(1, Ordering::Relaxed);
+                        if let SpatialError::StaleChunk { .. } = e {
+                            self.counters.failures.fetch_add(1, Ordering::Relaxed);
+                            return Err(e);
+                        }
+                        rejection = Some(e);
+                    }
+                },
+                Ok(None) => {
+                    self.counters.missing.fetch_add(1, Ordering::Relaxed);
+                    return Ok(None);
+                }
+                Err(e) => {
+                    message = e;
+                    rejection = None;
+                }
+            }
+        }
+
+        self.counters.failures.fetch_add(1, Ordering::Relaxed);
+        Err(rejection.unwrap_or(SpatialError::ChunkFetchFailed {
+            coord,
+            attempts,
+            message,
+        }))
+    }
+
+    fn attempt(&self, coord: ChunkCoord) -> Result<Option<ChunkPacket>, String> {
+        let (tx, rx) = std_mpsc::channel();
+        let source = self.source.clone();
+        let timeout = self.policy.timeout;
+        thread::spawn(move || {
+            let _ = tx.send(source.fetch(coord, timeout));
+        });
+
+        match rx.recv_timeout(timeout) {
+            Ok(result) => result.map_err(|e| e.to_string()),
+            Err(std_mpsc::RecvTimeoutError::Timeout) => {
+                self.counters.timeouts.fetch_add(1, Ordering::Relaxed);
+                Err(format!("timed out after {} ms", timeout.as_millis()))
+            }
+            Err(std_mpsc::RecvTimeoutError::Disconnected) => Err("source panicked".to_string()),
+        }
+    }
+}
+
+/// Work for the disk cache writer thread
+enum CacheWrite {
+    Chunk(PathBuf, Arc<Chunk>),
+    Flush(std_mpsc::Sender<()>),
+}
+
+/// Counters of a disk cache
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DiskCacheStats {
+    /// Chunks loaded from disk
+    pub hits: u64,
+    /// Chunks asked for but not on disk
+    pub misses: u64,
+    /// Chunk files removed as unreadable, corrupted or stale, also counted as misses
+    pub rejected: u64,
+    /// Chunks written to disk
+    pub writes: u64,
+    /// Chunks that failed to write
+    pub write_errors: u64,
+}
+
+#[derive(Default)]
+struct DiskCacheCounters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    rejected: AtomicU64,
+    writes: AtomicU64,
+    write_errors: AtomicU64,
+}
+
+/// Chunks persisted on disk, so they are reloaded instead of regenerated
+///
+/// Files are keyed by world id, seed and coordinate under
+/// `<root>/<world id>/<seed>-<recipe>/<x>_<y>.chunk`, where the recipe is the
+/// [`TerrainSource::recipe`] fingerprint. Chunks cached for any other recipe of the world are
+/// removed when the cache is opened. Chunks are stored as [`ChunkPacket`]s and verified when
+/// read back. Writes happen in order on a background thread.
+pub struct ChunkDiskCache {
+    dir: PathBuf,
+    world_dir: PathBuf,
+    recipe: u64,
+    writer: Mutex<std_mpsc::Sender<CacheWrite>>,
+    counters: Arc<DiskCacheCounters>,
+}
+
+impl ChunkDiskCache {
+    /// Open the cache of a world generated by a terrain source
+    pub fn new<P: AsRef<Path>>(root: P, world_id: &str, source: &dyn TerrainSource) -> Result<Self, SpatialError> {
+        let world_dir = root.as_ref().join(sanitize(world_id));
+        let recipe = source.recipe();
+        let dir = world_dir.join(format!("{}-{:016x}", source.seed(), recipe));
+        fs::create_dir_all(&dir).map_err(|e| SpatialError::IoError {
+            message: e.to_string(),
+        })?;
+
+        let (tx, rx) = std_mpsc::channel();
+        let counters = Arc::new(DiskCacheCounters::default());
+        let thread_counters = counters.clone();
+        thread::spawn(move || write_task(rx, thread_counters, recipe));
+
+        let cache = Self {
+            dir,
+            world_dir,
+            recipe,
+            writer: Mutex::new(tx),
+            counters,
+        };
+        cache.prune()?;
+        Ok(cache)
+    }
+
+    /// Get the directory holding the chunks of the current recipe
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Load a cached chunk, if any
+    ///
+    /// Files that are unreadable, corrupted or stale are removed and count as misses.
+    pub fn load(&self, coord: ChunkCoord) -> Option<Chunk> {
+        match self.read(coord) {
+            Ok(Some(chunk)) => {
+                self.counters.hits.fetch_add(1, Ordering::Relaxed);
+                return Some(chunk);
+            }
+            Ok(None) => {}
+            Err(_) => {
+                let _ = fs::remove_file(self.chunk_path(coord));
+                self.counters.rejected.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.counters.misses.fetch_add(1, Ordering::Relaxed);
+        None
+    }
+
+    /// Read and verify a cached chunk, without touching the counters or the file
+    pub fn read(&self, coord: ChunkCoord) -> Result<Option<Chunk>, SpatialError> {
+        let data = match fs::read(self.chunk_path(coord)) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(SpatialError::IoError {
+                    message: e.to_string(),
+                })
+            }
+        };
+        ChunkPacket::from_bytes(&data)?.open(coord, self.recipe).map(Some)
+    }
+
+    /// Queue a chunk to be written in the background
+    pub fn store(&self, chunk: Arc<Chunk>) -> Result<(), SpatialError> {
+        let path = self.chunk_path(chunk.coord);
+        self.writer
+            .lock()
+            .unwrap()
+            .send(CacheWrite::Chunk(path, chunk))
+            .map_err(|_| SpatialError::IoError {
+                message: "disk cache writer stopped".to_string(),
+            })
+    }
+
+    /// Wait until all queued chunks are written
+    pub fn flush(&self) {
+        let (tx, rx) = std_mpsc::channel();
+        if self.writer.lock().unwrap().send(CacheWrite::Flush(tx)).is_ok() {
+            let _ = rx.recv();
+        }
+    }
+
+    /// Check whether a chunk is on disk
+    pub fn contains(&self, coord: ChunkCoord) -> bool {
+        self.chunk_path(coord).exists()
+    }
+
+    /// Remove a chunk from the cache
+    pub fn remove(&self, coord: ChunkCoord) -> Result<(), SpatialError> {
+        self.flush();
+        match fs::remove_file(self.chunk_path(coord)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(SpatialError::IoError {
+                message: e.to_string(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Remove the chunks cached for other seeds or recipes of the world
+    ///
+    /// Returns the number of stale cache directories removed.
+    pub fn prune(&self) -> Result<usize, SpatialError> {
+        let io_error = |e: std::io::Error| SpatialError::IoError {
+            message: e.to_string(),
+        };
+
+        let mut removed = 0;
+        for entry in fs::read_dir(&self.world_dir).map_err(io_error)? {
+            let path = entry.map_err(io_error)?.path();
+            if path.is_dir() && path != self.dir {
+                fs::remove_dir_all(&path).map_err(io_error)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Get the cache counters
+    pub fn stats(&self) -> DiskCacheStats {
+        DiskCacheStats {
+            hits: self.counters.hits.load(Ordering::Relaxed),
+            misses: self.counters.misses.load(Ordering::Relaxed),
+            rejected: self.counters.rejected.load(Ordering::Relaxed),
+            writes: self.counters.writes.load(Ordering::Relaxed),
+            write_errors: self.counters.write_errors.load(Ordering::Relaxed),
+        }
+    }
+
+    fn chunk_path(&self, coord: ChunkCoord) -> PathBuf {
+        self.dir.join(format!("{}_{}.chunk", coord.x, coord.y))
+    }
+}
+
+/// Write queued chunks until the cache is dropped
+///
+/// Chunks go to a temporary file first, so readers never see half-written chunks.
+fn write_task(rx: std_mpsc::Receiver<CacheWrite>, counters: Arc<DiskCacheCounters>, recipe: u64) {
+    let io_error = |e: std::io::Error| SpatialError::IoError {
+        message: e.to_string(),
+    };
+
+    for write in rx {
+        match write {
+            CacheWrite::Chunk(path, chunk) => {
+                let temp = path.with_extension("tmp");
+                let written = ChunkPacket::new(&chunk, recipe)
+                    .and_then(|packet| packet.to_bytes())
+                    .and_then(|data| fs::write(&temp, data).map_err(io_error))
+                    .and_then(|_| fs::rename(&temp, &path).map_err(io_error));
+                match written {
+                    Ok(()) => counters.writes.fetch_add(1, Ordering::Relaxed),
+                    Err(_) => counters.write_errors.fetch_add(1, Ordering::Relaxed),
+                };
+            }
+            CacheWrite::Flush(done) => {
+                let _ = done.send(());
+            }
+        }
+    }
+}
+
+/// Make a world id safe to use as a directory name
+fn sanitize(world_id: &str) -> String {
+    world_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::TerrainGenerator;
 
     fn create_test_manager() -> Arc<ChunkManager> {
         let world = Arc::new(tokio::sync::RwLock::new(World::new(
@@ -216,4 +643,238 @@ mod tests {
 
         assert!(streamer.shutdown().is_ok());
     }
+
+    const RECIPE: u64 = 0x5eed;
+
+    /// Source failing a number of times, then serving flat chunks, and missing chunks past x = 5
+    struct FlakySource {
+        failures: AtomicU64,
+        delay: Duration,
+    }
+
+    impl FlakySource {
+        fn new(failures: u64, delay: Duration) -> Self {
+            Self {
+                failures: AtomicU64::new(failures),
+                delay,
+            }
+        }
+    }
+
+    impl ChunkSource for FlakySource {
+        fn fetch(&self, coord: ChunkCoord, _timeout: Duration) -> Result<Option<ChunkPacket>, SpatialError> {
+            thread::sleep(self.delay);
+            if self.failures.load(Ordering::SeqCst) > 0 {
+                self.failures.fetch_sub(1, Ordering::SeqCst);
+                return Err(SpatialError::IoError {
+                    message: "connection reset".to_string(),
+                });
+            }
+            if coord.x > 5 {
+                return Ok(None);
+            }
+            ChunkPacket::new(&Chunk::new(coord), RECIPE).map(Some)
+        }
+    }
+
+    /// Source serving chunks with a damaged payload a number of times, then intact ones
+    struct CorruptingSource {
+        corruptions: AtomicU64,
+        recipe: u64,
+    }
+
+    impl ChunkSource for CorruptingSource {
+        fn fetch(&self, coord: ChunkCoord, _timeout: Duration) -> Result<Option<ChunkPacket>, SpatialError> {
+            let mut packet = ChunkPacket::new(&Chunk::new(coord), self.recipe)?;
+            if self.corruptions.load(Ordering::SeqCst) > 0 {
+                self.corruptions.fetch_sub(1, Ordering::SeqCst);
+                let last = packet.payload.len() - 1;
+                packet.payload[last] ^= 0xff;
+            }
+            Ok(Some(packet))
+        }
+    }
+
+    fn quick_policy() -> FetchPolicy {
+        FetchPolicy::default()
+            .with_timeout(Duration::from_millis(200))
+            .with_retries(2, Duration::from_millis(1))
+    }
+
+    #[test]
+    fn test_fetcher_retries() {
+        let fetcher = ChunkFetcher::new(Arc::new(FlakySource::new(2, Duration::ZERO)), quick_policy(), RECIPE);
+        let chunk = fetcher.fetch(ChunkCoord::new(1, 2)).unwrap().unwrap();
+        assert_eq!(chunk.coord, ChunkCoord::new(1, 2));
+        assert!(fetcher.fetch(ChunkCoord::new(9, 2)).unwrap().is_none());
+
+        let stats = fetcher.stats();
+        assert_eq!((stats.fetched, stats.missing, stats.retries, stats.failures), (1, 1, 2, 0));
+
+        let fetcher = ChunkFetcher::new(Arc::new(FlakySource::new(3, Duration::ZERO)), quick_policy(), RECIPE);
+        match fetcher.fetch(ChunkCoord::new(1, 2)) {
+            Err(SpatialError::ChunkFetchFailed { attempts, message, .. }) => {
+                assert_eq!(attempts, 3);
+                assert!(message.contains("connection reset"));
+            }
+            other => panic!("expected a failed fetch, got {:?}", other.map(|c| c.map(|c| c.coord))),
+        }
+        assert_eq!(fetcher.stats().failures, 1);
+    }
+
+    #[test]
+    fn test_fetcher_timeout() {
+        let policy = quick_policy()
+            .with_timeout(Duration::from_millis(20))
+            .with_retries(1, Duration::ZERO);
+        let fetcher = ChunkFetcher::new(Arc::new(FlakySource::new(0, Duration::from_millis(300))), policy, RECIPE);
+
+        let started = std::time::Instant::now();
+        assert!(fetcher.fetch(ChunkCoord::new(0, 0)).is_err());
+        assert!(started.elapsed() < Duration::from_millis(250));
+        assert_eq!(fetcher.stats().timeouts, 2);
+    }
+
+    #[test]
+    fn test_fetcher_rejects_corrupted_and_stale_chunks() {
+        let coord = ChunkCoord::new(2, 3);
+        let corrupting = |corruptions, recipe| {
+            Arc::new(CorruptingSource {
+                corruptions: AtomicU64::new(corruptions),
+                recipe,
+            })
+        };
+
+        // A corrupted transfer is retried
+        let fetcher = ChunkFetcher::new(corrupting(1, RECIPE), quick_policy(), RECIPE);
+        assert_eq!(fetcher.fetch(coord).unwrap().unwrap().coord, coord);
+        assert_eq!((fetcher.stats().rejected, fetcher.stats().retries), (1, 1));
+
+        let fetcher = ChunkFetcher::new(corrupting(3, RECIPE), quick_policy(), RECIPE);
+        assert!(matches!(fetcher.fetch(coord), Err(SpatialError::ChunkChecksumMismatch { .. })));
+        assert_eq!((fetcher.stats().rejected, fetcher.stats().failures), (3, 1));
+
+        // Chunks of another terrain recipe are not retried
+        let fetcher = ChunkFetcher::new(corrupting(0, 1), quick_policy(), RECIPE);
+        assert!(matches!(
+            fetcher.fetch(coord),
+            Err(SpatialError::StaleChunk { expected: RECIPE, found: 1, .. })
+        ));
+        assert_eq!((fetcher.stats().rejected, fetcher.stats().retries), (1, 0));
+    }
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("entropic-cache-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        root
+    }
+
+    #[test]
+    fn test_disk_cache_roundtrip() {
+        let root = temp_root("roundtrip");
+        let generator = TerrainGenerator::with_seed(3);
+        let cache = ChunkDiskCache::new(&root, "world/1", &generator).unwrap();
+        let coord = ChunkCoord::new(2, 5);
+
+        assert!(cache.load(coord).is_none());
+        let chunk = Arc::new(generator.generate_chunk(coord).unwrap());
+        cache.store(chunk.clone()).unwrap();
+        cache.flush();
+
+        assert!(cache.contains(coord));
+        assert!(cache.dir().starts_with(root.join("world_1")));
+        let loaded = cache.load(coord).unwrap();
+        assert_eq!(loaded.elevation, chunk.elevation);
+        assert_eq!(
+            cache.stats(),
+            DiskCacheStats {
+                hits: 1,
+                misses: 1,
+                rejected: 0,
+                writes: 1,
+                write_errors: 0
+            }
+        );
+
+        cache.remove(coord).unwrap();
+        assert!(!cache.contains(coord));
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_disk_cache_invalidated_by_recipe() {
+        let root = temp_root("recipe");
+        let coord = ChunkCoord::new(0, 0);
+        let generator = TerrainGenerator::with_seed(3);
+        let cache = ChunkDiskCache::new(&root, "world", &generator).unwrap();
+        cache.store(Arc::new(Chunk::new(coord))).unwrap();
+        cache.flush();
+
+        // Same recipe finds the chunk
+        let reopened = ChunkDiskCache::new(&root, "world", &generator).unwrap();
+        assert!(reopened.contains(coord));
+
+        // Eroded terrain is another recipe, so the old chunks are dropped
+        let eroded = TerrainGenerator::with_seed(3).with_erosion(crate::ErosionConfig::default());
+        let changed = ChunkDiskCache::new(&root, "world", &eroded).unwrap();
+        assert_ne!(changed.dir(), cache.dir());
+        assert!(!changed.contains(coord));
+        assert!(!cache.dir().exists());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_disk_cache_rejects_corrupted_and_stale_files() {
+        let root = temp_root("verify");
+        let generator = TerrainGenerator::with_seed(5);
+        let cache = ChunkDiskCache::new(&root, "world", &generator).unwrap();
+        let coord = ChunkCoord::new(1, 2);
+        cache.store(Arc::new(generator.generate_chunk(coord).unwrap())).unwrap();
+        cache.flush();
+
+        let path = cache.chunk_path(coord);
+        let mut data = fs::read(&path).unwrap();
+        let last = data.len() - 1;
+        data[last] ^= 0xff;
+        fs::write(&path, data).unwrap();
+        assert!(matches!(cache.read(coord), Err(SpatialError::ChunkChecksumMismatch { .. })));
+        assert!(cache.load(coord).is_none());
+        assert!(!cache.contains(coord));
+
+        let packet = ChunkPacket::new(&Chunk::new(coord), generator.recipe() ^ 1).unwrap();
+        fs::write(&path, packet.to_bytes().unwrap()).unwrap();
+        assert!(matches!(cache.read(coord), Err(SpatialError::StaleChunk { .. })));
+        assert!(cache.load(coord).is_none());
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.rejected), (0, 2, 2));
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn test_manager_reloads_from_disk_cache() {
+        let root = temp_root("manager");
+        let generator = TerrainGenerator::with_seed(12345);
+        let cache = Arc::new(ChunkDiskCache::new(&root, "game1", &generator).unwrap());
+        let coord = ChunkCoord::new(1, 1);
+
+        let world = || Arc::new(std::sync::RwLock::new(World::new("Test".to_string(), "game1".to_string(), 4, 4)));
+        let manager = ChunkManager::new(world(), 2).with_disk_cache(cache.clone());
+        manager.preload_chunks(vec![coord]).unwrap();
+        cache.flush();
+        assert!(cache.contains(coord));
+
+        // A modified chunk replaces the cached one
+        let mut modified = Chunk::clone(&manager.get_chunk(coord).unwrap());
+        modified.water_level = 77.0;
+        manager.replace_chunk(modified).unwrap();
+        cache.flush();
+
+        let manager = ChunkManager::new(world(), 2).with_disk_cache(cache.clone());
+        manager.queue_chunk(coord, Priority::High).unwrap();
+        manager.process_load_queue().await.unwrap();
+        assert_eq!(manager.get_chunk(coord).unwrap().water_level, 77.0);
+        assert_eq!(cache.stats().hits, 1);
+        let _ = fs::remove_dir_all(&root);
+    }
 }
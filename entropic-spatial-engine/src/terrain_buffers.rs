@@ -0,0 +1,256 @@
+//! GPU-ready terrain buffers
+//!
+//! [`TerrainBuffers`] turns the [`LODMesh`] of a chunk into one interleaved vertex buffer and
+//! one index buffer, laid out so renderers can upload them as they are. Normals are taken from
+//! the world heightmap rather than the mesh, so they stay continuous across chunk edges.
+
+use crate::constants::*;
+use crate::errors::SpatialError;
+use crate::lod::{sample_height, LODMesh};
+use crate::{ChunkCoord, World};
+
+/// Width of the indices in an index buffer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IndexFormat {
+    U16,
+    U32,
+}
+
+impl IndexFormat {
+    /// Get the smallest format able to address a number of vertices
+    pub fn for_vertices(count: usize) -> Self {
+        if count <= u16::MAX as usize + 1 {
+            IndexFormat::U16
+        } else {
+            IndexFormat::U32
+        }
+    }
+
+    /// Get the size of one index in bytes
+    pub fn size(self) -> usize {
+        match self {
+            IndexFormat::U16 => 2,
+            IndexFormat::U32 => 4,
+        }
+    }
+}
+
+/// Triangle indices in either index format
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IndexBuffer {
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+}
+
+impl IndexBuffer {
+    /// Get the index format
+    pub fn format(&self) -> IndexFormat {
+        match self {
+            IndexBuffer::U16(_) => IndexFormat::U16,
+            IndexBuffer::U32(_) => IndexFormat::U32,
+        }
+    }
+
+    /// Get the number of indices
+    pub fn len(&self) -> usize {
+        match self {
+            IndexBuffer::U16(indices) => indices.len(),
+            IndexBuffer::U32(indices) => indices.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get an index widened to `u32`
+    pub fn get(&self, index: usize) -> Option<u32> {
+        match self {
+            IndexBuffer::U16(indices) => indices.get(index).map(|&i| i as u32),
+            IndexBuffer::U32(indices) => indices.get(index).copied(),
+        }
+    }
+
+    /// Get the indices as little-endian bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            IndexBuffer::U16(indices) => indices.iter().flat_map(|i| i.to_le_bytes()).collect(),
+            IndexBuffer::U32(indices) => indices.iter().flat_map(|i| i.to_le_bytes()).collect(),
+        }
+    }
+}
+
+/// Interleaved vertex and index buffers of a chunk at one LOD level
+///
+/// Every vertex is [`TerrainBuffers::VERTEX_FLOATS`] floats: the world position as
+/// (x, y, height), the unit normal, and the UV across the chunk, from (0, 0) at the chunk
+/// origin to (1, 1) at the opposite corner. Triangles are counter-clockwise seen from above,
+/// the surface first and the skirts, if any, after it.
+#[derive(Clone, Debug)]
+pub struct TerrainBuffers {
+    /// Chunk the buffers belong to
+    pub coord: ChunkCoord,
+    /// Cells per side of the mesh
+    pub resolution: usize,
+    /// Interleaved vertex data
+    pub vertices: Vec<f32>,
+    /// Triangle indices into the vertices
+    pub indices: IndexBuffer,
+    /// Number of indices belonging to the surface, the rest being skirts
+    pub surface_indices: usize,
+}
+
+impl TerrainBuffers {
+    /// Floats per vertex
+    pub const VERTEX_FLOATS: usize = 8;
+    /// Bytes per vertex
+    pub const VERTEX_STRIDE: usize = Self::VERTEX_FLOATS * 4;
+    /// Byte offset of the position in a vertex
+    pub const POSITION_OFFSET: usize = 0;
+    /// Byte offset of the normal in a vertex
+    pub const NORMAL_OFFSET: usize = 12;
+    /// Byte offset of the UV in a vertex
+    pub const UV_OFFSET: usize = 24;
+
+    /// Build the buffers of a chunk mesh
+    ///
+    /// Fails if the mesh has more vertices than `format` can address.
+    pub fn from_mesh(world: &World, coord: ChunkCoord, mesh: &LODMesh, format: IndexFormat) -> Result<Self, SpatialError> {
+        let max = match format {
+            IndexFormat::U16 => u16::MAX as usize + 1,
+            IndexFormat::U32 => u32::MAX as usize,
+        };
+        if mesh.vertices.len() > max {
+            return Err(SpatialError::TooManyVertices {
+                vertices: mesh.vertices.len(),
+                max,
+            });
+        }
+
+        let origin = (coord.x as f32 * CHUNK_SIZE, coord.y as f32 * CHUNK_SIZE);
+        let step = CHUNK_SIZE / mesh.resolution.max(1) as f32;
+        let mut vertices = Vec::with_capacity(mesh.vertices.len() * Self::VERTEX_FLOATS);
+        for &[x, y, z] in &mesh.vertices {
+            let dx = sample_height(world, coord, x + step, y) - sample_height(world, coord, x - step, y);
+            let dy = sample_height(world, coord, x, y + step) - sample_height(world, coord, x, y - step);
+            let (nx, ny, nz) = (-dx, -dy, 2.0 * step);
+            let length = (nx * nx + ny * ny + nz * nz).sqrt();
+
+            vertices.extend_from_slice(&[x, y, z, nx / length, ny / length, nz / length]);
+            vertices.extend_from_slice(&[(x - origin.0) / CHUNK_SIZE, (y - origin.1) / CHUNK_SIZE]);
+        }
+
+        let all = mesh.indices.iter().chain(&mesh.skirt_indices);
+        let indices = match format {
+            IndexFormat::U16 => IndexBuffer::U16(all.map(|&i| i as u16).collect()),
+            IndexFormat::U32 => IndexBuffer::U32(all.copied().collect()),
+        };
+
+        Ok(Self {
+            coord,
+            resolution: mesh.resolution,
+            vertices,
+            indices,
+            surface_indices: mesh.indices.len(),
+        })
+    }
+
+    /// Get the number of vertices
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len() / Self::VERTEX_FLOATS
+    }
+
+    /// Get the floats of one vertex
+    pub fn vertex(&self, index: usize) -> &[f32] {
+        &self.vertices[index * Self::VERTEX_FLOATS..(index + 1) * Self::VERTEX_FLOATS]
+    }
+
+    /// Get the vertices as little-endian bytes
+    pub fn vertex_bytes(&self) -> Vec<u8> {
+        self.vertices.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    /// Get the indices as little-endian bytes
+    pub fn index_bytes(&self) -> Vec<u8> {
+        self.indices.to_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lod::{LODManager, LODNeighbors};
+
+    fn create_test_world() -> World {
+        let mut world = World::new("Test".to_string(), "game1".to_string(), 2, 2);
+        world.initialize_chunks();
+        for chunk in world.chunks.values_mut() {
+            let origin = (chunk.coord.x as f32 * CHUNK_SIZE, chunk.coord.y as f32 * CHUNK_SIZE);
+            for sy in 0..HEIGHTMAP_RESOLUTION {
+                for sx in 0..HEIGHTMAP_RESOLUTION {
+                    let x = origin.0 + sx as f32;
+                    chunk.elevation[sy * HEIGHTMAP_RESOLUTION + sx] = 50.0 + x * 0.5;
+                }
+            }
+        }
+        world
+    }
+
+    #[test]
+    fn test_buffer_layout() {
+        let world = create_test_world();
+        let manager = LODManager::new().with_skirts(5.0);
+        let coord = ChunkCoord::new(1, 0);
+        let buffers = manager
+            .build_buffers(&world, coord, 3, LODNeighbors::uniform(32), IndexFormat::U16)
+            .unwrap();
+
+        assert_eq!(buffers.resolution, 32);
+        assert_eq!(buffers.vertex_count(), 33 * 33 + 4 * 33);
+        assert_eq!(buffers.indices.format(), IndexFormat::U16);
+        assert_eq!(buffers.surface_indices, 32 * 32 * 6);
+        assert_eq!(buffers.indices.len(), 32 * 32 * 6 + 4 * 32 * 6);
+        assert_eq!(buffers.vertex_bytes().len(), buffers.vertex_count() * TerrainBuffers::VERTEX_STRIDE);
+        assert_eq!(buffers.index_bytes().len(), buffers.indices.len() * 2);
+
+        // First vertex sits at the chunk origin, last grid vertex at the far corner
+        let first = buffers.vertex(0);
+        assert_eq!(&first[0..2], &[CHUNK_SIZE, 0.0]);
+        assert_eq!(&first[6..8], &[0.0, 0.0]);
+        assert_eq!(&buffers.vertex(33 * 33 - 1)[6..8], &[1.0, 1.0]);
+
+        // Height rises along x by 0.5 per meter, so normals lean towards -x
+        let normal = &buffers.vertex(16 * 33 + 16)[3..6];
+        let expected = [-0.5, 0.0, 1.0].map(|n: f32| n / 1.25f32.sqrt());
+        for (n, e) in normal.iter().zip(expected) {
+            assert!((n - e).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_index_format() {
+        let world = create_test_world();
+        let manager = LODManager::new();
+        let coord = ChunkCoord::new(0, 0);
+
+        assert_eq!(IndexFormat::for_vertices(65_536), IndexFormat::U16);
+        assert_eq!(IndexFormat::for_vertices(65_537), IndexFormat::U32);
+
+        // The full resolution level has 257 * 257 vertices, too many for 16-bit indices
+        assert!(matches!(
+            manager.build_buffers(&world, coord, 0, LODNeighbors::uniform(256), IndexFormat::U16),
+            Err(SpatialError::TooManyVertices { vertices: 66_049, .. })
+        ));
+        let buffers = manager
+            .build_buffers(&world, coord, 0, LODNeighbors::uniform(256), IndexFormat::U32)
+            .unwrap();
+        assert_eq!(buffers.indices.format(), IndexFormat::U32);
+        let vertices = buffers.vertex_count() as u32;
+        assert!((0..buffers.indices.len()).all(|i| buffers.indices.get(i).unwrap() < vertices));
+
+        assert!(matches!(
+            manager.build_buffers(&world, ChunkCoord::new(5, 5), 1, LODNeighbors::uniform(128), IndexFormat::U32),
+            Err(SpatialError::ChunkNotFound { .. })
+        ));
+    }
+}
@@ -1,6 +1,14 @@
+use crate::climate::ClimateModel;
 use crate::constants::*;
+use crate::determinism::{self, Determinism, FixedNoise};
+use crate::erosion::{self, ErosionConfig};
 use crate::errors::SpatialError;
+use crate::heightmap::ImportedTerrain;
 use crate::noise::PerlinNoise;
+use crate::noise_graph::{CompiledNoise, NoiseGraph};
+use ::noise::NoiseFn;
+use crate::splat::SplatRules;
+use crate::terrain_source::{biome_water_level, height_fingerprint, TerrainSource};
 use crate::{Biome, Chunk, ChunkCoord};
 use entropic_world_core::constants::HEIGHTMAP_RESOLUTION;
 use std::sync::Arc;
@@ -9,6 +17,12 @@ use std::sync::Arc;
 pub struct TerrainGenerator {
     perlin: Arc<PerlinNoise>,
     seed: u32,
+    erosion: Option<ErosionConfig>,
+    climate: ClimateModel,
+    imports: Vec<ImportedTerrain>,
+    graph: Option<(NoiseGraph, Arc<CompiledNoise>)>,
+    determinism: Determinism,
+    fixed_noise: Arc<FixedNoise>,
 }
 
 impl TerrainGenerator {
@@ -22,9 +36,76 @@ impl TerrainGenerator {
         Self {
             perlin: Arc::new(PerlinNoise::with_seed(seed)),
             seed,
+            erosion: None,
+            climate: ClimateModel::with_seed(seed),
+            imports: Vec::new(),
+            graph: None,
+            determinism: Determinism::Native,
+            fixed_noise: Arc::new(FixedNoise::with_seed(seed)),
         }
     }
 
+    /// Use a custom climate model for biome assignment
+    pub fn with_climate(mut self, climate: ClimateModel) -> Self {
+        self.climate = climate;
+        self
+    }
+
+    /// Get the climate model used for biome assignment
+    pub fn climate(&self) -> &ClimateModel {
+        &self.climate
+    }
+
+    /// Apply hydraulic and thermal erosion to generated heightmaps
+    pub fn with_erosion(mut self, config: ErosionConfig) -> Self {
+        self.erosion = Some(config);
+        self
+    }
+
+    /// Get the erosion applied to generated heightmaps, if any
+    pub fn erosion(&self) -> Option<&ErosionConfig> {
+        self.erosion.as_ref()
+    }
+
+    /// Replace the built-in height noise with a noise graph sampled in world meters
+    ///
+    /// The graph output is expected in [-1, 1] and mapped onto heights of 0-255.
+    pub fn with_noise_graph(mut self, graph: NoiseGraph) -> Self {
+        let noise = Arc::new(graph.build());
+        self.graph = Some((graph, noise));
+        self
+    }
+
+    /// Get the noise graph used for heights, if any
+    pub fn noise_graph(&self) -> Option<&NoiseGraph> {
+        self.graph.as_ref().map(|(graph, _)| graph)
+    }
+
+    /// Choose how heights are computed
+    ///
+    /// Fixed-point heights are identical on every platform. Noise graphs are always sampled
+    /// in floating point.
+    pub fn with_determinism(mut self, determinism: Determinism) -> Self {
+        self.determinism = determinism;
+        self
+    }
+
+    /// Get how heights are computed
+    pub fn determinism(&self) -> Determinism {
+        self.determinism
+    }
+
+    /// Use an imported heightmap as the terrain of a region, blended into its surroundings
+    pub fn with_import(mut self, import: ImportedTerrain) -> Self {
+        self.imports.push(import);
+        self
+    }
+
+    /// Get the imported terrain regions
+    pub fn imports(&self) -> &[ImportedTerrain] {
+        &self.imports
+    }
+
     /// Get the seed used for generation
     pub fn seed(&self) -> u32 {
         self.seed
@@ -36,21 +117,20 @@ impl TerrainGenerator {
 
         // Generate heightmap
         let heightmap = self.generate_heightmap(coord)?;
-        chunk.elevation = heightmap;
 
         // Generate biome
-        chunk.biome = self.determine_biome(coord)?;
+        chunk.biome = self.determine_biome(coord, &heightmap)?;
+        chunk.elevation = heightmap;
 
         // Generate vegetation
         let vegetation = self.generate_vegetation(coord)?;
         chunk.vegetation = vegetation;
 
         // Set water level based on biome
-        chunk.water_level = match chunk.biome {
-            Biome::Ocean => 128.0,
-            Biome::Swamp => 100.0,
-            _ => 50.0,
-        };
+        chunk.water_level = biome_water_level(chunk.biome);
+
+        // Paint materials from slope, altitude and biome
+        SplatRules::default().paint(&mut chunk);
 
         // Mark as loaded
         chunk.load();
@@ -72,50 +152,62 @@ impl TerrainGenerator {
                 let world_y = chunk_y
                     + (j as f64 * CHUNK_SIZE as f64 / HEIGHTMAP_RESOLUTION as f64);
 
-                // Multi-octave Perlin noise for natural terrain
-                let height = self.perlin.fbm(
-                    world_x * TERRAIN_NOISE_FREQUENCY,
-                    world_y * TERRAIN_NOISE_FREQUENCY,
-                    TERRAIN_NOISE_OCTAVES,
-                    0.5,
-                    2.0,
-                );
+                heights[i * HEIGHTMAP_RESOLUTION + j] = self.noise_height(world_x, world_y);
+            }
+        }
+
+        if let Some(config) = &self.erosion {
+            erosion::erode(&mut heights, HEIGHTMAP_RESOLUTION, self.chunk_seed(coord), config);
+            heights.iter_mut().for_each(|h| *h = h.clamp(0.0, 255.0));
+        }
 
-                // Normalize from [-1, 1] to [0, 255]
-                let normalized = (height + 1.0) / 2.0;
-                heights[i * HEIGHTMAP_RESOLUTION + j] = normalized * 255.0;
+        for import in self.imports.iter().filter(|import| import.overlaps(coord)) {
+            for i in 0..HEIGHTMAP_RESOLUTION {
+                for j in 0..HEIGHTMAP_RESOLUTION {
+                    let world_x = (chunk_x + i as f64 * CHUNK_SIZE as f64 / HEIGHTMAP_RESOLUTION as f64) as f32;
+                    let world_y = (chunk_y + j as f64 * CHUNK_SIZE as f64 / HEIGHTMAP_RESOLUTION as f64) as f32;
+                    let height = &mut heights[i * HEIGHTMAP_RESOLUTION + j];
+                    *height = import.blend(world_x, world_y, *height);
+                }
             }
         }
 
         Ok(heights)
     }
 
-    /// Determine biome for a chunk based on temperature and moisture
-    fn determine_biome(&self, coord: ChunkCoord) -> Result<Biome, SpatialError> {
-        let chunk_x = coord.x as f64;
-        let chunk_y = coord.y as f64;
+    /// Sample the procedural height at a world position, before erosion and imports
+    fn noise_height(&self, world_x: f64, world_y: f64) -> f32 {
+        if self.graph.is_none() && self.determinism == Determinism::FixedPoint {
+            let noise = determinism::fixed_terrain_noise(&self.fixed_noise, world_x, world_y);
+            return determinism::fixed_height(noise);
+        }
 
-        let temp = self.perlin.get(
-            chunk_x * BIOME_NOISE_FREQUENCY,
-            chunk_y * BIOME_NOISE_FREQUENCY,
-        );
+        let height = match &self.graph {
+            Some((_, noise)) => noise.get([world_x, world_y]).clamp(-1.0, 1.0),
+            // Multi-octave Perlin noise for natural terrain
+            None => self.perlin.fbm(
+                world_x * TERRAIN_NOISE_FREQUENCY,
+                world_y * TERRAIN_NOISE_FREQUENCY,
+                TERRAIN_NOISE_OCTAVES,
+                0.5,
+                2.0,
+            ),
+        };
 
-        let moisture = self.perlin.get(
-            chunk_x * BIOME_NOISE_FREQUENCY + 1000.0,
-            chunk_y * BIOME_NOISE_FREQUENCY + 1000.0,
-        );
+        // Normalize from [-1, 1] to [0, 255]
+        let normalized = (height + 1.0) / 2.0;
+        (normalized * 255.0) as f32
+    }
 
-        let biome = match (temp, moisture) {
-            (t, _) if t < -0.3 => Biome::Tundra,
-            (t, m) if t < 0.0 && m > 0.5 => Biome::Swamp,
-            (t, m) if t < 0.0 => Biome::Forest,
-            (_, m) if m > 0.6 => Biome::Swamp,
-            (_, m) if m > 0.4 => Biome::Grassland,
-            (_, m) if m > 0.2 => Biome::Plains,
-            _ => Biome::Desert,
-        };
+    /// Derive a per-chunk seed from the generator seed
+    fn chunk_seed(&self, coord: ChunkCoord) -> u64 {
+        (((self.seed as u64) << 32) ^ ((coord.x as u64) << 16) ^ coord.y as u64)
+            .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+    }
 
-        Ok(biome)
+    /// Determine biome for a chunk based on temperature and moisture
+    fn determine_biome(&self, coord: ChunkCoord, heightmap: &[f32]) -> Result<Biome, SpatialError> {
+        Ok(self.climate.chunk_biome(coord, heightmap))
     }
 
     /// Generate vegetation density map for a chunk
@@ -139,6 +231,48 @@ impl TerrainGenerator {
     }
 }
 
+impl TerrainSource for TerrainGenerator {
+    /// Procedural height including imported regions, but not erosion, which works per chunk
+    fn height(&self, x: f32, y: f32) -> f32 {
+        let height = self.noise_height(x as f64, y as f64);
+        self.imports
+            .iter()
+            .fold(height, |height, import| import.blend(x, y, height))
+    }
+
+    fn biome(&self, coord: ChunkCoord, heightmap: &[f32]) -> Biome {
+        self.climate.chunk_biome(coord, heightmap)
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    /// Probed heights plus the erosion, climate and imported heightmaps, which probes can miss
+    fn recipe(&self) -> u64 {
+        let mut hash = height_fingerprint(self);
+        hash = determinism::fnv1a(hash, format!("{:?}", self.erosion).as_bytes());
+        for (x, y) in [(0.0, 0.0), (3000.0, 9000.0), (20_000.0, 40_000.0)] {
+            let climate = self.climate.climate_at(x, y, 100.0);
+            hash = determinism::fnv1a(hash, &climate.temperature.to_bits().to_le_bytes());
+            hash = determinism::fnv1a(hash, &climate.moisture.to_bits().to_le_bytes());
+        }
+        for import in &self.imports {
+            let ((x0, y0), (x1, y1)) = import.bounds();
+            for value in [x0, y0, x1, y1, import.blend_width()] {
+                hash = determinism::fnv1a(hash, &value.to_bits().to_le_bytes());
+            }
+            let heights = determinism::heightmap_checksum(import.heightmap().heights());
+            hash = determinism::fnv1a(hash, &heights.to_le_bytes());
+        }
+        hash
+    }
+
+    fn generate_chunk(&self, coord: ChunkCoord) -> Result<Chunk, SpatialError> {
+        TerrainGenerator::generate_chunk(self, coord)
+    }
+}
+
 impl Default for TerrainGenerator {
     fn default() -> Self {
         Self::new()
@@ -212,6 +346,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_erosion_is_opt_in_and_deterministic() {
+        let coord = ChunkCoord::new(3, 2);
+        let plain = TerrainGenerator::with_seed(42).generate_chunk(coord).unwrap();
+        let config = ErosionConfig::default().with_droplets(5000);
+        let generator = TerrainGenerator::with_seed(42).with_erosion(config.clone());
+        let eroded1 = generator.generate_chunk(coord).unwrap();
+        let eroded2 = TerrainGenerator::with_seed(42)
+            .with_erosion(config)
+            .generate_chunk(coord)
+            .unwrap();
+
+        assert!(TerrainGenerator::new().erosion().is_none());
+        assert!(generator.erosion().is_some());
+        assert_eq!(eroded1.elevation, eroded2.elevation);
+        assert_ne!(eroded1.elevation, plain.elevation);
+        assert!(eroded1.elevation.iter().all(|h| (0.0..=255.0).contains(h)));
+    }
+
+    #[test]
+    fn test_biomes_follow_climate() {
+        let generator = TerrainGenerator::with_seed(9)
+            .with_climate(ClimateModel::with_seed(9).with_latitude_span(8.0 * CHUNK_SIZE));
+
+        let chunk = generator.generate_chunk(ChunkCoord::new(0, 8)).unwrap();
+        let expected = generator.climate().chunk_biome(chunk.coord, &chunk.elevation);
+        assert_eq!(chunk.biome, expected);
+        assert!(matches!(chunk.biome, Biome::Tundra | Biome::Ocean | Biome::Mountains));
+    }
+
+    #[test]
+    fn test_recipe_tracks_settings() {
+        let recipe = TerrainGenerator::with_seed(5).recipe();
+
+        assert_eq!(TerrainGenerator::with_seed(5).recipe(), recipe);
+        assert_ne!(TerrainGenerator::with_seed(6).recipe(), recipe);
+        assert_ne!(
+            TerrainGenerator::with_seed(5).with_erosion(ErosionConfig::default()).recipe(),
+            recipe
+        );
+        assert_ne!(
+            TerrainGenerator::with_seed(5).with_climate(ClimateModel::with_seed(6)).recipe(),
+            recipe
+        );
+    }
+
     #[test]
     fn test_vegetation_range() {
         let generator = TerrainGenerator::new();
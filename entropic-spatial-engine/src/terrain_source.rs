@@ -0,0 +1,271 @@
+//! Pluggable terrain sources
+//!
+//! The chunk manager generates missing chunks through a [`TerrainSource`], so games can swap
+//! in their own generators or mix authored chunks into procedural terrain without touching
+//! the engine. [`TerrainGenerator`](crate::TerrainGenerator) is the default source.
+
+use crate::constants::*;
+use crate::determinism::{self, fnv1a};
+use crate::errors::SpatialError;
+use crate::splat::SplatRules;
+use crate::{Biome, Chunk, ChunkCoord};
+use entropic_world_core::spatial::Structure;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Source of terrain for chunks that are not in the world yet
+pub trait TerrainSource: Send + Sync {
+    /// Get the terrain height at a world position (in meters)
+    fn height(&self, x: f32, y: f32) -> f32;
+
+    /// Pick the biome of a chunk from its heightmap, indexed like [`Chunk::elevation`]
+    fn biome(&self, coord: ChunkCoord, heightmap: &[f32]) -> Biome;
+
+    /// Get the structures standing in a chunk
+    fn features(&self, _coord: ChunkCoord, _heightmap: &[f32]) -> Vec<Structure> {
+        Vec::new()
+    }
+
+    /// Get the seed identifying the source, zero for sources without one
+    fn seed(&self) -> u32 {
+        0
+    }
+
+    /// Fingerprint of everything shaping the terrain, used to invalidate cached chunks
+    ///
+    /// The default hashes the seed with heights probed over the first chunks, so sources with
+    /// settings that do not show in [`height`](Self::height) should mix those in as well.
+    fn recipe(&self) -> u64 {
+        height_fingerprint(self)
+    }
+
+    /// Build a complete chunk
+    ///
+    /// The default samples [`height`](Self::height) over the chunk grid, then asks for the
+    /// biome and features, sets the water level from the biome and paints the materials.
+    fn generate_chunk(&self, coord: ChunkCoord) -> Result<Chunk, SpatialError> {
+        let mut chunk = Chunk::new(coord);
+        let step = CHUNK_SIZE / HEIGHTMAP_RESOLUTION as f32;
+        let origin = (coord.x as f32 * CHUNK_SIZE, coord.y as f32 * CHUNK_SIZE);
+
+        for y in 0..HEIGHTMAP_RESOLUTION {
+            for x in 0..HEIGHTMAP_RESOLUTION {
+                let height = self.height(origin.0 + x as f32 * step, origin.1 + y as f32 * step);
+                chunk.set_elevation_at(x, y, height);
+            }
+        }
+
+        chunk.biome = self.biome(coord, &chunk.elevation);
+        chunk.water_level = biome_water_level(chunk.biome);
+        SplatRules::default().paint(&mut chunk);
+        for structure in self.features(coord, &chunk.elevation) {
+            chunk.add_structure(structure);
+        }
+        chunk.load();
+
+        Ok(chunk)
+    }
+}
+
+/// Hash the seed of a source with heights probed over the first chunks
+pub(crate) fn height_fingerprint<S: TerrainSource + ?Sized>(source: &S) -> u64 {
+    let step = 4.0 * CHUNK_SIZE / TERRAIN_RECIPE_PROBES as f32;
+    let probes: Vec<f32> = (0..TERRAIN_RECIPE_PROBES * TERRAIN_RECIPE_PROBES)
+        .map(|i| {
+            // Off the grid lines, where chunks and imported regions tend to line up
+            let x = ((i % TERRAIN_RECIPE_PROBES) as f32 + 0.37) * step;
+            let y = ((i / TERRAIN_RECIPE_PROBES) as f32 + 0.61) * step;
+            source.height(x, y)
+        })
+        .collect();
+
+    fnv1a(determinism::heightmap_checksum(&probes), &source.seed().to_le_bytes())
+}
+
+/// Default water level of a biome
+pub fn biome_water_level(biome: Biome) -> f32 {
+    match biome {
+        Biome::Ocean => 128.0,
+        Biome::Swamp => 100.0,
+        _ => 50.0,
+    }
+}
+
+/// Authored chunks layered over another source
+///
+/// Authored chunks are returned as they are, only painted with materials if they have none, and
+/// everything else comes from the base source.
+#[derive(Clone)]
+pub struct HybridSource {
+    base: Arc<dyn TerrainSource>,
+    authored: HashMap<ChunkCoord, Arc<Chunk>>,
+}
+
+impl HybridSource {
+    /// Create a hybrid source without authored chunks
+    pub fn new(base: Arc<dyn TerrainSource>) -> Self {
+        Self {
+            base,
+            authored: HashMap::new(),
+        }
+    }
+
+    /// Add an authored chunk, replacing the base terrain at its coordinate
+    pub fn with_chunk(mut self, chunk: Chunk) -> Self {
+        self.authored.insert(chunk.coord, Arc::new(chunk));
+        self
+    }
+
+    /// Get the source used outside the authored chunks
+    pub fn base(&self) -> &dyn TerrainSource {
+        self.base.as_ref()
+    }
+
+    /// Check whether a chunk is authored
+    pub fn is_authored(&self, coord: ChunkCoord) -> bool {
+        self.authored.contains_key(&coord)
+    }
+}
+
+impl TerrainSource for HybridSource {
+    fn height(&self, x: f32, y: f32) -> f32 {
+        if x >= 0.0 && y >= 0.0 {
+            let coord = ChunkCoord::new((x / CHUNK_SIZE) as u32, (y / CHUNK_SIZE) as u32);
+            if let Some(chunk) = self.authored.get(&coord) {
+                let scale = HEIGHTMAP_RESOLUTION as f32 / CHUNK_SIZE;
+                let sx = (((x - coord.x as f32 * CHUNK_SIZE) * scale) as usize).min(HEIGHTMAP_RESOLUTION - 1);
+                let sy = (((y - coord.y as f32 * CHUNK_SIZE) * scale) as usize).min(HEIGHTMAP_RESOLUTION - 1);
+                if let Some(height) = chunk.get_elevation_at(sx, sy) {
+                    return height;
+                }
+            }
+        }
+        self.base.height(x, y)
+    }
+
+    fn biome(&self, coord: ChunkCoord, heightmap: &[f32]) -> Biome {
+        match self.authored.get(&coord) {
+            Some(chunk) => chunk.biome,
+            None => self.base.biome(coord, heightmap),
+        }
+    }
+
+    fn features(&self, coord: ChunkCoord, heightmap: &[f32]) -> Vec<Structure> {
+        match self.authored.get(&coord) {
+            Some(chunk) => chunk.structures.clone(),
+            None => self.base.features(coord, heightmap),
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.base.seed()
+    }
+
+    fn recipe(&self) -> u64 {
+        let mut coords: Vec<&ChunkCoord> = self.authored.keys().collect();
+        coords.sort_by_key(|c| (c.y, c.x));
+        coords.into_iter().fold(self.base.recipe(), |hash, coord| {
+            let chunk = &self.authored[coord];
+            let hash = fnv1a(hash, &coord.x.to_le_bytes());
+            let hash = fnv1a(hash, &coord.y.to_le_bytes());
+            fnv1a(hash, &determinism::heightmap_checksum(&chunk.elevation).to_le_bytes())
+        })
+    }
+
+    fn generate_chunk(&self, coord: ChunkCoord) -> Result<Chunk, SpatialError> {
+        match self.authored.get(&coord) {
+            Some(chunk) => {
+                let mut chunk = Chunk::clone(chunk);
+                if chunk.materials.is_empty() {
+                    SplatRules::default().paint(&mut chunk);
+                }
+                chunk.load();
+                Ok(chunk)
+            }
+            None => self.base.generate_chunk(coord),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TerrainGenerator;
+    use entropic_world_core::spatial::StructureType;
+
+    /// Terraces stepping up every chunk along x, with a shrine in every chunk
+    struct Terraces;
+
+    impl TerrainSource for Terraces {
+        fn height(&self, x: f32, _y: f32) -> f32 {
+            60.0 + (x / CHUNK_SIZE).floor() * 20.0
+        }
+
+        fn biome(&self, _coord: ChunkCoord, heightmap: &[f32]) -> Biome {
+            if heightmap[0] > 100.0 {
+                Biome::Mountains
+            } else {
+                Biome::Plains
+            }
+        }
+
+        fn features(&self, coord: ChunkCoord, _heightmap: &[f32]) -> Vec<Structure> {
+            let x = coord.x as f32 * CHUNK_SIZE + 10.0;
+            let y = coord.y as f32 * CHUNK_SIZE + 10.0;
+            vec![Structure::new(format!("shrine-{}-{}", coord.x, coord.y), StructureType::Temple, x, y, 0.0)]
+        }
+    }
+
+    #[test]
+    fn test_default_chunk_generation() {
+        let chunk = Terraces.generate_chunk(ChunkCoord::new(3, 1)).unwrap();
+
+        assert!(chunk.loaded);
+        assert_eq!(chunk.get_elevation_at(5, 200), Some(120.0));
+        assert_eq!(chunk.biome, Biome::Mountains);
+        assert_eq!(chunk.water_level, 50.0);
+        assert_eq!(chunk.structures.len(), 1);
+        assert_eq!(Terraces.generate_chunk(ChunkCoord::new(0, 0)).unwrap().biome, Biome::Plains);
+    }
+
+    #[test]
+    fn test_hybrid_source() {
+        let mut authored = Chunk::new(ChunkCoord::new(1, 0));
+        authored.elevation.iter_mut().for_each(|h| *h = 7.0);
+        authored.biome = Biome::Swamp;
+
+        let source = HybridSource::new(Arc::new(Terraces)).with_chunk(authored);
+        assert!(source.is_authored(ChunkCoord::new(1, 0)));
+        assert_eq!(source.height(CHUNK_SIZE + 5.0, 5.0), 7.0);
+        assert_eq!(source.height(2.0 * CHUNK_SIZE + 5.0, 5.0), 100.0);
+
+        let chunk = source.generate_chunk(ChunkCoord::new(1, 0)).unwrap();
+        assert!(chunk.loaded);
+        assert_eq!(chunk.biome, Biome::Swamp);
+        assert!(chunk.structures.is_empty());
+
+        let chunk = source.generate_chunk(ChunkCoord::new(2, 0)).unwrap();
+        assert_eq!(chunk.structures.len(), 1);
+    }
+
+    #[test]
+    fn test_recipe() {
+        let base: Arc<dyn TerrainSource> = Arc::new(TerrainGenerator::with_seed(1));
+        let hybrid = HybridSource::new(base.clone());
+        assert_eq!(hybrid.recipe(), base.recipe());
+        assert_ne!(hybrid.with_chunk(Chunk::new(ChunkCoord::new(2, 2))).recipe(), base.recipe());
+        assert_ne!(TerrainGenerator::with_seed(2).recipe(), base.recipe());
+        assert_eq!(Terraces.recipe(), Terraces.recipe());
+    }
+
+    #[test]
+    fn test_generator_as_source() {
+        let generator = TerrainGenerator::with_seed(9);
+        let source: &dyn TerrainSource = &generator;
+        let chunk = source.generate_chunk(ChunkCoord::new(2, 2)).unwrap();
+
+        assert_eq!(source.seed(), 9);
+        assert_eq!(chunk.elevation, generator.generate_chunk(ChunkCoord::new(2, 2)).unwrap().elevation);
+        assert_eq!(source.height(2.0 * CHUNK_SIZE, 2.0 * CHUNK_SIZE), chunk.elevation[0]);
+    }
+}
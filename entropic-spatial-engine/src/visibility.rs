@@ -0,0 +1,420 @@
+//! Fog of war
+//!
+//! Every faction gets a grid over the world recording the cells it has explored and the cells
+//! it sees right now. Each tick [`FogOfWar::update`] clears what is visible and reveals the
+//! circles seen by the faction's NPCs, so explored cells only ever grow. The explored cells
+//! serialize with the rest of a save; visible cells are rebuilt by the next update.
+
+use crate::constants::*;
+use crate::errors::SpatialError;
+use crate::{ChunkCoord, World};
+use entropic_world_core::population::{EntityId, FactionId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// What a faction knows about a place
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Visibility {
+    /// Never seen
+    Unexplored,
+    /// Seen before, but not right now
+    Explored,
+    /// In sight right now
+    Visible,
+}
+
+/// Circle seen by a faction during a tick
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sighting {
+    pub faction: FactionId,
+    pub x: f32,
+    pub y: f32,
+    /// Sight range (in meters)
+    pub range: f32,
+}
+
+impl Sighting {
+    pub fn new(faction: impl Into<FactionId>, x: f32, y: f32, range: f32) -> Self {
+        Self {
+            faction: faction.into(),
+            x,
+            y,
+            range,
+        }
+    }
+}
+
+/// Explored and visible cells of one faction, one bit per cell
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VisibilityGrid {
+    explored: Vec<u64>,
+    #[serde(skip)]
+    visible: Vec<u64>,
+}
+
+impl VisibilityGrid {
+    fn new(cells: usize) -> Self {
+        Self {
+            explored: vec![0; (cells + 63) / 64],
+            visible: vec![0; (cells + 63) / 64],
+        }
+    }
+
+    fn mark(&mut self, cell: usize) {
+        let (word, bit) = (cell / 64, 1 << (cell % 64));
+        self.explored[word] |= bit;
+        if self.visible.len() != self.explored.len() {
+            self.visible = vec![0; self.explored.len()];
+        }
+        self.visible[word] |= bit;
+    }
+
+    fn clear_visible(&mut self) {
+        self.visible.clear();
+        self.visible.resize(self.explored.len(), 0);
+    }
+
+    /// Check if a cell is explored
+    pub fn is_explored(&self, cell: usize) -> bool {
+        bit(&self.explored, cell)
+    }
+
+    /// Check if a cell is visible
+    pub fn is_visible(&self, cell: usize) -> bool {
+        bit(&self.visible, cell)
+    }
+
+    /// Count the explored cells
+    pub fn explored_cells(&self) -> usize {
+        self.explored.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Count the visible cells
+    pub fn visible_cells(&self) -> usize {
+        self.visible.iter().map(|word| word.count_ones() as usize).sum()
+    }
+}
+
+fn bit(words: &[u64], cell: usize) -> bool {
+    words.get(cell / 64).is_some_and(|word| word & (1 << (cell % 64)) != 0)
+}
+
+/// Per-faction fog of war over the world
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FogOfWar {
+    width_chunks: u32,
+    height_chunks: u32,
+    cells_per_chunk: usize,
+    default_sight_range: f32,
+    sight_ranges: HashMap<EntityId, f32>,
+    factions: HashMap<FactionId, VisibilityGrid>,
+}
+
+impl FogOfWar {
+    /// Create fog covering a world, with nothing explored yet
+    pub fn new(world: &World) -> Self {
+        Self {
+            width_chunks: world.width_chunks,
+            height_chunks: world.height_chunks,
+            cells_per_chunk: FOG_CELLS_PER_CHUNK,
+            default_sight_range: DEFAULT_SIGHT_RANGE,
+            sight_ranges: HashMap::new(),
+            factions: HashMap::new(),
+        }
+    }
+
+    /// Set how many cells the fog has along each side of a chunk
+    ///
+    /// Changing the resolution forgets what was explored.
+    pub fn with_cells_per_chunk(mut self, cells_per_chunk: usize) -> Self {
+        self.cells_per_chunk = cells_per_chunk.max(1);
+        self.factions.clear();
+        self
+    }
+
+    /// Set the sight range of entities without their own
+    pub fn with_sight_range(mut self, range: f32) -> Self {
+        self.default_sight_range = range.max(0.0);
+        self
+    }
+
+    /// Give an entity its own sight range
+    pub fn set_sight_range(&mut self, entity: EntityId, range: f32) {
+        self.sight_ranges.insert(entity, range.max(0.0));
+    }
+
+    /// Get the sight range of an entity
+    pub fn sight_range(&self, entity: &EntityId) -> f32 {
+        self.sight_ranges.get(entity).copied().unwrap_or(self.default_sight_range)
+    }
+
+    /// Get the size of a fog cell (in meters)
+    pub fn cell_size(&self) -> f32 {
+        CHUNK_SIZE / self.cells_per_chunk as f32
+    }
+
+    /// Update visibility from the NPCs of every faction
+    ///
+    /// Every living NPC with a faction reveals the circle of its sight range around its
+    /// entity. Factions of the world without NPCs still get a grid, with nothing visible.
+    pub fn update(&mut self, world: &World) {
+        for faction in world.factions.keys() {
+            self.grid_mut(faction);
+        }
+
+        let sightings: Vec<Sighting> = world
+            .npcs
+            .values()
+            .filter_map(|npc| {
+                let faction = npc.faction.as_ref()?;
+                let entity = world.entities.get(&npc.entity_id).filter(|entity| entity.is_alive)?;
+                Some(Sighting::new(faction.clone(), entity.x, entity.y, self.sight_range(&entity.id)))
+            })
+            .collect();
+
+        self.update_from(&sightings);
+    }
+
+    /// Update visibility from sightings, replacing what was visible before
+    pub fn update_from(&mut self, sightings: &[Sighting]) {
+        for grid in self.factions.values_mut() {
+            grid.clear_visible();
+        }
+        for sighting in sightings {
+            self.reveal(&sighting.faction, sighting.x, sighting.y, sighting.range);
+        }
+    }
+
+    /// Mark a circle as visible and explored for a faction
+    pub fn reveal(&mut self, faction: &str, x: f32, y: f32, range: f32) {
+        let cell_size = self.cell_size();
+        let (columns, rows) = self.dimensions();
+        let range_sq = range * range;
+        let cell_range = |center: f32, cells: usize| {
+            let first = ((center - range) / cell_size).floor().max(0.0) as usize;
+            let last = (((center + range) / cell_size).floor().max(0.0) as usize).min(cells.saturating_sub(1));
+            first..=last
+        };
+        if columns == 0 || rows == 0 || x + range < 0.0 || y + range < 0.0 {
+            return;
+        }
+        let (xs, ys) = (cell_range(x, columns), cell_range(y, rows));
+
+        let grid = self.grid_mut(faction);
+        for cy in ys {
+            for cx in xs.clone() {
+                let dx = (cx as f32 + 0.5) * cell_size - x;
+                let dy = (cy as f32 + 0.5) * cell_size - y;
+                if dx * dx + dy * dy <= range_sq {
+                    grid.mark(cy * columns + cx);
+                }
+            }
+        }
+    }
+
+    /// Get what a faction knows about a position
+    pub fn visibility(&self, faction: &str, x: f32, y: f32) -> Visibility {
+        match (self.factions.get(faction), self.cell_at(x, y)) {
+            (Some(grid), Some(cell)) if grid.is_visible(cell) => Visibility::Visible,
+            (Some(grid), Some(cell)) if grid.is_explored(cell) => Visibility::Explored,
+            _ => Visibility::Unexplored,
+        }
+    }
+
+    /// Check if a faction sees a position right now
+    pub fn is_visible(&self, faction: &str, x: f32, y: f32) -> bool {
+        self.visibility(faction, x, y) == Visibility::Visible
+    }
+
+    /// Check if a faction has ever seen a position
+    pub fn is_explored(&self, faction: &str, x: f32, y: f32) -> bool {
+        self.visibility(faction, x, y) != Visibility::Unexplored
+    }
+
+    /// Get the share of a chunk a faction has explored, from 0 to 1
+    pub fn explored_fraction(&self, faction: &str, coord: ChunkCoord) -> f32 {
+        let Some(grid) = self.factions.get(faction) else {
+            return 0.0;
+        };
+        if coord.x >= self.width_chunks || coord.y >= self.height_chunks {
+            return 0.0;
+        }
+
+        let explored = self
+            .chunk_cells(coord)
+            .filter(|&cell| grid.is_explored(cell))
+            .count();
+        explored as f32 / (self.cells_per_chunk * self.cells_per_chunk) as f32
+    }
+
+    /// Get the chunks in which a faction sees at least one cell
+    pub fn visible_chunks(&self, faction: &str) -> Vec<ChunkCoord> {
+        let Some(grid) = self.factions.get(faction) else {
+            return Vec::new();
+        };
+
+        (0..self.height_chunks)
+            .flat_map(|y| (0..self.width_chunks).map(move |x| ChunkCoord::new(x, y)))
+            .filter(|&coord| self.chunk_cells(coord).any(|cell| grid.is_visible(cell)))
+            .collect()
+    }
+
+    /// Get the grid of a faction
+    pub fn grid(&self, faction: &str) -> Option<&VisibilityGrid> {
+        self.factions.get(faction)
+    }
+
+    /// Get the factions with a grid
+    pub fn factions(&self) -> impl Iterator<Item = &FactionId> {
+        self.factions.keys()
+    }
+
+    /// Serialize the fog to JSON, with explored cells but not visible ones
+    pub fn to_json(&self) -> Result<String, SpatialError> {
+        serde_json::to_string(self).map_err(|e| SpatialError::SerializationError {
+            message: e.to_string(),
+        })
+    }
+
+    /// Deserialize fog from JSON
+    pub fn from_json(json: &str) -> Result<Self, SpatialError> {
+        let fog: Self = serde_json::from_str(json).map_err(|e| SpatialError::DeserializationError {
+            message: e.to_string(),
+        })?;
+
+        let words = (fog.cell_count() + 63) / 64;
+        if fog.cells_per_chunk == 0 || fog.factions.values().any(|grid| grid.explored.len() != words) {
+            return Err(SpatialError::DeserializationError {
+                message: "fog grid does not match the world size".to_string(),
+            });
+        }
+        Ok(fog)
+    }
+
+    fn grid_mut(&mut self, faction: &str) -> &mut VisibilityGrid {
+        let cells = self.cell_count();
+        self.factions
+            .entry(faction.to_string())
+            .or_insert_with(|| VisibilityGrid::new(cells))
+    }
+
+    /// Get the number of cell columns and rows
+    fn dimensions(&self) -> (usize, usize) {
+        (
+            self.width_chunks as usize * self.cells_per_chunk,
+            self.height_chunks as usize * self.cells_per_chunk,
+        )
+    }
+
+    fn cell_count(&self) -> usize {
+        let (columns, rows) = self.dimensions();
+        columns * rows
+    }
+
+    fn cell_at(&self, x: f32, y: f32) -> Option<usize> {
+        let (columns, rows) = self.dimensions();
+        let cell_size = self.cell_size();
+        let (cx, cy) = ((x / cell_size).floor(), (y / cell_size).floor());
+        (cx >= 0.0 && cy >= 0.0 && (cx as usize) < columns && (cy as usize) < rows)
+            .then(|| cy as usize * columns + cx as usize)
+    }
+
+    fn chunk_cells(&self, coord: ChunkCoord) -> impl Iterator<Item = usize> {
+        let (columns, _) = self.dimensions();
+        let n = self.cells_per_chunk;
+        let (x0, y0) = (coord.x as usize * n, coord.y as usize * n);
+        (0..n).flat_map(move |dy| (0..n).map(move |dx| (y0 + dy) * columns + x0 + dx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Entity;
+    use entropic_world_core::population::{EntityType, NPC};
+
+    fn create_test_world() -> World {
+        let mut world = World::new("Test".to_string(), "game1".to_string(), 4, 4);
+        world.initialize_chunks();
+        world
+    }
+
+    fn add_scout(world: &mut World, id: &str, faction: &str, x: f32, y: f32) {
+        let coord = ChunkCoord::new((x / CHUNK_SIZE) as u32, (y / CHUNK_SIZE) as u32);
+        world.add_entity(Entity::new(id.to_string(), EntityType::NPC, x, y, 0.0, coord));
+        let mut npc = NPC::new(format!("npc-{}", id), id.to_string(), id.to_string());
+        npc.faction = Some(faction.to_string());
+        world.add_npc(npc);
+    }
+
+    #[test]
+    fn test_reveal_and_forget_visible() {
+        let world = create_test_world();
+        let mut fog = FogOfWar::new(&world);
+
+        fog.update_from(&[Sighting::new("red", 100.0, 100.0, 40.0)]);
+        assert_eq!(fog.visibility("red", 100.0, 130.0), Visibility::Visible);
+        assert_eq!(fog.visibility("red", 100.0, 150.0), Visibility::Unexplored);
+        assert_eq!(fog.visibility("blue", 100.0, 100.0), Visibility::Unexplored);
+
+        fog.update_from(&[Sighting::new("red", 600.0, 100.0, 40.0)]);
+        assert_eq!(fog.visibility("red", 100.0, 100.0), Visibility::Explored);
+        assert!(fog.is_visible("red", 600.0, 100.0));
+        assert!(fog.is_explored("red", 100.0, 100.0));
+        assert_eq!(fog.visible_chunks("red"), vec![ChunkCoord::new(2, 0)]);
+
+        // Sight ranges reaching past the world edges
+        fog.reveal("red", 5.0, 5.0, 30.0);
+        fog.reveal("red", -500.0, 5.0, 30.0);
+        assert!(fog.is_visible("red", 0.0, 0.0));
+        assert!(!fog.is_visible("red", -1.0, 0.0));
+    }
+
+    #[test]
+    fn test_update_from_npcs() {
+        let mut world = create_test_world();
+        add_scout(&mut world, "a", "red", 128.0, 128.0);
+        add_scout(&mut world, "b", "blue", 900.0, 900.0);
+
+        let mut fog = FogOfWar::new(&world).with_sight_range(32.0);
+        fog.set_sight_range("b".to_string(), 300.0);
+        fog.update(&world);
+
+        assert!(fog.is_visible("red", 140.0, 140.0));
+        assert!(!fog.is_visible("red", 200.0, 128.0));
+        assert!(fog.is_visible("blue", 700.0, 900.0));
+        assert!(!fog.is_visible("blue", 128.0, 128.0));
+
+        // The whole chunk around the blue scout is in its sight
+        assert_eq!(fog.explored_fraction("blue", ChunkCoord::new(3, 3)), 1.0);
+        let partial = fog.explored_fraction("red", ChunkCoord::new(0, 0));
+        assert!(partial > 0.0 && partial < 0.1);
+
+        // Scouts that died stop revealing
+        world.entities.get_mut("a").unwrap().is_alive = false;
+        fog.update(&world);
+        assert_eq!(fog.visibility("red", 140.0, 140.0), Visibility::Explored);
+        assert_eq!(fog.grid("red").unwrap().visible_cells(), 0);
+    }
+
+    #[test]
+    fn test_exploration_persists() {
+        let world = create_test_world();
+        let mut fog = FogOfWar::new(&world).with_cells_per_chunk(8);
+        fog.update_from(&[Sighting::new("red", 300.0, 300.0, 100.0), Sighting::new("blue", 10.0, 900.0, 50.0)]);
+
+        let restored = FogOfWar::from_json(&fog.to_json().unwrap()).unwrap();
+        for faction in ["red", "blue"] {
+            assert_eq!(
+                restored.grid(faction).unwrap().explored_cells(),
+                fog.grid(faction).unwrap().explored_cells()
+            );
+        }
+        assert_eq!(restored.visibility("red", 300.0, 300.0), Visibility::Explored);
+        assert_eq!(restored.cell_size(), 32.0);
+
+        let mut bad: serde_json::Value = serde_json::from_str(&fog.to_json().unwrap()).unwrap();
+        bad["width_chunks"] = 5.into();
+        assert!(FogOfWar::from_json(&bad.to_string()).is_err());
+    }
+}
@@ -3,9 +3,13 @@
 //! This module is only available when the "voxel" feature is enabled.
 
 use crate::errors::SpatialError;
+use serde::{Deserialize, Serialize};
+
+/// Voxels per voxel chunk side
+pub const VOXEL_CHUNK_SIZE: usize = 16;
 
 /// Voxel data structure
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Voxel {
     Empty,
     Solid { material: u8, density: u8 },
@@ -19,7 +23,7 @@ impl Default for Voxel {
 }
 
 /// Voxel chunk
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VoxelChunk {
     pub coord: (u32, u32, u32),
     pub voxels: Vec<Voxel>,
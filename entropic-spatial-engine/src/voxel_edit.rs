@@ -0,0 +1,606 @@
+//! Voxel editing
+//!
+//! This module is only available when the "voxel" feature is enabled.
+//!
+//! [`VoxelTerrain`] holds the loaded voxel chunks and is where blocks get placed and destroyed.
+//! Every change is recorded in a [`VoxelJournal`], which can be saved with the world or sent
+//! to other peers and replayed there, and which is replayed onto chunks loaded later, so
+//! regenerated chunks keep their modifications. A change marks its chunk dirty, along with the
+//! neighbors sharing the changed face, and dirty chunks are remeshed off the async runtime by
+//! [`VoxelTerrain::spawn_remesh`].
+
+use crate::errors::SpatialError;
+use crate::voxel::{Voxel, VoxelChunk, VOXEL_CHUNK_SIZE};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use tokio::task::JoinHandle;
+
+/// Coordinate of a voxel chunk
+pub type VoxelChunkCoord = (u32, u32, u32);
+
+/// Position of a voxel in the world, counted in voxels
+pub type VoxelPosition = (u32, u32, u32);
+
+/// Voxels per side of a chunk with a one voxel border taken from its neighbors
+const PADDED_SIZE: usize = VOXEL_CHUNK_SIZE + 2;
+
+/// Face directions, as offsets to the neighboring voxel
+const FACES: [[i32; 3]; 6] = [[1, 0, 0], [-1, 0, 0], [0, 1, 0], [0, -1, 0], [0, 0, 1], [0, 0, -1]];
+
+/// Change of a single voxel
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VoxelEdit {
+    /// Order of the edit in its journal, starting at one
+    pub sequence: u64,
+    pub position: VoxelPosition,
+    pub before: Voxel,
+    pub after: Voxel,
+}
+
+impl VoxelEdit {
+    /// Get the chunk holding the edited voxel
+    pub fn chunk(&self) -> VoxelChunkCoord {
+        chunk_of(self.position).0
+    }
+}
+
+/// Ordered record of voxel edits
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct VoxelJournal {
+    edits: Vec<VoxelEdit>,
+    last_sequence: u64,
+}
+
+impl VoxelJournal {
+    /// Create an empty journal
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn record(&mut self, position: VoxelPosition, before: Voxel, after: Voxel) -> VoxelEdit {
+        self.last_sequence += 1;
+        let edit = VoxelEdit {
+            sequence: self.last_sequence,
+            position,
+            before,
+            after,
+        };
+        self.edits.push(edit);
+        edit
+    }
+
+    /// Get every edit, oldest first
+    pub fn edits(&self) -> &[VoxelEdit] {
+        &self.edits
+    }
+
+    /// Get the number of edits
+    pub fn len(&self) -> usize {
+        self.edits.len()
+    }
+
+    /// Check if no edits were recorded
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    /// Get the sequence number of the latest edit, zero before the first
+    pub fn last_sequence(&self) -> u64 {
+        self.last_sequence
+    }
+
+    /// Get the edits recorded after a sequence number, for peers that have seen up to it
+    pub fn since(&self, sequence: u64) -> &[VoxelEdit] {
+        let start = self.edits.partition_point(|edit| edit.sequence <= sequence);
+        &self.edits[start..]
+    }
+
+    /// Merge repeated edits of a voxel into its latest one, dropping edits undone later
+    ///
+    /// Sequence numbers are kept, so [`since`](Self::since) still works for peers.
+    pub fn compact(&mut self) {
+        let mut latest: HashMap<VoxelPosition, VoxelEdit> = HashMap::new();
+        for edit in &self.edits {
+            latest
+                .entry(edit.position)
+                .and_modify(|merged| {
+                    merged.sequence = edit.sequence;
+                    merged.after = edit.after;
+                })
+                .or_insert(*edit);
+        }
+
+        let mut edits: Vec<VoxelEdit> = latest.into_values().filter(|edit| edit.before != edit.after).collect();
+        edits.sort_by_key(|edit| edit.sequence);
+        self.edits = edits;
+    }
+
+    /// Serialize the journal to JSON
+    pub fn to_json(&self) -> Result<String, SpatialError> {
+        serde_json::to_string(self).map_err(|e| SpatialError::SerializationError {
+            message: e.to_string(),
+        })
+    }
+
+    /// Deserialize a journal from JSON
+    pub fn from_json(json: &str) -> Result<Self, SpatialError> {
+        serde_json::from_str(json).map_err(|e| SpatialError::DeserializationError {
+            message: e.to_string(),
+        })
+    }
+}
+
+/// Surface mesh of a voxel chunk, one quad per exposed solid face
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VoxelMesh {
+    /// Vertex positions (in meters)
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    /// Material of the voxel each vertex belongs to
+    pub materials: Vec<u8>,
+    /// Triangle list, counter-clockwise seen from outside
+    pub indices: Vec<u32>,
+}
+
+impl VoxelMesh {
+    /// Get the number of quads
+    pub fn face_count(&self) -> usize {
+        self.positions.len() / 4
+    }
+
+    /// Check if the mesh has no faces
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    fn add_face(&mut self, voxel: [usize; 3], direction: [i32; 3], material: u8) {
+        let axis = direction.iter().position(|d| *d != 0).unwrap_or(0);
+        let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+        let mut corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        if direction[axis] < 0 {
+            corners.reverse();
+        }
+
+        let first = self.positions.len() as u32;
+        let normal = direction.map(|d| d as f32);
+        for (du, dv) in corners {
+            let mut position = voxel.map(|c| c as f32);
+            if direction[axis] > 0 {
+                position[axis] += 1.0;
+            }
+            position[u] += du;
+            position[v] += dv;
+            self.positions.push(position);
+            self.normals.push(normal);
+            self.materials.push(material);
+        }
+        self.indices.extend([first, first + 1, first + 2, first, first + 2, first + 3]);
+    }
+}
+
+/// Copy of a chunk with a one voxel border from its neighbors, enough to mesh it
+struct Neighborhood {
+    origin: [usize; 3],
+    voxels: Vec<Voxel>,
+}
+
+impl Neighborhood {
+    fn voxel(&self, x: usize, y: usize, z: usize) -> Voxel {
+        self.voxels[x + y * PADDED_SIZE + z * PADDED_SIZE * PADDED_SIZE]
+    }
+
+    fn mesh(&self) -> VoxelMesh {
+        let mut mesh = VoxelMesh::default();
+        for z in 1..=VOXEL_CHUNK_SIZE {
+            for y in 1..=VOXEL_CHUNK_SIZE {
+                for x in 1..=VOXEL_CHUNK_SIZE {
+                    let Voxel::Solid { material, .. } = self.voxel(x, y, z) else {
+                        continue;
+                    };
+                    for direction in FACES {
+                        let neighbor = |axis: usize, c: usize| (c as i32 + direction[axis]) as usize;
+                        if !matches!(self.voxel(neighbor(0, x), neighbor(1, y), neighbor(2, z)), Voxel::Solid { .. }) {
+                            let world = [
+                                self.origin[0] + x - 1,
+                                self.origin[1] + y - 1,
+                                self.origin[2] + z - 1,
+                            ];
+                            mesh.add_face(world, direction, material);
+                        }
+                    }
+                }
+            }
+        }
+        mesh
+    }
+}
+
+/// Loaded voxel chunks with journaled edits and dirty tracking
+#[derive(Clone, Debug, Default)]
+pub struct VoxelTerrain {
+    chunks: HashMap<VoxelChunkCoord, VoxelChunk>,
+    dirty: BTreeSet<VoxelChunkCoord>,
+    journal: VoxelJournal,
+}
+
+impl VoxelTerrain {
+    /// Create terrain without chunks or edits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from a saved journal, replayed onto chunks as they are inserted
+    pub fn with_journal(mut self, journal: VoxelJournal) -> Self {
+        self.journal = journal;
+        self
+    }
+
+    /// Get the journal of every edit
+    pub fn journal(&self) -> &VoxelJournal {
+        &self.journal
+    }
+
+    /// Get a loaded chunk
+    pub fn chunk(&self, coord: VoxelChunkCoord) -> Option<&VoxelChunk> {
+        self.chunks.get(&coord)
+    }
+
+    /// Load a chunk, replaying the journaled edits that fall inside it
+    ///
+    /// The chunk and its loaded neighbors are marked dirty, since their shared faces change.
+    pub fn insert_chunk(&mut self, mut chunk: VoxelChunk) {
+        let coord = chunk.coord;
+        for edit in self.journal.edits.iter().filter(|edit| edit.chunk() == coord) {
+            let (_, (x, y, z)) = chunk_of(edit.position);
+            chunk.set(x, y, z, edit.after);
+        }
+
+        self.chunks.insert(coord, chunk);
+        self.dirty.insert(coord);
+        for direction in FACES {
+            self.mark_neighbor(coord, direction);
+        }
+    }
+
+    /// Unload a chunk, marking its loaded neighbors dirty
+    pub fn remove_chunk(&mut self, coord: VoxelChunkCoord) -> Option<VoxelChunk> {
+        let chunk = self.chunks.remove(&coord)?;
+        self.dirty.remove(&coord);
+        for direction in FACES {
+            self.mark_neighbor(coord, direction);
+        }
+        Some(chunk)
+    }
+
+    /// Get a voxel, `None` if its chunk is not loaded
+    pub fn get(&self, position: VoxelPosition) -> Option<Voxel> {
+        let (coord, (x, y, z)) = chunk_of(position);
+        self.chunks.get(&coord)?.get(x, y, z).copied()
+    }
+
+    /// Set a voxel, returning the journaled edit, or `None` if nothing changed
+    pub fn set(&mut self, position: VoxelPosition, voxel: Voxel) -> Result<Option<VoxelEdit>, SpatialError> {
+        let before = self.get(position).ok_or_else(|| not_loaded(position))?;
+        if before == voxel {
+            return Ok(None);
+        }
+
+        self.write(position, voxel);
+        Ok(Some(self.journal.record(position, before, voxel)))
+    }
+
+    /// Place a block into an empty or fluid voxel
+    ///
+    /// Returns `false` and changes nothing if the voxel is already solid.
+    pub fn place(&mut self, position: VoxelPosition, voxel: Voxel) -> Result<bool, SpatialError> {
+        match self.get(position) {
+            None => Err(not_loaded(position)),
+            Some(Voxel::Solid { .. }) => Ok(false),
+            Some(_) => self.set(position, voxel).map(|edit| edit.is_some()),
+        }
+    }
+
+    /// Destroy a voxel, returning what was there, or `None` if it was already empty
+    pub fn destroy(&mut self, position: VoxelPosition) -> Result<Option<Voxel>, SpatialError> {
+        Ok(self.set(position, Voxel::Empty)?.map(|edit| edit.before))
+    }
+
+    /// Apply edits received from a peer or a save, journaling them here as well
+    ///
+    /// Edits in chunks that are not loaded are applied once the chunk is inserted. Returns the
+    /// number of edits applied to loaded chunks.
+    pub fn apply_edits(&mut self, edits: &[VoxelEdit]) -> usize {
+        let mut applied = 0;
+        for edit in edits {
+            let before = self.get(edit.position);
+            self.journal.record(edit.position, before.unwrap_or(edit.before), edit.after);
+            if before.is_some() {
+                self.write(edit.position, edit.after);
+                applied += 1;
+            }
+        }
+        applied
+    }
+
+    /// Check if a chunk needs remeshing
+    pub fn is_dirty(&self, coord: VoxelChunkCoord) -> bool {
+        self.dirty.contains(&coord)
+    }
+
+    /// Get the chunks needing remeshing
+    pub fn dirty_chunks(&self) -> Vec<VoxelChunkCoord> {
+        self.dirty.iter().copied().collect()
+    }
+
+    /// Mesh a loaded chunk, using its neighbors to cull hidden faces on the borders
+    pub fn mesh_chunk(&self, coord: VoxelChunkCoord) -> Option<VoxelMesh> {
+        self.neighborhood(coord).map(|neighborhood| neighborhood.mesh())
+    }
+
+    /// Mesh every dirty chunk on the calling thread, clearing the dirty flags
+    pub fn remesh_dirty(&mut self) -> Vec<(VoxelChunkCoord, VoxelMesh)> {
+        std::mem::take(&mut self.dirty)
+            .into_iter()
+            .filter_map(|coord| Some((coord, self.mesh_chunk(coord)?)))
+            .collect()
+    }
+
+    /// Mesh every dirty chunk on the blocking thread pool, clearing the dirty flags
+    ///
+    /// The chunks are copied before the task starts, so editing can go on meanwhile. Chunks
+    /// edited again are marked dirty again and picked up by the next remesh.
+    pub fn spawn_remesh(&mut self) -> JoinHandle<Vec<(VoxelChunkCoord, VoxelMesh)>> {
+        let snapshots: Vec<(VoxelChunkCoord, Neighborhood)> = std::mem::take(&mut self.dirty)
+            .into_iter()
+            .filter_map(|coord| Some((coord, self.neighborhood(coord)?)))
+            .collect();
+
+        tokio::task::spawn_blocking(move || {
+            snapshots
+                .into_iter()
+                .map(|(coord, neighborhood)| (coord, neighborhood.mesh()))
+                .collect()
+        })
+    }
+
+    /// Write a voxel into its loaded chunk, marking the chunks touching it dirty
+    fn write(&mut self, position: VoxelPosition, voxel: Voxel) {
+        let (coord, (x, y, z)) = chunk_of(position);
+        if let Some(chunk) = self.chunks.get_mut(&coord) {
+            chunk.set(x, y, z, voxel);
+        }
+        self.dirty.insert(coord);
+
+        let last = VOXEL_CHUNK_SIZE - 1;
+        for (axis, local) in [x, y, z].into_iter().enumerate() {
+            let mut direction = [0; 3];
+            if local == 0 {
+                direction[axis] = -1;
+            } else if local == last {
+                direction[axis] = 1;
+            } else {
+                continue;
+            }
+            self.mark_neighbor(coord, direction);
+        }
+    }
+
+    fn mark_neighbor(&mut self, coord: VoxelChunkCoord, direction: [i32; 3]) {
+        let neighbor = (
+            coord.0.checked_add_signed(direction[0]),
+            coord.1.checked_add_signed(direction[1]),
+            coord.2.checked_add_signed(direction[2]),
+        );
+        if let (Some(x), Some(y), Some(z)) = neighbor {
+            if self.chunks.contains_key(&(x, y, z)) {
+                self.dirty.insert((x, y, z));
+            }
+        }
+    }
+
+    fn neighborhood(&self, coord: VoxelChunkCoord) -> Option<Neighborhood> {
+        let chunk = self.chunks.get(&coord)?;
+        let origin = [coord.0, coord.1, coord.2].map(|c| c as usize * VOXEL_CHUNK_SIZE);
+        let mut voxels = vec![Voxel::Empty; PADDED_SIZE * PADDED_SIZE * PADDED_SIZE];
+
+        for z in 0..PADDED_SIZE {
+            for y in 0..PADDED_SIZE {
+                for x in 0..PADDED_SIZE {
+                    let inside = [x, y, z].iter().all(|c| (1..=VOXEL_CHUNK_SIZE).contains(c));
+                    let voxel = if inside {
+                        chunk.get(x - 1, y - 1, z - 1).copied()
+                    } else {
+                        // Border voxels come from the neighbors, empty where none is loaded
+                        let world = |axis: usize, c: usize| (origin[axis] + c).checked_sub(1);
+                        match (world(0, x), world(1, y), world(2, z)) {
+                            (Some(wx), Some(wy), Some(wz)) => self.get((wx as u32, wy as u32, wz as u32)),
+                            _ => None,
+                        }
+                    };
+                    voxels[x + y * PADDED_SIZE + z * PADDED_SIZE * PADDED_SIZE] = voxel.unwrap_or(Voxel::Empty);
+                }
+            }
+        }
+
+        Some(Neighborhood { origin, voxels })
+    }
+}
+
+/// Split a voxel position into its chunk and the position inside the chunk
+fn chunk_of(position: VoxelPosition) -> (VoxelChunkCoord, (usize, usize, usize)) {
+    let size = VOXEL_CHUNK_SIZE as u32;
+    (
+        (position.0 / size, position.1 / size, position.2 / size),
+        ((position.0 % size) as usize, (position.1 % size) as usize, (position.2 % size) as usize),
+    )
+}
+
+fn not_loaded(position: VoxelPosition) -> SpatialError {
+    SpatialError::InvalidCoordinates {
+        message: format!("voxel {:?} is in chunk {:?}, which is not loaded", position, chunk_of(position).0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STONE: Voxel = Voxel::Solid {
+        material: 2,
+        density: 255,
+    };
+
+    fn create_test_terrain() -> VoxelTerrain {
+        let mut terrain = VoxelTerrain::new();
+        for x in 0..2 {
+            for y in 0..2 {
+                terrain.insert_chunk(VoxelChunk::new((x, y, 0)));
+            }
+        }
+        terrain.remesh_dirty();
+        terrain
+    }
+
+    #[test]
+    fn test_place_and_destroy() {
+        let mut terrain = create_test_terrain();
+
+        assert!(terrain.place((5, 5, 5), STONE).unwrap());
+        assert_eq!(terrain.get((5, 5, 5)), Some(STONE));
+        assert!(!terrain.place((5, 5, 5), Voxel::Solid { material: 1, density: 10 }).unwrap());
+
+        let water = Voxel::Fluid { fluid_type: 0, level: 8 };
+        terrain.set((6, 5, 5), water).unwrap();
+        assert!(terrain.place((6, 5, 5), STONE).unwrap());
+
+        assert_eq!(terrain.destroy((5, 5, 5)).unwrap(), Some(STONE));
+        assert_eq!(terrain.destroy((5, 5, 5)).unwrap(), None);
+        assert_eq!(terrain.get((5, 5, 5)), Some(Voxel::Empty));
+
+        assert!(terrain.place((40, 5, 5), STONE).is_err());
+        assert!(terrain.destroy((5, 5, 16)).is_err());
+        assert_eq!(terrain.journal().len(), 4);
+    }
+
+    #[test]
+    fn test_neighbor_aware_dirty_flags() {
+        let mut terrain = create_test_terrain();
+        assert!(terrain.dirty_chunks().is_empty());
+
+        terrain.place((5, 5, 5), STONE).unwrap();
+        assert_eq!(terrain.dirty_chunks(), vec![(0, 0, 0)]);
+        terrain.remesh_dirty();
+
+        // On the border with the chunk over +x, and the unloaded chunk over +z
+        terrain.place((15, 3, 15), STONE).unwrap();
+        assert_eq!(terrain.dirty_chunks(), vec![(0, 0, 0), (1, 0, 0)]);
+        terrain.remesh_dirty();
+
+        // In the corner shared with three neighbors
+        terrain.place((16, 16, 0), STONE).unwrap();
+        assert_eq!(terrain.dirty_chunks(), vec![(0, 1, 0), (1, 0, 0), (1, 1, 0)]);
+        terrain.remesh_dirty();
+
+        terrain.remove_chunk((1, 1, 0));
+        assert_eq!(terrain.dirty_chunks(), vec![(0, 1, 0), (1, 0, 0)]);
+        assert!(terrain.chunk((1, 1, 0)).is_none());
+    }
+
+    #[test]
+    fn test_journal() {
+        let mut terrain = create_test_terrain();
+        terrain.place((1, 1, 1), STONE).unwrap();
+        terrain.place((2, 1, 1), STONE).unwrap();
+        terrain.destroy((1, 1, 1)).unwrap();
+        terrain.place((3, 1, 1), STONE).unwrap();
+        terrain.set((2, 1, 1), Voxel::Solid { material: 7, density: 255 }).unwrap();
+
+        let journal = terrain.journal().clone();
+        assert_eq!(journal.last_sequence(), 5);
+        assert_eq!(journal.since(3).iter().map(|edit| edit.sequence).collect::<Vec<_>>(), vec![4, 5]);
+        assert!(journal.since(5).is_empty());
+        assert_eq!(journal.edits()[2].before, STONE);
+
+        let mut compacted = journal.clone();
+        compacted.compact();
+        assert_eq!(compacted.len(), 2);
+        assert_eq!(compacted.edits()[0].position, (3, 1, 1));
+        assert_eq!(compacted.edits()[1].before, Voxel::Empty);
+        assert_eq!(compacted.edits()[1].after, Voxel::Solid { material: 7, density: 255 });
+        assert_eq!(compacted.last_sequence(), 5);
+
+        let restored = VoxelJournal::from_json(&journal.to_json().unwrap()).unwrap();
+        assert_eq!(restored, journal);
+        assert!(VoxelJournal::from_json("{").is_err());
+    }
+
+    #[test]
+    fn test_replay_edits() {
+        let mut server = create_test_terrain();
+        server.place((1, 2, 3), STONE).unwrap();
+        server.place((20, 2, 3), STONE).unwrap();
+
+        // A peer with one chunk loaded catches up, and gets the rest once the chunk loads
+        let mut peer = VoxelTerrain::new();
+        peer.insert_chunk(VoxelChunk::new((0, 0, 0)));
+        assert_eq!(peer.apply_edits(server.journal().since(0)), 1);
+        assert_eq!(peer.get((1, 2, 3)), Some(STONE));
+        assert_eq!(peer.get((20, 2, 3)), None);
+        peer.insert_chunk(VoxelChunk::new((1, 0, 0)));
+        assert_eq!(peer.get((20, 2, 3)), Some(STONE));
+
+        // A saved journal is replayed onto regenerated chunks
+        let saved = VoxelJournal::from_json(&server.journal().to_json().unwrap()).unwrap();
+        let mut loaded = VoxelTerrain::new().with_journal(saved);
+        loaded.insert_chunk(VoxelChunk::new((0, 0, 0)));
+        assert_eq!(loaded.get((1, 2, 3)), Some(STONE));
+        assert_eq!(loaded.journal().len(), 2);
+    }
+
+    #[test]
+    fn test_mesh_culls_hidden_faces() {
+        let mut terrain = create_test_terrain();
+        terrain.place((4, 4, 4), STONE).unwrap();
+        let mesh = terrain.mesh_chunk((0, 0, 0)).unwrap();
+        assert_eq!(mesh.face_count(), 6);
+        assert_eq!(mesh.indices.len(), 36);
+        assert!(mesh.positions.iter().all(|p| p.iter().all(|c| (4.0..=5.0).contains(c))));
+
+        // Every face winds counter-clockwise around its outward normal
+        for (face, quad) in mesh.indices.chunks(6).enumerate() {
+            let [a, b, c] = [quad[0], quad[1], quad[2]].map(|i| mesh.positions[i as usize]);
+            let (ab, ac) = ([b[0] - a[0], b[1] - a[1], b[2] - a[2]], [c[0] - a[0], c[1] - a[1], c[2] - a[2]]);
+            let cross = [ab[1] * ac[2] - ab[2] * ac[1], ab[2] * ac[0] - ab[0] * ac[2], ab[0] * ac[1] - ab[1] * ac[0]];
+            let normal = mesh.normals[face * 4];
+            assert!(cross.iter().zip(normal).map(|(c, n)| c * n).sum::<f32>() > 0.0);
+        }
+
+        // Neighbors across a chunk border hide the faces they share
+        terrain.destroy((4, 4, 4)).unwrap();
+        terrain.place((15, 4, 4), STONE).unwrap();
+        terrain.place((16, 4, 4), STONE).unwrap();
+        let meshes = terrain.remesh_dirty();
+        assert_eq!(meshes.len(), 2);
+        assert!(meshes.iter().all(|(_, mesh)| mesh.face_count() == 5));
+        assert!(terrain.dirty_chunks().is_empty());
+        assert!(terrain.mesh_chunk((5, 5, 5)).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_spawn_remesh() {
+        let mut terrain = create_test_terrain();
+        terrain.place((15, 15, 0), STONE).unwrap();
+        let remesh = terrain.spawn_remesh();
+        assert!(terrain.dirty_chunks().is_empty());
+
+        // Editing while the remesh runs marks the chunk dirty again
+        terrain.destroy((15, 15, 0)).unwrap();
+        let meshes = remesh.await.unwrap();
+
+        assert_eq!(meshes.len(), 3);
+        let own = meshes.iter().find(|(coord, _)| *coord == (0, 0, 0)).unwrap();
+        assert_eq!(own.1.face_count(), 6);
+        assert!(meshes.iter().filter(|(coord, _)| *coord != (0, 0, 0)).all(|(_, mesh)| mesh.is_empty()));
+        assert!(terrain.is_dirty((0, 0, 0)));
+    }
+}
@@ -10,6 +10,9 @@ pub const DEFAULT_GRID_SIZE: f32 = 16.0;
 /// Default ticks per second
 pub const DEFAULT_TICKS_PER_SECOND: u64 = 20;
 
+/// Ticks in one in-game day at the default tick rate
+pub const TICKS_PER_DAY: u64 = 86_400 * DEFAULT_TICKS_PER_SECOND;
+
 /// Seconds per tick
 pub const SECONDS_PER_TICK: f32 = 1.0 / DEFAULT_TICKS_PER_SECOND as f32;
 
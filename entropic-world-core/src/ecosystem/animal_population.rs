@@ -0,0 +1,398 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use crate::ecosystem::species::SpeciesId;
+use crate::spatial::coordinates::ChunkCoord;
+
+type ChunkPopulations = HashMap<ChunkCoord, HashMap<SpeciesId, u32>>;
+
+/// Serializes chunk populations as a sequence of `(ChunkCoord, populations)` pairs, since
+/// JSON object keys must be strings.
+fn serialize_chunk_populations<S>(chunks: &ChunkPopulations, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let vec: Vec<(&ChunkCoord, &HashMap<SpeciesId, u32>)> = chunks.iter().collect();
+    vec.serialize(serializer)
+}
+
+/// Deserializes the sequence written by `serialize_chunk_populations`.
+fn deserialize_chunk_populations<'de, D>(deserializer: D) -> Result<ChunkPopulations, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let vec: Vec<(ChunkCoord, HashMap<SpeciesId, u32>)> = Vec::deserialize(deserializer)?;
+    Ok(vec.into_iter().collect())
+}
+
+/// The serialized forms of `AnimalPopulations`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredPopulations {
+    Chunks {
+        #[serde(deserialize_with = "deserialize_chunk_populations")]
+        chunks: ChunkPopulations,
+    },
+    /// Saves from before populations were resolved per chunk hold one count per species
+    Legacy(HashMap<SpeciesId, u32>),
+}
+
+impl From<StoredPopulations> for AnimalPopulations {
+    fn from(stored: StoredPopulations) -> Self {
+        match stored {
+            StoredPopulations::Chunks { chunks } => Self {
+                chunks,
+                unplaced: Vec::new(),
+            },
+            StoredPopulations::Legacy(counts) => {
+                let mut populations = Self::new();
+                for (species_id, count) in counts {
+                    if count > 0 {
+                        populations.unplaced.push(species_id.clone());
+                    }
+                    populations.add(LEGACY_CHUNK, species_id, count);
+                }
+                populations.unplaced.sort();
+                populations
+            }
+        }
+    }
+}
+
+/// Chunk that populations from saves without per-chunk counts are loaded into.
+pub const LEGACY_CHUNK: ChunkCoord = ChunkCoord { x: 0, y: 0 };
+
+/// Animal counts per species, resolved to the chunk the animals live in.
+///
+/// Only non-zero counts are stored. Aggregate queries such as `total` sum over chunks.
+/// Saves holding a single count per species load with every animal in `LEGACY_CHUNK`;
+/// loading a `World` then spreads them over each species' habitat.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(from = "StoredPopulations")]
+pub struct AnimalPopulations {
+    #[serde(serialize_with = "serialize_chunk_populations")]
+    chunks: ChunkPopulations,
+    /// Species loaded from a legacy save whose animals still sit in `LEGACY_CHUNK`
+    #[serde(skip)]
+    unplaced: Vec<SpeciesId>,
+}
+
+impl AnimalPopulations {
+    /// Creates an empty population store.
+    pub fn new() -> Self {
+        Self {
+            chunks: HashMap::new(),
+            unplaced: Vec::new(),
+        }
+    }
+
+    /// Removes the animals loaded from a legacy save, which all sit in `LEGACY_CHUNK`, and
+    /// returns their counts per species, sorted by species, so they can be placed in the
+    /// species' habitats.
+    pub fn take_unplaced(&mut self) -> Vec<(SpeciesId, u32)> {
+        std::mem::take(&mut self.unplaced)
+            .into_iter()
+            .map(|species_id| {
+                let count = self.in_chunk(&LEGACY_CHUNK, &species_id);
+                self.set(LEGACY_CHUNK, species_id.clone(), 0);
+                (species_id, count)
+            })
+            .collect()
+    }
+
+    /// Returns the number of `species_id` animals living in `coord`.
+    pub fn in_chunk(&self, coord: &ChunkCoord, species_id: &SpeciesId) -> u32 {
+        self.chunks
+            .get(coord)
+            .and_then(|species| species.get(species_id))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns a copy of every species' population in `coord`.
+    pub fn chunk_populations(&self, coord: &ChunkCoord) -> HashMap<SpeciesId, u32> {
+        self.chunks.get(coord).cloned().unwrap_or_default()
+    }
+
+    /// Sets the number of `species_id` animals in `coord`, removing the entry when `count`
+    /// is zero.
+    pub fn set(&mut self, coord: ChunkCoord, species_id: SpeciesId, count: u32) {
+        if count == 0 {
+            if let Some(species) = self.chunks.get_mut(&coord) {
+                species.remove(&species_id);
+                if species.is_empty() {
+                    self.chunks.remove(&coord);
+                }
+            }
+        } else {
+            self.chunks.entry(coord).or_default().insert(species_id, count);
+        }
+    }
+
+    /// Adds `count` animals of `species_id` to `coord`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::ecosystem::AnimalPopulations;
+    /// use entropic_world_core::spatial::ChunkCoord;
+    ///
+    /// let mut populations = AnimalPopulations::new();
+    /// populations.add(ChunkCoord::new(0, 0), "wolf".into(), 3);
+    /// populations.add(ChunkCoord::new(1, 0), "wolf".into(), 4);
+    /// assert_eq!(populations.total(&"wolf".into()), 7);
+    /// assert_eq!(populations.in_chunk(&ChunkCoord::new(1, 0), &"wolf".into()), 4);
+    /// ```
+    pub fn add(&mut self, coord: ChunkCoord, species_id: SpeciesId, count: u32) {
+        let current = self.in_chunk(&coord, &species_id);
+        self.set(coord, species_id, current.saturating_add(count));
+    }
+
+    /// Removes up to `count` animals of `species_id` from `coord` and returns how many were
+    /// actually removed.
+    pub fn remove(&mut self, coord: ChunkCoord, species_id: &SpeciesId, count: u32) -> u32 {
+        let current = self.in_chunk(&coord, species_id);
+        let removed = count.min(current);
+        self.set(coord, species_id.clone(), current - removed);
+        removed
+    }
+
+    /// Spreads `count` animals of `species_id` evenly over `coords`, giving any remainder to
+    /// the first coordinates in the order given.
+    pub fn distribute(&mut self, coords: &[ChunkCoord], species_id: &SpeciesId, count: u32) {
+        if coords.is_empty() {
+            return;
+        }
+        let share = count / coords.len() as u32;
+        let remainder = (count % coords.len() as u32) as usize;
+        for (i, coord) in coords.iter().enumerate() {
+            let amount = share + u32::from(i < remainder);
+            if amount > 0 {
+                self.add(*coord, species_id.clone(), amount);
+            }
+        }
+    }
+
+    /// Returns the world-wide population of `species_id`.
+    pub fn total(&self, species_id: &SpeciesId) -> u32 {
+        self.chunks
+            .values()
+            .filter_map(|species| species.get(species_id))
+            .sum()
+    }
+
+    /// Returns the world-wide population of every species with living animals.
+    pub fn totals(&self) -> HashMap<SpeciesId, u32> {
+        let mut totals = HashMap::new();
+        for species in self.chunks.values() {
+            for (species_id, count) in species {
+                *totals.entry(species_id.clone()).or_insert(0) += count;
+            }
+        }
+        totals
+    }
+
+    /// Returns the chunks inhabited by `species_id` with their counts, sorted by coordinate.
+    pub fn chunks_with(&self, species_id: &SpeciesId) -> Vec<(ChunkCoord, u32)> {
+        let mut result: Vec<(ChunkCoord, u32)> = self
+            .chunks
+            .iter()
+            .filter_map(|(coord, species)| species.get(species_id).map(|count| (*coord, *count)))
+            .collect();
+        result.sort_by_key(|(coord, _)| (coord.x, coord.y));
+        result
+    }
+
+    /// Returns every occupied chunk, sorted by coordinate.
+    pub fn occupied_chunks(&self) -> Vec<ChunkCoord> {
+        let mut coords: Vec<ChunkCoord> = self.chunks.keys().copied().collect();
+        coords.sort_by_key(|coord| (coord.x, coord.y));
+        coords
+    }
+
+    /// Counts the `species_id` animals living in chunks whose center lies within `radius`
+    /// meters of the world position `(x, y)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::ecosystem::AnimalPopulations;
+    /// use entropic_world_core::spatial::ChunkCoord;
+    ///
+    /// let mut populations = AnimalPopulations::new();
+    /// populations.add(ChunkCoord::new(0, 0), "wolf".into(), 3);
+    /// populations.add(ChunkCoord::new(9, 9), "wolf".into(), 4);
+    /// assert_eq!(populations.in_radius(&"wolf".into(), 128.0, 128.0, 300.0), 3);
+    /// ```
+    pub fn in_radius(&self, species_id: &SpeciesId, x: f32, y: f32, radius: f32) -> u32 {
+        self.chunks
+            .iter()
+            .filter(|(coord, _)| {
+                let (cx, cy) = coord.center();
+                (cx - x).powi(2) + (cy - y).powi(2) <= radius * radius
+            })
+            .filter_map(|(_, species)| species.get(species_id))
+            .sum()
+    }
+
+    /// Adds `count` animals of `species_id` to the chunks it already inhabits, in proportion
+    /// to each chunk's share of the population. Returns how many were added, which is zero
+    /// when the species inhabits no chunk.
+    pub fn add_distributed(&mut self, species_id: &SpeciesId, count: u32) -> u32 {
+        let total = self.total(species_id);
+        if total == 0 || count == 0 {
+            return 0;
+        }
+
+        let mut added = 0;
+        let inhabited = self.chunks_with(species_id);
+        for (coord, population) in &inhabited {
+            let share = (*population as u64 * count as u64 / total as u64) as u32;
+            self.add(*coord, species_id.clone(), share);
+            added += share;
+        }
+        if let Some((coord, _)) = inhabited.first() {
+            self.add(*coord, species_id.clone(), count - added);
+        }
+        count
+    }
+
+    /// Removes up to `count` animals of `species_id` across all chunks, taking from each
+    /// inhabited chunk in proportion to its share of the population. Returns how many
+    /// were removed.
+    pub fn remove_distributed(&mut self, species_id: &SpeciesId, count: u32) -> u32 {
+        let total = self.total(species_id);
+        if total == 0 || count == 0 {
+            return 0;
+        }
+        let count = count.min(total);
+
+        let mut removed = 0;
+        let inhabited = self.chunks_with(species_id);
+        for (coord, population) in &inhabited {
+            let share = (*population as u64 * count as u64 / total as u64) as u32;
+            removed += self.remove(*coord, species_id, share);
+        }
+        for (coord, _) in &inhabited {
+            if removed == count {
+                break;
+            }
+            removed += self.remove(*coord, species_id, count - removed);
+        }
+        removed
+    }
+
+    /// Removes every animal of `species_id`.
+    pub fn clear_species(&mut self, species_id: &SpeciesId) {
+        for (coord, _) in self.chunks_with(species_id) {
+            self.set(coord, species_id.clone(), 0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_and_total() {
+        let mut populations = AnimalPopulations::new();
+        populations.add(ChunkCoord::new(0, 0), "deer".to_string(), 10);
+        populations.add(ChunkCoord::new(0, 1), "deer".to_string(), 5);
+        populations.add(ChunkCoord::new(0, 1), "wolf".to_string(), 2);
+
+        assert_eq!(populations.total(&"deer".to_string()), 15);
+        assert_eq!(populations.totals()["wolf"], 2);
+        assert_eq!(populations.chunk_populations(&ChunkCoord::new(0, 1)).len(), 2);
+    }
+
+    #[test]
+    fn test_remove_clears_empty_entries() {
+        let mut populations = AnimalPopulations::new();
+        populations.add(ChunkCoord::new(2, 2), "deer".to_string(), 3);
+
+        assert_eq!(populations.remove(ChunkCoord::new(2, 2), &"deer".to_string(), 5), 3);
+        assert!(populations.occupied_chunks().is_empty());
+    }
+
+    #[test]
+    fn test_distribute() {
+        let mut populations = AnimalPopulations::new();
+        let coords = [ChunkCoord::new(0, 0), ChunkCoord::new(1, 0), ChunkCoord::new(2, 0)];
+        populations.distribute(&coords, &"deer".to_string(), 10);
+
+        assert_eq!(populations.in_chunk(&coords[0], &"deer".to_string()), 4);
+        assert_eq!(populations.in_chunk(&coords[2], &"deer".to_string()), 3);
+        assert_eq!(populations.total(&"deer".to_string()), 10);
+    }
+
+    #[test]
+    fn test_remove_distributed() {
+        let mut populations = AnimalPopulations::new();
+        populations.add(ChunkCoord::new(0, 0), "deer".to_string(), 30);
+        populations.add(ChunkCoord::new(1, 0), "deer".to_string(), 10);
+
+        assert_eq!(populations.remove_distributed(&"deer".to_string(), 20), 20);
+        assert_eq!(populations.in_chunk(&ChunkCoord::new(0, 0), &"deer".to_string()), 15);
+        assert_eq!(populations.in_chunk(&ChunkCoord::new(1, 0), &"deer".to_string()), 5);
+    }
+
+    #[test]
+    fn test_add_distributed() {
+        let mut populations = AnimalPopulations::new();
+        populations.add(ChunkCoord::new(0, 0), "deer".to_string(), 30);
+        populations.add(ChunkCoord::new(1, 0), "deer".to_string(), 10);
+
+        assert_eq!(populations.add_distributed(&"deer".to_string(), 9), 9);
+        assert_eq!(populations.in_chunk(&ChunkCoord::new(0, 0), &"deer".to_string()), 37);
+        assert_eq!(populations.in_chunk(&ChunkCoord::new(1, 0), &"deer".to_string()), 12);
+        assert_eq!(populations.add_distributed(&"wolf".to_string(), 5), 0);
+    }
+
+    #[test]
+    fn test_in_radius() {
+        let mut populations = AnimalPopulations::new();
+        populations.add(ChunkCoord::new(0, 0), "wolf".to_string(), 2);
+        populations.add(ChunkCoord::new(1, 0), "wolf".to_string(), 3);
+        populations.add(ChunkCoord::new(5, 5), "wolf".to_string(), 7);
+
+        assert_eq!(populations.in_radius(&"wolf".to_string(), 128.0, 128.0, 300.0), 5);
+    }
+
+    #[test]
+    fn test_serialization_round_trip() {
+        let mut populations = AnimalPopulations::new();
+        populations.add(ChunkCoord::new(3, 4), "deer".to_string(), 9);
+
+        let json = serde_json::to_string(&populations).unwrap();
+        let restored: AnimalPopulations = serde_json::from_str(&json).unwrap();
+        assert_eq!(populations, restored);
+    }
+
+    #[test]
+    fn test_legacy_counts_load_into_legacy_chunk() {
+        let restored: AnimalPopulations =
+            serde_json::from_str(r#"{"deer": 12, "wolf": 0}"#).unwrap();
+        assert_eq!(restored.in_chunk(&LEGACY_CHUNK, &"deer".to_string()), 12);
+        assert_eq!(restored.occupied_chunks(), vec![LEGACY_CHUNK]);
+
+        let empty: AnimalPopulations = serde_json::from_str("{}").unwrap();
+        assert_eq!(empty, AnimalPopulations::new());
+    }
+
+    #[test]
+    fn test_take_unplaced() {
+        let mut restored: AnimalPopulations =
+            serde_json::from_str(r#"{"wolf": 4, "deer": 12, "boar": 0}"#).unwrap();
+        assert_eq!(
+            restored.take_unplaced(),
+            vec![("deer".to_string(), 12), ("wolf".to_string(), 4)]
+        );
+        assert!(restored.occupied_chunks().is_empty());
+        assert!(restored.take_unplaced().is_empty());
+
+        let mut populations = AnimalPopulations::new();
+        populations.add(LEGACY_CHUNK, "deer".to_string(), 5);
+        assert!(populations.take_unplaced().is_empty());
+        assert_eq!(populations.total(&"deer".to_string()), 5);
+    }
+}
@@ -0,0 +1,352 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::ecosystem::species::SpeciesId;
+use crate::population::relationship::NpcId;
+use crate::spatial::coordinates::ChunkCoord;
+
+pub type DiseaseId = String;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Disease {
+    pub id: DiseaseId,
+    pub name: String,
+    pub infection_rate: f32,
+    pub recovery_rate: f32,
+    pub mortality_rate: f32,
+    pub species_susceptibility: HashMap<SpeciesId, f32>,
+    pub npc_susceptibility: f32,
+    pub adjacency_spread: f32,
+    pub trade_spread: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Outbreak {
+    pub disease_id: DiseaseId,
+    pub chunk: ChunkCoord,
+    pub started_tick: u64,
+    pub infected_animals: HashMap<SpeciesId, u32>,
+    pub infected_npcs: Vec<NpcId>,
+    pub immune_npcs: Vec<NpcId>,
+    pub carriers: u32,
+    pub recovered: u32,
+    pub deaths: u32,
+}
+
+/// Per-step tallies produced when an outbreak is advanced.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OutbreakStep {
+    pub new_infections: u32,
+    pub recoveries: u32,
+    pub animal_deaths: HashMap<SpeciesId, u32>,
+    pub npc_deaths: Vec<NpcId>,
+}
+
+/// Scales a host count by a rate, rounding to the nearest whole individual.
+fn scaled(count: u32, rate: f32) -> u32 {
+    (count as f32 * rate.max(0.0)).round() as u32
+}
+
+impl Disease {
+    /// Creates a `Disease` with the given `id` and `name` and moderate default rates.
+    ///
+    /// The disease starts with no susceptible species and does not infect NPCs; use
+    /// `with_species` and `with_npc_susceptibility` to define its hosts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::ecosystem::Disease;
+    ///
+    /// let plague = Disease::new("plague".to_string(), "Red Plague".to_string());
+    /// assert_eq!(plague.infection_rate, 0.3);
+    /// assert!(!plague.affects_npcs());
+    /// ```
+    pub fn new(id: DiseaseId, name: String) -> Self {
+        Self {
+            id,
+            name,
+            infection_rate: 0.3,
+            recovery_rate: 0.1,
+            mortality_rate: 0.05,
+            species_susceptibility: HashMap::new(),
+            npc_susceptibility: 0.0,
+            adjacency_spread: 0.1,
+            trade_spread: 0.05,
+        }
+    }
+
+    /// Sets the per-step infection, recovery and mortality rates, each clamped to 0.0..=1.0.
+    pub fn with_rates(mut self, infection_rate: f32, recovery_rate: f32, mortality_rate: f32) -> Self {
+        self.infection_rate = infection_rate.clamp(0.0, 1.0);
+        self.recovery_rate = recovery_rate.clamp(0.0, 1.0);
+        self.mortality_rate = mortality_rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Marks `species_id` as a host with the given susceptibility (clamped to 0.0..=1.0).
+    pub fn with_species(mut self, species_id: SpeciesId, susceptibility: f32) -> Self {
+        self.species_susceptibility
+            .insert(species_id, susceptibility.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Sets how susceptible NPCs are to the disease; `0.0` means NPCs cannot catch it.
+    pub fn with_npc_susceptibility(mut self, susceptibility: f32) -> Self {
+        self.npc_susceptibility = susceptibility.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the fraction of a chunk's infected hosts that seed adjacent chunks and
+    /// trade route destinations each step.
+    pub fn with_spread(mut self, adjacency_spread: f32, trade_spread: f32) -> Self {
+        self.adjacency_spread = adjacency_spread.clamp(0.0, 1.0);
+        self.trade_spread = trade_spread.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Returns the susceptibility of `species_id`, or `0.0` if the species is not a host.
+    pub fn susceptibility_of(&self, species_id: &SpeciesId) -> f32 {
+        *self.species_susceptibility.get(species_id).unwrap_or(&0.0)
+    }
+
+    /// Reports whether NPCs can contract the disease.
+    pub fn affects_npcs(&self) -> bool {
+        self.npc_susceptibility > 0.0
+    }
+}
+
+impl Outbreak {
+    /// Creates an empty outbreak of `disease_id` in `chunk`, starting at `started_tick`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::ecosystem::Outbreak;
+    /// use entropic_world_core::spatial::ChunkCoord;
+    ///
+    /// let outbreak = Outbreak::new("plague".to_string(), ChunkCoord::new(2, 3), 0)
+    ///     .with_carriers(5);
+    /// assert_eq!(outbreak.total_infected(), 5);
+    /// ```
+    pub fn new(disease_id: DiseaseId, chunk: ChunkCoord, started_tick: u64) -> Self {
+        Self {
+            disease_id,
+            chunk,
+            started_tick,
+            infected_animals: HashMap::new(),
+            infected_npcs: Vec::new(),
+            immune_npcs: Vec::new(),
+            carriers: 0,
+            recovered: 0,
+            deaths: 0,
+        }
+    }
+
+    /// Seeds the outbreak with infected animals of `species_id`.
+    pub fn with_infected_animals(mut self, species_id: SpeciesId, count: u32) -> Self {
+        *self.infected_animals.entry(species_id).or_insert(0) += count;
+        self
+    }
+
+    /// Seeds the outbreak with infected carriers that are not tracked as individual NPCs,
+    /// such as travellers arriving along a trade route.
+    pub fn with_carriers(mut self, count: u32) -> Self {
+        self.carriers += count;
+        self
+    }
+
+    /// Returns the number of infected animals currently in the outbreak.
+    pub fn infected_animal_count(&self) -> u32 {
+        self.infected_animals.values().sum()
+    }
+
+    /// Returns every infected host: animals, tracked NPCs and untracked carriers.
+    pub fn total_infected(&self) -> u32 {
+        self.infected_animal_count() + self.infected_npcs.len() as u32 + self.carriers
+    }
+
+    /// An outbreak is over once it has no infected hosts left.
+    pub fn is_over(&self) -> bool {
+        self.total_infected() == 0
+    }
+
+    /// Advances the outbreak by one step.
+    ///
+    /// `animal_populations` holds the current population of each species in the outbreak's
+    /// chunk and `local_npcs` the living NPCs present there. Infections are drawn
+    /// deterministically: each infected host infects `infection_rate * susceptibility`
+    /// susceptible hosts, and NPCs are infected, killed or cured in id order. The caller
+    /// is responsible for applying the returned deaths to the world.
+    pub fn step(
+        &mut self,
+        disease: &Disease,
+        animal_populations: &HashMap<SpeciesId, u32>,
+        local_npcs: &[NpcId],
+    ) -> OutbreakStep {
+        let mut result = OutbreakStep::default();
+        let pressure = self.total_infected();
+
+        let mut species: Vec<&SpeciesId> = disease.species_susceptibility.keys().collect();
+        species.sort();
+        for species_id in species {
+            let population = *animal_populations.get(species_id).unwrap_or(&0);
+            let infected = self.infected_animals.entry(species_id.clone()).or_insert(0);
+            *infected = (*infected).min(population);
+
+            let susceptible = population - *infected;
+            let rate = disease.infection_rate * disease.susceptibility_of(species_id);
+            let new_cases = scaled(pressure, rate).min(susceptible);
+
+            let deaths = scaled(*infected, disease.mortality_rate);
+            let recoveries = scaled(*infected - deaths, disease.recovery_rate);
+            *infected = *infected - deaths - recoveries + new_cases;
+
+            result.new_infections += new_cases;
+            result.recoveries += recoveries;
+            if deaths > 0 {
+                result.animal_deaths.insert(species_id.clone(), deaths);
+            }
+        }
+        self.infected_animals.retain(|_, count| *count > 0);
+
+        let carrier_recoveries = scaled(self.carriers, disease.recovery_rate).max(1).min(self.carriers);
+        self.carriers -= carrier_recoveries;
+        result.recoveries += carrier_recoveries;
+
+        if disease.affects_npcs() {
+            self.infected_npcs.retain(|id| local_npcs.contains(id));
+            self.infected_npcs.sort();
+
+            let npc_deaths = scaled(self.infected_npcs.len() as u32, disease.mortality_rate) as usize;
+            result.npc_deaths = self.infected_npcs.drain(..npc_deaths).collect();
+
+            let npc_recoveries = scaled(self.infected_npcs.len() as u32, disease.recovery_rate) as usize;
+            let cured: Vec<NpcId> = self.infected_npcs.drain(..npc_recoveries).collect();
+            result.recoveries += cured.len() as u32;
+            self.immune_npcs.extend(cured);
+
+            let mut susceptible: Vec<&NpcId> = local_npcs
+                .iter()
+                .filter(|id| !self.infected_npcs.contains(id) && !self.immune_npcs.contains(id))
+                .filter(|id| !result.npc_deaths.contains(id))
+                .collect();
+            susceptible.sort();
+
+            let rate = disease.infection_rate * disease.npc_susceptibility;
+            let new_cases = (scaled(pressure, rate) as usize).min(susceptible.len());
+            self.infected_npcs
+                .extend(susceptible.into_iter().take(new_cases).cloned());
+            result.new_infections += new_cases as u32;
+        }
+
+        self.recovered += result.recoveries;
+        self.deaths += result.animal_deaths.values().sum::<u32>() + result.npc_deaths.len() as u32;
+        result
+    }
+
+    /// Builds the outbreak that this one seeds in `target` when `spread_rate` of its
+    /// infected hosts carry the disease there, or `None` if too few hosts would travel.
+    ///
+    /// Animals spread species by species; NPC and carrier infections arrive as carriers.
+    pub fn seed(&self, target: ChunkCoord, spread_rate: f32, tick: u64) -> Option<Outbreak> {
+        let mut seeded = Outbreak::new(self.disease_id.clone(), target, tick);
+        for (species_id, count) in &self.infected_animals {
+            let travelling = scaled(*count, spread_rate);
+            if travelling > 0 {
+                seeded.infected_animals.insert(species_id.clone(), travelling);
+            }
+        }
+        seeded.carriers = scaled(self.infected_npcs.len() as u32 + self.carriers, spread_rate);
+
+        if seeded.is_over() {
+            None
+        } else {
+            Some(seeded)
+        }
+    }
+
+    /// Merges the infections carried by `other` into this outbreak.
+    pub fn absorb(&mut self, other: Outbreak) {
+        for (species_id, count) in other.infected_animals {
+            *self.infected_animals.entry(species_id).or_insert(0) += count;
+        }
+        self.carriers += other.carriers;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rabies() -> Disease {
+        Disease::new("rabies".to_string(), "Rabies".to_string())
+            .with_rates(0.5, 0.1, 0.2)
+            .with_species("wolf".to_string(), 1.0)
+            .with_npc_susceptibility(0.5)
+    }
+
+    #[test]
+    fn test_disease_creation() {
+        let disease = rabies();
+        assert_eq!(disease.susceptibility_of(&"wolf".to_string()), 1.0);
+        assert_eq!(disease.susceptibility_of(&"deer".to_string()), 0.0);
+        assert!(disease.affects_npcs());
+    }
+
+    #[test]
+    fn test_outbreak_infects_animals() {
+        let disease = rabies();
+        let mut outbreak = Outbreak::new("rabies".to_string(), ChunkCoord::new(0, 0), 0)
+            .with_infected_animals("wolf".to_string(), 10);
+
+        let mut populations = HashMap::new();
+        populations.insert("wolf".to_string(), 100);
+
+        let step = outbreak.step(&disease, &populations, &[]);
+        assert_eq!(step.new_infections, 5);
+        assert_eq!(step.animal_deaths.get("wolf"), Some(&2));
+        assert_eq!(outbreak.deaths, 2);
+        assert!(outbreak.infected_animal_count() > 10);
+    }
+
+    #[test]
+    fn test_outbreak_infects_and_kills_npcs() {
+        let disease = Disease::new("flu".to_string(), "Flu".to_string())
+            .with_rates(1.0, 0.0, 1.0)
+            .with_npc_susceptibility(1.0);
+        let mut outbreak = Outbreak::new("flu".to_string(), ChunkCoord::new(0, 0), 0)
+            .with_carriers(2);
+        let npcs = vec!["npc_a".to_string(), "npc_b".to_string(), "npc_c".to_string()];
+
+        let step = outbreak.step(&disease, &HashMap::new(), &npcs);
+        assert_eq!(step.new_infections, 2);
+        assert_eq!(outbreak.infected_npcs, vec!["npc_a".to_string(), "npc_b".to_string()]);
+
+        let step = outbreak.step(&disease, &HashMap::new(), &npcs);
+        assert_eq!(step.npc_deaths.len(), 2);
+    }
+
+    #[test]
+    fn test_outbreak_burns_out() {
+        let disease = Disease::new("cold".to_string(), "Cold".to_string()).with_rates(0.0, 0.5, 0.0);
+        let mut outbreak = Outbreak::new("cold".to_string(), ChunkCoord::new(0, 0), 0)
+            .with_carriers(4);
+
+        for _ in 0..10 {
+            outbreak.step(&disease, &HashMap::new(), &[]);
+        }
+        assert!(outbreak.is_over());
+        assert_eq!(outbreak.recovered, 4);
+    }
+
+    #[test]
+    fn test_outbreak_seed() {
+        let outbreak = Outbreak::new("rabies".to_string(), ChunkCoord::new(0, 0), 0)
+            .with_infected_animals("wolf".to_string(), 20);
+
+        let seeded = outbreak.seed(ChunkCoord::new(1, 0), 0.1, 5).unwrap();
+        assert_eq!(seeded.chunk, ChunkCoord::new(1, 0));
+        assert_eq!(seeded.infected_animals.get("wolf"), Some(&2));
+        assert!(outbreak.seed(ChunkCoord::new(1, 0), 0.01, 5).is_none());
+    }
+}
@@ -0,0 +1,235 @@
+use serde::{Deserialize, Serialize};
+use crate::spatial::terrain::Biome;
+
+/// Units of wood produced by clearing a chunk's entire forest cover.
+pub const WOOD_PER_FOREST_DENSITY: f32 = 1000.0;
+
+/// Units of food produced by harvesting a chunk's full crop potential.
+pub const FOOD_PER_CROP_YIELD: f32 = 1000.0;
+
+/// Fraction of forest cover (or crop potential) an average-skill worker harvests at once.
+pub const HARVEST_PER_WORKER: f32 = 0.02;
+
+/// Fraction of a chunk's forage eaten in one step by a herd as large as the biome's full
+/// forage capacity.
+pub const GRAZING_PER_STEP: f32 = 0.1;
+
+/// Per-biome growth parameters for the flora layer.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct FloraProfile {
+    pub max_forest_density: f32,
+    pub forest_regrowth: f32,
+    pub forage_regrowth: f32,
+    pub forage_capacity: u32,
+    pub crop_growth: f32,
+}
+
+/// Vegetation state of a single chunk.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Flora {
+    pub forest_density: f32,
+    pub forage: f32,
+    pub crops: f32,
+}
+
+impl FloraProfile {
+    /// Returns the growth parameters used for chunks of `biome`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::ecosystem::FloraProfile;
+    /// use entropic_world_core::spatial::Biome;
+    ///
+    /// let forest = FloraProfile::for_biome(Biome::Forest);
+    /// let desert = FloraProfile::for_biome(Biome::Desert);
+    /// assert!(forest.max_forest_density > desert.max_forest_density);
+    /// ```
+    pub fn for_biome(biome: Biome) -> Self {
+        let (max_forest_density, forest_regrowth, forage_regrowth, forage_capacity, crop_growth) =
+            match biome {
+                Biome::Forest => (0.9, 0.02, 0.05, 400, 0.01),
+                Biome::Plains | Biome::Custom(_) => (0.2, 0.01, 0.08, 600, 0.03),
+                Biome::Grassland => (0.1, 0.01, 0.1, 800, 0.03),
+                Biome::Swamp => (0.5, 0.015, 0.06, 300, 0.005),
+                Biome::Mountains => (0.3, 0.005, 0.03, 150, 0.005),
+                Biome::Tundra => (0.05, 0.002, 0.02, 100, 0.0),
+                Biome::Desert => (0.0, 0.0, 0.01, 30, 0.002),
+                Biome::Ocean => (0.0, 0.0, 0.0, 0, 0.0),
+            };
+        Self {
+            max_forest_density,
+            forest_regrowth,
+            forage_regrowth,
+            forage_capacity,
+            crop_growth,
+        }
+    }
+}
+
+impl Flora {
+    /// Creates a mature flora state for `biome`: forests at full density, forage fully
+    /// grown, and no crops planted yet.
+    pub fn for_biome(biome: Biome) -> Self {
+        let profile = FloraProfile::for_biome(biome);
+        Self {
+            forest_density: profile.max_forest_density,
+            forage: 1.0,
+            crops: 0.0,
+        }
+    }
+
+    /// Grows the flora by one step.
+    ///
+    /// `growth_modifier` scales every growth rate and is usually the product of the
+    /// current season's and weather's modifiers. Forests regrow logistically toward the
+    /// biome's maximum density, while forage and crops grow linearly up to `1.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::ecosystem::{Flora, FloraProfile};
+    /// use entropic_world_core::spatial::Biome;
+    ///
+    /// let profile = FloraProfile::for_biome(Biome::Plains);
+    /// let mut flora = Flora::for_biome(Biome::Plains);
+    /// flora.grow(&profile, 1.0);
+    /// assert!(flora.crops > 0.0);
+    /// ```
+    pub fn grow(&mut self, profile: &FloraProfile, growth_modifier: f32) {
+        let modifier = growth_modifier.max(0.0);
+
+        if profile.max_forest_density > 0.0 {
+            let headroom = 1.0 - self.forest_density / profile.max_forest_density;
+            self.forest_density += profile.forest_regrowth * modifier * headroom.max(0.0);
+            self.forest_density = self.forest_density.clamp(0.0, profile.max_forest_density);
+        }
+        self.forage = (self.forage + profile.forage_regrowth * modifier).clamp(0.0, 1.0);
+        self.crops = (self.crops + profile.crop_growth * modifier).clamp(0.0, 1.0);
+    }
+
+    /// Removes up to `amount` of forest cover and returns the units of wood produced.
+    pub fn harvest_timber(&mut self, amount: f32) -> u32 {
+        let taken = amount.clamp(0.0, self.forest_density);
+        self.forest_density -= taken;
+        (taken * WOOD_PER_FOREST_DENSITY).round() as u32
+    }
+
+    /// Removes up to `amount` of grown crops and returns the units of food produced.
+    pub fn harvest_crops(&mut self, amount: f32) -> u32 {
+        let taken = amount.clamp(0.0, self.crops);
+        self.crops -= taken;
+        (taken * FOOD_PER_CROP_YIELD).round() as u32
+    }
+
+    /// Lets a herd of `herbivores` animals graze the forage for one step.
+    ///
+    /// The herd eats in proportion to its size, up to `GRAZING_PER_STEP` for a herd as
+    /// large as the biome's full forage capacity; animals beyond that find nothing to eat.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::ecosystem::{Flora, FloraProfile};
+    /// use entropic_world_core::spatial::Biome;
+    ///
+    /// let profile = FloraProfile::for_biome(Biome::Grassland);
+    /// let mut flora = Flora::for_biome(Biome::Grassland);
+    /// flora.graze(&profile, 400);
+    /// assert_eq!(flora.herbivore_capacity(&profile), 760);
+    /// ```
+    pub fn graze(&mut self, profile: &FloraProfile, herbivores: u32) {
+        if profile.forage_capacity == 0 {
+            return;
+        }
+        let share = (herbivores as f32 / profile.forage_capacity as f32).min(1.0);
+        self.forage = (self.forage - GRAZING_PER_STEP * share).max(0.0);
+    }
+
+    /// Number of herbivores the chunk's current forage can sustain.
+    pub fn herbivore_capacity(&self, profile: &FloraProfile) -> u32 {
+        (profile.forage_capacity as f32 * self.forage).round() as u32
+    }
+}
+
+impl Default for Flora {
+    /// Creates the flora of the default biome.
+    fn default() -> Self {
+        Self::for_biome(Biome::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flora_for_biome() {
+        let flora = Flora::for_biome(Biome::Forest);
+        assert_eq!(flora.forest_density, 0.9);
+        assert_eq!(flora.forage, 1.0);
+        assert_eq!(flora.crops, 0.0);
+    }
+
+    #[test]
+    fn test_forest_regrowth() {
+        let profile = FloraProfile::for_biome(Biome::Forest);
+        let mut flora = Flora::for_biome(Biome::Forest);
+
+        let wood = flora.harvest_timber(0.5);
+        assert_eq!(wood, 500);
+
+        let before = flora.forest_density;
+        flora.grow(&profile, 1.0);
+        assert!(flora.forest_density > before);
+        assert!(flora.forest_density <= profile.max_forest_density);
+    }
+
+    #[test]
+    fn test_growth_modifier() {
+        let profile = FloraProfile::for_biome(Biome::Plains);
+        let mut summer = Flora::for_biome(Biome::Plains);
+        let mut winter = Flora::for_biome(Biome::Plains);
+
+        summer.grow(&profile, 1.0);
+        winter.grow(&profile, 0.1);
+        assert!(summer.crops > winter.crops);
+    }
+
+    #[test]
+    fn test_crop_harvest() {
+        let profile = FloraProfile::for_biome(Biome::Grassland);
+        let mut flora = Flora::for_biome(Biome::Grassland);
+        for _ in 0..10 {
+            flora.grow(&profile, 1.0);
+        }
+
+        let food = flora.harvest_crops(1.0);
+        assert_eq!(food, 300);
+        assert_eq!(flora.crops, 0.0);
+    }
+
+    #[test]
+    fn test_herbivore_capacity() {
+        let profile = FloraProfile::for_biome(Biome::Grassland);
+        let mut flora = Flora::for_biome(Biome::Grassland);
+        assert_eq!(flora.herbivore_capacity(&profile), 800);
+
+        flora.forage = 0.5;
+        assert_eq!(flora.herbivore_capacity(&profile), 400);
+    }
+
+    #[test]
+    fn test_grazing_and_regrowth() {
+        let profile = FloraProfile::for_biome(Biome::Grassland);
+        let mut flora = Flora::for_biome(Biome::Grassland);
+
+        flora.graze(&profile, 0);
+        assert_eq!(flora.forage, 1.0);
+        flora.graze(&profile, 5000);
+        assert_eq!(flora.herbivore_capacity(&profile), 720);
+
+        flora.grow(&profile, 1.0);
+        assert_eq!(flora.herbivore_capacity(&profile), 800);
+    }
+}
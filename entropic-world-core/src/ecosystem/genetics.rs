@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+
+/// Fraction of a trait's variance that is passed on to the next generation.
+pub const DEFAULT_HERITABILITY: f32 = 0.5;
+
+/// Variance reintroduced by mutation every generation, keeping selection from collapsing
+/// a distribution to a single value.
+pub const MUTATION_VARIANCE: f32 = 0.001;
+
+/// Temperature deviation (in degrees Celsius) treated as maximal climate stress.
+pub const CLIMATE_STRESS_RANGE: f32 = 20.0;
+
+/// Normal distribution of a trait across a population, expressed relative to the
+/// species baseline (a mean of `1.0` is the ancestral value).
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TraitDistribution {
+    pub mean: f32,
+    pub variance: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct HeritableTraits {
+    pub size: TraitDistribution,
+    pub speed: TraitDistribution,
+    pub resilience: TraitDistribution,
+    pub heritability: f32,
+    pub generation: u32,
+}
+
+/// Environmental forces acting on a population for one generation.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct SelectionPressure {
+    /// Strength of predation, from `0.0` (none) to `1.0` (overwhelming).
+    pub predation: f32,
+    /// Deviation of the experienced temperature from the species' comfort temperature,
+    /// in degrees Celsius; negative values are colder.
+    pub climate: f32,
+}
+
+impl TraitDistribution {
+    /// Creates a distribution with the given mean and variance.
+    pub fn new(mean: f32, variance: f32) -> Self {
+        Self {
+            mean: mean.max(0.0),
+            variance: variance.max(0.0),
+        }
+    }
+
+    /// Standard deviation of the distribution.
+    pub fn std_dev(&self) -> f32 {
+        self.variance.sqrt()
+    }
+
+    /// Shifts the distribution along a selection `gradient` using the breeder's equation.
+    ///
+    /// The mean moves by `heritability * variance * gradient`; the variance shrinks in
+    /// proportion to the strength of selection and is replenished by mutation.
+    pub fn select(&mut self, gradient: f32, heritability: f32) {
+        self.mean = (self.mean + heritability * self.variance * gradient).max(0.0);
+        let narrowing = (gradient.abs() * 0.05).min(0.5);
+        self.variance = self.variance * (1.0 - narrowing) + MUTATION_VARIANCE;
+    }
+}
+
+impl Default for TraitDistribution {
+    /// The ancestral distribution: mean `1.0` with a modest spread.
+    fn default() -> Self {
+        Self::new(1.0, 0.01)
+    }
+}
+
+impl HeritableTraits {
+    /// Creates ancestral traits at generation zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::ecosystem::HeritableTraits;
+    ///
+    /// let traits = HeritableTraits::new();
+    /// assert_eq!(traits.size.mean, 1.0);
+    /// assert_eq!(traits.generation, 0);
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            size: TraitDistribution::default(),
+            speed: TraitDistribution::default(),
+            resilience: TraitDistribution::default(),
+            heritability: DEFAULT_HERITABILITY,
+            generation: 0,
+        }
+    }
+
+    /// Advances the population one generation under `pressure`.
+    ///
+    /// Predation selects for speed and, more weakly, for size. Cold climates select for
+    /// larger bodies and warm ones for smaller bodies, and any climate stress selects for
+    /// resilience.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::ecosystem::{HeritableTraits, SelectionPressure};
+    ///
+    /// let mut traits = HeritableTraits::new();
+    /// traits.evolve(&SelectionPressure { predation: 1.0, climate: 0.0 });
+    /// assert!(traits.speed.mean > 1.0);
+    /// assert_eq!(traits.generation, 1);
+    /// ```
+    pub fn evolve(&mut self, pressure: &SelectionPressure) {
+        let predation = pressure.predation.clamp(0.0, 1.0);
+        let climate = (pressure.climate / CLIMATE_STRESS_RANGE).clamp(-1.0, 1.0);
+
+        self.speed.select(predation, self.heritability);
+        self.size.select(0.5 * predation - climate, self.heritability);
+        self.resilience.select(climate.abs(), self.heritability);
+        self.generation += 1;
+    }
+}
+
+impl Default for HeritableTraits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_pressure_keeps_means() {
+        let mut traits = HeritableTraits::new();
+        traits.evolve(&SelectionPressure::default());
+
+        assert_eq!(traits.size.mean, 1.0);
+        assert_eq!(traits.speed.mean, 1.0);
+        assert_eq!(traits.resilience.mean, 1.0);
+        assert!(traits.size.variance > 0.01);
+    }
+
+    #[test]
+    fn test_predation_selects_speed() {
+        let mut traits = HeritableTraits::new();
+        for _ in 0..10 {
+            traits.evolve(&SelectionPressure { predation: 1.0, climate: 0.0 });
+        }
+
+        assert!(traits.speed.mean > traits.size.mean);
+        assert!(traits.size.mean > 1.0);
+        assert_eq!(traits.generation, 10);
+    }
+
+    #[test]
+    fn test_climate_selects_size_and_resilience() {
+        let mut cold = HeritableTraits::new();
+        let mut hot = HeritableTraits::new();
+        for _ in 0..10 {
+            cold.evolve(&SelectionPressure { predation: 0.0, climate: -20.0 });
+            hot.evolve(&SelectionPressure { predation: 0.0, climate: 20.0 });
+        }
+
+        assert!(cold.size.mean > 1.0);
+        assert!(hot.size.mean < 1.0);
+        assert!(cold.resilience.mean > 1.0);
+        assert_eq!(cold.resilience.mean, hot.resilience.mean);
+    }
+
+    #[test]
+    fn test_traits_serialization() {
+        let mut traits = HeritableTraits::new();
+        traits.evolve(&SelectionPressure { predation: 0.5, climate: 5.0 });
+
+        let json = serde_json::to_string(&traits).unwrap();
+        let restored: HeritableTraits = serde_json::from_str(&json).unwrap();
+        assert_eq!(traits, restored);
+    }
+}
@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+use crate::ecosystem::species::SpeciesId;
+use crate::economy::resource::ResourceType;
+use crate::population::npc::Occupation;
+
+/// Fraction of a species' base population below which it is considered overhunted.
+pub const DEFAULT_SCARCITY_THRESHOLD: f32 = 0.25;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HarvestRule {
+    pub species_id: SpeciesId,
+    pub occupation: Occupation,
+    pub catch_per_worker: f32,
+    pub resource: ResourceType,
+    pub yield_per_animal: u32,
+    pub scarcity_threshold: f32,
+}
+
+impl HarvestRule {
+    /// Creates a rule letting NPCs with `occupation` harvest `species_id` into `resource`.
+    ///
+    /// Each worker catches one animal per harvest at average skill, each animal yields one
+    /// unit of `resource`, and the species is considered scarce below
+    /// `DEFAULT_SCARCITY_THRESHOLD` of its base population.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::ecosystem::HarvestRule;
+    /// use entropic_world_core::economy::ResourceType;
+    /// use entropic_world_core::population::Occupation;
+    ///
+    /// let rule = HarvestRule::new("deer".into(), Occupation::Hunter, ResourceType::Food)
+    ///     .with_yield(4);
+    /// assert_eq!(rule.catch_for(0.5, 100, 100), 1);
+    /// ```
+    pub fn new(species_id: SpeciesId, occupation: Occupation, resource: ResourceType) -> Self {
+        Self {
+            species_id,
+            occupation,
+            catch_per_worker: 1.0,
+            resource,
+            yield_per_animal: 1,
+            scarcity_threshold: DEFAULT_SCARCITY_THRESHOLD,
+        }
+    }
+
+    /// Sets how many animals an average-skill worker catches per harvest.
+    pub fn with_catch_rate(mut self, catch_per_worker: f32) -> Self {
+        self.catch_per_worker = catch_per_worker.max(0.0);
+        self
+    }
+
+    /// Sets how many units of the rule's resource each caught animal yields.
+    pub fn with_yield(mut self, yield_per_animal: u32) -> Self {
+        self.yield_per_animal = yield_per_animal;
+        self
+    }
+
+    /// Sets the fraction of base population below which the species counts as overhunted.
+    pub fn with_scarcity_threshold(mut self, threshold: f32) -> Self {
+        self.scarcity_threshold = threshold.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Reports whether `population` has dropped below the scarcity threshold.
+    pub fn is_scarce(&self, population: u32, base_population: u32) -> bool {
+        (population as f32) < base_population as f32 * self.scarcity_threshold
+    }
+
+    /// Number of animals a worker with `skill` would catch if game were plentiful.
+    pub fn expected_catch(&self, skill: f32) -> u32 {
+        (self.catch_per_worker * (0.5 + skill.clamp(0.0, 1.0))).round() as u32
+    }
+
+    /// Number of animals a worker with `skill` actually catches from `population`.
+    ///
+    /// When the species is scarce the catch is scaled down in proportion to how far the
+    /// population has fallen below the threshold, and it never exceeds `population`.
+    pub fn catch_for(&self, skill: f32, population: u32, base_population: u32) -> u32 {
+        let mut catch = self.expected_catch(skill) as f32;
+        if self.is_scarce(population, base_population) {
+            let floor = base_population as f32 * self.scarcity_threshold;
+            catch *= population as f32 / floor;
+        }
+        (catch.round() as u32).min(population)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_harvest_rule_creation() {
+        let rule = HarvestRule::new("trout".to_string(), Occupation::Fisher, ResourceType::Food)
+            .with_catch_rate(3.0)
+            .with_yield(2);
+        assert_eq!(rule.occupation, Occupation::Fisher);
+        assert_eq!(rule.expected_catch(0.5), 3);
+        assert_eq!(rule.yield_per_animal, 2);
+    }
+
+    #[test]
+    fn test_catch_limited_by_population() {
+        let rule = HarvestRule::new("deer".to_string(), Occupation::Hunter, ResourceType::Food)
+            .with_catch_rate(10.0)
+            .with_scarcity_threshold(0.0);
+        assert_eq!(rule.catch_for(1.0, 4, 100), 4);
+    }
+
+    #[test]
+    fn test_scarcity_reduces_catch() {
+        let rule = HarvestRule::new("deer".to_string(), Occupation::Hunter, ResourceType::Food)
+            .with_catch_rate(4.0);
+        assert!(!rule.is_scarce(50, 100));
+        assert!(rule.is_scarce(10, 100));
+        assert_eq!(rule.catch_for(0.5, 50, 100), 4);
+        assert!(rule.catch_for(0.5, 10, 100) < 4);
+    }
+}
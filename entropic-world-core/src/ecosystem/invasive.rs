@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+use crate::ecosystem::species::{Species, SpeciesId};
+use crate::spatial::coordinates::ChunkCoord;
+
+/// Propagation rules for a species introduced into the world by a scripted event.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Invasion {
+    pub species: Species,
+    pub initial_population: u32,
+    pub growth_rate: f32,
+    pub max_population: u32,
+    pub competitors: Vec<SpeciesId>,
+    pub displacement_rate: f32,
+    #[serde(default)]
+    pub origin: Option<ChunkCoord>,
+}
+
+impl Invasion {
+    /// Creates an invasion that introduces `initial_population` individuals of `species`.
+    ///
+    /// By default the invader grows 20% per step up to ten times its initial population
+    /// and displaces no native species.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::ecosystem::{Diet, Invasion, Species};
+    ///
+    /// let toad = Species::new("cane_toad".into(), "Cane Toad".into(), Diet::Carnivore);
+    /// let invasion = Invasion::new(toad, 50)
+    ///     .with_competitor("frog".into())
+    ///     .with_displacement_rate(0.1);
+    /// assert_eq!(invasion.grow(50), 60);
+    /// assert_eq!(invasion.displacement(60), 6);
+    /// ```
+    pub fn new(species: Species, initial_population: u32) -> Self {
+        Self {
+            species,
+            initial_population,
+            growth_rate: 0.2,
+            max_population: initial_population.saturating_mul(10),
+            competitors: Vec::new(),
+            displacement_rate: 0.0,
+            origin: None,
+        }
+    }
+
+    /// Sets the per-step growth rate and the population ceiling of the invader.
+    pub fn with_growth(mut self, growth_rate: f32, max_population: u32) -> Self {
+        self.growth_rate = growth_rate.max(0.0);
+        self.max_population = max_population;
+        self
+    }
+
+    /// Adds a native species that loses individuals to the invader.
+    pub fn with_competitor(mut self, species_id: SpeciesId) -> Self {
+        if !self.competitors.contains(&species_id) {
+            self.competitors.push(species_id);
+        }
+        self
+    }
+
+    /// Sets how many competitor individuals each invader displaces per step.
+    pub fn with_displacement_rate(mut self, displacement_rate: f32) -> Self {
+        self.displacement_rate = displacement_rate.max(0.0);
+        self
+    }
+
+    /// Releases the whole initial population into a single chunk instead of spreading it
+    /// over the species' habitat.
+    pub fn with_origin(mut self, origin: ChunkCoord) -> Self {
+        self.origin = Some(origin);
+        self
+    }
+
+    /// Returns the id of the invading species.
+    pub fn species_id(&self) -> &SpeciesId {
+        &self.species.id
+    }
+
+    /// Returns the invader's population after one step of growth from `population`.
+    pub fn grow(&self, population: u32) -> u32 {
+        let grown = (population as f32 * (1.0 + self.growth_rate)).round() as u32;
+        grown.min(self.max_population).max(population.min(self.max_population))
+    }
+
+    /// Returns how many individuals of each competitor `population` invaders displace per
+    /// step. Displacement only affects competitors living in the same chunk.
+    pub fn displacement(&self, population: u32) -> u32 {
+        (population as f32 * self.displacement_rate).round() as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecosystem::species::Diet;
+
+    fn rabbits() -> Invasion {
+        let species = Species::new("rabbit".to_string(), "Rabbit".to_string(), Diet::Herbivore);
+        Invasion::new(species, 100)
+    }
+
+    #[test]
+    fn test_invasion_growth_capped() {
+        let invasion = rabbits().with_growth(1.0, 300);
+        assert_eq!(invasion.grow(100), 200);
+        assert_eq!(invasion.grow(200), 300);
+        assert_eq!(invasion.grow(300), 300);
+    }
+
+    #[test]
+    fn test_invasion_competitors() {
+        let invasion = rabbits()
+            .with_competitor("hare".to_string())
+            .with_competitor("hare".to_string())
+            .with_displacement_rate(0.5);
+        assert_eq!(invasion.competitors.len(), 1);
+        assert_eq!(invasion.displacement(100), 50);
+        assert_eq!(invasion.species_id(), "rabbit");
+    }
+
+    #[test]
+    fn test_invasion_origin() {
+        let invasion = rabbits().with_origin(ChunkCoord::new(2, 3));
+        assert_eq!(invasion.origin, Some(ChunkCoord::new(2, 3)));
+        assert_eq!(rabbits().origin, None);
+    }
+}
@@ -1,7 +1,19 @@
+pub mod animal_population;
+pub mod disease;
+pub mod flora;
 pub mod food_chain;
+pub mod genetics;
+pub mod hunting;
+pub mod invasive;
 pub mod population_control;
 pub mod species;
 
+pub use animal_population::AnimalPopulations;
+pub use disease::{Disease, DiseaseId, Outbreak, OutbreakStep};
+pub use flora::{Flora, FloraProfile};
 pub use food_chain::FoodChain;
+pub use genetics::{HeritableTraits, SelectionPressure, TraitDistribution};
+pub use hunting::HarvestRule;
+pub use invasive::Invasion;
 pub use population_control::PopulationControl;
 pub use species::{Diet, Species, SpeciesId};
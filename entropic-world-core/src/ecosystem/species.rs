@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use crate::spatial::terrain::Biome;
+use crate::ecosystem::genetics::HeritableTraits;
 
 pub type SpeciesId = String;
 
@@ -14,6 +15,8 @@ pub struct Species {
     pub preferred_biomes: Vec<Biome>,
     pub hunting_prey: Vec<SpeciesId>,
     pub hunted_by: Vec<SpeciesId>,
+    #[serde(default)]
+    pub traits: HeritableTraits,
 }
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -54,6 +57,7 @@ impl Species {
             preferred_biomes: Vec::new(),
             hunting_prey: Vec::new(),
             hunted_by: Vec::new(),
+            traits: HeritableTraits::new(),
         }
     }
 
@@ -34,6 +34,15 @@ pub enum WorldError {
 
     #[error("Spatial index error: {0}")]
     SpatialIndexError(String),
+
+    #[error("Script error: {0}")]
+    ScriptError(String),
+
+    #[error("Replication error: {0}")]
+    ReplicationError(String),
+
+    #[error("Replay diverged at tick {tick}: state hash {actual:016x}, expected {expected:016x}")]
+    ReplayDivergence { tick: u64, expected: u64, actual: u64 },
 }
 
 pub type Result<T> = std::result::Result<T, WorldError>;
@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use crate::events::event::EventType;
+use crate::population::faction::FactionId;
+use crate::spatial::coordinates::ChunkCoord;
+use crate::spatial::region::RegionCoord;
+use crate::world::World;
+
+/// Event counts of one window of the timeline, keyed by event type name.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TimelineBucket {
+    pub start_tick: u64,
+    pub counts: BTreeMap<String, u64>,
+}
+
+/// Summary of a world's event history for balancing dashboards and in-game chronicles.
+///
+/// Counts and the timeline include events folded into compaction summaries; hotspots and
+/// faction involvement only cover retained events, since summaries keep no locations or
+/// participants. Hotspot and faction rankings are sorted by descending count.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EventAnalytics {
+    pub bucket_ticks: u64,
+    pub total: u64,
+    pub counts: BTreeMap<String, u64>,
+    pub timeline: Vec<TimelineBucket>,
+    pub chunk_hotspots: Vec<(ChunkCoord, u64)>,
+    pub region_hotspots: Vec<(RegionCoord, u64)>,
+    pub faction_involvement: Vec<(FactionId, u64)>,
+}
+
+impl EventAnalytics {
+    /// Analyzes the event history of `world`, grouping the timeline into windows of
+    /// `bucket_ticks` ticks and hotspots into regions of `region_size` chunks per side.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::World;
+    /// use entropic_world_core::events::{EventAnalytics, EventType, WorldEvent};
+    ///
+    /// let mut world = World::new("W".into(), "dna".into(), 4, 4);
+    /// for tick in 1..=3 {
+    ///     let id = format!("raid_{}", tick);
+    ///     let raid = WorldEvent::new(id, EventType::FactionWar, world.current_time, (300.0, 10.0), "Raid".into());
+    ///     world.event_queue.schedule(tick, raid);
+    /// }
+    /// for _ in 0..3 {
+    ///     world.advance_tick();
+    /// }
+    ///
+    /// let analytics = EventAnalytics::from_world(&world, 10, 2);
+    /// assert_eq!(analytics.count(&EventType::FactionWar), 3);
+    /// assert_eq!(analytics.hotspots(1)[0].0.x, 1);
+    /// ```
+    pub fn from_world(world: &World, bucket_ticks: u64, region_size: u32) -> Self {
+        let bucket_ticks = bucket_ticks.max(1);
+        let region_size = region_size.max(1);
+        let history = &world.event_history;
+
+        let mut counts: BTreeMap<String, u64> = BTreeMap::new();
+        let mut timeline: BTreeMap<u64, BTreeMap<String, u64>> = BTreeMap::new();
+        let mut chunks: HashMap<ChunkCoord, u64> = HashMap::new();
+        let mut regions: HashMap<RegionCoord, u64> = HashMap::new();
+        let mut factions: HashMap<FactionId, u64> = HashMap::new();
+
+        for summary in history.summaries() {
+            let name = summary.event_type.name().to_string();
+            *counts.entry(name.clone()).or_default() += summary.count;
+            let bucket = summary.first_tick - summary.first_tick % bucket_ticks;
+            *timeline.entry(bucket).or_default().entry(name).or_default() += summary.count;
+        }
+
+        for (tick, event) in history.iter_with_ticks() {
+            let name = event.event_type.name().to_string();
+            *counts.entry(name.clone()).or_default() += 1;
+            let bucket = tick - tick % bucket_ticks;
+            *timeline.entry(bucket).or_default().entry(name).or_default() += 1;
+
+            let chunk = ChunkCoord::from_world_position(event.location.0, event.location.1);
+            *chunks.entry(chunk).or_default() += 1;
+            *regions
+                .entry(RegionCoord::from_chunk_coord(&chunk, region_size))
+                .or_default() += 1;
+
+            for entity in &event.involved_entities {
+                if world.factions.contains_key(entity) {
+                    *factions.entry(entity.clone()).or_default() += 1;
+                }
+            }
+        }
+
+        let mut chunk_hotspots: Vec<(ChunkCoord, u64)> = chunks.into_iter().collect();
+        chunk_hotspots.sort_by_key(|(coord, count)| (Reverse(*count), coord.x, coord.y));
+        let mut region_hotspots: Vec<(RegionCoord, u64)> = regions.into_iter().collect();
+        region_hotspots.sort_by_key(|(coord, count)| (Reverse(*count), coord.x, coord.y));
+        let mut faction_involvement: Vec<(FactionId, u64)> = factions.into_iter().collect();
+        faction_involvement.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then_with(|| a.cmp(b)));
+
+        Self {
+            bucket_ticks,
+            total: counts.values().sum(),
+            counts,
+            timeline: timeline
+                .into_iter()
+                .map(|(start_tick, counts)| TimelineBucket { start_tick, counts })
+                .collect(),
+            chunk_hotspots,
+            region_hotspots,
+            faction_involvement,
+        }
+    }
+
+    /// Number of events of `event_type`.
+    pub fn count(&self, event_type: &EventType) -> u64 {
+        self.counts.get(event_type.name()).copied().unwrap_or(0)
+    }
+
+    /// Events of `event_type` per timeline window, as `(start_tick, count)` pairs covering
+    /// only the windows in which the type occurred.
+    pub fn series(&self, event_type: &EventType) -> Vec<(u64, u64)> {
+        self.timeline
+            .iter()
+            .filter_map(|bucket| {
+                let count = bucket.counts.get(event_type.name())?;
+                Some((bucket.start_tick, *count))
+            })
+            .collect()
+    }
+
+    /// The `n` chunks with the most events.
+    pub fn hotspots(&self, n: usize) -> &[(ChunkCoord, u64)] {
+        &self.chunk_hotspots[..n.min(self.chunk_hotspots.len())]
+    }
+
+    /// The `n` regions with the most events.
+    pub fn region_hotspots(&self, n: usize) -> &[(RegionCoord, u64)] {
+        &self.region_hotspots[..n.min(self.region_hotspots.len())]
+    }
+
+    /// The `n` factions involved in the most events.
+    pub fn top_factions(&self, n: usize) -> &[(FactionId, u64)] {
+        &self.faction_involvement[..n.min(self.faction_involvement.len())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::event::WorldEvent;
+    use crate::events::CompactionPolicy;
+    use crate::population::Faction;
+
+    fn event(id: &str, event_type: EventType, location: (f32, f32), entities: &[&str]) -> WorldEvent {
+        WorldEvent::new(
+            id.to_string(),
+            event_type,
+            Default::default(),
+            location,
+            String::new(),
+        )
+        .with_entities(entities.iter().map(|e| e.to_string()).collect())
+    }
+
+    fn world() -> World {
+        let mut world = World::new("W".to_string(), "dna".to_string(), 8, 8);
+        for id in ["north", "south"] {
+            let faction = Faction::new(id.to_string(), id.to_string(), "leader".to_string());
+            world.factions.insert(id.to_string(), faction);
+        }
+        world
+    }
+
+    #[test]
+    fn test_counts_timeline_and_hotspots() {
+        let mut world = world();
+        let history = &mut world.event_history;
+        history.record(1, event("war_1", EventType::FactionWar, (10.0, 10.0), &["north", "south"]));
+        history.record(4, event("war_2", EventType::FactionWar, (10.0, 10.0), &["north", "npc_1"]));
+        history.record(12, event("quake", EventType::Disaster, (600.0, 10.0), &[]));
+        history.record(13, event("raid", EventType::Custom("raid".to_string()), (1100.0, 10.0), &["north"]));
+
+        let analytics = EventAnalytics::from_world(&world, 10, 2);
+        assert_eq!(analytics.total, 4);
+        assert_eq!(analytics.count(&EventType::FactionWar), 2);
+        assert_eq!(analytics.count(&EventType::Custom("raid".to_string())), 1);
+        assert_eq!(analytics.series(&EventType::FactionWar), vec![(0, 2)]);
+        assert_eq!(analytics.timeline.len(), 2);
+
+        assert_eq!(analytics.hotspots(1), &[(ChunkCoord::new(0, 0), 2)]);
+        assert_eq!(analytics.hotspots(10).len(), 3);
+        // Chunks (0, 0) and (2, 0) fall into regions (0, 0) and (1, 0); chunk (4, 0) into (2, 0).
+        assert_eq!(analytics.region_hotspots(1), &[(RegionCoord::new(0, 0), 2)]);
+        assert_eq!(
+            analytics.top_factions(5),
+            &[("north".to_string(), 3), ("south".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_includes_compacted_events() {
+        let mut world = world();
+        world.event_history.set_compaction(Some(CompactionPolicy::new(0, 5)));
+        for tick in 0..7 {
+            world
+                .event_history
+                .record(tick, event(&format!("birth_{}", tick), EventType::NPCBirth, (0.0, 0.0), &[]));
+        }
+        world.event_history.compact(7);
+
+        let analytics = EventAnalytics::from_world(&world, 5, 4);
+        assert_eq!(analytics.count(&EventType::NPCBirth), 7);
+        assert_eq!(analytics.series(&EventType::NPCBirth), vec![(0, 5), (5, 2)]);
+        assert_eq!(analytics.hotspots(1)[0].1, 2);
+
+        let json = serde_json::to_string(&analytics).unwrap();
+        let restored: EventAnalytics = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.timeline, analytics.timeline);
+    }
+}
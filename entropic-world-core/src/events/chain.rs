@@ -0,0 +1,372 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::events::event::{EventSource, EventType, WorldEvent};
+use crate::events::triggers::TriggerCondition;
+use crate::temporal::time::WorldTime;
+
+/// Maximum number of steps a single chain instance may take, guarding against chains
+/// whose branches loop forever.
+pub const MAX_CHAIN_STEPS: usize = 64;
+
+/// Transition from one chain step to the next once the step's event has resolved.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChainBranch {
+    pub next_step: String,
+    pub delay: u64,
+    /// Outcome a handler must report for the branch to apply; `None` matches any outcome.
+    #[serde(default)]
+    pub outcome: Option<String>,
+    /// World condition that must hold for the branch to apply; `None` always holds.
+    #[serde(default)]
+    pub condition: Option<TriggerCondition>,
+}
+
+/// One event of a chain and the branches taken after it resolves.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChainStep {
+    pub id: String,
+    pub event_type: EventType,
+    pub description: String,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub branches: Vec<ChainBranch>,
+}
+
+/// Data-defined storyline: a graph of steps whose events enqueue follow-ups as they
+/// resolve.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EventChain {
+    pub id: String,
+    pub start_step: String,
+    pub start_delay: u64,
+    pub steps: Vec<ChainStep>,
+}
+
+/// A running chain waiting for the event of its current step to resolve.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ChainInstance {
+    pub id: String,
+    pub chain_id: String,
+    pub location: (f32, f32),
+    pub current_step: String,
+    pub pending_event: String,
+    pub visited: Vec<String>,
+}
+
+/// Registry of chain definitions and the instances currently running.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EventChains {
+    definitions: HashMap<String, EventChain>,
+    active: Vec<ChainInstance>,
+    next_instance: u64,
+}
+
+impl ChainBranch {
+    /// Creates an unconditional branch to `next_step`, fired `delay` ticks after the
+    /// current step resolves. Delays under one tick are treated as one tick.
+    pub fn new(next_step: impl Into<String>, delay: u64) -> Self {
+        Self {
+            next_step: next_step.into(),
+            delay,
+            outcome: None,
+            condition: None,
+        }
+    }
+
+    /// Restricts the branch to resolutions reporting `outcome`.
+    pub fn on_outcome(mut self, outcome: impl Into<String>) -> Self {
+        self.outcome = Some(outcome.into());
+        self
+    }
+
+    /// Restricts the branch to world states satisfying `condition`.
+    pub fn when(mut self, condition: TriggerCondition) -> Self {
+        self.condition = Some(condition);
+        self
+    }
+}
+
+impl ChainStep {
+    /// Creates a step producing an event of `event_type` with no follow-ups.
+    pub fn new(id: impl Into<String>, event_type: EventType, description: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            event_type,
+            description: description.into(),
+            priority: 0,
+            branches: Vec::new(),
+        }
+    }
+
+    /// Adds a branch; branches are tried in the order they were added.
+    pub fn with_branch(mut self, branch: ChainBranch) -> Self {
+        self.branches.push(branch);
+        self
+    }
+}
+
+impl EventChain {
+    /// Creates a chain starting at `start_step`, `start_delay` ticks after it is started.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::events::{ChainBranch, ChainStep, EventChain, EventType};
+    ///
+    /// let chain = EventChain::new("bandits", "raid", 1)
+    ///     .with_step(
+    ///         ChainStep::new("raid", EventType::Custom("raid".into()), "Bandits raid the village")
+    ///             .with_branch(ChainBranch::new("reprisal", 10).on_outcome("repelled")),
+    ///     )
+    ///     .with_step(ChainStep::new("reprisal", EventType::FactionWar, "The bandits strike back"));
+    /// assert!(chain.step("reprisal").is_some());
+    /// ```
+    pub fn new(id: impl Into<String>, start_step: impl Into<String>, start_delay: u64) -> Self {
+        Self {
+            id: id.into(),
+            start_step: start_step.into(),
+            start_delay,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Adds a step to the chain.
+    pub fn with_step(mut self, step: ChainStep) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Returns the step with the given id.
+    pub fn step(&self, step_id: &str) -> Option<&ChainStep> {
+        self.steps.iter().find(|step| step.id == step_id)
+    }
+}
+
+impl EventChains {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a chain definition, replacing any definition with the same id.
+    pub fn register(&mut self, chain: EventChain) {
+        self.definitions.insert(chain.id.clone(), chain);
+    }
+
+    /// Returns the chain definition with the given id.
+    pub fn get(&self, chain_id: &str) -> Option<&EventChain> {
+        self.definitions.get(chain_id)
+    }
+
+    /// Running chain instances, in the order they were started.
+    pub fn active(&self) -> &[ChainInstance] {
+        &self.active
+    }
+
+    /// Reports whether `event_id` is the pending event of a running chain.
+    pub fn is_pending(&self, event_id: &str) -> bool {
+        self.active.iter().any(|instance| instance.pending_event == event_id)
+    }
+
+    /// Starts an instance of `chain_id` at `location`.
+    ///
+    /// Returns the instance id together with the tick and event of its first step, which
+    /// the caller must enqueue, or `None` if the chain or its start step is unknown.
+    pub fn start(
+        &mut self,
+        chain_id: &str,
+        location: (f32, f32),
+        tick: u64,
+        time: WorldTime,
+    ) -> Option<(String, u64, WorldEvent)> {
+        let chain = self.definitions.get(chain_id)?;
+        let step = chain.step(&chain.start_step)?;
+
+        let mut instance = ChainInstance {
+            id: format!("{}_{}", chain.id, self.next_instance),
+            chain_id: chain.id.clone(),
+            location,
+            current_step: step.id.clone(),
+            pending_event: String::new(),
+            visited: Vec::new(),
+        };
+        let event = step_event(&mut instance, step, time);
+        let fire_tick = tick + chain.start_delay.max(1);
+
+        self.next_instance += 1;
+        let instance_id = instance.id.clone();
+        self.active.push(instance);
+        Some((instance_id, fire_tick, event))
+    }
+
+    /// Advances the chain waiting on `event_id` after that event resolved with `outcome`.
+    ///
+    /// The first branch of the current step whose outcome matches and for which
+    /// `condition_met` accepts the condition is taken, and the tick and event of its step
+    /// are returned for the caller to enqueue. The instance finishes when no branch
+    /// applies or when it reaches `MAX_CHAIN_STEPS`.
+    pub fn resolve<F>(
+        &mut self,
+        event_id: &str,
+        outcome: Option<&str>,
+        condition_met: F,
+        tick: u64,
+        time: WorldTime,
+    ) -> Option<(u64, WorldEvent)>
+    where
+        F: Fn(&TriggerCondition) -> bool,
+    {
+        let index = self
+            .active
+            .iter()
+            .position(|instance| instance.pending_event == event_id)?;
+        let mut instance = self.active.remove(index);
+        let chain = self.definitions.get(&instance.chain_id)?;
+        let step = chain.step(&instance.current_step)?;
+
+        let branch = step.branches.iter().find(|branch| {
+            let outcome_matches = match &branch.outcome {
+                Some(expected) => outcome == Some(expected.as_str()),
+                None => true,
+            };
+            let condition_holds = match &branch.condition {
+                Some(condition) => condition_met(condition),
+                None => true,
+            };
+            outcome_matches && condition_holds
+        })?;
+        if instance.visited.len() >= MAX_CHAIN_STEPS {
+            return None;
+        }
+        let next = chain.step(&branch.next_step)?;
+
+        instance.current_step = next.id.clone();
+        let event = step_event(&mut instance, next, time);
+        let fire_tick = tick + branch.delay.max(1);
+        self.active.push(instance);
+        Some((fire_tick, event))
+    }
+
+    /// Stops the chain waiting on `event_id`, for example because the event was cancelled.
+    /// Returns the stopped instance.
+    pub fn abort(&mut self, event_id: &str) -> Option<ChainInstance> {
+        let index = self
+            .active
+            .iter()
+            .position(|instance| instance.pending_event == event_id)?;
+        Some(self.active.remove(index))
+    }
+}
+
+/// Builds the event of `step` for `instance` and records it as the pending event.
+///
+/// The event is correlated with the instance and, after the first step, names the event
+/// of the previous step as its parent.
+fn step_event(instance: &mut ChainInstance, step: &ChainStep, time: WorldTime) -> WorldEvent {
+    let event_id = format!("{}_{}_{}", instance.id, instance.visited.len(), step.id);
+    let previous = std::mem::replace(&mut instance.pending_event, event_id.clone());
+    instance.visited.push(step.id.clone());
+    let mut event = WorldEvent::new(
+        event_id,
+        step.event_type.clone(),
+        time,
+        instance.location,
+        step.description.clone(),
+    )
+    .with_priority(step.priority)
+    .with_source(EventSource::Chain(instance.chain_id.clone()))
+    .with_correlation(instance.id.clone());
+    if !previous.is_empty() {
+        event.parent_id = Some(previous);
+    }
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raid_chain() -> EventChain {
+        EventChain::new("bandits", "raid", 2)
+            .with_step(
+                ChainStep::new("raid", EventType::Custom("raid".to_string()), "Bandit raid")
+                    .with_branch(ChainBranch::new("reprisal", 5).on_outcome("repelled"))
+                    .with_branch(ChainBranch::new("tribute", 1)),
+            )
+            .with_step(ChainStep::new("reprisal", EventType::FactionWar, "Reprisal"))
+            .with_step(ChainStep::new("tribute", EventType::TradeCompleted, "Tribute paid"))
+    }
+
+    #[test]
+    fn test_start_chain() {
+        let mut chains = EventChains::new();
+        chains.register(raid_chain());
+
+        let (instance_id, tick, event) = chains
+            .start("bandits", (5.0, 5.0), 10, WorldTime::default())
+            .unwrap();
+        assert_eq!(instance_id, "bandits_0");
+        assert_eq!(tick, 12);
+        assert_eq!(event.id, "bandits_0_0_raid");
+        assert_eq!(event.location, (5.0, 5.0));
+        assert!(chains.is_pending("bandits_0_0_raid"));
+        assert!(chains.start("missing", (0.0, 0.0), 0, WorldTime::default()).is_none());
+    }
+
+    #[test]
+    fn test_branch_on_outcome() {
+        let mut chains = EventChains::new();
+        chains.register(raid_chain());
+
+        let (_, _, first) = chains.start("bandits", (0.0, 0.0), 0, WorldTime::default()).unwrap();
+        let (tick, next) = chains
+            .resolve(&first.id, Some("repelled"), |_| true, 2, WorldTime::default())
+            .unwrap();
+        assert_eq!(tick, 7);
+        assert_eq!(next.event_type, EventType::FactionWar);
+        assert_eq!(next.parent_id.as_deref(), Some(first.id.as_str()));
+        assert_eq!(next.correlation_id.as_deref(), Some("bandits_0"));
+        assert_eq!(next.source, EventSource::Chain("bandits".to_string()));
+
+        // The reprisal has no branches, so the chain ends when it resolves.
+        assert!(chains.resolve(&next.id, None, |_| true, 7, WorldTime::default()).is_none());
+        assert!(chains.active().is_empty());
+    }
+
+    #[test]
+    fn test_branch_fallback_and_condition() {
+        let mut chain = raid_chain();
+        chain.steps[0].branches.insert(
+            0,
+            ChainBranch::new("reprisal", 1).when(TriggerCondition::TimeElapsed(100)),
+        );
+        let mut chains = EventChains::new();
+        chains.register(chain);
+
+        let (_, _, first) = chains.start("bandits", (0.0, 0.0), 0, WorldTime::default()).unwrap();
+        let (_, next) = chains
+            .resolve(&first.id, Some("looted"), |_| false, 2, WorldTime::default())
+            .unwrap();
+        assert_eq!(next.event_type, EventType::TradeCompleted);
+        assert_eq!(chains.active()[0].visited, vec!["raid", "tribute"]);
+    }
+
+    #[test]
+    fn test_abort() {
+        let mut chains = EventChains::new();
+        chains.register(raid_chain());
+        let (_, _, first) = chains.start("bandits", (0.0, 0.0), 0, WorldTime::default()).unwrap();
+
+        assert!(chains.abort(&first.id).is_some());
+        assert!(!chains.is_pending(&first.id));
+    }
+
+    #[test]
+    fn test_chain_serialization() {
+        let json = serde_json::to_string(&raid_chain()).unwrap();
+        let restored: EventChain = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.steps.len(), 3);
+        assert_eq!(restored.steps[0].branches[0].outcome.as_deref(), Some("repelled"));
+    }
+}
@@ -10,9 +10,39 @@ pub struct WorldEvent {
     pub location: (f32, f32),
     pub involved_entities: Vec<EntityId>,
     pub description: String,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub source: EventSource,
+    /// Id of the event whose handling produced this one.
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    /// Id shared by every event descending from the same root event or chain instance.
+    #[serde(default)]
+    pub correlation_id: Option<String>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+/// What produced an event.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum EventSource {
+    /// Scheduled from outside the simulation, for example by game code.
+    #[default]
+    External,
+    /// Recorded by a world system such as `"disease"` or `"hunting"`.
+    System(String),
+    /// Fired by the trigger with this id.
+    Trigger(String),
+    /// Produced by the event chain with this id.
+    Chain(String),
+    /// Spawned from the event template with this id.
+    Template(String),
+    /// An occurrence of the recurring event with this template id.
+    Recurring(String),
+    /// Emitted or scheduled by an event bus handler.
+    Handler,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum EventType {
     NPCBirth,
     NPCDeath,
@@ -22,9 +52,64 @@ pub enum EventType {
     TradeCompleted,
     Disaster,
     Discovery,
+    DiseaseOutbreak,
+    EcosystemWarning,
+    Extinction,
+    SpeciesIntroduced,
     Custom(String),
 }
 
+impl EventType {
+    /// Name of the event type: the variant name for built-in types and the custom name
+    /// for `Custom` types.
+    pub fn name(&self) -> &str {
+        match self {
+            EventType::NPCBirth => "NPCBirth",
+            EventType::NPCDeath => "NPCDeath",
+            EventType::NPCMarriage => "NPCMarriage",
+            EventType::FactionWar => "FactionWar",
+            EventType::Settlement => "Settlement",
+            EventType::TradeCompleted => "TradeCompleted",
+            EventType::Disaster => "Disaster",
+            EventType::Discovery => "Discovery",
+            EventType::DiseaseOutbreak => "DiseaseOutbreak",
+            EventType::EcosystemWarning => "EcosystemWarning",
+            EventType::Extinction => "Extinction",
+            EventType::SpeciesIntroduced => "SpeciesIntroduced",
+            EventType::Custom(name) => name,
+        }
+    }
+
+    /// Parses an event type from its name, falling back to a `Custom` type for names
+    /// that are not built in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::events::EventType;
+    ///
+    /// assert_eq!(EventType::from_name("Disaster"), EventType::Disaster);
+    /// assert_eq!(EventType::from_name("festival"), EventType::Custom("festival".to_string()));
+    /// ```
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "NPCBirth" => EventType::NPCBirth,
+            "NPCDeath" => EventType::NPCDeath,
+            "NPCMarriage" => EventType::NPCMarriage,
+            "FactionWar" => EventType::FactionWar,
+            "Settlement" => EventType::Settlement,
+            "TradeCompleted" => EventType::TradeCompleted,
+            "Disaster" => EventType::Disaster,
+            "Discovery" => EventType::Discovery,
+            "DiseaseOutbreak" => EventType::DiseaseOutbreak,
+            "EcosystemWarning" => EventType::EcosystemWarning,
+            "Extinction" => EventType::Extinction,
+            "SpeciesIntroduced" => EventType::SpeciesIntroduced,
+            other => EventType::Custom(other.to_string()),
+        }
+    }
+}
+
 impl WorldEvent {
     /// Constructs a `WorldEvent` with the given metadata and an empty list of involved entities.
     ///
@@ -56,9 +141,55 @@ impl WorldEvent {
             location,
             involved_entities: Vec::new(),
             description,
+            priority: 0,
+            source: EventSource::External,
+            parent_id: None,
+            correlation_id: None,
         }
     }
 
+    /// Sets what produced the event.
+    pub fn with_source(mut self, source: EventSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Marks the event as a consequence of `parent`: it records the parent's id and joins
+    /// the parent's correlation, or starts one named after the parent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::events::{EventType, WorldEvent};
+    /// use entropic_world_core::temporal::WorldTime;
+    ///
+    /// let drought = WorldEvent::new("drought".into(), EventType::Disaster, WorldTime::default(), (0.0, 0.0), "Drought".into());
+    /// let famine = WorldEvent::new("famine".into(), EventType::Disaster, WorldTime::default(), (0.0, 0.0), "Famine".into())
+    ///     .caused_by(&drought);
+    /// let riots = WorldEvent::new("riots".into(), EventType::Settlement, WorldTime::default(), (0.0, 0.0), "Riots".into())
+    ///     .caused_by(&famine);
+    /// assert_eq!(riots.parent_id.as_deref(), Some("famine"));
+    /// assert_eq!(riots.correlation_id.as_deref(), Some("drought"));
+    /// ```
+    pub fn caused_by(mut self, parent: &WorldEvent) -> Self {
+        self.parent_id = Some(parent.id.clone());
+        self.correlation_id = Some(parent.correlation_id.clone().unwrap_or_else(|| parent.id.clone()));
+        self
+    }
+
+    /// Sets the correlation id shared by related events.
+    pub fn with_correlation(mut self, correlation_id: impl Into<String>) -> Self {
+        self.correlation_id = Some(correlation_id.into());
+        self
+    }
+
+    /// Sets the event's priority. Among events due on the same tick, higher priorities are
+    /// processed first; events of equal priority keep their scheduling order.
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
     /// Sets the event's involved entities and returns the updated `WorldEvent`.
     ///
     /// Replaces the event's `involved_entities` list with the provided vector of entity IDs.
@@ -170,4 +301,15 @@ mod tests {
         event.add_entity("explorer_1".to_string());
         assert_eq!(event.involved_entities.len(), 1);
     }
+
+    #[test]
+    fn test_event_type_names_round_trip() {
+        for event_type in [
+            EventType::NPCMarriage,
+            EventType::SpeciesIntroduced,
+            EventType::Custom("festival".to_string()),
+        ] {
+            assert_eq!(EventType::from_name(event_type.name()), event_type);
+        }
+    }
 }
\ No newline at end of file
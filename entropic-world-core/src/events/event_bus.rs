@@ -0,0 +1,326 @@
+use std::fmt;
+use std::sync::Arc;
+use crate::events::event::{EventType, WorldEvent};
+use crate::world::World;
+
+/// Maximum number of events dispatched in a single tick. Follow-up events emitted past
+/// this limit are deferred to the next tick so a handler cascade cannot stall the world.
+pub const MAX_DISPATCHES_PER_TICK: usize = 1024;
+
+pub type SubscriptionId = u64;
+
+/// Callback invoked for every dispatched event a subscription matches.
+///
+/// Handlers get read access to the world the event happened in and can enqueue
+/// follow-up events through the `EventContext`.
+pub type EventHandler = Arc<dyn Fn(&World, &WorldEvent, &mut EventContext) + Send + Sync>;
+
+/// Follow-up events produced by handlers while an event is being dispatched.
+#[derive(Clone, Debug, Default)]
+pub struct EventContext {
+    tick: u64,
+    emitted: Vec<WorldEvent>,
+    scheduled: Vec<(u64, WorldEvent)>,
+    cancelled: Vec<String>,
+    consumed: bool,
+    outcome: Option<String>,
+}
+
+#[derive(Clone)]
+struct Subscription {
+    id: SubscriptionId,
+    event_type: Option<EventType>,
+    priority: i32,
+    handler: EventHandler,
+}
+
+/// Publish/subscribe registry dispatching world events to handlers.
+///
+/// Handlers run in descending subscription priority and, within a priority, in
+/// subscription order, so dispatch is deterministic. A handler that consumes an event
+/// stops it from reaching the handlers after it. The bus holds
+/// closures and is therefore not serialized with the world; systems must subscribe
+/// again after a world is loaded.
+#[derive(Clone, Default)]
+pub struct EventBus {
+    subscriptions: Vec<Subscription>,
+    next_id: SubscriptionId,
+}
+
+impl EventContext {
+    /// Creates an empty context for an event dispatched at `tick`.
+    pub fn new(tick: u64) -> Self {
+        Self {
+            tick,
+            emitted: Vec::new(),
+            scheduled: Vec::new(),
+            cancelled: Vec::new(),
+            consumed: false,
+            outcome: None,
+        }
+    }
+
+    /// Tick at which the current event is being dispatched.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Enqueues `event` for dispatch later in the current tick, after the events already
+    /// pending.
+    pub fn emit(&mut self, event: WorldEvent) {
+        self.emitted.push(event);
+    }
+
+    /// Enqueues `event` for dispatch `delay` ticks from now. A delay of zero is treated as
+    /// one tick.
+    pub fn schedule_after(&mut self, delay: u64, event: WorldEvent) {
+        self.scheduled.push((self.tick + delay.max(1), event));
+    }
+
+    /// Cancels the pending event with the given id so it is never dispatched or recorded.
+    /// Applies to events still waiting in the current tick and to events queued for later
+    /// ticks.
+    pub fn cancel(&mut self, event_id: impl Into<String>) {
+        self.cancelled.push(event_id.into());
+    }
+
+    /// Marks the event being dispatched as consumed, so handlers after the current one do
+    /// not receive it. The event is still recorded in the world's history.
+    pub fn consume(&mut self) {
+        self.consumed = true;
+    }
+
+    /// Reports whether a handler consumed the event being dispatched.
+    pub fn is_consumed(&self) -> bool {
+        self.consumed
+    }
+
+    /// Reports how the event being dispatched resolved, for example `"repelled"` for a
+    /// raid. Event chains branch on the outcome; the last handler to set it wins.
+    pub fn set_outcome(&mut self, outcome: impl Into<String>) {
+        self.outcome = Some(outcome.into());
+    }
+
+    /// Outcome reported by the handlers so far.
+    pub fn outcome(&self) -> Option<&str> {
+        self.outcome.as_deref()
+    }
+
+    /// Ids of the pending events cancelled by handlers.
+    pub fn cancelled(&self) -> &[String] {
+        &self.cancelled
+    }
+
+    /// Consumes the context and returns the events emitted for the current tick and the
+    /// events scheduled for later ticks.
+    pub fn into_parts(self) -> (Vec<WorldEvent>, Vec<(u64, WorldEvent)>) {
+        (self.emitted, self.scheduled)
+    }
+}
+
+impl EventBus {
+    /// Creates a bus without subscriptions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `handler` for events of `event_type` and returns its subscription id.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::World;
+    /// use entropic_world_core::events::{EventType, WorldEvent};
+    ///
+    /// let mut world = World::new("W".into(), "dna".into(), 1, 1);
+    /// world.event_bus.subscribe(EventType::Disaster, |world, event, ctx| {
+    ///     ctx.emit(WorldEvent::new(
+    ///         format!("{}_aftermath", event.id),
+    ///         EventType::Custom("aftermath".into()),
+    ///         world.current_time,
+    ///         event.location,
+    ///         "Survivors gather".into(),
+    ///     ));
+    /// });
+    /// let quake = WorldEvent::new(
+    ///     "quake".into(),
+    ///     EventType::Disaster,
+    ///     world.current_time,
+    ///     (0.0, 0.0),
+    ///     "Earthquake".into(),
+    /// );
+    /// world.event_queue.schedule(1, quake);
+    /// world.advance_tick();
+    /// assert_eq!(world.event_history.len(), 2);
+    /// ```
+    pub fn subscribe<F>(&mut self, event_type: EventType, handler: F) -> SubscriptionId
+    where
+        F: Fn(&World, &WorldEvent, &mut EventContext) + Send + Sync + 'static,
+    {
+        self.add_subscription(Some(event_type), 0, Arc::new(handler))
+    }
+
+    /// Registers `handler` for events of `event_type` ahead of handlers with a lower
+    /// `priority`. `subscribe` registers handlers at priority `0`.
+    pub fn subscribe_with_priority<F>(&mut self, event_type: EventType, priority: i32, handler: F) -> SubscriptionId
+    where
+        F: Fn(&World, &WorldEvent, &mut EventContext) + Send + Sync + 'static,
+    {
+        self.add_subscription(Some(event_type), priority, Arc::new(handler))
+    }
+
+    /// Registers `handler` for every event regardless of type.
+    pub fn subscribe_all<F>(&mut self, handler: F) -> SubscriptionId
+    where
+        F: Fn(&World, &WorldEvent, &mut EventContext) + Send + Sync + 'static,
+    {
+        self.add_subscription(None, 0, Arc::new(handler))
+    }
+
+    /// Removes a subscription. Returns `false` if no subscription had that id.
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        let before = self.subscriptions.len();
+        self.subscriptions.retain(|s| s.id != id);
+        self.subscriptions.len() != before
+    }
+
+    /// Number of registered subscriptions.
+    pub fn subscriber_count(&self) -> usize {
+        self.subscriptions.len()
+    }
+
+    /// Reports whether the bus has no subscriptions.
+    pub fn is_empty(&self) -> bool {
+        self.subscriptions.is_empty()
+    }
+
+    /// Invokes every handler matching `event` in priority order, stopping early if a
+    /// handler consumes the event.
+    pub fn dispatch(&self, world: &World, event: &WorldEvent, context: &mut EventContext) {
+        for subscription in &self.subscriptions {
+            if context.is_consumed() {
+                break;
+            }
+            let matches = match &subscription.event_type {
+                Some(event_type) => *event_type == event.event_type,
+                None => true,
+            };
+            if matches {
+                (subscription.handler)(world, event, context);
+            }
+        }
+    }
+
+    fn add_subscription(&mut self, event_type: Option<EventType>, priority: i32, handler: EventHandler) -> SubscriptionId {
+        let id = self.next_id;
+        self.next_id += 1;
+        let index = self
+            .subscriptions
+            .iter()
+            .position(|s| s.priority < priority)
+            .unwrap_or(self.subscriptions.len());
+        self.subscriptions.insert(
+            index,
+            Subscription {
+                id,
+                event_type,
+                priority,
+                handler,
+            },
+        );
+        id
+    }
+}
+
+impl fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventBus")
+            .field("subscriptions", &self.subscriptions.len())
+            .field("next_id", &self.next_id)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use crate::temporal::time::WorldTime;
+
+    fn event(id: &str, event_type: EventType) -> WorldEvent {
+        WorldEvent::new(
+            id.to_string(),
+            event_type,
+            WorldTime::default(),
+            (0.0, 0.0),
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn test_dispatch_in_subscription_order() {
+        let world = World::new("W".to_string(), "dna".to_string(), 1, 1);
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut bus = EventBus::new();
+
+        let first = log.clone();
+        bus.subscribe(EventType::Disaster, move |_, e, _| first.lock().unwrap().push(format!("a:{}", e.id)));
+        let all = log.clone();
+        bus.subscribe_all(move |_, e, _| all.lock().unwrap().push(format!("b:{}", e.id)));
+
+        let mut context = EventContext::new(0);
+        bus.dispatch(&world, &event("quake", EventType::Disaster), &mut context);
+        bus.dispatch(&world, &event("find", EventType::Discovery), &mut context);
+
+        assert_eq!(*log.lock().unwrap(), vec!["a:quake", "b:quake", "b:find"]);
+    }
+
+    #[test]
+    fn test_priority_and_consumption() {
+        let world = World::new("W".to_string(), "dna".to_string(), 1, 1);
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut bus = EventBus::new();
+
+        let low = log.clone();
+        bus.subscribe(EventType::Disaster, move |_, _, _| low.lock().unwrap().push("low"));
+        let guard = log.clone();
+        bus.subscribe_with_priority(EventType::Disaster, 10, move |_, e, ctx| {
+            guard.lock().unwrap().push("guard");
+            if e.id == "blocked" {
+                ctx.consume();
+            }
+        });
+
+        let mut context = EventContext::new(0);
+        bus.dispatch(&world, &event("quake", EventType::Disaster), &mut context);
+        assert!(!context.is_consumed());
+        let mut context = EventContext::new(0);
+        bus.dispatch(&world, &event("blocked", EventType::Disaster), &mut context);
+        assert!(context.is_consumed());
+
+        assert_eq!(*log.lock().unwrap(), vec!["guard", "low", "guard"]);
+    }
+
+    #[test]
+    fn test_unsubscribe() {
+        let mut bus = EventBus::new();
+        let id = bus.subscribe(EventType::Disaster, |_, _, _| {});
+        assert_eq!(bus.subscriber_count(), 1);
+        assert!(bus.unsubscribe(id));
+        assert!(!bus.unsubscribe(id));
+        assert!(bus.is_empty());
+    }
+
+    #[test]
+    fn test_context_follow_ups() {
+        let mut context = EventContext::new(5);
+        context.emit(event("now", EventType::Discovery));
+        context.schedule_after(0, event("next", EventType::Discovery));
+        context.schedule_after(3, event("later", EventType::Discovery));
+
+        let (emitted, scheduled) = context.into_parts();
+        assert_eq!(emitted.len(), 1);
+        assert_eq!(scheduled[0].0, 6);
+        assert_eq!(scheduled[1].0, 8);
+    }
+}
@@ -1,9 +1,12 @@
 use serde::{Deserialize, Serialize};
 use crate::events::event::WorldEvent;
+use crate::events::recurrence::RecurringEvent;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EventQueue {
     events: Vec<(u64, WorldEvent)>,
+    #[serde(default)]
+    recurring: Vec<RecurringEvent>,
 }
 
 impl EventQueue {
@@ -19,12 +22,17 @@ impl EventQueue {
     /// assert_eq!(q.len(), 0);
     /// ```
     pub fn new() -> Self {
-        Self { events: Vec::new() }
+        Self {
+            events: Vec::new(),
+            recurring: Vec::new(),
+        }
     }
 
     /// Schedules a `WorldEvent` to be executed at the specified tick.
     ///
     /// The event is appended to the queue and the queue is kept sorted in ascending tick order.
+    /// Events due on the same tick are ordered by descending priority, then by the order in
+    /// which they were scheduled.
     ///
     /// # Examples
     ///
@@ -36,13 +44,76 @@ impl EventQueue {
     /// ```
     pub fn schedule(&mut self, tick: u64, event: WorldEvent) {
         self.events.push((tick, event));
-        self.events.sort_by_key(|e| e.0);
+        self.events
+            .sort_by(|a, b| a.0.cmp(&b.0).then_with(|| b.1.priority.cmp(&a.1.priority)));
+    }
+
+    /// Registers an event that fires at `recurring.next_tick` and is re-armed
+    /// automatically after every occurrence, replacing any recurring event whose template
+    /// has the same id.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::events::{EventQueue, EventType, Recurrence, RecurringEvent, WorldEvent};
+    /// use entropic_world_core::temporal::WorldTime;
+    ///
+    /// let mut q = EventQueue::new();
+    /// let patrol = WorldEvent::new("patrol".into(), EventType::Custom("patrol".into()), WorldTime::default(), (0.0, 0.0), "Patrol".into());
+    /// q.schedule_recurring(RecurringEvent::new(patrol, Recurrence::EveryTicks(10), 10));
+    /// assert_eq!(q.get_events_at_tick(10)[0].id, "patrol_10");
+    /// assert_eq!(q.get_events_at_tick(20)[0].id, "patrol_20");
+    /// ```
+    pub fn schedule_recurring(&mut self, recurring: RecurringEvent) {
+        self.recurring
+            .retain(|existing| existing.template.id != recurring.template.id);
+        self.recurring.push(recurring);
+    }
+
+    /// Stops the recurring event whose template has the given id and returns it.
+    pub fn cancel_recurring(&mut self, template_id: &str) -> Option<RecurringEvent> {
+        let index = self
+            .recurring
+            .iter()
+            .position(|recurring| recurring.template.id == template_id)?;
+        Some(self.recurring.remove(index))
+    }
+
+    /// Registered recurring events, in registration order.
+    pub fn recurring(&self) -> &[RecurringEvent] {
+        &self.recurring
+    }
+
+    /// Removes the pending event with the given id and returns it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::events::{EventQueue, EventType, WorldEvent};
+    /// use entropic_world_core::temporal::WorldTime;
+    ///
+    /// let mut q = EventQueue::new();
+    /// q.schedule(5, WorldEvent::new("raid".into(), EventType::FactionWar, WorldTime::default(), (0.0, 0.0), "Raid".into()));
+    /// assert!(q.cancel("raid").is_some());
+    /// assert!(q.is_empty());
+    /// ```
+    pub fn cancel(&mut self, event_id: &str) -> Option<WorldEvent> {
+        let index = self.events.iter().position(|(_, event)| event.id == event_id)?;
+        Some(self.events.remove(index).1)
+    }
+
+    /// Reports whether an event with the given id is pending.
+    pub fn contains(&self, event_id: &str) -> bool {
+        self.events.iter().any(|(_, event)| event.id == event_id)
     }
 
     /// Removes and returns all events scheduled exactly at `tick`.
     ///
     /// Scans the queue from the front and removes any events whose tick equals the given value.
-    /// Scanning stops once an event with a greater tick is encountered.
+    /// Scanning stops once an event with a greater tick is encountered. Recurring events due
+    /// at `tick` contribute one occurrence each and are re-armed; occurrences of recurring
+    /// events that were due before `tick` are skipped. The result is ordered by descending
+    /// priority.
     ///
     /// # Returns
     ///
@@ -67,7 +138,20 @@ impl EventQueue {
                 i += 1;
             }
         }
-        
+
+        for recurring in &mut self.recurring {
+            if recurring.next_tick < tick {
+                let interval = recurring.recurrence.interval();
+                let missed = (tick - recurring.next_tick + interval - 1) / interval;
+                recurring.next_tick += missed * interval;
+            }
+            if recurring.next_tick == tick {
+                result.push(recurring.fire());
+            }
+        }
+        self.recurring.retain(|recurring| !recurring.is_exhausted());
+        result.sort_by_key(|event| std::cmp::Reverse(event.priority));
+
         result
     }
 
@@ -141,7 +225,8 @@ impl EventQueue {
     /// Removes and returns all scheduled events whose tick is less than or equal to `tick`.
     ///
     /// The returned vector contains the removed `WorldEvent` items in the same chronological order
-    /// they were scheduled.
+    /// they were scheduled. Every occurrence of a recurring event up to `tick` is included,
+    /// in tick order.
     ///
     /// # Examples
     ///
@@ -157,13 +242,21 @@ impl EventQueue {
         
         while let Some(&(event_tick, _)) = self.events.first() {
             if event_tick <= tick {
-                result.push(self.events.remove(0).1);
+                result.push((event_tick, self.events.remove(0).1));
             } else {
                 break;
             }
         }
-        
-        result
+
+        for recurring in &mut self.recurring {
+            while recurring.next_tick <= tick && !recurring.is_exhausted() {
+                result.push((recurring.next_tick, recurring.fire()));
+            }
+        }
+        self.recurring.retain(|recurring| !recurring.is_exhausted());
+        result.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| b.1.priority.cmp(&a.1.priority)));
+
+        result.into_iter().map(|(_, event)| event).collect()
     }
 }
 
@@ -275,4 +368,85 @@ mod tests {
         assert_eq!(events.len(), 3);
         assert_eq!(queue.len(), 2);
     }
+
+    #[test]
+    fn test_priority_ordering_within_tick() {
+        let mut queue = EventQueue::new();
+        for (id, priority) in [("low", -1), ("first", 0), ("high", 5), ("second", 0)] {
+            let event = WorldEvent::new(
+                id.to_string(),
+                EventType::Custom("test".to_string()),
+                WorldTime::default(),
+                (0.0, 0.0),
+                String::new(),
+            )
+            .with_priority(priority);
+            queue.schedule(10, event);
+        }
+
+        let ids: Vec<String> = queue.get_events_at_tick(10).into_iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec!["high", "first", "second", "low"]);
+    }
+
+    #[test]
+    fn test_recurring_events() {
+        use crate::events::recurrence::Recurrence;
+
+        let mut queue = EventQueue::new();
+        let template = WorldEvent::new(
+            "tithe".to_string(),
+            EventType::Custom("tithe".to_string()),
+            WorldTime::default(),
+            (0.0, 0.0),
+            "Tithe collected".to_string(),
+        );
+        queue.schedule_recurring(
+            RecurringEvent::new(template, Recurrence::EveryTicks(10), 10).with_max_occurrences(4),
+        );
+
+        assert!(queue.get_events_at_tick(5).is_empty());
+        assert_eq!(queue.get_events_at_tick(10).len(), 1);
+        // Tick 20 was never polled, so that occurrence is skipped.
+        assert_eq!(queue.get_events_at_tick(30)[0].id, "tithe_30");
+
+        let ids: Vec<String> = queue.get_events_until(100).into_iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec!["tithe_40", "tithe_50"]);
+        assert!(queue.recurring().is_empty());
+    }
+
+    #[test]
+    fn test_cancel_recurring() {
+        use crate::events::recurrence::Recurrence;
+
+        let mut queue = EventQueue::new();
+        let template = WorldEvent::new(
+            "bells".to_string(),
+            EventType::Custom("bells".to_string()),
+            WorldTime::default(),
+            (0.0, 0.0),
+            "Bells ring".to_string(),
+        );
+        queue.schedule_recurring(RecurringEvent::new(template, Recurrence::DailyAt { hour: 7 }, 1));
+
+        assert!(queue.cancel_recurring("bells").is_some());
+        assert!(queue.get_events_at_tick(1).is_empty());
+    }
+
+    #[test]
+    fn test_cancel_event() {
+        let mut queue = EventQueue::new();
+        let event = WorldEvent::new(
+            "event_1".to_string(),
+            EventType::NPCBirth,
+            WorldTime::default(),
+            (0.0, 0.0),
+            "Event 1".to_string(),
+        );
+        queue.schedule(10, event);
+
+        assert!(queue.contains("event_1"));
+        assert!(queue.cancel("missing").is_none());
+        assert_eq!(queue.cancel("event_1").unwrap().id, "event_1");
+        assert!(queue.is_empty());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,764 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::ops::{Bound, Index, RangeBounds};
+use crate::events::event::{EventType, WorldEvent};
+use crate::population::entity::EntityId;
+
+/// How many events the history keeps and what happens to the ones it drops.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RetentionPolicy {
+    /// Keep every event.
+    #[default]
+    Unbounded,
+    /// Keep the most recent events and discard older ones.
+    RingBuffer(usize),
+    /// Keep the most recent events and move older ones to the archive, from where they
+    /// can be taken and written to storage.
+    Archive(usize),
+}
+
+/// Rules for folding old events into aggregate summaries.
+///
+/// Events older than `keep_recent` ticks are compacted in whole `period`-tick windows:
+/// events with at least `min_priority`, or of a type listed in `keep_types`, stay in the
+/// history verbatim, and the rest are replaced by one `EventSummary` per event type and
+/// window.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CompactionPolicy {
+    pub keep_recent: u64,
+    pub period: u64,
+    pub min_priority: i32,
+    #[serde(default)]
+    pub keep_types: Vec<EventType>,
+}
+
+/// Aggregate record of the events of one type compacted out of a window of ticks.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EventSummary {
+    pub event_type: EventType,
+    pub start_tick: u64,
+    pub end_tick: u64,
+    pub count: u64,
+    pub first_tick: u64,
+    pub last_tick: u64,
+}
+
+/// Record of dispatched world events, indexed by event type, tick and involved entity.
+///
+/// Events are recorded together with the tick they happened on. Faction ids listed among
+/// an event's involved entities are indexed like any other entity.
+#[derive(Clone, Debug, Default)]
+pub struct EventHistory {
+    events: VecDeque<WorldEvent>,
+    ticks: VecDeque<u64>,
+    first_seq: u64,
+    retention: RetentionPolicy,
+    archived: Vec<(u64, WorldEvent)>,
+    compaction: Option<CompactionPolicy>,
+    compacted_until: u64,
+    summaries: Vec<EventSummary>,
+    by_type: HashMap<EventType, VecDeque<u64>>,
+    by_entity: HashMap<EntityId, VecDeque<u64>>,
+    by_id: HashMap<String, u64>,
+}
+
+#[derive(Serialize)]
+struct HistoryRef<'a> {
+    events: &'a VecDeque<WorldEvent>,
+    ticks: &'a VecDeque<u64>,
+    first_seq: u64,
+    retention: RetentionPolicy,
+    archived: &'a Vec<(u64, WorldEvent)>,
+    compaction: &'a Option<CompactionPolicy>,
+    compacted_until: u64,
+    summaries: &'a Vec<EventSummary>,
+}
+
+#[derive(Deserialize)]
+struct HistoryData {
+    events: VecDeque<WorldEvent>,
+    ticks: VecDeque<u64>,
+    first_seq: u64,
+    retention: RetentionPolicy,
+    archived: Vec<(u64, WorldEvent)>,
+    #[serde(default)]
+    compaction: Option<CompactionPolicy>,
+    #[serde(default)]
+    compacted_until: u64,
+    #[serde(default)]
+    summaries: Vec<EventSummary>,
+}
+
+/// Saved form of a history: the full state, or the plain event list older saves stored.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredHistory {
+    Full(HistoryData),
+    Legacy(Vec<WorldEvent>),
+}
+
+impl CompactionPolicy {
+    /// Creates a policy compacting events older than `keep_recent` ticks into summaries
+    /// covering `period` ticks each. Every old event is summarized until `with_min_priority`
+    /// or `keeping` mark events to keep verbatim.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::events::{CompactionPolicy, EventType};
+    ///
+    /// let policy = CompactionPolicy::new(10_000, 1_000)
+    ///     .with_min_priority(5)
+    ///     .keeping(EventType::FactionWar);
+    /// assert_eq!(policy.keep_types, vec![EventType::FactionWar]);
+    /// ```
+    pub fn new(keep_recent: u64, period: u64) -> Self {
+        Self {
+            keep_recent,
+            period: period.max(1),
+            min_priority: i32::MAX,
+            keep_types: Vec::new(),
+        }
+    }
+
+    /// Keeps events with at least `min_priority` verbatim.
+    pub fn with_min_priority(mut self, min_priority: i32) -> Self {
+        self.min_priority = min_priority;
+        self
+    }
+
+    /// Keeps every event of `event_type` verbatim.
+    pub fn keeping(mut self, event_type: EventType) -> Self {
+        self.keep_types.push(event_type);
+        self
+    }
+
+    /// Reports whether `event` survives compaction verbatim.
+    pub fn keeps(&self, event: &WorldEvent) -> bool {
+        event.priority >= self.min_priority || self.keep_types.contains(&event.event_type)
+    }
+}
+
+impl EventHistory {
+    /// Creates an empty history that keeps every event.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty history applying `retention`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::events::{EventHistory, EventType, RetentionPolicy, WorldEvent};
+    /// use entropic_world_core::temporal::WorldTime;
+    ///
+    /// let mut history = EventHistory::with_retention(RetentionPolicy::Archive(2));
+    /// for tick in 0..3 {
+    ///     let id = format!("quake_{}", tick);
+    ///     history.record(tick, WorldEvent::new(id, EventType::Disaster, WorldTime::default(), (0.0, 0.0), String::new()));
+    /// }
+    /// assert_eq!(history.len(), 2);
+    /// assert_eq!(history.take_archived()[0].1.id, "quake_0");
+    /// ```
+    pub fn with_retention(retention: RetentionPolicy) -> Self {
+        Self {
+            retention,
+            ..Self::default()
+        }
+    }
+
+    /// Active retention policy.
+    pub fn retention(&self) -> RetentionPolicy {
+        self.retention
+    }
+
+    /// Changes the retention policy, immediately dropping or archiving events that no
+    /// longer fit.
+    pub fn set_retention(&mut self, retention: RetentionPolicy) {
+        self.retention = retention;
+        self.enforce_retention();
+    }
+
+    /// Records `event` as having happened on `tick`.
+    ///
+    /// Ticks are expected to be recorded in order; an event recorded with an earlier tick
+    /// than the latest event is filed under the latest tick.
+    pub fn record(&mut self, tick: u64, event: WorldEvent) {
+        let tick = tick.max(self.ticks.back().copied().unwrap_or(0));
+        let seq = self.first_seq + self.events.len() as u64;
+        self.index_event(seq, &event);
+        self.events.push_back(event);
+        self.ticks.push_back(tick);
+        self.enforce_retention();
+    }
+
+    /// Number of retained events.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Reports whether no events are retained.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Number of events recorded since the history was created, including dropped and
+    /// archived ones.
+    pub fn total_recorded(&self) -> u64 {
+        self.first_seq + self.events.len() as u64
+    }
+
+    /// Retained events, oldest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &WorldEvent> + ExactSizeIterator {
+        self.events.iter()
+    }
+
+    /// Retained events with the tick each happened on, oldest first.
+    pub fn iter_with_ticks(&self) -> impl Iterator<Item = (u64, &WorldEvent)> {
+        self.ticks.iter().copied().zip(self.events.iter())
+    }
+
+    /// Returns the retained event at `index`, counted from the oldest.
+    pub fn get(&self, index: usize) -> Option<&WorldEvent> {
+        self.events.get(index)
+    }
+
+    /// Most recently recorded event.
+    pub fn last(&self) -> Option<&WorldEvent> {
+        self.events.back()
+    }
+
+    /// Retained events of `event_type`, oldest first.
+    pub fn of_type<'a>(&'a self, event_type: &EventType) -> impl Iterator<Item = &'a WorldEvent> + 'a {
+        self.lookup(self.by_type.get(event_type))
+    }
+
+    /// Retained events listing `entity_id`, which may be an NPC or a faction, among their
+    /// involved entities, oldest first.
+    pub fn involving<'a>(&'a self, entity_id: &str) -> impl Iterator<Item = &'a WorldEvent> + 'a {
+        self.lookup(self.by_entity.get(entity_id))
+    }
+
+    /// Returns the most recent retained event with the given id.
+    pub fn find(&self, event_id: &str) -> Option<&WorldEvent> {
+        let seq = self.by_id.get(event_id)?;
+        self.events.get((seq - self.first_seq) as usize)
+    }
+
+    /// Retained events sharing `correlation_id`, oldest first.
+    pub fn correlated<'a>(&'a self, correlation_id: &'a str) -> impl Iterator<Item = &'a WorldEvent> + 'a {
+        self.events
+            .iter()
+            .filter(move |event| event.correlation_id.as_deref() == Some(correlation_id))
+    }
+
+    /// Retained events produced directly by the event with id `event_id`, oldest first.
+    pub fn consequences_of<'a>(&'a self, event_id: &'a str) -> impl Iterator<Item = &'a WorldEvent> + 'a {
+        self.events
+            .iter()
+            .filter(move |event| event.parent_id.as_deref() == Some(event_id))
+    }
+
+    /// Chain of causes that led to the event with id `event_id`, from the earliest retained
+    /// cause to the event itself. Empty if the event is not retained.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::events::{EventHistory, EventType, WorldEvent};
+    /// use entropic_world_core::temporal::WorldTime;
+    ///
+    /// let event = |id: &str| WorldEvent::new(id.into(), EventType::Disaster, WorldTime::default(), (0.0, 0.0), String::new());
+    /// let drought = event("drought");
+    /// let famine = event("famine").caused_by(&drought);
+    /// let war = event("war").caused_by(&famine);
+    ///
+    /// let mut history = EventHistory::new();
+    /// history.record(1, drought);
+    /// history.record(2, famine);
+    /// history.record(3, war);
+    /// let chain: Vec<&str> = history.causal_chain("war").iter().map(|e| e.id.as_str()).collect();
+    /// assert_eq!(chain, vec!["drought", "famine", "war"]);
+    /// ```
+    pub fn causal_chain(&self, event_id: &str) -> Vec<&WorldEvent> {
+        let mut chain = Vec::new();
+        let mut next = self.find(event_id);
+        while let Some(event) = next {
+            if chain.len() > self.events.len() {
+                break;
+            }
+            chain.push(event);
+            next = event.parent_id.as_deref().and_then(|parent| self.find(parent));
+        }
+        chain.reverse();
+        chain
+    }
+
+    /// Retained events recorded on a tick within `ticks`, oldest first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::events::{EventHistory, EventType, WorldEvent};
+    /// use entropic_world_core::temporal::WorldTime;
+    ///
+    /// let mut history = EventHistory::new();
+    /// for tick in [1, 5, 5, 9] {
+    ///     let id = format!("find_{}", tick);
+    ///     history.record(tick, WorldEvent::new(id, EventType::Discovery, WorldTime::default(), (0.0, 0.0), String::new()));
+    /// }
+    /// assert_eq!(history.in_ticks(2..=5).count(), 2);
+    /// assert_eq!(history.in_ticks(6..).count(), 1);
+    /// ```
+    pub fn in_ticks<R: RangeBounds<u64>>(&self, ticks: R) -> impl Iterator<Item = &WorldEvent> {
+        let start = match ticks.start_bound() {
+            Bound::Included(&tick) => self.ticks.partition_point(|&t| t < tick),
+            Bound::Excluded(&tick) => self.ticks.partition_point(|&t| t <= tick),
+            Bound::Unbounded => 0,
+        };
+        let end = match ticks.end_bound() {
+            Bound::Included(&tick) => self.ticks.partition_point(|&t| t <= tick),
+            Bound::Excluded(&tick) => self.ticks.partition_point(|&t| t < tick),
+            Bound::Unbounded => self.ticks.len(),
+        };
+        self.events.range(start..end.max(start))
+    }
+
+    /// Events moved out of the history by `RetentionPolicy::Archive` and not yet taken,
+    /// with the tick each happened on.
+    pub fn archived(&self) -> &[(u64, WorldEvent)] {
+        &self.archived
+    }
+
+    /// Takes the archived events, leaving the archive empty, so they can be written to
+    /// storage.
+    pub fn take_archived(&mut self) -> Vec<(u64, WorldEvent)> {
+        std::mem::take(&mut self.archived)
+    }
+
+    /// Active compaction policy.
+    pub fn compaction(&self) -> Option<&CompactionPolicy> {
+        self.compaction.as_ref()
+    }
+
+    /// Sets the policy `compact` applies; `None` disables compaction.
+    pub fn set_compaction(&mut self, policy: Option<CompactionPolicy>) {
+        self.compaction = policy;
+    }
+
+    /// Summaries of compacted events, oldest window first.
+    pub fn summaries(&self) -> &[EventSummary] {
+        &self.summaries
+    }
+
+    /// Number of events of `event_type` recorded and still accounted for, whether retained
+    /// verbatim or folded into a summary.
+    pub fn count_of_type(&self, event_type: &EventType) -> u64 {
+        let summarized: u64 = self
+            .summaries
+            .iter()
+            .filter(|summary| summary.event_type == *event_type)
+            .map(|summary| summary.count)
+            .sum();
+        summarized + self.by_type.get(event_type).map_or(0, |seqs| seqs.len() as u64)
+    }
+
+    /// Compacts the windows of events that have fallen more than `keep_recent` ticks
+    /// behind `current_tick`. Does nothing without a compaction policy or until a whole
+    /// new window is old enough, so it is cheap to call every tick.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::events::{CompactionPolicy, EventHistory, EventType, WorldEvent};
+    /// use entropic_world_core::temporal::WorldTime;
+    ///
+    /// let mut history = EventHistory::new();
+    /// history.set_compaction(Some(CompactionPolicy::new(10, 10)));
+    /// for tick in 0..30 {
+    ///     let id = format!("birth_{}", tick);
+    ///     history.record(tick, WorldEvent::new(id, EventType::NPCBirth, WorldTime::default(), (0.0, 0.0), String::new()));
+    /// }
+    /// history.compact(30);
+    /// assert_eq!(history.len(), 10);
+    /// assert_eq!(history.summaries().len(), 2);
+    /// assert_eq!(history.count_of_type(&EventType::NPCBirth), 30);
+    /// ```
+    pub fn compact(&mut self, current_tick: u64) {
+        let Some(policy) = self.compaction.clone() else {
+            return;
+        };
+        let period = policy.period.max(1);
+        let cutoff = current_tick.saturating_sub(policy.keep_recent);
+        let cutoff = cutoff - cutoff % period;
+        if cutoff <= self.compacted_until {
+            return;
+        }
+
+        let start = self.ticks.partition_point(|&t| t < self.compacted_until);
+        let end = self.ticks.partition_point(|&t| t < cutoff);
+        let mut tail_events = self.events.split_off(end);
+        let mut tail_ticks = self.ticks.split_off(end);
+        let window_events = self.events.split_off(start);
+        let window_ticks = self.ticks.split_off(start);
+
+        let mut summaries: BTreeMap<(u64, String), EventSummary> = BTreeMap::new();
+        let mut removed = 0;
+        for (tick, event) in window_ticks.into_iter().zip(window_events) {
+            if policy.keeps(&event) {
+                self.events.push_back(event);
+                self.ticks.push_back(tick);
+                continue;
+            }
+            removed += 1;
+            let window = tick - tick % period;
+            summaries
+                .entry((window, event.event_type.name().to_string()))
+                .and_modify(|summary| {
+                    summary.count += 1;
+                    summary.last_tick = tick;
+                })
+                .or_insert(EventSummary {
+                    event_type: event.event_type,
+                    start_tick: window,
+                    end_tick: window + period - 1,
+                    count: 1,
+                    first_tick: tick,
+                    last_tick: tick,
+                });
+        }
+        self.events.append(&mut tail_events);
+        self.ticks.append(&mut tail_ticks);
+
+        self.summaries.extend(summaries.into_values());
+        self.compacted_until = cutoff;
+        self.first_seq += removed;
+        self.rebuild_indexes();
+    }
+
+    fn lookup<'a>(&'a self, seqs: Option<&'a VecDeque<u64>>) -> impl Iterator<Item = &'a WorldEvent> + 'a {
+        seqs.into_iter()
+            .flatten()
+            .map(move |&seq| &self.events[(seq - self.first_seq) as usize])
+    }
+
+    fn index_event(&mut self, seq: u64, event: &WorldEvent) {
+        self.by_id.insert(event.id.clone(), seq);
+        self.by_type
+            .entry(event.event_type.clone())
+            .or_default()
+            .push_back(seq);
+        for entity in &event.involved_entities {
+            let seqs = self.by_entity.entry(entity.clone()).or_default();
+            if seqs.back() != Some(&seq) {
+                seqs.push_back(seq);
+            }
+        }
+    }
+
+    fn rebuild_indexes(&mut self) {
+        self.by_type.clear();
+        self.by_entity.clear();
+        self.by_id.clear();
+        let events = std::mem::take(&mut self.events);
+        for (offset, event) in events.iter().enumerate() {
+            self.index_event(self.first_seq + offset as u64, event);
+        }
+        self.events = events;
+    }
+
+    fn enforce_retention(&mut self) {
+        let capacity = match self.retention {
+            RetentionPolicy::Unbounded => return,
+            RetentionPolicy::RingBuffer(capacity) | RetentionPolicy::Archive(capacity) => capacity,
+        };
+        while self.events.len() > capacity {
+            let (Some(event), Some(tick)) = (self.events.pop_front(), self.ticks.pop_front()) else {
+                break;
+            };
+            let seq = self.first_seq;
+            self.first_seq += 1;
+            unindex(&mut self.by_type, &event.event_type, seq);
+            for entity in &event.involved_entities {
+                unindex(&mut self.by_entity, entity, seq);
+            }
+            if self.by_id.get(&event.id) == Some(&seq) {
+                self.by_id.remove(&event.id);
+            }
+            if matches!(self.retention, RetentionPolicy::Archive(_)) {
+                self.archived.push((tick, event));
+            }
+        }
+    }
+}
+
+/// Drops `seq` from the front of the index entry for `key`, removing emptied entries.
+fn unindex<K>(index: &mut HashMap<K, VecDeque<u64>>, key: &K, seq: u64)
+where
+    K: std::hash::Hash + Eq,
+{
+    if let Some(seqs) = index.get_mut(key) {
+        if seqs.front() == Some(&seq) {
+            seqs.pop_front();
+        }
+        if seqs.is_empty() {
+            index.remove(key);
+        }
+    }
+}
+
+impl Index<usize> for EventHistory {
+    type Output = WorldEvent;
+
+    fn index(&self, index: usize) -> &WorldEvent {
+        &self.events[index]
+    }
+}
+
+impl Serialize for EventHistory {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        HistoryRef {
+            events: &self.events,
+            ticks: &self.ticks,
+            first_seq: self.first_seq,
+            retention: self.retention,
+            archived: &self.archived,
+            compaction: &self.compaction,
+            compacted_until: self.compacted_until,
+            summaries: &self.summaries,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for EventHistory {
+    /// Restores the retained events and rebuilds the indexes from them.
+    ///
+    /// Saves that stored the history as a plain list of events are also accepted; each
+    /// event is recorded on the tick of its timestamp.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = match StoredHistory::deserialize(deserializer)? {
+            StoredHistory::Full(data) if data.events.len() != data.ticks.len() => {
+                return Err(serde::de::Error::custom(format!(
+                    "event history has {} events but {} ticks",
+                    data.events.len(),
+                    data.ticks.len()
+                )));
+            }
+            StoredHistory::Full(data) => data,
+            StoredHistory::Legacy(events) => {
+                let mut history = EventHistory::new();
+                for event in events {
+                    history.record(event.timestamp.tick, event);
+                }
+                return Ok(history);
+            }
+        };
+        let mut history = EventHistory {
+            events: data.events,
+            ticks: data.ticks,
+            first_seq: data.first_seq,
+            retention: data.retention,
+            archived: data.archived,
+            compaction: data.compaction,
+            compacted_until: data.compacted_until,
+            summaries: data.summaries,
+            ..EventHistory::default()
+        };
+        history.rebuild_indexes();
+        Ok(history)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temporal::time::WorldTime;
+
+    fn event(id: &str, event_type: EventType, entities: &[&str]) -> WorldEvent {
+        WorldEvent::new(
+            id.to_string(),
+            event_type,
+            WorldTime::default(),
+            (0.0, 0.0),
+            String::new(),
+        )
+        .with_entities(entities.iter().map(|e| e.to_string()).collect())
+    }
+
+    fn ids<'a>(events: impl Iterator<Item = &'a WorldEvent>) -> Vec<&'a str> {
+        events.map(|e| e.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_indexed_queries() {
+        let mut history = EventHistory::new();
+        history.record(1, event("birth", EventType::NPCBirth, &["npc_1"]));
+        history.record(2, event("war", EventType::FactionWar, &["faction_a", "faction_b"]));
+        history.record(2, event("wedding", EventType::NPCMarriage, &["npc_1", "npc_2"]));
+        history.record(4, event("truce", EventType::FactionWar, &["faction_a"]));
+
+        assert_eq!(ids(history.of_type(&EventType::FactionWar)), vec!["war", "truce"]);
+        assert_eq!(ids(history.involving("npc_1")), vec!["birth", "wedding"]);
+        assert_eq!(ids(history.involving("faction_a")), vec!["war", "truce"]);
+        assert_eq!(ids(history.in_ticks(2..4)), vec!["war", "wedding"]);
+        assert_eq!(history.in_ticks(5..).count(), 0);
+        assert_eq!(history.of_type(&EventType::Disaster).count(), 0);
+        assert_eq!(history[1].id, "war");
+    }
+
+    #[test]
+    fn test_causality_queries() {
+        let mut history = EventHistory::with_retention(RetentionPolicy::RingBuffer(4));
+        let drought = event("drought", EventType::Disaster, &[]);
+        let famine = event("famine", EventType::Disaster, &[]).caused_by(&drought);
+        let riot = event("riot", EventType::Settlement, &[]).caused_by(&famine);
+        let exodus = event("exodus", EventType::Settlement, &[]).caused_by(&famine);
+        let war = event("war", EventType::FactionWar, &[]).caused_by(&riot);
+        for (tick, e) in [drought, famine, riot, exodus, war].into_iter().enumerate() {
+            history.record(tick as u64, e);
+        }
+
+        // The drought has been evicted, so the chain starts at the famine.
+        assert_eq!(history.causal_chain("war").len(), 3);
+        assert_eq!(ids(history.causal_chain("war").into_iter()), vec!["famine", "riot", "war"]);
+        assert_eq!(ids(history.consequences_of("famine")), vec!["riot", "exodus"]);
+        assert_eq!(history.correlated("drought").count(), 4);
+        assert!(history.find("drought").is_none());
+        assert!(history.causal_chain("drought").is_empty());
+    }
+
+    #[test]
+    fn test_ring_buffer_retention() {
+        let mut history = EventHistory::with_retention(RetentionPolicy::RingBuffer(2));
+        history.record(1, event("a", EventType::Disaster, &["npc_1"]));
+        history.record(2, event("b", EventType::Discovery, &[]));
+        history.record(3, event("c", EventType::Disaster, &["npc_1"]));
+
+        assert_eq!(ids(history.iter()), vec!["b", "c"]);
+        assert_eq!(ids(history.of_type(&EventType::Disaster)), vec!["c"]);
+        assert_eq!(ids(history.involving("npc_1")), vec!["c"]);
+        assert_eq!(history.total_recorded(), 3);
+        assert!(history.archived().is_empty());
+    }
+
+    #[test]
+    fn test_archive_retention() {
+        let mut history = EventHistory::new();
+        for tick in 0..5 {
+            history.record(tick, event(&format!("e{}", tick), EventType::Discovery, &[]));
+        }
+        history.set_retention(RetentionPolicy::Archive(3));
+
+        assert_eq!(ids(history.iter()), vec!["e2", "e3", "e4"]);
+        let archived = history.take_archived();
+        assert_eq!(archived.len(), 2);
+        assert_eq!(archived[1].0, 1);
+        assert!(history.archived().is_empty());
+    }
+
+    #[test]
+    fn test_out_of_order_tick_is_clamped() {
+        let mut history = EventHistory::new();
+        history.record(5, event("late", EventType::Discovery, &[]));
+        history.record(3, event("early", EventType::Discovery, &[]));
+        assert_eq!(ids(history.in_ticks(5..=5)), vec!["late", "early"]);
+    }
+
+    #[test]
+    fn test_compaction_keeps_important_events() {
+        let mut history = EventHistory::new();
+        history.set_compaction(Some(
+            CompactionPolicy::new(5, 4).with_min_priority(10).keeping(EventType::FactionWar),
+        ));
+        for tick in 0..12 {
+            history.record(tick, event(&format!("birth_{}", tick), EventType::NPCBirth, &["npc_1"]));
+            if tick == 2 {
+                history.record(tick, event("war", EventType::FactionWar, &["faction_a"]));
+                history.record(tick, event("omen", EventType::Discovery, &[]).with_priority(10));
+            }
+        }
+        history.record(12, event("death", EventType::NPCDeath, &["npc_1"]));
+
+        // Nothing is old enough before the first window closes.
+        history.compact(8);
+        assert!(history.summaries().is_empty());
+
+        history.compact(13);
+        // Windows [0, 4) and [4, 8) are compacted; ticks 8 and later are kept.
+        assert_eq!(history.summaries().len(), 2);
+        assert_eq!(history.summaries()[0].count, 4);
+        assert_eq!(history.summaries()[1].start_tick, 4);
+        assert_eq!(history.summaries()[1].end_tick, 7);
+        assert_eq!(ids(history.of_type(&EventType::FactionWar)), vec!["war"]);
+        assert_eq!(ids(history.in_ticks(..8)), vec!["war", "omen"]);
+        assert_eq!(ids(history.involving("npc_1")).first(), Some(&"birth_8"));
+        assert_eq!(history.count_of_type(&EventType::NPCBirth), 12);
+        assert_eq!(history.total_recorded(), 15);
+
+        // Calling again within the same window is a no-op.
+        history.compact(14);
+        assert_eq!(history.summaries().len(), 2);
+    }
+
+    #[test]
+    fn test_serialization_rebuilds_indexes() {
+        let mut history = EventHistory::with_retention(RetentionPolicy::RingBuffer(2));
+        history.record(1, event("a", EventType::NPCBirth, &["npc_1"]));
+        history.record(2, event("b", EventType::NPCDeath, &["npc_1"]));
+        history.record(3, event("c", EventType::NPCDeath, &["npc_2"]));
+
+        let json = serde_json::to_string(&history).unwrap();
+        let restored: EventHistory = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.retention(), RetentionPolicy::RingBuffer(2));
+        assert_eq!(ids(restored.involving("npc_1")), vec!["b"]);
+        assert_eq!(ids(restored.of_type(&EventType::NPCDeath)), vec!["b", "c"]);
+        assert_eq!(restored.total_recorded(), 3);
+    }
+
+    #[test]
+    fn test_compacted_history_serialization() {
+        let mut history = EventHistory::new();
+        history.set_compaction(Some(CompactionPolicy::new(0, 10)));
+        for tick in 0..15 {
+            history.record(tick, event(&format!("e{}", tick), EventType::TradeCompleted, &[]));
+        }
+        history.compact(15);
+
+        let json = serde_json::to_string(&history).unwrap();
+        let restored: EventHistory = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.summaries(), history.summaries());
+        assert_eq!(restored.compaction(), history.compaction());
+        assert_eq!(restored.count_of_type(&EventType::TradeCompleted), 15);
+        assert_eq!(ids(restored.of_type(&EventType::TradeCompleted)).first(), Some(&"e10"));
+    }
+
+    #[test]
+    fn test_legacy_event_list_deserializes() {
+        let mut storm = event("storm", EventType::Disaster, &["npc_1"]);
+        storm.timestamp.tick = 4;
+        let birth = event("birth", EventType::NPCBirth, &["npc_1"]);
+        let json = serde_json::to_string(&vec![storm, birth]).unwrap();
+
+        let restored: EventHistory = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.len(), 2);
+        assert_eq!(restored.retention(), RetentionPolicy::Unbounded);
+        assert_eq!(ids(restored.involving("npc_1")), vec!["storm", "birth"]);
+        assert_eq!(restored.find("birth").map(|e| e.id.as_str()), Some("birth"));
+        let ticks: Vec<u64> = restored.iter_with_ticks().map(|(tick, _)| tick).collect();
+        assert_eq!(ticks, vec![4, 4]);
+    }
+
+    #[test]
+    fn test_mismatched_ticks_are_rejected() {
+        let mut history = EventHistory::new();
+        history.record(1, event("storm", EventType::Disaster, &[]));
+        history.record(2, event("birth", EventType::NPCBirth, &[]));
+        let mut json = serde_json::to_value(&history).unwrap();
+        json["ticks"] = serde_json::json!([1]);
+
+        let err = serde_json::from_value::<EventHistory>(json).unwrap_err();
+        assert!(err.to_string().contains("2 events but 1 ticks"));
+    }
+}
@@ -1,7 +1,27 @@
+pub mod analytics;
+pub mod chain;
 pub mod event;
+pub mod event_bus;
 pub mod event_queue;
+pub mod history;
+pub mod recurrence;
+pub mod replay;
+#[cfg(feature = "scripting")]
+pub mod scripting;
+pub mod templates;
+pub mod trigger_engine;
 pub mod triggers;
 
-pub use event::{EventType, WorldEvent};
+pub use analytics::{EventAnalytics, TimelineBucket};
+pub use chain::{ChainBranch, ChainInstance, ChainStep, EventChain, EventChains};
+pub use event::{EventSource, EventType, WorldEvent};
+pub use event_bus::{EventBus, EventContext, EventHandler, SubscriptionId};
 pub use event_queue::EventQueue;
-pub use triggers::{EventTrigger, TriggerCondition};
+pub use history::{CompactionPolicy, EventHistory, EventSummary, RetentionPolicy};
+pub use recurrence::{Recurrence, RecurringEvent};
+pub use replay::EventLog;
+#[cfg(feature = "scripting")]
+pub use scripting::ScriptEngine;
+pub use templates::{EventTemplate, EventTemplateRegistry, SpawnContext, WeightModifier};
+pub use trigger_engine::{metric_value, CustomCondition, TriggerEngine};
+pub use triggers::{Comparison, EventTrigger, TriggerCondition, WorldMetric};
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+use crate::constants::{DEFAULT_TICKS_PER_SECOND, TICKS_PER_DAY};
+use crate::events::event::{EventSource, WorldEvent};
+use crate::temporal::time::WorldTime;
+
+/// Rule describing when a recurring event fires again.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Recurrence {
+    /// Fires every `n` ticks. An interval of zero is treated as one tick.
+    EveryTicks(u64),
+    /// Fires once per in-game day when the clock reaches `hour:00`.
+    DailyAt { hour: u8 },
+}
+
+/// Event template re-armed by the `EventQueue` every time it fires.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecurringEvent {
+    pub template: WorldEvent,
+    pub recurrence: Recurrence,
+    pub next_tick: u64,
+    pub occurrences: u64,
+    pub max_occurrences: Option<u64>,
+}
+
+impl Recurrence {
+    /// Number of ticks between two occurrences.
+    pub fn interval(&self) -> u64 {
+        match self {
+            Recurrence::EveryTicks(ticks) => (*ticks).max(1),
+            Recurrence::DailyAt { .. } => TICKS_PER_DAY,
+        }
+    }
+
+    /// Returns the first tick after `current_tick` at which the rule fires, given that the
+    /// world clock reads `current_time` at `current_tick`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::constants::DEFAULT_TICKS_PER_SECOND;
+    /// use entropic_world_core::events::Recurrence;
+    /// use entropic_world_core::temporal::WorldTime;
+    ///
+    /// // The default clock starts at 06:00; dawn bells at 07:00 ring one hour later.
+    /// let first = Recurrence::DailyAt { hour: 7 }.first_tick_after(0, &WorldTime::default());
+    /// assert_eq!(first, 3600 * DEFAULT_TICKS_PER_SECOND);
+    /// assert_eq!(Recurrence::EveryTicks(10).first_tick_after(5, &WorldTime::default()), 15);
+    /// ```
+    pub fn first_tick_after(&self, current_tick: u64, current_time: &WorldTime) -> u64 {
+        match self {
+            Recurrence::EveryTicks(_) => current_tick + self.interval(),
+            Recurrence::DailyAt { hour } => {
+                let now = (current_time.hour as u64 * 3600
+                    + current_time.minute as u64 * 60
+                    + current_time.second as u64)
+                    * DEFAULT_TICKS_PER_SECOND
+                    + current_time.tick;
+                let target = (*hour as u64 % 24) * 3600 * DEFAULT_TICKS_PER_SECOND;
+                let delta = (target + TICKS_PER_DAY - now) % TICKS_PER_DAY;
+                current_tick + if delta == 0 { TICKS_PER_DAY } else { delta }
+            }
+        }
+    }
+}
+
+impl RecurringEvent {
+    /// Creates a recurring event firing first at `first_tick` and then according to
+    /// `recurrence`, without an occurrence limit.
+    pub fn new(template: WorldEvent, recurrence: Recurrence, first_tick: u64) -> Self {
+        Self {
+            template,
+            recurrence,
+            next_tick: first_tick,
+            occurrences: 0,
+            max_occurrences: None,
+        }
+    }
+
+    /// Stops the event after it has fired `max_occurrences` times.
+    pub fn with_max_occurrences(mut self, max_occurrences: u64) -> Self {
+        self.max_occurrences = Some(max_occurrences);
+        self
+    }
+
+    /// Reports whether the event has fired as often as allowed.
+    pub fn is_exhausted(&self) -> bool {
+        self.max_occurrences
+            .is_some_and(|max| self.occurrences >= max)
+    }
+
+    /// Produces the occurrence due at `next_tick` and re-arms the event.
+    ///
+    /// Each occurrence is a copy of the template whose id is suffixed with the tick it
+    /// fires on, so occurrences stay distinguishable in the event history.
+    pub fn fire(&mut self) -> WorldEvent {
+        let mut event = self.template.clone();
+        event.id = format!("{}_{}", self.template.id, self.next_tick);
+        event.source = EventSource::Recurring(self.template.id.clone());
+        self.occurrences += 1;
+        self.next_tick += self.recurrence.interval();
+        event
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::event::EventType;
+
+    fn template() -> WorldEvent {
+        WorldEvent::new(
+            "market_day".to_string(),
+            EventType::Custom("market".to_string()),
+            WorldTime::default(),
+            (0.0, 0.0),
+            "Market day".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_fire_rearms() {
+        let mut recurring = RecurringEvent::new(template(), Recurrence::EveryTicks(5), 10)
+            .with_max_occurrences(2);
+
+        let first = recurring.fire();
+        assert_eq!(first.id, "market_day_10");
+        assert_eq!(first.source, EventSource::Recurring("market_day".to_string()));
+        assert_eq!(recurring.next_tick, 15);
+        assert!(!recurring.is_exhausted());
+        assert_eq!(recurring.fire().id, "market_day_15");
+        assert!(recurring.is_exhausted());
+    }
+
+    #[test]
+    fn test_daily_first_tick() {
+        let evening = WorldTime::new(1, 1, 1, 18, 0, 0);
+        let midnight = Recurrence::DailyAt { hour: 0 };
+        assert_eq!(midnight.first_tick_after(100, &evening), 100 + 6 * 3600 * DEFAULT_TICKS_PER_SECOND);
+
+        let at_six = Recurrence::DailyAt { hour: 6 };
+        assert_eq!(at_six.first_tick_after(0, &WorldTime::default()), TICKS_PER_DAY);
+        assert_eq!(at_six.interval(), TICKS_PER_DAY);
+    }
+
+    #[test]
+    fn test_zero_interval() {
+        assert_eq!(Recurrence::EveryTicks(0).interval(), 1);
+    }
+}
@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use crate::events::event::WorldEvent;
+use crate::events::history::EventHistory;
+
+/// Events dispatched by a world, with the tick each was dispatched on, in dispatch order.
+///
+/// Saved next to a world snapshot, the log lets the world be rebuilt by replaying it from
+/// the snapshot instead of storing a full dump of every later state.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EventLog {
+    entries: Vec<(u64, WorldEvent)>,
+}
+
+impl EventLog {
+    /// Creates an empty log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a log from the events `history` retains after `tick`, typically the tick of
+    /// the snapshot the log will be replayed from.
+    ///
+    /// The history must still retain every event since that tick for the replay to be
+    /// complete.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::World;
+    /// use entropic_world_core::events::{EventLog, EventType, WorldEvent};
+    ///
+    /// let mut world = World::new("W".into(), "dna".into(), 1, 1);
+    /// let snapshot = world.clone();
+    /// let storm = WorldEvent::new("storm".into(), EventType::Disaster, world.current_time, (0.0, 0.0), "Storm".into());
+    /// world.event_queue.schedule(2, storm);
+    /// world.advance_tick();
+    /// world.advance_tick();
+    ///
+    /// let log = EventLog::from_history(&world.event_history, snapshot.current_tick);
+    /// let mut replayed = snapshot;
+    /// replayed.replay_until(&log, world.current_tick);
+    /// assert_eq!(replayed.event_history.last().unwrap().id, "storm");
+    /// ```
+    pub fn from_history(history: &EventHistory, tick: u64) -> Self {
+        Self {
+            entries: history
+                .iter_with_ticks()
+                .filter(|(event_tick, _)| *event_tick > tick)
+                .map(|(event_tick, event)| (event_tick, event.clone()))
+                .collect(),
+        }
+    }
+
+    /// Appends an event dispatched on `tick`. Entries must be pushed in dispatch order;
+    /// an entry with an earlier tick than the last one is filed under the last tick.
+    pub fn push(&mut self, tick: u64, event: WorldEvent) {
+        let tick = tick.max(self.last_tick().unwrap_or(0));
+        self.entries.push((tick, event));
+    }
+
+    /// Logged events dispatched on `tick`, in dispatch order.
+    pub fn events_at(&self, tick: u64) -> impl Iterator<Item = &WorldEvent> {
+        let start = self.entries.partition_point(|(t, _)| *t < tick);
+        let end = self.entries.partition_point(|(t, _)| *t <= tick);
+        self.entries[start..end].iter().map(|(_, event)| event)
+    }
+
+    /// Every logged entry, in dispatch order.
+    pub fn entries(&self) -> &[(u64, WorldEvent)] {
+        &self.entries
+    }
+
+    /// Tick of the last logged event.
+    pub fn last_tick(&self) -> Option<u64> {
+        self.entries.last().map(|(tick, _)| *tick)
+    }
+
+    /// Number of logged events.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Reports whether the log is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::event::EventType;
+    use crate::temporal::time::WorldTime;
+
+    fn event(id: &str) -> WorldEvent {
+        WorldEvent::new(
+            id.to_string(),
+            EventType::Discovery,
+            WorldTime::default(),
+            (0.0, 0.0),
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn test_from_history_skips_snapshot_ticks() {
+        let mut history = EventHistory::new();
+        history.record(1, event("a"));
+        history.record(3, event("b"));
+        history.record(3, event("c"));
+
+        let log = EventLog::from_history(&history, 1);
+        assert_eq!(log.len(), 2);
+        assert_eq!(log.last_tick(), Some(3));
+        let ids: Vec<&str> = log.events_at(3).map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "c"]);
+        assert_eq!(log.events_at(2).count(), 0);
+    }
+
+    #[test]
+    fn test_push_keeps_ticks_ordered() {
+        let mut log = EventLog::new();
+        log.push(4, event("a"));
+        log.push(2, event("b"));
+        assert_eq!(log.entries()[1].0, 4);
+
+        let json = serde_json::to_string(&log).unwrap();
+        let restored: EventLog = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.events_at(4).count(), 2);
+    }
+}
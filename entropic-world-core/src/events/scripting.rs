@@ -0,0 +1,423 @@
+//! Event handlers and trigger conditions written in Rhai.
+//!
+//! Scripts run in a sandbox: they cannot load modules, call `eval` or print, and every run
+//! is limited in operations, call depth and data size. They see the world through a
+//! read-only `world` view captured when the script runs, and handlers act on the event
+//! being dispatched through `ctx`.
+//!
+//! Handler scripts get these variables:
+//!
+//! - `event`: map with `id`, `kind`, `description`, `x`, `y`, `priority` and `entities`.
+//! - `world`: `tick`, `year`, `month`, `day`, `hour`, `season`, `living_npcs`,
+//!   `has_tag(tag)`, `species_population(id)`, `settlement_population(id)`,
+//!   `settlement_happiness(id)` and `faction_stance(a, b)`.
+//! - `ctx`: `emit(id, kind, description)`, `schedule(delay, id, kind, description)`,
+//!   `cancel(id)`, `consume()` and `set_outcome(outcome)`.
+//!
+//! Condition scripts get `world` and must evaluate to a boolean.
+
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Array, Dynamic, Engine, Map, Scope, AST};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+use crate::errors::{Result, WorldError};
+use crate::events::event::{EventType, WorldEvent};
+use crate::events::event_bus::EventContext;
+use crate::events::trigger_engine::faction_relationship;
+use crate::population::faction::{Faction, FactionId};
+use crate::temporal::seasons::Season;
+use crate::world::World;
+
+/// Default number of operations a single script run may perform before it is aborted.
+pub const DEFAULT_MAX_SCRIPT_OPERATIONS: u64 = 100_000;
+
+/// Sandboxed Rhai engine compiling scripts into event handlers and trigger conditions.
+///
+/// Compiled handlers and conditions share the engine and report runtime errors to it;
+/// a failing handler has no effect and a failing condition does not hold.
+#[derive(Clone)]
+pub struct ScriptEngine {
+    engine: Arc<Engine>,
+    errors: Arc<Mutex<Vec<String>>>,
+}
+
+/// Read-only view of the world exposed to scripts as `world`.
+#[derive(Clone, Debug)]
+struct ScriptWorld {
+    tick: i64,
+    year: i64,
+    month: i64,
+    day: i64,
+    hour: i64,
+    season: String,
+    living_npcs: i64,
+    tags: BTreeSet<String>,
+    species: HashMap<String, i64>,
+    settlements: HashMap<String, (i64, f64)>,
+    factions: HashMap<FactionId, Faction>,
+}
+
+/// Actions requested by a handler script through `ctx`.
+#[derive(Clone, Debug, Default)]
+struct ScriptActions {
+    emitted: Vec<(String, String, String)>,
+    scheduled: Vec<(i64, String, String, String)>,
+    cancelled: Vec<String>,
+    consumed: bool,
+    outcome: Option<String>,
+}
+
+impl ScriptEngine {
+    /// Creates a sandboxed engine limited to `DEFAULT_MAX_SCRIPT_OPERATIONS` per run.
+    pub fn new() -> Self {
+        Self::with_max_operations(DEFAULT_MAX_SCRIPT_OPERATIONS)
+    }
+
+    /// Creates a sandboxed engine aborting any script run after `max_operations`.
+    pub fn with_max_operations(max_operations: u64) -> Self {
+        let mut engine = Engine::new();
+        engine.set_max_operations(max_operations);
+        engine.set_max_call_levels(32);
+        engine.set_max_expr_depths(64, 32);
+        engine.set_max_string_size(4096);
+        engine.set_max_array_size(1024);
+        engine.set_max_map_size(1024);
+        engine.set_module_resolver(DummyModuleResolver::new());
+        engine.disable_symbol("eval");
+        engine.on_print(|_| {});
+        engine.on_debug(|_, _, _| {});
+        register_world_api(&mut engine);
+
+        Self {
+            engine: Arc::new(engine),
+            errors: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Compiles `source` into a handler for `EventBus::subscribe`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::World;
+    /// use entropic_world_core::events::{EventType, ScriptEngine, WorldEvent};
+    ///
+    /// let scripts = ScriptEngine::new();
+    /// let handler = scripts
+    ///     .compile_handler(r#"
+    ///         if event.kind == "Disaster" && world.tick > 0 {
+    ///             ctx.emit(event.id + "_relief", "relief", "Aid arrives");
+    ///         }
+    ///     "#)
+    ///     .unwrap();
+    ///
+    /// let mut world = World::new("W".into(), "dna".into(), 1, 1);
+    /// world.event_bus.subscribe(EventType::Disaster, handler);
+    /// let quake = WorldEvent::new("quake".into(), EventType::Disaster, world.current_time, (0.0, 0.0), "Quake".into());
+    /// world.event_queue.schedule(1, quake);
+    /// world.advance_tick();
+    /// assert_eq!(world.event_history.last().unwrap().id, "quake_relief");
+    /// ```
+    pub fn compile_handler(
+        &self,
+        source: &str,
+    ) -> Result<impl Fn(&World, &WorldEvent, &mut EventContext) + Send + Sync + 'static> {
+        let ast = self.compile(source)?;
+        let scripts = self.clone();
+        Ok(move |world: &World, event: &WorldEvent, context: &mut EventContext| {
+            scripts.run_handler(&ast, world, event, context);
+        })
+    }
+
+    /// Compiles `source` into a predicate for `TriggerEngine::register_condition`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::World;
+    /// use entropic_world_core::events::ScriptEngine;
+    ///
+    /// let scripts = ScriptEngine::new();
+    /// let condition = scripts.compile_condition(r#"world.has_tag("war") && world.tick >= 3"#).unwrap();
+    ///
+    /// let mut world = World::new("W".into(), "dna".into(), 1, 1);
+    /// world.add_tag("war");
+    /// assert!(!condition(&world));
+    /// world.current_tick = 3;
+    /// assert!(condition(&world));
+    /// ```
+    pub fn compile_condition(&self, source: &str) -> Result<impl Fn(&World) -> bool + Send + Sync + 'static> {
+        let ast = self.compile(source)?;
+        let scripts = self.clone();
+        Ok(move |world: &World| {
+            let mut scope = Scope::new();
+            scope.push("world", ScriptWorld::capture(world));
+            match scripts.engine.eval_ast_with_scope::<bool>(&mut scope, &ast) {
+                Ok(holds) => holds,
+                Err(error) => {
+                    scripts.report(error.to_string());
+                    false
+                }
+            }
+        })
+    }
+
+    /// Takes the runtime errors reported by compiled scripts since the last call.
+    pub fn take_errors(&self) -> Vec<String> {
+        self.errors
+            .lock()
+            .map(|mut errors| std::mem::take(&mut *errors))
+            .unwrap_or_default()
+    }
+
+    fn compile(&self, source: &str) -> Result<AST> {
+        self.engine
+            .compile(source)
+            .map_err(|error| WorldError::ScriptError(error.to_string()))
+    }
+
+    fn report(&self, error: String) {
+        if let Ok(mut errors) = self.errors.lock() {
+            errors.push(error);
+        }
+    }
+
+    fn run_handler(&self, ast: &AST, world: &World, event: &WorldEvent, context: &mut EventContext) {
+        let mut scope = Scope::new();
+        scope.push("world", ScriptWorld::capture(world));
+        scope.push_constant("event", event_map(event));
+        scope.push("ctx", ScriptActions::default());
+        if let Err(error) = self.engine.run_ast_with_scope(&mut scope, ast) {
+            self.report(error.to_string());
+            return;
+        }
+        let Some(actions) = scope.get_value::<ScriptActions>("ctx") else {
+            return;
+        };
+
+        for (id, kind, description) in actions.emitted {
+            context.emit(WorldEvent::new(
+                id,
+                EventType::from_name(&kind),
+                world.current_time,
+                event.location,
+                description,
+            ));
+        }
+        for (delay, id, kind, description) in actions.scheduled {
+            let event = WorldEvent::new(
+                id,
+                EventType::from_name(&kind),
+                world.current_time,
+                event.location,
+                description,
+            );
+            context.schedule_after(delay.max(0) as u64, event);
+        }
+        for id in actions.cancelled {
+            context.cancel(id);
+        }
+        if let Some(outcome) = actions.outcome {
+            context.set_outcome(outcome);
+        }
+        if actions.consumed {
+            context.consume();
+        }
+    }
+}
+
+impl Default for ScriptEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for ScriptEngine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScriptEngine").finish_non_exhaustive()
+    }
+}
+
+impl ScriptWorld {
+    fn capture(world: &World) -> Self {
+        Self {
+            tick: world.current_tick as i64,
+            year: world.current_time.year as i64,
+            month: world.current_time.month as i64,
+            day: world.current_time.day as i64,
+            hour: world.current_time.hour as i64,
+            season: format!("{:?}", Season::from_month(world.current_time.month)),
+            living_npcs: world.npcs.values().filter(|npc| npc.is_alive()).count() as i64,
+            tags: world.tags.clone(),
+            species: world
+                .animal_populations
+                .totals()
+                .into_iter()
+                .map(|(species, count)| (species, count as i64))
+                .collect(),
+            settlements: world
+                .settlements
+                .iter()
+                .map(|(id, settlement)| (id.clone(), (settlement.population as i64, settlement.happiness as f64)))
+                .collect(),
+            factions: world.factions.clone(),
+        }
+    }
+}
+
+impl ScriptActions {
+    fn emit(&mut self, id: &str, kind: &str, description: &str) {
+        self.emitted.push((id.to_string(), kind.to_string(), description.to_string()));
+    }
+
+    fn schedule(&mut self, delay: i64, id: &str, kind: &str, description: &str) {
+        self.scheduled
+            .push((delay, id.to_string(), kind.to_string(), description.to_string()));
+    }
+}
+
+fn register_world_api(engine: &mut Engine) {
+    engine
+        .register_type_with_name::<ScriptWorld>("World")
+        .register_get("tick", |world: &mut ScriptWorld| world.tick)
+        .register_get("year", |world: &mut ScriptWorld| world.year)
+        .register_get("month", |world: &mut ScriptWorld| world.month)
+        .register_get("day", |world: &mut ScriptWorld| world.day)
+        .register_get("hour", |world: &mut ScriptWorld| world.hour)
+        .register_get("season", |world: &mut ScriptWorld| world.season.clone())
+        .register_get("living_npcs", |world: &mut ScriptWorld| world.living_npcs)
+        .register_fn("has_tag", |world: &mut ScriptWorld, tag: &str| world.tags.contains(tag))
+        .register_fn("species_population", |world: &mut ScriptWorld, id: &str| {
+            world.species.get(id).copied().unwrap_or(0)
+        })
+        .register_fn("settlement_population", |world: &mut ScriptWorld, id: &str| {
+            world.settlements.get(id).map_or(0, |(population, _)| *population)
+        })
+        .register_fn("settlement_happiness", |world: &mut ScriptWorld, id: &str| {
+            world.settlements.get(id).map_or(0.0, |(_, happiness)| *happiness)
+        })
+        .register_fn("faction_stance", |world: &mut ScriptWorld, a: &str, b: &str| {
+            world
+                .factions
+                .get(a)
+                .map_or(0.0, |faction| faction_relationship(faction, &b.to_string()) as f64)
+        });
+
+    engine
+        .register_type_with_name::<ScriptActions>("Context")
+        .register_fn("emit", ScriptActions::emit)
+        .register_fn("schedule", ScriptActions::schedule)
+        .register_fn("cancel", |actions: &mut ScriptActions, id: &str| {
+            actions.cancelled.push(id.to_string());
+        })
+        .register_fn("consume", |actions: &mut ScriptActions| actions.consumed = true)
+        .register_fn("set_outcome", |actions: &mut ScriptActions, outcome: &str| {
+            actions.outcome = Some(outcome.to_string());
+        });
+}
+
+fn event_map(event: &WorldEvent) -> Map {
+    let entities: Array = event
+        .involved_entities
+        .iter()
+        .map(|entity| Dynamic::from(entity.clone()))
+        .collect();
+    let mut map = Map::new();
+    map.insert("id".into(), event.id.clone().into());
+    map.insert("kind".into(), event.event_type.name().to_string().into());
+    map.insert("description".into(), event.description.clone().into());
+    map.insert("x".into(), (event.location.0 as f64).into());
+    map.insert("y".into(), (event.location.1 as f64).into());
+    map.insert("priority".into(), (event.priority as i64).into());
+    map.insert("entities".into(), entities.into());
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{EventTrigger, TriggerCondition};
+
+    fn world() -> World {
+        World::new("W".to_string(), "dna".to_string(), 1, 1)
+    }
+
+    fn event(id: &str, event_type: EventType) -> WorldEvent {
+        WorldEvent::new(
+            id.to_string(),
+            event_type,
+            Default::default(),
+            (3.0, 4.0),
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn test_handler_actions() {
+        let scripts = ScriptEngine::new();
+        let handler = scripts
+            .compile_handler(
+                r#"
+                ctx.schedule(5, "aftershock", "Disaster", "Aftershock");
+                ctx.cancel("picnic");
+                ctx.set_outcome(if event.x > 1.0 { "east" } else { "west" });
+                ctx.consume();
+                "#,
+            )
+            .unwrap();
+
+        let world = world();
+        let mut context = EventContext::new(10);
+        handler(&world, &event("quake", EventType::Disaster), &mut context);
+
+        assert_eq!(context.outcome(), Some("east"));
+        assert!(context.is_consumed());
+        assert_eq!(context.cancelled(), ["picnic".to_string()]);
+        let (emitted, scheduled) = context.into_parts();
+        assert!(emitted.is_empty());
+        assert_eq!(scheduled[0].0, 15);
+        assert_eq!(scheduled[0].1.event_type, EventType::Disaster);
+        assert_eq!(scheduled[0].1.location, (3.0, 4.0));
+    }
+
+    #[test]
+    fn test_script_condition_drives_trigger() {
+        let scripts = ScriptEngine::new();
+        let mut world = world();
+        world
+            .triggers
+            .register_condition("famine", scripts.compile_condition("world.living_npcs == 0 && world.tick >= 2").unwrap());
+        world.add_trigger(EventTrigger::new(
+            "famine".to_string(),
+            TriggerCondition::Custom("famine".to_string()),
+            "famine".to_string(),
+        ).once());
+
+        world.advance_tick();
+        assert!(world.event_history.is_empty());
+        world.advance_tick();
+        assert_eq!(world.event_history.len(), 1);
+    }
+
+    #[test]
+    fn test_sandbox_limits_and_errors() {
+        let scripts = ScriptEngine::with_max_operations(1_000);
+        assert!(matches!(
+            scripts.compile_handler("let x = ;"),
+            Err(WorldError::ScriptError(_))
+        ));
+        assert!(scripts.compile_condition(r#"eval("true")"#).is_err());
+
+        let runaway = scripts.compile_condition("loop {}").unwrap();
+        assert!(!runaway(&world()));
+        let not_bool = scripts.compile_condition("42").unwrap();
+        assert!(!not_bool(&world()));
+
+        let missing = scripts.compile_handler(r#"import "fs" as fs;"#).unwrap();
+        let mut context = EventContext::new(0);
+        missing(&world(), &event("e", EventType::Discovery), &mut context);
+
+        assert_eq!(scripts.take_errors().len(), 3);
+        assert!(scripts.take_errors().is_empty());
+    }
+}
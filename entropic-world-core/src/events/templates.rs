@@ -0,0 +1,232 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use crate::events::event::EventType;
+use crate::events::triggers::TriggerCondition;
+use crate::spatial::terrain::Biome;
+use crate::temporal::seasons::Season;
+use crate::world::WorldRng;
+
+/// Multiplies a template's spawn weight when its context matches.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum WeightModifier {
+    /// Applies when the event would take place in a chunk of `biome`.
+    Biome { biome: Biome, factor: f32 },
+    /// Applies during `season`.
+    Season { season: Season, factor: f32 },
+    /// Applies while the world carries `tag`.
+    Tag { tag: String, factor: f32 },
+    /// Applies while `condition` holds, for example a faction stance or population threshold.
+    Condition { condition: TriggerCondition, factor: f32 },
+}
+
+/// Blueprint of an event the world can spawn on its own.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EventTemplate {
+    pub id: String,
+    pub event_type: EventType,
+    pub description: String,
+    pub base_weight: f32,
+    #[serde(default)]
+    pub modifiers: Vec<WeightModifier>,
+    #[serde(default)]
+    pub required_tags: Vec<String>,
+}
+
+/// Context an event would spawn in, used to weigh templates.
+#[derive(Clone, Copy, Debug)]
+pub struct SpawnContext<'a> {
+    pub biome: Option<Biome>,
+    pub season: Season,
+    pub tags: &'a BTreeSet<String>,
+}
+
+/// Set of event templates sampled by weight.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EventTemplateRegistry {
+    templates: Vec<EventTemplate>,
+}
+
+impl EventTemplate {
+    /// Creates a template with the given base spawn weight and no modifiers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::events::{EventTemplate, EventType, WeightModifier};
+    /// use entropic_world_core::spatial::Biome;
+    ///
+    /// let wildfire = EventTemplate::new("wildfire", EventType::Disaster, "A wildfire spreads", 1.0)
+    ///     .with_modifier(WeightModifier::Biome { biome: Biome::Forest, factor: 4.0 })
+    ///     .with_modifier(WeightModifier::Biome { biome: Biome::Ocean, factor: 0.0 });
+    /// assert_eq!(wildfire.modifiers.len(), 2);
+    /// ```
+    pub fn new(
+        id: impl Into<String>,
+        event_type: EventType,
+        description: impl Into<String>,
+        base_weight: f32,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            event_type,
+            description: description.into(),
+            base_weight: base_weight.max(0.0),
+            modifiers: Vec::new(),
+            required_tags: Vec::new(),
+        }
+    }
+
+    /// Adds a weight modifier.
+    pub fn with_modifier(mut self, modifier: WeightModifier) -> Self {
+        self.modifiers.push(modifier);
+        self
+    }
+
+    /// Restricts the template to worlds carrying `tag`.
+    pub fn requires_tag(mut self, tag: impl Into<String>) -> Self {
+        self.required_tags.push(tag.into());
+        self
+    }
+
+    /// Spawn weight of the template in `context`.
+    ///
+    /// The base weight is multiplied by the factor of every matching modifier, and is
+    /// zero if a required tag is missing. `condition_met` evaluates the conditions of
+    /// `WeightModifier::Condition`.
+    pub fn weight<F>(&self, context: &SpawnContext, condition_met: F) -> f32
+    where
+        F: Fn(&TriggerCondition) -> bool,
+    {
+        if !self.required_tags.iter().all(|tag| context.tags.contains(tag)) {
+            return 0.0;
+        }
+
+        let mut weight = self.base_weight;
+        for modifier in &self.modifiers {
+            let (applies, factor) = match modifier {
+                WeightModifier::Biome { biome, factor } => (context.biome == Some(*biome), factor),
+                WeightModifier::Season { season, factor } => (context.season == *season, factor),
+                WeightModifier::Tag { tag, factor } => (context.tags.contains(tag), factor),
+                WeightModifier::Condition { condition, factor } => (condition_met(condition), factor),
+            };
+            if applies {
+                weight *= factor.max(0.0);
+            }
+        }
+        weight
+    }
+}
+
+impl EventTemplateRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a template, replacing any template with the same id.
+    pub fn register(&mut self, template: EventTemplate) {
+        self.templates.retain(|existing| existing.id != template.id);
+        self.templates.push(template);
+    }
+
+    /// Removes and returns the template with the given id.
+    pub fn remove(&mut self, template_id: &str) -> Option<EventTemplate> {
+        let index = self.templates.iter().position(|t| t.id == template_id)?;
+        Some(self.templates.remove(index))
+    }
+
+    /// Registered templates, in registration order.
+    pub fn templates(&self) -> &[EventTemplate] {
+        &self.templates
+    }
+
+    /// Number of registered templates.
+    pub fn len(&self) -> usize {
+        self.templates.len()
+    }
+
+    /// Reports whether no templates are registered.
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+
+    /// Samples a template with probability proportional to its weight in `context`.
+    /// Returns `None` if every template has zero weight.
+    pub fn sample<F>(&self, context: &SpawnContext, condition_met: F, rng: &mut WorldRng) -> Option<&EventTemplate>
+    where
+        F: Fn(&TriggerCondition) -> bool,
+    {
+        let weights: Vec<f32> = self
+            .templates
+            .iter()
+            .map(|template| template.weight(context, &condition_met))
+            .collect();
+        rng.choose_weighted(&weights).map(|index| &self.templates[index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(biome: Biome, season: Season, tags: &BTreeSet<String>) -> SpawnContext<'_> {
+        SpawnContext {
+            biome: Some(biome),
+            season,
+            tags,
+        }
+    }
+
+    #[test]
+    fn test_template_weight_modifiers() {
+        let tags: BTreeSet<String> = ["war".to_string()].into_iter().collect();
+        let template = EventTemplate::new("blizzard", EventType::Disaster, "Blizzard", 2.0)
+            .with_modifier(WeightModifier::Season { season: Season::Winter, factor: 5.0 })
+            .with_modifier(WeightModifier::Biome { biome: Biome::Desert, factor: 0.0 })
+            .with_modifier(WeightModifier::Tag { tag: "war".to_string(), factor: 0.5 });
+
+        assert_eq!(template.weight(&context(Biome::Tundra, Season::Winter, &tags), |_| true), 5.0);
+        assert_eq!(template.weight(&context(Biome::Tundra, Season::Summer, &tags), |_| true), 1.0);
+        assert_eq!(template.weight(&context(Biome::Desert, Season::Winter, &tags), |_| true), 0.0);
+    }
+
+    #[test]
+    fn test_required_tags_and_conditions() {
+        let empty = BTreeSet::new();
+        let template = EventTemplate::new("uprising", EventType::FactionWar, "Uprising", 1.0)
+            .requires_tag("tyranny")
+            .with_modifier(WeightModifier::Condition {
+                condition: TriggerCondition::TimeElapsed(10),
+                factor: 3.0,
+            });
+        assert_eq!(template.weight(&context(Biome::Plains, Season::Spring, &empty), |_| true), 0.0);
+
+        let tags: BTreeSet<String> = ["tyranny".to_string()].into_iter().collect();
+        assert_eq!(template.weight(&context(Biome::Plains, Season::Spring, &tags), |_| true), 3.0);
+        assert_eq!(template.weight(&context(Biome::Plains, Season::Spring, &tags), |_| false), 1.0);
+    }
+
+    #[test]
+    fn test_registry_sampling() {
+        let mut registry = EventTemplateRegistry::new();
+        registry.register(
+            EventTemplate::new("flood", EventType::Disaster, "Flood", 1.0)
+                .with_modifier(WeightModifier::Biome { biome: Biome::Desert, factor: 0.0 }),
+        );
+        registry.register(EventTemplate::new("caravan", EventType::TradeCompleted, "Caravan", 1.0));
+
+        let tags = BTreeSet::new();
+        let mut rng = WorldRng::new(11);
+        for _ in 0..50 {
+            let template = registry
+                .sample(&context(Biome::Desert, Season::Summer, &tags), |_| true, &mut rng)
+                .unwrap();
+            assert_eq!(template.id, "caravan");
+        }
+
+        registry.remove("caravan");
+        assert!(registry
+            .sample(&context(Biome::Desert, Season::Summer, &tags), |_| true, &mut rng)
+            .is_none());
+    }
+}
@@ -0,0 +1,386 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use crate::events::event::{EventSource, EventType, WorldEvent};
+use crate::events::triggers::{EventTrigger, TriggerCondition, WorldMetric};
+use crate::population::{Faction, FactionId};
+use crate::world::World;
+
+/// Predicate evaluated for `TriggerCondition::Custom` conditions registered under a name.
+pub type CustomCondition = Arc<dyn Fn(&World) -> bool + Send + Sync>;
+
+/// Evaluates registered triggers against world state and produces the events they fire.
+///
+/// Triggers are serialized with the world; custom condition predicates are closures and
+/// must be registered again after a world is loaded. Until then, custom conditions
+/// evaluate to `false`.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct TriggerEngine {
+    triggers: Vec<EventTrigger>,
+    #[serde(skip)]
+    custom_conditions: HashMap<String, CustomCondition>,
+}
+
+impl TriggerEngine {
+    /// Creates an engine without triggers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a trigger, replacing any trigger with the same id.
+    pub fn add_trigger(&mut self, trigger: EventTrigger) {
+        self.triggers.retain(|existing| existing.id != trigger.id);
+        self.triggers.push(trigger);
+    }
+
+    /// Removes and returns the trigger with the given id.
+    pub fn remove_trigger(&mut self, trigger_id: &str) -> Option<EventTrigger> {
+        let index = self.triggers.iter().position(|t| t.id == trigger_id)?;
+        Some(self.triggers.remove(index))
+    }
+
+    /// Returns the trigger with the given id.
+    pub fn get_trigger(&self, trigger_id: &str) -> Option<&EventTrigger> {
+        self.triggers.iter().find(|t| t.id == trigger_id)
+    }
+
+    /// Returns a mutable reference to the trigger with the given id.
+    pub fn get_trigger_mut(&mut self, trigger_id: &str) -> Option<&mut EventTrigger> {
+        self.triggers.iter_mut().find(|t| t.id == trigger_id)
+    }
+
+    /// Registered triggers, in registration order.
+    pub fn triggers(&self) -> &[EventTrigger] {
+        &self.triggers
+    }
+
+    /// Registers the predicate evaluated for `TriggerCondition::Custom(name)`.
+    pub fn register_condition<F>(&mut self, name: impl Into<String>, predicate: F)
+    where
+        F: Fn(&World) -> bool + Send + Sync + 'static,
+    {
+        self.custom_conditions.insert(name.into(), Arc::new(predicate));
+    }
+
+    /// Reports whether `condition` holds in `world`.
+    ///
+    /// - `TimeElapsed(ticks)`: the world has reached tick `ticks`.
+    /// - `PopulationThreshold`: the settlement's population is at least `threshold`.
+    /// - `ResourceDepletion`: the combined market supply of the resource whose name
+    ///   matches `resource` (case-insensitively) is at most `threshold`.
+    /// - `EntityDeath(id)`: the entity or NPC with that id exists and is dead.
+    /// - `FactionRelationship`: the relationship between the two factions is at most
+    ///   `threshold`, scoring allies `1.0`, enemies `-1.0` and everyone else `0.0`.
+    /// - `Custom(name)`: the predicate registered under `name` returns `true`.
+    /// - `All`, `Any` and `Not` combine nested conditions; an empty `All` holds and an
+    ///   empty `Any` does not.
+    /// - `Compare`: the metric's current value satisfies the comparison. Metrics that
+    ///   cannot be read, such as a missing settlement, never satisfy it.
+    /// - `WeatherIs`: the chunk exists and its weather condition matches.
+    pub fn condition_met(&self, condition: &TriggerCondition, world: &World) -> bool {
+        match condition {
+            TriggerCondition::TimeElapsed(ticks) => world.current_tick >= *ticks,
+            TriggerCondition::PopulationThreshold { settlement_id, threshold } => world
+                .settlements
+                .get(settlement_id)
+                .is_some_and(|settlement| settlement.population >= *threshold),
+            TriggerCondition::ResourceDepletion { resource, threshold } => {
+                let supply: u32 = world
+                    .markets
+                    .values()
+                    .flat_map(|market| market.supply.iter())
+                    .filter(|(resource_type, _)| resource_type.name().eq_ignore_ascii_case(resource))
+                    .map(|(_, amount)| *amount)
+                    .sum();
+                supply <= *threshold
+            }
+            TriggerCondition::EntityDeath(id) => {
+                if let Some(entity) = world.entities.get(id) {
+                    !entity.is_alive
+                } else {
+                    world.npcs.get(id).is_some_and(|npc| !npc.is_alive())
+                }
+            }
+            TriggerCondition::FactionRelationship { faction_a, faction_b, threshold } => {
+                match world.factions.get(faction_a) {
+                    Some(faction) => faction_relationship(faction, faction_b) <= *threshold,
+                    None => false,
+                }
+            }
+            TriggerCondition::Custom(name) => self
+                .custom_conditions
+                .get(name)
+                .is_some_and(|predicate| predicate(world)),
+            TriggerCondition::All(conditions) => {
+                conditions.iter().all(|condition| self.condition_met(condition, world))
+            }
+            TriggerCondition::Any(conditions) => {
+                conditions.iter().any(|condition| self.condition_met(condition, world))
+            }
+            TriggerCondition::Not(condition) => !self.condition_met(condition, world),
+            TriggerCondition::Compare { metric, comparison, value } => {
+                metric_value(metric, world).is_some_and(|current| comparison.holds(current, *value))
+            }
+            TriggerCondition::WeatherIs { chunk, condition } => world
+                .chunks
+                .get(chunk)
+                .is_some_and(|chunk| chunk.weather.condition == *condition),
+        }
+    }
+
+    /// Evaluates every ready trigger against `world` and returns the events fired.
+    ///
+    /// Triggers are checked in registration order. A fired trigger records the tick,
+    /// enters its cooldown, and deactivates if it is one-shot. Each fired event has
+    /// `EventType::Custom` set to the trigger's event template.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::World;
+    /// use entropic_world_core::events::{EventTrigger, TriggerCondition, TriggerEngine};
+    ///
+    /// let world = World::new("W".into(), "dna".into(), 1, 1);
+    /// let mut engine = TriggerEngine::new();
+    /// engine.add_trigger(
+    ///     EventTrigger::new("start".into(), TriggerCondition::TimeElapsed(0), "intro".into()).once(),
+    /// );
+    /// assert_eq!(engine.evaluate(&world).len(), 1);
+    /// assert!(engine.evaluate(&world).is_empty());
+    /// ```
+    pub fn evaluate(&mut self, world: &World) -> Vec<WorldEvent> {
+        let tick = world.current_tick;
+        let ready: Vec<usize> = self
+            .triggers
+            .iter()
+            .enumerate()
+            .filter(|(_, trigger)| trigger.is_ready(tick))
+            .filter(|(_, trigger)| self.condition_met(&trigger.condition, world))
+            .map(|(index, _)| index)
+            .collect();
+
+        let mut fired = Vec::new();
+        for index in ready {
+            let trigger = &mut self.triggers[index];
+            trigger.record_fire(tick);
+            fired.push(WorldEvent::new(
+                format!("trigger_{}_{}", trigger.id, tick),
+                EventType::Custom(trigger.event_template.clone()),
+                world.current_time,
+                (0.0, 0.0),
+                format!("Trigger {} fired", trigger.id),
+            )
+            .with_source(EventSource::Trigger(trigger.id.clone())));
+        }
+        fired
+    }
+
+    /// Number of registered triggers.
+    pub fn len(&self) -> usize {
+        self.triggers.len()
+    }
+
+    /// Reports whether no triggers are registered.
+    pub fn is_empty(&self) -> bool {
+        self.triggers.is_empty()
+    }
+}
+
+/// Reads the current value of `metric` from `world`, or `None` if what it refers to
+/// does not exist.
+pub fn metric_value(metric: &WorldMetric, world: &World) -> Option<f64> {
+    let value = match metric {
+        WorldMetric::CurrentTick => world.current_tick as f64,
+        WorldMetric::LivingNpcs => world.npcs.values().filter(|npc| npc.is_alive()).count() as f64,
+        WorldMetric::SettlementPopulation(id) => world.settlements.get(id)?.population as f64,
+        WorldMetric::SettlementHappiness(id) => world.settlements.get(id)?.happiness as f64,
+        WorldMetric::SpeciesPopulation(id) => {
+            if !world.species.contains_key(id) {
+                return None;
+            }
+            world.animal_populations.total(id) as f64
+        }
+        WorldMetric::MarketPrice { market_id, resource } => {
+            world.markets.get(market_id)?.get_price(resource)? as f64
+        }
+        WorldMetric::MarketSupply { market_id, resource } => {
+            *world.markets.get(market_id)?.supply.get(resource).unwrap_or(&0) as f64
+        }
+        WorldMetric::FactionStance { faction_a, faction_b } => {
+            faction_relationship(world.factions.get(faction_a)?, faction_b) as f64
+        }
+        WorldMetric::Temperature(coord) => world.chunks.get(coord)?.weather.temperature as f64,
+        WorldMetric::Precipitation(coord) => world.chunks.get(coord)?.weather.precipitation as f64,
+    };
+    Some(value)
+}
+
+/// Scores how `faction` regards `other`: `1.0` for allies, `-1.0` for enemies, `0.0` otherwise.
+pub(crate) fn faction_relationship(faction: &Faction, other: &FactionId) -> f32 {
+    if faction.is_allied_with(other) {
+        1.0
+    } else if faction.is_enemy_of(other) {
+        -1.0
+    } else {
+        0.0
+    }
+}
+
+impl fmt::Debug for TriggerEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut conditions: Vec<&String> = self.custom_conditions.keys().collect();
+        conditions.sort();
+        f.debug_struct("TriggerEngine")
+            .field("triggers", &self.triggers)
+            .field("custom_conditions", &conditions)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::economy::{Market, ResourceType, Settlement};
+    use crate::events::triggers::Comparison;
+    use crate::spatial::ChunkCoord;
+    use crate::temporal::WeatherCondition;
+
+    fn world() -> World {
+        World::new("W".to_string(), "dna".to_string(), 1, 1)
+    }
+
+    #[test]
+    fn test_cooldown_and_one_shot() {
+        let mut world = world();
+        let mut engine = TriggerEngine::new();
+        engine.add_trigger(
+            EventTrigger::new("tick".to_string(), TriggerCondition::TimeElapsed(0), "pulse".to_string())
+                .with_cooldown(3),
+        );
+        engine.add_trigger(
+            EventTrigger::new("once".to_string(), TriggerCondition::TimeElapsed(1), "intro".to_string()).once(),
+        );
+
+        let mut fired = Vec::new();
+        for tick in 0..7 {
+            world.current_tick = tick;
+            fired.extend(engine.evaluate(&world).into_iter().map(|e| e.id));
+        }
+        assert_eq!(fired, vec!["trigger_tick_0", "trigger_once_1", "trigger_tick_3", "trigger_tick_6"]);
+        assert!(!engine.get_trigger("once").unwrap().is_active());
+    }
+
+    #[test]
+    fn test_world_state_conditions() {
+        let mut world = world();
+        let mut settlement = Settlement::new(
+            "town".to_string(),
+            "Town".to_string(),
+            "faction_1".to_string(),
+            0.0,
+            0.0,
+        );
+        settlement.population = 150;
+        world.add_settlement(settlement);
+        let mut market = Market::new("market_1".to_string(), "town".to_string());
+        market.add_resource(ResourceType::Wood, 5, 0);
+        world.add_market(market);
+        let mut faction = Faction::new("faction_1".to_string(), "North".to_string(), "npc_1".to_string());
+        faction.add_enemy("faction_2".to_string());
+        world.factions.insert(faction.id.clone(), faction);
+
+        let engine = TriggerEngine::new();
+        let populous = TriggerCondition::PopulationThreshold {
+            settlement_id: "town".to_string(),
+            threshold: 100,
+        };
+        let depleted = TriggerCondition::ResourceDepletion {
+            resource: "wood".to_string(),
+            threshold: 10,
+        };
+        let hostile = TriggerCondition::FactionRelationship {
+            faction_a: "faction_1".to_string(),
+            faction_b: "faction_2".to_string(),
+            threshold: -0.5,
+        };
+        assert!(engine.condition_met(&populous, &world));
+        assert!(engine.condition_met(&depleted, &world));
+        assert!(engine.condition_met(&hostile, &world));
+        assert!(!engine.condition_met(&TriggerCondition::EntityDeath("ghost".to_string()), &world));
+    }
+
+    #[test]
+    fn test_composite_conditions() {
+        let mut world = world();
+        world.initialize_chunks();
+        let mut settlement = Settlement::new(
+            "town".to_string(),
+            "Town".to_string(),
+            "faction_1".to_string(),
+            0.0,
+            0.0,
+        );
+        settlement.population = 800;
+        world.add_settlement(settlement);
+        let mut market = Market::new("market_1".to_string(), "town".to_string());
+        market.add_resource(ResourceType::Food, 20, 50);
+        world.add_market(market);
+        world.get_chunk_mut(&ChunkCoord::new(0, 0)).unwrap().weather.condition = WeatherCondition::Snowy;
+
+        let engine = TriggerEngine::new();
+        let famine = TriggerCondition::all(vec![
+            TriggerCondition::compare(
+                WorldMetric::SettlementPopulation("town".to_string()),
+                Comparison::GreaterThan,
+                500.0,
+            ),
+            TriggerCondition::compare(
+                WorldMetric::MarketSupply { market_id: "market_1".to_string(), resource: ResourceType::Food },
+                Comparison::LessThan,
+                100.0,
+            ),
+            TriggerCondition::WeatherIs {
+                chunk: ChunkCoord::new(0, 0),
+                condition: WeatherCondition::Snowy,
+            },
+        ]);
+        assert!(engine.condition_met(&famine, &world));
+        assert!(!engine.condition_met(&TriggerCondition::negate(famine.clone()), &world));
+
+        let missing = TriggerCondition::compare(
+            WorldMetric::SettlementPopulation("nowhere".to_string()),
+            Comparison::GreaterOrEqual,
+            0.0,
+        );
+        assert!(!engine.condition_met(&missing, &world));
+        assert!(engine.condition_met(&TriggerCondition::any(vec![missing, famine]), &world));
+        assert!(engine.condition_met(&TriggerCondition::all(Vec::new()), &world));
+        assert!(!engine.condition_met(&TriggerCondition::any(Vec::new()), &world));
+    }
+
+    #[test]
+    fn test_custom_conditions() {
+        let world = world();
+        let mut engine = TriggerEngine::new();
+        let condition = TriggerCondition::Custom("always".to_string());
+        assert!(!engine.condition_met(&condition, &world));
+
+        engine.register_condition("always", |_| true);
+        assert!(engine.condition_met(&condition, &world));
+    }
+
+    #[test]
+    fn test_trigger_engine_serialization() {
+        let mut engine = TriggerEngine::new();
+        engine.add_trigger(
+            EventTrigger::new("t".to_string(), TriggerCondition::TimeElapsed(5), "e".to_string()).once(),
+        );
+        engine.register_condition("skipped", |_| true);
+
+        let json = serde_json::to_string(&engine).unwrap();
+        let restored: TriggerEngine = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.len(), 1);
+        assert!(restored.get_trigger("t").unwrap().one_shot);
+    }
+}
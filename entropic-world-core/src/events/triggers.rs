@@ -1,4 +1,9 @@
 use serde::{Deserialize, Serialize};
+use crate::economy::{MarketId, ResourceType, SettlementId};
+use crate::ecosystem::SpeciesId;
+use crate::population::FactionId;
+use crate::spatial::ChunkCoord;
+use crate::temporal::WeatherCondition;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EventTrigger {
@@ -6,6 +11,14 @@ pub struct EventTrigger {
     pub condition: TriggerCondition,
     pub event_template: String,
     pub active: bool,
+    #[serde(default)]
+    pub cooldown_ticks: u64,
+    #[serde(default)]
+    pub one_shot: bool,
+    #[serde(default)]
+    pub last_fired: Option<u64>,
+    #[serde(default)]
+    pub fire_count: u32,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -16,6 +29,94 @@ pub enum TriggerCondition {
     EntityDeath(String),
     FactionRelationship { faction_a: String, faction_b: String, threshold: f32 },
     Custom(String),
+    All(Vec<TriggerCondition>),
+    Any(Vec<TriggerCondition>),
+    Not(Box<TriggerCondition>),
+    Compare { metric: WorldMetric, comparison: Comparison, value: f64 },
+    WeatherIs { chunk: ChunkCoord, condition: WeatherCondition },
+}
+
+/// Numeric quantity read from world state by `TriggerCondition::Compare`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum WorldMetric {
+    CurrentTick,
+    LivingNpcs,
+    SettlementPopulation(SettlementId),
+    SettlementHappiness(SettlementId),
+    SpeciesPopulation(SpeciesId),
+    MarketPrice { market_id: MarketId, resource: ResourceType },
+    MarketSupply { market_id: MarketId, resource: ResourceType },
+    /// Stance of `faction_a` toward `faction_b`: `1.0` allied, `-1.0` hostile, `0.0` neutral.
+    FactionStance { faction_a: FactionId, faction_b: FactionId },
+    Temperature(ChunkCoord),
+    Precipitation(ChunkCoord),
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Comparison {
+    LessThan,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+    GreaterOrEqual,
+    GreaterThan,
+}
+
+impl TriggerCondition {
+    /// Condition holding when every one of `conditions` holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::events::{Comparison, TriggerCondition, WorldMetric};
+    ///
+    /// let famine = TriggerCondition::all(vec![
+    ///     TriggerCondition::compare(WorldMetric::SettlementPopulation("town".into()), Comparison::GreaterThan, 500.0),
+    ///     TriggerCondition::negate(TriggerCondition::compare(WorldMetric::CurrentTick, Comparison::LessThan, 100.0)),
+    /// ]);
+    /// assert!(matches!(famine, TriggerCondition::All(ref c) if c.len() == 2));
+    /// ```
+    pub fn all(conditions: Vec<TriggerCondition>) -> Self {
+        TriggerCondition::All(conditions)
+    }
+
+    /// Condition holding when at least one of `conditions` holds.
+    pub fn any(conditions: Vec<TriggerCondition>) -> Self {
+        TriggerCondition::Any(conditions)
+    }
+
+    /// Condition holding when `condition` does not.
+    pub fn negate(condition: TriggerCondition) -> Self {
+        TriggerCondition::Not(Box::new(condition))
+    }
+
+    /// Condition comparing a world metric against a constant.
+    pub fn compare(metric: WorldMetric, comparison: Comparison, value: f64) -> Self {
+        TriggerCondition::Compare { metric, comparison, value }
+    }
+}
+
+impl Comparison {
+    /// Applies the comparison as `left <op> right`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::events::Comparison;
+    ///
+    /// assert!(Comparison::LessThan.holds(1.0, 2.0));
+    /// assert!(!Comparison::GreaterOrEqual.holds(1.0, 2.0));
+    /// ```
+    pub fn holds(&self, left: f64, right: f64) -> bool {
+        match self {
+            Comparison::LessThan => left < right,
+            Comparison::LessOrEqual => left <= right,
+            Comparison::Equal => left == right,
+            Comparison::NotEqual => left != right,
+            Comparison::GreaterOrEqual => left >= right,
+            Comparison::GreaterThan => left > right,
+        }
+    }
 }
 
 impl EventTrigger {
@@ -37,6 +138,56 @@ impl EventTrigger {
             condition,
             event_template,
             active: true,
+            cooldown_ticks: 0,
+            one_shot: false,
+            last_fired: None,
+            fire_count: 0,
+        }
+    }
+
+    /// Sets the minimum number of ticks between two firings of the trigger.
+    pub fn with_cooldown(mut self, cooldown_ticks: u64) -> Self {
+        self.cooldown_ticks = cooldown_ticks;
+        self
+    }
+
+    /// Makes the trigger deactivate itself after firing once.
+    pub fn once(mut self) -> Self {
+        self.one_shot = true;
+        self
+    }
+
+    /// Reports whether the trigger is active and out of cooldown at `tick`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::events::{EventTrigger, TriggerCondition};
+    ///
+    /// let mut trigger = EventTrigger::new(
+    ///     "t1".to_string(),
+    ///     TriggerCondition::TimeElapsed(0),
+    ///     "event_template".to_string(),
+    /// )
+    /// .with_cooldown(5);
+    /// trigger.record_fire(10);
+    /// assert!(!trigger.is_ready(14));
+    /// assert!(trigger.is_ready(15));
+    /// ```
+    pub fn is_ready(&self, tick: u64) -> bool {
+        match self.last_fired {
+            _ if !self.active => false,
+            Some(last) => tick >= last.saturating_add(self.cooldown_ticks),
+            None => true,
+        }
+    }
+
+    /// Records that the trigger fired at `tick`, deactivating one-shot triggers.
+    pub fn record_fire(&mut self, tick: u64) {
+        self.last_fired = Some(tick);
+        self.fire_count += 1;
+        if self.one_shot {
+            self.active = false;
         }
     }
 
@@ -124,4 +275,45 @@ mod tests {
         trigger.activate();
         assert!(trigger.is_active());
     }
+
+    #[test]
+    fn test_composite_condition_serialization() {
+        let condition = TriggerCondition::any(vec![
+            TriggerCondition::compare(WorldMetric::LivingNpcs, Comparison::LessOrEqual, 10.0),
+            TriggerCondition::negate(TriggerCondition::WeatherIs {
+                chunk: ChunkCoord::new(0, 0),
+                condition: WeatherCondition::Clear,
+            }),
+        ]);
+
+        let json = serde_json::to_string(&condition).unwrap();
+        let restored: TriggerCondition = serde_json::from_str(&json).unwrap();
+        match restored {
+            TriggerCondition::Any(conditions) => assert_eq!(conditions.len(), 2),
+            other => panic!("unexpected condition {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_comparisons() {
+        assert!(Comparison::Equal.holds(3.0, 3.0));
+        assert!(Comparison::NotEqual.holds(3.0, 4.0));
+        assert!(Comparison::GreaterThan.holds(5.0, 4.0));
+        assert!(!Comparison::LessOrEqual.holds(5.0, 4.0));
+    }
+
+    #[test]
+    fn test_one_shot_trigger() {
+        let mut trigger = EventTrigger::new(
+            "trigger_1".to_string(),
+            TriggerCondition::TimeElapsed(0),
+            "event_template_1".to_string(),
+        )
+        .once();
+
+        assert!(trigger.is_ready(0));
+        trigger.record_fire(0);
+        assert!(!trigger.is_ready(100));
+        assert_eq!(trigger.fire_count, 1);
+    }
 }
\ No newline at end of file
@@ -12,7 +12,11 @@
 //! - **Economy System**: Markets, settlements, trade routes, and resources
 //! - **Ecosystem System**: Species, population control, and food chains
 //! - **Event System**: World events, event queues, and triggers
+//! - **Recording**: Deterministic input recording and replay with divergence detection
+//! - **Replication**: Interest-filtered, delta-compressed snapshots for clients
 //! - **Serialization**: JSON and binary serialization support
+//! - **Observability**: Optional `tracing` spans and tick metrics behind the `observability` feature
+//! - **Test Support**: `proptest` strategies and fixture worlds behind the `test-support` feature
 //!
 //! ## Example
 //!
@@ -42,10 +46,15 @@ pub mod economy;
 pub mod ecosystem;
 pub mod errors;
 pub mod events;
+pub mod observability;
 pub mod population;
+pub mod recording;
+pub mod replication;
 pub mod serialization;
 pub mod spatial;
 pub mod temporal;
+#[cfg(feature = "test-support")]
+pub mod test_support;
 pub mod world;
 
 pub use world::World;
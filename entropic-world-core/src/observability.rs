@@ -0,0 +1,207 @@
+//! Tracing spans and metrics shared by the ENTROPIC crates.
+//!
+//! With the `observability` feature, simulation hot paths are wrapped in `tracing` spans
+//! and report counters, gauges and histograms through the `metrics` facade, which any
+//! exporter (Prometheus, StatsD, ...) can collect once installed with
+//! [`set_metrics_exporter`]. Without the feature every helper here compiles to nothing, so
+//! call sites need no `cfg` of their own.
+//!
+//! Metric names are the constants below; durations are in seconds and rates such as
+//! chunks loaded per second are derived from the `_total` counters by the exporter.
+
+/// Histogram of whole `World::advance_tick` calls
+pub const TICK_DURATION_SECONDS: &str = "entropic_tick_duration_seconds";
+/// Histogram of the parts of a tick, labelled by `subsystem`
+pub const SUBSYSTEM_DURATION_SECONDS: &str = "entropic_subsystem_duration_seconds";
+/// Counter of simulated ticks
+pub const TICKS_TOTAL: &str = "entropic_ticks_total";
+/// Counter of world events dispatched to handlers
+pub const EVENTS_DISPATCHED_TOTAL: &str = "entropic_events_dispatched_total";
+/// Counter of chunks loaded by chunk managers, labelled by `source` (`generated` or `cached`)
+pub const CHUNKS_LOADED_TOTAL: &str = "entropic_chunks_loaded_total";
+/// Gauge of chunks held in memory by chunk managers
+pub const CHUNKS_RESIDENT: &str = "entropic_chunks_resident";
+/// Histogram of chunk packet verification and decoding
+pub const CHUNK_VALIDATION_DURATION_SECONDS: &str = "entropic_chunk_validation_duration_seconds";
+/// Counter of chunk packets rejected by verification
+pub const CHUNK_VALIDATION_FAILURES_TOTAL: &str = "entropic_chunk_validation_failures_total";
+/// Gauge of path jobs queued or running
+pub const PATH_QUEUE_DEPTH: &str = "entropic_path_queue_depth";
+
+#[cfg(feature = "observability")]
+pub use metrics;
+#[cfg(feature = "observability")]
+pub use tracing;
+
+/// Installs `recorder` as the process-wide metrics exporter and describes every metric
+/// above to it.
+///
+/// Only one exporter can be installed per process; later calls fail and hand the recorder
+/// back.
+///
+/// # Examples
+///
+/// ```ignore
+/// let recorder = metrics_exporter_prometheus::PrometheusBuilder::new().build_recorder();
+/// let handle = recorder.handle();
+/// entropic_world_core::observability::set_metrics_exporter(recorder).unwrap();
+/// println!("{}", handle.render());
+/// ```
+#[cfg(feature = "observability")]
+pub fn set_metrics_exporter<R>(recorder: R) -> Result<(), metrics::SetRecorderError<R>>
+where
+    R: metrics::Recorder + Send + Sync + 'static,
+{
+    metrics::set_global_recorder(recorder)?;
+    describe_metrics();
+    Ok(())
+}
+
+#[cfg(feature = "observability")]
+fn describe_metrics() {
+    use metrics::{describe_counter, describe_gauge, describe_histogram, Unit};
+
+    describe_histogram!(TICK_DURATION_SECONDS, Unit::Seconds, "Duration of a world tick");
+    describe_histogram!(
+        SUBSYSTEM_DURATION_SECONDS,
+        Unit::Seconds,
+        "Duration of one subsystem's part of a world tick"
+    );
+    describe_counter!(TICKS_TOTAL, "World ticks simulated");
+    describe_counter!(EVENTS_DISPATCHED_TOTAL, "World events dispatched to handlers");
+    describe_counter!(CHUNKS_LOADED_TOTAL, "Chunks loaded by chunk managers");
+    describe_gauge!(CHUNKS_RESIDENT, "Chunks held in memory by chunk managers");
+    describe_histogram!(
+        CHUNK_VALIDATION_DURATION_SECONDS,
+        Unit::Seconds,
+        "Duration of chunk packet verification and decoding"
+    );
+    describe_counter!(CHUNK_VALIDATION_FAILURES_TOTAL, "Chunk packets rejected by verification");
+    describe_gauge!(PATH_QUEUE_DEPTH, "Path jobs queued or running");
+}
+
+#[cfg(feature = "observability")]
+fn labels(labels: &[(&'static str, &'static str)]) -> Vec<metrics::Label> {
+    labels.iter().map(|&(key, value)| metrics::Label::new(key, value)).collect()
+}
+
+/// Adds `value` to a counter.
+#[inline]
+pub fn increment_counter(name: &'static str, labels: &[(&'static str, &'static str)], value: u64) {
+    #[cfg(feature = "observability")]
+    metrics::counter!(name, self::labels(labels)).increment(value);
+    #[cfg(not(feature = "observability"))]
+    let _ = (name, labels, value);
+}
+
+/// Sets a gauge to `value`.
+#[inline]
+pub fn set_gauge(name: &'static str, value: f64) {
+    #[cfg(feature = "observability")]
+    metrics::gauge!(name).set(value);
+    #[cfg(not(feature = "observability"))]
+    let _ = (name, value);
+}
+
+/// Records the time from its creation until it is dropped into a histogram.
+///
+/// # Examples
+///
+/// ```
+/// use entropic_world_core::observability::Timer;
+///
+/// {
+///     let _timer = Timer::subsystem("economy");
+///     // ... simulate the economy ...
+/// } // recorded here
+/// ```
+#[must_use = "the duration is recorded when the timer is dropped"]
+pub struct Timer {
+    #[cfg(feature = "observability")]
+    histogram: metrics::Histogram,
+    #[cfg(feature = "observability")]
+    start: std::time::Instant,
+}
+
+impl Timer {
+    #[inline]
+    pub fn start(name: &'static str, labels: &[(&'static str, &'static str)]) -> Self {
+        #[cfg(not(feature = "observability"))]
+        let _ = (name, labels);
+        Self {
+            #[cfg(feature = "observability")]
+            histogram: metrics::histogram!(name, self::labels(labels)),
+            #[cfg(feature = "observability")]
+            start: std::time::Instant::now(),
+        }
+    }
+
+    /// Times one subsystem's part of a tick.
+    #[inline]
+    pub fn subsystem(subsystem: &'static str) -> Self {
+        Self::start(SUBSYSTEM_DURATION_SECONDS, &[("subsystem", subsystem)])
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        #[cfg(feature = "observability")]
+        self.histogram.record(self.start.elapsed().as_secs_f64());
+    }
+}
+
+#[cfg(all(test, feature = "observability"))]
+mod tests {
+    use super::*;
+    use metrics::{Counter, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// Counts ticks and notes which subsystems were timed
+    #[derive(Default)]
+    struct TestRecorder {
+        ticks: Arc<AtomicU64>,
+        subsystems: Mutex<Vec<String>>,
+    }
+
+    impl Recorder for TestRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            if key.name() == TICKS_TOTAL {
+                Counter::from_arc(self.ticks.clone())
+            } else {
+                Counter::noop()
+            }
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+            if key.name() == SUBSYSTEM_DURATION_SECONDS {
+                let mut subsystems = self.subsystems.lock().unwrap();
+                subsystems.extend(key.labels().map(|label| label.value().to_string()));
+            }
+            Histogram::noop()
+        }
+    }
+
+    #[test]
+    fn test_tick_metrics_are_recorded() {
+        let recorder = TestRecorder::default();
+        metrics::with_local_recorder(&recorder, || {
+            let mut world = crate::World::new("Metrics".to_string(), "dna".to_string(), 2, 2);
+            world.advance_tick();
+            world.advance_tick();
+        });
+
+        assert_eq!(recorder.ticks.load(Ordering::Relaxed), 2);
+        let subsystems = recorder.subsystems.lock().unwrap();
+        assert!(subsystems.iter().any(|s| s == "events"));
+        assert!(subsystems.iter().any(|s| s == "history"));
+    }
+}
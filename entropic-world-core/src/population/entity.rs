@@ -19,7 +19,7 @@ pub struct Entity {
     pub last_updated: WorldTime,
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum EntityType {
     NPC,
     Animal,
@@ -8,6 +8,6 @@ pub mod schedule;
 pub use entity::{Entity, EntityId, EntityType};
 pub use faction::{Alignment, Faction, FactionId, SettlementId};
 pub use memory::{Memory, MemoryEvent};
-pub use npc::{Gender, NpcStatus, Personality, NPC};
+pub use npc::{Gender, NpcStatus, Occupation, Personality, NPC};
 pub use relationship::{NpcId, Relationship};
 pub use schedule::{Activity, DayType, Schedule, ScheduleEntry};
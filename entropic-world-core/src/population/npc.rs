@@ -21,6 +21,8 @@ pub struct NPC {
     pub status: NpcStatus,
     pub age: f32,
     pub gender: Gender,
+    #[serde(default)]
+    pub occupation: Option<Occupation>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -40,6 +42,18 @@ pub enum Gender {
     Other,
 }
 
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Occupation {
+    Hunter,
+    Fisher,
+    Farmer,
+    Lumberjack,
+    Merchant,
+    Guard,
+    Crafter,
+    Custom(u32),
+}
+
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum NpcStatus {
     Active,
@@ -86,9 +100,26 @@ impl NPC {
             status: NpcStatus::Idle,
             age: 20.0,
             gender: Gender::Other,
+            occupation: None,
         }
     }
 
+    /// Assigns the NPC's occupation, returning the updated NPC.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::population::{Occupation, NPC};
+    ///
+    /// let npc = NPC::new("npc_1".into(), "Hal".into(), "entity_1".into())
+    ///     .with_occupation(Occupation::Hunter);
+    /// assert_eq!(npc.occupation, Some(Occupation::Hunter));
+    /// ```
+    pub fn with_occupation(mut self, occupation: Occupation) -> Self {
+        self.occupation = Some(occupation);
+        self
+    }
+
     /// Adds or updates a skill on the NPC.
     ///
     /// The provided `proficiency` is clamped to the range 0.0..=1.0 before being stored under the given skill name in the NPC's skills map.
@@ -241,6 +272,22 @@ impl NPC {
     }
 }
 
+impl Occupation {
+    /// Returns the skill name that governs how well an NPC performs this occupation.
+    pub fn skill_name(&self) -> &'static str {
+        match self {
+            Occupation::Hunter => "hunting",
+            Occupation::Fisher => "fishing",
+            Occupation::Farmer => "farming",
+            Occupation::Lumberjack => "woodcutting",
+            Occupation::Merchant => "trading",
+            Occupation::Guard => "combat",
+            Occupation::Crafter => "crafting",
+            Occupation::Custom(_) => "custom",
+        }
+    }
+}
+
 impl Personality {
     /// Creates a `Personality` where each trait value is clamped to the range 0.0 to 1.0.
     ///
@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use crate::ecosystem::{Species, SpeciesId};
+use crate::events::WorldEvent;
+use crate::population::{Entity, EntityId, Faction, NPC};
+use crate::spatial::ChunkCoord;
+use crate::world::World;
+
+/// An input applied to a world from outside the simulation.
+///
+/// Every change a game makes to a recorded world goes through a command, so that the
+/// recording holds everything needed to re-simulate the world.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum WorldCommand {
+    InitializeChunks,
+    AddEntity(Entity),
+    RemoveEntity(EntityId),
+    /// Moves an entity, updating its chunk and the spatial index
+    MoveEntity { id: EntityId, x: f32, y: f32 },
+    AddNpc(NPC),
+    AddFaction(Faction),
+    AddSpecies(Species),
+    SpawnAnimals { species_id: SpeciesId, coord: ChunkCoord, count: u32 },
+    /// Schedules an event for `tick`, as injected by a game master or a scripted scenario
+    InjectEvent { tick: u64, event: WorldEvent },
+    StartChain { chain_id: String, location: (f32, f32) },
+    AddTag(String),
+    RemoveTag(String),
+    SetRandomEventChance(f32),
+    SimulateDiseases,
+    SimulateHunting,
+    SimulateFlora,
+    SimulateSelection,
+    SimulateInvasions,
+}
+
+impl WorldCommand {
+    /// Applies the command to `world`.
+    pub fn apply(&self, world: &mut World) {
+        match self {
+            WorldCommand::InitializeChunks => world.initialize_chunks(),
+            WorldCommand::AddEntity(entity) => world.add_entity(entity.clone()),
+            WorldCommand::RemoveEntity(id) => world.remove_entity(id),
+            WorldCommand::MoveEntity { id, x, y } => {
+                if let Some(mut entity) = world.entities.get(id).cloned() {
+                    world.remove_entity(id);
+                    entity.x = *x;
+                    entity.y = *y;
+                    entity.chunk = ChunkCoord::from_world_position(*x, *y);
+                    world.add_entity(entity);
+                }
+            }
+            WorldCommand::AddNpc(npc) => world.add_npc(npc.clone()),
+            WorldCommand::AddFaction(faction) => world.add_faction(faction.clone()),
+            WorldCommand::AddSpecies(species) => world.add_species(species.clone()),
+            WorldCommand::SpawnAnimals {
+                species_id,
+                coord,
+                count,
+            } => {
+                world.spawn_animals(species_id, *coord, *count);
+            }
+            WorldCommand::InjectEvent { tick, event } => {
+                world.event_queue.schedule(*tick, event.clone());
+            }
+            WorldCommand::StartChain { chain_id, location } => {
+                world.start_chain(chain_id, *location);
+            }
+            WorldCommand::AddTag(tag) => world.add_tag(tag.clone()),
+            WorldCommand::RemoveTag(tag) => {
+                world.remove_tag(tag);
+            }
+            WorldCommand::SetRandomEventChance(chance) => world.random_event_chance = *chance,
+            WorldCommand::SimulateDiseases => world.simulate_diseases(),
+            WorldCommand::SimulateHunting => {
+                world.simulate_hunting();
+            }
+            WorldCommand::SimulateFlora => world.simulate_flora(),
+            WorldCommand::SimulateSelection => world.simulate_selection(),
+            WorldCommand::SimulateInvasions => world.simulate_invasions(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::population::EntityType;
+
+    #[test]
+    fn test_move_entity_updates_chunk() {
+        let mut world = World::new("Commands".to_string(), "dna".to_string(), 4, 4);
+        WorldCommand::InitializeChunks.apply(&mut world);
+        let chunk = ChunkCoord::new(0, 0);
+        let entity = Entity::new("e".to_string(), EntityType::Animal, 1.0, 1.0, 0.0, chunk);
+        WorldCommand::AddEntity(entity).apply(&mut world);
+
+        let (x, y) = ChunkCoord::new(2, 1).center();
+        WorldCommand::MoveEntity { id: "e".to_string(), x, y }.apply(&mut world);
+
+        let moved = &world.entities["e"];
+        assert_eq!(moved.chunk, ChunkCoord::new(2, 1));
+        assert!(world.chunks[&ChunkCoord::new(2, 1)].entities.contains(&"e".to_string()));
+        assert!(!world.chunks[&ChunkCoord::new(0, 0)].entities.contains(&"e".to_string()));
+    }
+}
@@ -0,0 +1,9 @@
+pub mod command;
+pub mod player;
+pub mod recorder;
+pub mod state_hash;
+
+pub use command::WorldCommand;
+pub use player::Player;
+pub use recorder::{Recorder, Recording, RecordingStart};
+pub use state_hash::state_hash;
@@ -0,0 +1,184 @@
+use crate::errors::{Result, WorldError};
+use crate::recording::recorder::Recording;
+use crate::recording::state_hash::state_hash;
+use crate::world::World;
+
+/// Re-simulates a recording tick by tick, checking the world against the recorded state
+/// hashes.
+///
+/// The first tick whose hash differs is reported as `WorldError::ReplayDivergence`, with
+/// the player's world left at that tick for inspection. Stepping to the tick before it and
+/// comparing against a save from the original run narrows down what went wrong.
+pub struct Player<'a> {
+    recording: &'a Recording,
+    world: World,
+    next_command: usize,
+}
+
+impl<'a> Player<'a> {
+    /// Builds the recording's starting world.
+    pub fn new(recording: &'a Recording) -> Self {
+        Self {
+            recording,
+            world: recording.start.build(),
+            next_command: 0,
+        }
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// Mutable access to the world, for registering the event handlers the recorded world
+    /// had before replaying.
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    pub fn into_world(self) -> World {
+        self.world
+    }
+
+    /// Reports whether every recorded tick has been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.world.current_tick >= self.recording.end_tick
+    }
+
+    /// Applies the commands recorded at the current tick, then advances the world and
+    /// checks its hash if one was recorded for the new tick.
+    ///
+    /// Returns `false` without doing anything once the recording is finished.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WorldError::ReplayDivergence` when the world's state differs from the
+    /// recorded one.
+    pub fn step(&mut self) -> Result<bool> {
+        if self.is_finished() {
+            return Ok(false);
+        }
+        self.apply_due_commands();
+        self.world.advance_tick();
+
+        let tick = self.world.current_tick;
+        if let Some(&expected) = self.recording.hashes.get(&tick) {
+            self.check(tick, expected)?;
+        }
+        Ok(true)
+    }
+
+    /// Replays until the world reaches `tick` or the end of the recording.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WorldError::ReplayDivergence` at the first diverging tick.
+    pub fn run_to(&mut self, tick: u64) -> Result<()> {
+        while self.world.current_tick < tick && self.step()? {}
+        Ok(())
+    }
+
+    /// Replays the whole recording, including the commands applied after its last tick,
+    /// and checks the final state.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WorldError::ReplayDivergence` at the first diverging tick.
+    pub fn run_to_end(&mut self) -> Result<()> {
+        while self.step()? {}
+        self.apply_due_commands();
+        self.check(self.world.current_tick, self.recording.final_hash)
+    }
+
+    fn apply_due_commands(&mut self) {
+        let commands = &self.recording.commands;
+        while let Some((tick, command)) = commands.get(self.next_command) {
+            if *tick > self.world.current_tick {
+                break;
+            }
+            command.apply(&mut self.world);
+            self.next_command += 1;
+        }
+    }
+
+    fn check(&self, tick: u64, expected: u64) -> Result<()> {
+        let actual = state_hash(&self.world)?;
+        if actual != expected {
+            return Err(WorldError::ReplayDivergence {
+                tick,
+                expected,
+                actual,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{EventType, WorldEvent};
+    use crate::population::{Entity, EntityType};
+    use crate::recording::command::WorldCommand;
+    use crate::recording::recorder::Recorder;
+    use crate::spatial::ChunkCoord;
+    use crate::temporal::time::WorldTime;
+    use crate::world::WorldConfig;
+
+    fn record() -> Recording {
+        let config = WorldConfig::new(4, 4).with_seed(3);
+        let mut recorder = Recorder::new("Sim".to_string(), "dna".to_string(), config);
+        recorder.apply(WorldCommand::InitializeChunks);
+        let chunk = ChunkCoord::new(0, 0);
+        let entity = Entity::new("e".to_string(), EntityType::NPC, 1.0, 1.0, 0.0, chunk);
+        recorder.apply(WorldCommand::AddEntity(entity));
+        for tick in 0..20 {
+            if tick == 5 {
+                let storm = WorldEvent::new(
+                    "storm".to_string(),
+                    EventType::Disaster,
+                    WorldTime::default(),
+                    (0.0, 0.0),
+                    "Storm".to_string(),
+                );
+                recorder.inject_event(8, storm);
+            }
+            let x = tick as f32 * 10.0;
+            recorder.apply(WorldCommand::MoveEntity { id: "e".to_string(), x, y: 1.0 });
+            recorder.advance_tick().unwrap();
+        }
+        recorder.apply(WorldCommand::RemoveEntity("e".to_string()));
+        recorder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_replay_reproduces_recording() {
+        let recording = record();
+        assert_eq!(recording.ticks(), 20);
+        assert_eq!(recording.hashes.len(), 20);
+
+        let mut player = Player::new(&recording);
+        player.run_to_end().unwrap();
+        assert!(player.world().entities.is_empty());
+        assert!(player.world().event_history.iter().any(|e| e.id == "storm"));
+
+        // The recording survives serialization
+        let json = serde_json::to_string(&recording).unwrap();
+        let restored: Recording = serde_json::from_str(&json).unwrap();
+        Player::new(&restored).run_to_end().unwrap();
+    }
+
+    #[test]
+    fn test_divergence_is_detected_at_first_tick() {
+        let recording = record();
+        let mut player = Player::new(&recording);
+        player.run_to(10).unwrap();
+
+        // An input the recording doesn't know about
+        player.world_mut().random_event_chance = 0.5;
+        player.world_mut().add_tag("tampered");
+        match player.step() {
+            Err(WorldError::ReplayDivergence { tick, .. }) => assert_eq!(tick, 11),
+            other => panic!("expected a divergence, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::errors::Result;
+use crate::events::WorldEvent;
+use crate::recording::command::WorldCommand;
+use crate::recording::state_hash::state_hash;
+use crate::world::{World, WorldConfig};
+
+/// The world a recording starts from.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum RecordingStart {
+    /// A world created from a configuration, whose seed is always set
+    Config {
+        name: String,
+        game_dna_id: String,
+        config: WorldConfig,
+    },
+    /// A snapshot of an existing world, such as a loaded save
+    Snapshot(Box<World>),
+}
+
+impl RecordingStart {
+    /// Builds the world the recording starts from.
+    pub fn build(&self) -> World {
+        match self {
+            RecordingStart::Config {
+                name,
+                game_dna_id,
+                config,
+            } => World::from_config(name.clone(), game_dna_id.clone(), config.clone()),
+            RecordingStart::Snapshot(world) => world.as_ref().clone(),
+        }
+    }
+}
+
+/// Everything needed to re-simulate a world: where it started, the commands applied to it
+/// and the state hashes it went through.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Recording {
+    pub start: RecordingStart,
+    /// Commands with the tick the world was at when each was applied, in order
+    pub commands: Vec<(u64, WorldCommand)>,
+    /// State hashes by tick, taken right after the world advanced to the tick
+    pub hashes: BTreeMap<u64, u64>,
+    pub start_tick: u64,
+    pub end_tick: u64,
+    /// State hash once every command was applied, including those after the last tick
+    pub final_hash: u64,
+}
+
+impl Recording {
+    /// Commands applied while the world was at `tick`, in order.
+    pub fn commands_at(&self, tick: u64) -> impl Iterator<Item = &WorldCommand> {
+        let start = self.commands.partition_point(|(t, _)| *t < tick);
+        let end = self.commands.partition_point(|(t, _)| *t <= tick);
+        self.commands[start..end].iter().map(|(_, command)| command)
+    }
+
+    /// Number of ticks recorded.
+    pub fn ticks(&self) -> u64 {
+        self.end_tick - self.start_tick
+    }
+}
+
+/// Records the inputs of a world as it is simulated.
+///
+/// The recorder owns the world. Ticks are advanced and commands applied through the
+/// recorder, which logs them along with a state hash every `hash_interval` ticks. Event
+/// handlers are not recorded: a `Player` must be given the same `event_bus` subscriptions.
+///
+/// # Examples
+///
+/// ```
+/// use entropic_world_core::recording::{Player, Recorder, WorldCommand};
+/// use entropic_world_core::world::WorldConfig;
+///
+/// let config = WorldConfig::new(4, 4);
+/// let mut recorder = Recorder::new("Sim".to_string(), "dna_1".to_string(), config);
+/// recorder.apply(WorldCommand::InitializeChunks);
+/// for _ in 0..10 {
+///     recorder.advance_tick().unwrap();
+/// }
+/// let recording = recorder.finish().unwrap();
+///
+/// let mut player = Player::new(&recording);
+/// player.run_to_end().unwrap();
+/// assert_eq!(player.world().current_tick, 10);
+/// ```
+pub struct Recorder {
+    world: World,
+    start: RecordingStart,
+    start_tick: u64,
+    commands: Vec<(u64, WorldCommand)>,
+    hashes: BTreeMap<u64, u64>,
+    hash_interval: u64,
+}
+
+impl Recorder {
+    /// Creates a world from `config` and starts recording it. A seed is picked when the
+    /// configuration has none, and stored in the recording.
+    pub fn new(name: String, game_dna_id: String, mut config: WorldConfig) -> Self {
+        let world = World::from_config(name.clone(), game_dna_id.clone(), config.clone());
+        config.seed = Some(world.seed);
+        let start = RecordingStart::Config {
+            name,
+            game_dna_id,
+            config,
+        };
+        Self::with_start(world, start)
+    }
+
+    /// Starts recording an existing world from its current state.
+    pub fn from_world(world: World) -> Self {
+        let start = RecordingStart::Snapshot(Box::new(world.clone()));
+        Self::with_start(world, start)
+    }
+
+    fn with_start(world: World, start: RecordingStart) -> Self {
+        Self {
+            start_tick: world.current_tick,
+            world,
+            start,
+            commands: Vec::new(),
+            hashes: BTreeMap::new(),
+            hash_interval: 1,
+        }
+    }
+
+    /// Sets how many ticks pass between state hashes; 0 hashes only the final state.
+    pub fn with_hash_interval(mut self, hash_interval: u64) -> Self {
+        self.hash_interval = hash_interval;
+        self
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// Mutable access to the world, for registering event handlers.
+    ///
+    /// Changes made through it are not recorded and will make replays diverge; apply
+    /// them as commands instead.
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    /// Applies a command to the world and records it.
+    pub fn apply(&mut self, command: WorldCommand) {
+        command.apply(&mut self.world);
+        self.commands.push((self.world.current_tick, command));
+    }
+
+    /// Schedules `event` for `tick` and records the injection.
+    pub fn inject_event(&mut self, tick: u64, event: WorldEvent) {
+        self.apply(WorldCommand::InjectEvent { tick, event });
+    }
+
+    /// Advances the world by one tick, hashing its state when the tick is due.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WorldError::SerializationError` if the world can't be hashed.
+    pub fn advance_tick(&mut self) -> Result<()> {
+        self.world.advance_tick();
+        let tick = self.world.current_tick;
+        if self.hash_interval > 0 && (tick - self.start_tick) % self.hash_interval == 0 {
+            self.hashes.insert(tick, state_hash(&self.world)?);
+        }
+        Ok(())
+    }
+
+    /// Stops recording, returning the recording and the recorded world.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WorldError::SerializationError` if the world can't be hashed.
+    pub fn finish_with_world(self) -> Result<(Recording, World)> {
+        let recording = Recording {
+            start: self.start,
+            commands: self.commands,
+            hashes: self.hashes,
+            start_tick: self.start_tick,
+            end_tick: self.world.current_tick,
+            final_hash: state_hash(&self.world)?,
+        };
+        Ok((recording, self.world))
+    }
+
+    /// Stops recording and returns the recording.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WorldError::SerializationError` if the world can't be hashed.
+    pub fn finish(self) -> Result<Recording> {
+        self.finish_with_world().map(|(recording, _)| recording)
+    }
+}
@@ -0,0 +1,187 @@
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use crate::ecosystem::{
+    AnimalPopulations, Disease, DiseaseId, Flora, HarvestRule, Invasion, Outbreak, Species,
+    SpeciesId,
+};
+use crate::economy::{Market, Settlement, SettlementId, TradeRoute};
+use crate::errors::{Result, WorldError};
+use crate::events::{EventChains, EventHistory, EventQueue, EventTemplateRegistry, TriggerEngine};
+use crate::population::{Entity, EntityId, Faction, FactionId, NpcId, NPC};
+use crate::spatial::{Biome, Chunk, ChunkCoord, Structure};
+use crate::temporal::{Weather, WorldTime};
+use crate::world::{World, WorldRng};
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Everything in a world that the simulation evolves, except chunks. The world's
+/// identity, timestamps and the spatial index, which is derived from the entities, are
+/// left out.
+#[derive(Serialize)]
+struct SimulatedState<'a> {
+    current_tick: u64,
+    current_time: WorldTime,
+    entities: &'a HashMap<EntityId, Entity>,
+    npcs: &'a HashMap<NpcId, NPC>,
+    factions: &'a HashMap<FactionId, Faction>,
+    markets: &'a HashMap<String, Market>,
+    settlements: &'a HashMap<SettlementId, Settlement>,
+    trade_routes: &'a [TradeRoute],
+    species: &'a HashMap<SpeciesId, Species>,
+    animal_populations: &'a AnimalPopulations,
+    diseases: &'a HashMap<DiseaseId, Disease>,
+    outbreaks: &'a [Outbreak],
+    harvest_rules: &'a [HarvestRule],
+    overhunted_species: &'a [SpeciesId],
+    extinct_species: &'a [SpeciesId],
+    invasions: &'a [Invasion],
+    pending_invasions: &'a HashMap<String, Invasion>,
+    event_queue: &'a EventQueue,
+    event_history: &'a EventHistory,
+    triggers: &'a TriggerEngine,
+    chains: &'a EventChains,
+    event_templates: &'a EventTemplateRegistry,
+    random_event_chance: f32,
+    seed: u64,
+    rng: &'a WorldRng,
+    tags: &'a BTreeSet<String>,
+}
+
+/// The parts of a chunk other than its per-sample buffers.
+#[derive(Serialize)]
+struct ChunkHeader<'a> {
+    coord: ChunkCoord,
+    biome: &'a Biome,
+    water_level: f32,
+    entities: &'a [EntityId],
+    structures: &'a [Structure],
+    weather: &'a Weather,
+    flora: &'a Flora,
+    loaded: bool,
+}
+
+/// Hashes the simulated state of a world.
+///
+/// Two worlds hash the same when their simulation state is the same, regardless of their
+/// IDs, names, timestamps or the iteration order of their maps, so the hash of a replayed
+/// world can be compared against the hash recorded for the same tick. Chunk heightmaps and
+/// other per-sample buffers are hashed as raw bits.
+///
+/// # Errors
+///
+/// Returns `WorldError::SerializationError` if the world can't be serialized.
+///
+/// # Examples
+///
+/// ```
+/// use entropic_world_core::recording::state_hash;
+/// use entropic_world_core::World;
+///
+/// let mut a = World::new("A".to_string(), "dna".to_string(), 2, 2);
+/// let mut b = World::new("B".to_string(), "dna".to_string(), 2, 2);
+/// a.reseed(7);
+/// b.reseed(7);
+/// assert_eq!(state_hash(&a).unwrap(), state_hash(&b).unwrap());
+///
+/// b.advance_tick();
+/// assert_ne!(state_hash(&a).unwrap(), state_hash(&b).unwrap());
+/// ```
+pub fn state_hash(world: &World) -> Result<u64> {
+    let mut hasher = Fnv(FNV_OFFSET);
+    hasher.write_json(&SimulatedState {
+        current_tick: world.current_tick,
+        current_time: world.current_time,
+        entities: &world.entities,
+        npcs: &world.npcs,
+        factions: &world.factions,
+        markets: &world.markets,
+        settlements: &world.settlements,
+        trade_routes: &world.trade_routes,
+        species: &world.species,
+        animal_populations: &world.animal_populations,
+        diseases: &world.diseases,
+        outbreaks: &world.outbreaks,
+        harvest_rules: &world.harvest_rules,
+        overhunted_species: &world.overhunted_species,
+        extinct_species: &world.extinct_species,
+        invasions: &world.invasions,
+        pending_invasions: &world.pending_invasions,
+        event_queue: &world.event_queue,
+        event_history: &world.event_history,
+        triggers: &world.triggers,
+        chains: &world.chains,
+        event_templates: &world.event_templates,
+        random_event_chance: world.random_event_chance,
+        seed: world.seed,
+        rng: &world.rng,
+        tags: &world.tags,
+    })?;
+
+    let mut coords: Vec<_> = world.chunks.keys().collect();
+    coords.sort_by_key(|coord| (coord.x, coord.y));
+    for coord in coords {
+        hasher.write_chunk(&world.chunks[coord])?;
+    }
+    Ok(hasher.0)
+}
+
+/// 64-bit FNV-1a, which unlike the std hashers is stable across platforms and releases
+struct Fnv(u64);
+
+impl Fnv {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    /// Hashes `value` as JSON, going through `serde_json::Value` so map keys are sorted
+    fn write_json<T: Serialize>(&mut self, value: &T) -> Result<()> {
+        let value = serde_json::to_value(value)
+            .map_err(|e| WorldError::SerializationError(e.to_string()))?;
+        self.write(value.to_string().as_bytes());
+        Ok(())
+    }
+
+    fn write_chunk(&mut self, chunk: &Chunk) -> Result<()> {
+        self.write_json(&ChunkHeader {
+            coord: chunk.coord,
+            biome: &chunk.biome,
+            water_level: chunk.water_level,
+            entities: &chunk.entities,
+            structures: &chunk.structures,
+            weather: &chunk.weather,
+            flora: &chunk.flora,
+            loaded: chunk.loaded,
+        })?;
+        for height in &chunk.elevation {
+            self.write(&height.to_bits().to_le_bytes());
+        }
+        self.write(&chunk.vegetation);
+        for weights in &chunk.materials {
+            self.write(weights);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_ignores_chunk_order() {
+        let mut a = World::new("A".to_string(), "dna".to_string(), 3, 3);
+        a.reseed(1);
+        a.initialize_chunks();
+
+        // A round trip rebuilds the chunk map, changing its iteration order
+        let json = serde_json::to_string(&a).unwrap();
+        let mut b: World = serde_json::from_str(&json).unwrap();
+        assert_eq!(state_hash(&a).unwrap(), state_hash(&b).unwrap());
+
+        b.chunks.values_mut().next().unwrap().elevation[0] += 1.0;
+        assert_ne!(state_hash(&a).unwrap(), state_hash(&b).unwrap());
+    }
+}
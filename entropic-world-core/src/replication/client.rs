@@ -0,0 +1,73 @@
+use std::collections::BTreeMap;
+use crate::errors::Result;
+use crate::replication::snapshot::{SnapshotDelta, WorldSnapshot};
+
+/// Client side of snapshot replication.
+///
+/// Keeps the snapshots a server may still use as baselines and rebuilds each incoming
+/// delta on top of the one it references.
+///
+/// # Examples
+///
+/// ```
+/// use entropic_world_core::replication::{ClientReplica, Interest, ReplicationServer};
+/// use entropic_world_core::World;
+///
+/// let world = World::new("Shard".to_string(), "dna_1".to_string(), 4, 4);
+/// let mut server = ReplicationServer::default();
+/// server.add_client("player_1".to_string(), Interest::All);
+///
+/// let mut replica = ClientReplica::new();
+/// let delta = server.snapshot_for("player_1", &world).unwrap();
+/// let ack = replica.apply(&delta).unwrap();
+/// server.acknowledge("player_1", ack).unwrap();
+/// assert_eq!(replica.latest_tick(), Some(world.current_tick));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ClientReplica {
+    snapshots: BTreeMap<u64, WorldSnapshot>,
+}
+
+impl ClientReplica {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies a delta from the server and returns the tick to acknowledge.
+    ///
+    /// Snapshots older than the delta's baseline are dropped, since the server has moved
+    /// past them. A delta older than the newest snapshot still becomes available as a
+    /// baseline but doesn't replace the current state.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WorldError::ReplicationError` when the referenced baseline is no longer
+    /// held; the server must then be asked to re-baseline.
+    pub fn apply(&mut self, delta: &SnapshotDelta) -> Result<u64> {
+        let baseline = delta.baseline_tick.and_then(|tick| self.snapshots.get(&tick));
+        let snapshot = delta.apply(baseline)?;
+
+        let keep_from = delta.baseline_tick.unwrap_or(delta.tick);
+        self.snapshots = self.snapshots.split_off(&keep_from);
+        self.snapshots.insert(snapshot.tick, snapshot);
+        Ok(delta.tick)
+    }
+
+    /// The newest replicated state.
+    pub fn current(&self) -> Option<&WorldSnapshot> {
+        self.snapshots.values().next_back()
+    }
+
+    pub fn latest_tick(&self) -> Option<u64> {
+        self.snapshots.keys().next_back().copied()
+    }
+
+    pub fn snapshot_at(&self, tick: u64) -> Option<&WorldSnapshot> {
+        self.snapshots.get(&tick)
+    }
+
+    /// Drops every held snapshot, e.g. before requesting a re-baseline.
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+    }
+}
@@ -0,0 +1,7 @@
+pub mod client;
+pub mod server;
+pub mod snapshot;
+
+pub use client::ClientReplica;
+pub use server::{ClientId, Interest, ReplicationConfig, ReplicationServer};
+pub use snapshot::{EntityDelta, EntityState, SnapshotDelta, WorldSnapshot};
@@ -0,0 +1,297 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use crate::errors::{Result, WorldError};
+use crate::population::entity::Entity;
+use crate::replication::snapshot::{SnapshotDelta, WorldSnapshot};
+use crate::world::World;
+
+pub type ClientId = String;
+
+/// The part of the world a client is sent.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Interest {
+    All,
+    /// Entities within `radius` world units of (`x`, `y`)
+    Radius { x: f32, y: f32, radius: f32 },
+}
+
+impl Interest {
+    pub fn contains(&self, entity: &Entity) -> bool {
+        match *self {
+            Interest::All => true,
+            Interest::Radius { x, y, radius } => {
+                let dx = entity.x - x;
+                let dy = entity.y - y;
+                dx * dx + dy * dy <= radius * radius
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplicationConfig {
+    /// Unacknowledged snapshots kept per client
+    pub max_history: usize,
+    /// Ticks an acknowledged baseline stays usable before a full snapshot is sent instead
+    pub max_baseline_age: u64,
+}
+
+impl ReplicationConfig {
+    pub fn with_max_history(mut self, max_history: usize) -> Self {
+        self.max_history = max_history.max(1);
+        self
+    }
+
+    pub fn with_max_baseline_age(mut self, max_baseline_age: u64) -> Self {
+        self.max_baseline_age = max_baseline_age;
+        self
+    }
+}
+
+impl Default for ReplicationConfig {
+    fn default() -> Self {
+        Self {
+            max_history: 64,
+            max_baseline_age: 120,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ClientState {
+    interest: Interest,
+    /// Snapshots sent to the client and not yet superseded by an acknowledgment
+    history: BTreeMap<u64, WorldSnapshot>,
+    acked_tick: Option<u64>,
+}
+
+impl ClientState {
+    fn new(interest: Interest) -> Self {
+        Self {
+            interest,
+            history: BTreeMap::new(),
+            acked_tick: None,
+        }
+    }
+
+    fn baseline(&self, tick: u64, max_age: u64) -> Option<&WorldSnapshot> {
+        let acked = self.acked_tick?;
+        if tick.saturating_sub(acked) > max_age {
+            return None;
+        }
+        self.history.get(&acked)
+    }
+
+    /// Drops the oldest snapshots beyond `max_history`, never the acknowledged baseline.
+    fn trim(&mut self, max_history: usize) {
+        while self.history.len() > max_history {
+            let oldest = self
+                .history
+                .keys()
+                .copied()
+                .find(|tick| Some(*tick) != self.acked_tick);
+            match oldest {
+                Some(tick) => self.history.remove(&tick),
+                None => break,
+            };
+        }
+    }
+}
+
+/// Server side of snapshot replication.
+///
+/// Each client is sent the entities in its area of interest, encoded against the last
+/// snapshot it acknowledged. Until a client acknowledges one, or once its baseline is
+/// older than `max_baseline_age`, it is sent full snapshots.
+///
+/// # Examples
+///
+/// ```
+/// use entropic_world_core::replication::{Interest, ReplicationServer};
+/// use entropic_world_core::World;
+///
+/// let mut world = World::new("Shard".to_string(), "dna_1".to_string(), 4, 4);
+/// let mut server = ReplicationServer::default();
+/// server.add_client("player_1".to_string(), Interest::All);
+///
+/// let first = server.snapshot_for("player_1", &world).unwrap();
+/// assert!(first.is_full());
+///
+/// server.acknowledge("player_1", first.tick).unwrap();
+/// world.advance_tick();
+/// let next = server.snapshot_for("player_1", &world).unwrap();
+/// assert_eq!(next.baseline_tick, Some(first.tick));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ReplicationServer {
+    pub config: ReplicationConfig,
+    clients: HashMap<ClientId, ClientState>,
+}
+
+impl ReplicationServer {
+    pub fn new(config: ReplicationConfig) -> Self {
+        Self {
+            config,
+            clients: HashMap::new(),
+        }
+    }
+
+    /// Registers a client, replacing any previous state under the same ID.
+    pub fn add_client(&mut self, client: ClientId, interest: Interest) {
+        self.clients.insert(client, ClientState::new(interest));
+    }
+
+    pub fn remove_client(&mut self, client: &str) -> bool {
+        self.clients.remove(client).is_some()
+    }
+
+    pub fn has_client(&self, client: &str) -> bool {
+        self.clients.contains_key(client)
+    }
+
+    pub fn client_count(&self) -> usize {
+        self.clients.len()
+    }
+
+    pub fn set_interest(&mut self, client: &str, interest: Interest) -> Result<()> {
+        self.client_mut(client)?.interest = interest;
+        Ok(())
+    }
+
+    /// The newest tick the client acknowledged, if any.
+    pub fn acked_tick(&self, client: &str) -> Option<u64> {
+        self.clients.get(client).and_then(|c| c.acked_tick)
+    }
+
+    /// Captures the client's view of `world` and encodes it for sending.
+    pub fn snapshot_for(&mut self, client: &str, world: &World) -> Result<SnapshotDelta> {
+        let config = self.config.clone();
+        let state = self.client_mut(client)?;
+
+        let interest = state.interest;
+        let snapshot = WorldSnapshot::capture(world, |e| interest.contains(e));
+        let delta = snapshot.delta_from(state.baseline(snapshot.tick, config.max_baseline_age));
+
+        state.history.insert(snapshot.tick, snapshot);
+        state.trim(config.max_history);
+        Ok(delta)
+    }
+
+    /// Encodes a snapshot for every client.
+    pub fn snapshot_all(&mut self, world: &World) -> Vec<(ClientId, SnapshotDelta)> {
+        let mut clients: Vec<ClientId> = self.clients.keys().cloned().collect();
+        clients.sort();
+        clients
+            .into_iter()
+            .filter_map(|client| {
+                let delta = self.snapshot_for(&client, world).ok()?;
+                Some((client, delta))
+            })
+            .collect()
+    }
+
+    /// Records that the client holds the snapshot of `tick`, making it the baseline for
+    /// later deltas. Acknowledgments older than the current baseline are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WorldError::ReplicationError` when no snapshot of `tick` is awaiting
+    /// acknowledgment.
+    pub fn acknowledge(&mut self, client: &str, tick: u64) -> Result<()> {
+        let state = self.client_mut(client)?;
+        if state.acked_tick.is_some_and(|acked| tick <= acked) {
+            return Ok(());
+        }
+        if !state.history.contains_key(&tick) {
+            return Err(WorldError::ReplicationError(format!(
+                "client {client} acknowledged unknown tick {tick}"
+            )));
+        }
+
+        state.acked_tick = Some(tick);
+        state.history = state.history.split_off(&tick);
+        Ok(())
+    }
+
+    /// Forgets the client's baseline so the next snapshot is sent in full.
+    pub fn force_rebaseline(&mut self, client: &str) -> Result<()> {
+        let state = self.client_mut(client)?;
+        state.acked_tick = None;
+        state.history.clear();
+        Ok(())
+    }
+
+    fn client_mut(&mut self, client: &str) -> Result<&mut ClientState> {
+        self.clients
+            .get_mut(client)
+            .ok_or_else(|| WorldError::ReplicationError(format!("unknown client: {client}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::population::entity::EntityType;
+    use crate::spatial::coordinates::ChunkCoord;
+
+    fn world_with(positions: &[(&str, f32, f32)]) -> World {
+        let mut world = World::new("Test".to_string(), "dna".to_string(), 4, 4);
+        for (id, x, y) in positions {
+            let chunk = ChunkCoord::new(0, 0);
+            world.add_entity(Entity::new(id.to_string(), EntityType::Animal, *x, *y, 0.0, chunk));
+        }
+        world
+    }
+
+    #[test]
+    fn test_interest_filters_entities() {
+        let world = world_with(&[("near", 1.0, 1.0), ("far", 100.0, 100.0)]);
+        let mut server = ReplicationServer::default();
+        let interest = Interest::Radius { x: 0.0, y: 0.0, radius: 10.0 };
+        server.add_client("c".to_string(), interest);
+
+        let delta = server.snapshot_for("c", &world).unwrap();
+        let ids: Vec<_> = delta.spawned.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["near"]);
+    }
+
+    #[test]
+    fn test_stale_baseline_forces_full_snapshot() {
+        let mut world = world_with(&[("a", 0.0, 0.0)]);
+        let config = ReplicationConfig::default().with_max_baseline_age(2);
+        let mut server = ReplicationServer::new(config);
+        server.add_client("c".to_string(), Interest::All);
+
+        let first = server.snapshot_for("c", &world).unwrap();
+        server.acknowledge("c", first.tick).unwrap();
+
+        world.advance_tick();
+        assert!(!server.snapshot_for("c", &world).unwrap().is_full());
+        world.advance_tick();
+        world.advance_tick();
+        assert!(server.snapshot_for("c", &world).unwrap().is_full());
+    }
+
+    #[test]
+    fn test_acknowledge_prunes_history() {
+        let mut world = world_with(&[]);
+        let config = ReplicationConfig::default().with_max_history(2);
+        let mut server = ReplicationServer::new(config);
+        server.add_client("c".to_string(), Interest::All);
+
+        for _ in 0..4 {
+            server.snapshot_for("c", &world).unwrap();
+            world.advance_tick();
+        }
+        // Only ticks 2 and 3 are still held
+        assert!(server.acknowledge("c", 0).is_err());
+        server.acknowledge("c", 3).unwrap();
+        assert_eq!(server.acked_tick("c"), Some(3));
+        server.acknowledge("c", 2).unwrap();
+        assert_eq!(server.acked_tick("c"), Some(3));
+
+        server.force_rebaseline("c").unwrap();
+        assert!(server.snapshot_for("c", &world).unwrap().is_full());
+        assert!(server.acknowledge("missing", 0).is_err());
+    }
+}
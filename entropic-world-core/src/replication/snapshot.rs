@@ -0,0 +1,269 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::errors::{Result, WorldError};
+use crate::population::entity::{Entity, EntityId, EntityType};
+use crate::temporal::time::WorldTime;
+use crate::world::World;
+
+/// The replicated part of an entity.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EntityState {
+    pub id: EntityId,
+    pub entity_type: EntityType,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub velocity: (f32, f32),
+    pub health: f32,
+    pub is_alive: bool,
+}
+
+impl From<&Entity> for EntityState {
+    fn from(entity: &Entity) -> Self {
+        Self {
+            id: entity.id.clone(),
+            entity_type: entity.entity_type,
+            x: entity.x,
+            y: entity.y,
+            z: entity.z,
+            velocity: entity.velocity,
+            health: entity.health,
+            is_alive: entity.is_alive,
+        }
+    }
+}
+
+/// The fields of one entity that changed since a baseline; unchanged fields are `None`
+/// and left out of the serialized form.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EntityDelta {
+    pub id: EntityId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub z: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub velocity: Option<(f32, f32)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_alive: Option<bool>,
+}
+
+impl EntityDelta {
+    /// Changes turning `old` into `new`, or `None` when they are equal.
+    ///
+    /// The entity type never changes for a given ID, so it is not part of a delta.
+    pub fn between(old: &EntityState, new: &EntityState) -> Option<Self> {
+        fn changed<T: PartialEq + Copy>(old: T, new: T) -> Option<T> {
+            (old != new).then_some(new)
+        }
+
+        let delta = Self {
+            id: new.id.clone(),
+            x: changed(old.x, new.x),
+            y: changed(old.y, new.y),
+            z: changed(old.z, new.z),
+            velocity: changed(old.velocity, new.velocity),
+            health: changed(old.health, new.health),
+            is_alive: changed(old.is_alive, new.is_alive),
+        };
+        (delta != Self { id: delta.id.clone(), ..Self::default() }).then_some(delta)
+    }
+
+    /// Applies the changed fields to `state`.
+    pub fn apply(&self, state: &mut EntityState) {
+        if let Some(x) = self.x {
+            state.x = x;
+        }
+        if let Some(y) = self.y {
+            state.y = y;
+        }
+        if let Some(z) = self.z {
+            state.z = z;
+        }
+        if let Some(velocity) = self.velocity {
+            state.velocity = velocity;
+        }
+        if let Some(health) = self.health {
+            state.health = health;
+        }
+        if let Some(is_alive) = self.is_alive {
+            state.is_alive = is_alive;
+        }
+    }
+}
+
+/// The replicated state one client sees at a tick.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WorldSnapshot {
+    pub tick: u64,
+    pub time: WorldTime,
+    pub entities: BTreeMap<EntityId, EntityState>,
+}
+
+impl WorldSnapshot {
+    /// Captures the world's entities accepted by `filter`.
+    pub fn capture(world: &World, filter: impl Fn(&Entity) -> bool) -> Self {
+        Self {
+            tick: world.current_tick,
+            time: world.current_time,
+            entities: world
+                .entities
+                .values()
+                .filter(|e| filter(e))
+                .map(|e| (e.id.clone(), EntityState::from(e)))
+                .collect(),
+        }
+    }
+
+    /// Encodes this snapshot relative to `baseline`, or in full when there is none.
+    pub fn delta_from(&self, baseline: Option<&WorldSnapshot>) -> SnapshotDelta {
+        let Some(baseline) = baseline else {
+            return SnapshotDelta {
+                tick: self.tick,
+                baseline_tick: None,
+                time: self.time,
+                spawned: self.entities.values().cloned().collect(),
+                updated: Vec::new(),
+                despawned: Vec::new(),
+            };
+        };
+
+        let mut spawned = Vec::new();
+        let mut updated = Vec::new();
+        for (id, state) in &self.entities {
+            match baseline.entities.get(id) {
+                Some(old) => updated.extend(EntityDelta::between(old, state)),
+                None => spawned.push(state.clone()),
+            }
+        }
+        let despawned = baseline
+            .entities
+            .keys()
+            .filter(|id| !self.entities.contains_key(*id))
+            .cloned()
+            .collect();
+
+        SnapshotDelta {
+            tick: self.tick,
+            baseline_tick: Some(baseline.tick),
+            time: self.time,
+            spawned,
+            updated,
+            despawned,
+        }
+    }
+}
+
+/// A snapshot as sent over the wire: a full snapshot when `baseline_tick` is `None`,
+/// otherwise the changes since the snapshot of `baseline_tick`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotDelta {
+    pub tick: u64,
+    pub baseline_tick: Option<u64>,
+    pub time: WorldTime,
+    /// Entities new to the client, or every entity in a full snapshot
+    pub spawned: Vec<EntityState>,
+    pub updated: Vec<EntityDelta>,
+    /// Entities that left the world or the client's area of interest
+    pub despawned: Vec<EntityId>,
+}
+
+impl SnapshotDelta {
+    /// Whether this is a full snapshot that needs no baseline.
+    pub fn is_full(&self) -> bool {
+        self.baseline_tick.is_none()
+    }
+
+    /// Rebuilds the snapshot this delta encodes on top of `baseline`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WorldError::ReplicationError` when a delta is given the wrong baseline or
+    /// updates an entity the baseline doesn't have.
+    pub fn apply(&self, baseline: Option<&WorldSnapshot>) -> Result<WorldSnapshot> {
+        let mut entities = match (self.baseline_tick, baseline) {
+            (None, _) => BTreeMap::new(),
+            (Some(tick), Some(baseline)) if baseline.tick == tick => baseline.entities.clone(),
+            (Some(tick), _) => {
+                return Err(WorldError::ReplicationError(format!(
+                    "snapshot {} needs baseline {tick}",
+                    self.tick
+                )))
+            }
+        };
+
+        for id in &self.despawned {
+            entities.remove(id);
+        }
+        for state in &self.spawned {
+            entities.insert(state.id.clone(), state.clone());
+        }
+        for delta in &self.updated {
+            let state = entities.get_mut(&delta.id).ok_or_else(|| {
+                WorldError::ReplicationError(format!(
+                    "snapshot {} updates unknown entity {}",
+                    self.tick, delta.id
+                ))
+            })?;
+            delta.apply(state);
+        }
+
+        Ok(WorldSnapshot {
+            tick: self.tick,
+            time: self.time,
+            entities,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spatial::coordinates::ChunkCoord;
+
+    fn state(id: &str, x: f32) -> EntityState {
+        let chunk = ChunkCoord::new(0, 0);
+        EntityState::from(&Entity::new(id.to_string(), EntityType::Animal, x, 0.0, 0.0, chunk))
+    }
+
+    fn snapshot(tick: u64, states: Vec<EntityState>) -> WorldSnapshot {
+        WorldSnapshot {
+            tick,
+            time: WorldTime::default(),
+            entities: states.into_iter().map(|s| (s.id.clone(), s)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_delta_only_carries_changes() {
+        let old = snapshot(1, vec![state("a", 0.0), state("b", 0.0), state("c", 0.0)]);
+        let new = snapshot(2, vec![state("a", 5.0), state("b", 0.0), state("d", 1.0)]);
+
+        let delta = new.delta_from(Some(&old));
+        assert_eq!(delta.baseline_tick, Some(1));
+        let moved = EntityDelta {
+            id: "a".to_string(),
+            x: Some(5.0),
+            ..Default::default()
+        };
+        assert_eq!(delta.updated, vec![moved]);
+        assert_eq!(delta.spawned, vec![state("d", 1.0)]);
+        assert_eq!(delta.despawned, vec!["c".to_string()]);
+
+        assert_eq!(delta.apply(Some(&old)).unwrap(), new);
+        assert!(delta.apply(Some(&new)).is_err());
+        assert!(delta.apply(None).is_err());
+    }
+
+    #[test]
+    fn test_full_snapshot_needs_no_baseline() {
+        let new = snapshot(3, vec![state("a", 2.0)]);
+        let full = new.delta_from(None);
+        assert!(full.is_full());
+        assert_eq!(full.apply(None).unwrap(), new);
+    }
+}
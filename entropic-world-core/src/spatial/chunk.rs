@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
 use crate::spatial::coordinates::ChunkCoord;
-use crate::spatial::terrain::{Biome, Structure};
+use crate::spatial::terrain::{Biome, Material, Structure};
 use crate::spatial::spatial_index::EntityId;
 use crate::temporal::weather::Weather;
+use crate::ecosystem::flora::Flora;
 use crate::constants::HEIGHTMAP_RESOLUTION;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -15,6 +16,12 @@ pub struct Chunk {
     pub entities: Vec<EntityId>,
     pub structures: Vec<Structure>,
     pub weather: Weather,
+    #[serde(default)]
+    pub flora: Flora,
+    /// Material weights per heightmap sample, `[grass, rock, sand, snow]` summing to 255,
+    /// empty until painted by the terrain generator
+    #[serde(default)]
+    pub materials: Vec<[u8; 4]>,
     pub loaded: bool,
 }
 
@@ -40,6 +47,8 @@ impl Chunk {
             entities: Vec::new(),
             structures: Vec::new(),
             weather: Weather::default(),
+            flora: Flora::default(),
+            materials: Vec::new(),
             loaded: false,
         }
     }
@@ -63,6 +72,7 @@ impl Chunk {
     /// ```
     pub fn with_biome(mut self, biome: Biome) -> Self {
         self.biome = biome;
+        self.flora = Flora::for_biome(biome);
         self
     }
 
@@ -229,6 +239,59 @@ impl Chunk {
             self.vegetation[y * HEIGHTMAP_RESOLUTION + x] = density;
         }
     }
+
+    /// Gets the material weights at the given heightmap coordinates.
+    ///
+    /// Returns `None` when the coordinates are out of bounds or the chunk has no materials yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut chunk = Chunk::new(ChunkCoord(0, 0));
+    /// assert_eq!(chunk.get_materials_at(1, 2), None);
+    /// chunk.set_materials_at(1, 2, [0, 55, 200, 0]);
+    /// assert_eq!(chunk.get_materials_at(1, 2), Some([0, 55, 200, 0]));
+    /// ```
+    pub fn get_materials_at(&self, x: usize, y: usize) -> Option<[u8; 4]> {
+        if x < HEIGHTMAP_RESOLUTION && y < HEIGHTMAP_RESOLUTION {
+            self.materials.get(y * HEIGHTMAP_RESOLUTION + x).copied()
+        } else {
+            None
+        }
+    }
+
+    /// Sets the material weights at the given heightmap coordinates if they are within bounds.
+    ///
+    /// A chunk without materials gets a full grass layer first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut chunk = Chunk::new(ChunkCoord(0, 0));
+    /// chunk.set_materials_at(3, 3, [0, 0, 0, 255]);
+    /// assert_eq!(chunk.get_materials_at(0, 0), Some([255, 0, 0, 0]));
+    /// ```
+    pub fn set_materials_at(&mut self, x: usize, y: usize, weights: [u8; 4]) {
+        if x < HEIGHTMAP_RESOLUTION && y < HEIGHTMAP_RESOLUTION {
+            if self.materials.is_empty() {
+                self.materials = vec![[255, 0, 0, 0]; HEIGHTMAP_RESOLUTION * HEIGHTMAP_RESOLUTION];
+            }
+            self.materials[y * HEIGHTMAP_RESOLUTION + x] = weights;
+        }
+    }
+
+    /// Gets the material with the highest weight at the given heightmap coordinates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut chunk = Chunk::new(ChunkCoord(0, 0));
+    /// chunk.set_materials_at(1, 1, [40, 200, 15, 0]);
+    /// assert_eq!(chunk.get_material_at(1, 1), Some(Material::Rock));
+    /// ```
+    pub fn get_material_at(&self, x: usize, y: usize) -> Option<Material> {
+        self.get_materials_at(x, y).map(Material::dominant)
+    }
 }
 
 #[cfg(test)]
@@ -20,6 +20,35 @@ impl ChunkCoord {
         Self { x, y }
     }
 
+    /// Returns the chunk containing the world-space position `(x, y)`, in meters.
+    ///
+    /// Negative positions are clamped to chunk `0` on that axis.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::spatial::ChunkCoord;
+    ///
+    /// let c = ChunkCoord::from_world_position(300.0, 10.0);
+    /// assert_eq!(c, ChunkCoord::new(1, 0));
+    /// ```
+    pub fn from_world_position(x: f32, y: f32) -> Self {
+        let chunk_size = crate::constants::DEFAULT_CHUNK_SIZE;
+        Self {
+            x: (x / chunk_size).floor().max(0.0) as u32,
+            y: (y / chunk_size).floor().max(0.0) as u32,
+        }
+    }
+
+    /// Returns the world-space position of this chunk's center, in meters.
+    pub fn center(&self) -> (f32, f32) {
+        let chunk_size = crate::constants::DEFAULT_CHUNK_SIZE;
+        (
+            (self.x as f32 + 0.5) * chunk_size,
+            (self.y as f32 + 0.5) * chunk_size,
+        )
+    }
+
     /// Computes the Euclidean distance between two chunk coordinates.
     ///
     /// # Examples
@@ -8,4 +8,4 @@ pub use chunk::Chunk;
 pub use coordinates::{ChunkCoord, WorldPosition};
 pub use region::{Region, RegionCoord};
 pub use spatial_index::SpatialIndex;
-pub use terrain::{Biome, Structure, StructureType, StructureId};
+pub use terrain::{Biome, Material, Structure, StructureType, StructureId};
@@ -28,6 +28,38 @@ impl Default for Biome {
     }
 }
 
+/// Surface material painted on terrain, as weighted per heightmap sample in
+/// [`Chunk::materials`](crate::spatial::Chunk::materials)
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Material {
+    Grass,
+    Rock,
+    Sand,
+    Snow,
+}
+
+impl Material {
+    /// All materials, in the order of their weights
+    pub const ALL: [Material; 4] = [Material::Grass, Material::Rock, Material::Sand, Material::Snow];
+
+    /// Picks the material with the highest weight, preferring the earlier material on ties.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert_eq!(Material::dominant([40, 200, 15, 0]), Material::Rock);
+    /// ```
+    pub fn dominant(weights: [u8; 4]) -> Material {
+        let mut best = 0;
+        for i in 1..4 {
+            if weights[i] > weights[best] {
+                best = i;
+            }
+        }
+        Material::ALL[best]
+    }
+}
+
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum StructureType {
     House,
@@ -108,6 +140,13 @@ mod tests {
         assert_eq!(biome, Biome::Plains);
     }
 
+    #[test]
+    fn test_dominant_material() {
+        assert_eq!(Material::dominant([255, 0, 0, 0]), Material::Grass);
+        assert_eq!(Material::dominant([10, 20, 30, 195]), Material::Snow);
+        assert_eq!(Material::dominant([100, 100, 55, 0]), Material::Grass);
+    }
+
     #[test]
     fn test_structure_creation() {
         let structure = Structure::new(
@@ -59,6 +59,24 @@ impl Season {
             Season::Winter => -15.0,
         }
     }
+
+    /// Multiplier applied to vegetation growth during the season.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::temporal::Season;
+    ///
+    /// assert!(Season::Spring.growth_modifier() > Season::Winter.growth_modifier());
+    /// ```
+    pub fn growth_modifier(&self) -> f32 {
+        match self {
+            Season::Spring => 1.2,
+            Season::Summer => 1.0,
+            Season::Fall => 0.5,
+            Season::Winter => 0.1,
+        }
+    }
 }
 
 #[cfg(test)]
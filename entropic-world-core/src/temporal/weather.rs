@@ -112,6 +112,23 @@ impl Weather {
     pub fn is_clear(&self) -> bool {
         self.condition == WeatherCondition::Clear
     }
+
+    /// Multiplier applied to vegetation growth under the current conditions.
+    ///
+    /// Rain helps plants grow, storms and snow set them back, and freezing temperatures
+    /// halt growth entirely.
+    pub fn growth_modifier(&self) -> f32 {
+        if self.temperature <= 0.0 {
+            return 0.0;
+        }
+        match self.condition {
+            WeatherCondition::Clear => 1.0,
+            WeatherCondition::Cloudy | WeatherCondition::Fog => 0.9,
+            WeatherCondition::Rainy => 1.3,
+            WeatherCondition::Stormy => 0.7,
+            WeatherCondition::Snowy => 0.2,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,197 @@
+use crate::constants::DEFAULT_CHUNK_SIZE;
+use crate::economy::{Market, ResourceType};
+use crate::events::{EventType, WorldEvent};
+use crate::population::{Entity, EntityType, NPC};
+use crate::spatial::ChunkCoord;
+use crate::temporal::WorldTime;
+use crate::world::{World, WorldRng};
+
+const ENTITY_TYPES: [EntityType; 5] = [
+    EntityType::NPC,
+    EntityType::Animal,
+    EntityType::Structure,
+    EntityType::Item,
+    EntityType::Effect,
+];
+
+const NPC_NAMES: [&str; 6] = ["Alice", "Bram", "Cora", "Doran", "Elin", "Fenn"];
+
+/// Builds small, populated worlds for tests.
+///
+/// Everything is derived from the seed, so the same builder always produces the same
+/// world. Entities are numbered `entity_0`, `entity_1`, ...; NPC `npc_i` is bound to
+/// `entity_i` and markets are numbered `market_i`.
+///
+/// # Examples
+///
+/// ```
+/// use entropic_world_core::test_support::TestWorldBuilder;
+///
+/// let world = TestWorldBuilder::new().with_entities(10).with_npcs(3).build();
+/// assert_eq!(world.total_entities(), 10);
+/// assert_eq!(world.npcs["npc_2"].entity_id, "entity_2");
+/// assert_eq!(world.total_chunks(), 4);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TestWorldBuilder {
+    width_chunks: u32,
+    height_chunks: u32,
+    seed: u64,
+    entities: usize,
+    npcs: usize,
+    markets: usize,
+    chunks: bool,
+}
+
+impl TestWorldBuilder {
+    /// A 2x2 chunk world seeded with 42, holding 4 entities, 2 NPCs and a market, with
+    /// its chunks initialized.
+    pub fn new() -> Self {
+        Self {
+            width_chunks: 2,
+            height_chunks: 2,
+            seed: 42,
+            entities: 4,
+            npcs: 2,
+            markets: 1,
+            chunks: true,
+        }
+    }
+
+    pub fn with_size(mut self, width_chunks: u32, height_chunks: u32) -> Self {
+        self.width_chunks = width_chunks.max(1);
+        self.height_chunks = height_chunks.max(1);
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_entities(mut self, count: usize) -> Self {
+        self.entities = count;
+        self
+    }
+
+    /// Sets the number of NPCs. NPCs beyond the number of entities get entities of their
+    /// own.
+    pub fn with_npcs(mut self, count: usize) -> Self {
+        self.npcs = count;
+        self
+    }
+
+    pub fn with_markets(mut self, count: usize) -> Self {
+        self.markets = count;
+        self
+    }
+
+    /// Sets whether the world's chunks are initialized. Skipping them keeps tests that
+    /// don't need terrain fast.
+    pub fn with_chunks(mut self, chunks: bool) -> Self {
+        self.chunks = chunks;
+        self
+    }
+
+    pub fn build(&self) -> World {
+        let mut world = World::new(
+            "Test World".to_string(),
+            "test_dna".to_string(),
+            self.width_chunks,
+            self.height_chunks,
+        );
+        world.reseed(self.seed);
+        if self.chunks {
+            world.initialize_chunks();
+        }
+
+        // A separate generator, so the fixture doesn't consume the world's random state
+        let mut rng = WorldRng::new(self.seed);
+        let max_x = self.width_chunks as f32 * DEFAULT_CHUNK_SIZE;
+        let max_y = self.height_chunks as f32 * DEFAULT_CHUNK_SIZE;
+        for index in 0..self.entities.max(self.npcs) {
+            let entity_type = if index < self.npcs {
+                EntityType::NPC
+            } else {
+                ENTITY_TYPES[index % ENTITY_TYPES.len()]
+            };
+            let x = rng.next_f32() * max_x;
+            let y = rng.next_f32() * max_y;
+            world.add_entity(entity(&format!("entity_{}", index), entity_type, x, y));
+        }
+
+        for index in 0..self.npcs {
+            let name = NPC_NAMES[index % NPC_NAMES.len()];
+            let mut npc = NPC::new(
+                format!("npc_{}", index),
+                name.to_string(),
+                format!("entity_{}", index),
+            );
+            npc.age = 18.0 + rng.below(50) as f32;
+            world.add_npc(npc);
+        }
+
+        for index in 0..self.markets {
+            let mut market =
+                Market::new(format!("market_{}", index), format!("settlement_{}", index));
+            market.add_resource(ResourceType::Food, 50 + rng.below(100) as u32, 60);
+            market.add_resource(ResourceType::Wood, 50 + rng.below(100) as u32, 40);
+            world.add_market(market);
+        }
+        world
+    }
+}
+
+impl Default for TestWorldBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The default `TestWorldBuilder` world.
+pub fn small_world() -> World {
+    TestWorldBuilder::new().build()
+}
+
+/// An entity at `(x, y)` on the ground, in the chunk containing that position.
+pub fn entity(id: &str, entity_type: EntityType, x: f32, y: f32) -> Entity {
+    let chunk = ChunkCoord::from_world_position(x, y);
+    Entity::new(id.to_string(), entity_type, x, y, 0.0, chunk)
+}
+
+/// An event at the start of the world's calendar, at the origin.
+pub fn event(id: &str, event_type: EventType) -> WorldEvent {
+    WorldEvent::new(
+        id.to_string(),
+        event_type,
+        WorldTime::default(),
+        (0.0, 0.0),
+        format!("Test event {}", id),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_is_deterministic() {
+        let builder = TestWorldBuilder::new().with_size(3, 2).with_entities(8).with_chunks(false);
+        let a = builder.build();
+        let b = builder.build();
+        assert_eq!(a.total_chunks(), 0);
+        for (id, entity) in &a.entities {
+            assert_eq!((entity.x, entity.y), (b.entities[id].x, b.entities[id].y));
+            assert!(entity.x < 3.0 * DEFAULT_CHUNK_SIZE && entity.y < 2.0 * DEFAULT_CHUNK_SIZE);
+        }
+    }
+
+    #[test]
+    fn test_npcs_get_entities() {
+        let world = TestWorldBuilder::new().with_entities(1).with_npcs(3).build();
+        assert_eq!(world.total_entities(), 3);
+        assert!(world.npcs.values().all(|npc| world.entities.contains_key(&npc.entity_id)));
+        let chunk = &world.chunks[&world.entities["entity_2"].chunk];
+        assert!(chunk.entities.contains(&"entity_2".to_string()));
+    }
+}
@@ -0,0 +1,8 @@
+pub mod fixtures;
+pub mod strategies;
+
+pub use fixtures::{entity, event, small_world, TestWorldBuilder};
+pub use strategies::{
+    chunk_coord_within, entity_within, event_within, named_resource, position_within,
+    MAX_WORLD_CHUNKS,
+};
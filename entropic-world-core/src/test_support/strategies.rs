@@ -0,0 +1,389 @@
+use proptest::collection::vec;
+use proptest::prelude::*;
+use crate::constants::{DEFAULT_CHUNK_SIZE, HEIGHTMAP_RESOLUTION};
+use crate::economy::{Market, ResourceType};
+use crate::events::{EventSource, EventType, WorldEvent};
+use crate::population::{Entity, EntityType, Gender, NpcStatus, Occupation, Personality, NPC};
+use crate::spatial::{Biome, Chunk, ChunkCoord};
+use crate::temporal::WorldTime;
+use crate::world::World;
+
+/// Largest world generated by `World`'s strategy, in chunks per side. Worlds stay small
+/// because every initialized chunk carries full heightmap buffers.
+pub const MAX_WORLD_CHUNKS: u32 = 4;
+
+/// Short lowercase identifiers, for IDs and names.
+pub fn id() -> impl Strategy<Value = String> {
+    "[a-z][a-z0-9_]{0,11}"
+}
+
+/// A chunk coordinate inside a `width` by `height` chunk world.
+pub fn chunk_coord_within(width: u32, height: u32) -> impl Strategy<Value = ChunkCoord> {
+    (0..width.max(1), 0..height.max(1)).prop_map(|(x, y)| ChunkCoord::new(x, y))
+}
+
+/// A world-space position inside a `width` by `height` chunk world.
+pub fn position_within(width: u32, height: u32) -> impl Strategy<Value = (f32, f32)> {
+    let max_x = width.max(1) as f32 * DEFAULT_CHUNK_SIZE;
+    let max_y = height.max(1) as f32 * DEFAULT_CHUNK_SIZE;
+    (0.0..max_x, 0.0..max_y)
+}
+
+/// An entity positioned inside a `width` by `height` chunk world, with its chunk
+/// matching its position.
+pub fn entity_within(width: u32, height: u32) -> impl Strategy<Value = Entity> {
+    (
+        id(),
+        any::<EntityType>(),
+        position_within(width, height),
+        0.0f32..100.0,
+        (-5.0f32..5.0, -5.0f32..5.0),
+        0.0f32..=100.0,
+    )
+        .prop_map(|(id, entity_type, (x, y), z, velocity, health)| {
+            let chunk = ChunkCoord::from_world_position(x, y);
+            let mut entity = Entity::new(id, entity_type, x, y, z, chunk);
+            entity.velocity = velocity;
+            entity.health = health;
+            entity.is_alive = health > 0.0;
+            entity
+        })
+}
+
+/// A world event at a position inside a `width` by `height` chunk world.
+pub fn event_within(width: u32, height: u32) -> impl Strategy<Value = WorldEvent> {
+    (
+        id(),
+        any::<EventType>(),
+        any::<WorldTime>(),
+        position_within(width, height),
+        ".{0,32}",
+        -10i32..10,
+        vec(id(), 0..4),
+    )
+        .prop_map(|(id, event_type, time, location, description, priority, entities)| {
+            WorldEvent::new(id, event_type, time, location, description)
+                .with_priority(priority)
+                .with_entities(entities)
+        })
+}
+
+impl Arbitrary for ChunkCoord {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        chunk_coord_within(MAX_WORLD_CHUNKS, MAX_WORLD_CHUNKS).boxed()
+    }
+}
+
+impl Arbitrary for WorldTime {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (1u32..1000, 1u8..=12, 1u8..=28, 0u8..24, 0u8..60, 0u8..60, 0u64..20)
+            .prop_map(|(year, month, day, hour, minute, second, tick)| WorldTime {
+                tick,
+                ..WorldTime::new(year, month, day, hour, minute, second)
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for EntityType {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(EntityType::NPC),
+            Just(EntityType::Animal),
+            Just(EntityType::Structure),
+            Just(EntityType::Item),
+            Just(EntityType::Effect),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for Biome {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(Biome::Forest),
+            Just(Biome::Desert),
+            Just(Biome::Mountains),
+            Just(Biome::Plains),
+            Just(Biome::Swamp),
+            Just(Biome::Tundra),
+            Just(Biome::Ocean),
+            Just(Biome::Grassland),
+            any::<u32>().prop_map(Biome::Custom),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for ResourceType {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![named_resource(), (0u32..16).prop_map(ResourceType::Custom)].boxed()
+    }
+}
+
+impl Arbitrary for EventType {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(EventType::NPCBirth),
+            Just(EventType::NPCDeath),
+            Just(EventType::NPCMarriage),
+            Just(EventType::FactionWar),
+            Just(EventType::Settlement),
+            Just(EventType::TradeCompleted),
+            Just(EventType::Disaster),
+            Just(EventType::Discovery),
+            Just(EventType::DiseaseOutbreak),
+            Just(EventType::EcosystemWarning),
+            Just(EventType::Extinction),
+            Just(EventType::SpeciesIntroduced),
+            id().prop_map(EventType::Custom),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for EventSource {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(EventSource::External),
+            id().prop_map(EventSource::System),
+            id().prop_map(EventSource::Trigger),
+            id().prop_map(EventSource::Chain),
+            id().prop_map(EventSource::Template),
+            id().prop_map(EventSource::Recurring),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for Entity {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        entity_within(MAX_WORLD_CHUNKS, MAX_WORLD_CHUNKS).boxed()
+    }
+}
+
+impl Arbitrary for Personality {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        let trait_value = || 0.0f32..=1.0;
+        (trait_value(), trait_value(), trait_value(), trait_value(), trait_value(), trait_value())
+            .prop_map(|(aggression, courage, honesty, intelligence, greed, compassion)| {
+                Personality {
+                    aggression,
+                    courage,
+                    honesty,
+                    intelligence,
+                    greed,
+                    compassion,
+                }
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for NPC {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        let gender = prop_oneof![Just(Gender::Male), Just(Gender::Female), Just(Gender::Other)];
+        let status = prop_oneof![
+            Just(NpcStatus::Active),
+            Just(NpcStatus::Idle),
+            Just(NpcStatus::Working),
+            Just(NpcStatus::Sleeping),
+            Just(NpcStatus::Traveling),
+        ];
+        let occupation = proptest::option::of(prop_oneof![
+            Just(Occupation::Hunter),
+            Just(Occupation::Fisher),
+            Just(Occupation::Farmer),
+            Just(Occupation::Lumberjack),
+            Just(Occupation::Merchant),
+            Just(Occupation::Guard),
+            Just(Occupation::Crafter),
+        ]);
+        (
+            (id(), "[A-Z][a-z]{2,9}", id()),
+            proptest::option::of(id()),
+            any::<Personality>(),
+            vec((id(), 0.0f32..=1.0), 0..4),
+            (status, 0.0f32..90.0, gender, occupation),
+        )
+            .prop_map(|((id, name, entity_id), faction, personality, skills, details)| {
+                let (status, age, gender, occupation) = details;
+                let mut npc = NPC::new(id, name, entity_id);
+                npc.faction = faction;
+                npc.personality = personality;
+                npc.skills = skills.into_iter().collect();
+                npc.status = status;
+                npc.age = age;
+                npc.gender = gender;
+                npc.occupation = occupation;
+                npc
+            })
+            .boxed()
+    }
+}
+
+/// The resources other than `ResourceType::Custom`.
+pub fn named_resource() -> impl Strategy<Value = ResourceType> {
+    prop_oneof![
+        Just(ResourceType::Food),
+        Just(ResourceType::Wood),
+        Just(ResourceType::Metal),
+        Just(ResourceType::Stone),
+        Just(ResourceType::Cloth),
+        Just(ResourceType::Herbs),
+        Just(ResourceType::Gold),
+    ]
+}
+
+impl Arbitrary for Market {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Generates markets trading named resources only: a `Custom` resource key can't be
+    /// written as a JSON object key, so markets holding one don't serialize to JSON.
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (id(), id(), vec((named_resource(), 0u32..1000, 0u32..1000), 0..6))
+            .prop_map(|(id, settlement_id, resources)| {
+                let mut market = Market::new(id, settlement_id);
+                for (resource, supply, demand) in resources {
+                    market.add_resource(resource, supply, demand);
+                }
+                market
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for WorldEvent {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            event_within(MAX_WORLD_CHUNKS, MAX_WORLD_CHUNKS),
+            any::<EventSource>(),
+            proptest::option::of(id()),
+        )
+            .prop_map(|(event, source, correlation_id)| {
+                let event = event.with_source(source);
+                match correlation_id {
+                    Some(correlation_id) => event.with_correlation(correlation_id),
+                    None => event,
+                }
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Chunk {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Generates chunks with a handful of edited heightmap and vegetation samples rather
+    /// than fully random buffers, which would make every case tens of thousands of values.
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        let samples = HEIGHTMAP_RESOLUTION * HEIGHTMAP_RESOLUTION;
+        (
+            any::<ChunkCoord>(),
+            any::<Biome>(),
+            -10.0f32..10.0,
+            vec((0..samples, -50.0f32..200.0, any::<u8>()), 0..16),
+            any::<bool>(),
+        )
+            .prop_map(move |(coord, biome, water_level, edits, loaded)| {
+                let mut chunk = Chunk::new(coord);
+                chunk.biome = biome;
+                chunk.water_level = water_level;
+                for (index, height, vegetation) in edits {
+                    chunk.elevation[index] = height;
+                    chunk.vegetation[index] = vegetation;
+                }
+                chunk.loaded = loaded;
+                chunk
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for World {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Generates seeded worlds of up to `MAX_WORLD_CHUNKS` per side, populated with
+    /// entities inside their bounds, NPCs bound to those entities, markets and scheduled
+    /// events. IDs are numbered so they never collide. Chunks are left uninitialized; call
+    /// `initialize_chunks` when a test needs them.
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (1..=MAX_WORLD_CHUNKS, 1..=MAX_WORLD_CHUNKS)
+            .prop_flat_map(|(width, height)| {
+                (
+                    Just((width, height)),
+                    any::<u64>(),
+                    vec(entity_within(width, height), 0..12),
+                    vec(any::<NPC>(), 0..6),
+                    vec(any::<Market>(), 0..3),
+                    vec(event_within(width, height), 0..4),
+                )
+            })
+            .prop_map(|((width, height), seed, entities, npcs, markets, events)| {
+                let mut world = World::new(
+                    "Arbitrary World".to_string(),
+                    "test_dna".to_string(),
+                    width,
+                    height,
+                );
+                world.reseed(seed);
+                let entity_count = entities.len();
+                for (index, mut entity) in entities.into_iter().enumerate() {
+                    entity.id = format!("entity_{}", index);
+                    world.add_entity(entity);
+                }
+                for (index, mut npc) in npcs.into_iter().enumerate() {
+                    npc.id = format!("npc_{}", index);
+                    if index < entity_count {
+                        npc.entity_id = format!("entity_{}", index);
+                    }
+                    world.add_npc(npc);
+                }
+                for (index, mut market) in markets.into_iter().enumerate() {
+                    market.id = format!("market_{}", index);
+                    world.add_market(market);
+                }
+                for (tick, event) in (1..).zip(events) {
+                    world.event_queue.schedule(tick, event);
+                }
+                world
+            })
+            .boxed()
+    }
+}
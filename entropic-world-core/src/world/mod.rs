@@ -1,9 +1,10 @@
 pub mod world_config;
+pub mod rng;
 pub mod world_metadata;
 pub mod world_state;
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use uuid::Uuid;
 use chrono::Utc;
 
@@ -11,13 +12,30 @@ use crate::spatial::{Chunk, ChunkCoord, SpatialIndex};
 use crate::temporal::time::WorldTime;
 use crate::population::{Entity, EntityId, NPC, NpcId, Faction, FactionId};
 use crate::economy::{Market, Settlement, SettlementId, TradeRoute};
-use crate::ecosystem::{Species, SpeciesId};
-use crate::events::{WorldEvent, EventQueue};
+use crate::ecosystem::{
+    AnimalPopulations, Diet, Disease, DiseaseId, FloraProfile, HarvestRule, Invasion, Outbreak, SelectionPressure,
+    Species, SpeciesId,
+};
+use crate::ecosystem::animal_population::LEGACY_CHUNK;
+use crate::ecosystem::flora::HARVEST_PER_WORKER;
+use crate::economy::ResourceType;
+use crate::population::Occupation;
+use crate::temporal::Season;
+use crate::events::{
+    EventBus, EventChain, EventSource, EventChains, EventContext, EventHistory, EventLog, EventTemplate, EventTemplateRegistry, EventTrigger, EventType, WorldEvent, EventQueue, Recurrence,
+    RecurringEvent, SpawnContext, TriggerEngine,
+};
+use crate::events::event_bus::MAX_DISPATCHES_PER_TICK;
+use crate::observability::{self, increment_counter, Timer};
 
+pub use rng::WorldRng;
 pub use world_config::WorldConfig;
 pub use world_metadata::{SemanticVersion, WorldMetadata};
 pub use world_state::WorldState;
 
+/// Temperature (in degrees Celsius) at which species experience no climate pressure.
+const COMFORT_TEMPERATURE: f32 = 15.0;
+
 /// Serializes a map of chunk coordinates to chunks as a sequence of `(ChunkCoord, Chunk)` pairs.
 ///
 /// This helper converts the provided `HashMap<ChunkCoord, Chunk>` into a `Vec<(&ChunkCoord, &Chunk)>`
@@ -71,7 +89,10 @@ where
     Ok(vec.into_iter().collect())
 }
 
+// The derived impls become `World::serialize` and `World::deserialize`, which the trait
+// impls below wrap to finish loading older saves
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct World {
     pub id: String,
     pub name: String,
@@ -98,10 +119,41 @@ pub struct World {
     pub trade_routes: Vec<TradeRoute>,
     
     pub species: HashMap<SpeciesId, Species>,
-    pub animal_populations: HashMap<SpeciesId, u32>,
+    pub animal_populations: AnimalPopulations,
+    #[serde(default)]
+    pub diseases: HashMap<DiseaseId, Disease>,
+    #[serde(default)]
+    pub outbreaks: Vec<Outbreak>,
+    #[serde(default)]
+    pub harvest_rules: Vec<HarvestRule>,
+    #[serde(default)]
+    pub overhunted_species: Vec<SpeciesId>,
+    #[serde(default)]
+    pub extinct_species: Vec<SpeciesId>,
+    #[serde(default)]
+    pub invasions: Vec<Invasion>,
+    #[serde(default)]
+    pub pending_invasions: HashMap<String, Invasion>,
     
     pub event_queue: EventQueue,
-    pub event_history: Vec<WorldEvent>,
+    pub event_history: EventHistory,
+    #[serde(skip)]
+    pub event_bus: EventBus,
+    #[serde(default)]
+    pub triggers: TriggerEngine,
+    #[serde(default)]
+    pub chains: EventChains,
+    #[serde(default)]
+    pub event_templates: EventTemplateRegistry,
+    #[serde(default)]
+    pub random_event_chance: f32,
+
+    #[serde(default)]
+    pub seed: u64,
+    #[serde(default)]
+    pub rng: WorldRng,
+    #[serde(default)]
+    pub tags: BTreeSet<String>,
     
     pub time_scale: f32,
     pub weather_enabled: bool,
@@ -112,6 +164,22 @@ pub struct World {
     pub persistent: bool,
 }
 
+impl Serialize for World {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        World::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for World {
+    /// Restores the world, then spreads animals from saves without per-chunk populations
+    /// over the habitat of their species.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut world = World::deserialize(deserializer)?;
+        world.place_unplaced_animals();
+        Ok(world)
+    }
+}
+
 impl World {
     /// Creates a new World with the supplied name, game DNA identifier, and chunk grid size, initialized to default runtime state and feature flags enabled.
     ///
@@ -139,6 +207,7 @@ impl World {
         height_chunks: u32,
     ) -> Self {
         let now = Utc::now();
+        let seed = Uuid::new_v4().as_u128() as u64;
         Self {
             id: Uuid::new_v4().to_string(),
             name,
@@ -159,9 +228,24 @@ impl World {
             settlements: HashMap::new(),
             trade_routes: Vec::new(),
             species: HashMap::new(),
-            animal_populations: HashMap::new(),
+            animal_populations: AnimalPopulations::new(),
+            diseases: HashMap::new(),
+            outbreaks: Vec::new(),
+            harvest_rules: Vec::new(),
+            overhunted_species: Vec::new(),
+            extinct_species: Vec::new(),
+            invasions: Vec::new(),
+            pending_invasions: HashMap::new(),
             event_queue: EventQueue::new(),
-            event_history: Vec::new(),
+            event_history: EventHistory::new(),
+            event_bus: EventBus::new(),
+            triggers: TriggerEngine::new(),
+            chains: EventChains::new(),
+            event_templates: EventTemplateRegistry::new(),
+            random_event_chance: 0.0,
+            seed,
+            rng: WorldRng::new(seed),
+            tags: BTreeSet::new(),
             time_scale: crate::constants::DEFAULT_TIME_SCALE,
             weather_enabled: true,
             seasons_enabled: true,
@@ -209,9 +293,38 @@ impl World {
         world.economy_enabled = config.economy_enabled;
         world.ai_enabled = config.ai_enabled;
         world.persistent = config.persistent;
+        world.event_history.set_retention(config.history_retention);
+        world.event_history.set_compaction(config.history_compaction);
+        if let Some(seed) = config.seed {
+            world.reseed(seed);
+        }
         world
     }
 
+    /// Replaces the world's seed and restarts its random generator from it.
+    ///
+    /// Worlds created without a configured seed get a random one; reseeding makes every
+    /// later random draw reproducible.
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = WorldRng::new(seed);
+    }
+
+    /// Adds a world tag, such as `"war"` or `"plague_year"`, used to condition events.
+    pub fn add_tag(&mut self, tag: impl Into<String>) {
+        self.tags.insert(tag.into());
+    }
+
+    /// Removes a world tag. Returns `false` if the world did not carry it.
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        self.tags.remove(tag)
+    }
+
+    /// Reports whether the world carries `tag`.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(tag)
+    }
+
     /// Populates the world's chunk map with newly created chunks covering its configured dimensions.
     ///
     /// Iterates over the range [0, width_chunks) × [0, height_chunks) and inserts a new `Chunk` at
@@ -381,8 +494,8 @@ impl World {
     /// Registers a species in the world and sets its initial animal population.
     ///
     /// The species is stored in the world's `species` map keyed by its `id`, and
-    /// the species' `base_population` is inserted into `animal_populations` under
-    /// the same `id`.
+    /// the species' `base_population` is spread evenly over its `habitat_chunks` in
+    /// `animal_populations`.
     ///
     /// # Examples
     ///
@@ -390,13 +503,830 @@ impl World {
     /// let mut world = World::new("Test".to_string(), "dna".to_string(), 10, 10);
     /// let species = Species { id: "rabbit".to_string(), base_population: 50, ..Default::default() };
     /// world.add_species(species);
-    /// assert_eq!(world.animal_populations.get("rabbit"), Some(&50));
+    /// assert_eq!(world.animal_populations.total(&"rabbit".to_string()), 50);
     /// ```
     pub fn add_species(&mut self, species: Species) {
         let species_id = species.id.clone();
         let base_population = species.base_population;
+        let habitat = self.habitat_chunks(&species);
         self.species.insert(species_id.clone(), species);
-        self.animal_populations.insert(species_id, base_population);
+        self.animal_populations
+            .distribute(&habitat, &species_id, base_population);
+    }
+
+    /// Returns the chunks of the world grid a species can live in, sorted by coordinate.
+    ///
+    /// Generated chunks whose biome the species does not prefer are excluded. If no chunk
+    /// qualifies, every grid chunk is returned, and a world without a grid falls back to
+    /// the origin chunk.
+    pub fn habitat_chunks(&self, species: &Species) -> Vec<ChunkCoord> {
+        let grid: Vec<ChunkCoord> = (0..self.width_chunks)
+            .flat_map(|x| (0..self.height_chunks).map(move |y| ChunkCoord::new(x, y)))
+            .collect();
+        let habitat: Vec<ChunkCoord> = grid
+            .iter()
+            .copied()
+            .filter(|coord| {
+                species.preferred_biomes.is_empty()
+                    || !self
+                        .chunks
+                        .get(coord)
+                        .is_some_and(|chunk| !species.prefers_biome(&chunk.biome))
+            })
+            .collect();
+
+        if !habitat.is_empty() {
+            habitat
+        } else if !grid.is_empty() {
+            grid
+        } else {
+            vec![ChunkCoord::new(0, 0)]
+        }
+    }
+
+    /// Spreads animals loaded without a chunk evenly over their species' habitat, as
+    /// `add_species` does. Animals of unknown species stay in `LEGACY_CHUNK`.
+    fn place_unplaced_animals(&mut self) {
+        for (species_id, count) in self.animal_populations.take_unplaced() {
+            let habitat = match self.species.get(&species_id) {
+                Some(species) => self.habitat_chunks(species),
+                None => vec![LEGACY_CHUNK],
+            };
+            self.animal_populations.distribute(&habitat, &species_id, count);
+        }
+    }
+
+    /// Adds `count` animals of a registered species to a single chunk.
+    ///
+    /// Use this to localize spawning, for example a herd migrating into a valley.
+    /// Returns `false` and spawns nothing if the species is unknown.
+    pub fn spawn_animals(&mut self, species_id: &SpeciesId, coord: ChunkCoord, count: u32) -> bool {
+        if !self.species.contains_key(species_id) {
+            return false;
+        }
+        self.animal_populations.add(coord, species_id.clone(), count);
+        self.extinct_species.retain(|id| id != species_id);
+        true
+    }
+
+    /// Counts the animals of a species living within `radius` meters of `(x, y)`.
+    ///
+    /// A chunk's animals are counted when the chunk's center lies inside the radius.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::World;
+    /// use entropic_world_core::ecosystem::{Diet, Species};
+    /// use entropic_world_core::spatial::ChunkCoord;
+    ///
+    /// let mut world = World::new("W".into(), "dna".into(), 4, 4);
+    /// let mut wolf = Species::new("wolf".into(), "Wolf".into(), Diet::Carnivore);
+    /// wolf.base_population = 0;
+    /// world.add_species(wolf);
+    /// world.spawn_animals(&"wolf".into(), ChunkCoord::new(3, 3), 6);
+    /// assert_eq!(world.animals_near(&"wolf".into(), 896.0, 896.0, 100.0), 6);
+    /// ```
+    pub fn animals_near(&self, species_id: &SpeciesId, x: f32, y: f32, radius: f32) -> u32 {
+        self.animal_populations.in_radius(species_id, x, y, radius)
+    }
+
+    /// Schedules `event` to recur according to `recurrence`, starting with its first
+    /// occurrence after the current tick. Returns the tick of the first occurrence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::World;
+    /// use entropic_world_core::events::{EventType, Recurrence, WorldEvent};
+    ///
+    /// let mut world = World::new("W".into(), "dna".into(), 1, 1);
+    /// let patrol = WorldEvent::new("patrol".into(), EventType::Custom("patrol".into()), world.current_time, (0.0, 0.0), "Patrol".into());
+    /// assert_eq!(world.schedule_recurring(patrol, Recurrence::EveryTicks(2)), 2);
+    /// for _ in 0..6 {
+    ///     world.advance_tick();
+    /// }
+    /// assert_eq!(world.event_history.len(), 3);
+    /// ```
+    pub fn schedule_recurring(&mut self, event: WorldEvent, recurrence: Recurrence) -> u64 {
+        let first_tick = recurrence.first_tick_after(self.current_tick, &self.current_time);
+        self.event_queue
+            .schedule_recurring(RecurringEvent::new(event, recurrence, first_tick));
+        first_tick
+    }
+
+    /// Registers an event chain so it can be started with `start_chain`.
+    pub fn register_chain(&mut self, chain: EventChain) {
+        self.chains.register(chain);
+    }
+
+    /// Starts an instance of a registered event chain at `location` and enqueues the event
+    /// of its first step. Returns the instance id, or `None` if the chain is unknown.
+    ///
+    /// When a chain event is dispatched, the outcome reported by handlers through
+    /// `EventContext::set_outcome` and the current world state select the branch that
+    /// enqueues the next step.
+    pub fn start_chain(&mut self, chain_id: &str, location: (f32, f32)) -> Option<String> {
+        let (instance_id, tick, event) =
+            self.chains
+                .start(chain_id, location, self.current_tick, self.current_time)?;
+        self.event_queue.schedule(tick, event);
+        Some(instance_id)
+    }
+
+    /// Registers a template the world can spawn events from, replacing any template with
+    /// the same id.
+    pub fn register_event_template(&mut self, template: EventTemplate) {
+        self.event_templates.register(template);
+    }
+
+    /// Spawns an event from the registered templates and enqueues it for the next tick.
+    ///
+    /// A chunk of the world grid is picked at random, then a template is sampled with the
+    /// world RNG using its weight for the chunk's biome, the current season, the world
+    /// tags and the current world state. The event takes place at the chunk's center.
+    /// Returns the enqueued event, or `None` if no template can spawn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::World;
+    /// use entropic_world_core::events::{EventTemplate, EventType};
+    ///
+    /// let mut world = World::new("W".into(), "dna".into(), 4, 4);
+    /// world.reseed(7);
+    /// world.register_event_template(EventTemplate::new("caravan", EventType::TradeCompleted, "A caravan arrives", 1.0));
+    /// let event = world.generate_random_event().unwrap();
+    /// assert_eq!(event.event_type, EventType::TradeCompleted);
+    /// assert_eq!(world.event_queue.len(), 1);
+    /// ```
+    pub fn generate_random_event(&mut self) -> Option<WorldEvent> {
+        if self.event_templates.is_empty() {
+            return None;
+        }
+
+        let coord = ChunkCoord::new(
+            self.rng.below(self.width_chunks as u64) as u32,
+            self.rng.below(self.height_chunks as u64) as u32,
+        );
+        let context = SpawnContext {
+            biome: self.chunks.get(&coord).map(|chunk| chunk.biome),
+            season: Season::from_month(self.current_time.month),
+            tags: &self.tags,
+        };
+        let mut rng = std::mem::take(&mut self.rng);
+        let template = self
+            .event_templates
+            .sample(&context, |condition| self.triggers.condition_met(condition, self), &mut rng)
+            .cloned();
+        self.rng = rng;
+        let template = template?;
+
+        let event = WorldEvent::new(
+            format!("{}_{}_{}_{}", template.id, self.current_tick, coord.x, coord.y),
+            template.event_type,
+            self.current_time,
+            coord.center(),
+            template.description,
+        )
+        .with_source(EventSource::Template(template.id));
+        self.event_queue.schedule(self.current_tick + 1, event.clone());
+        Some(event)
+    }
+
+    /// Registers a trigger evaluated at the start of every tick, replacing any trigger
+    /// with the same id.
+    pub fn add_trigger(&mut self, trigger: EventTrigger) {
+        self.triggers.add_trigger(trigger);
+    }
+
+    /// Registers a disease so outbreaks referencing its id can be simulated.
+    pub fn add_disease(&mut self, disease: Disease) {
+        self.diseases.insert(disease.id.clone(), disease);
+    }
+
+    /// Reports whether `coord` lies inside the world's configured chunk grid.
+    pub fn contains_chunk_coord(&self, coord: &ChunkCoord) -> bool {
+        coord.x < self.width_chunks && coord.y < self.height_chunks
+    }
+
+    /// Returns the ids of living NPCs whose entity currently stands in `coord`, sorted by id.
+    pub fn npcs_in_chunk(&self, coord: &ChunkCoord) -> Vec<NpcId> {
+        let mut ids: Vec<NpcId> = self
+            .npcs
+            .values()
+            .filter(|npc| npc.is_alive())
+            .filter(|npc| {
+                self.entities
+                    .get(&npc.entity_id)
+                    .is_some_and(|entity| entity.chunk == *coord)
+            })
+            .map(|npc| npc.id.clone())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Starts an outbreak, recording a `DiseaseOutbreak` event in the history.
+    ///
+    /// If the same disease is already active in the outbreak's chunk, the new infections
+    /// are merged into the existing outbreak and no event is recorded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::World;
+    /// use entropic_world_core::ecosystem::{Disease, Outbreak};
+    /// use entropic_world_core::spatial::ChunkCoord;
+    ///
+    /// let mut world = World::new("W".into(), "dna".into(), 4, 4);
+    /// world.add_disease(Disease::new("flu".into(), "Flu".into()).with_npc_susceptibility(0.5));
+    /// world.start_outbreak(Outbreak::new("flu".into(), ChunkCoord::new(1, 1), 0).with_carriers(3));
+    /// assert_eq!(world.outbreaks.len(), 1);
+    /// assert_eq!(world.event_history.len(), 1);
+    /// ```
+    pub fn start_outbreak(&mut self, outbreak: Outbreak) {
+        if let Some(existing) = self
+            .outbreaks
+            .iter_mut()
+            .find(|o| o.disease_id == outbreak.disease_id && o.chunk == outbreak.chunk)
+        {
+            existing.absorb(outbreak);
+            return;
+        }
+
+        let disease_name = self
+            .diseases
+            .get(&outbreak.disease_id)
+            .map_or(outbreak.disease_id.clone(), |d| d.name.clone());
+        let event = WorldEvent::new(
+            format!(
+                "outbreak_{}_{}_{}_{}",
+                outbreak.disease_id, outbreak.chunk.x, outbreak.chunk.y, self.current_tick
+            ),
+            EventType::DiseaseOutbreak,
+            self.current_time,
+            outbreak.chunk.center(),
+            format!(
+                "Outbreak of {} in chunk ({}, {})",
+                disease_name, outbreak.chunk.x, outbreak.chunk.y
+            ),
+        )
+        .with_source(EventSource::System("disease".to_string()));
+        self.event_history.record(self.current_tick, event);
+        self.outbreaks.push(outbreak);
+    }
+
+    /// Advances every active outbreak by one epidemic step.
+    ///
+    /// Infected animals that die are removed from the outbreak's chunk, infected NPCs that
+    /// die are marked dead with an `NPCDeath` event, and each outbreak seeds adjacent chunks
+    /// and the destinations of active trade routes leaving its chunk. Outbreaks without
+    /// infected hosts are dropped. Intended to be driven at a coarser cadence than
+    /// `advance_tick`, for example once per in-game day.
+    #[cfg_attr(feature = "observability", tracing::instrument(level = "debug", skip_all))]
+    pub fn simulate_diseases(&mut self) {
+        let _timer = Timer::subsystem("diseases");
+        let mut outbreaks = std::mem::take(&mut self.outbreaks);
+        let mut seeded = Vec::new();
+
+        for outbreak in outbreaks.iter_mut() {
+            let disease = match self.diseases.get(&outbreak.disease_id) {
+                Some(disease) => disease.clone(),
+                None => continue,
+            };
+
+            let local_npcs = self.npcs_in_chunk(&outbreak.chunk);
+            let local_animals = self.animal_populations.chunk_populations(&outbreak.chunk);
+            let step = outbreak.step(&disease, &local_animals, &local_npcs);
+
+            for (species_id, deaths) in &step.animal_deaths {
+                self.animal_populations
+                    .remove(outbreak.chunk, species_id, *deaths);
+            }
+            for npc_id in &step.npc_deaths {
+                self.record_npc_death(npc_id, format!("Died of {}", disease.name), "disease");
+            }
+
+            if outbreak.is_over() {
+                continue;
+            }
+            for neighbor in outbreak.chunk.neighbors() {
+                if self.contains_chunk_coord(&neighbor) {
+                    seeded.extend(outbreak.seed(neighbor, disease.adjacency_spread, self.current_tick));
+                }
+            }
+            for destination in self.trade_destinations_from(&outbreak.chunk) {
+                seeded.extend(outbreak.seed(destination, disease.trade_spread, self.current_tick));
+            }
+        }
+
+        outbreaks.retain(|o| !o.is_over());
+        self.outbreaks = outbreaks;
+        for outbreak in seeded {
+            self.start_outbreak(outbreak);
+        }
+        self.check_extinctions();
+    }
+
+    /// Returns the chunks of settlements reached by active trade routes departing from
+    /// settlements located in `coord`.
+    fn trade_destinations_from(&self, coord: &ChunkCoord) -> Vec<ChunkCoord> {
+        let settlement_chunk = |id: &SettlementId| {
+            self.settlements
+                .get(id)
+                .map(|s| ChunkCoord::from_world_position(s.x, s.y))
+        };
+
+        let mut destinations = Vec::new();
+        for route in self.trade_routes.iter().filter(|r| r.is_active()) {
+            if settlement_chunk(&route.from) != Some(*coord) {
+                continue;
+            }
+            if let Some(destination) = settlement_chunk(&route.to) {
+                if destination != *coord && !destinations.contains(&destination) {
+                    destinations.push(destination);
+                }
+            }
+        }
+        destinations
+    }
+
+    /// Registers a rule letting NPCs of a given occupation hunt or fish a species.
+    pub fn add_harvest_rule(&mut self, rule: HarvestRule) {
+        self.harvest_rules.push(rule);
+    }
+
+    /// Runs one harvest for every registered `HarvestRule`.
+    ///
+    /// Each living NPC whose occupation matches a rule catches animals according to its
+    /// skill from its own chunk and the adjacent ones, and delivers the yield to the market
+    /// of the nearest settlement. Catches that fall short because game is scarce are added to
+    /// that market's demand and its price is updated. The first time a species drops below
+    /// its rule's scarcity threshold an `EcosystemWarning` event is recorded; the warning
+    /// re-arms once the population recovers.
+    ///
+    /// Returns the number of animals caught per species.
+    #[cfg_attr(feature = "observability", tracing::instrument(level = "debug", skip_all))]
+    pub fn simulate_hunting(&mut self) -> HashMap<SpeciesId, u32> {
+        let _timer = Timer::subsystem("hunting");
+        let mut catches: HashMap<SpeciesId, u32> = HashMap::new();
+        let rules = self.harvest_rules.clone();
+
+        for rule in &rules {
+            let base_population = match self.species.get(&rule.species_id) {
+                Some(species) => species.base_population,
+                None => continue,
+            };
+
+            let mut workers: Vec<(&NpcId, ChunkCoord, f32, f32, f32)> = self
+                .npcs
+                .values()
+                .filter(|npc| npc.is_alive() && npc.occupation == Some(rule.occupation))
+                .filter_map(|npc| {
+                    let entity = self.entities.get(&npc.entity_id)?;
+                    let skill = npc.get_skill(rule.occupation.skill_name());
+                    Some((&npc.id, entity.chunk, entity.x, entity.y, skill))
+                })
+                .collect();
+            workers.sort_by(|a, b| a.0.cmp(b.0));
+            let workers: Vec<(ChunkCoord, f32, f32, f32)> = workers
+                .into_iter()
+                .map(|(_, chunk, x, y, skill)| (chunk, x, y, skill))
+                .collect();
+
+            for (chunk, x, y, skill) in workers {
+                let mut grounds = vec![chunk];
+                grounds.extend(chunk.neighbors());
+                let local: u32 = grounds
+                    .iter()
+                    .map(|coord| self.animal_populations.in_chunk(coord, &rule.species_id))
+                    .sum();
+                let population = self.animal_populations.total(&rule.species_id);
+                let caught = rule.catch_for(skill, population, base_population).min(local);
+                let shortfall = rule.expected_catch(skill).saturating_sub(caught);
+
+                let mut remaining = caught;
+                for coord in grounds {
+                    remaining -= self.animal_populations.remove(coord, &rule.species_id, remaining);
+                }
+                *catches.entry(rule.species_id.clone()).or_insert(0) += caught;
+
+                let time = self.current_time;
+                if let Some(market) = self
+                    .nearest_market_id(x, y)
+                    .and_then(|id| self.markets.get_mut(&id))
+                {
+                    if caught > 0 {
+                        market.add_supply(rule.resource, caught * rule.yield_per_animal);
+                    }
+                    if shortfall > 0 {
+                        market.add_demand(rule.resource, shortfall * rule.yield_per_animal);
+                        market.update_price(rule.resource, time);
+                    }
+                }
+            }
+
+            let population = self.animal_populations.total(&rule.species_id);
+            let warned = self.overhunted_species.contains(&rule.species_id);
+            if rule.is_scarce(population, base_population) && !warned {
+                self.overhunted_species.push(rule.species_id.clone());
+                let event = WorldEvent::new(
+                    format!("overhunting_{}_{}", rule.species_id, self.current_tick),
+                    EventType::EcosystemWarning,
+                    self.current_time,
+                    (0.0, 0.0),
+                    format!(
+                        "{} overhunted: {} of {} remaining",
+                        rule.species_id, population, base_population
+                    ),
+                )
+                .with_source(EventSource::System("hunting".to_string()));
+                self.event_history.record(self.current_tick, event);
+            } else if !rule.is_scarce(population, base_population) && warned {
+                self.overhunted_species.retain(|id| id != &rule.species_id);
+            }
+        }
+
+        self.check_extinctions();
+        catches
+    }
+
+    /// Returns how many individuals of a herbivore species the world's forage can sustain.
+    ///
+    /// Forage is counted in every chunk whose biome the species prefers, or in every chunk
+    /// if the species has no biome preference. Returns `None` for unknown species.
+    pub fn herbivore_carrying_capacity(&self, species_id: &SpeciesId) -> Option<u32> {
+        let species = self.species.get(species_id)?;
+        let capacity = self
+            .chunks
+            .values()
+            .filter(|chunk| species.preferred_biomes.is_empty() || species.prefers_biome(&chunk.biome))
+            .map(|chunk| chunk.flora.herbivore_capacity(&FloraProfile::for_biome(chunk.biome)))
+            .sum();
+        Some(capacity)
+    }
+
+    /// Grows every chunk's flora by one step, lets herbivores graze it and caps them at what
+    /// the remaining forage sustains.
+    ///
+    /// Growth is scaled by the current season when seasons are enabled and by each chunk's
+    /// weather when weather is enabled. Every chunk's herbivores then eat forage in
+    /// proportion to their number (see `Flora::graze`), so heavy grazing lowers the
+    /// chunk's capacity until regrowth restores it. In every generated chunk, herbivores
+    /// above what the chunk's forage sustains are reduced to it. Intended to run at the same
+    /// cadence as the other ecosystem steps, for example once per in-game day.
+    #[cfg_attr(feature = "observability", tracing::instrument(level = "debug", skip_all))]
+    pub fn simulate_flora(&mut self) {
+        let _timer = Timer::subsystem("flora");
+        let season_modifier = if self.seasons_enabled {
+            Season::from_month(self.current_time.month).growth_modifier()
+        } else {
+            1.0
+        };
+
+        for chunk in self.chunks.values_mut() {
+            let weather_modifier = if self.weather_enabled {
+                chunk.weather.growth_modifier()
+            } else {
+                1.0
+            };
+            let profile = FloraProfile::for_biome(chunk.biome);
+            chunk.flora.grow(&profile, season_modifier * weather_modifier);
+        }
+
+        if self.chunks.is_empty() {
+            return;
+        }
+        let mut herbivores: Vec<SpeciesId> = self
+            .species
+            .values()
+            .filter(|species| species.diet == Diet::Herbivore)
+            .map(|species| species.id.clone())
+            .collect();
+        herbivores.sort();
+
+        let mut herds: HashMap<ChunkCoord, u32> = HashMap::new();
+        for species_id in &herbivores {
+            for (coord, population) in self.animal_populations.chunks_with(species_id) {
+                *herds.entry(coord).or_insert(0) += population;
+            }
+        }
+        for (coord, herd) in herds {
+            if let Some(chunk) = self.chunks.get_mut(&coord) {
+                chunk.flora.graze(&FloraProfile::for_biome(chunk.biome), herd);
+            }
+        }
+
+        for species_id in herbivores {
+            for (coord, population) in self.animal_populations.chunks_with(&species_id) {
+                let capacity = match self.chunks.get(&coord) {
+                    Some(chunk) => chunk.flora.herbivore_capacity(&FloraProfile::for_biome(chunk.biome)),
+                    None => continue,
+                };
+                if population > capacity {
+                    self.animal_populations.set(coord, species_id.clone(), capacity);
+                }
+            }
+        }
+        self.check_extinctions();
+    }
+
+    /// Lets lumberjack and farmer NPCs harvest the flora of the chunk they stand in.
+    ///
+    /// Lumberjacks clear forest cover into `ResourceType::Wood` and farmers gather grown
+    /// crops into `ResourceType::Food`, each taking more with higher occupation skill. The
+    /// yield is delivered to the market of the nearest settlement.
+    ///
+    /// Returns the total units produced per resource.
+    pub fn harvest_flora(&mut self) -> HashMap<ResourceType, u32> {
+        let mut workers: Vec<(&NpcId, Occupation, ChunkCoord, f32, f32, f32)> = self
+            .npcs
+            .values()
+            .filter(|npc| npc.is_alive())
+            .filter_map(|npc| {
+                let occupation = npc.occupation?;
+                if !matches!(occupation, Occupation::Lumberjack | Occupation::Farmer) {
+                    return None;
+                }
+                let entity = self.entities.get(&npc.entity_id)?;
+                let skill = npc.get_skill(occupation.skill_name());
+                Some((&npc.id, occupation, entity.chunk, entity.x, entity.y, skill))
+            })
+            .collect();
+        workers.sort_by(|a, b| a.0.cmp(b.0));
+        let workers: Vec<(Occupation, ChunkCoord, f32, f32, f32)> = workers
+            .into_iter()
+            .map(|(_, occupation, chunk, x, y, skill)| (occupation, chunk, x, y, skill))
+            .collect();
+
+        let mut produced: HashMap<ResourceType, u32> = HashMap::new();
+        for (occupation, coord, x, y, skill) in workers {
+            let chunk = match self.chunks.get_mut(&coord) {
+                Some(chunk) => chunk,
+                None => continue,
+            };
+            let amount = HARVEST_PER_WORKER * (0.5 + skill);
+            let (resource, units) = match occupation {
+                Occupation::Lumberjack => (ResourceType::Wood, chunk.flora.harvest_timber(amount)),
+                _ => (ResourceType::Food, chunk.flora.harvest_crops(amount)),
+            };
+            if units == 0 {
+                continue;
+            }
+
+            *produced.entry(resource).or_insert(0) += units;
+            if let Some(market) = self
+                .nearest_market_id(x, y)
+                .and_then(|id| self.markets.get_mut(&id))
+            {
+                market.add_supply(resource, units);
+            }
+        }
+
+        produced
+    }
+
+    /// Measures the predation and climate pressure a species currently experiences.
+    ///
+    /// Predation is the share of predator individuals in the combined predator and prey
+    /// population. Climate is the mean temperature of the chunks the species inhabits
+    /// (including the seasonal offset when seasons are enabled) relative to the default
+    /// comfort temperature of 15°C. Returns `None` for unknown species.
+    pub fn selection_pressure(&self, species_id: &SpeciesId) -> Option<SelectionPressure> {
+        let species = self.species.get(species_id)?;
+
+        let mut predators: Vec<&SpeciesId> = species.hunted_by.iter().collect();
+        for other in self.species.values() {
+            if other.hunting_prey.contains(species_id) && !predators.contains(&&other.id) {
+                predators.push(&other.id);
+            }
+        }
+        let predator_population: u32 = predators
+            .iter()
+            .map(|id| self.animal_populations.total(id))
+            .sum();
+        let own_population = self.animal_populations.total(species_id);
+        let total = predator_population + own_population;
+        let predation = if total > 0 {
+            predator_population as f32 / total as f32
+        } else {
+            0.0
+        };
+
+        let season_offset = if self.seasons_enabled {
+            Season::from_month(self.current_time.month).temperature_modifier()
+        } else {
+            0.0
+        };
+        let temperatures: Vec<f32> = self
+            .chunks
+            .values()
+            .filter(|chunk| species.preferred_biomes.is_empty() || species.prefers_biome(&chunk.biome))
+            .map(|chunk| chunk.weather.temperature + season_offset)
+            .collect();
+        let climate = if temperatures.is_empty() {
+            0.0
+        } else {
+            temperatures.iter().sum::<f32>() / temperatures.len() as f32 - COMFORT_TEMPERATURE
+        };
+
+        Some(SelectionPressure { predation, climate })
+    }
+
+    /// Advances every species with a living population by one generation of selection.
+    ///
+    /// Each species' heritable traits shift under its current `selection_pressure`.
+    /// Extinct species do not evolve.
+    #[cfg_attr(feature = "observability", tracing::instrument(level = "debug", skip_all))]
+    pub fn simulate_selection(&mut self) {
+        let _timer = Timer::subsystem("selection");
+        let mut species_ids: Vec<SpeciesId> = self.species.keys().cloned().collect();
+        species_ids.sort();
+
+        let pressures: Vec<(SpeciesId, SelectionPressure)> = species_ids
+            .into_iter()
+            .filter(|id| self.animal_populations.total(id) > 0)
+            .filter_map(|id| {
+                let pressure = self.selection_pressure(&id)?;
+                Some((id, pressure))
+            })
+            .collect();
+
+        for (species_id, pressure) in pressures {
+            if let Some(species) = self.species.get_mut(&species_id) {
+                species.traits.evolve(&pressure);
+            }
+        }
+    }
+
+    /// Records an `Extinction` event for every registered species whose population has
+    /// reached zero since the last check.
+    ///
+    /// Extinct species are tracked in `extinct_species`; a species that regains a
+    /// population (for example through reintroduction) is removed from the list and can go
+    /// extinct again later. Returns the species that went extinct during this call.
+    pub fn check_extinctions(&mut self) -> Vec<SpeciesId> {
+        let mut species_ids: Vec<SpeciesId> = self.species.keys().cloned().collect();
+        species_ids.sort();
+
+        let mut newly_extinct = Vec::new();
+        for species_id in species_ids {
+            let population = self.animal_populations.total(&species_id);
+            let recorded = self.extinct_species.contains(&species_id);
+            if population == 0 && !recorded {
+                newly_extinct.push(species_id);
+            } else if population > 0 && recorded {
+                self.extinct_species.retain(|id| id != &species_id);
+            }
+        }
+
+        for species_id in &newly_extinct {
+            let name = self.species[species_id].name.clone();
+            let event = WorldEvent::new(
+                format!("extinction_{}_{}", species_id, self.current_tick),
+                EventType::Extinction,
+                self.current_time,
+                (0.0, 0.0),
+                format!("{} has gone extinct", name),
+            )
+            .with_source(EventSource::System("ecosystem".to_string()));
+            self.event_history.record(self.current_tick, event);
+            self.extinct_species.push(species_id.clone());
+        }
+        newly_extinct
+    }
+
+    /// Schedules an invasive species to be introduced when `tick` is reached.
+    ///
+    /// A `SpeciesIntroduced` event is queued for `tick`; when `advance_tick` processes it,
+    /// the invasion is released through `introduce_species`. Returns the event id.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::World;
+    /// use entropic_world_core::ecosystem::{Diet, Invasion, Species};
+    ///
+    /// let mut world = World::new("W".into(), "dna".into(), 1, 1);
+    /// let rats = Species::new("rat".into(), "Rat".into(), Diet::Omnivore);
+    /// world.schedule_invasion(1, Invasion::new(rats, 30));
+    /// world.advance_tick();
+    /// assert_eq!(world.animal_populations.total(&"rat".into()), 30);
+    /// ```
+    pub fn schedule_invasion(&mut self, tick: u64, invasion: Invasion) -> String {
+        let event_id = format!("invasion_{}_{}", invasion.species_id(), tick);
+        let event = WorldEvent::new(
+            event_id.clone(),
+            EventType::SpeciesIntroduced,
+            self.current_time,
+            (0.0, 0.0),
+            format!("{} introduced", invasion.species.name),
+        );
+        self.event_queue.schedule(tick, event);
+        self.pending_invasions.insert(event_id.clone(), invasion);
+        event_id
+    }
+
+    /// Immediately introduces an invasive species, registering it with its initial
+    /// population and activating its propagation rules.
+    ///
+    /// The initial population is released into the invasion's origin chunk, or spread over
+    /// the species' `habitat_chunks` when no origin is set.
+    pub fn introduce_species(&mut self, invasion: Invasion) {
+        let species_id = invasion.species_id().clone();
+        self.species.insert(species_id.clone(), invasion.species.clone());
+        match invasion.origin {
+            Some(origin) => {
+                self.animal_populations
+                    .add(origin, species_id.clone(), invasion.initial_population)
+            }
+            None => {
+                let habitat = self.habitat_chunks(&invasion.species);
+                self.animal_populations
+                    .distribute(&habitat, &species_id, invasion.initial_population);
+            }
+        }
+        self.extinct_species.retain(|id| id != &species_id);
+
+        self.invasions.retain(|active| active.species_id() != &species_id);
+        self.invasions.push(invasion);
+    }
+
+    /// Advances every active invasion by one step.
+    ///
+    /// Invaders grow according to their rules, with the growth spread over the chunks they
+    /// already occupy, and displace individuals of their competitor species living in the
+    /// same chunks. Invasions whose species has died out are dropped, and extinctions
+    /// caused by the displacement are recorded.
+    #[cfg_attr(feature = "observability", tracing::instrument(level = "debug", skip_all))]
+    pub fn simulate_invasions(&mut self) {
+        let _timer = Timer::subsystem("invasions");
+        for invasion in &self.invasions {
+            let species_id = invasion.species_id();
+            let population = self.animal_populations.total(species_id);
+            if population == 0 {
+                continue;
+            }
+
+            let grown = invasion.grow(population);
+            self.animal_populations
+                .add_distributed(species_id, grown.saturating_sub(population));
+
+            for (coord, invaders) in self.animal_populations.chunks_with(species_id) {
+                let displaced = invasion.displacement(invaders);
+                for competitor in &invasion.competitors {
+                    self.animal_populations.remove(coord, competitor, displaced);
+                }
+            }
+        }
+
+        let populations = &self.animal_populations;
+        self.invasions
+            .retain(|invasion| populations.total(invasion.species_id()) > 0);
+        self.check_extinctions();
+    }
+
+    /// Returns the id of the market belonging to the settlement closest to `(x, y)`.
+    fn nearest_market_id(&self, x: f32, y: f32) -> Option<String> {
+        self.markets
+            .values()
+            .filter_map(|market| {
+                let settlement = self.settlements.get(&market.settlement_id)?;
+                let distance = (settlement.x - x).powi(2) + (settlement.y - y).powi(2);
+                Some((distance, &market.id))
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.cmp(b.1)))
+            .map(|(_, id)| id.clone())
+    }
+
+    /// Marks an NPC and its entity as dead and records an `NPCDeath` event attributed to
+    /// `system`.
+    fn record_npc_death(&mut self, npc_id: &NpcId, cause: String, system: &str) {
+        let entity_id = match self.npcs.get_mut(npc_id) {
+            Some(npc) => {
+                npc.set_status(crate::population::NpcStatus::Dead);
+                npc.entity_id.clone()
+            }
+            None => return,
+        };
+
+        let mut location = (0.0, 0.0);
+        if let Some(entity) = self.entities.get_mut(&entity_id) {
+            entity.is_alive = false;
+            location = (entity.x, entity.y);
+        }
+
+        let event = WorldEvent::new(
+            format!("npc_death_{}_{}", npc_id, self.current_tick),
+            EventType::NPCDeath,
+            self.current_time,
+            location,
+            cause,
+        )
+        .with_entities(vec![entity_id])
+        .with_source(EventSource::System(system.to_string()));
+        self.event_history.record(self.current_tick, event);
     }
 
     /// Advances the world state by one simulation tick.
@@ -404,6 +1334,22 @@ impl World {
     /// This increments the world's tick counter, advances its time by the configured
     /// tick rate, updates the `last_simulated` timestamp to now, and moves any events
     /// scheduled for the new tick from the event queue into the world's event history.
+    /// `SpeciesIntroduced` events scheduled through `schedule_invasion` release their
+    /// invasive species into the world as they fire.
+    ///
+    /// Before the queue is drained, a random event is generated from the registered
+    /// templates with probability `random_event_chance`, to fire on the following tick,
+    /// and registered `triggers` are evaluated against the world and the events they fire
+    /// are scheduled for the new tick.
+    ///
+    /// Events due on the tick are processed in descending priority, then in scheduling
+    /// order. Each event is dispatched to the matching `event_bus` subscribers before it is
+    /// recorded. Follow-up events emitted by handlers join the tick's pending events by
+    /// priority and are dispatched later in the same tick, up to `MAX_DISPATCHES_PER_TICK`
+    /// events, after which they are deferred to the next tick. Events cancelled by a
+    /// handler are dropped without being dispatched or recorded. Dispatching the pending
+    /// event of a running event chain advances the chain. Finally, the history is
+    /// compacted if its compaction policy has a new window of old events to summarize.
     ///
     /// # Examples
     ///
@@ -413,15 +1359,143 @@ impl World {
     /// world.advance_tick();
     /// assert_eq!(world.current_tick, 1);
     /// ```
+    #[cfg_attr(
+        feature = "observability",
+        tracing::instrument(level = "debug", skip_all, fields(tick = self.current_tick + 1))
+    )]
     pub fn advance_tick(&mut self) {
+        let _timer = Timer::start(observability::TICK_DURATION_SECONDS, &[]);
+        let pending = self.begin_tick();
+        self.dispatch_events(pending.into(), false);
+    }
+
+    /// Advances the world by one tick, dispatching the events `log` recorded for the new
+    /// tick instead of the ones the world would produce itself.
+    ///
+    /// The tick runs exactly like `advance_tick`, including random event generation and
+    /// trigger evaluation, so the world's RNG, triggers and chains evolve as they did when
+    /// the log was recorded; the events due from the queue are discarded in favour of the
+    /// logged ones. Follow-up events emitted or scheduled by handlers and the next steps
+    /// of event chains are not queued again, since the log already holds them, and events
+    /// recorded by world systems such as disease outbreaks are recorded without being
+    /// dispatched, as they were originally. Replaying requires the same `event_bus` subscriptions
+    /// the world had when the log was recorded.
+    #[cfg_attr(
+        feature = "observability",
+        tracing::instrument(level = "debug", skip_all, fields(tick = self.current_tick + 1))
+    )]
+    pub fn replay_tick(&mut self, log: &EventLog) {
+        let _timer = Timer::start(observability::TICK_DURATION_SECONDS, &[]);
+        self.begin_tick();
+        let pending = log.events_at(self.current_tick).cloned().collect();
+        self.dispatch_events(pending, true);
+    }
+
+    /// Replays `log` tick by tick until the world reaches `tick`.
+    ///
+    /// Loading the snapshot a log was recorded from and replaying the log reproduces the
+    /// recorded world, which makes a save plus its event log enough to reproduce a bug.
+    pub fn replay_until(&mut self, log: &EventLog, tick: u64) {
+        while self.current_tick < tick {
+            self.replay_tick(log);
+        }
+    }
+
+    /// Advances the clock, generates random events and evaluates triggers, then returns
+    /// the events due on the new tick.
+    fn begin_tick(&mut self) -> Vec<WorldEvent> {
         self.current_tick += 1;
         self.current_time.advance_tick(crate::constants::DEFAULT_TICKS_PER_SECOND);
         self.last_simulated = Utc::now();
+        increment_counter(observability::TICKS_TOTAL, &[], 1);
         
-        let events = self.event_queue.get_events_at_tick(self.current_tick);
-        for event in events {
-            self.event_history.push(event);
+        if self.random_event_chance > 0.0 && self.rng.chance(self.random_event_chance) {
+            let _timer = Timer::subsystem("random_events");
+            self.generate_random_event();
         }
+
+        if !self.triggers.is_empty() {
+            let _timer = Timer::subsystem("triggers");
+            let mut triggers = std::mem::take(&mut self.triggers);
+            let fired = triggers.evaluate(self);
+            self.triggers = triggers;
+            for event in fired {
+                self.event_queue.schedule(self.current_tick, event);
+            }
+        }
+
+        self.event_queue.get_events_at_tick(self.current_tick)
+    }
+
+    /// Dispatches `pending` and the follow-ups they produce. When `replaying`, events the
+    /// world's systems recorded are recorded without being dispatched, and emitted or
+    /// scheduled follow-ups and chain steps are dropped because the event log being
+    /// replayed already contains them.
+    fn dispatch_events(&mut self, mut pending: std::collections::VecDeque<WorldEvent>, replaying: bool) {
+        let timer = Timer::subsystem("events");
+        let mut dispatched = 0;
+        while let Some(event) = pending.pop_front() {
+            if event.event_type == EventType::SpeciesIntroduced {
+                if let Some(invasion) = self.pending_invasions.remove(&event.id) {
+                    self.introduce_species(invasion);
+                }
+            }
+
+            if replaying && matches!(event.source, EventSource::System(_)) {
+                self.event_history.record(self.current_tick, event);
+                continue;
+            }
+
+            let mut context = EventContext::new(self.current_tick);
+            self.event_bus.dispatch(self, &event, &mut context);
+            if self.chains.is_pending(&event.id) {
+                let mut chains = std::mem::take(&mut self.chains);
+                let next = chains.resolve(
+                    &event.id,
+                    context.outcome(),
+                    |condition| self.triggers.condition_met(condition, self),
+                    self.current_tick,
+                    self.current_time,
+                );
+                self.chains = chains;
+                if let Some((tick, next_event)) = next.filter(|_| !replaying) {
+                    self.event_queue.schedule(tick, next_event);
+                }
+            }
+            dispatched += 1;
+
+            for event_id in context.cancelled() {
+                pending.retain(|event| &event.id != event_id);
+                self.event_queue.cancel(event_id);
+                self.chains.abort(event_id);
+            }
+            let (emitted, scheduled) = context.into_parts();
+            self.event_history.record(self.current_tick, event.clone());
+            if replaying {
+                continue;
+            }
+            for (tick, follow_up) in scheduled {
+                self.event_queue.schedule(tick, attribute_follow_up(follow_up, &event));
+            }
+
+            for follow_up in emitted {
+                let follow_up = attribute_follow_up(follow_up, &event);
+                if dispatched + pending.len() < MAX_DISPATCHES_PER_TICK {
+                    let index = pending
+                        .iter()
+                        .position(|event| event.priority < follow_up.priority)
+                        .unwrap_or(pending.len());
+                    pending.insert(index, follow_up);
+                } else {
+                    self.event_queue.schedule(self.current_tick + 1, follow_up);
+                }
+            }
+        }
+        increment_counter(observability::EVENTS_DISPATCHED_TOTAL, &[], dispatched as u64);
+        drop(timer);
+
+        let _timer = Timer::subsystem("history");
+        self.event_history.compact(self.current_tick);
     }
 
     /// Creates a WorldMetadata snapshot containing the world's identifying fields.
@@ -500,6 +1574,19 @@ impl World {
     }
 }
 
+/// Marks an event emitted or scheduled by a handler as a consequence of the event being
+/// dispatched, unless the handler already attributed it.
+fn attribute_follow_up(follow_up: WorldEvent, parent: &WorldEvent) -> WorldEvent {
+    if follow_up.parent_id.is_some() {
+        return follow_up;
+    }
+    let mut follow_up = follow_up.caused_by(parent);
+    if follow_up.source == EventSource::External {
+        follow_up.source = EventSource::Handler;
+    }
+    follow_up
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -565,6 +1652,405 @@ mod tests {
         assert_eq!(world.current_tick, 1);
     }
 
+    #[test]
+    fn test_event_bus_dispatch_during_tick() {
+        let mut world = World::new(
+            "Test World".to_string(),
+            "game_dna_1".to_string(),
+            1,
+            1,
+        );
+        world.event_bus.subscribe(EventType::Disaster, |world, event, ctx| {
+            ctx.emit(WorldEvent::new(
+                format!("{}_relief", event.id),
+                EventType::Custom("relief".to_string()),
+                world.current_time,
+                event.location,
+                "Relief arrives".to_string(),
+            ));
+            ctx.schedule_after(2, WorldEvent::new(
+                format!("{}_rebuild", event.id),
+                EventType::Settlement,
+                world.current_time,
+                event.location,
+                "Rebuilding begins".to_string(),
+            ));
+        });
+        // A handler re-emitting its own event type would loop forever without the cap.
+        world.event_bus.subscribe(EventType::Discovery, |_, event, ctx| ctx.emit(event.clone()));
+
+        world.event_queue.schedule(1, WorldEvent::new(
+            "flood".to_string(),
+            EventType::Disaster,
+            world.current_time,
+            (0.0, 0.0),
+            "Flood".to_string(),
+        ));
+        world.advance_tick();
+        let ids: Vec<&str> = world.event_history.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["flood", "flood_relief"]);
+
+        world.advance_tick();
+        world.advance_tick();
+        assert_eq!(world.event_history.last().unwrap().id, "flood_rebuild");
+
+        world.event_queue.schedule(4, WorldEvent::new(
+            "ruins".to_string(),
+            EventType::Discovery,
+            world.current_time,
+            (0.0, 0.0),
+            "Ruins".to_string(),
+        ));
+        let before = world.event_history.len();
+        world.advance_tick();
+        assert_eq!(world.event_history.len() - before, MAX_DISPATCHES_PER_TICK);
+        assert_eq!(world.event_queue.len(), 1);
+    }
+
+    #[test]
+    fn test_event_priority_and_cancellation() {
+        let mut world = World::new(
+            "Test World".to_string(),
+            "game_dna_1".to_string(),
+            1,
+            1,
+        );
+        let event = |id: &str, event_type: EventType, priority: i32| {
+            WorldEvent::new(
+                id.to_string(),
+                event_type,
+                WorldTime::default(),
+                (0.0, 0.0),
+                String::new(),
+            )
+            .with_priority(priority)
+        };
+        // Peace talks preempt the war declared on the same tick.
+        world.event_bus.subscribe(EventType::Custom("truce".to_string()), |_, _, ctx| {
+            ctx.cancel("war");
+            ctx.cancel("raid");
+        });
+
+        world.event_queue.schedule(1, event("war", EventType::FactionWar, 0));
+        world.event_queue.schedule(1, event("truce", EventType::Custom("truce".to_string()), 10));
+        world.event_queue.schedule(1, event("harvest", EventType::Discovery, 0));
+        world.event_queue.schedule(3, event("raid", EventType::FactionWar, 0));
+        world.advance_tick();
+
+        let ids: Vec<&str> = world.event_history.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["truce", "harvest"]);
+        assert!(world.event_queue.is_empty());
+    }
+
+    #[test]
+    fn test_event_chain_branches_on_outcome() {
+        use crate::events::{ChainBranch, ChainStep};
+
+        let mut world = World::new(
+            "Test World".to_string(),
+            "game_dna_1".to_string(),
+            1,
+            1,
+        );
+        world.register_chain(
+            EventChain::new("bandits", "raid", 1)
+                .with_step(
+                    ChainStep::new("raid", EventType::Custom("raid".to_string()), "Bandits raid the farms")
+                        .with_branch(ChainBranch::new("militia", 1)),
+                )
+                .with_step(
+                    ChainStep::new("militia", EventType::Custom("militia".to_string()), "The militia responds")
+                        .with_branch(ChainBranch::new("reprisal", 2).on_outcome("repelled"))
+                        .with_branch(ChainBranch::new("tribute", 1).on_outcome("routed")),
+                )
+                .with_step(ChainStep::new("reprisal", EventType::FactionWar, "The bandits retaliate"))
+                .with_step(ChainStep::new("tribute", EventType::TradeCompleted, "The village pays tribute")),
+        );
+        world.event_bus.subscribe(EventType::Custom("militia".to_string()), |_, _, ctx| {
+            ctx.set_outcome("repelled");
+        });
+
+        let instance = world.start_chain("bandits", (10.0, 20.0)).unwrap();
+        assert!(world.start_chain("unknown", (0.0, 0.0)).is_none());
+        for _ in 0..4 {
+            world.advance_tick();
+        }
+
+        let ids: Vec<&str> = world.event_history.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                format!("{}_0_raid", instance),
+                format!("{}_1_militia", instance),
+                format!("{}_2_reprisal", instance),
+            ]
+        );
+        assert!(world.chains.active().is_empty());
+    }
+
+    #[test]
+    fn test_random_events_are_seeded() {
+        use crate::events::WeightModifier;
+
+        let build = || {
+            let config = WorldConfig::new(4, 4).with_seed(1234);
+            let mut world = World::from_config("Test World".to_string(), "game_dna_1".to_string(), config);
+            world.initialize_chunks();
+            world.register_event_template(EventTemplate::new("bandits", EventType::FactionWar, "Bandits", 1.0));
+            world.register_event_template(
+                EventTemplate::new("omen", EventType::Discovery, "An omen", 1.0).requires_tag("prophecy"),
+            );
+            world.register_event_template(
+                EventTemplate::new("storm", EventType::Disaster, "A storm", 1.0)
+                    .with_modifier(WeightModifier::Tag { tag: "drought".to_string(), factor: 0.0 }),
+            );
+            world.random_event_chance = 0.5;
+            world
+        };
+
+        let mut a = build();
+        let mut b = build();
+        a.add_tag("drought");
+        b.add_tag("drought");
+        for _ in 0..40 {
+            a.advance_tick();
+            b.advance_tick();
+        }
+        let ids = |world: &World| world.event_history.iter().map(|e| e.id.clone()).collect::<Vec<_>>();
+        assert!(!a.event_history.is_empty());
+        assert_eq!(ids(&a), ids(&b));
+        assert!(a.event_history.iter().all(|e| e.event_type == EventType::FactionWar));
+
+        let json = crate::serialization::serialize_to_json(&a).unwrap();
+        let restored = crate::serialization::deserialize_from_json(&json).unwrap();
+        assert_eq!(restored.rng, a.rng);
+        assert!(restored.has_tag("drought"));
+    }
+
+    #[test]
+    fn test_replay_reproduces_world_from_snapshot() {
+        use crate::events::{ChainBranch, ChainStep};
+
+        fn subscribe(world: &mut World) {
+            world.event_bus.subscribe(EventType::Disaster, |world, event, ctx| {
+                ctx.emit(WorldEvent::new(
+                    format!("{}_relief", event.id),
+                    EventType::Custom("relief".to_string()),
+                    world.current_time,
+                    event.location,
+                    String::new(),
+                ));
+            });
+            world.event_bus.subscribe(EventType::Custom("raid".to_string()), |_, _, ctx| {
+                ctx.set_outcome("repelled");
+            });
+        }
+
+        let config = WorldConfig::new(2, 2).with_seed(99);
+        let mut world = World::from_config("Test World".to_string(), "game_dna_1".to_string(), config);
+        world.initialize_chunks();
+        world.register_event_template(EventTemplate::new("quake", EventType::Disaster, "Quake", 1.0));
+        world.random_event_chance = 0.3;
+        world.register_chain(
+            EventChain::new("bandits", "raid", 2)
+                .with_step(
+                    ChainStep::new("raid", EventType::Custom("raid".to_string()), "Raid")
+                        .with_branch(ChainBranch::new("reprisal", 3).on_outcome("repelled")),
+                )
+                .with_step(ChainStep::new("reprisal", EventType::FactionWar, "Reprisal")),
+        );
+        subscribe(&mut world);
+        world.advance_tick();
+
+        let json = crate::serialization::serialize_to_json(&world).unwrap();
+        let snapshot_tick = world.current_tick;
+        world.start_chain("bandits", (1.0, 1.0));
+        for tick in 0..30 {
+            if tick == 10 {
+                let flood = WorldEvent::new("flood".to_string(), EventType::Disaster, world.current_time, (0.0, 0.0), String::new());
+                world.event_queue.schedule(world.current_tick + 1, flood);
+            }
+            world.advance_tick();
+        }
+
+        // The chain start and the flood were never in the snapshot; the log carries them.
+        let log = EventLog::from_history(&world.event_history, snapshot_tick);
+        let mut replayed = crate::serialization::deserialize_from_json(&json).unwrap();
+        subscribe(&mut replayed);
+        replayed.replay_until(&log, world.current_tick);
+
+        let ids = |world: &World| world.event_history.iter().map(|e| e.id.clone()).collect::<Vec<_>>();
+        assert!(ids(&world).contains(&"flood_relief".to_string()));
+        assert_eq!(ids(&replayed), ids(&world));
+        assert_eq!(replayed.current_tick, world.current_tick);
+        assert_eq!(replayed.rng, world.rng);
+        assert_eq!(replayed.current_time, world.current_time);
+    }
+
+    #[test]
+    fn test_replay_does_not_queue_follow_ups_again() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        fn subscribe(world: &mut World, outbreaks: Arc<AtomicUsize>) {
+            world.event_bus.subscribe(EventType::Disaster, |world, event, ctx| {
+                ctx.schedule_after(3, WorldEvent::new(
+                    format!("{}_aftershock", event.id),
+                    EventType::Custom("aftershock".to_string()),
+                    world.current_time,
+                    event.location,
+                    String::new(),
+                ));
+            });
+            world.event_bus.subscribe(EventType::DiseaseOutbreak, move |_, _, _| {
+                outbreaks.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+
+        let mut world = World::new("Test World".to_string(), "game_dna_1".to_string(), 1, 1);
+        let json = crate::serialization::serialize_to_json(&world).unwrap();
+        subscribe(&mut world, Arc::new(AtomicUsize::new(0)));
+        let storm = WorldEvent::new("storm".to_string(), EventType::Disaster, world.current_time, (0.0, 0.0), String::new());
+        world.event_queue.schedule(1, storm);
+        world.advance_tick();
+        let outbreak = WorldEvent::new("outbreak".to_string(), EventType::DiseaseOutbreak, world.current_time, (0.0, 0.0), String::new())
+            .with_source(EventSource::System("disease".to_string()));
+        world.event_history.record(world.current_tick, outbreak);
+        for _ in 0..3 {
+            world.advance_tick();
+        }
+
+        let log = EventLog::from_history(&world.event_history, 0);
+        let mut replayed = crate::serialization::deserialize_from_json(&json).unwrap();
+        let outbreaks = Arc::new(AtomicUsize::new(0));
+        subscribe(&mut replayed, outbreaks.clone());
+        replayed.replay_until(&log, 3);
+        assert!(replayed.event_queue.is_empty());
+        assert_eq!(outbreaks.load(Ordering::SeqCst), 0);
+
+        replayed.replay_until(&log, world.current_tick);
+        let ids = |world: &World| world.event_history.iter().map(|e| e.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&replayed), vec!["storm", "outbreak", "storm_aftershock"]);
+        assert_eq!(ids(&replayed), ids(&world));
+    }
+
+    #[test]
+    fn test_event_history_indexes_and_retention() {
+        use crate::events::RetentionPolicy;
+
+        let config = WorldConfig::new(2, 2).with_history_retention(RetentionPolicy::Archive(3));
+        let mut world = World::from_config("Test World".to_string(), "game_dna_1".to_string(), config);
+        for tick in 1..=5u64 {
+            let event_type = if tick % 2 == 0 { EventType::FactionWar } else { EventType::Discovery };
+            let event = WorldEvent::new(format!("e{}", tick), event_type, world.current_time, (0.0, 0.0), String::new())
+                .with_entities(vec!["faction_north".to_string()]);
+            world.event_queue.schedule(tick, event);
+        }
+        for _ in 0..5 {
+            world.advance_tick();
+        }
+
+        let ids = |events: Vec<&WorldEvent>| events.iter().map(|e| e.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(world.event_history.of_type(&EventType::FactionWar).collect()), vec!["e4"]);
+        assert_eq!(ids(world.event_history.in_ticks(4..).collect()), vec!["e4", "e5"]);
+        assert_eq!(world.event_history.involving("faction_north").count(), 3);
+
+        let archived = world.event_history.take_archived();
+        assert_eq!(archived.iter().map(|(tick, _)| *tick).collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_event_history_compacts_during_ticks() {
+        use crate::events::CompactionPolicy;
+
+        let config = WorldConfig::new(1, 1).with_history_compaction(CompactionPolicy::new(10, 10));
+        let mut world = World::from_config("Test World".to_string(), "game_dna_1".to_string(), config);
+        world.schedule_recurring(
+            WorldEvent::new("harvest".to_string(), EventType::TradeCompleted, world.current_time, (0.0, 0.0), String::new()),
+            Recurrence::EveryTicks(1),
+        );
+        for _ in 0..35 {
+            world.advance_tick();
+        }
+
+        assert_eq!(world.event_history.summaries().len(), 2);
+        assert!(world.event_history.len() < 20);
+        assert_eq!(world.event_history.count_of_type(&EventType::TradeCompleted), 35);
+    }
+
+    #[test]
+    fn test_follow_ups_record_their_cause() {
+        let mut world = World::new("Test World".to_string(), "game_dna_1".to_string(), 1, 1);
+        world.add_trigger(
+            EventTrigger::new(
+                "drought".to_string(),
+                crate::events::TriggerCondition::TimeElapsed(1),
+                "drought".to_string(),
+            )
+            .once(),
+        );
+        world.event_bus.subscribe(EventType::Custom("drought".to_string()), |world, event, ctx| {
+            ctx.emit(WorldEvent::new(
+                "famine".to_string(),
+                EventType::Disaster,
+                world.current_time,
+                event.location,
+                String::new(),
+            ));
+        });
+        world.event_bus.subscribe(EventType::Disaster, |world, event, ctx| {
+            ctx.schedule_after(
+                2,
+                WorldEvent::new("war".to_string(), EventType::FactionWar, world.current_time, event.location, String::new()),
+            );
+        });
+        for _ in 0..4 {
+            world.advance_tick();
+        }
+
+        let chain: Vec<&str> = world
+            .event_history
+            .causal_chain("war")
+            .iter()
+            .map(|e| e.id.as_str())
+            .collect();
+        assert_eq!(chain, vec!["trigger_drought_1", "famine", "war"]);
+        let root = world.event_history.find("trigger_drought_1").unwrap();
+        assert_eq!(root.source, EventSource::Trigger("drought".to_string()));
+        let war = world.event_history.find("war").unwrap();
+        assert_eq!(war.source, EventSource::Handler);
+        assert_eq!(war.correlation_id.as_deref(), Some("trigger_drought_1"));
+    }
+
+    #[test]
+    fn test_triggers_fire_during_tick() {
+        let mut world = World::new(
+            "Test World".to_string(),
+            "game_dna_1".to_string(),
+            1,
+            1,
+        );
+        world.add_trigger(
+            EventTrigger::new(
+                "harvest_festival".to_string(),
+                crate::events::TriggerCondition::TimeElapsed(2),
+                "festival".to_string(),
+            )
+            .once(),
+        );
+
+        world.advance_tick();
+        assert!(world.event_history.is_empty());
+        world.advance_tick();
+        world.advance_tick();
+        assert_eq!(world.event_history.len(), 1);
+        assert_eq!(
+            world.event_history[0].event_type,
+            EventType::Custom("festival".to_string())
+        );
+        assert_eq!(world.triggers.get_trigger("harvest_festival").unwrap().last_fired, Some(2));
+    }
+
     #[test]
     fn test_from_config() {
         let config = WorldConfig::new(100, 100)
@@ -581,4 +2067,336 @@ mod tests {
         assert_eq!(world.time_scale, 2.0);
         assert!(!world.weather_enabled);
     }
+
+    #[test]
+    fn test_outbreak_spreads_and_kills() {
+        let mut world = World::new(
+            "Test World".to_string(),
+            "game_dna_1".to_string(),
+            3,
+            3,
+        );
+        world.initialize_chunks();
+
+        let mut wolf = Species::new("wolf".to_string(), "Wolf".to_string(), crate::ecosystem::Diet::Carnivore);
+        wolf.base_population = 200;
+        world.add_species(wolf);
+        world.add_disease(
+            Disease::new("rabies".to_string(), "Rabies".to_string())
+                .with_rates(0.5, 0.0, 0.2)
+                .with_species("wolf".to_string(), 1.0)
+                .with_spread(0.2, 0.0),
+        );
+        world.start_outbreak(
+            Outbreak::new("rabies".to_string(), ChunkCoord::new(1, 1), 0)
+                .with_infected_animals("wolf".to_string(), 20),
+        );
+
+        world.simulate_diseases();
+
+        assert!(world.animal_populations.total(&"wolf".to_string()) < 200);
+        assert_eq!(world.outbreaks.len(), 9);
+        let outbreak_events = world
+            .event_history
+            .iter()
+            .filter(|e| e.event_type == EventType::DiseaseOutbreak)
+            .count();
+        assert_eq!(outbreak_events, 9);
+    }
+
+    #[test]
+    fn test_hunting_supplies_market_and_warns() {
+        let mut world = World::new(
+            "Test World".to_string(),
+            "game_dna_1".to_string(),
+            2,
+            2,
+        );
+        world.initialize_chunks();
+
+        let mut deer = Species::new("deer".to_string(), "Deer".to_string(), crate::ecosystem::Diet::Herbivore);
+        deer.base_population = 20;
+        world.add_species(deer);
+        world.add_settlement(Settlement::new(
+            "town".to_string(),
+            "Town".to_string(),
+            "faction_1".to_string(),
+            50.0,
+            50.0,
+        ));
+        let mut market = Market::new("market_1".to_string(), "town".to_string());
+        market.add_resource(crate::economy::ResourceType::Food, 10, 10);
+        world.add_market(market);
+
+        world.add_entity(Entity::new(
+            "entity_1".to_string(),
+            crate::population::EntityType::NPC,
+            10.0,
+            10.0,
+            0.0,
+            ChunkCoord::new(0, 0),
+        ));
+        let mut hunter = NPC::new("npc_1".to_string(), "Hal".to_string(), "entity_1".to_string())
+            .with_occupation(crate::population::Occupation::Hunter);
+        hunter.add_skill("hunting".to_string(), 0.5);
+        world.add_npc(hunter);
+        world.add_harvest_rule(
+            HarvestRule::new(
+                "deer".to_string(),
+                crate::population::Occupation::Hunter,
+                crate::economy::ResourceType::Food,
+            )
+            .with_catch_rate(8.0)
+            .with_yield(2),
+        );
+
+        let catches = world.simulate_hunting();
+        assert_eq!(catches["deer"], 8);
+        assert_eq!(world.animal_populations.total(&"deer".to_string()), 12);
+        assert_eq!(world.markets["market_1"].supply[&crate::economy::ResourceType::Food], 26);
+
+        world.simulate_hunting();
+        world.simulate_hunting();
+        assert!(world.overhunted_species.contains(&"deer".to_string()));
+        let warnings = world
+            .event_history
+            .iter()
+            .filter(|e| e.event_type == EventType::EcosystemWarning)
+            .count();
+        assert_eq!(warnings, 1);
+        assert!(world.markets["market_1"].demand[&crate::economy::ResourceType::Food] > 10);
+    }
+
+    #[test]
+    fn test_flora_growth_caps_herbivores() {
+        let mut world = World::new(
+            "Test World".to_string(),
+            "game_dna_1".to_string(),
+            1,
+            1,
+        );
+        world.initialize_chunks();
+
+        let mut deer = Species::new("deer".to_string(), "Deer".to_string(), Diet::Herbivore);
+        deer.base_population = 5000;
+        world.add_species(deer);
+        assert_eq!(world.herbivore_carrying_capacity(&"deer".to_string()), Some(600));
+
+        world.get_chunk_mut(&ChunkCoord::new(0, 0)).unwrap().flora.crops = 0.0;
+        world.simulate_flora();
+
+        // The oversized herd grazes a full step's worth of forage before being capped
+        assert_eq!(world.animal_populations.total(&"deer".to_string()), 540);
+        assert!(world.get_chunk(&ChunkCoord::new(0, 0)).unwrap().flora.crops > 0.0);
+    }
+
+    #[test]
+    fn test_grazing_lowers_capacity_until_regrowth() {
+        let mut world = World::new(
+            "Test World".to_string(),
+            "game_dna_1".to_string(),
+            1,
+            1,
+        );
+        world.initialize_chunks();
+        world.seasons_enabled = false;
+        world.weather_enabled = false;
+        let deer_id = "deer".to_string();
+        let mut deer = Species::new(deer_id.clone(), "Deer".to_string(), Diet::Herbivore);
+        deer.base_population = 300;
+        world.add_species(deer);
+
+        // Half the capacity grazes half a step's worth of forage
+        world.simulate_flora();
+        assert_eq!(world.herbivore_carrying_capacity(&deer_id), Some(570));
+        assert_eq!(world.animal_populations.total(&deer_id), 300);
+
+        world.animal_populations.set(ChunkCoord::new(0, 0), deer_id.clone(), 0);
+        world.simulate_flora();
+        assert_eq!(world.herbivore_carrying_capacity(&deer_id), Some(600));
+    }
+
+    #[test]
+    fn test_flora_harvest_supplies_market() {
+        let mut world = World::new(
+            "Test World".to_string(),
+            "game_dna_1".to_string(),
+            1,
+            1,
+        );
+        world.initialize_chunks();
+        world.chunks.insert(
+            ChunkCoord::new(0, 0),
+            Chunk::new(ChunkCoord::new(0, 0)).with_biome(crate::spatial::Biome::Forest),
+        );
+        world.add_settlement(Settlement::new(
+            "town".to_string(),
+            "Town".to_string(),
+            "faction_1".to_string(),
+            50.0,
+            50.0,
+        ));
+        world.add_market(Market::new("market_1".to_string(), "town".to_string()));
+        world.add_entity(Entity::new(
+            "entity_1".to_string(),
+            crate::population::EntityType::NPC,
+            10.0,
+            10.0,
+            0.0,
+            ChunkCoord::new(0, 0),
+        ));
+        world.add_npc(
+            NPC::new("npc_1".to_string(), "Jack".to_string(), "entity_1".to_string())
+                .with_occupation(Occupation::Lumberjack),
+        );
+
+        let produced = world.harvest_flora();
+        assert_eq!(produced[&ResourceType::Wood], 10);
+        assert_eq!(world.markets["market_1"].supply[&ResourceType::Wood], 10);
+        assert!(world.chunks[&ChunkCoord::new(0, 0)].flora.forest_density < 0.9);
+    }
+
+    #[test]
+    fn test_selection_under_predation() {
+        let mut world = World::new(
+            "Test World".to_string(),
+            "game_dna_1".to_string(),
+            2,
+            2,
+        );
+        world.initialize_chunks();
+        world.seasons_enabled = false;
+
+        let mut deer = Species::new("deer".to_string(), "Deer".to_string(), Diet::Herbivore);
+        deer.add_predator("wolf".to_string());
+        world.add_species(deer);
+        world.add_species(Species::new("wolf".to_string(), "Wolf".to_string(), Diet::Carnivore));
+
+        let pressure = world.selection_pressure(&"deer".to_string()).unwrap();
+        assert_eq!(pressure.predation, 0.5);
+        assert_eq!(pressure.climate, 0.0);
+
+        world.simulate_selection();
+        let deer = &world.species["deer"];
+        assert_eq!(deer.traits.generation, 1);
+        assert!(deer.traits.speed.mean > 1.0);
+
+        let json = crate::serialization::serialize_to_json(&world).unwrap();
+        let restored = crate::serialization::deserialize_from_json(&json).unwrap();
+        assert_eq!(restored.species["deer"].traits, world.species["deer"].traits);
+    }
+
+    #[test]
+    fn test_localized_populations() {
+        let mut world = World::new(
+            "Test World".to_string(),
+            "game_dna_1".to_string(),
+            4,
+            4,
+        );
+        world.initialize_chunks();
+        world.chunks.insert(
+            ChunkCoord::new(3, 3),
+            Chunk::new(ChunkCoord::new(3, 3)).with_biome(crate::spatial::Biome::Forest),
+        );
+
+        let mut wolf = Species::new("wolf".to_string(), "Wolf".to_string(), Diet::Carnivore);
+        wolf.base_population = 10;
+        wolf.add_preferred_biome(crate::spatial::Biome::Forest);
+        world.add_species(wolf);
+        assert_eq!(
+            world.animal_populations.chunks_with(&"wolf".to_string()),
+            vec![(ChunkCoord::new(3, 3), 10)]
+        );
+
+        world.add_settlement(Settlement::new(
+            "town".to_string(),
+            "Town".to_string(),
+            "faction_1".to_string(),
+            100.0,
+            100.0,
+        ));
+        let town = &world.settlements["town"];
+        let (x, y) = (town.x, town.y);
+        assert_eq!(world.animals_near(&"wolf".to_string(), x, y, 300.0), 0);
+
+        assert!(world.spawn_animals(&"wolf".to_string(), ChunkCoord::new(0, 0), 4));
+        assert!(!world.spawn_animals(&"bear".to_string(), ChunkCoord::new(0, 0), 4));
+        assert_eq!(world.animals_near(&"wolf".to_string(), x, y, 300.0), 4);
+        assert_eq!(world.animal_populations.total(&"wolf".to_string()), 14);
+    }
+
+    #[test]
+    fn test_invasion_drives_competitor_extinct() {
+        let mut world = World::new(
+            "Test World".to_string(),
+            "game_dna_1".to_string(),
+            1,
+            1,
+        );
+        let mut frog = Species::new("frog".to_string(), "Frog".to_string(), Diet::Carnivore);
+        frog.base_population = 40;
+        world.add_species(frog);
+
+        let toad = Species::new("toad".to_string(), "Cane Toad".to_string(), Diet::Carnivore);
+        world.schedule_invasion(
+            2,
+            Invasion::new(toad, 20)
+                .with_competitor("frog".to_string())
+                .with_displacement_rate(1.0),
+        );
+        world.advance_tick();
+        assert!(!world.species.contains_key("toad"));
+        world.advance_tick();
+        assert_eq!(world.animal_populations.total(&"toad".to_string()), 20);
+
+        world.simulate_invasions();
+        world.simulate_invasions();
+
+        assert_eq!(world.animal_populations.total(&"frog".to_string()), 0);
+        assert_eq!(world.extinct_species, vec!["frog".to_string()]);
+        let extinctions = world
+            .event_history
+            .iter()
+            .filter(|e| e.event_type == EventType::Extinction)
+            .count();
+        assert_eq!(extinctions, 1);
+
+        world.simulate_invasions();
+        assert_eq!(world.check_extinctions(), Vec::<SpeciesId>::new());
+    }
+
+    #[test]
+    fn test_outbreak_kills_npcs() {
+        let mut world = World::new(
+            "Test World".to_string(),
+            "game_dna_1".to_string(),
+            2,
+            2,
+        );
+        world.initialize_chunks();
+        world.add_entity(Entity::new(
+            "entity_1".to_string(),
+            crate::population::EntityType::NPC,
+            10.0,
+            10.0,
+            0.0,
+            ChunkCoord::new(0, 0),
+        ));
+        world.add_npc(NPC::new("npc_1".to_string(), "Ada".to_string(), "entity_1".to_string()));
+        world.add_disease(
+            Disease::new("plague".to_string(), "Plague".to_string())
+                .with_rates(1.0, 0.0, 1.0)
+                .with_npc_susceptibility(1.0)
+                .with_spread(0.0, 0.0),
+        );
+        world.start_outbreak(Outbreak::new("plague".to_string(), ChunkCoord::new(0, 0), 0).with_carriers(1));
+
+        world.simulate_diseases();
+        world.simulate_diseases();
+
+        assert!(!world.npcs["npc_1"].is_alive());
+        assert!(!world.entities["entity_1"].is_alive);
+        assert!(world.event_history.iter().any(|e| e.event_type == EventType::NPCDeath));
+    }
 }
\ No newline at end of file
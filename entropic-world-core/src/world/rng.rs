@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+/// Deterministic pseudo-random generator (SplitMix64) owned by the world.
+///
+/// The generator state is serialized with the world, so a saved world resumes the same
+/// random sequence when loaded.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorldRng {
+    state: u64,
+}
+
+impl WorldRng {
+    /// Creates a generator from `seed`. Equal seeds produce equal sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::world::WorldRng;
+    ///
+    /// let mut a = WorldRng::new(42);
+    /// let mut b = WorldRng::new(42);
+    /// assert_eq!(a.next_u64(), b.next_u64());
+    /// ```
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a uniformly distributed float in `[0.0, 1.0)`.
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Returns a uniformly distributed integer in `[0, upper)`, or `0` when `upper` is zero.
+    pub fn below(&mut self, upper: u64) -> u64 {
+        if upper == 0 {
+            return 0;
+        }
+        self.next_u64() % upper
+    }
+
+    /// Returns `true` with the given probability.
+    pub fn chance(&mut self, probability: f32) -> bool {
+        self.next_f32() < probability
+    }
+
+    /// Picks an index with probability proportional to its weight. Negative weights
+    /// count as zero; returns `None` if no weight is positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::world::WorldRng;
+    ///
+    /// let mut rng = WorldRng::new(7);
+    /// assert_eq!(rng.choose_weighted(&[0.0, 3.0, 0.0]), Some(1));
+    /// assert_eq!(rng.choose_weighted(&[0.0, -1.0]), None);
+    /// ```
+    pub fn choose_weighted(&mut self, weights: &[f32]) -> Option<usize> {
+        let total: f32 = weights.iter().map(|w| w.max(0.0)).sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut roll = self.next_f32() * total;
+        let mut last_positive = None;
+        for (index, weight) in weights.iter().enumerate() {
+            let weight = weight.max(0.0);
+            if weight <= 0.0 {
+                continue;
+            }
+            if roll < weight {
+                return Some(index);
+            }
+            roll -= weight;
+            last_positive = Some(index);
+        }
+        last_positive
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic_sequence() {
+        let mut a = WorldRng::new(1);
+        let mut b = WorldRng::new(1);
+        let mut c = WorldRng::new(2);
+        let first: Vec<u64> = (0..5).map(|_| a.next_u64()).collect();
+        let second: Vec<u64> = (0..5).map(|_| b.next_u64()).collect();
+        assert_eq!(first, second);
+        assert_ne!(first[0], c.next_u64());
+    }
+
+    #[test]
+    fn test_ranges() {
+        let mut rng = WorldRng::new(99);
+        for _ in 0..1000 {
+            let f = rng.next_f32();
+            assert!((0.0..1.0).contains(&f));
+            assert!(rng.below(10) < 10);
+        }
+        assert_eq!(rng.below(0), 0);
+    }
+
+    #[test]
+    fn test_weighted_choice_distribution() {
+        let mut rng = WorldRng::new(3);
+        let mut counts = [0u32; 2];
+        for _ in 0..10_000 {
+            counts[rng.choose_weighted(&[1.0, 3.0]).unwrap()] += 1;
+        }
+        assert!(counts[1] > counts[0] * 2);
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use crate::events::{CompactionPolicy, RetentionPolicy};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct WorldConfig {
@@ -12,6 +13,10 @@ pub struct WorldConfig {
     pub ai_enabled: bool,
     pub persistent: bool,
     pub seed: Option<u64>,
+    #[serde(default)]
+    pub history_retention: RetentionPolicy,
+    #[serde(default)]
+    pub history_compaction: Option<CompactionPolicy>,
 }
 
 impl WorldConfig {
@@ -40,6 +45,8 @@ impl WorldConfig {
             ai_enabled: true,
             persistent: true,
             seed: None,
+            history_retention: RetentionPolicy::Unbounded,
+            history_compaction: None,
         }
     }
 
@@ -58,6 +65,28 @@ impl WorldConfig {
         self
     }
 
+    /// Sets how many events the world's history retains.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropic_world_core::events::RetentionPolicy;
+    /// use entropic_world_core::world::WorldConfig;
+    ///
+    /// let cfg = WorldConfig::new(8, 8).with_history_retention(RetentionPolicy::RingBuffer(10_000));
+    /// assert_eq!(cfg.history_retention, RetentionPolicy::RingBuffer(10_000));
+    /// ```
+    pub fn with_history_retention(mut self, retention: RetentionPolicy) -> Self {
+        self.history_retention = retention;
+        self
+    }
+
+    /// Sets the policy used to compact old events of the world's history into summaries.
+    pub fn with_history_compaction(mut self, policy: CompactionPolicy) -> Self {
+        self.history_compaction = Some(policy);
+        self
+    }
+
     /// Sets the world's time scale for the configuration.
     ///
     /// The `time_scale` is the multiplier applied to in-world time (e.g., `1.0` represents normal real-time).
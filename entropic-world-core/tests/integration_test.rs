@@ -202,8 +202,8 @@ fn test_ecosystem_system() {
     world.add_species(wolf);
 
     assert_eq!(world.species.len(), 2);
-    assert_eq!(world.animal_populations.get("deer"), Some(&500));
-    assert_eq!(world.animal_populations.get("wolf"), Some(&50));
+    assert_eq!(world.animal_populations.total(&"deer".to_string()), 500);
+    assert_eq!(world.animal_populations.total(&"wolf".to_string()), 50);
 }
 
 #[test]
@@ -0,0 +1,70 @@
+//! Saves written before the world's data model grew must keep loading.
+
+use entropic_world_core::ecosystem::animal_population::LEGACY_CHUNK;
+use entropic_world_core::ecosystem::{AnimalPopulations, Diet, Flora, Species};
+use entropic_world_core::spatial::{Biome, ChunkCoord};
+use entropic_world_core::events::{EventHistory, EventType};
+use entropic_world_core::world::World;
+use serde_json::Value;
+
+/// A world saved by the first release: a 2x2 world with a rabbit species, one NPC and two
+/// events in its history
+const BASELINE_SAVE: &str = include_str!("fixtures/baseline_world.json");
+
+fn baseline_field(name: &str) -> Value {
+    let save: Value = serde_json::from_str(BASELINE_SAVE).unwrap();
+    save[name].clone()
+}
+
+#[test]
+fn test_baseline_animal_populations_load() {
+    let populations: AnimalPopulations =
+        serde_json::from_value(baseline_field("animal_populations")).unwrap();
+    assert_eq!(populations.total(&"rabbit".to_string()), 120);
+    assert_eq!(populations.in_chunk(&LEGACY_CHUNK, &"rabbit".to_string()), 120);
+}
+
+#[test]
+fn test_baseline_event_history_loads() {
+    let history: EventHistory = serde_json::from_value(baseline_field("event_history")).unwrap();
+    let ids: Vec<&str> = history.iter().map(|event| event.id.as_str()).collect();
+    assert_eq!(ids, vec!["storm", "birth"]);
+    assert_eq!(history.of_type(&EventType::NPCBirth).count(), 1);
+    assert!(history.find("storm").is_some());
+}
+
+#[test]
+fn test_baseline_world_loads() {
+    let world: World = serde_json::from_str(BASELINE_SAVE).unwrap();
+    assert_eq!(world.event_history.len(), 2);
+    assert_eq!(world.npcs.len(), 1);
+
+    // The rabbits are spread over the 2x2 grid rather than left in one chunk
+    let rabbit = "rabbit".to_string();
+    assert_eq!(world.animal_populations.total(&rabbit), 120);
+    assert_eq!(world.animal_populations.chunks_with(&rabbit).len(), 4);
+    assert_eq!(world.animal_populations.in_chunk(&LEGACY_CHUNK, &rabbit), 30);
+}
+
+#[test]
+fn test_legacy_herd_survives_flora_tick() {
+    let mut world = World::new("Meadow".to_string(), "dna".to_string(), 2, 2);
+    world.initialize_chunks();
+    for chunk in world.chunks.values_mut() {
+        chunk.biome = Biome::Grassland;
+        chunk.flora = Flora::for_biome(Biome::Grassland);
+    }
+    let mut deer = Species::new("deer".to_string(), "Deer".to_string(), Diet::Herbivore);
+    deer.base_population = 0;
+    world.add_species(deer);
+
+    // More deer than one grassland chunk can feed, stored the way older saves did
+    let mut save = serde_json::to_value(&world).unwrap();
+    save["animal_populations"] = serde_json::json!({ "deer": 2000 });
+    let mut world: World = serde_json::from_value(save).unwrap();
+
+    world.simulate_flora();
+    let deer = "deer".to_string();
+    assert_eq!(world.animal_populations.total(&deer), 2000);
+    assert_eq!(world.animal_populations.in_chunk(&ChunkCoord::new(1, 1), &deer), 500);
+}
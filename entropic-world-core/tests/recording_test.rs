@@ -0,0 +1,85 @@
+use entropic_world_core::population::{Entity, EntityType, NPC};
+use entropic_world_core::recording::{state_hash, Player, Recorder, Recording, WorldCommand};
+use entropic_world_core::spatial::ChunkCoord;
+use entropic_world_core::world::World;
+use entropic_world_core::WorldError;
+
+fn saved_world() -> World {
+    let mut world = World::new(
+        "Recording Test".to_string(),
+        "game_dna_1".to_string(),
+        2,
+        2,
+    );
+    world.reseed(11);
+    world.initialize_chunks();
+    for _ in 0..3 {
+        world.advance_tick();
+    }
+    world
+}
+
+fn record_session(hash_interval: u64) -> (Recording, World) {
+    let mut recorder = Recorder::from_world(saved_world()).with_hash_interval(hash_interval);
+    for i in 0..20 {
+        if i % 4 == 0 {
+            let entity = Entity::new(
+                format!("entity_{}", i),
+                EntityType::Animal,
+                i as f32,
+                0.0,
+                0.0,
+                ChunkCoord::new(0, 0),
+            );
+            recorder.apply(WorldCommand::AddEntity(entity));
+        }
+        if i == 10 {
+            recorder.apply(WorldCommand::AddNpc(NPC::new(
+                "npc_1".to_string(),
+                "Alice".to_string(),
+                "entity_0".to_string(),
+            )));
+            recorder.apply(WorldCommand::SimulateFlora);
+        }
+        recorder.advance_tick().unwrap();
+    }
+    recorder.finish_with_world().unwrap()
+}
+
+#[test]
+fn test_replay_from_snapshot_matches_recorded_world() {
+    let (recording, recorded) = record_session(5);
+    assert_eq!(recording.start_tick, 3);
+    assert_eq!(recording.end_tick, 23);
+    assert_eq!(recording.hashes.keys().copied().collect::<Vec<_>>(), vec![8, 13, 18, 23]);
+    assert_eq!(recording.commands_at(13).count(), 2);
+
+    let mut player = Player::new(&recording);
+    player.run_to(13).unwrap();
+    assert_eq!(player.world().total_entities(), 3);
+
+    player.run_to_end().unwrap();
+    assert!(player.is_finished());
+    let replayed = player.into_world();
+    assert_eq!(state_hash(&replayed).unwrap(), state_hash(&recorded).unwrap());
+    assert_eq!(replayed.total_npcs(), 1);
+}
+
+#[test]
+fn test_unrecorded_change_is_reported() {
+    let (recording, _) = record_session(1);
+    let mut player = Player::new(&recording);
+    player.run_to(6).unwrap();
+
+    player.world_mut().add_entity(Entity::new(
+        "intruder".to_string(),
+        EntityType::Animal,
+        0.0,
+        0.0,
+        0.0,
+        ChunkCoord::new(0, 0),
+    ));
+    let error = player.run_to_end().unwrap_err();
+    assert!(matches!(error, WorldError::ReplayDivergence { tick: 7, .. }));
+    assert!(error.to_string().starts_with("Replay diverged at tick 7"));
+}
@@ -0,0 +1,129 @@
+use entropic_world_core::population::{Entity, EntityType};
+use entropic_world_core::replication::{ClientReplica, Interest, ReplicationServer, WorldSnapshot};
+use entropic_world_core::spatial::ChunkCoord;
+use entropic_world_core::world::World;
+
+fn populated_world() -> World {
+    let mut world = World::new(
+        "Replication Test".to_string(),
+        "game_dna_1".to_string(),
+        10,
+        10,
+    );
+    for i in 0..20 {
+        let mut entity = Entity::new(
+            format!("entity_{}", i),
+            EntityType::Animal,
+            i as f32 * 5.0,
+            10.0,
+            0.0,
+            ChunkCoord::new(0, 0),
+        );
+        entity.velocity = (1.0, 0.0);
+        world.add_entity(entity);
+    }
+    world
+}
+
+/// Moves every entity along its velocity and removes one, standing in for a simulation step
+fn step(world: &mut World) {
+    for entity in world.entities.values_mut() {
+        entity.x += entity.velocity.0;
+        entity.y += entity.velocity.1;
+    }
+    let tick = world.current_tick;
+    world.entities.remove(&format!("entity_{}", tick));
+    world.advance_tick();
+}
+
+#[test]
+fn test_replica_tracks_world_despite_lost_packets() {
+    let mut world = populated_world();
+    let mut server = ReplicationServer::default();
+    server.add_client("client_1".to_string(), Interest::All);
+    let mut replica = ClientReplica::new();
+
+    for tick in 0..30 {
+        let delta = server.snapshot_for("client_1", &world).unwrap();
+
+        // Every third packet never arrives, and every other ack is lost
+        if tick % 3 != 2 {
+            let ack = replica.apply(&delta).unwrap();
+            if tick % 2 == 0 {
+                server.acknowledge("client_1", ack).unwrap();
+            }
+            let expected = WorldSnapshot::capture(&world, |_| true);
+            assert_eq!(replica.current(), Some(&expected));
+        }
+
+        step(&mut world);
+    }
+}
+
+#[test]
+fn test_deltas_are_smaller_than_full_snapshots() {
+    let mut world = populated_world();
+    let mut server = ReplicationServer::default();
+    server.add_client("client_1".to_string(), Interest::All);
+    let mut replica = ClientReplica::new();
+
+    let full = server.snapshot_for("client_1", &world).unwrap();
+    let ack = replica.apply(&full).unwrap();
+    server.acknowledge("client_1", ack).unwrap();
+
+    world.entities.get_mut("entity_3").unwrap().health = 50.0;
+    world.advance_tick();
+    let delta = server.snapshot_for("client_1", &world).unwrap();
+
+    assert!(delta.spawned.is_empty());
+    assert_eq!(delta.updated.len(), 1);
+    let full_size = serde_json::to_vec(&full).unwrap().len();
+    let delta_size = serde_json::to_vec(&delta).unwrap().len();
+    assert!(delta_size * 10 < full_size);
+}
+
+#[test]
+fn test_interest_change_spawns_and_despawns() {
+    let world = populated_world();
+    let mut server = ReplicationServer::default();
+    let near_origin = Interest::Radius { x: 0.0, y: 10.0, radius: 12.0 };
+    server.add_client("client_1".to_string(), near_origin);
+    let mut replica = ClientReplica::new();
+
+    let delta = server.snapshot_for("client_1", &world).unwrap();
+    server.acknowledge("client_1", replica.apply(&delta).unwrap()).unwrap();
+    assert_eq!(replica.current().unwrap().entities.len(), 3);
+
+    let far_end = Interest::Radius { x: 95.0, y: 10.0, radius: 7.0 };
+    server.set_interest("client_1", far_end).unwrap();
+    let delta = server.snapshot_for("client_1", &world).unwrap();
+    assert_eq!(delta.despawned.len(), 3);
+    assert_eq!(delta.spawned.len(), 2);
+
+    replica.apply(&delta).unwrap();
+    let ids: Vec<_> = replica.current().unwrap().entities.keys().cloned().collect();
+    assert_eq!(ids, vec!["entity_18".to_string(), "entity_19".to_string()]);
+}
+
+#[test]
+fn test_missing_baseline_recovers_with_rebaseline() {
+    let mut world = populated_world();
+    let mut server = ReplicationServer::default();
+    server.add_client("client_1".to_string(), Interest::All);
+    let mut replica = ClientReplica::new();
+
+    let delta = server.snapshot_for("client_1", &world).unwrap();
+    server.acknowledge("client_1", replica.apply(&delta).unwrap()).unwrap();
+
+    // The client loses its state, e.g. after a reconnect
+    replica.clear();
+    step(&mut world);
+    let delta = server.snapshot_for("client_1", &world).unwrap();
+    assert!(replica.apply(&delta).is_err());
+
+    server.force_rebaseline("client_1").unwrap();
+    let delta = server.snapshot_for("client_1", &world).unwrap();
+    assert!(delta.is_full());
+    replica.apply(&delta).unwrap();
+    assert_eq!(replica.current().unwrap().entities.len(), 19);
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "test-support")]
+
+use entropic_world_core::economy::Market;
+use entropic_world_core::events::WorldEvent;
+use entropic_world_core::spatial::ChunkCoord;
+use entropic_world_core::test_support::{small_world, MAX_WORLD_CHUNKS};
+use entropic_world_core::world::World;
+use proptest::prelude::*;
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn test_arbitrary_world_is_consistent(world in any::<World>()) {
+        prop_assert!(world.width_chunks <= MAX_WORLD_CHUNKS);
+        for entity in world.entities.values() {
+            prop_assert_eq!(entity.chunk, ChunkCoord::from_world_position(entity.x, entity.y));
+            prop_assert!(entity.chunk.x < world.width_chunks);
+            prop_assert!(entity.chunk.y < world.height_chunks);
+        }
+        let size = MAX_WORLD_CHUNKS as f32 * 256.0;
+        let indexed = world.spatial_index.query_rect(0.0, 0.0, size, size);
+        prop_assert_eq!(indexed.len(), world.total_entities());
+    }
+
+    #[test]
+    fn test_arbitrary_world_survives_json(world in any::<World>()) {
+        let json = serde_json::to_string(&world).unwrap();
+        let restored: World = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(restored.seed, world.seed);
+        prop_assert_eq!(restored.total_entities(), world.total_entities());
+        prop_assert_eq!(restored.npcs.len(), world.npcs.len());
+        prop_assert_eq!(restored.markets.len(), world.markets.len());
+    }
+
+    #[test]
+    fn test_arbitrary_market_and_event_survive_json(
+        market in any::<Market>(),
+        event in any::<WorldEvent>(),
+    ) {
+        let json = serde_json::to_string(&market).unwrap();
+        let restored: Market = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(restored.supply, market.supply);
+        let json = serde_json::to_string(&event).unwrap();
+        let restored: WorldEvent = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(restored.event_type, event.event_type);
+        prop_assert_eq!(restored.source, event.source);
+    }
+}
+
+#[test]
+fn test_small_world_simulates() {
+    let mut world = small_world();
+    for _ in 0..10 {
+        world.advance_tick();
+    }
+    assert_eq!(world.current_tick, 10);
+    assert_eq!(world.total_entities(), 4);
+    assert_eq!(world.total_npcs(), 2);
+}
@@ -0,0 +1,33 @@
+//! Conversions between Python objects and JSON values
+//!
+//! Values cross the boundary through Python's own `json` module, so any JSON-compatible
+//! object (dicts, lists, strings, numbers, bools, `None`) is accepted and returned.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Convert a JSON-compatible Python object into a JSON value
+pub(crate) fn to_json_value(obj: &Bound<'_, PyAny>) -> PyResult<Value> {
+    let json: String = obj
+        .py()
+        .import("json")?
+        .call_method1("dumps", (obj,))?
+        .extract()?;
+    serde_json::from_str(&json).map_err(value_error)
+}
+
+/// Convert any serializable value into the equivalent Python object
+pub(crate) fn to_python<'py, T: Serialize>(
+    py: Python<'py>,
+    value: &T,
+) -> PyResult<Bound<'py, PyAny>> {
+    let json = serde_json::to_string(value).map_err(value_error)?;
+    py.import("json")?.call_method1("loads", (json,))
+}
+
+/// Raise an error message as a Python `ValueError`
+pub(crate) fn value_error(e: impl ToString) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
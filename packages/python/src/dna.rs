@@ -0,0 +1,209 @@
+//! GameDNA building, validation, diffing and migration
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde_json::{Map, Value};
+
+use entropic_dna_core::{
+    diff::DnaDiff,
+    schema::{GameDNA, SemanticVersion},
+    serialization::to_json_string,
+    validation::{checksum, ValidationEngine},
+    version::MigrationManager,
+};
+
+use crate::convert::{to_json_value, to_python, value_error};
+
+/// A Game DNA document
+#[pyclass(name = "GameDNA", module = "entropic")]
+#[derive(Clone, Debug)]
+pub struct PyGameDNA {
+    dna: GameDNA,
+}
+
+#[pymethods]
+impl PyGameDNA {
+    /// Parse a GameDNA JSON document.
+    ///
+    /// Documents written with older schema versions are accepted, so they can be upgraded
+    /// with `migrate`.
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        let dna = serde_json::from_str(json).map_err(value_error)?;
+        Ok(Self { dna })
+    }
+
+    /// Build a document from a dict shaped like the output of `to_dict`
+    #[staticmethod]
+    fn from_dict(data: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let dna = serde_json::from_value(to_json_value(data)?).map_err(value_error)?;
+        Ok(Self { dna })
+    }
+
+    /// Serialize the document as normalized JSON
+    fn to_json(&self) -> PyResult<String> {
+        to_json_string(&self.dna).map_err(value_error)
+    }
+
+    /// Convert the document into a dict
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        to_python(py, &self.dna)
+    }
+
+    /// Game ID
+    #[getter]
+    fn id(&self) -> &str {
+        &self.dna.id
+    }
+
+    /// Game name
+    #[getter]
+    fn name(&self) -> &str {
+        &self.dna.name
+    }
+
+    /// Semantic version as `"MAJOR.MINOR.PATCH"`
+    #[getter]
+    fn version(&self) -> String {
+        self.dna.version.to_string()
+    }
+
+    /// Deterministic checksum of the document
+    fn checksum(&self) -> String {
+        checksum::generate_checksum(&self.dna)
+    }
+
+    /// Run the validation engine and return its report as a dict with `is_valid`,
+    /// `errors` and `warnings`
+    fn validate<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        to_python(py, &ValidationEngine::new().validate(&self.dna))
+    }
+
+    /// Whether the validation engine reports no errors
+    fn is_valid(&self) -> bool {
+        ValidationEngine::new().validate(&self.dna).is_valid
+    }
+
+    /// List the changes from this document to `other`.
+    ///
+    /// Each change is a dict with `path`, `kind` (`"Added"`, `"Removed"` or `"Modified"`),
+    /// `before` and `after`, sorted by path.
+    fn diff<'py>(&self, py: Python<'py>, other: &PyGameDNA) -> PyResult<Bound<'py, PyAny>> {
+        to_python(py, &DnaDiff::between(&self.dna, &other.dna).changes)
+    }
+
+    /// Return a copy migrated to `target_version` through the built-in migrations.
+    fn migrate(&self, target_version: &str) -> PyResult<PyGameDNA> {
+        let dna = MigrationManager::with_builtin_migrations()
+            .migrate(self.dna.clone(), target_version)
+            .map_err(value_error)?;
+        Ok(Self { dna })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "GameDNA(id={:?}, name={:?}, version=\"{}\")",
+            self.dna.id, self.dna.name, self.dna.version
+        )
+    }
+}
+
+impl PyGameDNA {
+    /// The wrapped document
+    pub fn dna(&self) -> &GameDNA {
+        &self.dna
+    }
+}
+
+impl From<GameDNA> for PyGameDNA {
+    fn from(dna: GameDNA) -> Self {
+        Self { dna }
+    }
+}
+
+/// Build a GameDNA document named `name`.
+///
+/// Every other field starts from the builder defaults and can be overridden by keyword,
+/// using the field names of `GameDNA.to_dict()`. Enum fields take their variant names
+/// (`genre="RPG"`, `target_platforms=["PC", "Console"]`); custom variants are given as
+/// a dict such as `tone={"Custom": "Noir"}`. `version` also accepts
+/// `"MAJOR.MINOR.PATCH"`.
+///
+/// Raises `ValueError` for unknown fields, values of the wrong shape, or a document that
+/// fails schema checks.
+#[pyfunction]
+#[pyo3(signature = (name, **fields))]
+pub fn build_game_dna(name: &str, fields: Option<&Bound<'_, PyDict>>) -> PyResult<PyGameDNA> {
+    let mut overrides = Map::new();
+    if let Some(fields) = fields {
+        for (key, value) in fields.iter() {
+            overrides.insert(key.extract()?, to_json_value(&value)?);
+        }
+    }
+    build(name, overrides).map(PyGameDNA::from).map_err(value_error)
+}
+
+fn build(name: &str, overrides: Map<String, Value>) -> Result<GameDNA, String> {
+    let base = GameDNA::builder().name(name).build().map_err(|e| e.to_string())?;
+    let Value::Object(mut doc) = serde_json::to_value(&base).map_err(|e| e.to_string())? else {
+        return Err("GameDNA did not serialize to an object".to_string());
+    };
+
+    for (key, mut value) in overrides {
+        if !doc.contains_key(&key) {
+            return Err(format!("Unknown GameDNA field: {key}"));
+        }
+        if let ("version", Value::String(version)) = (key.as_str(), &value) {
+            let version = version.parse::<SemanticVersion>().map_err(|e| e.to_string())?;
+            value = serde_json::to_value(version).map_err(|e| e.to_string())?;
+        }
+        doc.insert(key, value);
+    }
+
+    let dna: GameDNA = serde_json::from_value(Value::Object(doc)).map_err(|e| e.to_string())?;
+    dna.validate().map_err(|e| e.to_string())?;
+    Ok(dna)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entropic_dna_core::schema::{Genre, TargetPlatform, Tone};
+    use serde_json::json;
+
+    fn overrides(value: Value) -> Map<String, Value> {
+        value.as_object().cloned().unwrap()
+    }
+
+    #[test]
+    fn test_build_with_overrides() {
+        let dna = build(
+            "Notebook Game",
+            overrides(json!({
+                "genre": "RPG",
+                "tone": { "Custom": "Noir" },
+                "target_platforms": ["PC", "Console"],
+                "version": "1.2.0",
+                "target_fps": 30,
+            })),
+        )
+        .unwrap();
+
+        assert_eq!(dna.name, "Notebook Game");
+        assert_eq!(dna.genre, Genre::RPG);
+        assert_eq!(dna.tone, Tone::CustomTone("Noir".to_string()));
+        assert_eq!(dna.target_platforms, vec![TargetPlatform::PC, TargetPlatform::Console]);
+        assert_eq!(dna.version, SemanticVersion::new(1, 2, 0));
+        assert_eq!(dna.target_fps, 30);
+    }
+
+    #[test]
+    fn test_build_rejects_bad_fields() {
+        let err = build("Game", overrides(json!({ "colour": "red" }))).unwrap_err();
+        assert!(err.contains("colour"));
+
+        assert!(build("Game", overrides(json!({ "genre": 7 }))).is_err());
+        assert!(build("Game", overrides(json!({ "target_platforms": [] }))).is_err());
+        assert!(build("", Map::new()).is_err());
+    }
+}
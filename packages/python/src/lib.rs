@@ -0,0 +1,36 @@
+//! Python bindings for ENTROPIC
+//!
+//! Exposes Game DNA building, validation, diffing and migration plus world creation and
+//! tick stepping as the `entropic` Python module, so configurations can be generated and
+//! batch-analyzed from notebooks with the canonical Rust implementation. Structured
+//! results (validation reports, diffs) are returned as plain dicts and lists, ready for
+//! `json` or `pandas`.
+//!
+//! ```python
+//! import entropic
+//!
+//! dna = entropic.build_game_dna("Notebook Game", genre="RPG", target_platforms=["PC"])
+//! report = dna.validate()
+//! world = entropic.World.for_game(dna, 8, 8)
+//! world.tick(100)
+//! ```
+
+use pyo3::prelude::*;
+
+mod convert;
+mod dna;
+mod world;
+
+pub use dna::{build_game_dna, PyGameDNA};
+pub use world::PyWorld;
+
+/// The `entropic` Python module
+#[pymodule]
+fn entropic(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    m.add("SCHEMA_VERSION", entropic_dna_core::CURRENT_VERSION)?;
+    m.add_class::<PyGameDNA>()?;
+    m.add_class::<PyWorld>()?;
+    m.add_function(wrap_pyfunction!(build_game_dna, m)?)?;
+    Ok(())
+}
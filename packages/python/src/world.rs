@@ -0,0 +1,158 @@
+//! World creation and tick stepping
+
+use pyo3::prelude::*;
+
+use entropic_world_core::{
+    serialization::{deserialize_from_json, serialize_to_json_compact},
+    World,
+};
+
+use crate::convert::value_error;
+use crate::dna::PyGameDNA;
+
+/// A simulated world
+#[pyclass(name = "World", module = "entropic")]
+#[derive(Debug)]
+pub struct PyWorld {
+    world: World,
+}
+
+#[pymethods]
+impl PyWorld {
+    /// Create a world of `width_chunks * height_chunks` chunks with its chunks initialized
+    #[new]
+    fn new(
+        name: &str,
+        game_dna_id: &str,
+        width_chunks: u32,
+        height_chunks: u32,
+    ) -> PyResult<Self> {
+        create(name, game_dna_id, width_chunks, height_chunks)
+            .map(|world| Self { world })
+            .map_err(value_error)
+    }
+
+    /// Create a world for a GameDNA document, named after the game
+    #[staticmethod]
+    fn for_game(dna: &PyGameDNA, width_chunks: u32, height_chunks: u32) -> PyResult<Self> {
+        let dna = dna.dna();
+        Self::new(&dna.name, &dna.id, width_chunks, height_chunks)
+    }
+
+    /// Restore a world from JSON written by `to_json`
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        let world = deserialize_from_json(json).map_err(value_error)?;
+        Ok(Self { world })
+    }
+
+    /// Serialize the full world state as compact JSON
+    fn to_json(&self) -> PyResult<String> {
+        serialize_to_json_compact(&self.world).map_err(value_error)
+    }
+
+    /// Advance the simulation by `ticks` ticks, releasing the GIL while it runs
+    #[pyo3(signature = (ticks = 1))]
+    fn tick(&mut self, py: Python<'_>, ticks: u32) {
+        let world = &mut self.world;
+        py.allow_threads(|| {
+            for _ in 0..ticks {
+                world.advance_tick();
+            }
+        });
+    }
+
+    /// World identifier
+    #[getter]
+    fn id(&self) -> &str {
+        &self.world.id
+    }
+
+    /// World name
+    #[getter]
+    fn name(&self) -> &str {
+        &self.world.name
+    }
+
+    /// ID of the GameDNA the world was created for
+    #[getter]
+    fn game_dna_id(&self) -> &str {
+        &self.world.game_dna_id
+    }
+
+    /// Ticks simulated so far
+    #[getter]
+    fn current_tick(&self) -> u64 {
+        self.world.current_tick
+    }
+
+    /// Number of chunks in the world
+    #[getter]
+    fn total_chunks(&self) -> usize {
+        self.world.total_chunks()
+    }
+
+    /// Number of entities in the world
+    #[getter]
+    fn total_entities(&self) -> usize {
+        self.world.total_entities()
+    }
+
+    /// Number of NPCs in the world
+    #[getter]
+    fn total_npcs(&self) -> usize {
+        self.world.total_npcs()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "World(name={:?}, size={}x{}, tick={})",
+            self.world.name,
+            self.world.width_chunks,
+            self.world.height_chunks,
+            self.world.current_tick
+        )
+    }
+}
+
+impl PyWorld {
+    /// The simulated world
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+}
+
+fn create(
+    name: &str,
+    game_dna_id: &str,
+    width_chunks: u32,
+    height_chunks: u32,
+) -> Result<World, String> {
+    if width_chunks == 0 || height_chunks == 0 {
+        return Err("A world needs at least one chunk on each axis".to_string());
+    }
+    let mut world =
+        World::new(name.to_string(), game_dna_id.to_string(), width_chunks, height_chunks);
+    world.initialize_chunks();
+    Ok(world)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_and_round_trip() {
+        let mut world = create("Notebook", "dna_1", 3, 2).unwrap();
+        assert_eq!(world.total_chunks(), 6);
+        for _ in 0..4 {
+            world.advance_tick();
+        }
+
+        let restored = deserialize_from_json(&serialize_to_json_compact(&world).unwrap()).unwrap();
+        assert_eq!(restored.current_tick, 4);
+        assert_eq!(restored.game_dna_id, "dna_1");
+
+        assert!(create("Empty", "dna_1", 0, 2).is_err());
+    }
+}
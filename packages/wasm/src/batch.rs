@@ -0,0 +1,198 @@
+//! Batch validation of many GameDNA documents
+//!
+//! Validating a whole project library in one call would block the editor's UI thread, so
+//! [`BatchValidator`] works through the documents in slices: the editor calls `step` from
+//! its own scheduler (`requestIdleCallback`, `setTimeout`, ...) and gets a progress
+//! callback after every document. [`validate_game_dna_batch`] runs a whole batch at once,
+//! for workers and small libraries.
+
+use serde::{Deserialize, Serialize};
+use tsify::Tsify;
+use wasm_bindgen::prelude::*;
+
+use entropic_dna_core::{
+    serialization::from_json_str,
+    validation::{ValidationEngine, ValidationResult},
+};
+
+/// Validation outcome of one document in a batch
+#[derive(Clone, Debug, Serialize, Deserialize, Tsify)]
+pub struct BatchItem {
+    /// Position of the document in the batch
+    pub index: u32,
+    /// Validation result, when the document could be parsed
+    pub result: Option<ValidationResult>,
+    /// Parse error, when it could not
+    pub error: Option<String>,
+}
+
+impl BatchItem {
+    /// Whether the document parsed and passed validation
+    pub fn is_valid(&self) -> bool {
+        self.result.as_ref().is_some_and(|r| r.is_valid)
+    }
+}
+
+/// Results of a batch, in document order
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi)]
+pub struct BatchReport {
+    /// One item per validated document
+    pub items: Vec<BatchItem>,
+    /// Documents that parsed and passed validation
+    pub valid: u32,
+    /// Documents that failed to parse or validate
+    pub invalid: u32,
+}
+
+impl BatchReport {
+    fn push(&mut self, item: BatchItem) {
+        if item.is_valid() {
+            self.valid += 1;
+        } else {
+            self.invalid += 1;
+        }
+        self.items.push(item);
+    }
+}
+
+/// Incremental validator over a list of GameDNA JSON documents
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct BatchValidator {
+    documents: Vec<String>,
+    report: BatchReport,
+    engine: ValidationEngine,
+}
+
+#[wasm_bindgen]
+impl BatchValidator {
+    /// Create a validator over `documents`; nothing is validated until `step` is called
+    #[wasm_bindgen(constructor)]
+    pub fn new(documents: Vec<String>) -> BatchValidator {
+        BatchValidator {
+            documents,
+            report: BatchReport::default(),
+            engine: ValidationEngine::new(),
+        }
+    }
+
+    /// Number of documents in the batch
+    #[wasm_bindgen(getter)]
+    pub fn total(&self) -> u32 {
+        self.documents.len() as u32
+    }
+
+    /// Number of documents validated so far
+    #[wasm_bindgen(getter)]
+    pub fn completed(&self) -> u32 {
+        self.report.items.len() as u32
+    }
+
+    /// Whether every document has been validated
+    #[wasm_bindgen(getter)]
+    pub fn done(&self) -> bool {
+        self.completed() == self.total()
+    }
+
+    /// Validate up to `count` more documents and return how many were validated.
+    ///
+    /// `on_progress(completed, total)` is called after each document.
+    ///
+    /// # Errors
+    ///
+    /// Returns the exception thrown by `on_progress`; the document it was reporting on
+    /// stays validated.
+    pub fn step(
+        &mut self,
+        count: u32,
+        on_progress: Option<js_sys::Function>,
+    ) -> Result<u32, JsValue> {
+        let mut validated = 0;
+        while validated < count && !self.done() {
+            self.validate_next();
+            validated += 1;
+
+            if let Some(callback) = &on_progress {
+                callback.call2(
+                    &JsValue::NULL,
+                    &JsValue::from(self.completed()),
+                    &JsValue::from(self.total()),
+                )?;
+            }
+        }
+        Ok(validated)
+    }
+
+    /// Results of the documents validated so far
+    pub fn report(&self) -> BatchReport {
+        self.report.clone()
+    }
+}
+
+impl BatchValidator {
+    fn validate_next(&mut self) {
+        let index = self.report.items.len();
+        let item = match from_json_str(&self.documents[index]) {
+            Ok(dna) => BatchItem {
+                index: index as u32,
+                result: Some(self.engine.validate(&dna)),
+                error: None,
+            },
+            Err(e) => BatchItem {
+                index: index as u32,
+                result: None,
+                error: Some(e.to_string()),
+            },
+        };
+        self.report.push(item);
+    }
+}
+
+/// Validate every document in one call, reporting `on_progress(completed, total)` after
+/// each.
+///
+/// # Errors
+///
+/// Returns the exception thrown by `on_progress`.
+#[wasm_bindgen]
+pub fn validate_game_dna_batch(
+    documents: Vec<String>,
+    on_progress: Option<js_sys::Function>,
+) -> Result<BatchReport, JsValue> {
+    let mut validator = BatchValidator::new(documents);
+    validator.step(validator.total(), on_progress)?;
+    Ok(validator.report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entropic_dna_core::{
+        schema::{Genre, TargetPlatform},
+        serialization::to_json_string,
+        GameDNA,
+    };
+
+    #[test]
+    fn test_batch_validates_in_steps() {
+        let dna = GameDNA::minimal("Batch".to_string(), Genre::RPG, vec![TargetPlatform::PC]);
+        let json = to_json_string(&dna).unwrap();
+        let mut validator =
+            BatchValidator::new(vec![json.clone(), "not json".to_string(), json]);
+
+        assert_eq!(validator.step(2, None).unwrap(), 2);
+        assert_eq!((validator.completed(), validator.total()), (2, 3));
+        assert!(!validator.done());
+
+        assert_eq!(validator.step(5, None).unwrap(), 1);
+        assert!(validator.done());
+
+        let report = validator.report();
+        assert_eq!(report.items.len(), 3);
+        assert!(report.items[1].error.is_some());
+        assert!(!report.items[1].is_valid());
+        assert_eq!(report.items[2].index, 2);
+        assert_eq!(report.valid + report.invalid, 3);
+    }
+}
@@ -0,0 +1,133 @@
+//! Binary GameDNA and world snapshot encoding
+//!
+//! JSON stringification dominates the editor's round trips on large documents, so these
+//! exports move documents between JS objects and compact MessagePack `Uint8Array`s
+//! directly. Each payload starts with a four-byte tag naming its kind and format version,
+//! so a world snapshot can't be decoded as a GameDNA by mistake.
+
+use serde::{de::DeserializeOwned, Serialize};
+use wasm_bindgen::prelude::*;
+
+use entropic_dna_core::{
+    schema::GameDNA,
+    serialization::{from_json_str, to_json_string},
+};
+use entropic_spatial_engine::World;
+
+/// Tag leading every binary GameDNA payload
+pub const DNA_BINARY_TAG: &[u8; 4] = b"EDN1";
+
+/// Tag leading every binary world snapshot payload
+pub const WORLD_BINARY_TAG: &[u8; 4] = b"EWS1";
+
+/// Encode a GameDNA JS object as a binary `Uint8Array`.
+///
+/// # Errors
+///
+/// Returns a `JsValue` holding an error message when encoding fails.
+#[wasm_bindgen]
+pub fn encode_game_dna(dna: GameDNA) -> Result<Vec<u8>, JsValue> {
+    encode(DNA_BINARY_TAG, &dna).map_err(|e| JsValue::from_str(&e))
+}
+
+/// Decode a binary GameDNA payload into a JS object.
+///
+/// # Errors
+///
+/// Returns a `JsValue` holding an error message when the bytes are not a binary GameDNA.
+#[wasm_bindgen]
+pub fn decode_game_dna(bytes: &[u8]) -> Result<GameDNA, JsValue> {
+    decode(DNA_BINARY_TAG, bytes).map_err(|e| JsValue::from_str(&e))
+}
+
+/// Convert a GameDNA JSON string to its binary form, for documents stored as JSON.
+///
+/// # Errors
+///
+/// Returns a `JsValue` holding an error message when the JSON cannot be parsed.
+#[wasm_bindgen]
+pub fn game_dna_json_to_binary(json: &str) -> Result<Vec<u8>, JsValue> {
+    let dna = from_json_str(json).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    encode(DNA_BINARY_TAG, &dna).map_err(|e| JsValue::from_str(&e))
+}
+
+/// Convert a binary GameDNA back to normalized JSON.
+///
+/// # Errors
+///
+/// Returns a `JsValue` holding an error message when the bytes are not a binary GameDNA.
+#[wasm_bindgen]
+pub fn game_dna_binary_to_json(bytes: &[u8]) -> Result<String, JsValue> {
+    let dna: GameDNA = decode(DNA_BINARY_TAG, bytes).map_err(|e| JsValue::from_str(&e))?;
+    to_json_string(&dna).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Encode a world snapshot JS object as a binary `Uint8Array`.
+///
+/// # Errors
+///
+/// Returns a `JsValue` holding an error message when the object is not a valid world.
+#[wasm_bindgen]
+pub fn encode_world_snapshot(world: JsValue) -> Result<Vec<u8>, JsValue> {
+    let world: World =
+        serde_wasm_bindgen::from_value(world).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    encode(WORLD_BINARY_TAG, &world).map_err(|e| JsValue::from_str(&e))
+}
+
+/// Decode a binary world snapshot into a JS object.
+///
+/// # Errors
+///
+/// Returns a `JsValue` holding an error message when the bytes are not a binary snapshot.
+#[wasm_bindgen]
+pub fn decode_world_snapshot(bytes: &[u8]) -> Result<JsValue, JsValue> {
+    let world: World = decode(WORLD_BINARY_TAG, bytes).map_err(|e| JsValue::from_str(&e))?;
+    serde_wasm_bindgen::to_value(&world).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Tag `value` and encode it as MessagePack with named fields, which keeps
+/// `#[serde(default)]` and skipped fields working across versions.
+pub(crate) fn encode<T: Serialize>(tag: &[u8; 4], value: &T) -> Result<Vec<u8>, String> {
+    let mut bytes = tag.to_vec();
+    rmp_serde::encode::write_named(&mut bytes, value).map_err(|e| e.to_string())?;
+    Ok(bytes)
+}
+
+pub(crate) fn decode<T: DeserializeOwned>(tag: &[u8; 4], bytes: &[u8]) -> Result<T, String> {
+    match bytes.strip_prefix(tag.as_slice()) {
+        Some(payload) => rmp_serde::from_slice(payload).map_err(|e| e.to_string()),
+        None => Err(format!(
+            "Expected a payload tagged {}",
+            String::from_utf8_lossy(tag)
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entropic_dna_core::schema::{Genre, TargetPlatform};
+
+    #[test]
+    fn test_game_dna_round_trip() {
+        let dna = GameDNA::minimal("Binary".to_string(), Genre::RPG, vec![TargetPlatform::PC]);
+        let bytes = encode(DNA_BINARY_TAG, &dna).unwrap();
+        assert!(bytes.len() < to_json_string(&dna).unwrap().len());
+
+        let decoded: GameDNA = decode(DNA_BINARY_TAG, &bytes).unwrap();
+        assert_eq!(to_json_string(&decoded).unwrap(), to_json_string(&dna).unwrap());
+    }
+
+    #[test]
+    fn test_world_snapshot_round_trip() {
+        let world = World::new("Snapshot".to_string(), "dna".to_string(), 4, 4);
+        let bytes = encode(WORLD_BINARY_TAG, &world).unwrap();
+
+        let decoded: World = decode(WORLD_BINARY_TAG, &bytes).unwrap();
+        assert_eq!(decoded.id, world.id);
+        assert_eq!(decoded.name, world.name);
+
+        // A snapshot is not a GameDNA
+        assert!(decode::<GameDNA>(DNA_BINARY_TAG, &bytes).is_err());
+    }
+}
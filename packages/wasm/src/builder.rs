@@ -0,0 +1,333 @@
+//! Incremental GameDNA construction for the DNA editor
+//!
+//! Wraps the core [`GameDNABuilder`] in a mutable JS class, so editor forms can set fields
+//! one at a time and ask for the finished document whenever they like. Enum fields take
+//! their variant names (`"FPS"`, `"Isometric"`, ...); names that match no variant become
+//! the enum's custom variant.
+
+use serde::de::DeserializeOwned;
+use wasm_bindgen::prelude::*;
+
+use entropic_dna_core::{
+    schema::{
+        CameraMode, DifficultyMode, GameDNABuilder, Genre, MonetizationModel, PhysicsProfile,
+        SemanticVersion, TargetPlatform, Tone, WorldScale,
+    },
+    serialization::to_json_string,
+    validation::{ValidationEngine, ValidationError, ValidationResult},
+};
+
+/// Mutable GameDNA builder exposed to JavaScript
+///
+/// Starts from the same defaults as [`GameDNABuilder::new`]. `build()` can be called any
+/// number of times; the builder keeps its state between calls.
+#[wasm_bindgen]
+#[derive(Clone, Debug, Default)]
+pub struct DnaBuilder {
+    inner: GameDNABuilder,
+}
+
+#[wasm_bindgen]
+impl DnaBuilder {
+    /// Create a builder with default values
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> DnaBuilder {
+        DnaBuilder::default()
+    }
+
+    /// Set the game name
+    pub fn set_name(&mut self, name: String) {
+        self.update(|b| b.name(name));
+    }
+
+    /// Set the game ID; a fresh UUID is generated when never set
+    pub fn set_id(&mut self, id: String) {
+        self.update(|b| b.id(id));
+    }
+
+    /// Set the semantic version
+    pub fn set_version(&mut self, major: u32, minor: u32, patch: u32) {
+        self.update(|b| b.version(SemanticVersion::new(major, minor, patch)));
+    }
+
+    /// Set the genre by variant name
+    pub fn set_genre(&mut self, genre: &str) {
+        let genre = parse_or_custom(genre, Genre::CustomGenre);
+        self.update(|b| b.genre(genre));
+    }
+
+    /// Set the camera mode by variant name
+    pub fn set_camera(&mut self, camera: &str) {
+        let camera = parse_or_custom(camera, CameraMode::CustomCamera);
+        self.update(|b| b.camera(camera));
+    }
+
+    /// Set the tone by variant name
+    pub fn set_tone(&mut self, tone: &str) {
+        let tone = parse_or_custom(tone, Tone::CustomTone);
+        self.update(|b| b.tone(tone));
+    }
+
+    /// Set the world scale by variant name
+    pub fn set_world_scale(&mut self, world_scale: &str) {
+        let world_scale = parse_or_custom(world_scale, WorldScale::CustomScale);
+        self.update(|b| b.world_scale(world_scale));
+    }
+
+    /// Replace the target platforms
+    ///
+    /// # Errors
+    ///
+    /// Returns a `JsValue` error message naming the first unknown platform; the current
+    /// platforms are left unchanged.
+    pub fn set_target_platforms(&mut self, platforms: Vec<String>) -> Result<(), JsValue> {
+        let platforms = platforms
+            .iter()
+            .map(|p| parse_platform(p))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| JsValue::from_str(&e))?;
+        self.update(|b| b.target_platforms(platforms));
+        Ok(())
+    }
+
+    /// Add one target platform
+    ///
+    /// # Errors
+    ///
+    /// Returns a `JsValue` error message when the platform name is unknown.
+    pub fn add_platform(&mut self, platform: &str) -> Result<(), JsValue> {
+        let platform = parse_platform(platform).map_err(|e| JsValue::from_str(&e))?;
+        self.update(|b| b.add_platform(platform));
+        Ok(())
+    }
+
+    /// Set the physics profile by variant name
+    pub fn set_physics_profile(&mut self, physics: &str) {
+        let physics = parse_or_custom(physics, PhysicsProfile::CustomPhysics);
+        self.update(|b| b.physics_profile(physics));
+    }
+
+    /// Set the maximum player count
+    pub fn set_max_players(&mut self, max_players: u32) {
+        self.update(|b| b.max_players(max_players));
+    }
+
+    /// Set whether the game is competitive
+    pub fn set_competitive(&mut self, competitive: bool) {
+        self.update(|b| b.is_competitive(competitive));
+    }
+
+    /// Set whether the game supports coop
+    pub fn set_supports_coop(&mut self, coop: bool) {
+        self.update(|b| b.supports_coop(coop));
+    }
+
+    /// Set the difficulty mode by variant name
+    pub fn set_difficulty(&mut self, difficulty: &str) {
+        let difficulty = parse_or_custom(difficulty, DifficultyMode::CustomDifficulty);
+        self.update(|b| b.difficulty(difficulty));
+    }
+
+    /// Set the monetization model by variant name
+    pub fn set_monetization(&mut self, monetization: &str) {
+        let monetization = parse_or_custom(monetization, MonetizationModel::Custom);
+        self.update(|b| b.monetization(monetization));
+    }
+
+    /// Set the target audience description
+    pub fn set_target_audience(&mut self, audience: String) {
+        self.update(|b| b.target_audience(audience));
+    }
+
+    /// Set the ESRB rating
+    pub fn set_esrb_rating(&mut self, rating: String) {
+        self.update(|b| b.esrb_rating(rating));
+    }
+
+    /// Set the target frames per second
+    pub fn set_target_fps(&mut self, fps: u32) {
+        self.update(|b| b.target_fps(fps));
+    }
+
+    /// Set the maximum draw distance in world units
+    pub fn set_max_draw_distance(&mut self, distance: f32) {
+        self.update(|b| b.max_draw_distance(distance));
+    }
+
+    /// Set the maximum entity count
+    pub fn set_max_entities(&mut self, entities: u32) {
+        self.update(|b| b.max_entities(entities));
+    }
+
+    /// Set the maximum NPC count
+    pub fn set_max_npc_count(&mut self, npc_count: u32) {
+        self.update(|b| b.max_npc_count(npc_count));
+    }
+
+    /// Set the time scale (1.0 = real-time)
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        self.update(|b| b.time_scale(time_scale));
+    }
+
+    /// Set whether weather simulation is enabled
+    pub fn set_weather_enabled(&mut self, enabled: bool) {
+        self.update(|b| b.weather_enabled(enabled));
+    }
+
+    /// Set whether seasons are enabled
+    pub fn set_seasons_enabled(&mut self, enabled: bool) {
+        self.update(|b| b.seasons_enabled(enabled));
+    }
+
+    /// Set whether the day/night cycle is enabled
+    pub fn set_day_night_cycle(&mut self, enabled: bool) {
+        self.update(|b| b.day_night_cycle(enabled));
+    }
+
+    /// Set whether the world is persistent
+    pub fn set_persistent_world(&mut self, persistent: bool) {
+        self.update(|b| b.persistent_world(persistent));
+    }
+
+    /// Set the NPC count
+    pub fn set_npc_count(&mut self, npc_count: u32) {
+        self.update(|b| b.npc_count(npc_count));
+    }
+
+    /// Set whether AI is enabled
+    pub fn set_ai_enabled(&mut self, enabled: bool) {
+        self.update(|b| b.ai_enabled(enabled));
+    }
+
+    /// Set whether AI difficulty scales
+    pub fn set_ai_difficulty_scaling(&mut self, scaling: bool) {
+        self.update(|b| b.ai_difficulty_scaling(scaling));
+    }
+
+    /// Set whether the game has a campaign
+    pub fn set_has_campaign(&mut self, has_campaign: bool) {
+        self.update(|b| b.has_campaign(has_campaign));
+    }
+
+    /// Set whether the game has side quests
+    pub fn set_has_side_quests(&mut self, has_quests: bool) {
+        self.update(|b| b.has_side_quests(has_quests));
+    }
+
+    /// Set whether dynamic quests are enabled
+    pub fn set_dynamic_quests(&mut self, dynamic: bool) {
+        self.update(|b| b.dynamic_quests(dynamic));
+    }
+
+    /// Add a descriptive tag
+    pub fn add_tag(&mut self, tag: String) {
+        self.update(|b| b.tag(tag));
+    }
+
+    /// Set a custom property, replacing any previous value for the key
+    pub fn set_custom_property(&mut self, key: String, value: String) {
+        self.update(|b| b.custom_property(key, value));
+    }
+
+    /// Build the GameDNA and return it as normalized JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ValidationResult`-shaped object when the document fails schema
+    /// checks or the validation engine reports errors, or an error message when the result
+    /// cannot be converted.
+    pub fn build(&self) -> Result<String, JsValue> {
+        self.build_json().map_err(|result| {
+            serde_wasm_bindgen::to_value(&result)
+                .unwrap_or_else(|e| JsValue::from_str(&e.to_string()))
+        })
+    }
+}
+
+impl DnaBuilder {
+    fn update(&mut self, f: impl FnOnce(GameDNABuilder) -> GameDNABuilder) {
+        self.inner = f(std::mem::take(&mut self.inner));
+    }
+
+    fn build_json(&self) -> Result<String, ValidationResult> {
+        let mut result = ValidationResult::new();
+        let dna = match self.inner.clone().build() {
+            Ok(dna) => dna,
+            Err(e) => {
+                result.add_error(ValidationError::new(
+                    "SCHEMA_ERROR".to_string(),
+                    String::new(),
+                    e.to_string(),
+                    String::new(),
+                ));
+                return Err(result);
+            }
+        };
+
+        result = ValidationEngine::new().validate(&dna);
+        if !result.is_valid {
+            return Err(result);
+        }
+
+        to_json_string(&dna).map_err(|e| {
+            let mut result = ValidationResult::new();
+            result.add_error(ValidationError::new(
+                "SERIALIZATION_ERROR".to_string(),
+                String::new(),
+                e.to_string(),
+                String::new(),
+            ));
+            result
+        })
+    }
+}
+
+/// Parse a unit variant by name, falling back to the enum's custom variant
+fn parse_or_custom<T: DeserializeOwned>(name: &str, custom: fn(String) -> T) -> T {
+    parse_variant(name).unwrap_or_else(|_| custom(name.to_string()))
+}
+
+fn parse_platform(name: &str) -> Result<TargetPlatform, String> {
+    parse_variant(name).map_err(|_| format!("Unknown target platform: {name}"))
+}
+
+fn parse_variant<T: DeserializeOwned>(name: &str) -> Result<T, serde_json::Error> {
+    serde_json::from_value(serde_json::Value::String(name.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entropic_dna_core::serialization::from_json_str;
+
+    #[test]
+    fn test_builder_produces_dna_json() {
+        let mut builder = DnaBuilder::new();
+        builder.set_name("Editor Game".to_string());
+        builder.set_genre("RPG");
+        builder.set_camera("Isometric");
+        builder.set_tone("Noir");
+        builder.add_platform("Console").unwrap();
+        builder.add_tag("fantasy".to_string());
+
+        let dna = from_json_str(&builder.build_json().unwrap()).unwrap();
+        assert_eq!(dna.name, "Editor Game");
+        assert_eq!(dna.genre, Genre::RPG);
+        assert_eq!(dna.camera, CameraMode::Isometric);
+        assert_eq!(dna.tone, Tone::CustomTone("Noir".to_string()));
+        assert_eq!(dna.target_platforms, vec![TargetPlatform::PC, TargetPlatform::Console]);
+        assert_eq!(dna.tags, vec!["fantasy".to_string()]);
+    }
+
+    #[test]
+    fn test_builder_reports_errors() {
+        let mut builder = DnaBuilder::new();
+        let result = builder.build_json().unwrap_err();
+        assert!(!result.is_valid);
+        assert_eq!(result.errors[0].code, "SCHEMA_ERROR");
+
+        builder.set_name("Fixed".to_string());
+        assert!(builder.build_json().is_ok());
+        assert!(parse_platform("Toaster").is_err());
+    }
+}
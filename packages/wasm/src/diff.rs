@@ -0,0 +1,52 @@
+//! GameDNA diffs for side-by-side comparison in the DNA editor
+
+use wasm_bindgen::prelude::*;
+
+use entropic_dna_core::{diff::DnaDiff, schema::GameDNA};
+
+/// Compare two GameDNA JSON documents and return their changes.
+///
+/// The result is a typed `DnaDiff` object whose `changes` list every added, removed or modified
+/// value from `a_json` to `b_json`, sorted by path (`"name"`, `"tags[1]"`, ...).
+///
+/// # Errors
+///
+/// Returns a `JsValue` holding an error message when either document cannot be parsed.
+#[wasm_bindgen]
+pub fn diff_game_dna(a_json: &str, b_json: &str) -> Result<DnaDiff, JsValue> {
+    diff_json(a_json, b_json).map_err(|e| JsValue::from_str(&e))
+}
+
+fn diff_json(a_json: &str, b_json: &str) -> Result<DnaDiff, String> {
+    // Parse the schema directly so documents from other schema versions can be compared
+    let a: GameDNA = serde_json::from_str(a_json).map_err(|e| format!("First document: {e}"))?;
+    let b: GameDNA = serde_json::from_str(b_json).map_err(|e| format!("Second document: {e}"))?;
+    Ok(DnaDiff::between(&a, &b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entropic_dna_core::{
+        diff::ChangeKind,
+        schema::{Genre, TargetPlatform},
+        serialization::to_json_string,
+    };
+
+    #[test]
+    fn test_diff_json() {
+        let a = GameDNA::minimal("Game".to_string(), Genre::FPS, vec![TargetPlatform::PC]);
+        let mut b = a.clone();
+        b.genre = Genre::RPG;
+        b.tags.push("co-op".to_string());
+
+        let a_json = to_json_string(&a).unwrap();
+        let diff = diff_json(&a_json, &to_json_string(&b).unwrap()).unwrap();
+        assert_eq!(diff.changes.len(), 2);
+        assert_eq!(diff.changes[0].path, "genre");
+        assert_eq!(diff.changes[1].kind, ChangeKind::Added);
+
+        assert!(diff_json(&a_json, &a_json).unwrap().is_empty());
+        assert!(diff_json(&a_json, "{}").unwrap_err().starts_with("Second document"));
+    }
+}
@@ -3,13 +3,32 @@ use wasm_bindgen::prelude::*;
 use entropic_dna_core::{
     schema::GameDNA,
     serialization::{from_json_str, to_json_string},
-    validation::{checksum, ValidationEngine},
+    validation::{checksum, ValidationEngine, ValidationResult},
 };
 
+pub mod batch;
+pub mod binary;
+pub mod builder;
+pub mod diff;
+pub mod migration;
+pub mod terrain;
+pub mod world;
+
+pub use batch::{validate_game_dna_batch, BatchItem, BatchReport, BatchValidator};
+pub use binary::{
+    decode_game_dna, decode_world_snapshot, encode_game_dna, encode_world_snapshot,
+    game_dna_binary_to_json, game_dna_json_to_binary,
+};
+pub use builder::DnaBuilder;
+pub use diff::diff_game_dna;
+pub use migration::{migrate_game_dna, MigrationReport};
+pub use terrain::{generate_terrain_preview, PreviewBiome, TerrainPreview};
+pub use world::WasmWorld;
+
 /// Validate a GameDNA JSON string and produce a JavaScript-serializable validation result.
 ///
 /// Parses the given JSON into a GameDNA configuration, runs the validation engine on it,
-/// and converts the validation outcome into a `JsValue` suitable for consumption by JavaScript.
+/// and returns the outcome as a typed `ValidationResult` object.
 ///
 /// # Examples
 ///
@@ -24,16 +43,34 @@ use entropic_dna_core::{
 ///
 /// # Returns
 ///
-/// - `Ok`: a `JsValue` containing the validation outcome (success or structured validation errors).
-/// - `Err`: a `JsValue` holding an error message when parsing or conversion fails.
+/// - `Ok`: the validation outcome (success or structured validation errors).
+/// - `Err`: a `JsValue` holding an error message when parsing fails.
 #[wasm_bindgen]
-pub fn validate_game_dna(json: &str) -> Result<JsValue, JsValue> {
+pub fn validate_game_dna(json: &str) -> Result<ValidationResult, JsValue> {
     let config: GameDNA = from_json_str(json).map_err(|e| JsValue::from_str(&e.to_string()))?;
 
     let engine = ValidationEngine::new();
-    let result = engine.validate(&config);
+    Ok(engine.validate(&config))
+}
+
+/// Validate a single field of a GameDNA JSON string.
+///
+/// Runs only the rules for `field` (e.g. `"name"`, `"target_fps"`), which is cheap enough
+/// to call on every keystroke. Unknown field names produce an `UNKNOWN_FIELD` warning
+/// rather than an error.
+///
+/// # Errors
+///
+/// Returns a `JsValue` holding an error message when parsing fails.
+#[wasm_bindgen]
+pub fn validate_game_dna_field(json: &str, field: &str) -> Result<ValidationResult, JsValue> {
+    validate_field_json(json, field).map_err(|e| JsValue::from_str(&e))
+}
 
-    serde_wasm_bindgen::to_value(&result).map_err(|e| JsValue::from_str(&e.to_string()))
+fn validate_field_json(json: &str, field: &str) -> Result<ValidationResult, String> {
+    // Parse without validating, so problems in other fields don't hide this one's result
+    let config: GameDNA = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    Ok(ValidationEngine::new().validate_field(&config, field))
 }
 
 /// Serialize a GameDNA JSON string into a normalized JSON representation.
@@ -130,4 +167,23 @@ mod tests {
         let result = validate_game_dna(json);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_validate_single_field() {
+        use entropic_dna_core::schema::{Genre, TargetPlatform};
+
+        let mut dna = GameDNA::minimal("Field".to_string(), Genre::FPS, vec![TargetPlatform::PC]);
+        dna.target_fps = 0;
+        let json = to_json_string(&dna).unwrap();
+
+        let fps = validate_field_json(&json, "target_fps").unwrap();
+        assert!(!fps.is_valid);
+        assert!(fps.errors.iter().all(|e| e.field == "target_fps"));
+
+        let unknown = validate_field_json(&json, "no_such_field").unwrap();
+        assert!(unknown.is_valid);
+        assert_eq!(unknown.warnings[0].code, "UNKNOWN_FIELD");
+
+        assert!(validate_field_json("{", "name").is_err());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,131 @@
+//! GameDNA schema migration for the DNA editor
+//!
+//! Lets the editor upgrade documents written by older tool versions without a server round
+//! trip. Every migration reports the steps it ran and a field-level diff against the
+//! original, so the editor can show a dry run and let the user decide whether to keep it.
+
+use serde::Serialize;
+use tsify::Tsify;
+use wasm_bindgen::prelude::*;
+
+use entropic_dna_core::{
+    diff::{DnaDiff, FieldChange},
+    schema::GameDNA,
+    serialization::to_json_string,
+    version::MigrationManager,
+};
+
+/// Outcome of migrating one document
+#[derive(Clone, Debug, Serialize, Tsify)]
+#[tsify(into_wasm_abi)]
+pub struct MigrationReport {
+    /// Version the document was written with
+    pub from_version: String,
+    /// Version the document was migrated to
+    pub to_version: String,
+    /// Migration steps that ran, as `"FROM -> TO"`
+    pub steps: Vec<String>,
+    /// Every field the migration changed
+    pub changes: Vec<FieldChange>,
+    /// Migrated document as normalized JSON
+    pub dna: String,
+}
+
+/// Migrate a GameDNA JSON document to `target_version`.
+///
+/// Nothing is written anywhere: the result holds the migrated JSON next to the diff, so
+/// the same call serves as a dry run.
+///
+/// # Errors
+///
+/// Returns a `JsValue` holding an error message when the JSON cannot be parsed, the target
+/// version is malformed, or no chain of migrations leads to it.
+#[wasm_bindgen]
+pub fn migrate_game_dna(json: &str, target_version: &str) -> Result<MigrationReport, JsValue> {
+    migrate(&MigrationManager::with_builtin_migrations(), json, target_version)
+        .map_err(|e| JsValue::from_str(&e))
+}
+
+fn migrate(
+    manager: &MigrationManager,
+    json: &str,
+    target_version: &str,
+) -> Result<MigrationReport, String> {
+    // Older documents fail the current-version check in `from_json_str`, so parse the
+    // schema directly
+    let original: GameDNA = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let from_version = original.version.to_string();
+
+    let steps = manager
+        .find_migration_path(&from_version, target_version)
+        .map(|path| {
+            path.iter()
+                .map(|m| format!("{} -> {}", m.from_version(), m.to_version()))
+                .collect()
+        })
+        .unwrap_or_default();
+    let migrated = manager
+        .migrate(original.clone(), target_version)
+        .map_err(|e| e.to_string())?;
+
+    Ok(MigrationReport {
+        from_version,
+        to_version: migrated.version.to_string(),
+        steps,
+        changes: DnaDiff::between(&original, &migrated).changes,
+        dna: to_json_string(&migrated).map_err(|e| e.to_string())?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entropic_dna_core::{
+        errors::VersionError,
+        schema::{Genre, TargetPlatform},
+        version::Migration,
+    };
+
+    struct RenameAudience;
+
+    impl Migration for RenameAudience {
+        fn from_version(&self) -> &str {
+            "0.1.0"
+        }
+
+        fn to_version(&self) -> &str {
+            "0.2.0"
+        }
+
+        fn migrate(&self, mut dna: GameDNA) -> Result<GameDNA, VersionError> {
+            dna.target_audience = "Everyone".to_string();
+            Ok(dna)
+        }
+    }
+
+    #[test]
+    fn test_migrate_reports_steps_and_diff() {
+        let mut manager = MigrationManager::new();
+        manager.add_migration(RenameAudience);
+        let dna = GameDNA::minimal("Old".to_string(), Genre::FPS, vec![TargetPlatform::PC]);
+        let json = to_json_string(&dna).unwrap();
+
+        let report = migrate(&manager, &json, "0.2.0").unwrap();
+        assert_eq!((report.from_version.as_str(), report.to_version.as_str()), ("0.1.0", "0.2.0"));
+        assert_eq!(report.steps, vec!["0.1.0 -> 0.2.0"]);
+        let paths: Vec<_> = report.changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, vec!["target_audience", "version.minor"]);
+        assert!(report.dna.contains("\"Everyone\""));
+    }
+
+    #[test]
+    fn test_migrate_without_path_fails() {
+        let dna = GameDNA::minimal("Old".to_string(), Genre::FPS, vec![TargetPlatform::PC]);
+        let json = to_json_string(&dna).unwrap();
+        let manager = MigrationManager::with_builtin_migrations();
+
+        assert!(migrate(&manager, &json, "9.0.0").is_err());
+        assert!(migrate(&manager, &json, "not-a-version").is_err());
+        assert!(migrate(&manager, &json, "0.1.0").unwrap().changes.is_empty());
+    }
+}
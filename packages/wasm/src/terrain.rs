@@ -0,0 +1,197 @@
+//! Terrain previews for the DNA editor
+//!
+//! Runs the spatial engine's terrain generator in the browser, so the editor can draw a map
+//! of a seed without a server round trip. Previews cover a rectangle of chunks, downsampled
+//! to a chosen number of samples per chunk side and stitched into one row-major map.
+
+use wasm_bindgen::prelude::*;
+
+use entropic_spatial_engine::{Biome, ChunkCoord, TerrainGenerator, HEIGHTMAP_RESOLUTION};
+
+/// Most chunks a single preview may generate
+pub const MAX_PREVIEW_CHUNKS: u32 = 256;
+
+/// Biome codes used in preview biome maps
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PreviewBiome {
+    Forest = 0,
+    Desert = 1,
+    Mountains = 2,
+    Plains = 3,
+    Swamp = 4,
+    Tundra = 5,
+    Ocean = 6,
+    Grassland = 7,
+    Custom = 255,
+}
+
+impl From<Biome> for PreviewBiome {
+    fn from(biome: Biome) -> Self {
+        match biome {
+            Biome::Forest => PreviewBiome::Forest,
+            Biome::Desert => PreviewBiome::Desert,
+            Biome::Mountains => PreviewBiome::Mountains,
+            Biome::Plains => PreviewBiome::Plains,
+            Biome::Swamp => PreviewBiome::Swamp,
+            Biome::Tundra => PreviewBiome::Tundra,
+            Biome::Ocean => PreviewBiome::Ocean,
+            Biome::Grassland => PreviewBiome::Grassland,
+            Biome::Custom(_) => PreviewBiome::Custom,
+        }
+    }
+}
+
+/// Heightmap and biome map of a rectangle of chunks
+///
+/// Both maps are `width * height` samples indexed `[y * width + x]`, with `x` and `y`
+/// growing along the world axes from the first chunk's origin.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct TerrainPreview {
+    width: u32,
+    height: u32,
+    resolution: u32,
+    heights: Vec<f32>,
+    biomes: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl TerrainPreview {
+    /// Samples per row
+    #[wasm_bindgen(getter)]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Samples per column
+    #[wasm_bindgen(getter)]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Samples per chunk side
+    #[wasm_bindgen(getter)]
+    pub fn resolution(&self) -> u32 {
+        self.resolution
+    }
+
+    /// Terrain heights (0-255) as a `Float32Array`
+    pub fn heights(&self) -> Vec<f32> {
+        self.heights.clone()
+    }
+
+    /// [`PreviewBiome`] codes as a `Uint8Array`, constant over each chunk
+    pub fn biomes(&self) -> Vec<u8> {
+        self.biomes.clone()
+    }
+}
+
+/// Generate a preview of `chunks_x * chunks_y` chunks starting at a chunk coordinate.
+///
+/// Every chunk is generated at full detail with the given seed, then downsampled to
+/// `resolution` samples per side (clamped to 1..=256).
+///
+/// # Errors
+///
+/// Returns a `JsValue` holding an error message when the rectangle is empty, larger than
+/// [`MAX_PREVIEW_CHUNKS`], or when terrain generation fails.
+///
+/// # Examples
+///
+/// ```
+/// use entropic_wasm_validator::generate_terrain_preview;
+///
+/// let preview = generate_terrain_preview(42, 0, 0, 2, 2, 64).unwrap();
+/// assert_eq!(preview.heights().len(), 128 * 128);
+/// ```
+#[wasm_bindgen]
+pub fn generate_terrain_preview(
+    seed: u32,
+    chunk_x: u32,
+    chunk_y: u32,
+    chunks_x: u32,
+    chunks_y: u32,
+    resolution: u32,
+) -> Result<TerrainPreview, JsValue> {
+    build_preview(seed, chunk_x, chunk_y, chunks_x, chunks_y, resolution)
+        .map_err(|e| JsValue::from_str(&e))
+}
+
+fn build_preview(
+    seed: u32,
+    chunk_x: u32,
+    chunk_y: u32,
+    chunks_x: u32,
+    chunks_y: u32,
+    resolution: u32,
+) -> Result<TerrainPreview, String> {
+    let chunks = chunks_x.saturating_mul(chunks_y);
+    if chunks == 0 || chunks > MAX_PREVIEW_CHUNKS {
+        return Err(format!(
+            "Preview must cover between 1 and {MAX_PREVIEW_CHUNKS} chunks, got {chunks_x}x{chunks_y}"
+        ));
+    }
+
+    let res = resolution.clamp(1, HEIGHTMAP_RESOLUTION as u32) as usize;
+    let width = chunks_x as usize * res;
+    let height = chunks_y as usize * res;
+    let mut heights = vec![0.0; width * height];
+    let mut biomes = vec![0; width * height];
+
+    let generator = TerrainGenerator::with_seed(seed);
+    for cy in 0..chunks_y {
+        for cx in 0..chunks_x {
+            let coord = ChunkCoord::new(chunk_x.saturating_add(cx), chunk_y.saturating_add(cy));
+            let chunk = generator.generate_chunk(coord).map_err(|e| e.to_string())?;
+            let biome = PreviewBiome::from(chunk.biome) as u8;
+
+            for j in 0..res {
+                for i in 0..res {
+                    // Chunk heightmaps are indexed [x * HEIGHTMAP_RESOLUTION + y]
+                    let sx = i * HEIGHTMAP_RESOLUTION / res;
+                    let sy = j * HEIGHTMAP_RESOLUTION / res;
+                    let index = (cy as usize * res + j) * width + cx as usize * res + i;
+                    heights[index] = chunk.elevation[sx * HEIGHTMAP_RESOLUTION + sy];
+                    biomes[index] = biome;
+                }
+            }
+        }
+    }
+
+    Ok(TerrainPreview {
+        width: width as u32,
+        height: height as u32,
+        resolution: res as u32,
+        heights,
+        biomes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview_matches_generated_chunks() {
+        let preview = build_preview(7, 3, 5, 2, 1, 64).unwrap();
+        assert_eq!((preview.width(), preview.height(), preview.resolution()), (128, 64, 64));
+        assert_eq!(preview.heights().len(), 128 * 64);
+
+        // Sample (x, y) = (70, 10) lies in the second chunk at local sample (6, 10)
+        let chunk = TerrainGenerator::with_seed(7).generate_chunk(ChunkCoord::new(4, 5)).unwrap();
+        let expected = chunk.elevation[(6 * 4) * HEIGHTMAP_RESOLUTION + 10 * 4];
+        assert_eq!(preview.heights()[10 * 128 + 70], expected);
+        assert_eq!(preview.biomes()[10 * 128 + 70], PreviewBiome::from(chunk.biome) as u8);
+
+        // Same seed, same preview
+        assert_eq!(build_preview(7, 3, 5, 2, 1, 64).unwrap().heights(), preview.heights());
+    }
+
+    #[test]
+    fn test_preview_limits() {
+        assert!(build_preview(7, 0, 0, 0, 4, 16).is_err());
+        assert!(build_preview(7, 0, 0, 17, 16, 16).is_err());
+        assert_eq!(build_preview(7, 0, 0, 1, 1, 1000).unwrap().resolution(), 256);
+    }
+}
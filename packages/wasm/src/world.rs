@@ -0,0 +1,134 @@
+//! World simulations in the browser
+//!
+//! [`WasmWorld`] keeps a [`World`] inside the wasm module, so a browser simulation can
+//! tick it without copying state to JS, then export a binary snapshot to IndexedDB and
+//! restore the session from it later. Snapshots use the same tagged MessagePack format as
+//! [`encode_world_snapshot`](crate::encode_world_snapshot).
+
+use wasm_bindgen::prelude::*;
+
+use entropic_spatial_engine::World;
+
+use crate::binary::{decode, encode, WORLD_BINARY_TAG};
+
+/// A simulated world owned by the wasm module
+#[wasm_bindgen(js_name = World)]
+#[derive(Debug)]
+pub struct WasmWorld {
+    world: World,
+}
+
+#[wasm_bindgen(js_class = World)]
+impl WasmWorld {
+    /// Create a world of `width_chunks * height_chunks` chunks with its chunks initialized
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        name: String,
+        game_dna_id: String,
+        width_chunks: u32,
+        height_chunks: u32,
+    ) -> WasmWorld {
+        let mut world = World::new(name, game_dna_id, width_chunks, height_chunks);
+        world.initialize_chunks();
+        WasmWorld { world }
+    }
+
+    /// Restore a world from a snapshot made by `export_snapshot`.
+    ///
+    /// Event bus subscriptions are not part of a snapshot and must be registered again.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `JsValue` holding an error message when the bytes are not a world snapshot.
+    pub fn import_snapshot(bytes: &[u8]) -> Result<WasmWorld, JsValue> {
+        let world = decode(WORLD_BINARY_TAG, bytes).map_err(|e| JsValue::from_str(&e))?;
+        Ok(WasmWorld { world })
+    }
+
+    /// Export the full world state as a binary `Uint8Array`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `JsValue` holding an error message when encoding fails.
+    pub fn export_snapshot(&self) -> Result<Vec<u8>, JsValue> {
+        encode(WORLD_BINARY_TAG, &self.world).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Advance the simulation by `ticks` ticks
+    pub fn tick(&mut self, ticks: u32) {
+        for _ in 0..ticks {
+            self.world.advance_tick();
+        }
+    }
+
+    /// World identifier
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> String {
+        self.world.id.clone()
+    }
+
+    /// World name
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.world.name.clone()
+    }
+
+    /// Ticks simulated so far
+    #[wasm_bindgen(getter)]
+    pub fn current_tick(&self) -> u64 {
+        self.world.current_tick
+    }
+
+    /// Number of chunks in the world
+    #[wasm_bindgen(getter)]
+    pub fn total_chunks(&self) -> usize {
+        self.world.total_chunks()
+    }
+
+    /// Number of entities in the world
+    #[wasm_bindgen(getter)]
+    pub fn total_entities(&self) -> usize {
+        self.world.total_entities()
+    }
+
+    /// Number of NPCs in the world
+    #[wasm_bindgen(getter)]
+    pub fn total_npcs(&self) -> usize {
+        self.world.total_npcs()
+    }
+}
+
+impl WasmWorld {
+    /// The simulated world
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// The simulated world, mutably
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_restores_session() {
+        let mut session = WasmWorld::new("Session".to_string(), "dna".to_string(), 2, 2);
+        session.tick(3);
+        let bytes = session.export_snapshot().unwrap();
+
+        let mut restored = WasmWorld::import_snapshot(&bytes).unwrap();
+        assert_eq!(restored.id(), session.id());
+        assert_eq!(restored.current_tick(), 3);
+        assert_eq!(restored.total_chunks(), 4);
+
+        // Both copies keep simulating in lockstep
+        session.tick(2);
+        restored.tick(2);
+        assert_eq!(restored.world().rng, session.world().rng);
+        assert_eq!(restored.current_tick(), 5);
+    }
+}